@@ -0,0 +1,318 @@
+//! The dependency graph between a project's workspace crates and their
+//! top-level modules.
+//!
+//! Crate dependencies come from each member's `[dependencies]`. Module
+//! dependencies come from paths in the source: `crate::config`,
+//! `crate::{checks, config}`, `super::config` in a module's root file, and
+//! `guardian_core::checks` for another workspace crate. A module is named
+//! by its directory or file under `src`, and a name shared by two crates is
+//! one node. Test modules are skipped.
+
+use crate::checks::text::{self, Text};
+use crate::checks::walk::{self, Scan};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Where a dependency is first seen.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Edge {
+    pub file: PathBuf,
+    pub line: usize,
+}
+
+/// Crates and top-level modules, and which depends on which.
+#[derive(Debug, Default)]
+pub struct Graph {
+    /// Crate and module names.
+    pub nodes: BTreeSet<String>,
+    /// Dependencies, from dependent to dependency.
+    pub edges: BTreeMap<(String, String), Edge>,
+}
+
+struct Crate {
+    name: String,
+    dir: PathBuf,
+    dependencies: Vec<String>,
+}
+
+impl Graph {
+    /// The graph of the project at `project_dir`, finding and reading its
+    /// files as `scan` says.
+    pub fn extract(project_dir: &Path, scan: &Scan) -> Self {
+        let crates = workspace_crates(project_dir);
+        let mut graph = Graph::default();
+        let mut modules = BTreeMap::new();
+        for krate in &crates {
+            graph.nodes.insert(krate.name.clone());
+            let found = top_level_modules(&krate.dir.join("src"), scan);
+            graph.nodes.extend(found.keys().cloned());
+            modules.insert(krate.name.as_str(), found);
+        }
+        for krate in &crates {
+            let manifest = krate.dir.join("Cargo.toml");
+            let text = fs::read_to_string(&manifest).unwrap_or_default();
+            for dependency in &krate.dependencies {
+                if !crates.iter().any(|c| &c.name == dependency) {
+                    continue;
+                }
+                let line = text
+                    .lines()
+                    .position(|l| l.trim_start().starts_with(dependency.as_str()))
+                    .map_or(1, |i| i + 1);
+                graph.record(&krate.name, dependency, &manifest, line);
+            }
+
+            let mut prefixes = vec!["crate::".to_string()];
+            prefixes.extend(
+                crates
+                    .iter()
+                    .filter(|c| c.name != krate.name)
+                    .map(|c| format!("{}::", c.name.replace('-', "_"))),
+            );
+            for (module, files) in &modules[krate.name.as_str()] {
+                for (file, is_root) in files {
+                    let Some(content) = text::read(file, scan.max_file_bytes)
+                        .ok()
+                        .and_then(Text::into_source)
+                    else {
+                        continue;
+                    };
+                    let content = content.split("#[cfg(test)]").next().unwrap_or_default();
+                    let root_prefix = is_root.then(|| "super::".to_string());
+                    for prefix in prefixes.iter().chain(&root_prefix) {
+                        for (at, target) in references(content, prefix) {
+                            if graph.nodes.contains(&target) && &target != module {
+                                let line = content[..at].matches('\n').count() + 1;
+                                graph.record(module, &target, file, line);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        graph
+    }
+
+    /// The graph as one line per crate or module, like
+    /// `commands -> checks, config`.
+    pub fn describe(&self) -> String {
+        let mut text = String::new();
+        for node in &self.nodes {
+            let targets: Vec<&str> = self
+                .edges
+                .keys()
+                .filter(|(from, _)| from == node)
+                .map(|(_, to)| to.as_str())
+                .collect();
+            let targets = if targets.is_empty() {
+                "(nothing)".to_string()
+            } else {
+                targets.join(", ")
+            };
+            text.push_str(&format!("{node} -> {targets}\n"));
+        }
+        text
+    }
+
+    /// Add the dependency of `from` on `to`, unless it is already known.
+    fn record(&mut self, from: &str, to: &str, file: &Path, line: usize) {
+        let edge = Edge {
+            file: file.to_path_buf(),
+            line,
+        };
+        self.edges
+            .entry((from.to_string(), to.to_string()))
+            .or_insert(edge);
+    }
+}
+
+/// The project's crates: the root package, if any, and each workspace member.
+fn workspace_crates(project_dir: &Path) -> Vec<Crate> {
+    let manifest = fs::read_to_string(project_dir.join("Cargo.toml"))
+        .ok()
+        .and_then(|text| text.parse::<toml::Table>().ok())
+        .unwrap_or_default();
+    let members = manifest
+        .get("workspace")
+        .and_then(|w| w.get("members"))
+        .and_then(|m| m.as_array())
+        .cloned()
+        .unwrap_or_default();
+
+    let mut dirs = vec![project_dir.to_path_buf()];
+    for member in members.iter().filter_map(|m| m.as_str()) {
+        match member.strip_suffix("/*") {
+            Some(parent) => {
+                let Ok(entries) = fs::read_dir(project_dir.join(parent)) else {
+                    continue;
+                };
+                let mut found: Vec<PathBuf> = entries.flatten().map(|e| e.path()).collect();
+                found.sort();
+                dirs.extend(found);
+            }
+            None => dirs.push(project_dir.join(member)),
+        }
+    }
+
+    dirs.into_iter()
+        .filter_map(|dir| {
+            let text = fs::read_to_string(dir.join("Cargo.toml")).ok()?;
+            let manifest: toml::Table = text.parse().ok()?;
+            let name = manifest.get("package")?.get("name")?.as_str()?.to_string();
+            let dependencies = manifest
+                .get("dependencies")
+                .and_then(|d| d.as_table())
+                .map(|d| d.keys().cloned().collect())
+                .unwrap_or_default();
+            Some(Crate {
+                name,
+                dir,
+                dependencies,
+            })
+        })
+        .collect()
+}
+
+/// The top-level modules under `src`, each with its files and whether each
+/// is the module's root file.
+fn top_level_modules(src: &Path, scan: &Scan) -> BTreeMap<String, Vec<(PathBuf, bool)>> {
+    let mut modules: BTreeMap<String, Vec<(PathBuf, bool)>> = BTreeMap::new();
+    let Ok(entries) = fs::read_dir(src) else {
+        return modules;
+    };
+    for path in entries.flatten().map(|e| e.path()) {
+        let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let name = stem.to_string();
+        if path.is_dir() {
+            for file in walk::files(&path, &["rs"], scan) {
+                let is_root = file == path.join("mod.rs");
+                modules
+                    .entry(name.clone())
+                    .or_default()
+                    .push((file, is_root));
+            }
+        } else if path.extension().is_some_and(|e| e == "rs") && !matches!(stem, "lib" | "main") {
+            modules.entry(name).or_default().push((path, true));
+        }
+    }
+    for files in modules.values_mut() {
+        files.sort();
+    }
+    modules
+}
+
+/// Each path in `content` starting with `prefix`, by the byte offset of the
+/// prefix and the path's next segment. A group such as `crate::{a, b::c}`
+/// gives one reference per item. Comments are skipped.
+fn references(content: &str, prefix: &str) -> Vec<(usize, String)> {
+    let ident = |s: &str| -> String {
+        s.chars()
+            .take_while(|c| c.is_alphanumeric() || *c == '_')
+            .collect()
+    };
+    let mut found = Vec::new();
+    for (at, _) in content.match_indices(prefix) {
+        let before = &content[..at];
+        if before
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_alphanumeric() || c == '_')
+        {
+            continue;
+        }
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        if before[line_start..].contains("//") {
+            continue;
+        }
+        let rest = &content[at + prefix.len()..];
+        let Some(group) = rest.strip_prefix('{') else {
+            found.push((at, ident(rest)));
+            continue;
+        };
+        let (mut depth, mut item_start) = (1, true);
+        for (i, c) in group.char_indices() {
+            match c {
+                '{' => depth += 1,
+                '}' if depth == 1 => break,
+                '}' => depth -= 1,
+                ',' if depth == 1 => item_start = true,
+                c if item_start && depth == 1 && !c.is_whitespace() => {
+                    item_start = false;
+                    found.push((at, ident(&group[i..])));
+                }
+                _ => {}
+            }
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_references() {
+        let content = "use crate::config;\n\
+                       use crate::{checks::{a, b}, ollama};\n\
+                       // crate::storage\n\
+                       let x = mycrate::thing;\n";
+        let found: Vec<String> = references(content, "crate::")
+            .into_iter()
+            .map(|(_, target)| target)
+            .collect();
+        assert_eq!(found, ["config", "checks", "ollama"]);
+    }
+
+    #[test]
+    fn test_extract_workspace_graph() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        let write = |path: &str, text: &str| {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, text).unwrap();
+        };
+        write(
+            "Cargo.toml",
+            "[package]\nname = \"app\"\n\n[dependencies]\nanyhow = \"1\"\ncore = { path = \"crates/core\" }\n\n[workspace]\nmembers = [\"crates/*\"]\n",
+        );
+        write("src/main.rs", "mod commands;\nuse core::checks;\n");
+        write("src/commands/mod.rs", "use super::output;\nmod run;\n");
+        write("src/commands/run.rs", "use core::{checks, config};\n");
+        write(
+            "src/output.rs",
+            "#[cfg(test)]\nmod tests { use crate::commands; }\n",
+        );
+        write("crates/core/Cargo.toml", "[package]\nname = \"core\"\n");
+        write(
+            "crates/core/src/lib.rs",
+            "pub mod checks;\npub mod config;\n",
+        );
+        write(
+            "crates/core/src/checks.rs",
+            "use crate::config::Settings;\n",
+        );
+        write("crates/core/src/config.rs", "");
+
+        let graph = Graph::extract(root, &Scan::default());
+        assert_eq!(
+            graph.describe(),
+            "app -> core\n\
+             checks -> config\n\
+             commands -> checks, config, output\n\
+             config -> (nothing)\n\
+             core -> (nothing)\n\
+             output -> (nothing)\n"
+        );
+        let edge = &graph.edges[&("commands".to_string(), "checks".to_string())];
+        assert_eq!(edge.file, root.join("src/commands/run.rs"));
+        assert_eq!(edge.line, 1);
+        let edge = &graph.edges[&("app".to_string(), "core".to_string())];
+        assert_eq!(edge.line, 6);
+    }
+}
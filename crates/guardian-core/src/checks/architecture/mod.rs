@@ -0,0 +1,193 @@
+//! Check that crates and modules depend on each other only as declared.
+//!
+//! `[check.architecture]` lists layers from the top down and, optionally,
+//! the only dependencies a crate or module may have. Each dependency in the
+//! project's [`Graph`] that points up a layer or isn't allowed is an error,
+//! at the line that introduces it.
+
+mod graph;
+
+use super::walk::Scan;
+use super::{CheckResult, Severity};
+use crate::config::ArchitectureRules;
+use std::path::Path;
+
+pub use graph::{Edge, Graph};
+
+/// Check the dependencies of the project at `project_dir` against `rules`,
+/// finding and reading its files as `scan` says.
+pub fn check(project_dir: &Path, rules: &ArchitectureRules, scan: &Scan) -> Vec<CheckResult> {
+    let graph = Graph::extract(project_dir, scan);
+    if rules.is_empty() {
+        return vec![CheckResult::pass(
+            "architecture",
+            &format!(
+                "{} dependencies between {} crates and modules; no layers declared",
+                graph.edges.len(),
+                graph.nodes.len()
+            ),
+        )];
+    }
+
+    let mut results = unknown_names(rules, &graph);
+    for ((from, to), edge) in &graph.edges {
+        let Some((message, fix)) = violation(rules, from, to) else {
+            continue;
+        };
+        results.push(
+            CheckResult::fail("architecture", Severity::Error, &message)
+                .with_file(&edge.file.display().to_string())
+                .with_line(edge.line)
+                .with_fix(&fix),
+        );
+    }
+    if results.is_empty() {
+        results.push(CheckResult::pass(
+            "architecture",
+            &format!(
+                "{} dependencies between {} crates and modules follow the declared layers",
+                graph.edges.len(),
+                graph.nodes.len()
+            ),
+        ));
+    }
+    results
+}
+
+/// Why `from` may not depend on `to`, and how to fix it, if it may not.
+fn violation(rules: &ArchitectureRules, from: &str, to: &str) -> Option<(String, String)> {
+    if let Some(allowed) = rules.allowed.get(from) {
+        if !allowed.iter().any(|name| name == to) {
+            let allowed = if allowed.is_empty() {
+                "nothing".to_string()
+            } else {
+                allowed.join(", ")
+            };
+            return Some((
+                format!("{from} depends on {to}, but may only depend on {allowed}"),
+                format!(
+                    "Remove the dependency, or add \"{to}\" to allowed.{from} in [check.architecture]"
+                ),
+            ));
+        }
+    }
+    let layer = |name: &str| {
+        rules
+            .layers
+            .iter()
+            .position(|l| l.iter().any(|n| n == name))
+    };
+    match (layer(from), layer(to)) {
+        (Some(from_layer), Some(to_layer)) if to_layer < from_layer => Some((
+            format!("{from} depends on {to}, which is in a higher layer"),
+            format!(
+                "Move what {from} needs from {to} into {from} or a lower layer, or have {to} pass it in"
+            ),
+        )),
+        _ => None,
+    }
+}
+
+/// Warnings for names in `rules` that match no crate or module, which are
+/// likely typos.
+fn unknown_names(rules: &ArchitectureRules, graph: &Graph) -> Vec<CheckResult> {
+    let mut names: Vec<&String> = rules.layers.iter().flatten().collect();
+    for (name, allowed) in &rules.allowed {
+        names.push(name);
+        names.extend(allowed);
+    }
+    names.sort();
+    names.dedup();
+    names
+        .into_iter()
+        .filter(|name| !graph.nodes.contains(*name))
+        .map(|name| {
+            CheckResult::fail(
+                "architecture",
+                Severity::Warning,
+                &format!("[check.architecture] names {name}, which is no crate or module"),
+            )
+            .with_fix("Use a workspace crate's name or a top-level module's name")
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn project() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir_all(src.join("commands")).unwrap();
+        fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        fs::write(
+            src.join("main.rs"),
+            "mod checks;\nmod commands;\nmod config;\n",
+        )
+        .unwrap();
+        fs::write(src.join("commands/mod.rs"), "use crate::checks;\n").unwrap();
+        fs::write(
+            src.join("checks.rs"),
+            "use crate::config;\n\nuse crate::commands;\n",
+        )
+        .unwrap();
+        fs::write(src.join("config.rs"), "").unwrap();
+        dir
+    }
+
+    fn rules(toml: &str) -> ArchitectureRules {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn test_layer_violations() {
+        let dir = project();
+        let results = check(dir.path(), &rules(""), &Scan::default());
+        assert!(results[0].passed);
+        assert!(results[0].message.starts_with("3 dependencies between 4"));
+
+        let results = check(
+            dir.path(),
+            &rules(r#"layers = ["commands", "checks", "config"]"#),
+            &Scan::default(),
+        );
+        assert_eq!(results.len(), 1);
+        assert!(!results[0].passed);
+        assert_eq!(
+            results[0].message,
+            "checks depends on commands, which is in a higher layer"
+        );
+        assert!(results[0].file.as_ref().unwrap().ends_with("checks.rs"));
+        assert_eq!(results[0].line, Some(3));
+
+        let results = check(
+            dir.path(),
+            &rules(r#"layers = [["commands", "checks"], "config"]"#),
+            &Scan::default(),
+        );
+        assert!(results[0].passed);
+    }
+
+    #[test]
+    fn test_allowed_dependencies_and_unknown_names() {
+        let dir = project();
+        let results = check(
+            dir.path(),
+            &rules("[allowed]\ncommands = [\"config\"]\nconfg = []\n"),
+            &Scan::default(),
+        );
+        let messages: Vec<&str> = results.iter().map(|r| r.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "[check.architecture] names confg, which is no crate or module",
+                "commands depends on checks, but may only depend on config",
+            ]
+        );
+        assert_eq!(results[0].severity, Severity::Warning);
+        assert_eq!(results[1].severity, Severity::Error);
+    }
+}
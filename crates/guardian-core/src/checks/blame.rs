@@ -0,0 +1,71 @@
+//! Blame attribution: who last changed the line a failure points at.
+//!
+//! Failures are grouped by file so each file costs one `git blame` (or
+//! `jj file annotate`) run. Files outside a repository, untracked files, and
+//! lines nobody has committed yet are left without attribution.
+
+use super::CheckResult;
+use super::vcs::Vcs;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// The commit that last changed a line.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Blame {
+    /// Full hash of the commit.
+    pub commit: String,
+    pub author: String,
+    /// Author email, without the angle brackets.
+    pub email: String,
+    /// Unix timestamp (seconds) when the commit was authored.
+    pub time: u64,
+    /// First line of the commit message.
+    pub summary: String,
+}
+
+/// Set [`CheckResult::blame`] on every failure that names a file and line.
+pub fn attribute(results: &mut [CheckResult]) {
+    let mut lines: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    for result in results.iter().filter(|r| !r.passed) {
+        if let (Some(file), Some(line)) = (&result.file, result.line) {
+            lines.entry(file.clone()).or_default().push(line);
+        }
+    }
+    let mut blamed: HashMap<String, HashMap<usize, Blame>> = HashMap::new();
+    for (file, lines) in lines {
+        let path = Path::new(&file);
+        let dir = path.parent().filter(|dir| !dir.as_os_str().is_empty());
+        match Vcs::detect(dir.unwrap_or(Path::new("."))).blame(path, &lines) {
+            Ok(blames) => {
+                blamed.insert(file, blames);
+            }
+            Err(e) => tracing::debug!(file, error = %e, "No blame"),
+        }
+    }
+    for result in results.iter_mut().filter(|r| !r.passed) {
+        if let (Some(file), Some(line)) = (&result.file, result.line) {
+            result.blame = blamed.get(file).and_then(|b| b.get(&line)).cloned();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checks::Severity;
+
+    #[test]
+    fn test_attribute_skips_untracked_files() {
+        let temp = tempfile::tempdir().unwrap();
+        let file = temp.path().join("main.rs");
+        std::fs::write(&file, "fn main() {}\n").unwrap();
+        let mut results = vec![
+            CheckResult::fail("clippy-disables", Severity::Warning, "allow")
+                .with_file(&file.display().to_string())
+                .with_line(1),
+        ];
+        attribute(&mut results);
+        assert_eq!(results[0].blame, None);
+    }
+}
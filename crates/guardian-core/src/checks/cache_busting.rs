@@ -1,5 +1,7 @@
 //! Check that README image links use cache-busting query parameters.
 
+use super::text::{self, Text};
+use super::walk::Scan;
 use super::{CheckResult, Severity};
 use std::collections::HashSet;
 use std::fs;
@@ -8,8 +10,9 @@ use std::path::Path;
 /// Image extensions to check for.
 const IMAGE_EXTENSIONS: &[&str] = &[".png", ".jpg", ".jpeg", ".gif", ".svg", ".webp"];
 
-/// Check README files for image links without cache-busting.
-pub fn check(project_dir: &Path) -> Vec<CheckResult> {
+/// Check README files for image links without cache-busting, skipping
+/// files larger than `scan` allows.
+pub fn check(project_dir: &Path, scan: &Scan) -> Vec<CheckResult> {
     let mut results = Vec::new();
     let mut checked_files = HashSet::new();
 
@@ -22,7 +25,7 @@ pub fn check(project_dir: &Path) -> Vec<CheckResult> {
             // Use canonical path to deduplicate on case-insensitive filesystems
             if let Ok(canonical) = readme_path.canonicalize() {
                 if checked_files.insert(canonical) {
-                    results.extend(check_readme(&readme_path));
+                    results.extend(check_readme(&readme_path, scan.max_file_bytes));
                 }
             }
         }
@@ -32,12 +35,13 @@ pub fn check(project_dir: &Path) -> Vec<CheckResult> {
     let docs_dir = project_dir.join("docs");
     if docs_dir.exists() {
         if let Ok(entries) = fs::read_dir(&docs_dir) {
-            for entry in entries.flatten() {
-                let path = entry.path();
+            let mut docs: Vec<_> = entries.flatten().map(|entry| entry.path()).collect();
+            docs.sort();
+            for path in docs {
                 if path.extension().is_some_and(|e| e == "md") {
                     if let Ok(canonical) = path.canonicalize() {
                         if checked_files.insert(canonical) {
-                            results.extend(check_readme(&path));
+                            results.extend(check_readme(&path, scan.max_file_bytes));
                         }
                     }
                 }
@@ -55,9 +59,17 @@ pub fn check(project_dir: &Path) -> Vec<CheckResult> {
     results
 }
 
-fn check_readme(file_path: &Path) -> Vec<CheckResult> {
-    let content = match fs::read_to_string(file_path) {
-        Ok(c) => c,
+fn check_readme(file_path: &Path, max_bytes: u64) -> Vec<CheckResult> {
+    let mut results = Vec::new();
+    let content = match text::read(file_path, max_bytes) {
+        Ok(Text::Source(c)) => c,
+        Ok(Text::Lossy(c)) => {
+            results.push(text::lossy("cache-busting", file_path));
+            c
+        }
+        Ok(Text::Oversized(reason) | Text::Skipped(reason)) => {
+            return vec![text::skipped("cache-busting", file_path, &reason)];
+        }
         Err(e) => {
             return vec![
                 CheckResult::fail(
@@ -70,7 +82,6 @@ fn check_readme(file_path: &Path) -> Vec<CheckResult> {
         }
     };
 
-    let mut results = Vec::new();
     let file_name = file_path
         .file_name()
         .map(|n| n.to_string_lossy().to_string())
@@ -99,6 +110,7 @@ fn check_readme(file_path: &Path) -> Vec<CheckResult> {
 
             // Check for cache-busting parameter
             if !has_cache_busting(&link) {
+                let separator = if link.contains('?') { '&' } else { '?' };
                 results.push(
                     CheckResult::fail(
                         "cache-busting",
@@ -107,10 +119,16 @@ fn check_readme(file_path: &Path) -> Vec<CheckResult> {
                     )
                     .with_file(&file_path.display().to_string())
                     .with_line(line_number)
+                    .with_column(line.find(link.as_str()).map_or(1, |i| i + 1))
                     .with_fix(&format!(
-                        "Add cache-busting parameter: {}?v=<version> or {}?ts=<timestamp>",
-                        link, link
-                    )),
+                        "Add cache-busting parameter: {link}{separator}v=<version> or {link}{separator}ts=<timestamp>"
+                    ))
+                    .with_edit(
+                        &content,
+                        line_number,
+                        1,
+                        &[line.replacen(&link, &format!("{link}{separator}v=1"), 1)],
+                    ),
                 );
             }
         }
@@ -195,10 +213,26 @@ mod tests {
         )
         .unwrap();
 
-        let results = check(temp.path());
+        let results = check(temp.path(), &Scan::default());
         let failures: Vec<_> = results.iter().filter(|r| !r.passed).collect();
         assert_eq!(failures.len(), 1);
         assert!(failures[0].message.contains("screenshot.png"));
+        let diff = failures[0].diff.as_deref().unwrap();
+        assert!(diff.contains("-![Screenshot](./images/screenshot.png)\n"));
+        assert!(diff.contains("+![Screenshot](./images/screenshot.png?v=1)\n"));
+    }
+
+    #[test]
+    fn test_fix_appends_to_a_query() {
+        let temp = TempDir::new().unwrap();
+        let readme = temp.path().join("README.md");
+        fs::write(&readme, "![Chart](./images/chart.svg?theme=dark)\n").unwrap();
+
+        let results = check(temp.path(), &Scan::default());
+        let fix = results[0].fix.as_deref().unwrap();
+        assert!(fix.contains("chart.svg?theme=dark&v=<version>"));
+        let diff = results[0].diff.as_deref().unwrap();
+        assert!(diff.contains("+![Chart](./images/chart.svg?theme=dark&v=1)\n"));
     }
 
     #[test]
@@ -215,7 +249,7 @@ mod tests {
         )
         .unwrap();
 
-        let results = check(temp.path());
+        let results = check(temp.path(), &Scan::default());
         assert!(results.iter().all(|r| r.passed));
     }
 
@@ -233,7 +267,7 @@ mod tests {
         )
         .unwrap();
 
-        let results = check(temp.path());
+        let results = check(temp.path(), &Scan::default());
         assert!(results.iter().all(|r| r.passed));
     }
 
@@ -251,7 +285,7 @@ mod tests {
         )
         .unwrap();
 
-        let results = check(temp.path());
+        let results = check(temp.path(), &Scan::default());
         assert!(results.iter().all(|r| r.passed));
     }
 
@@ -269,7 +303,7 @@ mod tests {
         )
         .unwrap();
 
-        let results = check(temp.path());
+        let results = check(temp.path(), &Scan::default());
         let failures: Vec<_> = results.iter().filter(|r| !r.passed).collect();
         assert_eq!(failures.len(), 1);
     }
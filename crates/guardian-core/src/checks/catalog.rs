@@ -0,0 +1,215 @@
+//! The check catalog: what each registered check looks for, the settings
+//! it reads with their defaults, and how severe its failures are.
+//!
+//! `guardian-cli rules` prints it, and tools and docs generators read it
+//! with `--json`. The defaults are taken from [`CheckSettings::default`],
+//! so they don't drift from what a run uses.
+
+use super::{OPT_IN_CHECKS, Severity};
+use crate::config::CheckSettings;
+use serde::Serialize;
+
+/// One registered check, as `guardian-cli rules` describes it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CheckInfo {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Severities its failures are reported at.
+    pub severities: Vec<Severity>,
+    /// Only runs when asked for, as the [`OPT_IN_CHECKS`] do.
+    pub opt_in: bool,
+    /// The config settings it reads.
+    pub options: Vec<CheckOption>,
+}
+
+/// A config setting a check reads.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CheckOption {
+    /// The dotted config key, e.g. `check.max_loc`.
+    pub key: &'static str,
+    pub description: &'static str,
+    /// The default value, written as TOML.
+    pub default: String,
+}
+
+/// Every check in [`CHECK_NAMES`], in the order they run.
+///
+/// [`CHECK_NAMES`]: super::CHECK_NAMES
+pub fn catalog() -> Vec<CheckInfo> {
+    let defaults = CheckSettings::default();
+    let (clippy, readability) = (&defaults.clippy, &defaults.readability);
+    let option = |key, description, default: String| CheckOption {
+        key,
+        description,
+        default,
+    };
+    let entries = [
+        (
+            "rust-edition",
+            "Every Cargo.toml declares the required Rust edition",
+            vec![Severity::Error],
+            vec![option(
+                "check.edition",
+                "Required Rust edition",
+                format!("{:?}", defaults.edition),
+            )],
+        ),
+        (
+            "loc-limits",
+            "Source files stay under a line count",
+            vec![Severity::Warning, Severity::Error],
+            vec![
+                option(
+                    "check.max_loc",
+                    "Lines a file may have before it is an error",
+                    defaults.max_loc.to_string(),
+                ),
+                option(
+                    "check.warn_loc",
+                    "Lines a file may have before it is a warning",
+                    defaults.warn_loc.to_string(),
+                ),
+            ],
+        ),
+        (
+            "function-count",
+            "Source files define a limited number of functions, tests aside",
+            vec![Severity::Error],
+            vec![option(
+                "check.max_functions",
+                "Functions a file may define",
+                defaults.max_functions.to_string(),
+            )],
+        ),
+        (
+            "module-count",
+            "Each crate has a limited number of top-level modules",
+            vec![Severity::Error],
+            vec![option(
+                "check.max_modules",
+                "Top-level modules a crate may declare",
+                defaults.max_modules.to_string(),
+            )],
+        ),
+        (
+            "architecture",
+            "Crates and modules only depend on what their layer allows",
+            vec![Severity::Error],
+            vec![
+                option(
+                    "check.architecture.layers",
+                    "Layers from the top down; each may depend only on those below",
+                    "[]".to_string(),
+                ),
+                option(
+                    "check.architecture.allowed",
+                    "The only names each listed crate or module may depend on",
+                    "{}".to_string(),
+                ),
+            ],
+        ),
+        (
+            "test-quality",
+            "Tests assert something rather than being placeholders",
+            vec![Severity::Warning],
+            Vec::new(),
+        ),
+        (
+            "clippy-disables",
+            "No lint is silenced with an allow attribute; clippy lints are errors",
+            vec![Severity::Warning, Severity::Error],
+            Vec::new(),
+        ),
+        (
+            "cache-busting",
+            "Image links in Markdown carry a cache-busting query",
+            vec![Severity::Warning],
+            Vec::new(),
+        ),
+        (
+            "supply-chain",
+            "A cargo-deny config sets license and security advisory policies",
+            vec![Severity::Warning, Severity::Error],
+            Vec::new(),
+        ),
+        (
+            "clippy-budget",
+            "cargo clippy warns within a budget and reports no denied lint (builds the project, running its build scripts and proc macros)",
+            vec![Severity::Warning, Severity::Error],
+            vec![
+                option(
+                    "check.clippy.max_warnings",
+                    "Warnings clippy may report before the check fails",
+                    clippy.max_warnings.to_string(),
+                ),
+                option(
+                    "check.clippy.deny",
+                    "Lints and lint groups that fail the check whatever the budget",
+                    format!("{:?}", clippy.deny),
+                ),
+                option(
+                    "check.clippy.timeout_secs",
+                    "Seconds clippy may run before it is stopped",
+                    clippy.timeout_secs.to_string(),
+                ),
+            ],
+        ),
+        (
+            "llm-readability",
+            "A model reviews changed files for code that is hard to read",
+            vec![Severity::Warning],
+            vec![
+                option(
+                    "check.readability.min_confidence",
+                    "Findings the model is less sure of are dropped, from 0 to 1",
+                    readability.min_confidence.to_string(),
+                ),
+                option(
+                    "check.readability.files",
+                    "Files to review; when empty, those changed since the last commit",
+                    "[]".to_string(),
+                ),
+                option(
+                    "check.readability.max_files",
+                    "Most files reviewed in one run",
+                    readability.max_files.to_string(),
+                ),
+            ],
+        ),
+    ];
+    entries
+        .into_iter()
+        .map(|(name, description, severities, options)| CheckInfo {
+            name,
+            description,
+            severities,
+            opt_in: OPT_IN_CHECKS.contains(&name),
+            options,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checks::CHECK_NAMES;
+
+    #[test]
+    fn test_catalog_covers_the_registry() {
+        let catalog = catalog();
+        let names: Vec<_> = catalog.iter().map(|c| c.name).collect();
+        assert_eq!(names, CHECK_NAMES);
+
+        let loc = &catalog[1];
+        assert_eq!(loc.name, "loc-limits");
+        assert_eq!(loc.options[0].key, "check.max_loc");
+        assert_eq!(loc.options[0].default, "500");
+        assert_eq!(catalog[0].options[0].default, "\"2024\"");
+        let opt_in: Vec<_> = catalog
+            .iter()
+            .filter(|c| c.opt_in)
+            .map(|c| c.name)
+            .collect();
+        assert_eq!(opt_in, OPT_IN_CHECKS);
+    }
+}
@@ -0,0 +1,250 @@
+//! Check that `cargo clippy` warns within a budget.
+//!
+//! Runs clippy on every target with JSON diagnostics, passing each lint in
+//! `[check.clippy] deny` as `-D` so that clippy reports those as errors.
+//! Every error fails the check, and warnings fail it once there are more
+//! than `max_warnings`. Each diagnostic is reported at its file and line.
+//! Clippy is stopped after `timeout_secs`, or when the run is interrupted.
+//! Where `clippy-disables` looks for lints switched off, this looks at what
+//! the lints left on find.
+//!
+//! Unlike the other checks, this one runs the project's code: clippy builds
+//! it, build scripts and proc macros included. Only run it on projects you
+//! trust.
+
+use super::interrupt::interrupted;
+use super::{CheckResult, Severity};
+use crate::config::ClippySettings;
+use anyhow::{Context, Result, bail};
+use std::collections::HashSet;
+use std::io::Read;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Help clippy gives that isn't a fix: links to the lint's docs, and how
+/// to silence it, which `clippy-disables` would flag.
+const SKIPPED_HELP: &[&str] = &["for further information", "to override"];
+
+/// Run clippy on `project_dir` and judge its diagnostics by `settings`.
+pub fn check(project_dir: &Path, settings: &ClippySettings) -> Vec<CheckResult> {
+    let fail = |message: &str| {
+        vec![
+            CheckResult::fail("clippy-budget", Severity::Warning, message)
+                .with_fix("Run `cargo clippy` in the project to see what went wrong"),
+        ]
+    };
+    let (success, stdout, stderr) = match run_clippy(project_dir, settings) {
+        Ok(output) => output,
+        Err(e) => return fail(&format!("{e:#}")),
+    };
+    let mut seen = HashSet::new();
+    let diagnostics: Vec<CheckResult> = stdout
+        .lines()
+        .filter_map(|line| diagnostic(project_dir, line))
+        .filter(|r| seen.insert((r.file.clone(), r.line, r.column, r.message.clone())))
+        .collect();
+    if !success && !diagnostics.iter().any(|r| r.severity == Severity::Error) {
+        let reason = stderr.lines().rev().find(|line| !line.trim().is_empty());
+        return fail(&format!(
+            "cargo clippy failed: {}",
+            reason.unwrap_or("no output")
+        ));
+    }
+    budget(diagnostics, settings.max_warnings)
+}
+
+/// Run `cargo clippy` in `project_dir` until it exits, returning whether it
+/// succeeded, and its stdout and stderr.
+fn run_clippy(project_dir: &Path, settings: &ClippySettings) -> Result<(bool, String, String)> {
+    let mut args = vec![
+        "clippy",
+        "--all-targets",
+        "--message-format=json",
+        "--quiet",
+        "--",
+    ];
+    for lint in &settings.deny {
+        args.extend(["-D", lint]);
+    }
+    let mut child = Command::new("cargo")
+        .current_dir(project_dir)
+        .args(&args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to run cargo clippy")?;
+    let (stdout, stderr) = (read_all(child.stdout.take()), read_all(child.stderr.take()));
+
+    let deadline = Instant::now() + Duration::from_secs(settings.timeout_secs);
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if interrupted() || Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            if interrupted() {
+                bail!("cargo clippy was interrupted");
+            }
+            bail!("cargo clippy took longer than {}s", settings.timeout_secs);
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    };
+    let join = |reader: JoinHandle<String>| reader.join().unwrap_or_default();
+    Ok((status.success(), join(stdout), join(stderr)))
+}
+
+/// Read all of `pipe` on another thread, so a full pipe can't stall the
+/// process writing to it.
+fn read_all(pipe: Option<impl Read + Send + 'static>) -> JoinHandle<String> {
+    std::thread::spawn(move || {
+        let mut bytes = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut bytes);
+        }
+        String::from_utf8_lossy(&bytes).into_owned()
+    })
+}
+
+/// The warning or error in one line of cargo's JSON output, if it has one
+/// with a place in the code. Its first help that isn't [`SKIPPED_HELP`] is
+/// the fix.
+fn diagnostic(project_dir: &Path, line: &str) -> Option<CheckResult> {
+    let json: serde_json::Value = serde_json::from_str(line).ok()?;
+    if json["reason"] != "compiler-message" {
+        return None;
+    }
+    let message = &json["message"];
+    let severity = match message["level"].as_str()? {
+        "error" => Severity::Error,
+        "warning" => Severity::Warning,
+        _ => return None,
+    };
+    let spans = message["spans"].as_array()?;
+    let span = spans.iter().find(|span| span["is_primary"] == true)?;
+    let file = Path::new(span["file_name"].as_str()?);
+    let name = file.file_name()?.to_string_lossy();
+    let text = message["message"].as_str()?;
+    let text = match message["code"]["code"].as_str() {
+        Some(code) => format!("{name}: {code}: {text}"),
+        None => format!("{name}: {text}"),
+    };
+    let file = match project_dir.join(file).is_file() {
+        true => project_dir.join(file),
+        false => file.to_path_buf(),
+    };
+    let result = CheckResult::fail("clippy-budget", severity, &text)
+        .with_file(&file.display().to_string())
+        .with_line(span["line_start"].as_u64()? as usize)
+        .with_column(span["column_start"].as_u64()? as usize);
+    let children = message["children"].as_array().into_iter().flatten();
+    let help = children
+        .filter(|child| child["level"] == "help")
+        .filter_map(|child| child["message"].as_str())
+        .find(|help| !SKIPPED_HELP.iter().any(|skipped| help.starts_with(skipped)));
+    Some(match help {
+        Some(help) => result.with_fix(help),
+        None => result,
+    })
+}
+
+/// The results for `diagnostics`: every error, and a verdict on the
+/// warnings, which are listed too when there are more than `max_warnings`.
+fn budget(diagnostics: Vec<CheckResult>, max_warnings: usize) -> Vec<CheckResult> {
+    let warnings = diagnostics
+        .iter()
+        .filter(|r| r.severity == Severity::Warning)
+        .count();
+    let plural = if warnings == 1 { "" } else { "s" };
+    if warnings > max_warnings {
+        let message =
+            format!("{warnings} clippy warning{plural}, over the budget of {max_warnings}");
+        let verdict = CheckResult::fail("clippy-budget", Severity::Error, &message)
+            .with_fix("Fix the warnings, or raise check.clippy.max_warnings");
+        return std::iter::once(verdict).chain(diagnostics).collect();
+    }
+    let message = format!("{warnings} clippy warning{plural}, within the budget of {max_warnings}");
+    let errors = diagnostics
+        .into_iter()
+        .filter(|r| r.severity == Severity::Error);
+    std::iter::once(CheckResult::pass("clippy-budget", &message))
+        .chain(errors)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(level: &str, code: &str, line: usize) -> String {
+        serde_json::json!({
+            "reason": "compiler-message",
+            "message": {
+                "level": level,
+                "message": "this looks wrong",
+                "code": {"code": code},
+                "spans": [
+                    {"is_primary": false, "file_name": "src/other.rs", "line_start": 1, "column_start": 1},
+                    {"is_primary": true, "file_name": "src/lib.rs", "line_start": line, "column_start": 5},
+                ],
+                "children": [
+                    {"level": "note", "message": "on by default"},
+                    {"level": "help", "message": "for further information visit https://example.com"},
+                    {"level": "help", "message": "to override `-D warnings` add an attribute"},
+                    {"level": "help", "message": "do it right"},
+                ],
+            },
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_diagnostic() {
+        let dir = Path::new("/nonexistent");
+        let warning = diagnostic(dir, &message("warning", "clippy::needless_return", 3)).unwrap();
+        assert_eq!(warning.severity, Severity::Warning);
+        assert_eq!(
+            warning.message,
+            "lib.rs: clippy::needless_return: this looks wrong"
+        );
+        assert_eq!(warning.file.as_deref(), Some("src/lib.rs"));
+        assert_eq!((warning.line, warning.column), (Some(3), Some(5)));
+        assert_eq!(warning.fix.as_deref(), Some("do it right"));
+
+        let summary = r#"{"reason":"compiler-message","message":{"level":"warning","message":"2 warnings emitted","code":null,"spans":[],"children":[]}}"#;
+        assert!(diagnostic(dir, summary).is_none());
+        assert!(diagnostic(dir, r#"{"reason":"build-finished","success":true}"#).is_none());
+        assert!(diagnostic(dir, &message("note", "x", 1)).is_none());
+    }
+
+    #[test]
+    fn test_budget() {
+        let dir = Path::new("/nonexistent");
+        let warnings =
+            || [2, 4].map(|line| diagnostic(dir, &message("warning", "w", line)).unwrap());
+        let results = budget(warnings().to_vec(), 2);
+        assert_eq!(results.len(), 1);
+        assert!(results[0].passed);
+        assert_eq!(
+            results[0].message,
+            "2 clippy warnings, within the budget of 2"
+        );
+
+        let mut diagnostics = warnings().to_vec();
+        diagnostics.push(diagnostic(dir, &message("error", "clippy::correctness", 9)).unwrap());
+        let results = budget(diagnostics.clone(), 2);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[1].line, Some(9));
+
+        let results = budget(diagnostics, 1);
+        assert_eq!(results.len(), 4);
+        assert_eq!(
+            results[0].message,
+            "2 clippy warnings, over the budget of 1"
+        );
+        assert_eq!(results[0].severity, Severity::Error);
+    }
+}
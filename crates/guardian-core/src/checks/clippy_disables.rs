@@ -1,7 +1,8 @@
 //! Check for clippy lint suppressions in source code.
 
+use super::sources::SourceFiles;
+use super::walk::Scan;
 use super::{CheckResult, Severity};
-use std::fs;
 use std::path::Path;
 
 /// Patterns that suppress clippy or rustc lints.
@@ -19,49 +20,16 @@ const ALLOWED_SUPPRESSIONS: &[&str] = &[
 
 /// Check for clippy disable patterns in all Rust source files.
 pub fn check(project_dir: &Path) -> Vec<CheckResult> {
-    let mut results = Vec::new();
-    let src_dir = project_dir.join("src");
-
-    if !src_dir.exists() {
-        return results;
-    }
-
-    check_directory(&src_dir, &mut results);
-    results
+    check_files(&SourceFiles::load(project_dir, &Scan::default()))
 }
 
-fn check_directory(dir: &Path, results: &mut Vec<CheckResult>) {
-    let entries = match fs::read_dir(dir) {
-        Ok(e) => e,
-        Err(_) => return,
-    };
-
-    for entry in entries.flatten() {
-        let path = entry.path();
-
-        if path.is_dir() {
-            check_directory(&path, results);
-        } else if path.extension().is_some_and(|e| e == "rs") {
-            results.extend(check_file(&path));
-        }
-    }
+/// Check already loaded source files for clippy disable patterns.
+pub fn check_files(files: &SourceFiles) -> Vec<CheckResult> {
+    files.results("clippy-disables", check_source)
 }
 
-fn check_file(file_path: &Path) -> Vec<CheckResult> {
-    let content = match fs::read_to_string(file_path) {
-        Ok(c) => c,
-        Err(e) => {
-            return vec![
-                CheckResult::fail(
-                    "clippy-disables",
-                    Severity::Warning,
-                    &format!("Read error: {e}"),
-                )
-                .with_file(&file_path.display().to_string()),
-            ];
-        }
-    };
-
+/// Check one file's `content`, such as an editor buffer, for suppressions.
+pub fn check_source(file_path: &Path, content: &str) -> Vec<CheckResult> {
     let mut results = Vec::new();
     let mut in_raw_string = false;
     let file_name = file_path
@@ -121,6 +89,7 @@ fn check_file(file_path: &Path) -> Vec<CheckResult> {
                     )
                     .with_file(&file_path.display().to_string())
                     .with_line(line_number)
+                    .with_column(line.find(pattern).map_or(1, |i| i + 1))
                     .with_fix("Remove the #[allow(...)] and fix the underlying issue instead"),
                 );
             }
@@ -0,0 +1,86 @@
+//! Check that commit messages follow the process guide's format.
+//!
+//! This check looks at commits rather than files, so it isn't in
+//! [`CHECK_NAMES`](super::CHECK_NAMES); `pre-push` runs it on each commit
+//! being pushed.
+
+use super::{CheckResult, Severity};
+
+/// Longest allowed subject line.
+pub const MAX_SUBJECT: usize = 50;
+
+/// Check the message of `commit`, an abbreviated hash or jj change id.
+pub fn check(commit: &str, message: &str) -> Vec<CheckResult> {
+    let fail = |severity, problem: String, fix: &str| {
+        CheckResult::fail("commit-message", severity, &format!("{commit}: {problem}")).with_fix(fix)
+    };
+    let mut lines = message.lines();
+    let subject = lines.next().unwrap_or_default().trim();
+    let mut results = Vec::new();
+
+    if subject.is_empty() {
+        results.push(fail(
+            Severity::Error,
+            "empty commit subject".to_string(),
+            "Reword the commit with a short summary of the change",
+        ));
+    } else if subject.starts_with("fixup!") || subject.starts_with("squash!") {
+        results.push(fail(
+            Severity::Error,
+            format!("unsquashed commit '{subject}'"),
+            "Squash it with `git rebase -i --autosquash` before pushing",
+        ));
+    }
+    let length = subject.chars().count();
+    if length > MAX_SUBJECT {
+        results.push(fail(
+            Severity::Warning,
+            format!("subject is {length} characters (max {MAX_SUBJECT})"),
+            "Shorten the subject and move detail into the body",
+        ));
+    }
+    if lines.next().is_some_and(|line| !line.trim().is_empty()) {
+        results.push(fail(
+            Severity::Warning,
+            "no blank line between subject and body".to_string(),
+            "Separate the subject from the body with a blank line",
+        ));
+    }
+
+    if results.is_empty() {
+        results.push(CheckResult::pass(
+            "commit-message",
+            &format!("{commit}: {subject}"),
+        ));
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_good_message_passes() {
+        let results = check("abc1234", "feat: Add pre-push\n\nChecks pushed commits.\n");
+        assert_eq!(results.len(), 1);
+        assert!(results[0].passed);
+        assert_eq!(results[0].message, "abc1234: feat: Add pre-push");
+    }
+
+    #[test]
+    fn test_bad_messages_fail() {
+        let results = check("abc1234", "");
+        assert_eq!(results[0].severity, Severity::Error);
+        assert_eq!(results[0].message, "abc1234: empty commit subject");
+
+        let results = check("abc1234", "fixup! feat: Add pre-push");
+        assert_eq!(results[0].severity, Severity::Error);
+
+        let long = "x".repeat(MAX_SUBJECT + 1);
+        let results = check("abc1234", &format!("{long}\nbody right away"));
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|r| r.severity == Severity::Warning));
+        assert!(results[0].message.ends_with("51 characters (max 50)"));
+    }
+}
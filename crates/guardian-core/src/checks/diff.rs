@@ -0,0 +1,166 @@
+//! Machine-applicable fixes, as unified diffs that `git apply` accepts.
+
+use super::CheckResult;
+use std::fmt::Write;
+
+/// Lines of unchanged context around each edit.
+const CONTEXT: usize = 3;
+
+impl CheckResult {
+    /// Attach a diff that replaces `remove` lines of `content`, starting at
+    /// 1-based `line`, with `insert`. Call after `with_file`.
+    pub fn with_edit(
+        mut self,
+        content: &str,
+        line: usize,
+        remove: usize,
+        insert: &[String],
+    ) -> Self {
+        let path = self.file.as_deref().unwrap_or_default();
+        self.diff = Some(unified_diff(
+            path.trim_start_matches("./"),
+            content,
+            line,
+            remove,
+            insert,
+        ));
+        self
+    }
+}
+
+/// A single-hunk unified diff of one edit to `content`.
+///
+/// Lines keep their `\r` in a CRLF file, and `insert` gets one too. When
+/// the file doesn't end in a newline, the hunk says so wherever its last
+/// line shows up, as `git diff` does.
+pub fn unified_diff(
+    path: &str,
+    content: &str,
+    line: usize,
+    remove: usize,
+    insert: &[String],
+) -> String {
+    let lines: Vec<&str> = content
+        .split_inclusive('\n')
+        .map(|l| l.strip_suffix('\n').unwrap_or(l))
+        .collect();
+    let cr = if content.contains("\r\n") { "\r" } else { "" };
+    let unterminated = !content.is_empty() && !content.ends_with('\n');
+    let mut start = line.saturating_sub(1).min(lines.len());
+    let end = (start + remove).min(lines.len());
+    let mut insert = insert.to_vec();
+    // Lines added after an unterminated last line end it, so it changes.
+    if unterminated && start == lines.len() && start > 0 && !insert.is_empty() {
+        start -= 1;
+        insert.insert(0, lines[start].to_string());
+    }
+    let before = start.saturating_sub(CONTEXT);
+    let after = (end + CONTEXT).min(lines.len());
+    let kept = (start - before) + (after - end);
+    let no_newline = "\\ No newline at end of file\n";
+    let last = lines.len().wrapping_sub(1);
+
+    let mut diff = format!("--- a/{path}\n+++ b/{path}\n");
+    let _ = writeln!(
+        diff,
+        "@@ -{},{} +{},{} @@",
+        before + 1,
+        kept + end - start,
+        before + 1,
+        kept + insert.len()
+    );
+    let old = |diff: &mut String, prefix: char, at: usize| {
+        let _ = writeln!(diff, "{prefix}{}", lines[at]);
+        if unterminated && at == last {
+            diff.push_str(no_newline);
+        }
+    };
+    for at in before..start {
+        old(&mut diff, ' ', at);
+    }
+    for at in start..end {
+        old(&mut diff, '-', at);
+    }
+    for (i, added) in insert.iter().enumerate() {
+        if unterminated && end == lines.len() && i + 1 == insert.len() {
+            let _ = writeln!(diff, "+{added}");
+            diff.push_str(no_newline);
+        } else {
+            let _ = writeln!(diff, "+{added}{cr}");
+        }
+    }
+    for at in end..after {
+        old(&mut diff, ' ', at);
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CARGO: &str =
+        "[package]\nname = \"demo\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\n";
+
+    #[test]
+    fn test_replace_line() {
+        let diff = unified_diff(
+            "Cargo.toml",
+            CARGO,
+            4,
+            1,
+            &["edition = \"2024\"".to_string()],
+        );
+        assert_eq!(
+            diff,
+            "--- a/Cargo.toml\n+++ b/Cargo.toml\n@@ -1,6 +1,6 @@\n [package]\n name = \"demo\"\n \
+             version = \"0.1.0\"\n-edition = \"2021\"\n+edition = \"2024\"\n \n [dependencies]\n"
+        );
+    }
+
+    #[test]
+    fn test_insert_line() {
+        let diff = unified_diff(
+            "Cargo.toml",
+            "[package]\nname = \"demo\"\n",
+            2,
+            0,
+            &["edition = \"2024\"".to_string()],
+        );
+        assert!(
+            diff.contains("@@ -1,2 +1,3 @@\n [package]\n+edition = \"2024\"\n name = \"demo\"\n")
+        );
+    }
+
+    #[test]
+    fn test_keeps_crlf_line_endings() {
+        let diff = unified_diff("a.md", "a\r\nb\r\nc\r\n", 2, 1, &["B".to_string()]);
+        assert_eq!(
+            diff,
+            "--- a/a.md\n+++ b/a.md\n@@ -1,3 +1,3 @@\n a\r\n-b\r\n+B\r\n c\r\n"
+        );
+    }
+
+    #[test]
+    fn test_marks_a_missing_final_newline() {
+        let header = "--- a/a.md\n+++ b/a.md\n";
+        let marker = "\\ No newline at end of file\n";
+        let diff = unified_diff("a.md", "a\nb", 2, 1, &["B".to_string()]);
+        assert_eq!(
+            diff,
+            format!("{header}@@ -1,2 +1,2 @@\n a\n-b\n{marker}+B\n{marker}")
+        );
+
+        let diff = unified_diff("a.md", "a\nb", 1, 1, &["A".to_string()]);
+        assert_eq!(
+            diff,
+            format!("{header}@@ -1,2 +1,2 @@\n-a\n+A\n b\n{marker}")
+        );
+
+        let diff = unified_diff("a.md", "a\nb", 3, 0, &["c".to_string()]);
+        assert_eq!(
+            diff,
+            format!("{header}@@ -1,2 +1,3 @@\n a\n-b\n{marker}+b\n+c\n{marker}")
+        );
+    }
+}
@@ -0,0 +1,161 @@
+//! Check that modules don't have too many functions.
+
+use super::sources::SourceFiles;
+use super::walk::Scan;
+use super::{CheckResult, Severity};
+use std::path::Path;
+
+/// Check function count per module.
+pub fn check(project_dir: &Path, max_functions: usize) -> Vec<CheckResult> {
+    check_files(&SourceFiles::load(project_dir, &Scan::default()), max_functions)
+}
+
+/// Check function count per module in already loaded source files.
+pub fn check_files(files: &SourceFiles, max_functions: usize) -> Vec<CheckResult> {
+    files.results("function-count", |path, content| {
+        vec![check_source(path, content, max_functions)]
+    })
+}
+
+/// Check the function count of one file's `content`, such as an editor buffer.
+pub fn check_source(path: &Path, content: &str, max_functions: usize) -> CheckResult {
+    let fn_lines = function_lines(content);
+    let function_count = fn_lines.len();
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    if function_count > max_functions {
+        CheckResult::fail(
+            "function-count",
+            Severity::Error,
+            &format!("{file_name}: {function_count} functions exceeds max {max_functions}"),
+        )
+        .with_file(&path.display().to_string())
+        .with_line(fn_lines[max_functions])
+        .with_fix(&format!(
+            "Split {file_name} into smaller modules with fewer functions"
+        ))
+    } else {
+        CheckResult::pass(
+            "function-count",
+            &format!("{file_name}: {function_count} functions (OK)"),
+        )
+        .with_file(&path.display().to_string())
+    }
+}
+
+/// Line numbers of function definitions in Rust source code, excluding test modules and string literals.
+fn function_lines(content: &str) -> Vec<usize> {
+    let mut lines = Vec::new();
+    let mut in_test_module = false;
+    let mut in_raw_string = false;
+    let mut brace_depth = 0;
+    let mut test_module_depth = 0;
+
+    let fn_patterns = [
+        "fn ", "pub fn ", "pub(crate) fn ", "pub(super) fn ",
+        "async fn ", "pub async fn ", "const fn ", "pub const fn ",
+        "unsafe fn ", "pub unsafe fn ",
+    ];
+
+    for (line_num, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+
+        // Track raw string boundaries (r#" ... "#)
+        if trimmed.contains("r#\"") {
+            in_raw_string = true;
+        }
+        if in_raw_string {
+            if trimmed.contains("\"#") {
+                in_raw_string = false;
+            }
+            continue;
+        }
+
+        // Track test module boundaries
+        if trimmed.contains("#[cfg(test)]") {
+            in_test_module = true;
+            test_module_depth = brace_depth;
+        }
+
+        // Count braces to track scope
+        for ch in line.chars() {
+            match ch {
+                '{' => brace_depth += 1,
+                '}' if in_test_module && brace_depth - 1 <= test_module_depth => {
+                    brace_depth -= 1;
+                    in_test_module = false;
+                }
+                '}' => brace_depth -= 1,
+                _ => {}
+            }
+        }
+
+        // Skip test module content and comments
+        if in_test_module
+            || trimmed.starts_with("//")
+            || trimmed.starts_with("/*")
+            || trimmed.starts_with('*')
+        {
+            continue;
+        }
+
+        // Count function definitions
+        if fn_patterns.iter().any(|p| trimmed.contains(p)) && trimmed.contains('(') {
+            lines.push(line_num + 1);
+        }
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_simple_functions() {
+        let content = r#"
+fn foo() {}
+fn bar() {}
+pub fn baz() {}
+"#;
+        assert_eq!(function_lines(content), vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn test_excludes_test_functions() {
+        let content = r#"
+fn production_fn() {}
+
+#[cfg(test)]
+mod tests {
+    fn test_helper() {}
+
+    #[test]
+    fn test_something() {}
+}
+"#;
+        assert_eq!(function_lines(content).len(), 1);
+    }
+
+    #[test]
+    fn test_count_async_functions() {
+        let content = r#"
+async fn async_foo() {}
+pub async fn async_bar() {}
+"#;
+        assert_eq!(function_lines(content).len(), 2);
+    }
+
+    #[test]
+    fn test_ignores_comments() {
+        let content = r#"
+// fn not_a_function() {}
+fn real_function() {}
+"#;
+        assert_eq!(function_lines(content).len(), 1);
+    }
+}
@@ -0,0 +1,22 @@
+//! Stopping a run early, keeping what it found.
+//!
+//! On Ctrl-C, `check` and `evaluate` call [`interrupt`] instead of exiting
+//! mid-print. [`run_selected_checks`] then starts no more checks, and the
+//! command reports the results it has, marked partial.
+//!
+//! [`run_selected_checks`]: super::run_selected_checks
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Set by [`interrupt`], for the rest of the run.
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// Start no more work in this run.
+pub fn interrupt() {
+    INTERRUPTED.store(true, Ordering::Relaxed);
+}
+
+/// Whether the run was interrupted.
+pub fn interrupted() -> bool {
+    INTERRUPTED.load(Ordering::Relaxed)
+}
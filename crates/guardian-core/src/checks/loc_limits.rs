@@ -1,49 +1,42 @@
 //! Check that source files don't exceed line count limits.
+//!
+//! Only line counts are needed, so a file over the size limit is still
+//! checked: its lines are counted as it streams by (see
+//! [`SourceFiles::line_counts`]).
 
+use super::sources::SourceFiles;
+use super::walk::Scan;
 use super::{CheckResult, Severity};
-use std::fs;
 use std::path::Path;
 
 /// Check LOC limits for all Rust source files.
 pub fn check(project_dir: &Path, max_loc: usize, warn_loc: usize) -> Vec<CheckResult> {
-    let mut results = Vec::new();
-    let src_dir = project_dir.join("src");
-
-    if !src_dir.exists() {
-        return results;
-    }
-
-    check_directory(&src_dir, max_loc, warn_loc, &mut results);
-    results
+    check_files(
+        &SourceFiles::load(project_dir, &Scan::default()),
+        max_loc,
+        warn_loc,
+    )
 }
 
-fn check_directory(dir: &Path, max_loc: usize, warn_loc: usize, results: &mut Vec<CheckResult>) {
-    let entries = match fs::read_dir(dir) {
-        Ok(e) => e,
-        Err(_) => return,
-    };
-
-    for entry in entries.flatten() {
-        let path = entry.path();
-
-        if path.is_dir() {
-            check_directory(&path, max_loc, warn_loc, results);
-        } else if path.extension().is_some_and(|e| e == "rs") {
-            results.push(check_file(&path, max_loc, warn_loc));
-        }
-    }
+/// Check LOC limits for already loaded source files.
+pub fn check_files(files: &SourceFiles, max_loc: usize, warn_loc: usize) -> Vec<CheckResult> {
+    files.line_counts("loc-limits", |path, loc| {
+        check_lines(path, loc, max_loc, warn_loc)
+    })
 }
 
-fn check_file(file_path: &Path, max_loc: usize, warn_loc: usize) -> CheckResult {
-    let content = match fs::read_to_string(file_path) {
-        Ok(c) => c,
-        Err(e) => {
-            return CheckResult::fail("loc-limits", Severity::Warning, &format!("Read error: {e}"))
-                .with_file(&file_path.display().to_string());
-        }
-    };
+/// Check the LOC limits of one file's `content`, such as an editor buffer.
+pub fn check_source(
+    file_path: &Path,
+    content: &str,
+    max_loc: usize,
+    warn_loc: usize,
+) -> CheckResult {
+    check_lines(file_path, content.lines().count(), max_loc, warn_loc)
+}
 
-    let loc = content.lines().count();
+/// Check a file's `loc` against the limits.
+fn check_lines(file_path: &Path, loc: usize, max_loc: usize, warn_loc: usize) -> CheckResult {
     let file_name = file_path
         .file_name()
         .map(|n| n.to_string_lossy().to_string())
@@ -56,6 +49,7 @@ fn check_file(file_path: &Path, max_loc: usize, warn_loc: usize) -> CheckResult
             &format!("{file_name}: {loc} lines exceeds max {max_loc}"),
         )
         .with_file(&file_path.display().to_string())
+        .with_line(max_loc + 1)
         .with_fix(&format!(
             "Split {file_name} into smaller modules (each under {max_loc} lines)"
         ))
@@ -66,6 +60,7 @@ fn check_file(file_path: &Path, max_loc: usize, warn_loc: usize) -> CheckResult
             &format!("{file_name}: {loc} lines exceeds warning threshold {warn_loc}"),
         )
         .with_file(&file_path.display().to_string())
+        .with_line(warn_loc + 1)
         .with_fix("Consider splitting into smaller modules")
     } else {
         CheckResult::pass("loc-limits", &format!("{file_name}: {loc} lines (OK)"))
@@ -76,6 +71,7 @@ fn check_file(file_path: &Path, max_loc: usize, warn_loc: usize) -> CheckResult
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
     use tempfile::TempDir;
 
     fn create_file_with_lines(dir: &Path, name: &str, lines: usize) {
@@ -114,5 +110,6 @@ mod tests {
         assert_eq!(results.len(), 1);
         assert!(!results[0].passed);
         assert_eq!(results[0].severity, Severity::Error);
+        assert_eq!(results[0].line, Some(501));
     }
 }
@@ -2,17 +2,65 @@
 //!
 //! Each check module implements specific validation rules that can be
 //! run against a Rust project to enforce coding standards.
+//! [`select_checks`] resolves which checks run, and [`run_selected_checks`]
+//! and [`run_source_checks`] run them on a project or on one file's text.
 
+pub mod architecture;
+pub mod blame;
 pub mod cache_busting;
+pub mod catalog;
+pub mod clippy_budget;
 pub mod clippy_disables;
+pub mod commit_message;
+pub mod diff;
 pub mod function_count;
+pub mod interrupt;
 pub mod loc_limits;
 pub mod module_count;
+pub mod projects;
+mod run;
 pub mod rust_edition;
+pub mod sources;
+pub mod supply_chain;
 pub mod test_quality;
+pub mod text;
+pub mod untested;
+pub mod vcs;
+pub mod walk;
+
+use crate::config::{ArchitectureRules, ClippySettings};
+use serde::{Deserialize, Serialize};
+use walk::Scan;
+
+pub use run::{configured_thresholds, run_selected_checks, run_source_checks, select_checks};
+
+/// Registry of check names, in the order the checks run.
+///
+/// `--only`, `--skip`, and `[checks.NAME]` config tables are resolved
+/// against these names.
+pub const CHECK_NAMES: &[&str] = &[
+    "rust-edition",
+    "loc-limits",
+    "function-count",
+    "module-count",
+    "architecture",
+    "test-quality",
+    "clippy-disables",
+    "cache-busting",
+    "supply-chain",
+    "clippy-budget",
+    "llm-readability",
+];
+
+/// Checks that only run when asked for: by `--only`, `check.only`, or a
+/// `[checks.NAME]` table. `clippy-budget` builds the project, which can
+/// take minutes, and `llm-readability` needs a model and sends code to it,
+/// so neither is ever a surprise.
+pub const OPT_IN_CHECKS: &[&str] = &["clippy-budget", "llm-readability"];
 
 /// Severity level for check results.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Severity {
     /// Informational - no action required
     Info,
@@ -23,9 +71,13 @@ pub enum Severity {
 }
 
 /// Result of a single check.
-#[derive(Debug, Clone)]
+///
+/// Serializes with the field names of `check --format json`, so saved
+/// results and JSON reports read back the same way.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CheckResult {
     /// Name of the check
+    #[serde(rename = "check")]
     pub check_name: String,
     /// Whether the check passed
     pub passed: bool,
@@ -37,8 +89,17 @@ pub struct CheckResult {
     pub file: Option<String>,
     /// Line number (if applicable)
     pub line: Option<usize>,
+    /// Column number, 1-based (if applicable)
+    pub column: Option<usize>,
     /// Suggested fix
     pub fix: Option<String>,
+    /// The fix as a unified diff, when the check can compute the edit
+    pub diff: Option<String>,
+    /// Who last changed the line, when [`blame::attribute`] found out
+    pub blame: Option<blame::Blame>,
+    /// The project under the root a `check --recursive` run found this in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
 }
 
 impl CheckResult {
@@ -51,7 +112,11 @@ impl CheckResult {
             message: message.to_string(),
             file: None,
             line: None,
+            column: None,
             fix: None,
+            diff: None,
+            blame: None,
+            project: None,
         }
     }
 
@@ -64,7 +129,11 @@ impl CheckResult {
             message: message.to_string(),
             file: None,
             line: None,
+            column: None,
             fix: None,
+            diff: None,
+            blame: None,
+            project: None,
         }
     }
 
@@ -80,6 +149,12 @@ impl CheckResult {
         self
     }
 
+    /// Add column number to result.
+    pub fn with_column(mut self, column: usize) -> Self {
+        self.column = Some(column);
+        self
+    }
+
     /// Add suggested fix to result.
     pub fn with_fix(mut self, fix: &str) -> Self {
         self.fix = Some(fix.to_string());
@@ -100,6 +175,12 @@ pub struct CheckConfig {
     pub max_modules_per_crate: usize,
     /// Required Rust edition
     pub required_edition: String,
+    /// Layers and allowed dependencies between crates and modules
+    pub architecture: ArchitectureRules,
+    /// Warning budget and denied lints for `clippy-budget`
+    pub clippy: ClippySettings,
+    /// How the files to check are found and read
+    pub scan: Scan,
 }
 
 impl Default for CheckConfig {
@@ -110,6 +191,9 @@ impl Default for CheckConfig {
             max_functions_per_module: 7,
             max_modules_per_crate: 4,
             required_edition: "2024".to_string(),
+            architecture: ArchitectureRules::default(),
+            clippy: ClippySettings::default(),
+            scan: Scan::default(),
         }
     }
 }
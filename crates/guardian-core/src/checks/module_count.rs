@@ -41,13 +41,13 @@ fn check_workspace(workspace_dir: &Path, max_modules: usize, results: &mut Vec<C
     }
 
     // Check member directories
-    let entries = match fs::read_dir(workspace_dir) {
-        Ok(e) => e,
+    let mut members: Vec<_> = match fs::read_dir(workspace_dir) {
+        Ok(e) => e.flatten().map(|entry| entry.path()).collect(),
         Err(_) => return,
     };
+    members.sort();
 
-    for entry in entries.flatten() {
-        let path = entry.path();
+    for path in members {
         if path.is_dir() && !is_ignored_dir(&path) {
             let src_dir = path.join("src");
             if src_dir.exists() {
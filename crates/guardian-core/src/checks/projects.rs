@@ -0,0 +1,141 @@
+//! Finding the projects under a monorepo root, for `check --recursive`.
+//!
+//! Only directories are visited, and the search stops at each project and
+//! skips directories no `[projects]` glob could match, so it stays cheap on
+//! a large tree. Hidden directories and `target` are skipped, and symlinks
+//! aren't followed.
+
+use crate::config::ProjectSettings;
+use std::path::{Path, PathBuf};
+
+/// The project directories under `root`, sorted. `root` itself is never
+/// one.
+pub fn discover(root: &Path, settings: &ProjectSettings) -> Vec<PathBuf> {
+    let mut projects = Vec::new();
+    search(root, &[], settings, &mut projects);
+    projects.sort();
+    projects
+}
+
+/// Add `dir`, at `relative` path components under the root, to `found` if
+/// it is a project, or else search its subdirectories.
+fn search(dir: &Path, relative: &[String], settings: &ProjectSettings, found: &mut Vec<PathBuf>) {
+    if !relative.is_empty() {
+        if matches(&settings.exclude, relative, false) {
+            return;
+        }
+        let project = if settings.paths.is_empty() {
+            dir.join("Cargo.toml").is_file()
+        } else {
+            matches(&settings.paths, relative, false)
+        };
+        if project {
+            found.push(dir.to_path_buf());
+            return;
+        }
+        if !settings.paths.is_empty() && !matches(&settings.paths, relative, true) {
+            return;
+        }
+    }
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let is_dir = entry.file_type().is_ok_and(|t| t.is_dir());
+        if is_dir && name != "target" && !name.starts_with('.') {
+            let relative: Vec<String> = relative.iter().cloned().chain([name]).collect();
+            search(&entry.path(), &relative, settings, found);
+        }
+    }
+}
+
+/// Whether one of `globs` matches the path `components`, or with
+/// `partial`, could match a path under it.
+fn matches(globs: &[String], components: &[String], partial: bool) -> bool {
+    let components: Vec<&str> = components.iter().map(String::as_str).collect();
+    globs.iter().any(|pattern| {
+        let pattern: Vec<&str> = pattern.split('/').filter(|p| !p.is_empty()).collect();
+        glob(&pattern, &components, partial)
+    })
+}
+
+/// Whether `path`'s components match `pattern`'s, or with `partial`, are
+/// the start of a path that could.
+fn glob(pattern: &[&str], path: &[&str], partial: bool) -> bool {
+    match (pattern.split_first(), path.split_first()) {
+        (None, None) => true,
+        (None, Some(_)) => false,
+        (Some(_), None) => partial || pattern.iter().all(|p| *p == "**"),
+        (Some((&"**", rest)), Some((_, path_rest))) => {
+            glob(rest, path, partial) || glob(pattern, path_rest, partial)
+        }
+        (Some((first, rest)), Some((name, path_rest))) => {
+            let (first, name): (Vec<char>, Vec<char>) =
+                (first.chars().collect(), name.chars().collect());
+            component(&first, &name) && glob(rest, path_rest, partial)
+        }
+    }
+}
+
+/// Whether `name` matches `pattern`, where `*` matches any run of
+/// characters and `?` any one.
+fn component(pattern: &[char], name: &[char]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some(('*', rest)) => (0..=name.len()).any(|skip| component(rest, &name[skip..])),
+        Some(('?', rest)) => !name.is_empty() && component(rest, &name[1..]),
+        Some((c, rest)) => name.first() == Some(c) && component(rest, &name[1..]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(root: &Path, projects: &[PathBuf]) -> Vec<String> {
+        let relative = projects.iter().map(|p| p.strip_prefix(root).unwrap());
+        relative.map(|p| p.display().to_string()).collect()
+    }
+
+    #[test]
+    fn test_glob() {
+        let path = ["services", "api"];
+        assert!(glob(&["services", "*"], &path, false));
+        assert!(glob(&["s?rvices", "a*"], &path, false));
+        assert!(glob(&["**", "api"], &path, false));
+        assert!(glob(&["services", "**"], &path, false));
+        assert!(!glob(&["services"], &path, false));
+        assert!(!glob(&["tools", "*"], &path, false));
+        assert!(glob(&["services", "*", "v2"], &path, true));
+        assert!(!glob(&["tools", "*", "v2"], &path, true));
+    }
+
+    #[test]
+    fn test_discover() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        let manifests = [
+            "Cargo.toml",
+            "a/Cargo.toml",
+            "a/nested/Cargo.toml",
+            "libs/b/Cargo.toml",
+            "target/debug/Cargo.toml",
+            ".cache/c/Cargo.toml",
+        ];
+        for manifest in manifests.map(|file| root.join(file)) {
+            std::fs::create_dir_all(manifest.parent().unwrap()).unwrap();
+            std::fs::write(manifest, "[package]\n").unwrap();
+        }
+        std::fs::create_dir_all(root.join("libs/d/x")).unwrap();
+
+        let settings = ProjectSettings::default();
+        assert_eq!(names(root, &discover(root, &settings)), ["a", "libs/b"]);
+
+        let settings = ProjectSettings {
+            paths: vec!["libs/*".to_string()],
+            exclude: vec!["libs/b".to_string()],
+        };
+        assert_eq!(names(root, &discover(root, &settings)), ["libs/d"]);
+    }
+}
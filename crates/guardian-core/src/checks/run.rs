@@ -0,0 +1,262 @@
+//! Running checks: choosing them by name, and running them on a project
+//! or on one file's text.
+
+use super::interrupt::interrupted;
+use super::sources::SourceFiles;
+use super::walk::Scan;
+use super::{
+    CHECK_NAMES, CheckConfig, CheckResult, OPT_IN_CHECKS, architecture, cache_busting,
+    clippy_budget, clippy_disables, function_count, loc_limits, module_count, rust_edition,
+    supply_chain, test_quality,
+};
+use crate::config::{CheckSettings, CheckToggle};
+use anyhow::{Result, bail};
+use std::cell::LazyCell;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Resolve which checks run, in registry order.
+///
+/// Starts from `only` (or else `config_only`, or else every check but the
+/// [`OPT_IN_CHECKS`] without a `[checks.NAME]` table), drops checks
+/// disabled in the config unless `only` names them, then drops `skip`.
+/// Unknown check names are an error.
+pub fn select_checks(
+    only: Option<&str>,
+    skip: Option<&str>,
+    config_only: Option<&[String]>,
+    toggles: &BTreeMap<String, CheckToggle>,
+) -> Result<Vec<&'static str>> {
+    let only = only
+        .map(|s| check_names(s.split(','), "--only"))
+        .transpose()?;
+    let skip = skip
+        .map(|s| check_names(s.split(','), "--skip"))
+        .transpose()?
+        .unwrap_or_default();
+    let config_only = config_only
+        .map(|names| check_names(names.iter().map(String::as_str), "check.only"))
+        .transpose()?;
+    let disabled = check_names(
+        toggles
+            .iter()
+            .filter(|(_, t)| !t.enabled)
+            .map(|(name, _)| name.as_str()),
+        "[checks]",
+    )?;
+    check_names(toggles.keys().map(String::as_str), "[checks]")?;
+
+    Ok(CHECK_NAMES
+        .iter()
+        .copied()
+        .filter(|name| match (&only, &config_only) {
+            (Some(only), _) => only.contains(name),
+            (None, Some(config_only)) => config_only.contains(name) && !disabled.contains(name),
+            (None, None) => {
+                !disabled.contains(name)
+                    && (!OPT_IN_CHECKS.contains(name) || toggles.contains_key(*name))
+            }
+        })
+        .filter(|name| !skip.contains(name))
+        .collect())
+}
+
+/// Trim and validate check names against the registry.
+fn check_names<'a>(names: impl Iterator<Item = &'a str>, source: &str) -> Result<Vec<&'a str>> {
+    let names: Vec<&str> = names.map(str::trim).filter(|n| !n.is_empty()).collect();
+    if let Some(unknown) = names.iter().find(|n| !CHECK_NAMES.contains(n)) {
+        bail!(
+            "Unknown check '{unknown}' in {source}; known checks: {} (see `guardian-cli rules`)",
+            CHECK_NAMES.join(", ")
+        );
+    }
+    Ok(names)
+}
+
+/// Run the `selected` checks on `project_dir`. The checks that scan every
+/// source file share one read of them. Once the run is [`interrupted`], no
+/// more checks start.
+pub fn run_selected_checks(
+    project_dir: &Path,
+    config: &CheckConfig,
+    selected: &[&str],
+) -> Vec<CheckResult> {
+    let should_run = |name: &str| selected.contains(&name) && !interrupted();
+    let sources = LazyCell::new(|| SourceFiles::load(project_dir, &config.scan));
+
+    let mut results = Vec::new();
+
+    if should_run("rust-edition") {
+        results.extend(rust_edition::check(project_dir, &config.required_edition));
+    }
+
+    if should_run("loc-limits") {
+        results.extend(loc_limits::check_files(
+            &sources,
+            config.max_file_loc,
+            config.warn_file_loc,
+        ));
+    }
+
+    if should_run("function-count") {
+        results.extend(function_count::check_files(
+            &sources,
+            config.max_functions_per_module,
+        ));
+    }
+
+    if should_run("module-count") {
+        results.extend(module_count::check(
+            project_dir,
+            config.max_modules_per_crate,
+        ));
+    }
+
+    if should_run("architecture") {
+        results.extend(architecture::check(
+            project_dir,
+            &config.architecture,
+            &config.scan,
+        ));
+    }
+
+    if should_run("test-quality") {
+        results.extend(test_quality::check_files(&sources));
+    }
+
+    if should_run("clippy-disables") {
+        results.extend(clippy_disables::check_files(&sources));
+    }
+
+    if should_run("cache-busting") {
+        results.extend(cache_busting::check(project_dir, &config.scan));
+    }
+
+    if should_run("supply-chain") {
+        results.extend(supply_chain::check(project_dir));
+    }
+
+    if should_run("clippy-budget") {
+        results.extend(clippy_budget::check(project_dir, &config.clippy));
+    }
+
+    results
+}
+
+/// Check thresholds from the `[check]` config section.
+pub fn configured_thresholds(settings: &CheckSettings) -> CheckConfig {
+    CheckConfig {
+        max_file_loc: settings.max_loc,
+        warn_file_loc: settings.warn_loc,
+        max_functions_per_module: settings.max_functions,
+        max_modules_per_crate: settings.max_modules,
+        required_edition: settings.edition.clone(),
+        architecture: settings.architecture.clone(),
+        clippy: settings.clippy.clone(),
+        scan: Scan::from(settings),
+    }
+}
+
+/// Run the `selected` checks that look at one file at a time on `content`,
+/// the text of the Rust file at `file_path`.
+pub fn run_source_checks(
+    file_path: &Path,
+    content: &str,
+    config: &CheckConfig,
+    selected: &[&str],
+) -> Vec<CheckResult> {
+    let should_run = |name: &str| selected.contains(&name);
+
+    let mut results = Vec::new();
+
+    if should_run("loc-limits") {
+        results.push(loc_limits::check_source(
+            file_path,
+            content,
+            config.max_file_loc,
+            config.warn_file_loc,
+        ));
+    }
+
+    if should_run("function-count") {
+        results.push(function_count::check_source(
+            file_path,
+            content,
+            config.max_functions_per_module,
+        ));
+    }
+
+    if should_run("test-quality") {
+        results.extend(test_quality::check_source(file_path, content));
+    }
+
+    if should_run("clippy-disables") {
+        results.extend(clippy_disables::check_source(file_path, content));
+    }
+
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn toggles(disabled: &[&str]) -> BTreeMap<String, CheckToggle> {
+        disabled
+            .iter()
+            .map(|name| (name.to_string(), CheckToggle { enabled: false }))
+            .collect()
+    }
+
+    #[test]
+    fn test_select_checks() {
+        let none = BTreeMap::new();
+        let default = select_checks(None, None, None, &none).unwrap();
+        assert_eq!(default, CHECK_NAMES[..CHECK_NAMES.len() - 2]);
+        assert!(!default.contains(&"llm-readability"));
+        assert!(!default.contains(&"clippy-budget"));
+        assert_eq!(
+            select_checks(Some("loc-limits, rust-edition"), None, None, &none).unwrap(),
+            ["rust-edition", "loc-limits"]
+        );
+
+        let disabled = toggles(&["cache-busting", "module-count"]);
+        let selected = select_checks(None, Some("test-quality"), None, &disabled).unwrap();
+        assert_eq!(selected.len(), CHECK_NAMES.len() - 5);
+        assert!(!selected.contains(&"cache-busting"));
+        assert!(!selected.contains(&"test-quality"));
+
+        // --only overrides the config, including disabled checks
+        let selected = select_checks(Some("module-count"), None, None, &disabled).unwrap();
+        assert_eq!(selected, ["module-count"]);
+        let config_only = ["module-count".to_string(), "loc-limits".to_string()];
+        let selected = select_checks(None, None, Some(&config_only), &disabled).unwrap();
+        assert_eq!(selected, ["loc-limits"]);
+    }
+
+    #[test]
+    fn test_select_opt_in_checks() {
+        let opted_in = OPT_IN_CHECKS
+            .iter()
+            .map(|name| (name.to_string(), CheckToggle { enabled: true }))
+            .collect();
+        let selected = select_checks(None, None, None, &opted_in).unwrap();
+        assert_eq!(selected, CHECK_NAMES);
+        let selected = select_checks(Some("llm-readability"), None, None, &BTreeMap::new());
+        assert_eq!(selected.unwrap(), ["llm-readability"]);
+        let opted_out = toggles(&["llm-readability"]);
+        let selected = select_checks(None, None, None, &opted_out).unwrap();
+        assert!(!selected.contains(&"llm-readability"));
+    }
+
+    #[test]
+    fn test_select_checks_rejects_unknown_names() {
+        let none = BTreeMap::new();
+        let err = select_checks(None, Some("cache-bust"), None, &none).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Unknown check 'cache-bust' in --skip")
+        );
+        assert!(select_checks(None, None, None, &toggles(&["loc"])).is_err());
+    }
+}
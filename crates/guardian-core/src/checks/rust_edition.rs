@@ -39,8 +39,9 @@ fn find_cargo_files(dir: &Path) -> Vec<std::path::PathBuf> {
 
     // Check subdirectories for workspace members
     if let Ok(entries) = fs::read_dir(dir) {
-        for entry in entries.flatten() {
-            let path = entry.path();
+        let mut members: Vec<_> = entries.flatten().map(|entry| entry.path()).collect();
+        members.sort();
+        for path in members {
             if path.is_dir() && !is_ignored_dir(&path) {
                 let sub_cargo = path.join("Cargo.toml");
                 if sub_cargo.exists() {
@@ -79,7 +80,14 @@ fn check_cargo_toml(cargo_path: &Path, required_edition: &str) -> CheckResult {
     // Parse TOML to find edition
     let parsed: Result<toml::Value, _> = content.parse();
     match parsed {
-        Ok(toml) => check_edition_value(&toml, &rel_path, cargo_path, required_edition),
+        Ok(toml) => {
+            let result = check_edition_value(&toml, &rel_path, cargo_path, required_edition);
+            if result.passed {
+                result
+            } else {
+                locate_fix(result, &content, required_edition)
+            }
+        }
         Err(e) => CheckResult::fail(
             "rust-edition",
             Severity::Error,
@@ -89,18 +97,51 @@ fn check_cargo_toml(cargo_path: &Path, required_edition: &str) -> CheckResult {
     }
 }
 
+/// Point a failed result at the `edition` key, or at `[package]` when there
+/// is none, and attach the edit that sets the required edition.
+fn locate_fix(result: CheckResult, content: &str, required: &str) -> CheckResult {
+    let lines: Vec<&str> = content.lines().collect();
+    let find = |prefix: &str| {
+        lines
+            .iter()
+            .position(|l| l.trim_start().starts_with(prefix))
+    };
+
+    if let Some(i) = find("edition") {
+        let result = result.with_line(i + 1);
+        // Only plain `edition = "..."` values are rewritten, not `edition.workspace`
+        match lines[i].split_once('=') {
+            Some((key, value)) if key.trim() == "edition" && value.trim().starts_with('"') => {
+                result.with_edit(content, i + 1, 1, &[format!("{key}= \"{required}\"")])
+            }
+            _ => result,
+        }
+    } else if let Some(i) = find("[package]") {
+        let edition = format!("edition = \"{required}\"");
+        result
+            .with_line(i + 1)
+            .with_edit(content, i + 2, 0, &[edition])
+    } else {
+        result
+    }
+}
+
 fn check_edition_value(
     toml: &toml::Value,
     rel_path: &str,
     cargo_path: &Path,
     required: &str,
 ) -> CheckResult {
-    let edition = toml
-        .get("package")
-        .and_then(|p| p.get("edition"))
-        .and_then(|e| e.as_str());
+    let edition = match toml.get("package").and_then(|p| p.get("edition")) {
+        Some(toml::Value::String(e)) => Some(e.clone()),
+        // `edition.workspace = true` inherits `[workspace.package] edition`
+        Some(e) if e.get("workspace").and_then(|w| w.as_bool()) == Some(true) => {
+            workspace_edition(cargo_path)
+        }
+        _ => None,
+    };
 
-    match edition {
+    match edition.as_deref() {
         Some(e) if e == required => CheckResult::pass(
             "rust-edition",
             &format!("{rel_path}: Using Rust {required} edition"),
@@ -140,6 +181,20 @@ fn check_edition_value(
     }
 }
 
+/// The `[workspace.package] edition` of the nearest workspace root at or
+/// above `cargo_path`.
+fn workspace_edition(cargo_path: &Path) -> Option<String> {
+    let cargo_path = cargo_path.canonicalize().ok()?;
+    cargo_path.ancestors().skip(1).find_map(|dir| {
+        let toml: toml::Value = fs::read_to_string(dir.join("Cargo.toml"))
+            .ok()?
+            .parse()
+            .ok()?;
+        let edition = toml.get("workspace")?.get("package")?.get("edition")?;
+        edition.as_str().map(str::to_string)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -184,6 +239,9 @@ edition = "2021"
         assert!(!results[0].passed);
         assert!(results[0].message.contains("2021"));
         assert!(results[0].fix.is_some());
+        assert_eq!(results[0].line, Some(4));
+        let diff = results[0].diff.as_deref().unwrap();
+        assert!(diff.contains("-edition = \"2021\"\n+edition = \"2024\"\n"));
     }
 
     #[test]
@@ -203,5 +261,43 @@ name = "test"
         assert_eq!(results.len(), 1);
         assert!(!results[0].passed);
         assert!(results[0].message.contains("No edition"));
+        let diff = results[0].diff.as_deref().unwrap();
+        assert!(diff.contains(" [package]\n+edition = \"2024\"\n name = \"test\"\n"));
+    }
+
+    #[test]
+    fn test_workspace_inherited_edition() {
+        let temp = TempDir::new().unwrap();
+        fs::write(
+            temp.path().join("Cargo.toml"),
+            r#"
+[workspace]
+members = ["member"]
+
+[workspace.package]
+edition = "2021"
+"#,
+        )
+        .unwrap();
+        fs::create_dir(temp.path().join("member")).unwrap();
+        fs::write(
+            temp.path().join("member/Cargo.toml"),
+            r#"
+[package]
+name = "member"
+edition.workspace = true
+"#,
+        )
+        .unwrap();
+
+        let results = check(temp.path(), "2021");
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|r| r.passed));
+
+        let results = check(&temp.path().join("member"), "2024");
+        assert!(!results[0].passed);
+        assert!(results[0].message.contains("'2021'"));
+        assert_eq!(results[0].line, Some(4));
+        assert!(results[0].diff.is_none());
     }
 }
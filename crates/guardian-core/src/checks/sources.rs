@@ -0,0 +1,148 @@
+//! The Rust files under a project's `src`, read once for every check.
+//!
+//! `loc-limits`, `function-count`, `test-quality`, and `clippy-disables` all
+//! look at every file under `src`. [`run_selected_checks`] loads them into
+//! one [`SourceFiles`] and hands it to each check, so a run walks the tree
+//! and reads each file once rather than once per check. Each check still
+//! reports files that couldn't be read, or were skipped, under its own name.
+//!
+//! [`run_selected_checks`]: super::run_selected_checks
+
+use super::text::{self, Text};
+use super::walk::{self, Scan};
+use super::{CheckResult, Severity};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The `.rs` files under a project's `src`, in path order, with their text.
+#[derive(Debug, Default)]
+pub struct SourceFiles {
+    files: Vec<(PathBuf, io::Result<Text>)>,
+}
+
+impl SourceFiles {
+    /// Walk `project_dir`'s `src` as `scan` says and read each `.rs` file
+    /// in it, `scan.jobs` files at a time.
+    pub fn load(project_dir: &Path, scan: &Scan) -> Self {
+        let src_dir = project_dir.join("src");
+        if !src_dir.exists() {
+            return Self::default();
+        }
+        let paths = walk::files(&src_dir, &["rs"], scan);
+        let chunk = paths.len().div_ceil(scan.jobs).max(1);
+        let files = std::thread::scope(|scope| {
+            let readers: Vec<_> = paths
+                .chunks(chunk)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        let read =
+                            |path: &PathBuf| (path.clone(), text::read(path, scan.max_file_bytes));
+                        chunk.iter().map(read).collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            readers
+                .into_iter()
+                .flat_map(|reader| reader.join().unwrap_or_default())
+                .collect()
+        });
+        Self { files }
+    }
+
+    /// Run `check` on each file's text, giving the files it can't look at
+    /// a read-error or skipped result under `check_name`, and the ones that
+    /// weren't valid UTF-8 a warning.
+    pub fn results(
+        &self,
+        check_name: &str,
+        mut check: impl FnMut(&Path, &str) -> Vec<CheckResult>,
+    ) -> Vec<CheckResult> {
+        let mut results = Vec::new();
+        for (path, text) in &self.files {
+            match text {
+                Ok(Text::Source(content)) => results.extend(check(path, content)),
+                Ok(Text::Lossy(content)) => {
+                    results.push(text::lossy(check_name, path));
+                    results.extend(check(path, content));
+                }
+                Ok(Text::Oversized(reason) | Text::Skipped(reason)) => {
+                    results.push(text::skipped(check_name, path, reason));
+                }
+                Err(e) => results.push(
+                    CheckResult::fail(check_name, Severity::Warning, &format!("Read error: {e}"))
+                        .with_file(&path.display().to_string()),
+                ),
+            }
+        }
+        results
+    }
+
+    /// Run `check` on each file's line count. A file over the size limit
+    /// is counted without being read into memory; other files that
+    /// weren't read get a read-error or skipped result under `check_name`.
+    pub fn line_counts(
+        &self,
+        check_name: &str,
+        mut check: impl FnMut(&Path, usize) -> CheckResult,
+    ) -> Vec<CheckResult> {
+        let read_error = |path: &Path, e: &io::Error| {
+            CheckResult::fail(check_name, Severity::Warning, &format!("Read error: {e}"))
+                .with_file(&path.display().to_string())
+        };
+        self.files
+            .iter()
+            .map(|(path, text)| match text {
+                Ok(Text::Source(content) | Text::Lossy(content)) => {
+                    check(path, content.lines().count())
+                }
+                Ok(Text::Oversized(_)) => match text::count_lines(path) {
+                    Ok(lines) => check(path, lines),
+                    Err(e) => read_error(path, &e),
+                },
+                Ok(Text::Skipped(reason)) => text::skipped(check_name, path, reason),
+                Err(e) => read_error(path, e),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_results_cover_every_file() {
+        let temp = tempfile::tempdir().unwrap();
+        let src = temp.path().join("src");
+        fs::create_dir(&src).unwrap();
+        fs::write(src.join("lib.rs"), "fn a() {}\n").unwrap();
+        fs::write(src.join("blob.rs"), b"\0").unwrap();
+
+        let sources = SourceFiles::load(temp.path(), &Scan::default());
+        let mut seen = Vec::new();
+        let results = sources.results("loc-limits", |path, content| {
+            seen.push(content.to_string());
+            vec![CheckResult::pass("loc-limits", &path.display().to_string())]
+        });
+        assert_eq!(seen, ["fn a() {}\n"]);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].message, "blob.rs: skipped, binary content");
+
+        let large = src.join("large.rs");
+        fs::write(&large, "fn a() {}\n".repeat(3)).unwrap();
+        let reason = "30 bytes, over the 4 byte limit".to_string();
+        let oversized = SourceFiles {
+            files: vec![(large, Ok(Text::Oversized(reason)))],
+        };
+        let counted = oversized.line_counts("loc-limits", |path, lines| {
+            CheckResult::pass("loc-limits", &format!("{}: {lines}", path.display()))
+        });
+        assert!(counted[0].message.ends_with("large.rs: 3"));
+        let skipped = oversized.results("function-count", |_, _| panic!());
+        assert!(skipped[0].message.starts_with("large.rs: skipped"));
+
+        let missing = SourceFiles::load(&temp.path().join("gone"), &Scan::default());
+        assert!(missing.results("loc-limits", |_, _| panic!()).is_empty());
+    }
+}
@@ -0,0 +1,207 @@
+//! Check that a project has cargo-deny supply-chain guardrails.
+//!
+//! The `deny.toml` (or `.deny.toml`, or `.cargo/deny.toml`) is looked for
+//! in the project directory and its parents up to the repository root, as
+//! cargo-deny finds it. It must have a `[licenses]` section that allows or
+//! denies some license, and an `[advisories]` section that sets a policy
+//! without letting vulnerable crates through.
+
+use super::{CheckResult, Severity};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Config file names cargo-deny reads, in the order it looks for them.
+const DENY_CONFIG_NAMES: &[&str] = &["deny.toml", ".deny.toml", ".cargo/deny.toml"];
+
+/// Check `project_dir`'s cargo-deny config.
+pub fn check(project_dir: &Path) -> Vec<CheckResult> {
+    let Some(path) = find_config(project_dir) else {
+        return vec![
+            CheckResult::fail(
+                "supply-chain",
+                Severity::Warning,
+                "No deny.toml: dependency licenses and advisories aren't checked",
+            )
+            .with_fix("Run `cargo deny init`, then list the allowed licenses under [licenses]"),
+        ];
+    };
+    let file = path.display().to_string();
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) => {
+            let message = format!("Read error: {e}");
+            return vec![
+                CheckResult::fail("supply-chain", Severity::Warning, &message).with_file(&file),
+            ];
+        }
+    };
+    let config: toml::Table = match toml::from_str(&content) {
+        Ok(config) => config,
+        Err(e) => {
+            let message = format!("deny.toml isn't valid TOML: {}", e.message().trim());
+            return vec![
+                CheckResult::fail("supply-chain", Severity::Error, &message).with_file(&file),
+            ];
+        }
+    };
+
+    let mut results: Vec<CheckResult> = [
+        ("licenses", licenses_problem(&config)),
+        ("advisories", advisories_problem(&config)),
+    ]
+    .into_iter()
+    .filter_map(|(section, problem)| {
+        let (message, fix) = problem?;
+        let result = CheckResult::fail("supply-chain", Severity::Warning, &message)
+            .with_file(&file)
+            .with_fix(fix);
+        Some(match section_line(&content, section) {
+            Some(line) => result.with_line(line),
+            None => result,
+        })
+    })
+    .collect();
+    if results.is_empty() {
+        results.push(
+            CheckResult::pass(
+                "supply-chain",
+                "deny.toml: license and advisory policies set",
+            )
+            .with_file(&file),
+        );
+    }
+    results
+}
+
+/// The first cargo-deny config in `project_dir` or a parent, stopping at
+/// the repository root.
+fn find_config(project_dir: &Path) -> Option<PathBuf> {
+    let start = project_dir.canonicalize().ok()?;
+    for dir in start.ancestors() {
+        let found = DENY_CONFIG_NAMES
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file());
+        if let Some(found) = found {
+            let relative = found.strip_prefix(&start).map(|rel| project_dir.join(rel));
+            return Some(relative.unwrap_or(found));
+        }
+        if dir.join(".git").exists() {
+            break;
+        }
+    }
+    None
+}
+
+/// What's missing from the `[licenses]` policy, with a fix.
+fn licenses_problem(config: &toml::Table) -> Option<(String, &'static str)> {
+    let Some(licenses) = config.get("licenses") else {
+        return Some((
+            "deny.toml has no [licenses] section".to_string(),
+            "Add [licenses] with the `allow` list of licenses dependencies may use",
+        ));
+    };
+    let listed = |key: &str| {
+        licenses
+            .get(key)
+            .and_then(toml::Value::as_array)
+            .is_some_and(|list| !list.is_empty())
+    };
+    if listed("allow") || listed("deny") {
+        return None;
+    }
+    Some((
+        "deny.toml's [licenses] allows and denies no license".to_string(),
+        "List the licenses dependencies may use under `allow` in [licenses]",
+    ))
+}
+
+/// What's missing from the `[advisories]` policy, with a fix.
+fn advisories_problem(config: &toml::Table) -> Option<(String, &'static str)> {
+    let advisories = config.get("advisories").and_then(toml::Value::as_table);
+    let Some(advisories) = advisories.filter(|table| !table.is_empty()) else {
+        return Some((
+            "deny.toml sets no [advisories] policy".to_string(),
+            "Add [advisories] with `version = 2` so crates with security advisories are denied",
+        ));
+    };
+    if advisories
+        .get("vulnerability")
+        .and_then(toml::Value::as_str)
+        == Some("allow")
+    {
+        return Some((
+            "deny.toml's [advisories] allows vulnerable crates".to_string(),
+            "Remove `vulnerability = \"allow\"` from [advisories]",
+        ));
+    }
+    None
+}
+
+/// The line of `content`'s `[section]` header, if it has one.
+fn section_line(content: &str, section: &str) -> Option<usize> {
+    let header = format!("[{section}]");
+    content
+        .lines()
+        .position(|line| line.trim() == header)
+        .map(|index| index + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_config(content: &str) -> Vec<CheckResult> {
+        let temp = tempfile::tempdir().unwrap();
+        fs::create_dir(temp.path().join(".git")).unwrap();
+        fs::write(temp.path().join("deny.toml"), content).unwrap();
+        check(temp.path())
+    }
+
+    #[test]
+    fn test_policies() {
+        let results = check_config(
+            "[advisories]\nversion = 2\n\n[licenses]\nallow = [\"MIT\", \"Apache-2.0\"]\n",
+        );
+        assert_eq!(results.len(), 1);
+        assert!(results[0].passed);
+
+        let results = check_config("[advisories]\nvulnerability = \"allow\"\n\n[licenses]\n");
+        let messages: Vec<_> = results.iter().map(|r| r.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "deny.toml's [licenses] allows and denies no license",
+                "deny.toml's [advisories] allows vulnerable crates",
+            ]
+        );
+        assert_eq!((results[0].line, results[1].line), (Some(4), Some(1)));
+
+        let results = check_config("[bans]\nmultiple-versions = \"warn\"\n");
+        assert_eq!(results.len(), 2);
+        assert!(results[1].message.contains("no [advisories] policy"));
+        assert_eq!(check_config("[licenses")[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn test_finds_config_in_parents() {
+        let temp = tempfile::tempdir().unwrap();
+        let member = temp.path().join("crates/app");
+        fs::create_dir_all(&member).unwrap();
+        fs::create_dir(temp.path().join(".git")).unwrap();
+        let results = check(&member);
+        assert!(results[0].message.starts_with("No deny.toml"));
+
+        fs::create_dir(temp.path().join(".cargo")).unwrap();
+        fs::write(temp.path().join(".cargo/deny.toml"), "").unwrap();
+        let results = check(&member);
+        assert!(
+            results[0]
+                .file
+                .as_deref()
+                .unwrap()
+                .ends_with(".cargo/deny.toml")
+        );
+        assert!(!results[0].passed);
+    }
+}
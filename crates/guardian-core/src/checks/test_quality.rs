@@ -1,7 +1,8 @@
 //! Check that tests are not trivial or placeholder tests.
 
+use super::sources::SourceFiles;
+use super::walk::Scan;
 use super::{CheckResult, Severity};
-use std::fs;
 use std::path::Path;
 
 /// Patterns that indicate a trivial or placeholder test.
@@ -19,51 +20,31 @@ const TRIVIAL_PATTERNS: &[&str] = &[
 
 /// Check test quality in all Rust source files.
 pub fn check(project_dir: &Path) -> Vec<CheckResult> {
-    let mut results = Vec::new();
-    let src_dir = project_dir.join("src");
-
-    if !src_dir.exists() {
-        return results;
-    }
+    check_files(&SourceFiles::load(project_dir, &Scan::default()))
+}
 
-    collect_results(&src_dir, &mut results);
-    results
+/// Check test quality in already loaded source files.
+pub fn check_files(files: &SourceFiles) -> Vec<CheckResult> {
+    files.results("test-quality", check_source)
 }
 
-fn collect_results(dir: &Path, results: &mut Vec<CheckResult>) {
-    let entries = match fs::read_dir(dir) {
-        Ok(e) => e,
-        Err(_) => return,
-    };
-
-    for entry in entries.flatten() {
-        let path = entry.path();
-        if path.is_dir() {
-            collect_results(&path, results);
-        } else if path.extension().is_some_and(|e| e == "rs") {
-            let content = match fs::read_to_string(&path) {
-                Ok(c) => c,
-                Err(e) => {
-                    results.push(
-                        CheckResult::fail("test-quality", Severity::Warning, &format!("Read error: {e}"))
-                            .with_file(&path.display().to_string()),
-                    );
-                    continue;
-                }
-            };
-
-            let file_results = analyze_file(&content, &path);
-            if file_results.is_empty() {
-                let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
-                results.push(
-                    CheckResult::pass("test-quality", &format!("{file_name}: No trivial tests found"))
-                        .with_file(&path.display().to_string()),
-                );
-            } else {
-                results.extend(file_results);
-            }
-        }
+/// Check the tests in one file's `content`, such as an editor buffer.
+pub fn check_source(file_path: &Path, content: &str) -> Vec<CheckResult> {
+    let results = analyze_file(content, file_path);
+    if !results.is_empty() {
+        return results;
     }
+    let file_name = file_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    vec![
+        CheckResult::pass(
+            "test-quality",
+            &format!("{file_name}: No trivial tests found"),
+        )
+        .with_file(&file_path.display().to_string()),
+    ]
 }
 
 fn analyze_file(content: &str, file_path: &Path) -> Vec<CheckResult> {
@@ -133,6 +114,7 @@ fn analyze_file(content: &str, file_path: &Path) -> Vec<CheckResult> {
                         )
                         .with_file(&file_path.display().to_string())
                         .with_line(line_number)
+                        .with_column(line.find(pattern).map_or(1, |i| i + 1))
                         .with_fix(&format!(
                             "Replace trivial assertion with meaningful test logic in '{test_name}' (started at line {test_start_line})"
                         )),
@@ -0,0 +1,167 @@
+//! Reading the files the checks scan, skipping ones that aren't source.
+//!
+//! A committed fixture can be hundreds of megabytes, or an image with an
+//! `.rs` name; reading it into a `String` wastes memory and then fails.
+//! [`read`] looks at the extension and size first and the first bytes
+//! after, and says why it skipped a file instead. Checks report a skipped
+//! file as a passing result, with [`skipped`]. Line counts don't need the
+//! text, so [`count_lines`] streams even a file too large to read.
+//!
+//! Legacy repos mix encodings, so a file that isn't valid UTF-8 is still
+//! checked, with the invalid bytes replaced; checks note it with a warning
+//! from [`lossy`]. A byte order mark is dropped, so it doesn't end up in
+//! the first line.
+
+use super::{CheckResult, Severity};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::Path;
+
+/// The UTF-8 byte order mark.
+const BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Bytes looked at for a NUL, as git does to spot binary files.
+const SNIFF_BYTES: usize = 8000;
+
+/// Extensions of files that are never text.
+const BINARY_EXTENSIONS: &[&str] = &[
+    "png", "jpg", "jpeg", "gif", "ico", "webp", "pdf", "zip", "gz", "tgz", "xz", "zst", "bz2",
+    "7z", "tar", "jar", "wasm", "so", "dylib", "dll", "exe", "a", "o", "rlib", "bin", "db",
+    "sqlite",
+];
+
+/// A file's contents, or why they weren't read.
+#[derive(Debug, PartialEq)]
+pub enum Text {
+    Source(String),
+    /// Contents that weren't valid UTF-8, with the invalid bytes replaced
+    /// by U+FFFD.
+    Lossy(String),
+    /// Not read, since it is over the size limit; says by how much.
+    Oversized(String),
+    Skipped(String),
+}
+
+impl Text {
+    /// The contents, if the file was read.
+    pub fn into_source(self) -> Option<String> {
+        match self {
+            Self::Source(text) | Self::Lossy(text) => Some(text),
+            Self::Oversized(_) | Self::Skipped(_) => None,
+        }
+    }
+}
+
+/// The text of the file at `path`, unless it is binary or over `max`
+/// bytes.
+pub fn read(path: &Path, max: u64) -> io::Result<Text> {
+    let extension = path.extension().unwrap_or_default().to_string_lossy();
+    if BINARY_EXTENSIONS.contains(&extension.to_ascii_lowercase().as_str()) {
+        return Ok(Text::Skipped("a binary file type".to_string()));
+    }
+    let size = fs::metadata(path)?.len();
+    if size > max {
+        return Ok(Text::Oversized(format!(
+            "{size} bytes, over the {max} byte limit"
+        )));
+    }
+    let mut bytes = Vec::new();
+    File::open(path)?.take(max).read_to_end(&mut bytes)?;
+    if bytes.iter().take(SNIFF_BYTES).any(|&b| b == 0) {
+        return Ok(Text::Skipped("binary content".to_string()));
+    }
+    if bytes.starts_with(BOM) {
+        bytes.drain(..BOM.len());
+    }
+    match String::from_utf8(bytes) {
+        Ok(text) => Ok(Text::Source(text)),
+        Err(e) => Ok(Text::Lossy(
+            String::from_utf8_lossy(e.as_bytes()).into_owned(),
+        )),
+    }
+}
+
+/// The number of lines in the file at `path`, counted as `str::lines`
+/// does, a block at a time rather than with the whole file in memory.
+pub fn count_lines(path: &Path) -> io::Result<usize> {
+    let mut reader = BufReader::with_capacity(64 * 1024, File::open(path)?);
+    let (mut lines, mut last) = (0, b'\n');
+    loop {
+        let block = reader.fill_buf()?;
+        let Some(&end) = block.last() else {
+            break;
+        };
+        lines += block.iter().filter(|&&b| b == b'\n').count();
+        last = end;
+        let len = block.len();
+        reader.consume(len);
+    }
+    Ok(lines + usize::from(last != b'\n'))
+}
+
+/// The passing result `check_name` gives a file it skipped, and why.
+pub fn skipped(check_name: &str, path: &Path, reason: &str) -> CheckResult {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    CheckResult::pass(check_name, &format!("{name}: skipped, {reason}"))
+        .with_file(&path.display().to_string())
+}
+
+/// The warning `check_name` gives a file it checked with invalid UTF-8
+/// replaced.
+pub fn lossy(check_name: &str, path: &Path) -> CheckResult {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let message = format!("{name}: not valid UTF-8; checked with the invalid bytes replaced");
+    CheckResult::fail(check_name, Severity::Warning, &message)
+        .with_file(&path.display().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_skips_binary_files() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        fs::write(dir.join("lib.rs"), "fn main() {}\n").unwrap();
+        fs::write(dir.join("blob.rs"), b"fn\0main").unwrap();
+        fs::write(dir.join("logo.PNG"), "fn main() {}\n").unwrap();
+        fs::write(dir.join("latin1.rs"), b"// caf\xe9\n").unwrap();
+
+        let max = 1024;
+        let source = Text::Source("fn main() {}\n".to_string());
+        assert_eq!(read(&dir.join("lib.rs"), max).unwrap(), source);
+        let reason = |name: &str| match read(&dir.join(name), max).unwrap() {
+            Text::Skipped(reason) => reason,
+            other => panic!("{name} was read as {other:?}"),
+        };
+        assert_eq!(reason("blob.rs"), "binary content");
+        assert_eq!(reason("logo.PNG"), "a binary file type");
+        assert!(read(&dir.join("missing.rs"), max).is_err());
+        let replaced = Text::Lossy("// caf\u{FFFD}\n".to_string());
+        assert_eq!(read(&dir.join("latin1.rs"), max).unwrap(), replaced);
+        fs::write(dir.join("bom.rs"), b"\xEF\xBB\xBFfn main() {}\n").unwrap();
+        assert_eq!(
+            read(&dir.join("bom.rs"), max)
+                .unwrap()
+                .into_source()
+                .unwrap(),
+            "fn main() {}\n"
+        );
+
+        let large = read(&dir.join("lib.rs"), 4).unwrap();
+        let limit = "13 bytes, over the 4 byte limit".to_string();
+        assert_eq!(large, Text::Oversized(limit));
+        let result = skipped("loc-limits", &dir.join("blob.rs"), "binary content");
+        assert!(result.passed);
+        assert_eq!(result.message, "blob.rs: skipped, binary content");
+        for content in ["", "a", "a\n", "a\nb", "a\r\n\nb\n"] {
+            fs::write(dir.join("count.rs"), content).unwrap();
+            let counted = count_lines(&dir.join("count.rs")).unwrap();
+            assert_eq!(counted, content.lines().count(), "{content:?}");
+        }
+        let warning = lossy("loc-limits", &dir.join("latin1.rs"));
+        assert!(!warning.passed);
+        assert_eq!(warning.severity, Severity::Warning);
+    }
+}
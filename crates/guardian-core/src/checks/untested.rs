@@ -0,0 +1,204 @@
+//! Public functions that no test mentions, for `suggest-tests`.
+//!
+//! A function counts as tested when its name appears as a word in test
+//! code: a `#[cfg(test)]` module under `src`, or a file under `tests`. The
+//! match is by name alone, so a test of another function with the same
+//! name hides it; in return, the detection is fast and needs no compiler.
+
+use super::text::{self, Text};
+use super::walk::{self, Scan};
+use std::path::{Path, PathBuf};
+
+/// Lines of a function's source kept, doc comments included.
+const MAX_SOURCE_LINES: usize = 80;
+
+/// Where a file's test module starts.
+const TEST_MODULE: &str = r#"#[cfg(test)]"#;
+
+/// A public function without a test.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UntestedFunction {
+    pub file: PathBuf,
+    /// 1-based line of the `fn`.
+    pub line: usize,
+    pub name: String,
+    /// The function's doc comment and source, cut off after
+    /// [`MAX_SOURCE_LINES`] lines.
+    pub source: String,
+}
+
+/// The public functions under `project_dir/src` that no test mentions, by
+/// file and line, finding and reading the files as `scan` says.
+pub fn untested_functions(project_dir: &Path, scan: &Scan) -> Vec<UntestedFunction> {
+    let mut sources = Vec::new();
+    let mut tests = String::new();
+    for file in walk::files(&project_dir.join("src"), &["rs"], scan) {
+        let Some(content) = text::read(&file, scan.max_file_bytes)
+            .ok()
+            .and_then(Text::into_source)
+        else {
+            continue;
+        };
+        let split = content.find(TEST_MODULE).unwrap_or(content.len());
+        tests.push_str(&content[split..]);
+        sources.push((file, content[..split].to_string()));
+    }
+    for file in walk::files(&project_dir.join("tests"), &["rs"], scan) {
+        if let Some(content) = text::read(&file, scan.max_file_bytes)
+            .ok()
+            .and_then(Text::into_source)
+        {
+            tests.push_str(&content);
+        }
+    }
+
+    let mut untested = Vec::new();
+    for (file, code) in &sources {
+        let lines: Vec<&str> = code.lines().collect();
+        for (i, line) in lines.iter().enumerate() {
+            let Some(name) = public_fn_name(line) else {
+                continue;
+            };
+            if name == "main" || mentions(&tests, name) {
+                continue;
+            }
+            untested.push(UntestedFunction {
+                file: file.clone(),
+                line: i + 1,
+                name: name.to_string(),
+                source: source_of(&lines, i),
+            });
+        }
+    }
+    untested
+}
+
+/// The name of the function `line` declares, if it is `pub`, in any form
+/// such as `pub(crate) async fn`.
+fn public_fn_name(line: &str) -> Option<&str> {
+    let rest = line.trim_start().strip_prefix("pub")?;
+    let rest = match rest.strip_prefix('(') {
+        Some(scoped) => scoped.split_once(')')?.1,
+        None => rest,
+    };
+    let mut words = rest.split_whitespace();
+    loop {
+        match words.next()? {
+            "const" | "async" | "unsafe" => {}
+            "fn" => break,
+            _ => return None,
+        }
+    }
+    let name = words.next()?;
+    let end = name
+        .find(|c: char| !c.is_alphanumeric() && c != '_')
+        .unwrap_or(name.len());
+    (end > 0).then(|| &name[..end])
+}
+
+/// Whether `name` appears in `text` as a whole word.
+fn mentions(text: &str, name: &str) -> bool {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    text.match_indices(name).any(|(at, _)| {
+        let before = text[..at].chars().next_back();
+        let after = text[at + name.len()..].chars().next();
+        !before.is_some_and(is_ident) && !after.is_some_and(is_ident)
+    })
+}
+
+/// The source of the function declared on `lines[start]`: its doc comment
+/// and attributes, then lines until its braces balance.
+fn source_of(lines: &[&str], start: usize) -> String {
+    let mut first = start;
+    while first > 0 {
+        let above = lines[first - 1].trim_start();
+        if !(above.starts_with("///") || above.starts_with("#[")) {
+            break;
+        }
+        first -= 1;
+    }
+    let mut depth = 0_i32;
+    let mut opened = false;
+    let mut end = start;
+    for (i, line) in lines.iter().enumerate().skip(start) {
+        end = i;
+        depth += line.matches('{').count() as i32 - line.matches('}').count() as i32;
+        opened |= line.contains('{');
+        if (opened && depth <= 0) || (!opened && line.trim_end().ends_with(';')) {
+            break;
+        }
+    }
+    let end = (end + 1).min(first + MAX_SOURCE_LINES);
+    let mut source = lines[first..end].join("\n");
+    source.push('\n');
+    source
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_public_fn_name() {
+        assert_eq!(public_fn_name("pub fn parse(s: &str) {"), Some("parse"));
+        assert_eq!(
+            public_fn_name("    pub(crate) async fn load<T>() {"),
+            Some("load")
+        );
+        assert_eq!(public_fn_name("pub const fn max() -> u8 {"), Some("max"));
+        assert_eq!(public_fn_name("fn private() {"), None);
+        assert_eq!(public_fn_name("pub struct Fn;"), None);
+        assert!(mentions("let x = parse(\"a\");", "parse"));
+        assert!(!mentions("let x = parse_all(\"a\");", "parse"));
+    }
+
+    #[test]
+    fn test_untested_functions() {
+        let dir = TempDir::new().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::create_dir_all(dir.path().join("tests")).unwrap();
+        let lib = r#"/// Parse it.
+pub fn parse(s: &str) -> u8 {
+    if s.is_empty() {
+        return 0;
+    }
+    1
+}
+
+pub fn load() {}
+
+fn helper() {}
+
+pub fn save() {}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_load() {
+        super::load();
+    }
+}
+"#;
+        fs::write(src.join("lib.rs"), lib).unwrap();
+        fs::write(
+            dir.path().join("tests/io.rs"),
+            "#[test]\nfn saves() { app::save(); }\n",
+        )
+        .unwrap();
+
+        let untested = untested_functions(dir.path(), &Scan::default());
+        assert_eq!(untested.len(), 1);
+        assert_eq!(untested[0].name, "parse");
+        assert_eq!(untested[0].line, 2);
+        assert_eq!(untested[0].file, src.join("lib.rs"));
+        assert!(
+            untested[0]
+                .source
+                .starts_with("/// Parse it.\npub fn parse")
+        );
+        assert!(untested[0].source.ends_with("    1\n}\n"));
+    }
+}
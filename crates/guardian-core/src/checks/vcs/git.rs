@@ -0,0 +1,145 @@
+//! Git queries.
+
+use super::{Range, run};
+use crate::checks::blame::Blame;
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// `git log` arguments selecting the commits in `range`.
+fn revisions(range: Range<'_>) -> Vec<String> {
+    match range.base {
+        Some(base) => vec![format!("{base}..{}", range.head)],
+        None => vec![range.head.to_string(), "--not".into(), "--remotes".into()],
+    }
+}
+
+/// Run `git log` in `dir` with `args`, then the revisions of `range`.
+fn log(dir: &Path, args: &[&str], range: Range<'_>) -> Result<String> {
+    let revisions = revisions(range);
+    let mut args = [&["log"], args].concat();
+    args.extend(revisions.iter().map(String::as_str));
+    run("git", dir, &args)
+}
+
+pub(super) fn commits(dir: &Path, range: Range<'_>) -> Result<String> {
+    log(dir, &["--reverse", "--format=%h%x00%B%x00"], range)
+}
+
+pub(super) fn changed_files(dir: &Path, range: Range<'_>) -> Result<String> {
+    log(dir, &["--name-only", "--relative", "--format="], range)
+}
+
+pub(super) fn patches(dir: &Path, range: Range<'_>) -> Result<String> {
+    let args = ["--reverse", "-p", "--relative", "--format=commit %h %s"];
+    log(dir, &args, range)
+}
+
+pub(super) fn blame(dir: &Path, file: &Path, lines: &[usize]) -> Result<HashMap<usize, Blame>> {
+    let file = file.to_string_lossy();
+    let ranges: Vec<String> = lines
+        .iter()
+        .map(|line| format!("-L{line},{line}"))
+        .collect();
+    let mut args = vec!["blame", "--porcelain"];
+    args.extend(ranges.iter().map(String::as_str));
+    args.extend(["--", &file]);
+    Ok(parse_porcelain(&run("git", dir, &args)?))
+}
+
+/// Read `git blame --porcelain` output. Commit details follow only the first
+/// line from each commit, so they're collected by hash first.
+fn parse_porcelain(output: &str) -> HashMap<usize, Blame> {
+    let mut line_commits: Vec<(usize, &str)> = Vec::new();
+    let mut details: HashMap<&str, HashMap<&str, &str>> = HashMap::new();
+    let mut current = None;
+    for line in output.lines().filter(|l| !l.starts_with('\t')) {
+        let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+        let is_hash = key.len() >= 40 && key.bytes().all(|b| b.is_ascii_hexdigit());
+        if is_hash {
+            // <hash> <original line> <final line> [<lines in group>]
+            if let Some(Ok(final_line)) = value.split(' ').nth(1).map(str::parse) {
+                line_commits.push((final_line, key));
+            }
+            current = Some(key);
+        } else if let Some(commit) = current {
+            details.entry(commit).or_default().insert(key, value);
+        }
+    }
+
+    let mut blames = HashMap::new();
+    for (line, commit) in line_commits {
+        // Lines changed in the working tree blame to an all-zero hash.
+        if commit.bytes().all(|b| b == b'0') {
+            continue;
+        }
+        let Some(info) = details.get(commit) else {
+            continue;
+        };
+        let field = |name: &str| info.get(name).copied().unwrap_or_default();
+        let blame = Blame {
+            commit: commit.to_string(),
+            author: field("author").to_string(),
+            email: field("author-mail").trim_matches(['<', '>']).to_string(),
+            time: field("author-time").parse().unwrap_or(0),
+            summary: field("summary").to_string(),
+        };
+        blames.insert(line, blame);
+    }
+    blames
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PORCELAIN: &str = "\
+1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b 3 3 1
+author Jane Doe
+author-mail <jane@example.com>
+author-time 1760000000
+author-tz +0000
+committer Jane Doe
+summary Add the parser
+filename src/lib.rs
+\tfn parse() {}
+1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b 9 12 1
+filename src/lib.rs
+\tparse();
+0000000000000000000000000000000000000000 20 20 1
+author Not Committed Yet
+author-mail <not.committed.yet>
+summary Version of src/lib.rs from src/lib.rs
+filename src/lib.rs
+\tlet x = 1;
+";
+
+    #[test]
+    fn test_parse_porcelain() {
+        let blames = parse_porcelain(PORCELAIN);
+        assert_eq!(blames.len(), 2);
+        let blame = &blames[&3];
+        assert_eq!(blame.commit, "1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b");
+        assert_eq!(blame.author, "Jane Doe");
+        assert_eq!(blame.email, "jane@example.com");
+        assert_eq!(blame.time, 1_760_000_000);
+        assert_eq!(blame.summary, "Add the parser");
+        // Later lines of the same commit reuse its details.
+        assert_eq!(&blames[&12], blame);
+        assert!(!blames.contains_key(&20));
+    }
+
+    #[test]
+    fn test_revisions() {
+        let range = Range {
+            base: Some("origin/main"),
+            head: "HEAD",
+        };
+        assert_eq!(revisions(range), ["origin/main..HEAD"]);
+        let range = Range {
+            base: None,
+            head: "abc123",
+        };
+        assert_eq!(revisions(range), ["abc123", "--not", "--remotes"]);
+    }
+}
@@ -0,0 +1,119 @@
+//! Jujutsu (jj) queries.
+//!
+//! Output is shaped with templates, so it matches what the git queries
+//! return. Commits are named by change id, the id jj users work with.
+
+use super::{Range, run};
+use crate::checks::blame::Blame;
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// One tab-separated line per annotated line: commit, line number, whether
+/// the commit is the working copy, author, email, time, and subject.
+const ANNOTATE_TEMPLATE: &str = r#"commit.commit_id() ++ "\t" ++ line_number ++ "\t"
+    ++ commit.current_working_copy() ++ "\t" ++ commit.author().name() ++ "\t"
+    ++ commit.author().email() ++ "\t" ++ commit.author().timestamp().format("%s") ++ "\t"
+    ++ commit.description().first_line() ++ "\n""#;
+
+/// The revset selecting the commits in `range`.
+fn revset(range: Range<'_>) -> String {
+    let base = range.base.unwrap_or("remote_bookmarks()");
+    format!("{base}..{}", range.head)
+}
+
+/// Run `jj log` in `dir` on the commits of `range`, oldest first.
+fn log(dir: &Path, args: &[&str], range: Range<'_>) -> Result<String> {
+    let revset = revset(range);
+    let head = ["log", "--no-graph", "--reversed", "-r", &revset];
+    run("jj", dir, &[&head, args].concat())
+}
+
+pub(super) fn commits(dir: &Path, range: Range<'_>) -> Result<String> {
+    let template = r#"change_id.short() ++ "\0" ++ description ++ "\0""#;
+    log(dir, &["-T", template], range)
+}
+
+pub(super) fn changed_files(dir: &Path, range: Range<'_>) -> Result<String> {
+    run("jj", dir, &["diff", "--name-only", "-r", &revset(range)])
+}
+
+pub(super) fn patches(dir: &Path, range: Range<'_>) -> Result<String> {
+    let template = r#""commit " ++ change_id.short() ++ " " ++ description.first_line() ++ "\n""#;
+    log(dir, &["--git", "-T", template], range)
+}
+
+pub(super) fn blame(dir: &Path, file: &Path, lines: &[usize]) -> Result<HashMap<usize, Blame>> {
+    let file = file.to_string_lossy();
+    let output = run(
+        "jj",
+        dir,
+        &["file", "annotate", "-T", ANNOTATE_TEMPLATE, &file],
+    )?;
+    let mut blames = parse_annotate(&output);
+    blames.retain(|line, _| lines.contains(line));
+    Ok(blames)
+}
+
+/// Read `jj file annotate` output in [`ANNOTATE_TEMPLATE`]'s shape. Lines of
+/// the working-copy commit aren't committed yet, so they're left out.
+fn parse_annotate(output: &str) -> HashMap<usize, Blame> {
+    let mut blames = HashMap::new();
+    for line in output.lines() {
+        let fields: Vec<&str> = line.splitn(7, '\t').collect();
+        let [commit, number, working_copy, author, email, time, summary] = fields[..] else {
+            continue;
+        };
+        let Ok(number) = number.parse() else {
+            continue;
+        };
+        if working_copy == "true" {
+            continue;
+        }
+        let blame = Blame {
+            commit: commit.to_string(),
+            author: author.to_string(),
+            email: email.to_string(),
+            time: time.parse().unwrap_or(0),
+            summary: summary.to_string(),
+        };
+        blames.insert(number, blame);
+    }
+    blames
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_revset() {
+        let range = Range {
+            base: Some("main"),
+            head: "@",
+        };
+        assert_eq!(revset(range), "main..@");
+        let range = Range {
+            base: None,
+            head: "abc123",
+        };
+        assert_eq!(revset(range), "remote_bookmarks()..abc123");
+    }
+
+    #[test]
+    fn test_parse_annotate() {
+        let output = "\
+9f8e7d6c5b4a39281706f5e4d3c2b1a098765432\t1\tfalse\tJane Doe\tjane@example.com\t1760000000\tAdd the parser
+9f8e7d6c5b4a39281706f5e4d3c2b1a098765432\t2\tfalse\tJane Doe\tjane@example.com\t1760000000\tAdd the parser
+0123456789abcdef0123456789abcdef01234567\t3\ttrue\tJane Doe\tjane@example.com\t1760000500\t
+";
+        let blames = parse_annotate(output);
+        assert_eq!(blames.len(), 2);
+        let blame = &blames[&2];
+        assert_eq!(blame.commit, "9f8e7d6c5b4a39281706f5e4d3c2b1a098765432");
+        assert_eq!(blame.email, "jane@example.com");
+        assert_eq!(blame.time, 1_760_000_000);
+        assert_eq!(blame.summary, "Add the parser");
+        assert!(!blames.contains_key(&3));
+    }
+}
@@ -0,0 +1,192 @@
+//! Version control queries, for git and Jujutsu (jj) repositories.
+//!
+//! `pre-push`, `evaluate --staged`, and blame attribution ask the repository
+//! for commits, changed files, patches, staged changes, and line history;
+//! `repair` tries its fixes in a [`Worktree`]. [`Vcs::detect`] picks the tool by the
+//! repository's metadata directory; a jj repository colocated with git uses
+//! jj, since that's what its users commit with.
+
+mod git;
+mod jj;
+mod staged;
+mod worktree;
+
+pub use worktree::Worktree;
+
+use crate::checks::blame::Blame;
+use anyhow::{Context, Result, bail};
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The version control tool a project uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Vcs {
+    Git,
+    Jujutsu,
+}
+
+/// The commits a push sends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Range<'a> {
+    /// The revision the remote already has, or `None` for the commits no
+    /// remote has yet.
+    pub base: Option<&'a str>,
+    pub head: &'a str,
+}
+
+/// A commit in a [`Range`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Commit {
+    /// Abbreviated commit hash, or change id under jj.
+    pub id: String,
+    pub message: String,
+}
+
+impl Vcs {
+    /// The tool managing `dir`: the nearest ancestor with a `.jj` or `.git`
+    /// directory decides. Outside any repository this is git, so errors read
+    /// as they always have.
+    pub fn detect(dir: &Path) -> Self {
+        let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+        for ancestor in dir.ancestors() {
+            if ancestor.join(".jj").is_dir() {
+                return Self::Jujutsu;
+            }
+            if ancestor.join(".git").exists() {
+                return Self::Git;
+            }
+        }
+        Self::Git
+    }
+
+    /// Name of the tool's command.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Git => "git",
+            Self::Jujutsu => "jj",
+        }
+    }
+
+    /// The commits in `range`, oldest first.
+    pub fn commits(self, dir: &Path, range: Range<'_>) -> Result<Vec<Commit>> {
+        // Both tools print each commit as `<id>\0<message>\0`.
+        let output = match self {
+            Self::Git => git::commits(dir, range)?,
+            Self::Jujutsu => jj::commits(dir, range)?,
+        };
+        let fields: Vec<&str> = output.split('\0').collect();
+        Ok(fields
+            .chunks_exact(2)
+            .map(|commit| Commit {
+                id: commit[0].trim().to_string(),
+                message: commit[1].to_string(),
+            })
+            .collect())
+    }
+
+    /// Files the commits in `range` change, relative to `dir`.
+    pub fn changed_files(self, dir: &Path, range: Range<'_>) -> Result<BTreeSet<PathBuf>> {
+        let output = match self {
+            Self::Git => git::changed_files(dir, range)?,
+            Self::Jujutsu => jj::changed_files(dir, range)?,
+        };
+        Ok(output
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(PathBuf::from)
+            .collect())
+    }
+
+    /// The patches of the commits in `range`, oldest first, each headed
+    /// `commit <id> <subject>`.
+    pub fn patches(self, dir: &Path, range: Range<'_>) -> Result<String> {
+        match self {
+            Self::Git => git::patches(dir, range),
+            Self::Jujutsu => jj::patches(dir, range),
+        }
+    }
+
+    /// The commits that last changed `lines` of `file`, by line number.
+    /// Lines nobody has committed yet are left out.
+    pub fn blame(self, file: &Path, lines: &[usize]) -> Result<HashMap<usize, Blame>> {
+        let (Some(dir), Some(name)) = (file.parent(), file.file_name()) else {
+            bail!("not a file path");
+        };
+        let dir = if dir.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            dir
+        };
+        let name = Path::new(name);
+        match self {
+            Self::Git => git::blame(dir, name, lines),
+            Self::Jujutsu => jj::blame(dir, name, lines),
+        }
+    }
+}
+
+/// Run `program` in `dir` with `args` and return its stdout.
+fn run(program: &str, dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new(program)
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .with_context(|| format!("Failed to run {program}"))?;
+    if !output.status.success() {
+        bail!(
+            "{program} {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        let temp = tempfile::tempdir().unwrap();
+        let src = temp.path().join("src");
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::create_dir(temp.path().join(".git")).unwrap();
+        assert_eq!(Vcs::detect(&src), Vcs::Git);
+        // Colocated: jj wins.
+        std::fs::create_dir(temp.path().join(".jj")).unwrap();
+        assert_eq!(Vcs::detect(&src), Vcs::Jujutsu);
+        assert_eq!(Vcs::detect(&src).name(), "jj");
+    }
+
+    #[test]
+    fn test_git_commits_and_files() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        let git = |args: &[&str]| run("git", dir, args).unwrap();
+        git(&["init", "-q"]);
+        std::fs::write(dir.join("a.rs"), "fn a() {}\n").unwrap();
+        git(&["add", "a.rs"]);
+        git(&[
+            "-c",
+            "user.name=Jane Doe",
+            "-c",
+            "user.email=jane@example.com",
+            "commit",
+            "-qm",
+            "Add a\n\nWith a body.",
+        ]);
+        let range = Range {
+            base: None,
+            head: "HEAD",
+        };
+        let commits = Vcs::Git.commits(dir, range).unwrap();
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].message.trim_end(), "Add a\n\nWith a body.");
+        let changed = Vcs::Git.changed_files(dir, range).unwrap();
+        assert_eq!(changed, BTreeSet::from([PathBuf::from("a.rs")]));
+        let blames = Vcs::Git.blame(&dir.join("a.rs"), &[1]).unwrap();
+        assert_eq!(blames[&1].author, "Jane Doe");
+    }
+}
@@ -0,0 +1,108 @@
+//! Staged and uncommitted changes: what the next commit would contain.
+//!
+//! jj has no staging area; there, the working-copy change `@` is what the
+//! next commit is, and all that is uncommitted.
+
+use super::{Vcs, run};
+use anyhow::Result;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+impl Vcs {
+    /// Files the staged changes touch, relative to `dir`.
+    pub fn staged_files(self, dir: &Path) -> Result<BTreeSet<PathBuf>> {
+        let output = match self {
+            Self::Git => run(
+                "git",
+                dir,
+                &["diff", "--cached", "--name-only", "--relative"],
+            )?,
+            Self::Jujutsu => run("jj", dir, &["diff", "--name-only", "-r", "@"])?,
+        };
+        Ok(paths(&output))
+    }
+
+    /// Files changed since the last commit, staged or not, and new files
+    /// git doesn't ignore, relative to `dir`.
+    pub fn uncommitted_files(self, dir: &Path) -> Result<BTreeSet<PathBuf>> {
+        match self {
+            Self::Git => {
+                let mut output = run("git", dir, &["diff", "HEAD", "--name-only", "--relative"])?;
+                output.push_str(&run(
+                    "git",
+                    dir,
+                    &["ls-files", "--others", "--exclude-standard"],
+                )?);
+                Ok(paths(&output))
+            }
+            Self::Jujutsu => self.staged_files(dir),
+        }
+    }
+
+    /// The staged changes as a git-style diff.
+    pub fn staged_diff(self, dir: &Path) -> Result<String> {
+        match self {
+            Self::Git => run("git", dir, &["diff", "--cached", "--relative"]),
+            Self::Jujutsu => run("jj", dir, &["diff", "--git", "-r", "@"]),
+        }
+    }
+}
+
+/// One path per non-empty line of `output`.
+fn paths(output: &str) -> BTreeSet<PathBuf> {
+    output
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(PathBuf::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_git_staged_changes() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        let git = |args: &[&str]| run("git", dir, args).unwrap();
+        git(&["init", "-q"]);
+        std::fs::write(dir.join("a.rs"), "fn a() {}\n").unwrap();
+        std::fs::write(dir.join("b.rs"), "fn b() {}\n").unwrap();
+        assert!(Vcs::Git.staged_files(dir).unwrap().is_empty());
+
+        git(&["add", "a.rs"]);
+        let staged = Vcs::Git.staged_files(dir).unwrap();
+        assert_eq!(staged, BTreeSet::from([PathBuf::from("a.rs")]));
+        let diff = Vcs::Git.staged_diff(dir).unwrap();
+        assert!(diff.starts_with("diff --git a/a.rs b/a.rs\n"));
+        assert!(diff.contains("+fn a() {}\n"));
+        assert!(!diff.contains("b.rs"));
+    }
+
+    #[test]
+    fn test_git_uncommitted_files() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        let git = |args: &[&str]| run("git", dir, args).unwrap();
+        git(&["init", "-q"]);
+        git(&["config", "user.email", "dev@example.com"]);
+        git(&["config", "user.name", "Dev"]);
+        for name in ["a.rs", "b.rs", "c.rs"] {
+            std::fs::write(dir.join(name), "fn f() {}\n").unwrap();
+        }
+        git(&["add", "a.rs", "b.rs"]);
+        git(&["commit", "-q", "-m", "Add a and b"]);
+        assert_eq!(
+            Vcs::Git.uncommitted_files(dir).unwrap(),
+            BTreeSet::from([PathBuf::from("c.rs")])
+        );
+
+        std::fs::write(dir.join("a.rs"), "fn g() {}\n").unwrap();
+        std::fs::write(dir.join("b.rs"), "fn g() {}\n").unwrap();
+        git(&["add", "b.rs"]);
+        let changed = Vcs::Git.uncommitted_files(dir).unwrap();
+        let expected = ["a.rs", "b.rs", "c.rs"].map(PathBuf::from);
+        assert_eq!(changed, BTreeSet::from(expected));
+    }
+}
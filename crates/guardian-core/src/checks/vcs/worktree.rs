@@ -0,0 +1,130 @@
+//! A throwaway git worktree, for trying changes out of the user's way.
+//!
+//! `repair` edits a copy of the project, re-running the checks after each
+//! fix, and only touches the real working tree once a person approves the
+//! result. The copy is a worktree of `HEAD` with the uncommitted changes to
+//! tracked files applied and staged, so [`Worktree::diff`] shows just what
+//! was changed in it since. Untracked files aren't copied.
+
+use super::run;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A temporary worktree of a project's repository, removed on drop.
+#[derive(Debug)]
+pub struct Worktree {
+    /// Where the worktree is checked out.
+    root: PathBuf,
+    /// The repository it belongs to, for removing it.
+    repo: PathBuf,
+    /// The project's directory within the repository.
+    prefix: PathBuf,
+}
+
+impl Worktree {
+    /// Check out the repository holding `project_dir` into a temporary
+    /// worktree, as it is in the working tree.
+    pub fn create(project_dir: &Path) -> Result<Self> {
+        let repo = run("git", project_dir, &["rev-parse", "--show-toplevel"])
+            .context("repair needs a git repository")?;
+        let repo = PathBuf::from(repo.trim());
+        let prefix = run("git", project_dir, &["rev-parse", "--show-prefix"])?;
+        let stamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos());
+        let root = std::env::temp_dir().join(format!("guardian-worktree-{stamp}"));
+        let path = root.to_string_lossy();
+        run(
+            "git",
+            &repo,
+            &["worktree", "add", "--detach", &path, "HEAD"],
+        )?;
+        let worktree = Self {
+            root,
+            repo,
+            prefix: PathBuf::from(prefix.trim()),
+        };
+
+        let changes = run("git", &worktree.repo, &["diff", "HEAD", "--binary"])?;
+        if !changes.is_empty() {
+            let patch = worktree.root.with_extension("patch");
+            fs::write(&patch, changes).context("Failed to copy the uncommitted changes")?;
+            let applied = run(
+                "git",
+                &worktree.root,
+                &["apply", "--index", &patch.to_string_lossy()],
+            );
+            let _ = fs::remove_file(&patch);
+            applied?;
+        }
+        Ok(worktree)
+    }
+
+    /// The project's directory in the worktree.
+    pub fn project_dir(&self) -> PathBuf {
+        self.root.join(&self.prefix)
+    }
+
+    /// The project's directory relative to the top of the repository, which
+    /// is where diff paths start.
+    pub fn prefix(&self) -> &Path {
+        &self.prefix
+    }
+
+    /// What was changed in the worktree since it was created, as a diff
+    /// with paths from the top of the repository.
+    pub fn diff(&self) -> Result<String> {
+        run("git", &self.root, &["diff", "--binary"])
+    }
+}
+
+impl Drop for Worktree {
+    fn drop(&mut self) {
+        let path = self.root.to_string_lossy();
+        let removed = run("git", &self.repo, &["worktree", "remove", "--force", &path]);
+        if let Err(e) = removed {
+            tracing::warn!("Couldn't remove the worktree at {path}: {e:#}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_worktree_copies_uncommitted_changes() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        let git = |args: &[&str]| run("git", dir, args).unwrap();
+        git(&["init", "-q"]);
+        git(&["config", "user.email", "dev@example.com"]);
+        git(&["config", "user.name", "Dev"]);
+        fs::create_dir(dir.join("app")).unwrap();
+        fs::write(dir.join("app/lib.rs"), "fn a() {}\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-q", "-m", "Add lib"]);
+        fs::write(dir.join("app/lib.rs"), "fn b() {}\n").unwrap();
+
+        let worktree = Worktree::create(&dir.join("app")).unwrap();
+        assert_eq!(worktree.prefix(), Path::new("app/"));
+        let lib = worktree.project_dir().join("lib.rs");
+        assert_eq!(fs::read_to_string(&lib).unwrap(), "fn b() {}\n");
+        assert_eq!(worktree.diff().unwrap(), "");
+
+        fs::write(&lib, "fn c() {}\n").unwrap();
+        let diff = worktree.diff().unwrap();
+        assert!(diff.starts_with("diff --git a/app/lib.rs b/app/lib.rs\n"));
+        assert!(diff.contains("-fn b() {}\n+fn c() {}\n"));
+
+        let root = worktree.root.clone();
+        drop(worktree);
+        assert!(!root.exists());
+        assert_eq!(
+            fs::read_to_string(dir.join("app/lib.rs")).unwrap(),
+            "fn b() {}\n"
+        );
+    }
+}
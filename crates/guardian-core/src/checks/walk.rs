@@ -0,0 +1,209 @@
+//! Finding the files to check.
+//!
+//! On a monorepo with 100k files, a recursive `read_dir` on one thread is
+//! most of a check's run time. [`files`] and [`project_files`] walk with the
+//! `ignore` crate's parallel walker instead, then sort what they find, so
+//! results come out in the same order on every run. Ignore files aren't
+//! consulted.
+//!
+//! Symlinks are skipped unless the [`Scan`] says to follow them
+//! (`--follow-symlinks`); a followed link back to an ancestor directory is
+//! reported and not followed again. Walks stop `max_depth` directories
+//! down, saying where. Each such warning is given once per [`Scan`], however
+//! many of its walks come across the path.
+
+use crate::config::{CheckSettings, jobs};
+use ignore::{DirEntry, Error, WalkBuilder, WalkState};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// How the checks find and read files: whether walks follow symlinks, how
+/// deep they go, the largest file read, and how many threads do it.
+#[derive(Debug, Clone)]
+pub struct Scan {
+    pub follow_symlinks: bool,
+    pub max_depth: usize,
+    /// Files larger than this are skipped; see [`super::text::read`].
+    pub max_file_bytes: u64,
+    /// Threads walking and reading files, one per core unless `--jobs` says.
+    pub jobs: usize,
+    /// Paths already warned about, shared with clones, so each check's walk
+    /// in a run doesn't repeat a warning.
+    warned: Arc<Mutex<BTreeSet<PathBuf>>>,
+}
+
+impl Default for Scan {
+    fn default() -> Self {
+        Self::from(&CheckSettings::default())
+    }
+}
+
+impl From<&CheckSettings> for Scan {
+    fn from(settings: &CheckSettings) -> Self {
+        Self {
+            follow_symlinks: settings.follow_symlinks,
+            max_depth: settings.max_depth,
+            max_file_bytes: settings.max_file_bytes,
+            jobs: jobs(settings.jobs),
+            warned: Arc::default(),
+        }
+    }
+}
+
+/// The files under `dir` with one of `extensions`, sorted.
+pub fn files(dir: &Path, extensions: &[&str], scan: &Scan) -> Vec<PathBuf> {
+    walk(dir, extensions, scan, |_| true)
+}
+
+/// The files under `dir` with one of `extensions`, sorted, leaving out
+/// `target` and hidden directories.
+pub fn project_files(dir: &Path, extensions: &[&str], scan: &Scan) -> Vec<PathBuf> {
+    walk(dir, extensions, scan, |entry| {
+        let name = entry.file_name().to_string_lossy();
+        entry.depth() == 0
+            || !entry.file_type().is_some_and(|t| t.is_dir())
+            || (name != "target" && !name.starts_with('.'))
+    })
+}
+
+/// The files under `dir` with one of `extensions`, sorted, descending only
+/// into the directories `keep` accepts.
+fn walk(
+    dir: &Path,
+    extensions: &[&str],
+    scan: &Scan,
+    keep: impl Fn(&DirEntry) -> bool + Send + Sync + 'static,
+) -> Vec<PathBuf> {
+    let found = Mutex::new(Vec::new());
+    WalkBuilder::new(dir)
+        .standard_filters(false)
+        .follow_links(scan.follow_symlinks)
+        .max_depth(Some(scan.max_depth))
+        .threads(scan.jobs)
+        .filter_entry(keep)
+        .build_parallel()
+        .run(|| {
+            let found = &found;
+            Box::new(move |entry| {
+                let entry = match entry {
+                    Ok(entry) => entry,
+                    Err(err) => {
+                        let mut err = &err;
+                        while let Error::WithPath { err: inner, .. }
+                        | Error::WithDepth { err: inner, .. } = err
+                        {
+                            err = inner;
+                        }
+                        if let Error::Loop { child, .. } = err {
+                            scan.warn_once(child, || {
+                                format!("Not following {}: it links back to a directory above it", child.display())
+                            });
+                        }
+                        return WalkState::Continue;
+                    }
+                };
+                let is_dir = entry.file_type().is_some_and(|t| t.is_dir());
+                if is_dir && entry.depth() == scan.max_depth && entry.depth() > 0 {
+                    scan.warn_once(entry.path(), || {
+                        format!(
+                            "Not looking inside {}: it is {} directories down, the most [check] max_depth allows",
+                            entry.path().display(),
+                            scan.max_depth
+                        )
+                    });
+                }
+                let wanted = entry.file_type().is_some_and(|t| t.is_file())
+                    && entry
+                        .path()
+                        .extension()
+                        .is_some_and(|e| extensions.iter().any(|x| e == *x));
+                if wanted {
+                    if let Ok(mut found) = found.lock() {
+                        found.push(entry.into_path());
+                    }
+                }
+                WalkState::Continue
+            })
+        });
+    let mut files = found.into_inner().unwrap_or_default();
+    files.sort();
+    files
+}
+
+impl Scan {
+    /// Warn with `message` unless `path` was warned about already.
+    fn warn_once(&self, path: &Path, message: impl FnOnce() -> String) {
+        let new = self
+            .warned
+            .lock()
+            .map(|mut warned| warned.insert(path.to_path_buf()))
+            .unwrap_or(true);
+        if new {
+            tracing::warn!("{}", message());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_files_are_sorted_and_filtered() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        for path in [
+            "b/z.rs",
+            "b/a.rs",
+            "a.rs",
+            "target/x.rs",
+            ".git/y.rs",
+            "c.md",
+        ] {
+            let path = dir.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+        let relative = |files: Vec<PathBuf>| -> Vec<String> {
+            files
+                .iter()
+                .map(|f| f.strip_prefix(dir).unwrap().display().to_string())
+                .collect()
+        };
+
+        let scan = Scan::default();
+        let all = relative(files(dir, &["rs"], &scan));
+        assert_eq!(
+            all,
+            [".git/y.rs", "a.rs", "b/a.rs", "b/z.rs", "target/x.rs"]
+        );
+        let project = relative(project_files(dir, &["rs", "md"], &scan));
+        assert_eq!(project, ["a.rs", "b/a.rs", "b/z.rs", "c.md"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlinks_and_depth() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        fs::create_dir_all(dir.join("a/b/c")).unwrap();
+        fs::write(dir.join("a/b/c/deep.rs"), "").unwrap();
+        fs::write(dir.join("lib.rs"), "").unwrap();
+        std::os::unix::fs::symlink(dir.join("lib.rs"), dir.join("a/linked.rs")).unwrap();
+        std::os::unix::fs::symlink(dir, dir.join("a/loop")).unwrap();
+        let count = |follow_symlinks, max_depth| {
+            let scan = Scan {
+                follow_symlinks,
+                max_depth,
+                ..Scan::default()
+            };
+            files(dir, &["rs"], &scan).len()
+        };
+
+        assert_eq!(count(false, 64), 2);
+        assert_eq!(count(true, 64), 3);
+        assert_eq!(count(false, 3), 1);
+    }
+}
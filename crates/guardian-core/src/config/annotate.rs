@@ -0,0 +1,134 @@
+//! The `[annotate]` configuration section: forges `annotate` posts to.
+//!
+//! ```toml
+//! [annotate.gitea]
+//! base_url = "https://git.example.com"
+//! token_keyring = "forgejo"
+//! ```
+//!
+//! Only the user config and the team policy can set the instance and its
+//! token, so a repository being checked can't have its CI's token sent to
+//! a server of its choosing.
+
+use super::SecretSource;
+use anyhow::{Result, bail};
+use serde::Deserialize;
+
+/// Settings for `guardian-cli annotate`.
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct AnnotateSettings {
+    /// Gitea or Forgejo instance for `annotate --gitea`.
+    #[serde(default)]
+    pub gitea: GiteaSettings,
+}
+
+/// A Gitea or Forgejo instance. The token is read from an environment
+/// variable or the OS keyring, like host credentials.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct GiteaSettings {
+    /// Web URL of the instance; the CI job's server URL when unset.
+    pub base_url: Option<String>,
+
+    /// Environment variable holding the API token.
+    pub token_env: Option<String>,
+
+    /// Name of a `set-secret` secret holding the API token.
+    pub token_keyring: Option<String>,
+
+    /// Name of the commit status guardian sets.
+    #[serde(default = "default_context")]
+    pub context: String,
+}
+
+impl Default for GiteaSettings {
+    fn default() -> Self {
+        Self {
+            base_url: None,
+            token_env: None,
+            token_keyring: None,
+            context: default_context(),
+        }
+    }
+}
+
+impl GiteaSettings {
+    /// Where the API token comes from: `GITEA_TOKEN` unless configured.
+    pub fn token_source(&self) -> Result<SecretSource<'_>> {
+        match (&self.token_env, &self.token_keyring) {
+            (Some(var), None) => Ok(SecretSource::Env(var)),
+            (None, Some(name)) => Ok(SecretSource::Keyring(name)),
+            (None, None) => Ok(SecretSource::Env("GITEA_TOKEN")),
+            (Some(_), Some(_)) => {
+                bail!("[annotate.gitea] takes one of token_env and token_keyring, not both")
+            }
+        }
+    }
+}
+
+fn default_context() -> String {
+    "guardian".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::{GuardianConfig, SecretSource};
+
+    #[test]
+    fn test_parse_gitea() {
+        let cfg: GuardianConfig = toml::from_str("").unwrap();
+        let gitea = &cfg.annotate.gitea;
+        assert_eq!(gitea.context, "guardian");
+        assert_eq!(
+            gitea.token_source().unwrap(),
+            SecretSource::Env("GITEA_TOKEN")
+        );
+
+        let toml = "[annotate.gitea]\nbase_url = \"https://git.example.com\"\ntoken_keyring = \"forgejo\"\n";
+        let cfg: GuardianConfig = toml::from_str(toml).unwrap();
+        let gitea = &cfg.annotate.gitea;
+        assert_eq!(gitea.base_url.as_deref(), Some("https://git.example.com"));
+        assert_eq!(
+            gitea.token_source().unwrap(),
+            SecretSource::Keyring("forgejo")
+        );
+
+        let toml = "[annotate.gitea]\ntoken_env = \"A\"\ntoken_keyring = \"b\"\n";
+        let cfg: GuardianConfig = toml::from_str(toml).unwrap();
+        assert!(cfg.annotate.gitea.token_source().is_err());
+    }
+
+    #[test]
+    fn test_project_cannot_set_gitea_credentials() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::create_dir(temp.path().join(".git")).unwrap();
+        let (user, project) = (
+            temp.path().join("user.toml"),
+            temp.path().join(".guardian.toml"),
+        );
+        let gitea = "[annotate.gitea]\nbase_url = \"https://git.example.com\"\n";
+        std::fs::write(&user, gitea).unwrap();
+        std::fs::write(&project, "[annotate.gitea]\ncontext = \"lint\"\n").unwrap();
+        let load = || GuardianConfig::load(Some(&user), Some(temp.path()), None, None);
+        let cfg = load().unwrap();
+        assert_eq!(cfg.annotate.gitea.context, "lint");
+        assert_eq!(
+            cfg.annotate.gitea.base_url.as_deref(),
+            Some("https://git.example.com")
+        );
+
+        std::fs::write(&project, gitea).unwrap();
+        let err = load().unwrap_err().to_string();
+        assert!(err.contains("defines [annotate.gitea] base_url"), "{err}");
+
+        let token = "[profile.ci.annotate.gitea]\ntoken_env = \"CI_TOKEN\"\n";
+        std::fs::write(temp.path().join("base.toml"), token).unwrap();
+        std::fs::write(&project, "extends = [\"base.toml\"]\n").unwrap();
+        let err = load().unwrap_err().to_string();
+        assert!(
+            err.contains("base.toml defines [annotate.gitea] token_env"),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,91 @@
+//! The `[check.architecture]` table: the structure the `architecture` check
+//! enforces.
+//!
+//! Names are top-level modules (such as `commands` or `checks`) or
+//! workspace crates (such as `guardian-core`).
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer};
+use std::collections::BTreeMap;
+
+/// Declared layers and allowed dependencies.
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ArchitectureRules {
+    /// Layers from the top down, each one or more names. A layer may depend
+    /// on the layers below it, never on those above.
+    #[serde(default, deserialize_with = "layers")]
+    pub layers: Vec<Vec<String>>,
+
+    /// The only names each listed module or crate may depend on.
+    #[serde(default)]
+    pub allowed: BTreeMap<String, Vec<String>>,
+}
+
+impl ArchitectureRules {
+    /// Whether any rule is declared.
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty() && self.allowed.is_empty()
+    }
+}
+
+/// A layer as written: one name, or several side by side.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Layer {
+    One(String),
+    Many(Vec<String>),
+}
+
+fn layers<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Vec<String>>, D::Error> {
+    let layers: Vec<Vec<String>> = Vec::<Layer>::deserialize(deserializer)?
+        .into_iter()
+        .map(|layer| match layer {
+            Layer::One(name) => vec![name],
+            Layer::Many(names) => names,
+        })
+        .collect();
+    let names: Vec<&String> = layers.iter().flatten().collect();
+    for (i, name) in names.iter().enumerate() {
+        if name.trim().is_empty() {
+            return Err(D::Error::custom("layer names can't be empty"));
+        }
+        if names[..i].contains(name) {
+            return Err(D::Error::custom(format!(
+                "`{name}` is in more than one layer"
+            )));
+        }
+    }
+    Ok(layers)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::GuardianConfig;
+
+    #[test]
+    fn test_parse_architecture_rules() {
+        let cfg: GuardianConfig = toml::from_str("").unwrap();
+        assert!(cfg.check.architecture.is_empty());
+
+        let toml = r#"
+[check.architecture]
+layers = ["commands", ["checks", "ollama"], "config"]
+allowed = { storage = ["config"] }
+"#;
+        let cfg: GuardianConfig = toml::from_str(toml).unwrap();
+        let rules = &cfg.check.architecture;
+        assert_eq!(rules.layers[1], ["checks", "ollama"]);
+        assert_eq!(rules.layers[2], ["config"]);
+        assert_eq!(rules.allowed["storage"], ["config"]);
+
+        for toml in [
+            "[check.architecture]\nlayers = [\"checks\", [\"config\", \"checks\"]]",
+            "[check.architecture]\nlayers = [\"\"]",
+            "[check.architecture]\nlayers = [1]",
+            "[check.architecture]\ndeny = { checks = [\"commands\"] }",
+        ] {
+            assert!(toml::from_str::<GuardianConfig>(toml).is_err(), "{toml}");
+        }
+    }
+}
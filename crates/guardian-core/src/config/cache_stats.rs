@@ -0,0 +1,41 @@
+//! Hit and miss counts of guardian's caches, for `serve`'s metrics.
+//!
+//! Every process that looks something up in a cache bumps the counts in one
+//! shared state file. Concurrent processes can lose an update now and then,
+//! which is fine for a hit rate.
+
+use super::state;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// State file holding the counts of every cache.
+const STATS_FILE: &str = "cache-stats.json";
+
+/// Lookups in one cache.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+/// Count a lookup in the cache called `cache`.
+pub fn record(cache: &str, hit: bool) {
+    let Some(path) = state::path(STATS_FILE) else {
+        return;
+    };
+    let mut stats: BTreeMap<String, CacheStats> = state::read(&path).unwrap_or_default();
+    let entry = stats.entry(cache.to_string()).or_default();
+    if hit {
+        entry.hits += 1;
+    } else {
+        entry.misses += 1;
+    }
+    state::write(&path, &stats);
+}
+
+/// The counts of every cache that has been used, by name.
+pub fn load() -> BTreeMap<String, CacheStats> {
+    state::path(STATS_FILE)
+        .and_then(|path| state::read(&path))
+        .unwrap_or_default()
+}
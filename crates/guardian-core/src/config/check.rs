@@ -0,0 +1,158 @@
+//! The `[check]` configuration section: thresholds for `guardian-cli check`.
+//!
+//! Usually set per repository in a project config, so a project's limits
+//! travel with its code instead of with every invocation.
+
+mod clippy;
+
+pub use clippy::ClippySettings;
+
+use super::{ArchitectureRules, CrateKind, CrateKinds, ReadabilitySettings};
+use serde::Deserialize;
+use std::num::NonZeroUsize;
+
+/// Thresholds used by `check` when no command-line flag overrides them.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CheckSettings {
+    /// Maximum lines of code per file.
+    #[serde(default = "default_max_loc")]
+    pub max_loc: usize,
+
+    /// Warning threshold for file LOC.
+    #[serde(default = "default_warn_loc")]
+    pub warn_loc: usize,
+
+    /// Maximum functions per module.
+    #[serde(default = "default_max_functions")]
+    pub max_functions: usize,
+
+    /// Maximum modules per crate.
+    #[serde(default = "default_max_modules")]
+    pub max_modules: usize,
+
+    /// Required Rust edition.
+    #[serde(default = "default_edition")]
+    pub edition: String,
+
+    /// Fail when more than this many warnings are found.
+    #[serde(default)]
+    pub max_warnings: Option<usize>,
+
+    /// Only run these checks, like `--only`.
+    #[serde(default)]
+    pub only: Option<Vec<String>>,
+
+    /// Policy expression deciding whether results pass, like `--policy`.
+    #[serde(default)]
+    pub policy: Option<String>,
+
+    /// The project's crate kind, instead of the one its `Cargo.toml` shows.
+    #[serde(default)]
+    pub crate_kind: Option<CrateKind>,
+
+    /// Settings per crate kind, over these.
+    #[serde(default)]
+    pub kinds: CrateKinds,
+
+    /// Layers and allowed dependencies for the `architecture` check.
+    #[serde(default)]
+    pub architecture: ArchitectureRules,
+
+    /// Warning budget and denied lints for the `clippy-budget` check.
+    #[serde(default)]
+    pub clippy: ClippySettings,
+
+    /// Files and confidence threshold for the `llm-readability` check.
+    #[serde(default)]
+    pub readability: ReadabilitySettings,
+
+    /// Follow symlinks when looking for files, like `--follow-symlinks`.
+    #[serde(default)]
+    pub follow_symlinks: bool,
+
+    /// Most directories deep a walk for files goes.
+    #[serde(default = "super::scan::default_max_depth")]
+    pub max_depth: usize,
+
+    /// Files larger than this many bytes are skipped instead of read.
+    #[serde(default = "super::scan::default_max_file_bytes")]
+    pub max_file_bytes: u64,
+
+    /// Most files read at once; set by `--jobs`, one per core when unset.
+    #[serde(skip)]
+    pub jobs: Option<NonZeroUsize>,
+}
+
+impl Default for CheckSettings {
+    fn default() -> Self {
+        Self {
+            max_loc: default_max_loc(),
+            warn_loc: default_warn_loc(),
+            max_functions: default_max_functions(),
+            max_modules: default_max_modules(),
+            edition: default_edition(),
+            max_warnings: None,
+            only: None,
+            policy: None,
+            crate_kind: None,
+            kinds: CrateKinds::default(),
+            architecture: ArchitectureRules::default(),
+            clippy: ClippySettings::default(),
+            readability: ReadabilitySettings::default(),
+            follow_symlinks: false,
+            max_depth: super::scan::default_max_depth(),
+            max_file_bytes: super::scan::default_max_file_bytes(),
+            jobs: None,
+        }
+    }
+}
+
+fn default_max_loc() -> usize {
+    500
+}
+
+fn default_warn_loc() -> usize {
+    350
+}
+
+fn default_max_functions() -> usize {
+    7
+}
+
+fn default_max_modules() -> usize {
+    4
+}
+
+fn default_edition() -> String {
+    "2024".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::GuardianConfig;
+
+    #[test]
+    fn test_parse_check_settings() {
+        let cfg: GuardianConfig = toml::from_str("").unwrap();
+        assert_eq!(cfg.check.max_loc, 500);
+        assert_eq!(cfg.check.edition, "2024");
+
+        let toml = r#"
+[check]
+max_loc = 800
+max_warnings = 10
+"#;
+        let cfg: GuardianConfig = toml::from_str(toml).unwrap();
+        assert_eq!(cfg.check.max_loc, 800);
+        assert_eq!(cfg.check.warn_loc, 350);
+        assert_eq!(cfg.check.max_warnings, Some(10));
+        assert_eq!(cfg.check.clippy, super::ClippySettings::default());
+
+        let toml = "[check.clippy]\nmax_warnings = 5\ndeny = []\n";
+        let cfg: GuardianConfig = toml::from_str(toml).unwrap();
+        assert_eq!(cfg.check.clippy.max_warnings, 5);
+        assert!(cfg.check.clippy.deny.is_empty());
+        assert_eq!(cfg.check.clippy.timeout_secs, 300);
+    }
+}
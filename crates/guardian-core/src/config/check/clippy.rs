@@ -0,0 +1,51 @@
+//! The `[check.clippy]` table: the opt-in `clippy-budget` check.
+//!
+//! ```toml
+//! [checks.clippy-budget]
+//!
+//! [check.clippy]
+//! max_warnings = 10
+//! deny = ["clippy::correctness", "clippy::unwrap_used"]
+//! ```
+
+use serde::Deserialize;
+
+/// The `[check.clippy]` table: how much clippy may warn about before the
+/// opt-in `clippy-budget` check fails.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ClippySettings {
+    /// Warnings clippy may report before the check fails.
+    #[serde(default)]
+    pub max_warnings: usize,
+
+    /// Lints and lint groups passed to clippy as `-D`, so any of them
+    /// fails the check whatever the budget.
+    #[serde(default = "default_deny")]
+    pub deny: Vec<String>,
+
+    /// Seconds clippy may run before it is stopped.
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl Default for ClippySettings {
+    fn default() -> Self {
+        Self {
+            max_warnings: 0,
+            deny: default_deny(),
+            timeout_secs: default_timeout_secs(),
+        }
+    }
+}
+
+fn default_deny() -> Vec<String> {
+    vec![
+        "clippy::correctness".to_string(),
+        "clippy::suspicious".to_string(),
+    ]
+}
+
+fn default_timeout_secs() -> u64 {
+    300
+}
@@ -0,0 +1,74 @@
+//! Circuit breaker settings under `[ollama.circuit_breaker]`.
+
+use serde::Deserialize;
+
+/// Settings for the per-host circuit breaker.
+///
+/// After `failure_threshold` consecutive failed requests a host is skipped
+/// during automatic host selection for `cooldown_secs` seconds.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CircuitBreakerSettings {
+    /// Whether the circuit breaker is active.
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// Consecutive failures before a host's circuit opens.
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: u32,
+
+    /// How long an open circuit skips the host, in seconds.
+    #[serde(default = "default_cooldown_secs")]
+    pub cooldown_secs: u64,
+}
+
+impl Default for CircuitBreakerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            failure_threshold: default_failure_threshold(),
+            cooldown_secs: default_cooldown_secs(),
+        }
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_failure_threshold() -> u32 {
+    3
+}
+
+fn default_cooldown_secs() -> u64 {
+    300
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::GuardianConfig;
+
+    #[test]
+    fn test_circuit_breaker_defaults() {
+        let cfg: GuardianConfig = toml::from_str("[ollama]").unwrap();
+        assert_eq!(
+            cfg.ollama.circuit_breaker,
+            super::CircuitBreakerSettings::default()
+        );
+        assert!(cfg.ollama.circuit_breaker.enabled);
+        assert_eq!(cfg.ollama.circuit_breaker.failure_threshold, 3);
+    }
+
+    #[test]
+    fn test_parse_circuit_breaker() {
+        let toml = r#"
+[ollama.circuit_breaker]
+failure_threshold = 5
+cooldown_secs = 60
+"#;
+        let cfg: GuardianConfig = toml::from_str(toml).unwrap();
+        assert!(cfg.ollama.circuit_breaker.enabled);
+        assert_eq!(cfg.ollama.circuit_breaker.failure_threshold, 5);
+        assert_eq!(cfg.ollama.circuit_breaker.cooldown_secs, 60);
+    }
+}
@@ -0,0 +1,163 @@
+//! `GUARDIAN_*` environment variables that override config file settings.
+//!
+//! Lets CI runners and containers configure guardian without a TOML file.
+//! Each variable maps to one config key; see `ENV_VARS` for the mapping.
+
+use super::GuardianConfig;
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+
+/// How a variable's value is parsed.
+#[derive(Clone, Copy)]
+enum Kind {
+    Str,
+    Int,
+    Bool,
+    /// Comma-separated `name=url` entries, or bare URLs named by host.
+    Hosts,
+}
+
+/// Environment variables and the config keys they set.
+const ENV_VARS: &[(&str, &str, Kind)] = &[
+    ("GUARDIAN_OLLAMA_HOSTS", "ollama.hosts", Kind::Hosts),
+    ("GUARDIAN_DEFAULT_HOST", "ollama.default_host", Kind::Str),
+    ("GUARDIAN_DEFAULT_MODEL", "ollama.default_model", Kind::Str),
+    (
+        "GUARDIAN_TIMEOUT_MS",
+        "ollama.default_timeout_ms",
+        Kind::Int,
+    ),
+    ("GUARDIAN_SYSTEM_PROMPT", "ollama.system_prompt", Kind::Str),
+    ("GUARDIAN_NUM_CTX", "ollama.num_ctx", Kind::Int),
+    ("GUARDIAN_PROMPT_BUDGET", "ollama.prompt_budget", Kind::Int),
+    ("GUARDIAN_AUTO_PULL", "ollama.auto_pull", Kind::Bool),
+    ("GUARDIAN_SERVE_BIND", "serve.bind", Kind::Str),
+    ("GUARDIAN_HYPERLINKS", "output.hyperlinks", Kind::Str),
+    ("GUARDIAN_EDITOR_URL", "output.editor_url", Kind::Str),
+    ("GUARDIAN_MAX_LOC", "check.max_loc", Kind::Int),
+    ("GUARDIAN_WARN_LOC", "check.warn_loc", Kind::Int),
+    ("GUARDIAN_MAX_FUNCTIONS", "check.max_functions", Kind::Int),
+    ("GUARDIAN_MAX_MODULES", "check.max_modules", Kind::Int),
+    ("GUARDIAN_EDITION", "check.edition", Kind::Str),
+    ("GUARDIAN_MAX_WARNINGS", "check.max_warnings", Kind::Int),
+];
+
+/// One single-key config table per set variable, named by the variable.
+///
+/// Empty variables are ignored, so `GUARDIAN_DEFAULT_MODEL=` leaves the file
+/// config alone.
+pub(super) fn env_layers(
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Result<Vec<(String, toml::Table)>> {
+    let mut layers = Vec::new();
+    for &(var, key, kind) in ENV_VARS {
+        let Some(raw) = lookup(var).filter(|v| !v.trim().is_empty()) else {
+            continue;
+        };
+        let value =
+            parse_value(kind, raw.trim()).with_context(|| format!("Invalid {var}={raw}"))?;
+
+        // Build the nested table for the dotted key, innermost first.
+        let mut parts = key.rsplit('.');
+        let mut table = toml::Table::new();
+        table.insert(parts.next().unwrap_or(key).to_string(), value);
+        for part in parts {
+            let mut outer = toml::Table::new();
+            outer.insert(part.to_string(), toml::Value::Table(table));
+            table = outer;
+        }
+        // Catch values the config rejects, such as GUARDIAN_HYPERLINKS=maybe.
+        GuardianConfig::deserialize(toml::Value::Table(table.clone()))
+            .with_context(|| format!("Invalid {var}={raw}"))?;
+        layers.push((var.to_string(), table));
+    }
+    Ok(layers)
+}
+
+fn parse_value(kind: Kind, raw: &str) -> Result<toml::Value> {
+    Ok(match kind {
+        Kind::Str => toml::Value::String(raw.to_string()),
+        Kind::Int => toml::Value::Integer(raw.parse().context("expected a whole number")?),
+        Kind::Bool => match raw.to_lowercase().as_str() {
+            "1" | "true" | "yes" | "on" => toml::Value::Boolean(true),
+            "0" | "false" | "no" | "off" => toml::Value::Boolean(false),
+            _ => bail!("expected true or false"),
+        },
+        Kind::Hosts => toml::Value::Array(
+            raw.split(',')
+                .map(str::trim)
+                .filter(|entry| !entry.is_empty())
+                .map(parse_host)
+                .collect::<Result<_>>()?,
+        ),
+    })
+}
+
+/// Parse `name=url`, or a bare URL named after its host.
+fn parse_host(entry: &str) -> Result<toml::Value> {
+    let (name, url) = match entry.split_once('=') {
+        Some((name, url)) => (name.trim().to_string(), url.trim()),
+        None => {
+            let rest = entry.split_once("://").map_or(entry, |(_, rest)| rest);
+            let host = rest.split([':', '/']).next().unwrap_or_default();
+            (host.to_string(), entry)
+        }
+    };
+    if name.is_empty() || !url.contains("://") {
+        bail!("expected name=http://host:port, got '{entry}'");
+    }
+    let mut host = toml::Table::new();
+    host.insert("name".to_string(), toml::Value::String(name));
+    host.insert("base_url".to_string(), toml::Value::String(url.to_string()));
+    Ok(toml::Value::Table(host))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn env(vars: &[(&str, &str)]) -> Result<Vec<(String, toml::Table)>> {
+        let vars: HashMap<_, _> = vars.iter().copied().collect();
+        env_layers(|name| vars.get(name).map(|v| v.to_string()))
+    }
+
+    #[test]
+    fn test_env_layers() {
+        let layers = env(&[
+            ("GUARDIAN_TIMEOUT_MS", "4000"),
+            ("GUARDIAN_DEFAULT_MODEL", ""),
+            ("GUARDIAN_AUTO_PULL", "yes"),
+        ])
+        .unwrap();
+        assert_eq!(layers.len(), 2);
+        assert_eq!(layers[0].0, "GUARDIAN_TIMEOUT_MS");
+        assert_eq!(
+            layers[0].1["ollama"]["default_timeout_ms"].as_integer(),
+            Some(4000)
+        );
+        assert_eq!(layers[1].1["ollama"]["auto_pull"].as_bool(), Some(true));
+
+        let err = env(&[("GUARDIAN_MAX_LOC", "lots")]).unwrap_err();
+        assert!(format!("{err:#}").contains("GUARDIAN_MAX_LOC=lots"));
+    }
+
+    #[test]
+    fn test_env_hosts() {
+        let layers = env(&[(
+            "GUARDIAN_OLLAMA_HOSTS",
+            "big72=http://big72:11434, http://localhost:11434",
+        )])
+        .unwrap();
+        let hosts = layers[0].1["ollama"]["hosts"].as_array().unwrap();
+        assert_eq!(hosts[0]["name"].as_str(), Some("big72"));
+        assert_eq!(hosts[1]["name"].as_str(), Some("localhost"));
+        assert_eq!(
+            hosts[1]["base_url"].as_str(),
+            Some("http://localhost:11434")
+        );
+
+        assert!(env(&[("GUARDIAN_OLLAMA_HOSTS", "big72")]).is_err());
+        assert!(env(&[("GUARDIAN_HYPERLINKS", "maybe")]).is_err());
+    }
+}
@@ -0,0 +1,115 @@
+//! Where a config file lives: a local path, an HTTPS URL, or a file in a
+//! git repository, and reading it from there.
+
+use crate::config::{remote, state};
+use anyhow::{Context, Result, bail};
+use reqwest::Url;
+use std::fs;
+use std::path::PathBuf;
+
+/// Directory of shallow checkouts for `git+` bases.
+const GIT_CACHE_DIR: &str = "git-config";
+
+/// Where a config file lives.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Source {
+    File(PathBuf),
+    Url(Url),
+    /// `path` within `repo` at `reference`, or the remote's HEAD.
+    Git {
+        repo: String,
+        reference: Option<String>,
+        path: String,
+    },
+}
+
+impl std::fmt::Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::File(path) => write!(f, "{}", path.display()),
+            Source::Url(url) => write!(f, "{url}"),
+            Source::Git {
+                repo,
+                reference,
+                path,
+            } => {
+                write!(f, "git+{repo}//{path}")?;
+                match reference {
+                    Some(reference) => write!(f, "?ref={reference}"),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+/// Parse an HTTPS URL or a `git+REPO//PATH?ref=REF` entry; `None` for paths.
+pub(super) fn parse_remote(entry: &str) -> Result<Option<Source>> {
+    if entry.starts_with("https://") || entry.starts_with("http://") {
+        return Ok(Some(Source::Url(Url::parse(entry)?)));
+    }
+    let Some(rest) = entry.strip_prefix("git+") else {
+        return Ok(None);
+    };
+    let (rest, reference) = match rest.rsplit_once("?ref=") {
+        Some((rest, reference)) => (rest, Some(reference.to_string())),
+        None => (rest, None),
+    };
+    // The repository URL has its own `//` after the scheme.
+    let after_scheme = rest.find("://").map_or(0, |i| i + 3);
+    let Some(split) = rest[after_scheme..].find("//").map(|i| i + after_scheme) else {
+        bail!("expected git+REPO//PATH/TO/FILE.toml");
+    };
+    let path = remote::join_repo_path("", &rest[split + 2..])?;
+    // git would take either for an option, such as `--upload-pack=COMMAND`.
+    if rest.starts_with('-') || reference.as_deref().is_some_and(|r| r.starts_with('-')) {
+        bail!("a git repository or ref can't start with '-'");
+    }
+    Ok(Some(Source::Git {
+        repo: rest[..split].to_string(),
+        reference,
+        path,
+    }))
+}
+
+pub(super) fn read(source: &Source) -> Result<String> {
+    match source {
+        Source::File(path) => fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file at {}", path.display())),
+        Source::Url(url) => remote::fetch_url(url),
+        Source::Git {
+            repo,
+            reference,
+            path,
+        } => {
+            let cache =
+                state::path(GIT_CACHE_DIR).context("Could not determine cache directory")?;
+            remote::fetch_git(&cache, repo, reference.as_deref(), path)
+        }
+    }
+}
+
+/// The detached signature file next to `source`.
+pub(super) fn signature_of(source: &Source) -> Source {
+    match source {
+        Source::File(path) => {
+            let mut sig = path.clone().into_os_string();
+            sig.push(".sig");
+            Source::File(sig.into())
+        }
+        Source::Url(url) => {
+            let mut sig = url.clone();
+            sig.set_path(&format!("{}.sig", url.path()));
+            Source::Url(sig)
+        }
+        Source::Git {
+            repo,
+            reference,
+            path,
+        } => Source::Git {
+            repo: repo.clone(),
+            reference: reference.clone(),
+            path: format!("{path}.sig"),
+        },
+    }
+}
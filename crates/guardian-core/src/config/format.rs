@@ -0,0 +1,94 @@
+//! Config file formats: TOML, YAML, and JSON, chosen by file extension.
+//!
+//! Every format holds the same settings, so `guardian.yaml` or
+//! `guardian.json` can stand in for `guardian.toml` anywhere, and the
+//! files of one layered config can mix formats. YAML and JSON configs are
+//! converted to TOML tables before layering.
+
+use super::validate::validate;
+use anyhow::{Context, Result};
+
+/// File names recognized as a user config, in order of preference.
+pub(super) const USER_CONFIG_NAMES: &[&str] = &[
+    "guardian.toml",
+    "guardian.yaml",
+    "guardian.yml",
+    "guardian.json",
+];
+
+/// The syntax of a config file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Format {
+    Toml,
+    Yaml,
+    Json,
+}
+
+impl Format {
+    /// The format of a file name, path, or URL, by its extension. Anything
+    /// other than `.yaml`, `.yml`, or `.json` is TOML.
+    pub(super) fn of(name: &str) -> Self {
+        let name = name.split(['?', '#']).next().unwrap_or_default();
+        let extension = name
+            .rsplit_once('.')
+            .map(|(_, ext)| ext.to_ascii_lowercase());
+        match extension.as_deref() {
+            Some("yaml" | "yml") => Format::Yaml,
+            Some("json") => Format::Json,
+            _ => Format::Toml,
+        }
+    }
+
+    /// Validate `contents` and convert it to a TOML table.
+    pub(super) fn parse(self, source: &str, contents: &str) -> Result<toml::Table> {
+        // An empty YAML document is null, not an empty mapping.
+        if self == Format::Yaml && contents.trim().is_empty() {
+            return Ok(toml::Table::new());
+        }
+        validate(source, self, contents)?;
+        let context = || format!("Failed to parse config at {source}");
+        match self {
+            Format::Toml => toml::from_str(contents).with_context(context),
+            Format::Yaml => serde_norway::from_str(contents).with_context(context),
+            Format::Json => serde_json::from_str(contents).with_context(context),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_of() {
+        assert_eq!(Format::of("/etc/guardian.toml"), Format::Toml);
+        assert_eq!(Format::of(".guardian.YML"), Format::Yaml);
+        assert_eq!(
+            Format::of("https://example.com/base.json?token=1"),
+            Format::Json
+        );
+        assert_eq!(
+            Format::of("git+ssh://host/policy.git//base.yaml?ref=v3"),
+            Format::Yaml
+        );
+        assert_eq!(Format::of("guardian"), Format::Toml);
+    }
+
+    #[test]
+    fn test_formats_parse_alike() {
+        let toml = "[check]\nmax_loc = 400\n\n[[ollama.hosts]]\nname = \"local\"\nbase_url = \"http://localhost:11434\"\n";
+        let yaml = "check:\n  max_loc: 400\nollama:\n  hosts:\n    - name: local\n      base_url: http://localhost:11434\n";
+        let json = r#"{"check": {"max_loc": 400}, "ollama": {"hosts": [{"name": "local", "base_url": "http://localhost:11434"}]}}"#;
+        let expected = Format::Toml.parse("a.toml", toml).unwrap();
+        assert_eq!(Format::Yaml.parse("a.yaml", yaml).unwrap(), expected);
+        assert_eq!(Format::Json.parse("a.json", json).unwrap(), expected);
+        assert!(Format::Yaml.parse("a.yaml", "").unwrap().is_empty());
+
+        let err = Format::Yaml
+            .parse("a.yaml", "check:\n  max_lco: 400\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with("Invalid config at a.yaml: check: unknown field `max_lco`"));
+        assert!(err.contains("help: did you mean `max_loc`?"), "{err}");
+    }
+}
@@ -0,0 +1,178 @@
+//! Host groups and tags.
+//!
+//! Hosts carry free-form tags, and may join a group whose tags and defaults
+//! they inherit unless they set their own:
+//!
+//! ```toml
+//! [ollama.groups.lab]
+//! tags = ["gpu"]
+//! default_model = "qwen2.5-coder:14b"
+//! default_timeout_ms = 5000
+//! models = { evaluate = "qwen2.5-coder:32b" }
+//!
+//! [[ollama.hosts]]
+//! name = "big72"
+//! base_url = "http://big72:11434"
+//! group = "lab"
+//! tags = ["fast"]
+//! ```
+//!
+//! `--host-tag gpu` then restricts host selection to hosts tagged `gpu`.
+
+use super::GuardianConfig;
+use anyhow::{Result, bail};
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Settings shared by the hosts in one `[ollama.groups.NAME]`.
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct HostGroup {
+    /// Tags added to every host in the group.
+    #[serde(default)]
+    pub tags: Vec<String>,
+
+    /// Model for member hosts that don't set their own.
+    #[serde(default)]
+    pub default_model: Option<String>,
+
+    /// Ping and model-listing timeout for member hosts that don't set their own.
+    #[serde(default)]
+    pub default_timeout_ms: Option<u64>,
+
+    /// Model per task for member hosts, under each host's own `models`.
+    #[serde(default)]
+    pub models: BTreeMap<String, String>,
+}
+
+impl GuardianConfig {
+    /// Give each host its group's tags and any defaults it doesn't set.
+    pub(super) fn apply_host_groups(&mut self) -> Result<()> {
+        for host in &mut self.ollama.hosts {
+            let Some(name) = &host.group else {
+                continue;
+            };
+            let Some(group) = self.ollama.groups.get(name) else {
+                bail!(
+                    "Host {} is in group '{name}', which is not defined in [ollama.groups]",
+                    host.name
+                );
+            };
+            for tag in &group.tags {
+                if !host.tags.contains(tag) {
+                    host.tags.push(tag.clone());
+                }
+            }
+            if host.default_model.is_none() {
+                host.default_model.clone_from(&group.default_model);
+            }
+            host.default_timeout_ms = host.default_timeout_ms.or(group.default_timeout_ms);
+            for (task, model) in &group.models {
+                host.models
+                    .entry(task.clone())
+                    .or_insert_with(|| model.clone());
+            }
+        }
+        Ok(())
+    }
+
+    /// Drop hosts missing any of `tags`, for `--host-tag`.
+    ///
+    /// Fails when no host has every tag, since selection could never succeed.
+    pub fn retain_tagged(&mut self, tags: &[String]) -> Result<()> {
+        if tags.is_empty() {
+            return Ok(());
+        }
+        let known: BTreeSet<String> = self
+            .ollama
+            .hosts
+            .iter()
+            .flat_map(|h| h.tags.iter().cloned())
+            .collect();
+        self.ollama
+            .hosts
+            .retain(|h| tags.iter().all(|tag| h.tags.contains(tag)));
+        if self.ollama.hosts.is_empty() {
+            let known: Vec<String> = known.into_iter().collect();
+            bail!(
+                "No hosts are tagged {} (known tags: {})",
+                tags.join(" and "),
+                if known.is_empty() {
+                    "none".to_string()
+                } else {
+                    known.join(", ")
+                }
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::GuardianConfig;
+
+    const CONFIG: &str = r#"
+[ollama.groups.lab]
+tags = ["gpu"]
+default_model = "qwen2.5-coder:14b"
+default_timeout_ms = 5000
+models = { evaluate = "qwen2.5-coder:32b", quick = "llama3.2:3b" }
+
+[[ollama.hosts]]
+name = "big72"
+base_url = "http://big72:11434"
+group = "lab"
+tags = ["fast"]
+default_timeout_ms = 1000
+models = { quick = "phi4-mini" }
+
+[[ollama.hosts]]
+name = "local"
+base_url = "http://localhost:11434"
+tags = ["fast"]
+"#;
+
+    fn load() -> GuardianConfig {
+        let mut cfg: GuardianConfig = toml::from_str(CONFIG).unwrap();
+        cfg.apply_host_groups().unwrap();
+        cfg
+    }
+
+    #[test]
+    fn test_hosts_inherit_group_settings() {
+        let cfg = load();
+        let big72 = &cfg.ollama.hosts[0];
+        assert_eq!(big72.tags, ["fast", "gpu"]);
+        assert_eq!(big72.default_model.as_deref(), Some("qwen2.5-coder:14b"));
+        assert_eq!(big72.default_timeout_ms, Some(1000));
+        assert_eq!(big72.models["evaluate"], "qwen2.5-coder:32b");
+        assert_eq!(big72.models["quick"], "phi4-mini");
+        assert_eq!(cfg.ollama.hosts[1].default_model, None);
+
+        let mut cfg: GuardianConfig =
+            toml::from_str(&CONFIG.replace("group = \"lab\"", "group = \"attic\"")).unwrap();
+        let err = cfg.apply_host_groups().unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("group 'attic', which is not defined")
+        );
+    }
+
+    #[test]
+    fn test_retain_tagged() {
+        let mut cfg = load();
+        cfg.retain_tagged(&["fast".to_string()]).unwrap();
+        assert_eq!(cfg.ollama.hosts.len(), 2);
+        cfg.retain_tagged(&["gpu".to_string(), "fast".to_string()])
+            .unwrap();
+        assert_eq!(cfg.ollama.hosts.len(), 1);
+        assert_eq!(cfg.ollama.hosts[0].name, "big72");
+
+        let err = load().retain_tagged(&["tpu".to_string()]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "No hosts are tagged tpu (known tags: fast, gpu)"
+        );
+    }
+}
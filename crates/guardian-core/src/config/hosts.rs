@@ -0,0 +1,309 @@
+//! Ollama host definitions and host ordering helpers.
+
+use super::GuardianConfig;
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// An Ollama host configuration.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct OllamaHost {
+    /// Human-readable name for this host (e.g., "big72", "local").
+    pub name: String,
+
+    /// Base URL for the Ollama API (e.g., "http://big72:11434").
+    pub base_url: String,
+
+    /// Whether this host is enabled for use.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Whether this is a fallback host (used only when primaries are unavailable).
+    #[serde(default)]
+    pub fallback: bool,
+
+    /// Optional description of this host.
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// Multiplier applied to this host's score during host selection.
+    #[serde(default = "default_weight")]
+    pub weight: f64,
+
+    /// Maximum concurrent generate requests guardian sends to this host.
+    #[serde(default)]
+    pub max_concurrent: Option<usize>,
+
+    /// Credentials for a host behind an authenticating reverse proxy.
+    #[serde(default)]
+    pub auth: Option<HostAuth>,
+
+    /// The `[ollama.groups.NAME]` this host belongs to.
+    #[serde(default)]
+    pub group: Option<String>,
+
+    /// Labels such as "gpu" or "lab", matched by `--host-tag`. Includes the
+    /// group's tags once the config is loaded.
+    #[serde(default)]
+    pub tags: Vec<String>,
+
+    /// Model to use on this host when the task has no routed model.
+    #[serde(default)]
+    pub default_model: Option<String>,
+
+    /// Model per task on this host, e.g. `evaluate = "qwen2.5-coder:32b"`.
+    /// Overrides the task's `[routing]` model when this host is selected.
+    #[serde(default)]
+    pub models: BTreeMap<String, String>,
+
+    /// Timeout in milliseconds for pings and model listings on this host.
+    #[serde(default)]
+    pub default_timeout_ms: Option<u64>,
+}
+
+/// How to authenticate to a host. Each secret is read from an environment
+/// variable (`*_env`) or the OS keyring (`*_keyring`), so it never has to be
+/// written into the config file.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase", deny_unknown_fields)]
+pub enum HostAuth {
+    /// `Authorization: Bearer <token>`, e.g. for oauth2-proxy.
+    Bearer {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        token_env: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        token_keyring: Option<String>,
+    },
+    /// HTTP basic auth, e.g. for Caddy's `basic_auth`.
+    Basic {
+        username: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        password_env: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        password_keyring: Option<String>,
+    },
+}
+
+/// Where a host's token or password is read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecretSource<'a> {
+    /// An environment variable.
+    Env(&'a str),
+    /// A secret stored with `set-secret`.
+    Keyring(&'a str),
+}
+
+impl HostAuth {
+    /// Where the secret comes from; exactly one source must be set.
+    pub fn secret_source(&self) -> Result<SecretSource<'_>> {
+        let (field, env, keyring) = match self {
+            HostAuth::Bearer {
+                token_env,
+                token_keyring,
+            } => ("token", token_env, token_keyring),
+            HostAuth::Basic {
+                password_env,
+                password_keyring,
+                ..
+            } => ("password", password_env, password_keyring),
+        };
+        match (env, keyring) {
+            (Some(var), None) => Ok(SecretSource::Env(var)),
+            (None, Some(name)) => Ok(SecretSource::Keyring(name)),
+            _ => bail!("auth needs exactly one of {field}_env and {field}_keyring"),
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_weight() -> f64 {
+    1.0
+}
+
+#[cfg(test)]
+impl OllamaHost {
+    /// An enabled primary host `name` at `http://NAME:11434`, with every
+    /// other setting at its default.
+    pub(crate) fn test(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            base_url: format!("http://{name}:11434"),
+            enabled: true,
+            fallback: false,
+            description: None,
+            weight: 1.0,
+            max_concurrent: None,
+            auth: None,
+            group: None,
+            tags: Vec::new(),
+            default_model: None,
+            default_timeout_ms: None,
+            models: Default::default(),
+        }
+    }
+}
+
+impl GuardianConfig {
+    /// Get primary (non-fallback) hosts that are enabled.
+    pub fn primary_hosts(&self) -> Vec<&OllamaHost> {
+        self.ollama
+            .hosts
+            .iter()
+            .filter(|h| h.enabled && !h.fallback)
+            .collect()
+    }
+
+    /// Get fallback hosts that are enabled.
+    pub fn fallback_hosts(&self) -> Vec<&OllamaHost> {
+        self.ollama
+            .hosts
+            .iter()
+            .filter(|h| h.enabled && h.fallback)
+            .collect()
+    }
+
+    /// Get all enabled hosts (primary first, then fallback).
+    pub fn enabled_hosts(&self) -> Vec<&OllamaHost> {
+        let mut hosts = self.primary_hosts();
+        hosts.extend(self.fallback_hosts());
+        hosts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_host_auth() {
+        let toml = r#"
+[[ollama.hosts]]
+name = "big72"
+base_url = "https://big72.example.com"
+auth = { type = "bearer", token_env = "GUARDIAN_BIG72_TOKEN" }
+
+[[ollama.hosts]]
+name = "caddy"
+base_url = "https://caddy.example.com"
+auth = { type = "basic", username = "guardian", password_env = "CADDY_PASSWORD" }
+"#;
+        let cfg: GuardianConfig = toml::from_str(toml).unwrap();
+        assert_eq!(
+            cfg.ollama.hosts[0].auth,
+            Some(HostAuth::Bearer {
+                token_env: Some("GUARDIAN_BIG72_TOKEN".to_string()),
+                token_keyring: None,
+            })
+        );
+        assert!(matches!(
+            &cfg.ollama.hosts[1].auth,
+            Some(HostAuth::Basic { username, .. }) if username == "guardian"
+        ));
+
+        let plaintext = r#"
+[[ollama.hosts]]
+name = "big72"
+base_url = "https://big72.example.com"
+auth = { type = "bearer", token = "secret" }
+"#;
+        assert!(toml::from_str::<GuardianConfig>(plaintext).is_err());
+    }
+
+    #[test]
+    fn test_project_cannot_define_hosts() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::create_dir(temp.path().join(".git")).unwrap();
+        let (user, project) = (
+            temp.path().join("user.toml"),
+            temp.path().join(".guardian.toml"),
+        );
+        let hosts = "[[ollama.hosts]]\nname = \"evil\"\nbase_url = \"https://example.com\"\n";
+        std::fs::write(&user, hosts).unwrap();
+        std::fs::write(&project, "[ollama]\ndefault_model = \"phi4\"\n").unwrap();
+        let load = || GuardianConfig::load(Some(&user), Some(temp.path()), None, None);
+        assert_eq!(load().unwrap().ollama.hosts[0].name, "evil");
+
+        std::fs::write(&project, hosts).unwrap();
+        let err = load().unwrap_err().to_string();
+        assert!(err.contains(".guardian.toml defines [[ollama.hosts]]"));
+
+        let base = hosts.replace("[[ollama", "[[profile.ci.ollama");
+        std::fs::write(temp.path().join("base.toml"), base).unwrap();
+        std::fs::write(&project, "extends = [\"base.toml\"]\n").unwrap();
+        let err = load().unwrap_err().to_string();
+        assert!(err.contains("base.toml defines [[ollama.hosts]]"));
+    }
+
+    #[test]
+    fn test_secret_source() {
+        let auth: HostAuth =
+            toml::from_str("type = \"bearer\"\ntoken_keyring = \"big72\"").unwrap();
+        assert_eq!(
+            auth.secret_source().unwrap(),
+            SecretSource::Keyring("big72")
+        );
+
+        let auth: HostAuth =
+            toml::from_str("type = \"basic\"\nusername = \"guardian\"\npassword_env = \"PW\"")
+                .unwrap();
+        assert_eq!(auth.secret_source().unwrap(), SecretSource::Env("PW"));
+
+        let auth: HostAuth = toml::from_str("type = \"bearer\"").unwrap();
+        let err = auth.secret_source().unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("exactly one of token_env and token_keyring")
+        );
+    }
+
+    #[test]
+    fn test_disabled_host_not_in_enabled_list() {
+        let toml = r#"
+[[ollama.hosts]]
+name = "disabled"
+base_url = "http://disabled:11434"
+enabled = false
+
+[[ollama.hosts]]
+name = "enabled"
+base_url = "http://enabled:11434"
+enabled = true
+"#;
+        let cfg: GuardianConfig = toml::from_str(toml).unwrap();
+        let enabled = cfg.enabled_hosts();
+        assert_eq!(enabled.len(), 1);
+        assert_eq!(enabled[0].name, "enabled");
+    }
+
+    #[test]
+    fn test_enabled_hosts_order() {
+        let toml = r#"
+[[ollama.hosts]]
+name = "fallback1"
+base_url = "http://fallback1:11434"
+fallback = true
+
+[[ollama.hosts]]
+name = "primary1"
+base_url = "http://primary1:11434"
+fallback = false
+
+[[ollama.hosts]]
+name = "primary2"
+base_url = "http://primary2:11434"
+fallback = false
+"#;
+        let cfg: GuardianConfig = toml::from_str(toml).unwrap();
+        let enabled = cfg.enabled_hosts();
+
+        // Primaries should come before fallbacks
+        assert_eq!(enabled.len(), 3);
+        assert!(!enabled[0].fallback);
+        assert!(!enabled[1].fallback);
+        assert!(enabled[2].fallback);
+    }
+}
@@ -0,0 +1,17 @@
+//! How many things a run does at once (`--jobs`).
+//!
+//! The limit covers the threads walking and reading files, the hosts pinged
+//! together, and the generate requests in flight across all hosts. It
+//! defaults to the number of cores; a lower one keeps guardian polite on a
+//! shared build machine. `--jobs` sets it on both [`super::CheckSettings`]
+//! and [`super::OllamaSection`], which pass it on to the scan and the client.
+
+use std::num::NonZeroUsize;
+
+/// How many things to do at once: `limit`, or one per core when `None`.
+pub fn jobs(limit: Option<NonZeroUsize>) -> usize {
+    limit.map_or_else(
+        || std::thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        NonZeroUsize::get,
+    )
+}
@@ -0,0 +1,256 @@
+//! Check defaults per crate kind: binaries, libraries, proc-macro crates,
+//! and test-support crates each get thresholds and checks of their own.
+//!
+//! The kind is read from the project's `Cargo.toml`, or set with
+//! `check.crate_kind`. A threshold comes from, highest first: the kind's
+//! `[check.kinds.KIND]` table, a `[check]` value a config file sets (even
+//! to the default), the kind's built-in default, and the `[check]` default. Checks a kind
+//! skips are turned off as if by a `[checks.NAME]` table, unless the
+//! config has one for that check.
+//!
+//! ```toml
+//! [check.kinds.test-support]
+//! max_loc = 1000
+//! skip = ["test-quality"]
+//! ```
+
+use super::{CheckToggle, GuardianConfig};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// What kind of crate a project is.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum CrateKind {
+    Bin,
+    Lib,
+    ProcMacro,
+    /// A library of fixtures and helpers for other crates' tests.
+    TestSupport,
+}
+
+/// Settings for one crate kind, over the `[check]` ones.
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct KindSettings {
+    pub max_loc: Option<usize>,
+    pub warn_loc: Option<usize>,
+    pub max_functions: Option<usize>,
+    pub max_modules: Option<usize>,
+    /// Checks that don't run on this kind of crate.
+    pub skip: Option<Vec<String>>,
+}
+
+/// `[check.kinds.*]` tables, one per crate kind.
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct CrateKinds {
+    pub bin: KindSettings,
+    pub lib: KindSettings,
+    pub proc_macro: KindSettings,
+    pub test_support: KindSettings,
+}
+
+/// Package name endings that mark a test-support crate.
+const TEST_SUPPORT_SUFFIXES: &[&str] = &["-test-support", "-test-utils", "-testing", "-fixtures"];
+
+impl CrateKind {
+    /// The kind of the crate in `project_dir`, from its `Cargo.toml`: a
+    /// proc-macro crate, a test-support crate by its package name, a
+    /// library when it has one (even beside binaries), or else a binary.
+    /// `None` when there's no package there, as in a virtual workspace.
+    pub fn detect(project_dir: &Path) -> Option<Self> {
+        let manifest = std::fs::read_to_string(project_dir.join("Cargo.toml")).ok()?;
+        let manifest: toml::Value = toml::from_str(&manifest).ok()?;
+        let name = manifest.get("package")?.get("name")?.as_str()?;
+        let lib = manifest.get("lib");
+        if lib
+            .and_then(|lib| lib.get("proc-macro"))
+            .and_then(toml::Value::as_bool)
+            == Some(true)
+        {
+            return Some(Self::ProcMacro);
+        }
+        let name = name.replace('_', "-");
+        if TEST_SUPPORT_SUFFIXES
+            .iter()
+            .any(|suffix| name.ends_with(suffix))
+        {
+            return Some(Self::TestSupport);
+        }
+        if lib.is_some() || project_dir.join("src/lib.rs").is_file() {
+            return Some(Self::Lib);
+        }
+        Some(Self::Bin)
+    }
+
+    /// The kind's name, as config files spell it.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Bin => "bin",
+            Self::Lib => "lib",
+            Self::ProcMacro => "proc-macro",
+            Self::TestSupport => "test-support",
+        }
+    }
+
+    /// The built-in settings for this kind: binaries and proc-macro crates
+    /// may have more functions per module, and test-support crates longer
+    /// files and no `test-quality` check, since their helpers aren't tests.
+    fn defaults(self) -> KindSettings {
+        match self {
+            Self::Lib => KindSettings::default(),
+            Self::Bin | Self::ProcMacro => KindSettings {
+                max_functions: Some(10),
+                ..KindSettings::default()
+            },
+            Self::TestSupport => KindSettings {
+                max_loc: Some(800),
+                warn_loc: Some(600),
+                skip: Some(vec!["test-quality".to_string()]),
+                ..KindSettings::default()
+            },
+        }
+    }
+}
+
+impl GuardianConfig {
+    /// Apply the settings for the kind of crate in `project_dir` to
+    /// `[check]` and `[checks]`, returning the kind, which is also left in
+    /// `check.crate_kind`.
+    pub fn apply_crate_kind(&mut self, project_dir: &Path) -> Option<CrateKind> {
+        let kind = self
+            .check
+            .crate_kind
+            .or_else(|| CrateKind::detect(project_dir))?;
+        let kinds = &self.check.kinds;
+        let set = match kind {
+            CrateKind::Bin => &kinds.bin,
+            CrateKind::Lib => &kinds.lib,
+            CrateKind::ProcMacro => &kinds.proc_macro,
+            CrateKind::TestSupport => &kinds.test_support,
+        }
+        .clone();
+        let builtin = kind.defaults();
+        let configured = |key: &str| self.origins.iter().any(|o| o.key == format!("check.{key}"));
+        let check = &mut self.check;
+        check.max_loc = pick(
+            set.max_loc,
+            builtin.max_loc,
+            configured("max_loc"),
+            check.max_loc,
+        );
+        check.warn_loc = pick(
+            set.warn_loc,
+            builtin.warn_loc,
+            configured("warn_loc"),
+            check.warn_loc,
+        );
+        check.warn_loc = check.warn_loc.min(check.max_loc);
+        check.max_functions = pick(
+            set.max_functions,
+            builtin.max_functions,
+            configured("max_functions"),
+            check.max_functions,
+        );
+        check.max_modules = pick(
+            set.max_modules,
+            builtin.max_modules,
+            configured("max_modules"),
+            check.max_modules,
+        );
+        for name in set.skip.or(builtin.skip).unwrap_or_default() {
+            self.checks
+                .entry(name)
+                .or_insert(CheckToggle { enabled: false });
+        }
+        self.check.crate_kind = Some(kind);
+        Some(kind)
+    }
+}
+
+/// The kind's own value, or the kind's `builtin` one unless a config file
+/// set the setting, or the `current` value.
+fn pick(set: Option<usize>, builtin: Option<usize>, configured: bool, current: usize) -> usize {
+    set.or(builtin.filter(|_| !configured)).unwrap_or(current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn crate_dir(manifest: &str, files: &[&str]) -> tempfile::TempDir {
+        let temp = tempfile::tempdir().unwrap();
+        fs::create_dir(temp.path().join("src")).unwrap();
+        fs::write(temp.path().join("Cargo.toml"), manifest).unwrap();
+        for file in files {
+            fs::write(temp.path().join(file), "").unwrap();
+        }
+        temp
+    }
+
+    #[test]
+    fn test_detect() {
+        let package = "[package]\nname = \"app\"\n";
+        let bin = crate_dir(package, &["src/main.rs"]);
+        assert_eq!(CrateKind::detect(bin.path()), Some(CrateKind::Bin));
+        let lib = crate_dir(package, &["src/main.rs", "src/lib.rs"]);
+        assert_eq!(CrateKind::detect(lib.path()), Some(CrateKind::Lib));
+        let derive = crate_dir(&format!("{package}[lib]\nproc-macro = true\n"), &[]);
+        assert_eq!(CrateKind::detect(derive.path()), Some(CrateKind::ProcMacro));
+        let support = crate_dir("[package]\nname = \"app_test_utils\"\n", &["src/lib.rs"]);
+        assert_eq!(
+            CrateKind::detect(support.path()),
+            Some(CrateKind::TestSupport)
+        );
+        let workspace = crate_dir("[workspace]\nmembers = [\"app\"]\n", &[]);
+        assert_eq!(CrateKind::detect(workspace.path()), None);
+    }
+
+    /// `toml` loaded as the user config, so its settings have origins.
+    fn load(toml: &str) -> GuardianConfig {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("config.toml");
+        fs::write(&path, toml).unwrap();
+        GuardianConfig::load(Some(&path), None, None, None).unwrap()
+    }
+
+    #[test]
+    fn test_apply_crate_kind() {
+        let support = crate_dir("[package]\nname = \"app-fixtures\"\n", &["src/lib.rs"]);
+        let toml = "[check]\nwarn_loc = 300\n[check.kinds.test-support]\nmax_functions = 20\n";
+        let mut cfg = load(toml);
+        assert_eq!(
+            cfg.apply_crate_kind(support.path()),
+            Some(CrateKind::TestSupport)
+        );
+        assert_eq!(cfg.check.max_loc, 800);
+        assert_eq!(cfg.check.warn_loc, 300);
+        assert_eq!(cfg.check.max_functions, 20);
+        assert!(!cfg.checks["test-quality"].enabled);
+
+        let mut cfg = load("[check]\nmax_loc = 600\n[checks.test-quality]\n");
+        cfg.apply_crate_kind(support.path());
+        assert_eq!((cfg.check.max_loc, cfg.check.warn_loc), (600, 600));
+        assert!(cfg.checks["test-quality"].enabled);
+
+        let mut cfg = load("[check]\ncrate_kind = \"lib\"\n");
+        assert_eq!(cfg.apply_crate_kind(support.path()), Some(CrateKind::Lib));
+        assert_eq!(cfg.check.max_loc, 500);
+        assert!(cfg.checks.is_empty());
+    }
+
+    #[test]
+    fn test_configured_default_beats_kind() {
+        let bin = crate_dir("[package]\nname = \"app\"\n", &["src/main.rs"]);
+        let mut cfg = load("");
+        cfg.apply_crate_kind(bin.path());
+        assert_eq!(cfg.check.max_functions, 10);
+
+        // 7 is also the default, but the config chose it
+        let mut cfg = load("[check]\nmax_functions = 7\n");
+        cfg.apply_crate_kind(bin.path());
+        assert_eq!(cfg.check.max_functions, 7);
+    }
+}
@@ -0,0 +1,326 @@
+//! Layered configuration: a project config, a named profile, and
+//! environment variables merged over the user config, all over an optional
+//! team policy config.
+//!
+//! Precedence, highest first: command-line flags, `GUARDIAN_*` environment
+//! variables, the selected `[profile.NAME]` (project, then user), the
+//! project's `.guardian.toml` (or `guardian.toml`), the user config, the
+//! `--policy-url` config, built-in defaults.
+//! Tables merge key by key; any other value, including an array such as
+//! `[[ollama.hosts]]`, replaces the lower layer's value whole.
+//!
+//! A project config is trusted less than the others; see [`project`].
+
+mod project;
+
+use super::extends;
+use anyhow::{Result, bail};
+use std::path::{Path, PathBuf};
+
+/// File names recognized as a project config, in order of preference.
+const PROJECT_CONFIG_NAMES: &[&str] = &[
+    ".guardian.toml",
+    "guardian.toml",
+    ".guardian.yaml",
+    "guardian.yaml",
+    ".guardian.yml",
+    "guardian.yml",
+    ".guardian.json",
+    "guardian.json",
+];
+
+/// Which layer a setting came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigLayer {
+    /// The team policy config named by `--policy-url` or `GUARDIAN_POLICY_URL`.
+    Policy,
+    /// The user-level config (`~/.config/guardian-cli/guardian.toml` or `--config`).
+    User,
+    /// The project config found at or above the project directory.
+    Project,
+    /// The `[profile.NAME]` section selected with `--profile`.
+    Profile,
+    /// A `GUARDIAN_*` environment variable.
+    Env,
+}
+
+impl ConfigLayer {
+    /// Lowercase name used in `show-config --origin` output.
+    pub fn name(self) -> &'static str {
+        match self {
+            ConfigLayer::Policy => "policy",
+            ConfigLayer::User => "user",
+            ConfigLayer::Project => "project",
+            ConfigLayer::Profile => "profile",
+            ConfigLayer::Env => "env",
+        }
+    }
+}
+
+/// One setting read from a config file or variable, as shown by
+/// `show-config --origin`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettingOrigin {
+    /// Dotted key, e.g. `check.max_loc` or `ollama.hosts`.
+    pub key: String,
+    /// The effective value.
+    pub value: toml::Value,
+    pub layer: ConfigLayer,
+    /// The file path or variable name that set the value.
+    pub source: String,
+}
+
+/// Find the project config for `start`.
+///
+/// Searches `start` and its parents, stopping at the repository root (the
+/// first directory containing `.git`), so a config outside the repository
+/// is never picked up.
+pub fn find_project_config(start: &Path) -> Option<PathBuf> {
+    for dir in start.ancestors() {
+        let found = PROJECT_CONFIG_NAMES
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file());
+        if found.is_some() {
+            return found;
+        }
+        if dir.join(".git").exists() {
+            break;
+        }
+    }
+    None
+}
+
+/// Read config files and the bases they extend, lowest precedence first,
+/// followed by their `[profile.NAME]` sections when `profile` is selected.
+///
+/// Each file is a path, or a remote source for the policy layer.
+pub(super) fn file_layers(
+    files: &[(ConfigLayer, &str)],
+    profile: Option<&str>,
+) -> Result<Vec<(ConfigLayer, String, toml::Table)>> {
+    let mut layers = Vec::new();
+    let mut profiles = Vec::new();
+    let chain = files
+        .iter()
+        .map(|&(layer, path)| {
+            let root =
+                (layer == ConfigLayer::Project).then(|| project::project_root(Path::new(path)));
+            Ok((layer, extends::load_chain(path, root.as_deref())?))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    for (layer, source, table) in chain
+        .into_iter()
+        .flat_map(|(layer, chain)| chain.into_iter().map(move |(s, t)| (layer, s, t)))
+    {
+        if layer == ConfigLayer::Project {
+            project::check(&source, &table)?;
+        }
+        if let (Some(name), Some(toml::Value::Table(defined))) = (profile, table.get("profile")) {
+            if let Some(toml::Value::Table(section)) = defined.get(name) {
+                if section.contains_key("profile") {
+                    bail!("[profile.{name}] in {source} cannot define profiles of its own");
+                }
+                let source = format!("{source} (profile.{name})");
+                profiles.push((ConfigLayer::Profile, source, section.clone()));
+            }
+        }
+        layers.push((layer, source, table));
+    }
+    if let Some(name) = profile {
+        if profiles.is_empty() {
+            bail!("Profile '{name}' is not defined in any config file");
+        }
+    }
+    layers.extend(profiles);
+    Ok(layers)
+}
+
+/// Merge layers, lowest precedence first, and record where each setting
+/// came from. Each layer is a source name and its table.
+pub(super) fn merge_layers(
+    layers: Vec<(ConfigLayer, String, toml::Table)>,
+) -> (toml::Table, Vec<SettingOrigin>) {
+    let mut merged = toml::Table::new();
+    let mut origins = Vec::new();
+    for (layer, source, table) in layers {
+        let mut keys = Vec::new();
+        flatten("", &table, &mut keys);
+        // Profile definitions are only in effect through the profile layer.
+        keys.retain(|(key, _)| !key.starts_with("profile."));
+        // A key set again by a higher layer belongs to that layer now.
+        origins.retain(|o: &SettingOrigin| {
+            !keys
+                .iter()
+                .any(|(k, _)| o.key == *k || o.key.starts_with(&format!("{k}.")))
+        });
+        origins.extend(keys.into_iter().map(|(key, value)| SettingOrigin {
+            key,
+            value,
+            layer,
+            source: source.clone(),
+        }));
+        merge(&mut merged, table);
+    }
+    origins.sort_by(|a, b| a.key.cmp(&b.key));
+    (merged, origins)
+}
+
+fn merge(base: &mut toml::Table, over: toml::Table) {
+    for (key, value) in over {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(over)) => merge(base, over),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+fn flatten(prefix: &str, table: &toml::Table, out: &mut Vec<(String, toml::Value)>) {
+    for (key, value) in table {
+        let key = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{prefix}.{key}")
+        };
+        match value {
+            toml::Value::Table(table) => flatten(&key, table, out),
+            value => out.push((key, value.clone())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GuardianConfig;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_project_overrides_user() {
+        let temp = TempDir::new().unwrap();
+        let user = temp.path().join("user.toml");
+        let project = temp.path().join(".guardian.toml");
+        fs::write(
+            &user,
+            "[check]\nmax_loc = 600\nwarn_loc = 400\n\n[routing]\nsummarize = { model = \"phi4\", host = \"big72\" }\n",
+        )
+        .unwrap();
+        fs::write(
+            &project,
+            "[check]\nmax_loc = 800\n\n[routing]\nsummarize = \"llama3.2\"\n",
+        )
+        .unwrap();
+
+        let files = [
+            (ConfigLayer::User, user.to_str().unwrap()),
+            (ConfigLayer::Project, project.to_str().unwrap()),
+        ];
+        let (merged, origins) = merge_layers(file_layers(&files, None).unwrap());
+        assert_eq!(merged["check"]["max_loc"].as_integer(), Some(800));
+        assert_eq!(merged["check"]["warn_loc"].as_integer(), Some(400));
+
+        let layer_of = |key: &str| origins.iter().find(|o| o.key == key).map(|o| o.layer);
+        assert_eq!(layer_of("check.max_loc"), Some(ConfigLayer::Project));
+        assert_eq!(layer_of("check.warn_loc"), Some(ConfigLayer::User));
+        assert_eq!(layer_of("routing.summarize"), Some(ConfigLayer::Project));
+        assert_eq!(layer_of("routing.summarize.host"), None);
+    }
+
+    #[test]
+    fn test_profile_overrides_project() {
+        let temp = TempDir::new().unwrap();
+        let user = temp.path().join("user.toml");
+        let project = temp.path().join(".guardian.toml");
+        fs::write(
+            &user,
+            "[check]\nmax_loc = 600\n\n[profile.strict.check]\nmax_loc = 300\n",
+        )
+        .unwrap();
+        fs::write(&project, "[check]\nmax_loc = 800\nwarn_loc = 700\n").unwrap();
+        let files = [
+            (ConfigLayer::User, user.to_str().unwrap()),
+            (ConfigLayer::Project, project.to_str().unwrap()),
+        ];
+
+        let (merged, origins) = merge_layers(file_layers(&files, Some("strict")).unwrap());
+        assert_eq!(merged["check"]["max_loc"].as_integer(), Some(300));
+        assert_eq!(merged["check"]["warn_loc"].as_integer(), Some(700));
+        assert_eq!(origins[0].key, "check.max_loc");
+        assert_eq!(origins[0].layer, ConfigLayer::Profile);
+        assert!(origins.iter().all(|o| !o.key.starts_with("profile.")));
+
+        let (merged, _) = merge_layers(file_layers(&files, None).unwrap());
+        assert_eq!(merged["check"]["max_loc"].as_integer(), Some(800));
+
+        let err = file_layers(&files, Some("ci")).unwrap_err();
+        assert!(err.to_string().contains("Profile 'ci' is not defined"));
+    }
+
+    #[test]
+    fn test_find_project_config_stops_at_repo_root() {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path().join("repo");
+        let sub = repo.join("src");
+        fs::create_dir_all(&sub).unwrap();
+        fs::write(temp.path().join("guardian.toml"), "").unwrap();
+        assert_eq!(
+            find_project_config(&sub),
+            Some(temp.path().join("guardian.toml"))
+        );
+
+        fs::create_dir(repo.join(".git")).unwrap();
+        assert_eq!(find_project_config(&sub), None);
+
+        fs::write(repo.join("guardian.toml"), "").unwrap();
+        fs::write(repo.join(".guardian.toml"), "").unwrap();
+        assert_eq!(find_project_config(&sub), Some(repo.join(".guardian.toml")));
+    }
+
+    #[test]
+    fn test_load_project_without_user_config() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir(temp.path().join(".git")).unwrap();
+        fs::write(
+            temp.path().join(".guardian.toml"),
+            "[check]\nmax_functions = 10\n",
+        )
+        .unwrap();
+
+        let missing = temp.path().join("missing.toml");
+        let cfg = GuardianConfig::load(Some(&missing), Some(temp.path()), None, None).unwrap();
+        assert_eq!(cfg.check.max_functions, 10);
+        assert_eq!(cfg.origins.len(), 1);
+        assert_eq!(cfg.origins[0].layer, ConfigLayer::Project);
+
+        let cfg = GuardianConfig::load(Some(&missing), None, None, None).unwrap();
+        assert_eq!(cfg.check.max_functions, 7);
+        assert!(cfg.origins.is_empty());
+    }
+
+    #[test]
+    fn test_load_yaml_and_json_configs() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir(temp.path().join(".git")).unwrap();
+        let user = temp.path().join("guardian.json");
+        fs::write(&user, r#"{"check": {"max_loc": 600, "warn_loc": 400}}"#).unwrap();
+        fs::write(
+            temp.path().join(".guardian.yaml"),
+            "extends: [base.json]\ncheck:\n  max_loc: 800\n",
+        )
+        .unwrap();
+        fs::write(
+            temp.path().join("base.json"),
+            r#"{"check": {"max_functions": 9}}"#,
+        )
+        .unwrap();
+
+        let cfg = GuardianConfig::load(Some(&user), Some(temp.path()), None, None).unwrap();
+        assert_eq!(cfg.check.max_loc, 800);
+        assert_eq!(cfg.check.warn_loc, 400);
+        assert_eq!(cfg.check.max_functions, 9);
+        assert!(cfg.files[2].ends_with(".guardian.yaml"));
+    }
+}
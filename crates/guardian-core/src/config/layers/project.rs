@@ -0,0 +1,79 @@
+//! What a project config may do.
+//!
+//! A project config, the bases it extends, and its profiles can't define
+//! `[[ollama.hosts]]`, `[[notify]]`, or where `annotate --gitea` posts and
+//! which token it sends: a repository being checked could otherwise send
+//! prompts, check results, or the credentials the config names wherever it
+//! liked. Its local files must stay within the project.
+
+use anyhow::{Result, bail};
+use std::path::{Path, PathBuf};
+
+/// The directory a project config's local files must stay within: the
+/// repository holding `config`, or its own directory outside one.
+pub(super) fn project_root(config: &Path) -> PathBuf {
+    let dir = config.parent().unwrap_or(Path::new("."));
+    let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    let repo = dir.ancestors().find(|dir| dir.join(".git").exists());
+    repo.unwrap_or(&dir).to_path_buf()
+}
+
+/// Settings only the user config, the team policy, or the named variable
+/// may have, by path.
+const USER_ONLY: &[(&[&str], &str, Option<&str>)] = &[
+    (
+        &["ollama", "hosts"],
+        "[[ollama.hosts]]",
+        Some("GUARDIAN_OLLAMA_HOSTS"),
+    ),
+    (&["notify"], "[[notify]]", None),
+    (
+        &["annotate", "gitea", "base_url"],
+        "[annotate.gitea] base_url",
+        None,
+    ),
+    (
+        &["annotate", "gitea", "token_env"],
+        "[annotate.gitea] token_env",
+        None,
+    ),
+    (
+        &["annotate", "gitea", "token_keyring"],
+        "[annotate.gitea] token_keyring",
+        None,
+    ),
+];
+
+/// Refuse a project config, or a base it extends, named `source` whose
+/// `table` has settings only the user config or team policy may have.
+pub(super) fn check(source: &str, table: &toml::Table) -> Result<()> {
+    let defined = table.get("profile").and_then(toml::Value::as_table);
+    let sections = defined.into_iter().flat_map(|defined| defined.values());
+    let tables = std::iter::once(table).chain(sections.filter_map(toml::Value::as_table));
+    for table in tables {
+        let found = USER_ONLY.iter().find(|(path, _, _)| contains(table, path));
+        match found {
+            Some((_, name, Some(var))) => bail!(
+                "{source} defines {name}, which only the user config, \
+                 the team policy, or {var} may do"
+            ),
+            Some((_, name, None)) => bail!(
+                "{source} defines {name}, which only the user config or \
+                 the team policy may do"
+            ),
+            None => {}
+        }
+    }
+    Ok(())
+}
+
+/// Whether `table` sets the key at `path`.
+fn contains(table: &toml::Table, path: &[&str]) -> bool {
+    let Some((last, parents)) = path.split_last() else {
+        return false;
+    };
+    let parent = parents.iter().try_fold(table, |table, key| {
+        table.get(*key).and_then(toml::Value::as_table)
+    });
+    parent.is_some_and(|parent| parent.contains_key(*last))
+}
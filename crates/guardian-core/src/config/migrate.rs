@@ -0,0 +1,247 @@
+//! Upgrading configs written for older layouts to the current schema.
+//!
+//! Each rule moves one key: a rename such as `ollama.timeout_ms` to
+//! `ollama.default_timeout_ms`, or a section moved to a new home such as
+//! `[ollama.routing]` to `[routing]`. The file is edited in place, so
+//! comments and formatting survive. Rules also apply inside each
+//! `[profile.NAME]`.
+
+use super::format::Format;
+use anyhow::{Context, Result, bail};
+use toml_edit::{DocumentMut, Item, Key, Table, TableLike};
+
+/// Old keys and where they live now. `*` matches every table in an array,
+/// such as each `[[ollama.hosts]]` entry.
+const MIGRATIONS: &[(&str, &str)] = &[
+    ("profiles", "profile"),
+    ("ollama.timeout_ms", "ollama.default_timeout_ms"),
+    ("ollama.host", "ollama.default_host"),
+    ("ollama.model", "ollama.default_model"),
+    ("ollama.hosts.*.url", "ollama.hosts.*.base_url"),
+    ("ollama.routing", "routing"),
+    ("thresholds", "check"),
+];
+
+/// One old key found by `migrate`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Migration {
+    /// The old dotted key, e.g. `ollama.hosts[0].url`.
+    pub from: String,
+    /// The key it belongs under now.
+    pub to: String,
+    /// False when the new key was already set, so the old one was left in
+    /// place for the user to reconcile.
+    pub applied: bool,
+}
+
+/// The result of migrating one config file.
+#[derive(Debug)]
+pub struct Migrated {
+    /// The upgraded file contents.
+    pub contents: String,
+    /// Every old key found, in rule order.
+    pub changes: Vec<Migration>,
+    /// The first problem the current schema still reports, if any.
+    pub problem: Option<String>,
+}
+
+/// Upgrade the config in `contents`, read from `source`.
+pub fn migrate(source: &str, contents: &str) -> Result<Migrated> {
+    if Format::of(source) != Format::Toml {
+        bail!("Only TOML configs can be migrated; rename the old keys in {source} by hand");
+    }
+    let mut doc: DocumentMut = contents
+        .parse()
+        .with_context(|| format!("Failed to parse TOML config at {source}"))?;
+    let mut changes = Vec::new();
+    let rules: Vec<(Vec<&str>, Vec<&str>)> = MIGRATIONS
+        .iter()
+        .map(|(from, to)| (from.split('.').collect(), to.split('.').collect()))
+        .collect();
+
+    for (from, to) in &rules {
+        apply(doc.as_table_mut(), from, to, "", &mut changes);
+    }
+    if let Some(profiles) = doc.get_mut("profile").and_then(Item::as_table_like_mut) {
+        for (name, item) in profiles.iter_mut() {
+            let at = format!("profile.{}.", name.get());
+            if let Some(profile) = item.as_table_like_mut() {
+                for (from, to) in &rules {
+                    apply(profile, from, to, &at, &mut changes);
+                }
+            }
+        }
+    }
+
+    let contents = doc.to_string();
+    let problem =
+        super::validate::validate(&format!("{source} (migrated)"), Format::Toml, &contents)
+            .err()
+            .map(|err| err.to_string());
+    Ok(Migrated {
+        contents,
+        changes,
+        problem,
+    })
+}
+
+/// Apply one rule to `table`, whose dotted path is `at`.
+fn apply(
+    table: &mut dyn TableLike,
+    from: &[&str],
+    to: &[&str],
+    at: &str,
+    changes: &mut Vec<Migration>,
+) {
+    // Walk down the part of the path the old and new keys share.
+    if from.len() > 1 && to.len() > 1 && from[0] == to[0] {
+        let Some(item) = table.get_mut(from[0]) else {
+            return;
+        };
+        let here = format!("{at}{}", from[0]);
+        if from[1] != "*" {
+            if let Some(inner) = item.as_table_like_mut() {
+                apply(inner, &from[1..], &to[1..], &format!("{here}."), changes);
+            }
+            return;
+        }
+        let entries: Vec<&mut dyn TableLike> = if let Some(array) = item.as_array_of_tables_mut() {
+            array.iter_mut().map(|t| t as &mut dyn TableLike).collect()
+        } else if let Some(array) = item.as_array_mut() {
+            array
+                .iter_mut()
+                .filter_map(|v| v.as_inline_table_mut())
+                .map(|t| t as &mut dyn TableLike)
+                .collect()
+        } else {
+            Vec::new()
+        };
+        for (i, entry) in entries.into_iter().enumerate() {
+            let at = format!("{here}[{i}].");
+            apply(entry, &from[2..], &to[2..], &at, changes);
+        }
+        return;
+    }
+
+    if find(table, from).is_none() {
+        return;
+    }
+    let applied = find(table, to).is_none();
+    if applied {
+        if let Some((key, item)) = take(table, from) {
+            put(table, to, &key, item);
+        }
+    }
+    changes.push(Migration {
+        from: format!("{at}{}", from.join(".")),
+        to: format!("{at}{}", to.join(".")),
+        applied,
+    });
+}
+
+fn find<'a>(table: &'a dyn TableLike, path: &[&str]) -> Option<&'a Item> {
+    match path {
+        [key] => table.get(key),
+        [key, rest @ ..] => find(table.get(key)?.as_table_like()?, rest),
+        [] => None,
+    }
+}
+
+/// Remove the item at `path`, with its key so comments on it can move too.
+fn take(table: &mut dyn TableLike, path: &[&str]) -> Option<(Key, Item)> {
+    match path {
+        [key] => {
+            let old = table.get_key_value(key)?.0.clone();
+            Some((old, table.remove(key)?))
+        }
+        [key, rest @ ..] => take(table.get_mut(key)?.as_table_like_mut()?, rest),
+        [] => None,
+    }
+}
+
+/// Insert `item` at `path`, creating tables on the way as needed.
+fn put(table: &mut dyn TableLike, path: &[&str], old: &Key, item: Item) {
+    match path {
+        [key] => {
+            let key = Key::new(*key).with_leaf_decor(old.leaf_decor().clone());
+            table.entry_format(&key).or_insert(item);
+        }
+        [key, rest @ ..] => {
+            if !table.contains_key(key) {
+                let mut inner = Table::new();
+                inner.set_implicit(true);
+                table.insert(key, Item::Table(inner));
+            }
+            if let Some(inner) = table.get_mut(key).and_then(Item::as_table_like_mut) {
+                put(inner, rest, old, item);
+            }
+        }
+        [] => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_renames_and_moves() {
+        let old = r#"# Shared hosts
+[ollama]
+# Slow network
+timeout_ms = 4000
+model = "phi4"
+
+[[ollama.hosts]]
+name = "big72"
+url = "http://big72:11434"
+
+[ollama.routing]
+summarize = "llama3.2"
+
+[profiles.ci.ollama]
+timeout_ms = 9000
+"#;
+        let migrated = migrate("guardian.toml", old).unwrap();
+        assert_eq!(migrated.problem, None);
+        assert!(migrated.changes.iter().all(|c| c.applied));
+        let moved: Vec<_> = migrated.changes.iter().map(|c| c.from.as_str()).collect();
+        assert_eq!(
+            moved,
+            [
+                "profiles",
+                "ollama.timeout_ms",
+                "ollama.model",
+                "ollama.hosts[0].url",
+                "ollama.routing",
+                "profile.ci.ollama.timeout_ms"
+            ]
+        );
+        assert!(
+            migrated
+                .contents
+                .contains("# Slow network\ndefault_timeout_ms = 4000")
+        );
+        assert!(migrated.contents.contains("[routing]\nsummarize"));
+
+        let cfg: crate::config::GuardianConfig = toml::from_str(&migrated.contents).unwrap();
+        assert_eq!(cfg.default_timeout_ms(), 4000);
+        assert_eq!(cfg.ollama.hosts[0].base_url, "http://big72:11434");
+        assert_eq!(cfg.routing["summarize"].model.as_deref(), Some("llama3.2"));
+        assert_eq!(cfg.profile["ci"].ollama.default_timeout_ms, Some(9000));
+    }
+
+    #[test]
+    fn test_migrate_leaves_conflicts_and_current_configs() {
+        let old = "[ollama]\nmodel = \"phi4\"\ndefault_model = \"qwen\"\n";
+        let migrated = migrate("guardian.toml", old).unwrap();
+        assert_eq!(migrated.contents, old);
+        assert!(!migrated.changes[0].applied);
+        assert!(migrated.problem.unwrap().contains("unknown key `model`"));
+
+        let current = "[check]\nmax_loc = 300\n";
+        let migrated = migrate("guardian.toml", current).unwrap();
+        assert!(migrated.changes.is_empty());
+        assert_eq!(migrated.contents, current);
+    }
+}
@@ -0,0 +1,350 @@
+//! Configuration loading for Guardian CLI.
+//!
+//! Loads configuration from TOML (or YAML or JSON) files, supporting:
+//! - Ollama host definitions with fallback support, tags, and groups
+//! - Default timeout and model settings
+//! - Circuit breaker settings for flaky hosts
+//! - Task-based model routing
+//! - Daemon (serve mode) settings
+//! - Terminal output settings
+//! - Check thresholds, also per crate kind, architecture layers, and what
+//!   the model-run readability check reviews
+//! - The rubric `evaluate`'s model applies
+//! - Webhooks notified of events
+//! - Jobs `serve` runs on cron schedules
+//! - Result history retention
+//! - The sub-projects of a monorepo
+//! - Forges `annotate` posts to
+//! - Shared base configs pulled in with `extends` or `--policy-url`, from
+//!   files, HTTPS URLs, or git repositories, optionally pinned
+//! - A project config and `GUARDIAN_*` environment variables layered over
+//!   the user config
+//! - Upgrading configs written for older layouts
+//! - Policy and script configurations (future)
+
+mod annotate;
+mod architecture;
+pub mod cache_stats;
+mod check;
+mod circuit;
+mod env;
+mod extends;
+mod format;
+mod groups;
+mod hosts;
+mod jobs;
+mod kinds;
+mod layers;
+mod migrate;
+mod notify;
+mod ollama;
+mod output;
+mod pin;
+mod projects;
+mod readability;
+mod remote;
+mod routing;
+mod rubric;
+mod scan;
+mod schedule;
+mod secret;
+mod serve;
+pub mod state;
+mod storage;
+mod toggle;
+mod validate;
+
+pub use annotate::{AnnotateSettings, GiteaSettings};
+pub use architecture::ArchitectureRules;
+pub use check::{CheckSettings, ClippySettings};
+pub use circuit::CircuitBreakerSettings;
+pub use groups::HostGroup;
+pub use hosts::{HostAuth, OllamaHost, SecretSource};
+pub use jobs::jobs;
+pub use kinds::{CrateKind, CrateKinds, KindSettings};
+pub use layers::{ConfigLayer, SettingOrigin, find_project_config};
+pub use migrate::migrate;
+pub use notify::{NotifyEvent, NotifyTarget, WebhookKind};
+pub use ollama::{ContextOverflow, OllamaSection, UnverifiedReferences};
+pub use output::{Hyperlinks, OutputSettings};
+pub use pin::sha256_hex;
+pub use projects::ProjectSettings;
+pub use readability::ReadabilitySettings;
+pub use routing::TaskRoute;
+pub use rubric::{LowConfidence, RubricSettings};
+pub use schedule::{Cron, ScheduledCommand, ScheduledJob};
+pub use secret::{delete_secret, get_secret, set_secret};
+pub use serve::ServeSettings;
+pub use storage::StorageSettings;
+pub use toggle::CheckToggle;
+
+use anyhow::{Context, Result};
+use directories::ProjectDirs;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Root configuration structure for Guardian CLI.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct GuardianConfig {
+    /// Base configs (paths or URLs) merged beneath this one. Only checked
+    /// here; bases are resolved while loading.
+    #[serde(default, rename = "extends")]
+    _extends: Vec<String>,
+
+    /// Ollama-related configuration.
+    #[serde(default)]
+    pub ollama: OllamaSection,
+
+    /// Per-task model routing, keyed by task name.
+    #[serde(default)]
+    pub routing: BTreeMap<String, TaskRoute>,
+
+    /// Daemon (serve mode) configuration.
+    #[serde(default)]
+    pub serve: ServeSettings,
+
+    /// Terminal output configuration.
+    #[serde(default)]
+    pub output: OutputSettings,
+
+    /// Thresholds for `check`.
+    #[serde(default)]
+    pub check: CheckSettings,
+
+    /// Per-check settings, keyed by check name.
+    #[serde(default)]
+    pub checks: BTreeMap<String, CheckToggle>,
+
+    /// The rubric `evaluate`'s model applies.
+    #[serde(default)]
+    pub rubric: RubricSettings,
+
+    /// Webhooks notified of events.
+    #[serde(default)]
+    pub notify: Vec<NotifyTarget>,
+
+    /// Jobs `serve` runs on cron schedules.
+    #[serde(default)]
+    pub schedule: Vec<ScheduledJob>,
+
+    /// Result history settings.
+    #[serde(default)]
+    pub storage: StorageSettings,
+
+    /// The sub-projects `check --recursive` checks.
+    #[serde(default)]
+    pub projects: ProjectSettings,
+
+    /// Forges `annotate` posts to.
+    #[serde(default)]
+    pub annotate: AnnotateSettings,
+
+    /// Named profiles, selected with `--profile` or `GUARDIAN_PROFILE`.
+    #[serde(default)]
+    pub profile: BTreeMap<String, Box<GuardianConfig>>,
+
+    /// The profile in effect, if any.
+    #[serde(skip)]
+    pub active_profile: Option<String>,
+
+    /// Config files and bases that were merged, lowest precedence first.
+    #[serde(skip)]
+    pub files: Vec<String>,
+
+    /// Settings read from config files and where each came from.
+    #[serde(skip)]
+    pub origins: Vec<SettingOrigin>,
+}
+
+impl GuardianConfig {
+    /// Load the user configuration, with the project config for
+    /// `project_dir`, the named `profile`, and `GUARDIAN_*` environment
+    /// variables merged over it, all over the team `policy` config.
+    ///
+    /// If `config_path` is `None`, the user config is loaded from the default
+    /// location. If `project_dir` is `None`, no project config is looked for.
+    /// If `profile` is `None`, `GUARDIAN_PROFILE` selects the profile, and if
+    /// `policy` is `None`, `GUARDIAN_POLICY_URL` names the policy.
+    pub fn load(
+        config_path: Option<&Path>,
+        project_dir: Option<&Path>,
+        profile: Option<&str>,
+        policy: Option<&str>,
+    ) -> Result<Self> {
+        let user = match config_path {
+            Some(p) => p.to_path_buf(),
+            None => default_config_path().context("Could not determine default config path")?,
+        };
+        let project = project_dir
+            .and_then(|dir| dir.canonicalize().ok())
+            .and_then(|dir| find_project_config(&dir));
+        let profile = profile
+            .map(str::to_string)
+            .or_else(|| std::env::var("GUARDIAN_PROFILE").ok())
+            .filter(|p| !p.is_empty());
+        let policy = policy
+            .map(str::to_string)
+            .or_else(|| std::env::var("GUARDIAN_POLICY_URL").ok())
+            .filter(|p| !p.is_empty());
+
+        let paths = [
+            (ConfigLayer::User, Some(user.as_path())),
+            (ConfigLayer::Project, project.as_deref()),
+        ]
+        .into_iter()
+        .filter_map(|(layer, path)| Some((layer, path.filter(|p| p.exists())?)))
+        .map(|(layer, path)| (layer, path.to_string_lossy()));
+        let files: Vec<_> = policy
+            .iter()
+            .map(|policy| (ConfigLayer::Policy, policy.into()))
+            .chain(paths)
+            .collect();
+        let files: Vec<_> = files.iter().map(|(l, f)| (*l, f.as_ref())).collect();
+        let mut layers = layers::file_layers(&files, profile.as_deref())?;
+        let loaded: Vec<String> = layers
+            .iter()
+            .filter(|(layer, _, _)| *layer != ConfigLayer::Profile)
+            .map(|(_, source, _)| source.clone())
+            .collect();
+        let vars = env::env_layers(|var| std::env::var(var).ok())?;
+        layers.extend(
+            vars.into_iter()
+                .map(|(var, table)| (ConfigLayer::Env, var, table)),
+        );
+
+        if layers.is_empty() {
+            tracing::warn!(
+                "Config file not found at {}, using defaults",
+                user.display()
+            );
+        }
+
+        let (table, origins) = layers::merge_layers(layers);
+        let mut cfg = Self::deserialize(toml::Value::Table(table))
+            .context("Failed to merge config layers")?;
+        cfg.apply_host_groups()?;
+        cfg.origins = origins;
+        cfg.files = loaded;
+        cfg.active_profile = profile;
+        Ok(cfg)
+    }
+
+    /// Get the default timeout in milliseconds.
+    pub fn default_timeout_ms(&self) -> u64 {
+        self.ollama.default_timeout_ms.unwrap_or(2500)
+    }
+}
+
+/// Get the default configuration file path.
+///
+/// Returns `~/.config/guardian-cli/guardian.toml` on Unix systems, or the
+/// `guardian.yaml`, `guardian.yml`, or `guardian.json` there when only that
+/// exists.
+pub fn default_config_path() -> Option<PathBuf> {
+    let proj = ProjectDirs::from("com", "softwarewrighter", "guardian-cli")?;
+    let dir = proj.config_dir();
+    let existing = format::USER_CONFIG_NAMES
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.is_file());
+    Some(existing.unwrap_or_else(|| dir.join(format::USER_CONFIG_NAMES[0])))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_minimal_config() {
+        let toml = r#"
+[ollama]
+"#;
+        let cfg: GuardianConfig = toml::from_str(toml).unwrap();
+        assert!(cfg.ollama.hosts.is_empty());
+        assert_eq!(cfg.default_timeout_ms(), 2500);
+    }
+
+    #[test]
+    fn test_parse_full_config() {
+        let toml = r#"
+[ollama]
+default_timeout_ms = 3000
+default_host = "big72"
+default_model = "qwen2.5-coder:7b"
+
+[[ollama.hosts]]
+name = "big72"
+base_url = "http://big72:11434"
+enabled = true
+fallback = false
+description = "Main server"
+
+[[ollama.hosts]]
+name = "local"
+base_url = "http://localhost:11434"
+enabled = true
+fallback = true
+"#;
+        let cfg: GuardianConfig = toml::from_str(toml).unwrap();
+
+        assert_eq!(cfg.default_timeout_ms(), 3000);
+        assert_eq!(cfg.ollama.default_host, Some("big72".to_string()));
+        assert_eq!(cfg.ollama.hosts.len(), 2);
+
+        let primaries = cfg.primary_hosts();
+        assert_eq!(primaries.len(), 1);
+        assert_eq!(primaries[0].name, "big72");
+
+        let fallbacks = cfg.fallback_hosts();
+        assert_eq!(fallbacks.len(), 1);
+        assert_eq!(fallbacks[0].name, "local");
+    }
+
+    #[test]
+    fn test_host_enabled_defaults_to_true() {
+        let toml = r#"
+[[ollama.hosts]]
+name = "test"
+base_url = "http://test:11434"
+"#;
+        let cfg: GuardianConfig = toml::from_str(toml).unwrap();
+        assert!(cfg.ollama.hosts[0].enabled);
+        assert!(!cfg.ollama.hosts[0].fallback);
+        assert_eq!(cfg.ollama.hosts[0].weight, 1.0);
+        assert_eq!(cfg.ollama.hosts[0].auth, None);
+    }
+
+    #[test]
+    fn test_load_from_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("guardian.toml");
+
+        let toml = r#"
+[ollama]
+default_timeout_ms = 5000
+
+[[ollama.hosts]]
+name = "test"
+base_url = "http://test:11434"
+"#;
+        fs::write(&config_path, toml).unwrap();
+
+        let cfg = GuardianConfig::load(Some(&config_path), None, None, None).unwrap();
+        assert_eq!(cfg.default_timeout_ms(), 5000);
+        assert_eq!(cfg.ollama.hosts.len(), 1);
+    }
+
+    #[test]
+    fn test_load_missing_file_returns_defaults() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("nonexistent.toml");
+
+        let cfg = GuardianConfig::load(Some(&config_path), None, None, None).unwrap();
+        assert!(cfg.ollama.hosts.is_empty());
+        assert_eq!(cfg.default_timeout_ms(), 2500);
+    }
+}
@@ -0,0 +1,138 @@
+//! The `[[notify]]` configuration section: webhooks told about events.
+//!
+//! ```toml
+//! [[notify]]
+//! url = "https://hooks.slack.com/services/T000/B000/XXXX"
+//! kind = "slack"
+//! events = ["new_errors", "host_down"]
+//! ```
+
+use serde::Deserialize;
+
+/// A webhook to notify.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct NotifyTarget {
+    /// URL the payload is POSTed to.
+    pub url: String,
+
+    /// Payload shape the receiver expects.
+    #[serde(default)]
+    pub kind: WebhookKind,
+
+    /// Events to send; every event when empty.
+    #[serde(default)]
+    pub events: Vec<NotifyEvent>,
+
+    /// Jinja template rendering the request body, instead of the payload
+    /// `kind` implies. It sees the event's fields, like the `json` payload.
+    pub template: Option<String>,
+}
+
+/// Payload shape of a webhook.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookKind {
+    /// Every field of the event as a JSON object.
+    #[default]
+    Json,
+    /// A Slack incoming webhook message.
+    Slack,
+    /// A Discord webhook message.
+    Discord,
+}
+
+/// Something that happened that a webhook can be told about.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotifyEvent {
+    /// `check` found errors that its last run on the project didn't.
+    NewErrors,
+    /// `evaluate` finished.
+    Evaluate,
+    /// `serve`'s health monitor marked a healthy host down.
+    HostDown,
+    /// `watchdog` found new errors, or a lower score, than the last
+    /// known-good run.
+    Regression,
+}
+
+impl NotifyEvent {
+    /// Name of the event in configs and payloads.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::NewErrors => "new_errors",
+            Self::Evaluate => "evaluate",
+            Self::HostDown => "host_down",
+            Self::Regression => "regression",
+        }
+    }
+}
+
+impl NotifyTarget {
+    /// Whether this webhook wants to hear about `event`.
+    pub fn wants(&self, event: NotifyEvent) -> bool {
+        self.events.is_empty() || self.events.contains(&event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GuardianConfig;
+
+    #[test]
+    fn test_parse_notify_targets() {
+        let cfg: GuardianConfig = toml::from_str("").unwrap();
+        assert!(cfg.notify.is_empty());
+
+        let toml = r#"
+[[notify]]
+url = "https://hooks.slack.com/services/x"
+kind = "slack"
+events = ["new_errors"]
+
+[[notify]]
+url = "https://ci.example.com/hook"
+template = '{"text": "{{ text }}"}'
+"#;
+        let cfg: GuardianConfig = toml::from_str(toml).unwrap();
+        let [slack, generic] = &cfg.notify[..] else {
+            panic!("Expected two targets");
+        };
+        assert_eq!(slack.kind, WebhookKind::Slack);
+        assert!(slack.wants(NotifyEvent::NewErrors));
+        assert!(!slack.wants(NotifyEvent::HostDown));
+        assert_eq!(generic.kind, WebhookKind::Json);
+        assert!(generic.wants(NotifyEvent::Evaluate));
+        let watched = "[[notify]]\nurl = \"x\"\nevents = [\"regression\"]\n";
+        let cfg: GuardianConfig = toml::from_str(watched).unwrap();
+        assert!(cfg.notify[0].wants(NotifyEvent::Regression));
+
+        let bad = "[[notify]]\nurl = \"x\"\nevents = [\"deploy\"]\n";
+        assert!(toml::from_str::<GuardianConfig>(bad).is_err());
+    }
+
+    #[test]
+    fn test_project_cannot_define_notify() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::create_dir(temp.path().join(".git")).unwrap();
+        let (user, project) = (
+            temp.path().join("user.toml"),
+            temp.path().join(".guardian.toml"),
+        );
+        let notify = "[[notify]]\nurl = \"https://example.com/hook\"\n";
+        std::fs::write(&user, notify).unwrap();
+        std::fs::write(&project, "[check]\nmax_loc = 800\n").unwrap();
+        let load = || GuardianConfig::load(Some(&user), Some(temp.path()), None, None);
+        assert_eq!(load().unwrap().notify[0].url, "https://example.com/hook");
+
+        std::fs::write(&project, notify).unwrap();
+        let err = load().unwrap_err().to_string();
+        assert!(err.contains(".guardian.toml defines [[notify]]"), "{err}");
+
+        let profile = notify.replace("[[notify", "[[profile.ci.notify");
+        std::fs::write(&project, profile).unwrap();
+        assert!(load().is_err());
+    }
+}
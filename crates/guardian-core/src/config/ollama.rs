@@ -0,0 +1,236 @@
+//! The `[ollama]` configuration section.
+
+use super::{CircuitBreakerSettings, HostGroup, OllamaHost};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+
+/// Ollama-related configuration.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct OllamaSection {
+    /// Default timeout in milliseconds for HTTP requests.
+    #[serde(default)]
+    pub default_timeout_ms: Option<u64>,
+
+    /// Default host name to use when not specified.
+    #[serde(default)]
+    pub default_host: Option<String>,
+
+    /// Default model to use for LLM operations.
+    #[serde(default)]
+    pub default_model: Option<String>,
+
+    /// System prompt sent ahead of every prompt (persona, rules, output format).
+    #[serde(default)]
+    pub system_prompt: Option<String>,
+
+    /// Context window (`num_ctx`) to request for generations.
+    #[serde(default)]
+    pub num_ctx: Option<u64>,
+
+    /// What to do when a prompt won't fit in the model's context window.
+    #[serde(default)]
+    pub context_overflow: ContextOverflow,
+
+    /// Tokens an evaluation prompt may use before low-priority results are
+    /// left out (default: derived from the model's context window).
+    #[serde(default)]
+    pub prompt_budget: Option<u64>,
+
+    /// Pull the requested model automatically when the host doesn't have it.
+    #[serde(default)]
+    pub auto_pull: bool,
+
+    /// Largest model (in GB) that `auto_pull` may download.
+    #[serde(default = "default_auto_pull_max_gb")]
+    pub auto_pull_max_gb: f64,
+
+    /// Seconds a request may wait for a free slot on a host with `max_concurrent`.
+    #[serde(default = "default_queue_timeout_secs")]
+    pub queue_timeout_secs: u64,
+
+    /// Seconds a host's ping and model list are reused by later commands
+    /// (0 to always probe).
+    #[serde(default = "default_probe_cache_secs")]
+    pub probe_cache_secs: u64,
+
+    /// Probe hosts again instead of reusing recent probes; set by `--fresh`.
+    #[serde(skip)]
+    pub fresh: bool,
+
+    /// Most hosts pinged and requests in flight at once; set by `--jobs`,
+    /// one per core when unset.
+    #[serde(skip)]
+    pub jobs: Option<NonZeroUsize>,
+
+    /// Race `ask` requests against two hosts and keep the first response.
+    #[serde(default)]
+    pub race: bool,
+
+    /// How many times to re-prompt a model that returns invalid JSON.
+    #[serde(default = "default_json_retries")]
+    pub json_retries: u32,
+
+    /// What to do with file and line references in a review that don't
+    /// match the project.
+    #[serde(default)]
+    pub unverified_references: UnverifiedReferences,
+
+    /// Also check that code a review quotes next to a line reference is
+    /// near that line.
+    #[serde(default)]
+    pub verify_quotes: bool,
+
+    /// Circuit breaker settings for flaky hosts.
+    #[serde(default)]
+    pub circuit_breaker: CircuitBreakerSettings,
+
+    /// Host groups, keyed by name, whose settings apply to member hosts.
+    #[serde(default)]
+    pub groups: BTreeMap<String, HostGroup>,
+
+    /// List of configured Ollama hosts.
+    #[serde(default)]
+    pub hosts: Vec<OllamaHost>,
+}
+
+impl OllamaSection {
+    /// Seconds a request may wait for a free slot when `queue_timeout_secs`
+    /// isn't set.
+    pub const DEFAULT_QUEUE_TIMEOUT_SECS: u64 = 120;
+}
+
+impl Default for OllamaSection {
+    fn default() -> Self {
+        Self {
+            default_timeout_ms: None,
+            default_host: None,
+            default_model: None,
+            system_prompt: None,
+            num_ctx: None,
+            context_overflow: ContextOverflow::default(),
+            prompt_budget: None,
+            auto_pull: false,
+            auto_pull_max_gb: default_auto_pull_max_gb(),
+            queue_timeout_secs: default_queue_timeout_secs(),
+            probe_cache_secs: default_probe_cache_secs(),
+            fresh: false,
+            jobs: None,
+            race: false,
+            json_retries: default_json_retries(),
+            unverified_references: UnverifiedReferences::default(),
+            verify_quotes: false,
+            circuit_breaker: CircuitBreakerSettings::default(),
+            groups: BTreeMap::new(),
+            hosts: Vec::new(),
+        }
+    }
+}
+
+/// Policy for prompts estimated to exceed the model's context window.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ContextOverflow {
+    /// Print a warning and send the prompt anyway.
+    #[default]
+    Warn,
+    /// Refuse to send the prompt.
+    Error,
+}
+
+/// Policy for references to files or lines that don't exist, which small
+/// models invent.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum UnverifiedReferences {
+    /// Mark them as unverified.
+    #[default]
+    Flag,
+    /// Leave out the points that make them.
+    Strip,
+    /// Show them as the model wrote them.
+    Keep,
+}
+
+fn default_auto_pull_max_gb() -> f64 {
+    10.0
+}
+
+fn default_queue_timeout_secs() -> u64 {
+    OllamaSection::DEFAULT_QUEUE_TIMEOUT_SECS
+}
+
+fn default_probe_cache_secs() -> u64 {
+    30
+}
+
+fn default_json_retries() -> u32 {
+    2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ContextOverflow, UnverifiedReferences};
+    use crate::config::GuardianConfig;
+
+    #[test]
+    fn test_auto_pull_defaults() {
+        let cfg = GuardianConfig::default();
+        assert!(!cfg.ollama.auto_pull);
+        assert_eq!(cfg.ollama.auto_pull_max_gb, 10.0);
+
+        let cfg: GuardianConfig =
+            toml::from_str("[ollama]\nauto_pull = true\nauto_pull_max_gb = 4.5").unwrap();
+        assert!(cfg.ollama.auto_pull);
+        assert_eq!(cfg.ollama.auto_pull_max_gb, 4.5);
+    }
+
+    #[test]
+    fn test_unverified_references() {
+        let cfg = GuardianConfig::default();
+        assert_eq!(cfg.ollama.unverified_references, UnverifiedReferences::Flag);
+        assert!(!cfg.ollama.verify_quotes);
+        let toml = "[ollama]\nunverified_references = \"strip\"\nverify_quotes = true";
+        let cfg: GuardianConfig = toml::from_str(toml).unwrap();
+        assert_eq!(
+            cfg.ollama.unverified_references,
+            UnverifiedReferences::Strip
+        );
+        assert!(cfg.ollama.verify_quotes);
+        assert!(
+            toml::from_str::<GuardianConfig>("[ollama]\nunverified_references = \"hide\"").is_err()
+        );
+    }
+
+    #[test]
+    fn test_json_retries() {
+        assert_eq!(GuardianConfig::default().ollama.json_retries, 2);
+        let cfg: GuardianConfig = toml::from_str("[ollama]\njson_retries = 0").unwrap();
+        assert_eq!(cfg.ollama.json_retries, 0);
+    }
+
+    #[test]
+    fn test_probe_cache_secs() {
+        assert_eq!(GuardianConfig::default().ollama.probe_cache_secs, 30);
+        let cfg: GuardianConfig = toml::from_str("[ollama]\nprobe_cache_secs = 0").unwrap();
+        assert_eq!(cfg.ollama.probe_cache_secs, 0);
+    }
+
+    #[test]
+    fn test_parse_context_settings() {
+        let cfg = GuardianConfig::default();
+        assert_eq!(cfg.ollama.context_overflow, ContextOverflow::Warn);
+
+        let toml = r#"
+[ollama]
+num_ctx = 16384
+context_overflow = "error"
+prompt_budget = 6000
+"#;
+        let cfg: GuardianConfig = toml::from_str(toml).unwrap();
+        assert_eq!(cfg.ollama.num_ctx, Some(16384));
+        assert_eq!(cfg.ollama.context_overflow, ContextOverflow::Error);
+        assert_eq!(cfg.ollama.prompt_budget, Some(6000));
+    }
+}
@@ -0,0 +1,53 @@
+//! The `[output]` configuration section for human-readable output.
+
+use serde::Deserialize;
+
+/// Settings for terminal output.
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct OutputSettings {
+    /// When to wrap file paths in clickable terminal hyperlinks.
+    #[serde(default)]
+    pub hyperlinks: Hyperlinks,
+
+    /// Link target, with `{path}` (absolute), `{line}`, and `{column}`
+    /// placeholders, e.g. `vscode://file{path}:{line}:{column}`.
+    /// Defaults to a `file://` URL.
+    pub editor_url: Option<String>,
+}
+
+/// When to emit OSC 8 hyperlinks.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Hyperlinks {
+    /// Only on terminals known to support them.
+    #[default]
+    Auto,
+    /// Whenever output goes to a terminal.
+    Always,
+    /// Never.
+    Never,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::GuardianConfig;
+
+    #[test]
+    fn test_parse_output_settings() {
+        let cfg: GuardianConfig = toml::from_str("").unwrap();
+        assert_eq!(cfg.output.hyperlinks, super::Hyperlinks::Auto);
+
+        let toml = r#"
+[output]
+hyperlinks = "always"
+editor_url = "vscode://file{path}:{line}:{column}"
+"#;
+        let cfg: GuardianConfig = toml::from_str(toml).unwrap();
+        assert_eq!(cfg.output.hyperlinks, super::Hyperlinks::Always);
+        assert_eq!(
+            cfg.output.editor_url.as_deref(),
+            Some("vscode://file{path}:{line}:{column}")
+        );
+    }
+}
@@ -0,0 +1,124 @@
+//! Integrity pins for remote config bases.
+//!
+//! A remote `extends` entry (or `--policy-url`) may end in a fragment that
+//! pins what it must contain:
+//!
+//! - `#sha256=HEX`: the file's SHA-256 digest.
+//! - `#ed25519=HEX`: a public key; the file must come with a detached
+//!   signature, as hex, in `FILE.sig` next to it.
+//!
+//! A base that fails its pin is an error, never silently used.
+
+use anyhow::{Context, Result, bail};
+use ring::digest::{SHA256, digest};
+use ring::signature::{ED25519, UnparsedPublicKey};
+
+/// What a remote base must match.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Pin {
+    /// Lowercase hex SHA-256 of the contents.
+    Sha256(String),
+    /// Ed25519 public key that signed the contents.
+    Ed25519(Vec<u8>),
+}
+
+impl Pin {
+    /// Split a trailing `#sha256=…` or `#ed25519=…` pin off a source.
+    pub(super) fn split(entry: &str) -> Result<(&str, Option<Self>)> {
+        match entry.rsplit_once('#') {
+            Some((entry, fragment)) if fragment.contains('=') => {
+                Ok((entry, Some(Self::parse(fragment)?)))
+            }
+            _ => Ok((entry, None)),
+        }
+    }
+
+    /// Parse a `sha256=HEX` or `ed25519=HEX` fragment.
+    fn parse(fragment: &str) -> Result<Self> {
+        let (kind, value) = fragment
+            .split_once('=')
+            .context("expected #sha256=HEX or #ed25519=HEX")?;
+        let bytes = decode_hex(value)?;
+        match (kind, bytes.len()) {
+            ("sha256", 32) => Ok(Pin::Sha256(value.to_lowercase())),
+            ("ed25519", 32) => Ok(Pin::Ed25519(bytes)),
+            ("sha256" | "ed25519", n) => bail!("{kind} pin must be 32 bytes, got {n}"),
+            _ => bail!("unknown pin '{kind}', expected sha256 or ed25519"),
+        }
+    }
+
+    /// Check `contents` against the pin. Ed25519 pins need the `.sig` file's
+    /// contents as `signature`.
+    pub(super) fn verify(&self, contents: &str, signature: Option<&str>) -> Result<()> {
+        match self {
+            Pin::Sha256(expected) => {
+                let actual = sha256_hex(contents.as_bytes());
+                if actual != *expected {
+                    bail!("sha256 mismatch: expected {expected}, got {actual}");
+                }
+            }
+            Pin::Ed25519(key) => {
+                let signature = decode_hex(signature.context("missing signature")?.trim())
+                    .context("invalid signature file")?;
+                UnparsedPublicKey::new(&ED25519, key)
+                    .verify(contents.as_bytes(), &signature)
+                    .map_err(|_| anyhow::anyhow!("ed25519 signature does not match"))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Lowercase hex SHA-256 digest of `data`.
+pub fn sha256_hex(data: &[u8]) -> String {
+    digest(&SHA256, data)
+        .as_ref()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+fn decode_hex(hex: &str) -> Result<Vec<u8>> {
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        bail!("invalid hex '{hex}'");
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&hex[i..i + 2], 16).with_context(|| format!("invalid hex '{hex}'"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::rand::SystemRandom;
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+
+    const BASE: &str = "[check]\nmax_loc = 400\n";
+
+    #[test]
+    fn test_sha256_pin() {
+        let pin = Pin::parse(&format!("sha256={}", sha256_hex(BASE.as_bytes()))).unwrap();
+        assert!(pin.verify(BASE, None).is_ok());
+        let err = pin.verify("[check]\nmax_loc = 9000\n", None).unwrap_err();
+        assert!(err.to_string().starts_with("sha256 mismatch"));
+
+        assert!(Pin::parse("sha256=abc").is_err());
+        assert!(Pin::parse("md5=00").is_err());
+    }
+
+    #[test]
+    fn test_ed25519_pin() {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        let pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        let hex = |bytes: &[u8]| bytes.iter().map(|b| format!("{b:02x}")).collect::<String>();
+        let pin = Pin::parse(&format!("ed25519={}", hex(pair.public_key().as_ref()))).unwrap();
+        let signature = format!("{}\n", hex(pair.sign(BASE.as_bytes()).as_ref()));
+
+        assert!(pin.verify(BASE, Some(&signature)).is_ok());
+        assert!(pin.verify("[check]\n", Some(&signature)).is_err());
+        assert!(pin.verify(BASE, None).is_err());
+    }
+}
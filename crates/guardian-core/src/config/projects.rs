@@ -0,0 +1,40 @@
+//! Monorepo sub-projects under `[projects]`.
+
+use serde::Deserialize;
+
+/// Where `check --recursive` finds the projects under a monorepo root.
+///
+/// `paths` and `exclude` are globs relative to the root, such as
+/// `"services/*"`: `*` and `?` match within one path component, and `**`
+/// matches any number of them. Without `paths`, every directory holding a
+/// `Cargo.toml` is a project. Either way, a project's subdirectories are
+/// part of it rather than projects of their own.
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectSettings {
+    /// Globs matching the project directories.
+    #[serde(default)]
+    pub paths: Vec<String>,
+
+    /// Globs matching directories that are never projects, nor searched.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::GuardianConfig;
+
+    #[test]
+    fn test_parse_projects() {
+        let cfg: GuardianConfig = toml::from_str("").unwrap();
+        assert!(cfg.projects.paths.is_empty());
+
+        let toml =
+            "[projects]\npaths = [\"services/*\", \"tools/cli\"]\nexclude = [\"services/old\"]\n";
+        let cfg: GuardianConfig = toml::from_str(toml).unwrap();
+        assert_eq!(cfg.projects.paths, ["services/*", "tools/cli"]);
+        assert_eq!(cfg.projects.exclude, ["services/old"]);
+        assert!(toml::from_str::<GuardianConfig>("[projects]\nglobs = []\n").is_err());
+    }
+}
@@ -0,0 +1,87 @@
+//! The `[check.readability]` configuration section: the `llm-readability`
+//! check.
+//!
+//! The check asks a model, so it only runs when opted into:
+//!
+//! ```toml
+//! [checks.llm-readability]
+//!
+//! [check.readability]
+//! min_confidence = 0.8
+//! files = ["src/parser.rs"]
+//! ```
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer};
+use std::path::PathBuf;
+
+/// Which files the `llm-readability` check sends to the model, and which of
+/// its findings count.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ReadabilitySettings {
+    /// Findings the model is less sure of than this, from 0 to 1, are
+    /// dropped.
+    #[serde(default = "default_min_confidence", deserialize_with = "confidence")]
+    pub min_confidence: f64,
+
+    /// Files to review, relative to the project. When empty, the files
+    /// changed since the last commit.
+    #[serde(default)]
+    pub files: Vec<PathBuf>,
+
+    /// Most files reviewed in one run.
+    #[serde(default = "default_max_files")]
+    pub max_files: usize,
+}
+
+impl Default for ReadabilitySettings {
+    fn default() -> Self {
+        Self {
+            min_confidence: default_min_confidence(),
+            files: Vec::new(),
+            max_files: default_max_files(),
+        }
+    }
+}
+
+fn default_min_confidence() -> f64 {
+    0.7
+}
+
+fn default_max_files() -> usize {
+    10
+}
+
+/// A `min_confidence`, which must be from 0 to 1.
+pub(super) fn confidence<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    let confidence = f64::deserialize(deserializer)?;
+    if !(0.0..=1.0).contains(&confidence) {
+        return Err(D::Error::custom(format!(
+            "min_confidence must be between 0 and 1, not {confidence}"
+        )));
+    }
+    Ok(confidence)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::GuardianConfig;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_parse_readability() {
+        let cfg: GuardianConfig = toml::from_str("").unwrap();
+        assert_eq!(cfg.check.readability.min_confidence, 0.7);
+        assert_eq!(cfg.check.readability.max_files, 10);
+
+        let toml = "[check.readability]\nmin_confidence = 0.9\nfiles = [\"src/lib.rs\"]\n";
+        let cfg: GuardianConfig = toml::from_str(toml).unwrap();
+        assert_eq!(cfg.check.readability.min_confidence, 0.9);
+        assert_eq!(cfg.check.readability.files, [PathBuf::from("src/lib.rs")]);
+
+        let err = toml::from_str::<GuardianConfig>("[check.readability]\nmin_confidence = 2\n")
+            .unwrap_err();
+        assert!(err.to_string().contains("between 0 and 1, not 2"));
+    }
+}
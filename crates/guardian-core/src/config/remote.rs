@@ -0,0 +1,217 @@
+//! Fetching remote config bases over HTTPS or from git repositories.
+//!
+//! HTTPS bases are cached with their `ETag` and revalidated on every load,
+//! so an unchanged base costs a `304 Not Modified`. Git bases are kept in a
+//! shallow checkout per repository and ref. Either way, the cached copy is
+//! used when the fetch fails, so an outage doesn't break every command.
+
+use super::{cache_stats, state};
+use anyhow::{Context, Result, anyhow, bail};
+use reqwest::header::{ETAG, IF_NONE_MATCH};
+use reqwest::{StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+/// Cache of fetched HTTPS bases, keyed by URL.
+const CACHE_FILE: &str = "remote-config-cache.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedBase {
+    #[serde(default)]
+    etag: Option<String>,
+    contents: String,
+}
+
+/// Fetch an HTTPS base, revalidating the cached copy with its ETag.
+pub(super) fn fetch_url(url: &Url) -> Result<String> {
+    let cache_path = state::path(CACHE_FILE);
+    let mut cache: BTreeMap<String, CachedBase> = cache_path
+        .as_deref()
+        .and_then(state::read)
+        .unwrap_or_default();
+    let cached = cache.get(url.as_str()).cloned();
+    let etag = cached.as_ref().and_then(|c| c.etag.clone());
+
+    let request = url.clone();
+    // Config loads synchronously, so fetch on a thread with its own runtime
+    // rather than blocking the command's runtime.
+    let fetched = std::thread::spawn(move || -> Result<Option<CachedBase>> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        runtime.block_on(async {
+            let client = reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()?;
+            let mut request = client.get(request);
+            if let Some(etag) = etag {
+                request = request.header(IF_NONE_MATCH, etag);
+            }
+            let response = request.send().await?;
+            if response.status() == StatusCode::NOT_MODIFIED {
+                return Ok(None);
+            }
+            let response = response.error_for_status()?;
+            let etag = response
+                .headers()
+                .get(ETAG)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string);
+            let contents = response.text().await?;
+            Ok(Some(CachedBase { etag, contents }))
+        })
+    })
+    .join()
+    .map_err(|_| anyhow!("Fetching {url} panicked"))?;
+    // A base still current, or the stale copy when the fetch failed, is a hit.
+    let hit = cached.is_some() && !matches!(fetched, Ok(Some(_)));
+    cache_stats::record("remote_config", hit);
+
+    match (fetched, cached) {
+        (Ok(Some(fresh)), _) => {
+            let contents = fresh.contents.clone();
+            cache.insert(url.to_string(), fresh);
+            if let Some(path) = &cache_path {
+                state::write(path, &cache);
+            }
+            Ok(contents)
+        }
+        (Ok(None), Some(cached)) => {
+            tracing::debug!(url = %url, "Config base not modified");
+            Ok(cached.contents)
+        }
+        (Ok(None), None) => bail!("{url} answered 304 Not Modified to an unconditional request"),
+        (Err(err), Some(cached)) => {
+            tracing::warn!(url = %url, error = %err, "Using cached copy of config base");
+            Ok(cached.contents)
+        }
+        (Err(err), None) => Err(err.context(format!("Failed to fetch config base {url}"))),
+    }
+}
+
+/// Read `path` from `repo` at `reference` (default: the remote's HEAD),
+/// through a shallow checkout under `cache_dir`.
+pub(super) fn fetch_git(
+    cache_dir: &Path,
+    repo: &str,
+    reference: Option<&str>,
+    path: &str,
+) -> Result<String> {
+    let reference = reference.unwrap_or("HEAD");
+    let key = super::pin::sha256_hex(format!("{repo} {reference}").as_bytes());
+    let checkout = cache_dir.join(&key[..16]);
+
+    let updated = (|| -> Result<()> {
+        if !checkout.join(".git").exists() {
+            fs::create_dir_all(&checkout)?;
+            git(&checkout, &["init", "-q"])?;
+        }
+        git(
+            &checkout,
+            &["fetch", "-q", "--depth", "1", "--", repo, reference],
+        )?;
+        git(&checkout, &["checkout", "-q", "--force", "FETCH_HEAD"])
+    })();
+    let file = checkout.join(path);
+    match updated {
+        Ok(()) => {}
+        Err(err) if file.is_file() => {
+            tracing::warn!(repo, error = %err, "Using cached checkout of config base");
+        }
+        Err(err) => return Err(err.context(format!("Failed to fetch config base from {repo}"))),
+    }
+    fs::read_to_string(&file).with_context(|| format!("No {path} in {repo} at {reference}"))
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<()> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        // Never hang on a credential prompt.
+        .env("GIT_TERMINAL_PROMPT", "0")
+        // No `ext::` or other transports that run commands.
+        .env("GIT_ALLOW_PROTOCOL", "https:ssh:file")
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// Resolve `entry` against the directory of `from`, both relative to the
+/// repository root, refusing paths that climb out of the repository.
+pub(super) fn join_repo_path(from: &str, entry: &str) -> Result<String> {
+    let dir = Path::new(from).parent().unwrap_or(Path::new(""));
+    let joined = dir.join(entry);
+    let mut parts: Vec<&str> = Vec::new();
+    for component in joined.components() {
+        match component {
+            Component::Normal(part) => parts.push(part.to_str().unwrap_or_default()),
+            Component::ParentDir if parts.pop().is_some() => {}
+            Component::CurDir => {}
+            _ => bail!("'{entry}' is outside the repository"),
+        }
+    }
+    Ok(parts
+        .iter()
+        .collect::<PathBuf>()
+        .to_string_lossy()
+        .into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_join_repo_path() {
+        assert_eq!(
+            join_repo_path("guardian/base.toml", "rust.toml").unwrap(),
+            "guardian/rust.toml"
+        );
+        assert_eq!(
+            join_repo_path("guardian/base.toml", "../org.toml").unwrap(),
+            "org.toml"
+        );
+        assert!(join_repo_path("base.toml", "../escape.toml").is_err());
+        assert!(join_repo_path("base.toml", "/etc/passwd").is_err());
+    }
+
+    #[test]
+    fn test_fetch_git() {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path().join("policy");
+        fs::create_dir_all(repo.join("guardian")).unwrap();
+        fs::write(repo.join("guardian/base.toml"), "[check]\nmax_loc = 400\n").unwrap();
+        let repo_git = |args: &[&str]| {
+            let mut all = vec!["-c", "user.name=t", "-c", "user.email=t@example.com"];
+            all.extend_from_slice(args);
+            git(&repo, &all).unwrap();
+        };
+        repo_git(&["init", "-q"]);
+        repo_git(&["add", "."]);
+        repo_git(&["commit", "-q", "-m", "policy"]);
+
+        let cache = temp.path().join("cache");
+        let url = format!("file://{}", repo.display());
+        let contents = fetch_git(&cache, &url, None, "guardian/base.toml").unwrap();
+        assert!(contents.contains("max_loc = 400"));
+
+        // An unreachable repository falls back to the cached checkout.
+        fs::rename(&repo, temp.path().join("moved")).unwrap();
+        let contents = fetch_git(&cache, &url, None, "guardian/base.toml").unwrap();
+        assert!(contents.contains("max_loc = 400"));
+        assert!(fetch_git(&cache, &url, Some("v2"), "guardian/base.toml").is_err());
+    }
+}
@@ -0,0 +1,225 @@
+//! The `[routing]` configuration section: which model handles which task.
+//!
+//! A single `default_model` forces one speed/quality tradeoff on every
+//! command. Routing lets each task name its own model, optionally pinned to a
+//! host:
+//!
+//! ```toml
+//! [routing]
+//! evaluate = "qwen2.5-coder:14b"
+//! summarize = { model = "phi4", host = "big72" }
+//! commit_msg = { model = "llama3.2:3b", system_prompt = "Write conventional commits." }
+//! ```
+//!
+//! A host can also map tasks to models it runs well, with
+//! `models = { evaluate = "qwen2.5-coder:32b" }`, which wins over `[routing]`
+//! once that host is selected.
+
+use super::{GuardianConfig, OllamaHost};
+use serde::Deserialize;
+
+/// Model (and optional host) used for one task.
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+#[serde(from = "RawRoute")]
+pub struct TaskRoute {
+    /// Model to use for the task.
+    pub model: Option<String>,
+    /// Host the task is pinned to.
+    pub host: Option<String>,
+    /// System prompt sent ahead of the task's prompts.
+    pub system_prompt: Option<String>,
+}
+
+/// Accepted TOML shapes: a bare model name or a `{ model, host }` table.
+#[derive(Deserialize)]
+#[serde(
+    untagged,
+    expecting = "expected a model name or a table of `model`, `host`, and `system_prompt`"
+)]
+enum RawRoute {
+    Model(String),
+    Table(RouteTable),
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RouteTable {
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default)]
+    host: Option<String>,
+    #[serde(default)]
+    system_prompt: Option<String>,
+}
+
+impl From<RawRoute> for TaskRoute {
+    fn from(raw: RawRoute) -> Self {
+        match raw {
+            RawRoute::Model(model) => Self {
+                model: Some(model),
+                ..Self::default()
+            },
+            RawRoute::Table(RouteTable {
+                model,
+                host,
+                system_prompt,
+            }) => Self {
+                model,
+                host,
+                system_prompt,
+            },
+        }
+    }
+}
+
+impl GuardianConfig {
+    /// Resolve the model and host for `task`.
+    ///
+    /// An unset model or system prompt falls back to `[ollama] default_model`
+    /// or `system_prompt`; the host stays unset so normal host selection applies.
+    pub fn route(&self, task: &str) -> TaskRoute {
+        let route = self.routing.get(task).cloned().unwrap_or_default();
+        TaskRoute {
+            model: route.model.or_else(|| self.ollama.default_model.clone()),
+            host: route.host,
+            system_prompt: route
+                .system_prompt
+                .or_else(|| self.ollama.system_prompt.clone()),
+        }
+    }
+
+    /// Resolve the model for `task` once `host` is chosen: the host's (or
+    /// its group's) model for the task, then the task's routed model, then
+    /// the host's default, then `[ollama] default_model`.
+    pub fn model_for(&self, task: &str, host: &OllamaHost) -> Option<String> {
+        host.models
+            .get(task)
+            .cloned()
+            .or_else(|| self.routing.get(task).and_then(|route| route.model.clone()))
+            .or_else(|| host.default_model.clone())
+            .or_else(|| self.ollama.default_model.clone())
+    }
+
+    /// Whether `task` is named in `[routing]` or in any host's `models`.
+    pub fn knows_task(&self, task: &str) -> bool {
+        self.routing.contains_key(task)
+            || self
+                .ollama
+                .hosts
+                .iter()
+                .any(|h| h.models.contains_key(task))
+    }
+
+    /// Every model the config could route a task to on `host`, each once.
+    pub fn models_on<'a>(&'a self, host: &'a OllamaHost) -> Vec<&'a str> {
+        let mut models: Vec<&str> = host
+            .models
+            .values()
+            .chain(
+                self.routing
+                    .values()
+                    .filter_map(|route| route.model.as_ref()),
+            )
+            .chain(&host.default_model)
+            .chain(&self.ollama.default_model)
+            .map(String::as_str)
+            .collect();
+        models.sort_unstable();
+        models.dedup();
+        models
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::GuardianConfig;
+
+    #[test]
+    fn test_parse_routing() {
+        let toml = r#"
+[ollama]
+default_model = "llama3.2"
+
+[routing]
+evaluate = "qwen2.5-coder:14b"
+summarize = { model = "phi4", host = "big72" }
+commit_msg = { host = "local" }
+"#;
+        let cfg: GuardianConfig = toml::from_str(toml).unwrap();
+
+        let evaluate = cfg.route("evaluate");
+        assert_eq!(evaluate.model.as_deref(), Some("qwen2.5-coder:14b"));
+        assert_eq!(evaluate.host, None);
+
+        let summarize = cfg.route("summarize");
+        assert_eq!(summarize.model.as_deref(), Some("phi4"));
+        assert_eq!(summarize.host.as_deref(), Some("big72"));
+
+        // Unset fields fall back to the defaults
+        assert_eq!(cfg.route("commit_msg").model.as_deref(), Some("llama3.2"));
+        assert_eq!(cfg.route("ask").model.as_deref(), Some("llama3.2"));
+    }
+
+    #[test]
+    fn test_model_for_host() {
+        let toml = r#"
+[ollama]
+default_model = "llama3.2"
+
+[routing]
+evaluate = "qwen2.5-coder:14b"
+
+[[ollama.hosts]]
+name = "big72"
+base_url = "http://big72:11434"
+default_model = "phi4"
+models = { evaluate = "qwen2.5-coder:32b", quick = "llama3.2:3b" }
+
+[[ollama.hosts]]
+name = "local"
+base_url = "http://localhost:11434"
+"#;
+        let cfg: GuardianConfig = toml::from_str(toml).unwrap();
+        let (big72, local) = (&cfg.ollama.hosts[0], &cfg.ollama.hosts[1]);
+        assert_eq!(
+            cfg.model_for("evaluate", big72).as_deref(),
+            Some("qwen2.5-coder:32b")
+        );
+        assert_eq!(
+            cfg.model_for("evaluate", local).as_deref(),
+            Some("qwen2.5-coder:14b")
+        );
+        assert_eq!(cfg.model_for("ask", big72).as_deref(), Some("phi4"));
+        assert_eq!(cfg.model_for("ask", local).as_deref(), Some("llama3.2"));
+        assert_eq!(cfg.model_for("quick", local).as_deref(), Some("llama3.2"));
+        assert!(cfg.knows_task("quick"));
+        assert!(!cfg.knows_task("summarize"));
+        let on_big72 = ["llama3.2", "llama3.2:3b", "phi4", "qwen2.5-coder:14b"];
+        assert_eq!(
+            cfg.models_on(big72),
+            [&on_big72[..], &["qwen2.5-coder:32b"]].concat()
+        );
+        assert_eq!(cfg.models_on(local), ["llama3.2", "qwen2.5-coder:14b"]);
+    }
+
+    #[test]
+    fn test_route_system_prompt() {
+        let toml = r#"
+[ollama]
+system_prompt = "You are guardian."
+
+[routing]
+commit_msg = { model = "llama3.2:3b", system_prompt = "Write conventional commits." }
+"#;
+        let cfg: GuardianConfig = toml::from_str(toml).unwrap();
+        assert_eq!(
+            cfg.route("commit_msg").system_prompt.as_deref(),
+            Some("Write conventional commits.")
+        );
+        assert_eq!(
+            cfg.route("evaluate").system_prompt.as_deref(),
+            Some("You are guardian.")
+        );
+        assert_eq!(GuardianConfig::default().route("ask").system_prompt, None);
+    }
+}
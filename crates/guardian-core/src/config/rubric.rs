@@ -0,0 +1,160 @@
+//! The `[rubric]` configuration section: how `evaluate`'s model judges.
+//!
+//! Teams care about different things, so the rubric says what to weigh
+//! most, in what tone to answer, which severities the model may let
+//! through, and which sections its review must have. It is written into
+//! the evaluation prompt, and the sections into the verdict's schema. It
+//! also says how sure the model must be for its verdict to fail a run.
+
+use crate::checks::Severity;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer};
+use std::collections::BTreeMap;
+
+/// The rubric the evaluating model applies.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RubricSettings {
+    /// Weight of each aspect of the code, e.g. `architecture = 3`.
+    #[serde(default, deserialize_with = "weights")]
+    pub weights: BTreeMap<String, u32>,
+
+    /// How the review should read, e.g. "blunt" or "mentoring".
+    #[serde(default)]
+    pub tone: Option<String>,
+
+    /// Severities the model may approve despite. Errors can't be waived.
+    #[serde(default = "default_waivable", deserialize_with = "waivable")]
+    pub waivable: Vec<Severity>,
+
+    /// Sections the review must fill in, by title.
+    #[serde(default, deserialize_with = "sections")]
+    pub sections: Vec<String>,
+
+    /// Blocking findings and verdicts the model is less sure of than this,
+    /// from 0 to 1, don't fail the run.
+    #[serde(
+        default = "default_min_confidence",
+        deserialize_with = "super::readability::confidence"
+    )]
+    pub min_confidence: f64,
+
+    /// What to do with a failing verdict below `min_confidence`.
+    #[serde(default)]
+    pub low_confidence: LowConfidence,
+}
+
+/// What to do when the model isn't sure enough of a failing verdict.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum LowConfidence {
+    /// Report it, but don't fail the run on it.
+    #[default]
+    Advise,
+    /// Ask the model routed to `second-opinion`, and go by its verdict
+    /// when it is sure enough.
+    SecondOpinion,
+}
+
+impl Default for RubricSettings {
+    fn default() -> Self {
+        Self {
+            weights: BTreeMap::new(),
+            tone: None,
+            waivable: default_waivable(),
+            sections: Vec::new(),
+            min_confidence: default_min_confidence(),
+            low_confidence: LowConfidence::default(),
+        }
+    }
+}
+
+fn default_waivable() -> Vec<Severity> {
+    vec![Severity::Info, Severity::Warning]
+}
+
+fn default_min_confidence() -> f64 {
+    0.5
+}
+
+fn weights<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BTreeMap<String, u32>, D::Error> {
+    let weights = BTreeMap::<String, u32>::deserialize(deserializer)?;
+    if weights.keys().any(|aspect| aspect.trim().is_empty()) {
+        return Err(D::Error::custom("aspect names can't be empty"));
+    }
+    Ok(weights)
+}
+
+fn waivable<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Severity>, D::Error> {
+    let waivable = Vec::<Severity>::deserialize(deserializer)?;
+    if waivable.contains(&Severity::Error) {
+        return Err(D::Error::custom(
+            "errors can't be waived; the policy decides them",
+        ));
+    }
+    Ok(waivable)
+}
+
+fn sections<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    let sections = Vec::<String>::deserialize(deserializer)?;
+    for (i, title) in sections.iter().enumerate() {
+        if title.trim().is_empty() {
+            return Err(D::Error::custom("section titles can't be empty"));
+        }
+        if sections[..i].contains(title) {
+            return Err(D::Error::custom(format!(
+                "section `{title}` is listed twice"
+            )));
+        }
+    }
+    Ok(sections)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LowConfidence;
+    use crate::checks::Severity;
+    use crate::config::GuardianConfig;
+
+    #[test]
+    fn test_parse_rubric() {
+        let cfg: GuardianConfig = toml::from_str("").unwrap();
+        assert!(cfg.rubric.weights.is_empty());
+        assert_eq!(cfg.rubric.waivable, [Severity::Info, Severity::Warning]);
+        assert_eq!(cfg.rubric.min_confidence, 0.5);
+        assert_eq!(cfg.rubric.low_confidence, LowConfidence::Advise);
+
+        let toml = r#"
+[rubric]
+weights = { architecture = 3, tests = 2, style = 1 }
+tone = "mentoring"
+waivable = ["info"]
+sections = ["Risks", "Test gaps"]
+min_confidence = 0.7
+low_confidence = "second-opinion"
+"#;
+        let cfg: GuardianConfig = toml::from_str(toml).unwrap();
+        assert_eq!(cfg.rubric.weights["architecture"], 3);
+        assert_eq!(cfg.rubric.tone.as_deref(), Some("mentoring"));
+        assert_eq!(cfg.rubric.waivable, [Severity::Info]);
+        assert_eq!(cfg.rubric.sections, ["Risks", "Test gaps"]);
+        assert_eq!(cfg.rubric.min_confidence, 0.7);
+        assert_eq!(cfg.rubric.low_confidence, LowConfidence::SecondOpinion);
+    }
+
+    #[test]
+    fn test_reject_invalid_rubric() {
+        for toml in [
+            "[rubric]\nwaivable = [\"error\"]",
+            "[rubric]\nwaivable = [\"critical\"]",
+            "[rubric]\nsections = [\"Risks\", \"Risks\"]",
+            "[rubric]\nsections = [\" \"]",
+            "[rubric]\nweights = { style = -1 }",
+            "[rubric]\nvoice = \"blunt\"",
+            "[rubric]\nmin_confidence = 1.5",
+            "[rubric]\nlow_confidence = \"retry\"",
+        ] {
+            assert!(toml::from_str::<GuardianConfig>(toml).is_err(), "{toml}");
+        }
+    }
+}
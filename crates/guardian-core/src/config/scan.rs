@@ -0,0 +1,31 @@
+//! Defaults for how the checks find and read files: the `follow_symlinks`,
+//! `max_depth`, and `max_file_bytes` keys of `[check]`.
+
+/// Directories deep a walk goes before it stops.
+pub(super) fn default_max_depth() -> usize {
+    64
+}
+
+/// Larger files are skipped; source files are far smaller than 1 MiB.
+pub(super) fn default_max_file_bytes() -> u64 {
+    1024 * 1024
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::GuardianConfig;
+
+    #[test]
+    fn test_parse_scan_settings() {
+        let cfg: GuardianConfig = toml::from_str("").unwrap();
+        assert!(!cfg.check.follow_symlinks);
+        assert_eq!(cfg.check.max_depth, 64);
+        assert_eq!(cfg.check.max_file_bytes, 1_048_576);
+
+        let toml = "[check]\nfollow_symlinks = true\nmax_depth = 8\nmax_file_bytes = 4096\n";
+        let cfg: GuardianConfig = toml::from_str(toml).unwrap();
+        assert!(cfg.check.follow_symlinks);
+        assert_eq!(cfg.check.max_depth, 8);
+        assert_eq!(cfg.check.max_file_bytes, 4096);
+    }
+}
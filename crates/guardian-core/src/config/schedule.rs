@@ -0,0 +1,250 @@
+//! The `[[schedule]]` configuration section: jobs `serve` runs on cron
+//! schedules.
+//!
+//! ```toml
+//! [[schedule]]
+//! cron = "0 * * * *"
+//! run = "check"
+//! path = "/home/me/src/agent-repo"
+//! ```
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::path::PathBuf;
+
+/// A command `serve` runs on a project on a schedule.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ScheduledJob {
+    /// When to run, as a five-field cron expression in UTC.
+    #[serde(deserialize_with = "cron")]
+    pub cron: Cron,
+
+    /// The command to run.
+    pub run: ScheduledCommand,
+
+    /// Project directory the command runs on.
+    pub path: PathBuf,
+
+    /// Only run these checks, comma-separated, like `--only`.
+    pub only: Option<String>,
+
+    /// Don't run these checks, comma-separated, like `--skip`.
+    pub skip: Option<String>,
+}
+
+/// A command a scheduled job can run.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ScheduledCommand {
+    Check,
+    Evaluate,
+}
+
+impl ScheduledCommand {
+    /// Name of the command, and of its JSON-RPC method.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Check => "check",
+            Self::Evaluate => "evaluate",
+        }
+    }
+}
+
+/// A parsed cron expression: minute, hour, day of month, month, and day of
+/// week, each a set of values held as bits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cron {
+    expression: String,
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    /// Whether the day of month or the day of week was `*`. When neither
+    /// was, a day matching either runs the job, as in cron.
+    any_day: bool,
+    any_weekday: bool,
+}
+
+impl Cron {
+    /// Parse a cron expression such as `*/15 9-17 * * 1-5`, or one of
+    /// `@hourly`, `@daily`, `@weekly`, and `@monthly`.
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let fields = match expression.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            fields => fields,
+        };
+        let fields: Vec<&str> = fields.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(format!(
+                "cron expression '{expression}' needs 5 fields (minute hour day month weekday), not {}",
+                fields.len()
+            ));
+        };
+        // Sunday is 0 or 7.
+        let weekdays = field(weekday, 0, 7, "weekday")?;
+        Ok(Self {
+            expression: expression.to_string(),
+            minutes: field(minute, 0, 59, "minute")?,
+            hours: field(hour, 0, 23, "hour")?,
+            days: field(day, 1, 31, "day")?,
+            months: field(month, 1, 12, "month")?,
+            weekdays: (weekdays | weekdays >> 7) & 0x7f,
+            any_day: day.starts_with('*'),
+            any_weekday: weekday.starts_with('*'),
+        })
+    }
+
+    /// The expression as written.
+    pub fn expression(&self) -> &str {
+        &self.expression
+    }
+
+    /// The first time the expression matches after `secs` seconds since the
+    /// Unix epoch, in the same units, or `None` if it matches no time in the
+    /// next five years (like `0 0 30 2 *`).
+    pub fn next_after(&self, secs: u64) -> Option<u64> {
+        let has = |set: u64, value: u64| set & (1 << value) != 0;
+        let mut minute = secs / 60 + 1;
+        let end = minute + 5 * 366 * 24 * 60;
+        while minute < end {
+            let days = minute / (24 * 60);
+            let (month, day) = month_and_day(days);
+            // 1970-01-01 was a Thursday.
+            let weekday = (days + 4) % 7;
+            let (by_day, by_weekday) = (has(self.days, day), has(self.weekdays, weekday));
+            let day_matches = match (self.any_day, self.any_weekday) {
+                (false, false) => by_day || by_weekday,
+                _ => by_day && by_weekday,
+            };
+            if !has(self.months, month) || !day_matches {
+                minute = (days + 1) * 24 * 60;
+            } else if !has(self.hours, minute / 60 % 24) {
+                minute = (minute / 60 + 1) * 60;
+            } else if !has(self.minutes, minute % 60) {
+                minute += 1;
+            } else {
+                return Some(minute * 60);
+            }
+        }
+        None
+    }
+}
+
+/// Parse one cron field, a comma-separated list of `*`, `N`, or `N-M`, each
+/// optionally followed by `/STEP`, into a set of values from `min` to `max`.
+fn field(text: &str, min: u64, max: u64, name: &str) -> Result<u64, String> {
+    let invalid = |why: &str| format!("Invalid cron {name} '{text}': {why}");
+    let number = |s: &str| s.parse::<u64>().map_err(|_| invalid("expected a number"));
+    let mut set = 0;
+    for part in text.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, number(step)?),
+            None => (part, 1),
+        };
+        let (low, high) = match range.split_once('-') {
+            _ if range == "*" => (min, max),
+            Some((low, high)) => (number(low)?, number(high)?),
+            None if step > 1 => (number(range)?, max),
+            None => (number(range)?, number(range)?),
+        };
+        if low < min || high > max || low > high {
+            return Err(invalid(&format!("values must be from {min} to {max}")));
+        }
+        if step == 0 {
+            return Err(invalid("step must be at least 1"));
+        }
+        for value in (low..=high).step_by(step as usize) {
+            set |= 1 << value;
+        }
+    }
+    Ok(set)
+}
+
+/// The month (1-12) and day of month of `days` days since the Unix epoch,
+/// by Howard Hinnant's `civil_from_days`.
+fn month_and_day(days: u64) -> (u64, u64) {
+    let z = days + 719_468;
+    let day_of_era = z % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    (month, day)
+}
+
+fn cron<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Cron, D::Error> {
+    Cron::parse(&String::deserialize(deserializer)?).map_err(D::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GuardianConfig;
+
+    /// 2024-03-01T00:00:00Z, a Friday.
+    const MARCH_1: u64 = 1_709_251_200;
+
+    #[test]
+    fn test_cron_next_after() {
+        let next = |expr: &str, secs| Cron::parse(expr).unwrap().next_after(secs);
+        assert_eq!(next("* * * * *", MARCH_1), Some(MARCH_1 + 60));
+        assert_eq!(next("*/15 * * * *", MARCH_1 + 60), Some(MARCH_1 + 15 * 60));
+        assert_eq!(
+            next("30 9 * * *", MARCH_1),
+            Some(MARCH_1 + 9 * 3600 + 30 * 60)
+        );
+        // The next Monday, 2024-03-04, and the next Sunday, 2024-03-03.
+        assert_eq!(next("0 0 * * 1", MARCH_1), Some(MARCH_1 + 3 * 86_400));
+        assert_eq!(next("0 0 * * 7", MARCH_1), Some(MARCH_1 + 2 * 86_400));
+        // Day of month or day of week, when both are given.
+        assert_eq!(next("0 0 2 * 1", MARCH_1), Some(MARCH_1 + 86_400));
+        // 2024-02-29 to 2025-01-01.
+        let leap_day = MARCH_1 - 86_400;
+        assert_eq!(next("@monthly", leap_day), Some(MARCH_1));
+        assert_eq!(next("0 0 1 1 *", MARCH_1), Some(1_735_689_600));
+        assert_eq!(next("0 0 30 2 *", MARCH_1), None);
+    }
+
+    #[test]
+    fn test_cron_rejects_bad_fields() {
+        assert!(Cron::parse("* * * *").unwrap_err().contains("5 fields"));
+        assert!(Cron::parse("60 * * * *").unwrap_err().contains("minute"));
+        assert!(Cron::parse("* * 0 * *").is_err());
+        assert!(Cron::parse("*/0 * * * *").is_err());
+        assert!(Cron::parse("5-1 * * * *").is_err());
+        assert!(Cron::parse("a * * * *").is_err());
+    }
+
+    #[test]
+    fn test_parse_schedule() {
+        let cfg: GuardianConfig = toml::from_str("").unwrap();
+        assert!(cfg.schedule.is_empty());
+
+        let toml = r#"
+[[schedule]]
+cron = "0 */2 * * *"
+run = "evaluate"
+path = "/src/agent"
+only = "loc-limits"
+"#;
+        let cfg: GuardianConfig = toml::from_str(toml).unwrap();
+        let job = &cfg.schedule[0];
+        assert_eq!(job.run, ScheduledCommand::Evaluate);
+        assert_eq!(job.cron.expression(), "0 */2 * * *");
+        assert_eq!(job.only.as_deref(), Some("loc-limits"));
+
+        let bad = "[[schedule]]\ncron = \"0 25 * * *\"\nrun = \"check\"\npath = \".\"\n";
+        assert!(toml::from_str::<GuardianConfig>(bad).is_err());
+    }
+}
@@ -0,0 +1,46 @@
+//! Secrets kept in the OS keyring, referenced from config by name.
+//!
+//! `set-secret NAME` stores a token under the `guardian-cli` service, and a
+//! host's `auth` reads it back with `token_keyring = "NAME"` or
+//! `password_keyring = "NAME"`, so no secret has to live in `guardian.toml`.
+//! The backend is the macOS Keychain, the Windows Credential Manager, or on
+//! Linux the Secret Service (GNOME Keyring, KWallet), which keeps secrets
+//! across reboots, with the kernel keyring as a cache in front of it.
+
+use anyhow::{Context, Result};
+use keyring::Entry;
+
+/// Keyring service name all guardian secrets are stored under.
+const SERVICE: &str = "guardian-cli";
+
+/// Store `value` as the secret `name`, replacing any previous value.
+pub fn set_secret(name: &str, value: &str) -> Result<()> {
+    entry(name)?
+        .set_password(value)
+        .with_context(|| format!("Failed to store secret '{name}' in the OS keyring"))
+}
+
+/// Read the secret `name`.
+pub fn get_secret(name: &str) -> Result<String> {
+    match entry(name)?.get_password() {
+        Ok(value) => Ok(value),
+        Err(keyring::Error::NoEntry) => anyhow::bail!(
+            "No secret '{name}' in the OS keyring; store it with `guardian-cli set-secret {name}`"
+        ),
+        Err(err) => Err(err).with_context(|| format!("Failed to read secret '{name}'")),
+    }
+}
+
+/// Remove the secret `name`.
+pub fn delete_secret(name: &str) -> Result<()> {
+    match entry(name)?.delete_credential() {
+        Ok(()) => Ok(()),
+        Err(keyring::Error::NoEntry) => anyhow::bail!("No secret '{name}' in the OS keyring"),
+        Err(err) => Err(err)
+            .with_context(|| format!("Failed to delete secret '{name}' from the OS keyring")),
+    }
+}
+
+fn entry(name: &str) -> Result<Entry> {
+    Entry::new(SERVICE, name).with_context(|| format!("Invalid secret name '{name}'"))
+}
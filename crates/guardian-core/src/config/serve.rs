@@ -0,0 +1,127 @@
+//! The `[serve]` configuration section for daemon mode.
+
+use super::SecretSource;
+use anyhow::{Result, bail};
+use serde::Deserialize;
+
+/// Settings for `guardian-cli serve`.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ServeSettings {
+    /// Address the HTTP API listens on.
+    #[serde(default = "default_bind")]
+    pub bind: String,
+
+    /// Seconds between health pings of all hosts.
+    #[serde(default = "default_health_interval_secs")]
+    pub health_interval_secs: u64,
+
+    /// Number of recent pings used for rolling latency and availability.
+    #[serde(default = "default_health_window")]
+    pub health_window: usize,
+
+    /// Consecutive failed pings before a healthy host is marked down.
+    #[serde(default = "default_hysteresis")]
+    pub down_after: u32,
+
+    /// Consecutive successful pings before a down host is marked up again.
+    #[serde(default = "default_hysteresis")]
+    pub up_after: u32,
+
+    /// Environment variable holding the token the job API requires.
+    pub token_env: Option<String>,
+
+    /// Name of a `set-secret` secret holding the token the job API
+    /// requires.
+    pub token_keyring: Option<String>,
+
+    /// Largest project tarball the job API accepts, in megabytes.
+    #[serde(default = "default_max_upload_mb")]
+    pub max_upload_mb: usize,
+
+    /// Whether jobs on uploaded projects may run checks that build them,
+    /// such as `clippy-budget`, which runs the project's build scripts and
+    /// proc macros on the daemon's machine.
+    #[serde(default)]
+    pub allow_build_scripts: bool,
+}
+
+impl Default for ServeSettings {
+    fn default() -> Self {
+        Self {
+            bind: default_bind(),
+            health_interval_secs: default_health_interval_secs(),
+            health_window: default_health_window(),
+            down_after: default_hysteresis(),
+            up_after: default_hysteresis(),
+            token_env: None,
+            token_keyring: None,
+            max_upload_mb: default_max_upload_mb(),
+            allow_build_scripts: false,
+        }
+    }
+}
+
+impl ServeSettings {
+    /// Where the job API's token comes from, or `None` when no token is
+    /// configured and the job API is off.
+    pub fn token_source(&self) -> Result<Option<SecretSource<'_>>> {
+        match (&self.token_env, &self.token_keyring) {
+            (Some(var), None) => Ok(Some(SecretSource::Env(var))),
+            (None, Some(name)) => Ok(Some(SecretSource::Keyring(name))),
+            (None, None) => Ok(None),
+            (Some(_), Some(_)) => {
+                bail!("[serve] takes one of token_env and token_keyring, not both")
+            }
+        }
+    }
+}
+
+fn default_bind() -> String {
+    "127.0.0.1:7878".to_string()
+}
+
+fn default_health_interval_secs() -> u64 {
+    30
+}
+
+fn default_health_window() -> usize {
+    10
+}
+
+fn default_hysteresis() -> u32 {
+    2
+}
+
+fn default_max_upload_mb() -> usize {
+    100
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::{GuardianConfig, SecretSource};
+
+    #[test]
+    fn test_parse_serve_settings() {
+        let toml = r#"
+[serve]
+bind = "0.0.0.0:9000"
+health_interval_secs = 10
+"#;
+        let cfg: GuardianConfig = toml::from_str(toml).unwrap();
+        assert_eq!(cfg.serve.bind, "0.0.0.0:9000");
+        assert_eq!(cfg.serve.health_interval_secs, 10);
+        assert_eq!(cfg.serve.down_after, 2);
+        assert_eq!(cfg.serve.token_source().unwrap(), None);
+        assert_eq!(cfg.serve.max_upload_mb, 100);
+        assert!(!cfg.serve.allow_build_scripts);
+
+        let toml = "[serve]\ntoken_env = \"GUARDIAN_API_TOKEN\"\n";
+        let cfg: GuardianConfig = toml::from_str(toml).unwrap();
+        let source = cfg.serve.token_source().unwrap();
+        assert_eq!(source, Some(SecretSource::Env("GUARDIAN_API_TOKEN")));
+        let toml = "[serve]\ntoken_env = \"A\"\ntoken_keyring = \"b\"\n";
+        let cfg: GuardianConfig = toml::from_str(toml).unwrap();
+        assert!(cfg.serve.token_source().is_err());
+    }
+}
@@ -0,0 +1,119 @@
+//! Small JSON state files kept between runs.
+//!
+//! State files (circuit breaker, caches, usage logs) only help later runs, so
+//! read and write failures are logged and treated as "no state" rather than
+//! failing the command.
+
+use anyhow::{Context, Result};
+use directories::ProjectDirs;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Get the path of a named state file.
+///
+/// Returns `~/.cache/guardian-cli/<name>` on Linux.
+pub fn path(name: &str) -> Option<PathBuf> {
+    let proj = ProjectDirs::from("com", "softwarewrighter", "guardian-cli")?;
+    Some(proj.cache_dir().join(name))
+}
+
+/// Read and deserialize a JSON state file, returning `None` if missing or invalid.
+pub fn read<T: DeserializeOwned>(path: &Path) -> Option<T> {
+    let contents = fs::read_to_string(path).ok()?;
+    match serde_json::from_str(&contents) {
+        Ok(value) => Some(value),
+        Err(e) => {
+            tracing::warn!(path = %path.display(), error = %e, "Ignoring invalid state file");
+            None
+        }
+    }
+}
+
+/// Serialize and write a JSON state file, creating parent directories.
+pub fn write<T: Serialize>(path: &Path, value: &T) {
+    if let Err(e) = try_write(path, value) {
+        tracing::warn!(path = %path.display(), error = %e, "Failed to save state file");
+    }
+}
+
+fn try_write<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    let json = serde_json::to_string_pretty(value)?;
+    fs::write(path, json).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Append a value as one JSON line to a log-style state file.
+pub fn append_line<T: Serialize>(path: &Path, value: &T) {
+    let result = (|| -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        writeln!(file, "{}", serde_json::to_string(value)?)?;
+        Ok(())
+    })();
+    if let Err(e) = result {
+        tracing::warn!(path = %path.display(), error = %e, "Failed to append to state file");
+    }
+}
+
+/// Current time as seconds since the Unix epoch.
+pub fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Format a Unix timestamp as `HH:MM:SS UTC`.
+pub fn format_utc(secs: u64) -> String {
+    let day_secs = secs % 86_400;
+    format!(
+        "{:02}:{:02}:{:02} UTC",
+        day_secs / 3600,
+        (day_secs % 3600) / 60,
+        day_secs % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_write_then_read() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("nested").join("state.json");
+        let value = BTreeMap::from([("big72".to_string(), 3u32)]);
+
+        write(&path, &value);
+        let loaded: Option<BTreeMap<String, u32>> = read(&path);
+        assert_eq!(loaded, Some(value));
+    }
+
+    #[test]
+    fn test_read_invalid_is_none() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("state.json");
+        fs::write(&path, "not json").unwrap();
+
+        let loaded: Option<BTreeMap<String, u32>> = read(&path);
+        assert!(loaded.is_none());
+    }
+
+    #[test]
+    fn test_format_utc() {
+        assert_eq!(format_utc(86_400 + 3661), "01:01:01 UTC");
+    }
+}
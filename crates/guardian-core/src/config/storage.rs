@@ -0,0 +1,63 @@
+//! History store settings under `[storage]`.
+
+use serde::Deserialize;
+
+/// Settings for the result history database.
+///
+/// Runs older than `retention_days`, and each project's runs beyond the
+/// newest `max_runs`, are deleted as new runs are recorded. Zero keeps them
+/// all.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct StorageSettings {
+    /// Whether check runs and verdicts are recorded.
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// Days a run is kept.
+    #[serde(default = "default_retention_days")]
+    pub retention_days: u64,
+
+    /// Runs kept per project.
+    #[serde(default = "default_max_runs")]
+    pub max_runs: u64,
+}
+
+impl Default for StorageSettings {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            retention_days: default_retention_days(),
+            max_runs: default_max_runs(),
+        }
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_retention_days() -> u64 {
+    90
+}
+
+fn default_max_runs() -> u64 {
+    1000
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::GuardianConfig;
+
+    #[test]
+    fn test_parse_storage() {
+        let cfg: GuardianConfig = toml::from_str("").unwrap();
+        assert_eq!(cfg.storage, super::StorageSettings::default());
+
+        let toml = "[storage]\nretention_days = 0\nmax_runs = 50\n";
+        let cfg: GuardianConfig = toml::from_str(toml).unwrap();
+        assert!(cfg.storage.enabled);
+        assert_eq!(cfg.storage.retention_days, 0);
+        assert_eq!(cfg.storage.max_runs, 50);
+    }
+}
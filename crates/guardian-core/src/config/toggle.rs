@@ -0,0 +1,40 @@
+//! `[checks.NAME]` tables: turning individual checks on and off.
+//!
+//! ```toml
+//! [checks.cache-busting]
+//! enabled = false
+//! ```
+
+use serde::Deserialize;
+
+/// Per-check settings from a `[checks.NAME]` table.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CheckToggle {
+    /// Whether the check runs (unless named in `--only`).
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::GuardianConfig;
+
+    #[test]
+    fn test_parse_check_toggles() {
+        let toml = r#"
+[checks.cache-busting]
+enabled = false
+
+[checks]
+module-count = { enabled = true }
+"#;
+        let cfg: GuardianConfig = toml::from_str(toml).unwrap();
+        assert!(!cfg.checks["cache-busting"].enabled);
+        assert!(cfg.checks["module-count"].enabled);
+    }
+}
@@ -0,0 +1,177 @@
+//! Strict config validation with errors that point at the offending line.
+//!
+//! Every config section rejects unknown keys, so a typo such as
+//! `defalt_model` fails loudly instead of being ignored. Errors name the
+//! file, line, and column, show the line, and suggest the closest valid key
+//! or value. YAML and JSON errors name the line and column their parser
+//! reports.
+
+use super::GuardianConfig;
+use super::format::Format;
+use anyhow::{Result, bail};
+use std::fmt::Write;
+
+/// Check that `contents` is a valid config, describing the first problem.
+pub(super) fn validate(source: &str, format: Format, contents: &str) -> Result<()> {
+    let message = match format {
+        Format::Toml => match toml::from_str::<GuardianConfig>(contents) {
+            Ok(_) => return Ok(()),
+            Err(err) => bail!("{}", describe(source, contents, &err)),
+        },
+        Format::Yaml => serde_norway::from_str::<GuardianConfig>(contents)
+            .err()
+            .map(|e| e.to_string()),
+        Format::Json => serde_json::from_str::<GuardianConfig>(contents)
+            .err()
+            .map(|e| e.to_string()),
+    };
+    match message {
+        Some(message) => bail!("{}", describe_plain(source, &message)),
+        None => Ok(()),
+    }
+}
+
+fn describe(source: &str, contents: &str, err: &toml::de::Error) -> String {
+    let (message, hint) = explain(err.message().trim());
+    let message = message.replace('\n', "\n  ");
+    let Some(span) = err.span() else {
+        return describe_plain(source, &message);
+    };
+
+    let line = contents[..span.start].matches('\n').count() + 1;
+    let line_start = contents[..span.start].rfind('\n').map_or(0, |i| i + 1);
+    let column = contents[line_start..span.start].chars().count() + 1;
+    let text = contents[line_start..].lines().next().unwrap_or_default();
+    let width = contents[span.clone()]
+        .lines()
+        .next()
+        .map_or(1, |s| s.chars().count().max(1));
+
+    let gutter = " ".repeat(line.to_string().len());
+    let mut out = format!("Invalid config at {source}:{line}:{column}");
+    let _ = write!(out, "\n  {message}\n {gutter} |\n {line} | {text}");
+    let _ = write!(
+        out,
+        "\n {gutter} | {}{}",
+        " ".repeat(column - 1),
+        "^".repeat(width)
+    );
+    if let Some(hint) = hint {
+        let _ = write!(out, "\n  help: {hint}");
+    }
+    out
+}
+
+/// Describe a problem without a span to point at, suggesting a fix for an
+/// unknown key or value anywhere in the message.
+fn describe_plain(source: &str, message: &str) -> String {
+    let mut out = format!("Invalid config at {source}: {message}");
+    let hint = message
+        .find("unknown ")
+        .and_then(|start| explain(&message[start..]).1);
+    if let Some(hint) = hint {
+        let _ = write!(out, "\n  help: {hint}");
+    }
+    out
+}
+
+/// Reword serde's unknown-field and unknown-variant messages and suggest
+/// the closest expected name.
+fn explain(message: &str) -> (String, Option<String>) {
+    let (kind, rest) = if let Some(rest) = message.strip_prefix("unknown field ") {
+        ("key", rest)
+    } else if let Some(rest) = message.strip_prefix("unknown variant ") {
+        ("value", rest)
+    } else {
+        return (message.to_string(), None);
+    };
+
+    // serde lists names as `name`, backtick-quoted.
+    let mut names = rest.split('`').skip(1).step_by(2);
+    let Some(unknown) = names.next() else {
+        return (message.to_string(), None);
+    };
+    let expected: Vec<&str> = names.collect();
+    let hint = match closest(unknown, &expected) {
+        Some(name) => format!("did you mean `{name}`?"),
+        None if expected.is_empty() => "this table takes no keys".to_string(),
+        None => format!("expected one of: {}", expected.join(", ")),
+    };
+    (format!("unknown {kind} `{unknown}`"), Some(hint))
+}
+
+/// The expected name nearest to `unknown`, if it is close enough to be a typo.
+fn closest<'a>(unknown: &str, expected: &[&'a str]) -> Option<&'a str> {
+    let limit = (unknown.chars().count() / 3).max(2);
+    expected
+        .iter()
+        .map(|name| (edit_distance(unknown, name), *name))
+        .filter(|(distance, _)| *distance <= limit)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, name)| name)
+}
+
+/// Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            let next = (row[j] + 1).min(row[j + 1] + 1).min(prev + cost);
+            prev = row[j + 1];
+            row[j + 1] = next;
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(contents: &str) -> String {
+        validate("guardian.toml", Format::Toml, contents)
+            .unwrap_err()
+            .to_string()
+    }
+
+    #[test]
+    fn test_unknown_key_suggests_typo_fix() {
+        let err = error("[ollama]\ndefault_host = \"big72\"\ndefalt_model = \"phi4\"\n");
+        assert!(err.starts_with("Invalid config at guardian.toml:3:1\n"));
+        assert!(err.contains("unknown key `defalt_model`"));
+        assert!(err.contains(" 3 | defalt_model = \"phi4\""));
+        assert!(err.contains("help: did you mean `default_model`?"));
+
+        let err = error("[output]\nhyperlinks = \"alwys\"\n");
+        assert!(err.contains("unknown value `alwys`"));
+        assert!(err.contains("did you mean `always`?"));
+
+        let err = error("[chekc]\nmax_loc = 300\n");
+        assert!(err.contains("did you mean `check`?"));
+    }
+
+    #[test]
+    fn test_type_mismatch_and_unrelated_keys() {
+        let err = error("[check]\nmax_loc = \"lots\"\n");
+        assert!(err.starts_with("Invalid config at guardian.toml:2:11\n"));
+        assert!(err.contains("invalid type: string \"lots\", expected usize"));
+
+        let err = error("[serve]\nport = 9000\n");
+        assert!(err.contains("unknown key `port`"));
+        assert!(err.contains("expected one of: bind, health_interval_secs"));
+
+        assert!(validate("guardian.toml", Format::Toml, "[check]\nmax_loc = 300\n").is_ok());
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("defalt_model", "default_model"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(closest("bnd", &["bind", "health_window"]), Some("bind"));
+        assert_eq!(closest("port", &["bind", "health_window"]), None);
+    }
+}
@@ -0,0 +1,36 @@
+//! Guardian core: the checks, config, and Ollama client behind guardian-cli.
+//!
+//! Embed these to run guardian's checks or pick an Ollama host from your
+//! own tools instead of shelling out to `guardian-cli` and parsing its
+//! output:
+//!
+//! - [`checks`]: the code quality checks and the [`CheckResult`] model
+//! - [`config`]: loading the layered guardian config
+//! - [`ollama`]: the Ollama client, host scoring, and host selection
+//! - [`storage`]: the history of check runs and LLM verdicts
+//!
+//! ```no_run
+//! use guardian_core::checks::{configured_thresholds, run_selected_checks, select_checks};
+//! use guardian_core::config::GuardianConfig;
+//! use std::path::Path;
+//!
+//! # fn main() -> anyhow::Result<()> {
+//! let project = Path::new(".");
+//! let config = GuardianConfig::load(None, Some(project), None, None)?;
+//! let selected = select_checks(None, None, config.check.only.as_deref(), &config.checks)?;
+//! let thresholds = configured_thresholds(&config.check);
+//! for result in run_selected_checks(project, &thresholds, &selected) {
+//!     if !result.passed {
+//!         println!("{}: {}", result.check_name, result.message);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`CheckResult`]: checks::CheckResult
+
+pub mod checks;
+pub mod config;
+pub mod ollama;
+pub mod storage;
@@ -0,0 +1,114 @@
+//! Credentials for hosts behind authenticating reverse proxies.
+
+use crate::config::{HostAuth, OllamaHost, SecretSource, get_secret};
+use anyhow::{Context, Result, anyhow};
+use reqwest::RequestBuilder;
+
+/// Attach `host`'s credentials, if any, to a request.
+pub(super) fn apply_auth(request: RequestBuilder, host: &OllamaHost) -> Result<RequestBuilder> {
+    authorize(request, host, |source| match source {
+        SecretSource::Env(var) => std::env::var(var)
+            .ok()
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| anyhow!("${var} is not set")),
+        SecretSource::Keyring(name) => get_secret(name),
+    })
+}
+
+/// Attach credentials, reading secrets through `lookup`.
+fn authorize(
+    request: RequestBuilder,
+    host: &OllamaHost,
+    lookup: impl Fn(SecretSource<'_>) -> Result<String>,
+) -> Result<RequestBuilder> {
+    let Some(auth) = &host.auth else {
+        return Ok(request);
+    };
+    let source = auth
+        .secret_source()
+        .with_context(|| format!("Invalid auth for host {}", host.name))?;
+    let secret =
+        lookup(source).map_err(|e| anyhow!("Host {} requires authentication: {e:#}", host.name))?;
+    Ok(match auth {
+        HostAuth::Bearer { .. } => request.bearer_auth(secret),
+        HostAuth::Basic { username, .. } => request.basic_auth(username, Some(secret)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::Client;
+    use reqwest::header::AUTHORIZATION;
+
+    fn host(auth: Option<HostAuth>) -> OllamaHost {
+        OllamaHost {
+            base_url: "https://big72.example.com".to_string(),
+            auth,
+            ..OllamaHost::test("big72")
+        }
+    }
+
+    fn auth_header(auth: Option<HostAuth>, value: Option<&str>) -> Result<Option<String>> {
+        let request = Client::new().get("https://big72.example.com/api/tags");
+        let lookup = |_: SecretSource<'_>| value.map(str::to_string).context("secret is not set");
+        let request = authorize(request, &host(auth), lookup)?;
+        let request = request.build()?;
+        Ok(request
+            .headers()
+            .get(AUTHORIZATION)
+            .map(|v| v.to_str().unwrap().to_string()))
+    }
+
+    #[test]
+    fn test_no_auth() {
+        assert_eq!(auth_header(None, Some("unused")).unwrap(), None);
+    }
+
+    #[test]
+    fn test_bearer_and_basic() {
+        let bearer = HostAuth::Bearer {
+            token_env: None,
+            token_keyring: Some("big72".to_string()),
+        };
+        assert_eq!(
+            auth_header(Some(bearer), Some("abc123"))
+                .unwrap()
+                .as_deref(),
+            Some("Bearer abc123")
+        );
+
+        let basic = HostAuth::Basic {
+            username: "guardian".to_string(),
+            password_env: Some("PASSWORD".to_string()),
+            password_keyring: None,
+        };
+        // base64("guardian:hunter2")
+        assert_eq!(
+            auth_header(Some(basic), Some("hunter2"))
+                .unwrap()
+                .as_deref(),
+            Some("Basic Z3VhcmRpYW46aHVudGVyMg==")
+        );
+    }
+
+    #[test]
+    fn test_missing_secret() {
+        let bearer = HostAuth::Bearer {
+            token_env: Some("GUARDIAN_BIG72_TOKEN".to_string()),
+            token_keyring: None,
+        };
+        let err = auth_header(Some(bearer), None).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Host big72 requires authentication: secret is not set"
+        );
+
+        let both = HostAuth::Bearer {
+            token_env: Some("GUARDIAN_BIG72_TOKEN".to_string()),
+            token_keyring: Some("big72".to_string()),
+        };
+        let err = auth_header(Some(both), Some("abc123")).unwrap_err();
+        assert!(format!("{err:#}").contains("exactly one of token_env and token_keyring"));
+    }
+}
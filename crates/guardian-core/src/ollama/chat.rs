@@ -0,0 +1,110 @@
+//! Chat requests via /api/chat, used to send a system prompt or earlier turns.
+//!
+//! A system message carries the guardian persona, required output format,
+//! and organizational rules separately from the user's content. Requests
+//! with a system prompt, or continuing a conversation, are sent here instead
+//! of /api/generate; the streamed reply is read the same way, so callers
+//! don't need to care.
+
+use super::ModelOptions;
+use super::generate::GenerateRequest;
+use serde::{Deserialize, Serialize};
+
+/// One message in a chat conversation.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ChatMessage {
+    /// "system", "user", or "assistant".
+    pub role: String,
+    /// Message text.
+    pub content: String,
+}
+
+impl ChatMessage {
+    pub fn new(role: &str, content: impl Into<String>) -> Self {
+        Self {
+            role: role.to_string(),
+            content: content.into(),
+        }
+    }
+}
+
+/// Request body for /api/chat.
+#[derive(Debug, Serialize)]
+pub struct ChatRequest<'a> {
+    model: &'a str,
+    messages: Vec<ChatMessage>,
+    stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<&'a serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    options: Option<&'a ModelOptions>,
+}
+
+impl<'a> ChatRequest<'a> {
+    /// Build a chat request equivalent to `request`: its system message,
+    /// then its earlier turns, then its prompt.
+    pub fn new(request: &'a GenerateRequest) -> Self {
+        let system = request.system.iter().map(|s| ChatMessage::new("system", s));
+        let mut messages: Vec<ChatMessage> =
+            system.chain(request.history.iter().cloned()).collect();
+        messages.push(ChatMessage::new("user", &request.prompt));
+        Self {
+            model: &request.model,
+            messages,
+            stream: request.stream,
+            format: request.format.as_ref(),
+            options: request.options.as_ref(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chat_request_leads_with_system() {
+        let request = GenerateRequest {
+            model: "llama3.2".to_string(),
+            prompt: "Review this".to_string(),
+            stream: true,
+            format: Some(serde_json::json!("json")),
+            options: None,
+            system: Some("You are a guardian".to_string()),
+            history: Vec::new(),
+        };
+        let json = serde_json::to_value(ChatRequest::new(&request)).unwrap();
+        assert_eq!(json["messages"][0]["role"], "system");
+        assert_eq!(json["messages"][0]["content"], "You are a guardian");
+        assert_eq!(json["messages"][1]["role"], "user");
+        assert_eq!(json["messages"][1]["content"], "Review this");
+        assert_eq!(json["format"], "json");
+        assert_eq!(json["stream"], true);
+        assert!(json.get("options").is_none());
+    }
+
+    #[test]
+    fn test_chat_request_keeps_history() {
+        let request = GenerateRequest {
+            model: "llama3.2".to_string(),
+            prompt: "Why is #2 blocking?".to_string(),
+            stream: true,
+            format: None,
+            options: None,
+            system: None,
+            history: vec![
+                ChatMessage::new("user", "Review this"),
+                ChatMessage::new("assistant", "REVISE"),
+            ],
+        };
+        let json = serde_json::to_value(ChatRequest::new(&request)).unwrap();
+        let roles: Vec<_> = json["messages"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m["role"].as_str().unwrap())
+            .collect();
+        assert_eq!(roles, ["user", "assistant", "user"]);
+        assert_eq!(json["messages"][2]["content"], "Why is #2 blocking?");
+    }
+}
@@ -0,0 +1,183 @@
+//! Per-host circuit breaker backed by a small JSON state file.
+//!
+//! Each failed request against a host increments its consecutive failure
+//! count. Once the count reaches the configured threshold the circuit opens
+//! and automatic host selection skips the host until the cooldown expires,
+//! so a known-down machine does not cost every command a full timeout.
+
+use crate::config::state::{self, format_utc, now_secs};
+use crate::config::{CircuitBreakerSettings, OllamaHost};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use tracing::{info, warn};
+
+/// Persisted state for one host.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+struct HostCircuit {
+    /// Consecutive failed requests.
+    failures: u32,
+    /// Unix timestamp (seconds) until which the circuit is open.
+    #[serde(default)]
+    open_until: Option<u64>,
+}
+
+/// Tracks recent failures per host and decides which hosts to skip.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    settings: CircuitBreakerSettings,
+    path: Option<PathBuf>,
+    hosts: BTreeMap<String, HostCircuit>,
+}
+
+impl CircuitBreaker {
+    /// Load breaker state from `path`.
+    ///
+    /// A missing or unreadable state file starts with all circuits closed.
+    /// When the breaker is disabled (or `path` is `None`) nothing is persisted.
+    pub fn load(settings: &CircuitBreakerSettings, path: Option<PathBuf>) -> Self {
+        let path = path.filter(|_| settings.enabled);
+        let hosts = path.as_deref().and_then(state::read).unwrap_or_default();
+
+        Self {
+            settings: settings.clone(),
+            path,
+            hosts,
+        }
+    }
+
+    /// Return the Unix timestamp until which `host`'s circuit is open, if it is open now.
+    pub fn open_until(&self, host: &str) -> Option<u64> {
+        if !self.settings.enabled {
+            return None;
+        }
+        self.hosts
+            .get(host)
+            .and_then(|c| c.open_until)
+            .filter(|until| *until > now_secs())
+    }
+
+    /// Filter out hosts whose circuit is currently open, logging each skip.
+    pub fn available<'a>(&self, hosts: Vec<&'a OllamaHost>) -> Vec<&'a OllamaHost> {
+        hosts
+            .into_iter()
+            .filter(|host| match self.open_until(&host.name) {
+                Some(until) => {
+                    info!(
+                        "host {} skipped (circuit open until {})",
+                        host.name,
+                        format_utc(until)
+                    );
+                    false
+                }
+                None => true,
+            })
+            .collect()
+    }
+
+    /// Record the outcome of a request against `host`.
+    pub fn record(&mut self, host: &str, success: bool) {
+        if !self.settings.enabled {
+            return;
+        }
+        let circuit = self.hosts.entry(host.to_string()).or_default();
+        if success {
+            *circuit = HostCircuit::default();
+            return;
+        }
+
+        circuit.failures += 1;
+        if circuit.failures >= self.settings.failure_threshold {
+            let now = now_secs();
+            if circuit.open_until.is_none_or(|until| until <= now) {
+                warn!(host = %host, failures = circuit.failures, "Opening circuit for host");
+            }
+            circuit.open_until = Some(now + self.settings.cooldown_secs);
+        }
+    }
+
+    /// Write the current state back to the state file.
+    pub fn save(&self) {
+        if let Some(path) = &self.path {
+            state::write(path, &self.hosts);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn settings(threshold: u32) -> CircuitBreakerSettings {
+        CircuitBreakerSettings {
+            enabled: true,
+            failure_threshold: threshold,
+            cooldown_secs: 60,
+        }
+    }
+
+    #[test]
+    fn test_opens_after_threshold() {
+        let mut breaker = CircuitBreaker::load(&settings(2), None);
+        breaker.record("big72", false);
+        assert!(breaker.open_until("big72").is_none());
+        breaker.record("big72", false);
+        assert!(breaker.open_until("big72").is_some());
+    }
+
+    #[test]
+    fn test_success_resets_failures() {
+        let mut breaker = CircuitBreaker::load(&settings(2), None);
+        breaker.record("big72", false);
+        breaker.record("big72", true);
+        breaker.record("big72", false);
+        assert!(breaker.open_until("big72").is_none());
+    }
+
+    #[test]
+    fn test_available_skips_open_hosts() {
+        let mut breaker = CircuitBreaker::load(&settings(1), None);
+        breaker.record("down", false);
+
+        let (down, up) = (OllamaHost::test("down"), OllamaHost::test("up"));
+        let available = breaker.available(vec![&down, &up]);
+        assert_eq!(available.len(), 1);
+        assert_eq!(available[0].name, "up");
+    }
+
+    #[test]
+    fn test_disabled_never_opens() {
+        let mut cfg = settings(1);
+        cfg.enabled = false;
+        let mut breaker = CircuitBreaker::load(&cfg, None);
+        breaker.record("big72", false);
+        assert!(breaker.open_until("big72").is_none());
+    }
+
+    #[test]
+    fn test_state_persists_between_loads() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("state").join("circuit.json");
+
+        let mut breaker = CircuitBreaker::load(&settings(1), Some(path.clone()));
+        breaker.record("big72", false);
+        breaker.save();
+
+        let reloaded = CircuitBreaker::load(&settings(1), Some(path));
+        assert!(reloaded.open_until("big72").is_some());
+    }
+
+    #[test]
+    fn test_expired_circuit_is_closed() {
+        let mut breaker = CircuitBreaker::load(&settings(1), None);
+        breaker.hosts.insert(
+            "big72".to_string(),
+            HostCircuit {
+                failures: 5,
+                open_until: Some(now_secs() - 1),
+            },
+        );
+        assert!(breaker.open_until("big72").is_none());
+    }
+}
@@ -0,0 +1,137 @@
+//! Token estimation and model context-window lookup.
+//!
+//! Ollama silently drops the start of prompts that exceed the context window,
+//! which makes evaluations confidently wrong. These helpers let commands
+//! estimate a prompt's size and compare it with the window before sending.
+
+use super::OllamaClient;
+use super::auth::apply_auth;
+use crate::config::OllamaHost;
+use anyhow::{Context, Result};
+use tracing::debug;
+
+/// Context size Ollama uses when neither the model nor the request sets `num_ctx`.
+pub const OLLAMA_DEFAULT_NUM_CTX: u64 = 2048;
+
+/// Context-related details of a model, from /api/show.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ModelInfo {
+    /// Maximum context the model was trained for.
+    pub context_length: Option<u64>,
+    /// `num_ctx` set in the model's Modelfile parameters.
+    pub num_ctx: Option<u64>,
+}
+
+impl ModelInfo {
+    /// Context window a request will actually get, in tokens.
+    ///
+    /// `requested` is the `num_ctx` guardian sends with requests, if any.
+    pub fn effective_context(&self, requested: Option<u64>) -> u64 {
+        let window = requested.or(self.num_ctx).unwrap_or(OLLAMA_DEFAULT_NUM_CTX);
+        self.context_length.map_or(window, |max| window.min(max))
+    }
+}
+
+impl OllamaClient {
+    /// Query a model's context details from /api/show.
+    pub async fn show_model(&self, host: &OllamaHost, model: &str) -> Result<ModelInfo> {
+        let url = format!("{}/api/show", host.base_url.trim_end_matches('/'));
+        let resp = apply_auth(self.client.post(&url), host)?
+            .json(&serde_json::json!({ "model": model }))
+            .send()
+            .await
+            .with_context(|| format!("Failed to connect to {}", host.name))?;
+
+        if !resp.status().is_success() {
+            anyhow::bail!(
+                "Host {} returned HTTP {} for /api/show",
+                host.name,
+                resp.status().as_u16()
+            );
+        }
+
+        let json: serde_json::Value = resp
+            .json()
+            .await
+            .with_context(|| format!("Failed to parse /api/show response from {}", host.name))?;
+        let info = parse_show(&json);
+        debug!(host = %host.name, model = %model, ?info, "Model info");
+        Ok(info)
+    }
+}
+
+/// Extract context details from an /api/show response.
+fn parse_show(json: &serde_json::Value) -> ModelInfo {
+    let context_length = json
+        .get("model_info")
+        .and_then(|info| info.as_object())
+        .and_then(|info| {
+            info.iter()
+                .find(|(key, _)| key.ends_with(".context_length"))
+                .and_then(|(_, value)| value.as_u64())
+        });
+
+    let num_ctx = json
+        .get("parameters")
+        .and_then(|p| p.as_str())
+        .and_then(|params| {
+            params.lines().find_map(|line| {
+                let mut parts = line.split_whitespace();
+                (parts.next() == Some("num_ctx"))
+                    .then(|| parts.next()?.parse().ok())
+                    .flatten()
+            })
+        });
+
+    ModelInfo {
+        context_length,
+        num_ctx,
+    }
+}
+
+/// Estimate the number of tokens in `text`.
+///
+/// Uses the common rule of thumb of ~4 characters per token for English and
+/// code, rounded up. Good enough to catch prompts that clearly won't fit.
+pub fn estimate_tokens(text: &str) -> u64 {
+    (text.chars().count() as u64).div_ceil(4)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcd"), 1);
+        assert_eq!(estimate_tokens("abcde"), 2);
+    }
+
+    #[test]
+    fn test_parse_show() {
+        let json = serde_json::json!({
+            "parameters": "stop \"<|im_end|>\"\nnum_ctx 8192",
+            "model_info": {
+                "general.architecture": "qwen2",
+                "qwen2.context_length": 32768
+            }
+        });
+        let info = parse_show(&json);
+        assert_eq!(info.context_length, Some(32768));
+        assert_eq!(info.num_ctx, Some(8192));
+    }
+
+    #[test]
+    fn test_effective_context() {
+        let info = ModelInfo {
+            context_length: Some(8192),
+            num_ctx: None,
+        };
+        assert_eq!(info.effective_context(None), OLLAMA_DEFAULT_NUM_CTX);
+        assert_eq!(info.effective_context(Some(4096)), 4096);
+        // Requests can't exceed what the model supports
+        assert_eq!(info.effective_context(Some(65536)), 8192);
+        assert_eq!(ModelInfo::default().effective_context(None), 2048);
+    }
+}
@@ -0,0 +1,198 @@
+//! Text generation via /api/generate (or /api/chat with a system prompt or
+//! earlier turns).
+
+use super::auth::apply_auth;
+use super::chat::{ChatMessage, ChatRequest};
+use super::{Capability, OllamaClient};
+use crate::config::OllamaHost;
+use anyhow::{Context, Result};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+/// Request for text generation.
+#[derive(Debug, Serialize)]
+pub struct GenerateRequest {
+    /// Model name to use.
+    pub model: String,
+    /// The prompt to send.
+    pub prompt: String,
+    /// Whether to stream responses (true so partial output survives Ctrl-C).
+    pub stream: bool,
+    /// Output format: `"json"` or a JSON schema.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<serde_json::Value>,
+    /// Model runtime options.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options: Option<ModelOptions>,
+    /// System prompt; when set the request is sent via /api/chat.
+    #[serde(skip)]
+    pub system: Option<String>,
+    /// Earlier turns of the conversation; when set the request is sent via
+    /// /api/chat.
+    #[serde(skip)]
+    pub history: Vec<ChatMessage>,
+}
+
+/// Runtime options sent with generation requests.
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct ModelOptions {
+    /// Context window size in tokens.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_ctx: Option<u64>,
+}
+
+/// Response from text generation.
+#[derive(Debug, Deserialize)]
+pub struct GenerateResponse {
+    /// The generated text.
+    pub response: String,
+    /// Whether generation is complete.
+    pub done: bool,
+    /// Total duration in nanoseconds.
+    #[serde(default)]
+    pub total_duration: Option<u64>,
+    /// Number of tokens generated in the response.
+    #[serde(default)]
+    pub eval_count: Option<u64>,
+    /// Number of prompt tokens the model evaluated.
+    #[serde(default)]
+    pub prompt_eval_count: Option<u64>,
+}
+
+impl OllamaClient {
+    /// Generate text using a model on a host.
+    pub async fn generate(
+        &self,
+        host: &OllamaHost,
+        model: &str,
+        prompt: &str,
+    ) -> Result<GenerateResponse> {
+        let request = self.generate_request(model, prompt);
+        self.send_generate(host, &request).await
+    }
+
+    /// Continue a conversation: ask `prompt` after the earlier turns in
+    /// `history`.
+    pub async fn chat(
+        &self,
+        host: &OllamaHost,
+        model: &str,
+        history: &[ChatMessage],
+        prompt: &str,
+    ) -> Result<GenerateResponse> {
+        let mut request = self.generate_request(model, prompt);
+        request.history = history.to_vec();
+        self.send_generate(host, &request).await
+    }
+
+    /// Build a streaming generate request using this client's model options.
+    pub fn generate_request(&self, model: &str, prompt: &str) -> GenerateRequest {
+        GenerateRequest {
+            model: model.to_string(),
+            prompt: prompt.to_string(),
+            stream: true,
+            format: None,
+            options: self.options.clone(),
+            system: self.system.clone(),
+            history: Vec::new(),
+        }
+    }
+
+    /// Send a generate request to a host.
+    ///
+    /// Requests with a system prompt or history go to /api/chat instead.
+    pub async fn send_generate(
+        &self,
+        host: &OllamaHost,
+        request: &GenerateRequest,
+    ) -> Result<GenerateResponse> {
+        self.check_capabilities(host, request).await?;
+        let _job = self.job_slots.clone().acquire_owned().await?;
+        let _permit = self.limiter.acquire(host).await?;
+        let start = std::time::Instant::now();
+        let result = self.post_generate(host, request, start).await;
+        self.record_usage(host, &request.model, &result, start.elapsed());
+        result
+    }
+
+    async fn post_generate(
+        &self,
+        host: &OllamaHost,
+        request: &GenerateRequest,
+        start: std::time::Instant,
+    ) -> Result<GenerateResponse> {
+        let (endpoint, body) = if request.system.is_some() || !request.history.is_empty() {
+            ("chat", serde_json::to_value(ChatRequest::new(request))?)
+        } else {
+            ("generate", serde_json::to_value(request)?)
+        };
+        let url = format!("{}/api/{endpoint}", host.base_url.trim_end_matches('/'));
+        let (model, prompt) = (&request.model, &request.prompt);
+
+        info!(
+            host = %host.name,
+            model = %model,
+            prompt_len = prompt.len(),
+            "Sending generate request"
+        );
+        debug!(prompt = %prompt, "Full prompt");
+
+        let resp = apply_auth(self.client.post(&url), host)?
+            .json(&body)
+            .send()
+            .await
+            .with_context(|| format!("Failed to connect to {}", host.name))?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            warn!(
+                host = %host.name,
+                status = %status,
+                body = %body,
+                "Generate request failed"
+            );
+            if status == StatusCode::NOT_FOUND {
+                anyhow::bail!(
+                    "Model '{}' is not available on host {}. Pull it with `ollama pull {}` or set auto_pull = true",
+                    model,
+                    host.name,
+                    model
+                );
+            }
+            anyhow::bail!("Host {} returned HTTP {}: {}", host.name, status, body);
+        }
+
+        let gen_resp = self
+            .read_stream(host, resp)
+            .await
+            .with_context(|| format!("Failed to read {endpoint} response from {}", host.name))?;
+
+        let duration_ms = start.elapsed().as_millis() as u64;
+        info!(
+            host = %host.name,
+            model = %model,
+            response_len = gen_resp.response.len(),
+            duration_ms = duration_ms,
+            prompt_eval_count = ?gen_resp.prompt_eval_count,
+            eval_count = ?gen_resp.eval_count,
+            "Generate complete"
+        );
+        debug!(response = %gen_resp.response, "Full response");
+
+        Ok(gen_resp)
+    }
+
+    /// Make sure the host's Ollama supports everything `request` uses.
+    async fn check_capabilities(&self, host: &OllamaHost, request: &GenerateRequest) -> Result<()> {
+        if request.system.is_some() || !request.history.is_empty() {
+            self.require(host, Capability::Chat).await?;
+        }
+        match &request.format {
+            Some(serde_json::Value::String(_)) => self.require(host, Capability::JsonFormat).await,
+            Some(_) => self.require(host, Capability::JsonSchema).await,
+            None => Ok(()),
+        }
+    }
+}
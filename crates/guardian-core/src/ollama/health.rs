@@ -0,0 +1,237 @@
+//! Rolling host health tracking for serve mode.
+//!
+//! The daemon pings every host on an interval and feeds the results into a
+//! [`HealthTracker`]. Each host keeps a window of recent samples for rolling
+//! latency and availability, and its up/down state only flips after several
+//! consecutive results agree so a single blip doesn't flap routing.
+
+use super::PingResult;
+use crate::config::ServeSettings;
+use crate::config::state::{self, now_secs};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::Path;
+
+/// Health state of a single host.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HostHealth {
+    /// Host name from the configuration.
+    pub name: String,
+    /// Whether routing should consider this host up.
+    pub healthy: bool,
+    /// Fraction of recent pings that succeeded (0.0 - 1.0).
+    pub availability: f64,
+    /// Mean latency of recent successful pings.
+    pub avg_latency_ms: Option<u64>,
+    /// Consecutive failed pings.
+    pub consecutive_failures: u32,
+    /// Consecutive successful pings.
+    pub consecutive_successes: u32,
+    /// Error from the most recent failed ping.
+    pub last_error: Option<String>,
+    /// Recent samples: latency for successes, `None` for failures.
+    #[serde(skip)]
+    samples: VecDeque<Option<u64>>,
+}
+
+/// Point-in-time view of all host health, shared with other commands.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HealthSnapshot {
+    /// Unix timestamp (seconds) of the last monitoring round.
+    pub updated_at: u64,
+    /// Seconds between monitoring rounds.
+    pub interval_secs: u64,
+    /// Per-host health, in configuration order.
+    pub hosts: Vec<HostHealth>,
+}
+
+/// Accumulates ping results into per-host health.
+#[derive(Debug)]
+pub struct HealthTracker {
+    settings: ServeSettings,
+    hosts: Vec<HostHealth>,
+}
+
+impl HealthTracker {
+    /// Create an empty tracker.
+    pub fn new(settings: &ServeSettings) -> Self {
+        Self {
+            settings: settings.clone(),
+            hosts: Vec::new(),
+        }
+    }
+
+    /// Feed one monitoring round of ping results into the tracker.
+    pub fn update(&mut self, results: &[PingResult]) {
+        for result in results {
+            let index = match self.hosts.iter().position(|h| h.name == result.host.name) {
+                Some(i) => i,
+                None => {
+                    self.hosts.push(HostHealth {
+                        name: result.host.name.clone(),
+                        healthy: result.reachable,
+                        availability: 0.0,
+                        avg_latency_ms: None,
+                        consecutive_failures: 0,
+                        consecutive_successes: 0,
+                        last_error: None,
+                        samples: VecDeque::new(),
+                    });
+                    self.hosts.len() - 1
+                }
+            };
+            apply_sample(&mut self.hosts[index], result, &self.settings);
+        }
+    }
+
+    /// Capture the current state for serving or persisting.
+    pub fn snapshot(&self) -> HealthSnapshot {
+        HealthSnapshot {
+            updated_at: now_secs(),
+            interval_secs: self.settings.health_interval_secs,
+            hosts: self.hosts.clone(),
+        }
+    }
+}
+
+impl HealthSnapshot {
+    /// Load a snapshot written by a running daemon, ignoring it if stale.
+    ///
+    /// A snapshot older than three monitoring intervals means the daemon is
+    /// not running, so callers fall back to pinging hosts themselves.
+    pub fn load_fresh(path: &Path) -> Option<Self> {
+        let snapshot: Self = state::read(path)?;
+        let max_age = snapshot.interval_secs.max(1) * 3;
+        (now_secs().saturating_sub(snapshot.updated_at) <= max_age).then_some(snapshot)
+    }
+
+    /// Whether `host` is currently considered healthy, if it is being tracked.
+    pub fn is_healthy(&self, host: &str) -> Option<bool> {
+        self.hosts
+            .iter()
+            .find(|h| h.name == host)
+            .map(|h| h.healthy)
+    }
+}
+
+fn apply_sample(health: &mut HostHealth, result: &PingResult, settings: &ServeSettings) {
+    let sample = if result.reachable {
+        result.latency_ms.or(Some(0))
+    } else {
+        None
+    };
+    health.samples.push_back(sample);
+    while health.samples.len() > settings.health_window.max(1) {
+        health.samples.pop_front();
+    }
+
+    if result.reachable {
+        health.consecutive_successes += 1;
+        health.consecutive_failures = 0;
+        if !health.healthy && health.consecutive_successes >= settings.up_after {
+            health.healthy = true;
+        }
+    } else {
+        health.consecutive_failures += 1;
+        health.consecutive_successes = 0;
+        health.last_error = result.error.clone();
+        if health.healthy && health.consecutive_failures >= settings.down_after {
+            health.healthy = false;
+        }
+    }
+
+    let latencies: Vec<u64> = health.samples.iter().flatten().copied().collect();
+    health.availability = latencies.len() as f64 / health.samples.len() as f64;
+    health.avg_latency_ms =
+        (!latencies.is_empty()).then(|| latencies.iter().sum::<u64>() / latencies.len() as u64);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::OllamaHost;
+    use tempfile::TempDir;
+
+    fn ping(name: &str, reachable: bool, latency: u64) -> PingResult {
+        PingResult {
+            host: OllamaHost::test(name),
+            reachable,
+            latency_ms: reachable.then_some(latency),
+            error: (!reachable).then(|| "Connection refused".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_first_sample_sets_state() {
+        let mut tracker = HealthTracker::new(&ServeSettings::default());
+        tracker.update(&[ping("up", true, 10), ping("down", false, 0)]);
+
+        let snapshot = tracker.snapshot();
+        assert_eq!(snapshot.is_healthy("up"), Some(true));
+        assert_eq!(snapshot.is_healthy("down"), Some(false));
+        assert_eq!(snapshot.is_healthy("unknown"), None);
+    }
+
+    #[test]
+    fn test_single_blip_does_not_flap() {
+        let mut tracker = HealthTracker::new(&ServeSettings::default());
+        tracker.update(&[ping("big72", true, 10)]);
+        tracker.update(&[ping("big72", false, 0)]);
+        assert_eq!(tracker.snapshot().is_healthy("big72"), Some(true));
+
+        tracker.update(&[ping("big72", false, 0)]);
+        assert_eq!(tracker.snapshot().is_healthy("big72"), Some(false));
+    }
+
+    #[test]
+    fn test_recovery_needs_consecutive_successes() {
+        let mut tracker = HealthTracker::new(&ServeSettings::default());
+        tracker.update(&[ping("big72", false, 0)]);
+        tracker.update(&[ping("big72", true, 10)]);
+        assert_eq!(tracker.snapshot().is_healthy("big72"), Some(false));
+
+        tracker.update(&[ping("big72", true, 10)]);
+        assert_eq!(tracker.snapshot().is_healthy("big72"), Some(true));
+    }
+
+    #[test]
+    fn test_rolling_stats() {
+        let settings = ServeSettings {
+            health_window: 3,
+            ..ServeSettings::default()
+        };
+        let mut tracker = HealthTracker::new(&settings);
+        for result in [
+            ping("big72", false, 0),
+            ping("big72", true, 10),
+            ping("big72", true, 30),
+            ping("big72", false, 0),
+        ] {
+            tracker.update(&[result]);
+        }
+
+        let host = &tracker.snapshot().hosts[0];
+        assert_eq!(host.avg_latency_ms, Some(20));
+        assert!((host.availability - 2.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_stale_snapshot_ignored() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("health.json");
+        let snapshot = HealthSnapshot {
+            updated_at: now_secs() - 1000,
+            interval_secs: 30,
+            hosts: Vec::new(),
+        };
+        state::write(&path, &snapshot);
+        assert!(HealthSnapshot::load_fresh(&path).is_none());
+
+        let fresh = HealthSnapshot {
+            updated_at: now_secs(),
+            ..snapshot
+        };
+        state::write(&path, &fresh);
+        assert_eq!(HealthSnapshot::load_fresh(&path), Some(fresh));
+    }
+}
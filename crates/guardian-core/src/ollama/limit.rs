@@ -0,0 +1,111 @@
+//! Limits on concurrent generate requests, per host and per client.
+//!
+//! A single-GPU Ollama host that receives several large requests at once can
+//! run out of memory. Hosts may set `max_concurrent`; requests beyond the
+//! limit queue until a slot frees up or the queue timeout expires. Across
+//! all hosts, at most `--jobs` requests from a client and its clones are in
+//! flight at once; another client has slots of its own.
+
+use super::OllamaClient;
+use crate::config::{OllamaHost, jobs};
+use anyhow::Result;
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tracing::debug;
+
+impl OllamaClient {
+    /// Ping at most `limit` hosts and keep at most `limit` generate requests
+    /// in flight at once, across all hosts; one per core when `None`.
+    pub fn with_jobs(mut self, limit: Option<NonZeroUsize>) -> Self {
+        self.jobs = jobs(limit);
+        self.job_slots = Arc::new(Semaphore::new(self.jobs));
+        self
+    }
+
+    /// How many hosts this client pings, or requests it sends, at once.
+    pub fn jobs(&self) -> usize {
+        self.jobs
+    }
+}
+
+/// Shared per-host semaphores, keyed by host name.
+#[derive(Debug)]
+pub struct HostLimiter {
+    semaphores: Mutex<HashMap<String, Arc<Semaphore>>>,
+    queue_timeout: Duration,
+}
+
+impl HostLimiter {
+    /// Create a limiter whose requests wait at most `queue_timeout` for a slot.
+    pub fn new(queue_timeout: Duration) -> Self {
+        Self {
+            semaphores: Mutex::new(HashMap::new()),
+            queue_timeout,
+        }
+    }
+
+    /// Wait for a request slot on `host`.
+    ///
+    /// Returns `None` when the host has no limit. The slot is released when
+    /// the returned permit is dropped.
+    pub async fn acquire(&self, host: &OllamaHost) -> Result<Option<OwnedSemaphorePermit>> {
+        let Some(max) = host.max_concurrent else {
+            return Ok(None);
+        };
+        let semaphore = self
+            .semaphores
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(host.name.clone())
+            .or_insert_with(|| Arc::new(Semaphore::new(max.max(1))))
+            .clone();
+
+        if semaphore.available_permits() == 0 {
+            debug!(host = %host.name, max, "Host at concurrency limit, queueing");
+        }
+        match tokio::time::timeout(self.queue_timeout, semaphore.acquire_owned()).await {
+            Ok(permit) => Ok(Some(permit?)),
+            Err(_) => anyhow::bail!(
+                "Timed out after {}s waiting for a free slot on host {} (max_concurrent = {max})",
+                self.queue_timeout.as_secs(),
+                host.name
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn host(max_concurrent: Option<usize>) -> OllamaHost {
+        OllamaHost {
+            max_concurrent,
+            ..OllamaHost::test("gpu1")
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unlimited_host_needs_no_permit() {
+        let limiter = HostLimiter::new(Duration::from_millis(10));
+        assert!(limiter.acquire(&host(None)).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_limit_queues_then_times_out() {
+        let limiter = HostLimiter::new(Duration::from_millis(20));
+        let limited = host(Some(1));
+
+        let first = limiter.acquire(&limited).await.unwrap();
+        assert!(first.is_some());
+        let err = limiter.acquire(&limited).await.unwrap_err();
+        assert!(err.to_string().contains("max_concurrent = 1"));
+
+        // Releasing the slot lets the next request through
+        drop(first);
+        assert!(limiter.acquire(&limited).await.unwrap().is_some());
+    }
+}
@@ -0,0 +1,295 @@
+//! Ollama HTTP client for Guardian CLI.
+//!
+//! Provides async communication with Ollama API servers for:
+//! - Health checks (ping), reusing recent ones across commands
+//! - Authentication for proxied hosts
+//! - Model listing
+//! - Text generation (with optional system prompt via chat)
+//! - Circuit breaking for flaky hosts
+//! - Rolling health tracking for serve mode
+//! - Model pulling and deleting
+//! - Token estimation and context windows
+//! - Structured (JSON) responses
+//! - Speculative racing of two hosts
+//! - Host scoring and selection
+//! - Per-host concurrency limits
+//! - Version detection and capability gating
+//! - Usage accounting
+//! - Streaming with partial output on interrupt
+//! - Model warmup
+
+mod auth;
+mod chat;
+mod circuit;
+mod context;
+mod generate;
+mod health;
+mod limit;
+mod ping;
+mod probes;
+mod prompt;
+mod pull;
+mod race;
+mod score;
+pub mod select;
+mod stream;
+mod structured;
+mod usage;
+mod version;
+mod warmup;
+
+pub use chat::ChatMessage;
+pub use circuit::CircuitBreaker;
+pub use context::estimate_tokens;
+pub use generate::{GenerateResponse, ModelOptions};
+pub use health::{HealthSnapshot, HealthTracker};
+pub use ping::PingResult;
+pub use probes::ProbeCache;
+pub use prompt::{Assembled, Priority, Prompt};
+pub use pull::{find_model, has_model};
+pub use race::race_hosts;
+pub use score::{HostScore, rank};
+pub use structured::{ResponseFormat, parse_json_response};
+pub use usage::{UsageSummary, load_usage, summarize};
+pub use version::{Capability, OllamaVersion};
+
+use crate::config::{OllamaHost, OllamaSection, jobs};
+use anyhow::{Context, Result};
+use auth::apply_auth;
+use limit::HostLimiter;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use tracing::{debug, info, warn};
+
+/// Information about a model available on an Ollama server.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct OllamaModel {
+    /// Model name/tag (e.g., "qwen2.5-coder:7b").
+    pub name: String,
+
+    /// Last modification timestamp.
+    #[serde(default)]
+    pub modified_at: Option<String>,
+
+    /// Model size in bytes.
+    #[serde(default)]
+    pub size: Option<u64>,
+
+    /// Additional model details.
+    #[serde(default)]
+    pub digest: Option<String>,
+}
+
+/// Response from the /api/tags endpoint.
+#[derive(Debug, Deserialize)]
+struct TagsResponse {
+    models: Vec<OllamaModel>,
+}
+
+/// HTTP client for communicating with Ollama servers.
+#[derive(Clone)]
+pub struct OllamaClient {
+    client: Client,
+    options: Option<ModelOptions>,
+    system: Option<String>,
+    limiter: Arc<HostLimiter>,
+    jobs: usize,
+    job_slots: Arc<Semaphore>,
+    versions: Arc<Mutex<HashMap<String, Option<OllamaVersion>>>>,
+    usage_log: Option<PathBuf>,
+    partial: Arc<Mutex<HashMap<String, String>>>,
+    probes: ProbeCache,
+}
+
+impl OllamaClient {
+    /// Create a new Ollama client with the specified timeout.
+    pub fn new(timeout_ms: u64) -> Result<Self> {
+        let client = Client::builder()
+            .timeout(Duration::from_millis(timeout_ms))
+            .build()
+            .context("Failed to build HTTP client")?;
+
+        Ok(Self {
+            client,
+            options: None,
+            system: None,
+            limiter: Arc::new(HostLimiter::new(Duration::from_secs(
+                OllamaSection::DEFAULT_QUEUE_TIMEOUT_SECS,
+            ))),
+            jobs: jobs(None),
+            job_slots: Arc::new(Semaphore::new(jobs(None))),
+            versions: Arc::default(),
+            usage_log: None,
+            partial: Arc::default(),
+            probes: ProbeCache::default(),
+        })
+    }
+
+    /// Request a specific context window (`num_ctx`) for generations.
+    pub fn with_num_ctx(mut self, num_ctx: Option<u64>) -> Self {
+        self.options = num_ctx.map(|n| ModelOptions { num_ctx: Some(n) });
+        self
+    }
+
+    /// Send `system` as a system prompt ahead of every prompt.
+    pub fn with_system_prompt(mut self, system: Option<String>) -> Self {
+        self.system = system;
+        self
+    }
+
+    /// Wait at most `secs` for a free slot on hosts with `max_concurrent`.
+    pub fn with_queue_timeout(mut self, secs: u64) -> Self {
+        self.limiter = Arc::new(HostLimiter::new(Duration::from_secs(secs)));
+        self
+    }
+
+    /// Reuse recent pings and model lists as `probes` says, instead of
+    /// always asking the host.
+    pub fn with_probe_cache(mut self, probes: ProbeCache) -> Self {
+        self.probes = probes;
+        self
+    }
+
+    /// List all models available on a host, or the ones it listed recently.
+    pub async fn list_models(&self, host: &OllamaHost) -> Result<Vec<OllamaModel>> {
+        if let Some(models) = self.probes.cached(host).and_then(|probe| probe.models) {
+            return Ok(models);
+        }
+        let url = format!("{}/api/tags", host.base_url.trim_end_matches('/'));
+
+        debug!(host = %host.name, url = %url, "Listing models");
+
+        let start = std::time::Instant::now();
+        let mut request = self.client.get(&url);
+        if let Some(ms) = host.default_timeout_ms {
+            request = request.timeout(Duration::from_millis(ms));
+        }
+        let resp = apply_auth(request, host)?
+            .send()
+            .await
+            .with_context(|| format!("Failed to connect to {}", host.name))?;
+
+        if !resp.status().is_success() {
+            warn!(host = %host.name, status = %resp.status(), "Failed to list models");
+            anyhow::bail!(
+                "Host {} returned HTTP {}: {}",
+                host.name,
+                resp.status().as_u16(),
+                resp.status().canonical_reason().unwrap_or("Unknown")
+            );
+        }
+
+        let tags: TagsResponse = resp
+            .json()
+            .await
+            .with_context(|| format!("Failed to parse response from {}", host.name))?;
+
+        info!(host = %host.name, model_count = tags.models.len(), "Listed models");
+        let ping = PingResult {
+            host: host.clone(),
+            reachable: true,
+            latency_ms: Some(start.elapsed().as_millis() as u64),
+            error: None,
+        };
+        self.probes.record(&ping, Some(tags.models.clone()));
+        Ok(tags.models)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::num::NonZeroUsize;
+
+    fn test_host(name: &str, port: u16) -> OllamaHost {
+        OllamaHost {
+            base_url: format!("http://127.0.0.1:{port}"),
+            ..OllamaHost::test(name)
+        }
+    }
+
+    #[test]
+    fn test_client_creation() {
+        let client = OllamaClient::new(2500);
+        assert!(client.is_ok());
+    }
+
+    #[test]
+    fn test_client_jobs() {
+        let two = OllamaClient::new(2500)
+            .unwrap()
+            .with_jobs(NonZeroUsize::new(2));
+        let one = two.clone().with_jobs(NonZeroUsize::new(1));
+        assert_eq!((two.jobs(), one.jobs()), (2, 1));
+        assert_eq!(two.job_slots.available_permits(), 2);
+        assert!(OllamaClient::new(2500).unwrap().jobs() >= 1);
+    }
+
+    #[test]
+    fn test_ping_result_reachable() {
+        let host = test_host("test", 11434);
+        let result = PingResult {
+            host: host.clone(),
+            reachable: true,
+            latency_ms: Some(50),
+            error: None,
+        };
+        assert!(result.reachable);
+        assert_eq!(result.latency_ms, Some(50));
+    }
+
+    #[test]
+    fn test_ping_result_unreachable() {
+        let host = test_host("test", 11434);
+        let result = PingResult {
+            host: host.clone(),
+            reachable: false,
+            latency_ms: None,
+            error: Some("Connection refused".to_string()),
+        };
+        assert!(!result.reachable);
+        assert!(result.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_ping_unreachable_host() {
+        // Use a port that's unlikely to be listening
+        let host = test_host("unreachable", 59999);
+        let client = OllamaClient::new(500).unwrap();
+
+        let result = client.ping_host(&host).await;
+        assert!(!result.reachable);
+        assert!(result.error.is_some());
+    }
+
+    #[test]
+    fn test_model_deserialization() {
+        let json = r#"{
+            "name": "qwen2.5-coder:7b",
+            "modified_at": "2024-01-01T00:00:00Z",
+            "size": 4000000000
+        }"#;
+        let model: OllamaModel = serde_json::from_str(json).unwrap();
+        assert_eq!(model.name, "qwen2.5-coder:7b");
+        assert_eq!(model.size, Some(4000000000));
+    }
+
+    #[test]
+    fn test_tags_response_deserialization() {
+        let json = r#"{
+            "models": [
+                {"name": "model1"},
+                {"name": "model2", "size": 1000}
+            ]
+        }"#;
+        let resp: TagsResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(resp.models.len(), 2);
+        assert_eq!(resp.models[0].name, "model1");
+        assert_eq!(resp.models[1].name, "model2");
+    }
+}
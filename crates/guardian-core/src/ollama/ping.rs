@@ -0,0 +1,94 @@
+//! Pinging hosts, reusing a recent ping when there is one (see
+//! [`super::probes`]).
+
+use super::auth::apply_auth;
+use super::{OllamaClient, TagsResponse};
+use crate::config::OllamaHost;
+use futures::StreamExt;
+use reqwest::StatusCode;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+/// Result of pinging a host.
+#[derive(Debug, Clone)]
+pub struct PingResult {
+    /// The host that was pinged.
+    pub host: OllamaHost,
+
+    /// Whether the host responded successfully.
+    pub reachable: bool,
+
+    /// Response time in milliseconds (if reachable).
+    pub latency_ms: Option<u64>,
+
+    /// Error message (if not reachable).
+    pub error: Option<String>,
+}
+
+impl OllamaClient {
+    /// Ping a host to check if it's reachable and Ollama is responding.
+    pub async fn ping_host(&self, host: &OllamaHost) -> PingResult {
+        if let Some(probe) = self.probes.cached(host) {
+            return probe.ping(host);
+        }
+        let url = format!("{}/api/tags", host.base_url.trim_end_matches('/'));
+        let start = Instant::now();
+
+        debug!(host = %host.name, url = %url, "Pinging Ollama host");
+
+        let mut request = self.client.get(&url);
+        if let Some(ms) = host.default_timeout_ms {
+            request = request.timeout(Duration::from_millis(ms));
+        }
+        let sent = match apply_auth(request, host) {
+            Ok(request) => request.send().await.map_err(anyhow::Error::from),
+            Err(e) => Err(e),
+        };
+        let mut models = None;
+        let result = match sent {
+            Ok(resp) if resp.status() == StatusCode::OK => {
+                let latency = start.elapsed().as_millis() as u64;
+                info!(host = %host.name, latency_ms = latency, "Host reachable");
+                models = resp.json::<TagsResponse>().await.ok().map(|t| t.models);
+                PingResult {
+                    host: host.clone(),
+                    reachable: true,
+                    latency_ms: Some(latency),
+                    error: None,
+                }
+            }
+            Ok(resp) => {
+                let latency = start.elapsed().as_millis() as u64;
+                let status = resp.status();
+                warn!(host = %host.name, status = %status, "Host returned non-OK status");
+                PingResult {
+                    host: host.clone(),
+                    reachable: false,
+                    latency_ms: Some(latency),
+                    error: Some(format!("HTTP status: {status}")),
+                }
+            }
+            Err(e) => {
+                warn!(host = %host.name, error = %e, "Failed to reach host");
+                PingResult {
+                    host: host.clone(),
+                    reachable: false,
+                    latency_ms: None,
+                    error: Some(e.to_string()),
+                }
+            }
+        };
+        self.probes.record(&result, models);
+        result
+    }
+
+    /// Ping multiple hosts concurrently, [`Self::jobs`] at a time, and return
+    /// results in the order of `hosts`.
+    pub async fn ping_hosts(&self, hosts: &[&OllamaHost]) -> Vec<PingResult> {
+        let pings: Vec<_> = hosts.iter().map(|host| self.ping_host(host)).collect();
+        futures::stream::iter(pings)
+            .buffered(self.jobs)
+            .collect()
+            .await
+    }
+}
@@ -0,0 +1,110 @@
+//! Recent pings and model lists, shared by commands run one after another.
+//!
+//! An agent that runs `select-host`, then `ask`, then `evaluate` would
+//! otherwise probe every host three times in as many seconds. What a host
+//! answered is kept in the `probes.json` state file and reused for
+//! `[ollama] probe_cache_secs` (default 30). `--fresh` probes again anyway,
+//! and pulling a model forgets the host's model list.
+//!
+//! A client keeps nothing unless given a [`ProbeCache`], so `serve`, which
+//! tracks health by pinging, and tests always probe.
+
+use super::{OllamaModel, PingResult};
+use crate::config::{OllamaHost, cache_stats, state};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+/// State file holding the probes, by host name.
+const PROBES_FILE: &str = "probes.json";
+
+/// Held while the file is read and rewritten, so concurrent pings don't
+/// drop each other's probes.
+static FILE_LOCK: Mutex<()> = Mutex::new(());
+
+/// What a host answered when it was last probed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct Probe {
+    /// The URL probed, so a probe doesn't outlive a change to the host.
+    base_url: String,
+    probed_at: u64,
+    reachable: bool,
+    latency_ms: Option<u64>,
+    error: Option<String>,
+    /// The host's models, when they were listed.
+    pub(super) models: Option<Vec<OllamaModel>>,
+}
+
+impl Probe {
+    /// The ping this probe records.
+    pub(super) fn ping(&self, host: &OllamaHost) -> PingResult {
+        PingResult {
+            host: host.clone(),
+            reachable: self.reachable,
+            latency_ms: self.latency_ms,
+            error: self.error.clone(),
+        }
+    }
+}
+
+/// How long a client keeps probes, and whether it reuses them. The default
+/// keeps none.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ProbeCache {
+    /// Seconds a probe is reused; 0 keeps none.
+    pub ttl_secs: u64,
+    /// Probe again instead of reusing a probe, like `--fresh`.
+    pub fresh: bool,
+}
+
+impl ProbeCache {
+    /// A probe of `host` recent enough to reuse.
+    pub(super) fn cached(self, host: &OllamaHost) -> Option<Probe> {
+        if self.fresh || self.ttl_secs == 0 {
+            return None;
+        }
+        let path = state::path(PROBES_FILE)?;
+        let mut probes: BTreeMap<String, Probe> = state::read(&path).unwrap_or_default();
+        let probe = probes.remove(&host.name).filter(|probe| {
+            probe.base_url == host.base_url && state::now_secs() <= probe.probed_at + self.ttl_secs
+        });
+        cache_stats::record("probes", probe.is_some());
+        probe
+    }
+
+    /// Remember `ping`, and the host's `models` if they were listed.
+    pub(super) fn record(self, ping: &PingResult, models: Option<Vec<OllamaModel>>) {
+        let probe = Probe {
+            base_url: ping.host.base_url.clone(),
+            probed_at: state::now_secs(),
+            reachable: ping.reachable,
+            latency_ms: ping.latency_ms,
+            error: ping.error.clone(),
+            models,
+        };
+        self.update(|probes| {
+            probes.insert(ping.host.name.clone(), probe);
+        });
+    }
+
+    /// Forget `host`'s probe, after a change such as a pulled model.
+    pub(super) fn forget(self, host: &OllamaHost) {
+        self.update(|probes| {
+            probes.remove(&host.name);
+        });
+    }
+
+    /// Apply `change` to the saved probes, when they are kept.
+    fn update(self, change: impl FnOnce(&mut BTreeMap<String, Probe>)) {
+        if self.ttl_secs == 0 {
+            return;
+        }
+        let Some(path) = state::path(PROBES_FILE) else {
+            return;
+        };
+        let _lock = FILE_LOCK.lock();
+        let mut probes = state::read(&path).unwrap_or_default();
+        change(&mut probes);
+        state::write(&path, &probes);
+    }
+}
@@ -0,0 +1,219 @@
+//! Prompt assembly under a token budget.
+//!
+//! A prompt is built from sections, each with a [`Priority`]. When the
+//! sections don't fit the budget, the lowest-priority ones are dropped,
+//! latest first, and a note in their place says what was left out. This is
+//! deterministic, unlike what a model does with an over-long prompt.
+
+use super::estimate_tokens;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+/// Tokens kept free for the note saying what was left out.
+const NOTE_TOKENS: u64 = 30;
+
+/// How much a section matters when the budget is tight. Sections are
+/// dropped from the lowest priority up; `Required` ones never are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+    /// Background the model can do without, such as passing checks.
+    Context,
+    Warning,
+    Error,
+    /// Instructions and framing the prompt is useless without.
+    Required,
+}
+
+/// A prompt being assembled.
+#[derive(Debug, Default)]
+pub struct Prompt {
+    sections: Vec<Section>,
+}
+
+#[derive(Debug)]
+struct Section {
+    priority: Priority,
+    /// What the section is, counted when it's omitted; `None` for headings.
+    kind: Option<&'static str>,
+    text: String,
+}
+
+/// An assembled prompt and what was left out of it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Assembled {
+    pub text: String,
+    /// Omitted sections by kind.
+    pub omitted: BTreeMap<&'static str, usize>,
+}
+
+impl Prompt {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `text`, such as a heading, that isn't counted when omitted.
+    pub fn push(&mut self, priority: Priority, text: impl Into<String>) {
+        self.sections.push(Section {
+            priority,
+            kind: None,
+            text: text.into(),
+        });
+    }
+
+    /// Add one `kind` of item, e.g. one `"warning"`.
+    pub fn push_item(&mut self, priority: Priority, kind: &'static str, text: impl Into<String>) {
+        self.sections.push(Section {
+            priority,
+            kind: Some(kind),
+            text: text.into(),
+        });
+    }
+
+    /// Join the sections, dropping what doesn't fit in `budget` tokens.
+    ///
+    /// A heading goes when everything under it does. If even the required
+    /// sections overflow the budget, they are sent anyway.
+    pub fn assemble(self, budget: Option<u64>) -> Assembled {
+        let sections = self.sections;
+        let cost: Vec<u64> = sections.iter().map(|s| estimate_tokens(&s.text)).collect();
+        let mut total: u64 = cost.iter().sum();
+        let mut dropped = vec![false; sections.len()];
+        if let Some(budget) = budget.filter(|&budget| total > budget) {
+            let mut order: Vec<usize> = (0..sections.len())
+                .filter(|&i| sections[i].priority != Priority::Required)
+                .collect();
+            // Lowest priority first, and the latest of equals first.
+            order.sort_by_key(|&i| (sections[i].priority, Reverse(i)));
+            let target = budget.saturating_sub(NOTE_TOKENS);
+            for i in order {
+                if total <= target {
+                    break;
+                }
+                dropped[i] = true;
+                total -= cost[i];
+            }
+            for i in 0..sections.len() {
+                let items = sections[i + 1..]
+                    .iter()
+                    .zip(&dropped[i + 1..])
+                    .take_while(|(s, _)| s.kind.is_some() && s.priority == sections[i].priority);
+                let mut items = items.peekable();
+                if sections[i].kind.is_none()
+                    && sections[i].priority != Priority::Required
+                    && items.peek().is_some()
+                    && items.all(|(_, &gone)| gone)
+                {
+                    dropped[i] = true;
+                }
+            }
+        }
+
+        let mut omitted = BTreeMap::new();
+        for (section, _) in sections.iter().zip(&dropped).filter(|(_, gone)| **gone) {
+            if let Some(kind) = section.kind {
+                *omitted.entry(kind).or_insert(0) += 1;
+            }
+        }
+        let first_dropped = dropped.iter().position(|&gone| gone);
+        let mut text = String::new();
+        for (i, section) in sections.into_iter().enumerate() {
+            if Some(i) == first_dropped {
+                text.push_str(&format!(
+                    "[... omitted to fit the token budget: {} ...]\n",
+                    summary(&omitted)
+                ));
+            }
+            if !dropped[i] {
+                text.push_str(&section.text);
+            }
+        }
+        Assembled { text, omitted }
+    }
+}
+
+impl Assembled {
+    /// What was omitted, e.g. "3 warning(s), 12 passing check(s)", if anything.
+    pub fn omitted_summary(&self) -> Option<String> {
+        (!self.omitted.is_empty()).then(|| summary(&self.omitted))
+    }
+}
+
+fn summary(omitted: &BTreeMap<&'static str, usize>) -> String {
+    if omitted.is_empty() {
+        return "some sections".to_string();
+    }
+    let counts: Vec<String> = omitted
+        .iter()
+        .map(|(kind, n)| format!("{n} {kind}(s)"))
+        .collect();
+    counts.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prompt() -> Prompt {
+        let mut prompt = Prompt::new();
+        prompt.push(Priority::Required, "Review these results.\n");
+        prompt.push(Priority::Error, "## Errors\n");
+        prompt.push_item(Priority::Error, "error", "- main.rs: 612 lines\n");
+        prompt.push(Priority::Warning, "## Warnings\n");
+        for i in 0..3 {
+            prompt.push_item(
+                Priority::Warning,
+                "warning",
+                format!("- w{i}: {}\n", "x".repeat(80)),
+            );
+        }
+        prompt.push(Priority::Context, "## Passed\n");
+        for i in 0..10 {
+            prompt.push_item(
+                Priority::Context,
+                "passing check",
+                format!("- p{i}: {}\n", "x".repeat(80)),
+            );
+        }
+        prompt.push(Priority::Required, "Answer with JSON.\n");
+        prompt
+    }
+
+    #[test]
+    fn test_fits_without_dropping() {
+        let assembled = prompt().assemble(Some(10_000));
+        assert_eq!(assembled.omitted_summary(), None);
+        assert!(assembled.text.contains("- p9: "));
+        assert_eq!(prompt().assemble(None).text, assembled.text);
+    }
+
+    #[test]
+    fn test_drops_context_then_warnings() {
+        let assembled = prompt().assemble(Some(100));
+        assert_eq!(
+            assembled.omitted_summary().as_deref(),
+            Some("10 passing check(s), 1 warning(s)")
+        );
+        let text = &assembled.text;
+        assert!(estimate_tokens(text) <= 100);
+        assert!(text.contains("- w1: ") && !text.contains("- w2: "));
+        assert!(!text.contains("## Passed"));
+        assert!(text.ends_with(
+            "[... omitted to fit the token budget: 10 passing check(s), 1 warning(s) ...]\nAnswer with JSON.\n"
+        ));
+    }
+
+    #[test]
+    fn test_required_sections_always_kept() {
+        let assembled = prompt().assemble(Some(1));
+        assert_eq!(
+            assembled.omitted_summary().as_deref(),
+            Some("1 error(s), 10 passing check(s), 3 warning(s)")
+        );
+        assert!(
+            assembled
+                .text
+                .starts_with("Review these results.\n[... omitted")
+        );
+        assert!(assembled.text.ends_with("Answer with JSON.\n"));
+    }
+}
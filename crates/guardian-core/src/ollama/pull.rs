@@ -0,0 +1,204 @@
+//! Model pulling via the streaming /api/pull endpoint, and deleting via
+//! /api/delete.
+
+use super::auth::apply_auth;
+use super::{OllamaClient, OllamaModel};
+use crate::config::OllamaHost;
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::{debug, info};
+
+/// Request body for /api/pull.
+#[derive(Debug, Serialize)]
+struct PullRequest<'a> {
+    model: &'a str,
+    stream: bool,
+}
+
+/// Request body for /api/delete.
+#[derive(Debug, Serialize)]
+struct DeleteRequest<'a> {
+    model: &'a str,
+}
+
+/// One progress line streamed from /api/pull.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct PullProgress {
+    /// Current phase (e.g., "pulling manifest", "pulling <digest>", "success").
+    #[serde(default)]
+    pub status: String,
+    /// Layer digest being downloaded.
+    #[serde(default)]
+    pub digest: Option<String>,
+    /// Layer size in bytes.
+    #[serde(default)]
+    pub total: Option<u64>,
+    /// Bytes of the layer downloaded so far.
+    #[serde(default)]
+    pub completed: Option<u64>,
+    /// Error reported by the server.
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+impl OllamaClient {
+    /// Pull `model` onto `host`, reporting each progress line to `on_progress`.
+    ///
+    /// The pull is aborted once the layers announced by the server add up to
+    /// more than `max_bytes`.
+    pub async fn pull_model(
+        &self,
+        host: &OllamaHost,
+        model: &str,
+        max_bytes: Option<u64>,
+        mut on_progress: impl FnMut(&PullProgress),
+    ) -> Result<()> {
+        let url = format!("{}/api/pull", host.base_url.trim_end_matches('/'));
+        info!(host = %host.name, model = %model, "Pulling model");
+
+        let mut resp = apply_auth(self.client.post(&url), host)?
+            .json(&PullRequest {
+                model,
+                stream: true,
+            })
+            .send()
+            .await
+            .with_context(|| format!("Failed to connect to {}", host.name))?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            anyhow::bail!("Host {} returned HTTP {}: {}", host.name, status, body);
+        }
+
+        let mut buffer = String::new();
+        let mut layers: HashMap<String, u64> = HashMap::new();
+        while let Some(chunk) = resp.chunk().await? {
+            buffer.push_str(&String::from_utf8_lossy(&chunk));
+            for progress in drain_lines::<PullProgress>(&mut buffer)? {
+                if let Some(error) = &progress.error {
+                    anyhow::bail!("Pull of {model} on {} failed: {error}", host.name);
+                }
+                if let (Some(digest), Some(total)) = (&progress.digest, progress.total) {
+                    layers.insert(digest.clone(), total);
+                }
+                let announced: u64 = layers.values().sum();
+                if let Some(max) = max_bytes.filter(|max| announced > *max) {
+                    anyhow::bail!(
+                        "Model {model} is at least {:.1} GB, over the auto-pull limit of {:.1} GB",
+                        announced as f64 / 1e9,
+                        max as f64 / 1e9
+                    );
+                }
+                on_progress(&progress);
+            }
+        }
+
+        debug!(host = %host.name, model = %model, "Pull complete");
+        self.probes.forget(host);
+        Ok(())
+    }
+
+    /// Delete `model` from `host`, freeing its disk space.
+    pub async fn delete_model(&self, host: &OllamaHost, model: &str) -> Result<()> {
+        let url = format!("{}/api/delete", host.base_url.trim_end_matches('/'));
+        info!(host = %host.name, model = %model, "Deleting model");
+
+        let resp = apply_auth(self.client.delete(&url), host)?
+            .json(&DeleteRequest { model })
+            .send()
+            .await
+            .with_context(|| format!("Failed to connect to {}", host.name))?;
+        self.probes.forget(host);
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            anyhow::bail!("Host {} returned HTTP {}: {}", host.name, status, body);
+        }
+        Ok(())
+    }
+}
+
+/// Parse and remove all complete JSON lines from a streamed response `buffer`.
+pub(super) fn drain_lines<T: DeserializeOwned>(buffer: &mut String) -> Result<Vec<T>> {
+    let Some(last_newline) = buffer.rfind('\n') else {
+        return Ok(Vec::new());
+    };
+    let complete: String = buffer.drain(..=last_newline).collect();
+    complete
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str(line).context("Invalid streamed response from server"))
+        .collect()
+}
+
+/// Whether `models` contains `name`, treating a missing tag as `:latest`.
+pub fn has_model(models: &[OllamaModel], name: &str) -> bool {
+    find_model(models, name).is_some()
+}
+
+/// The model in `models` called `name`, treating a missing tag as `:latest`.
+pub fn find_model<'a>(models: &'a [OllamaModel], name: &str) -> Option<&'a OllamaModel> {
+    let normalize = |n: &str| {
+        if n.contains(':') {
+            n.to_string()
+        } else {
+            format!("{n}:latest")
+        }
+    };
+    let wanted = normalize(name);
+    models.iter().find(|m| normalize(&m.name) == wanted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(name: &str) -> OllamaModel {
+        OllamaModel {
+            name: name.to_string(),
+            modified_at: None,
+            size: None,
+            digest: None,
+        }
+    }
+
+    #[test]
+    fn test_has_model_normalizes_latest() {
+        let models = vec![model("llama3.2:latest"), model("qwen2.5-coder:7b")];
+        assert!(has_model(&models, "llama3.2"));
+        assert!(has_model(&models, "qwen2.5-coder:7b"));
+        assert!(!has_model(&models, "qwen2.5-coder"));
+        assert!(!has_model(&models, "phi4"));
+        let found = find_model(&models, "llama3.2").unwrap();
+        assert_eq!(found.name, "llama3.2:latest");
+    }
+
+    #[test]
+    fn test_drain_lines_keeps_partial_line() {
+        let mut buffer = concat!(
+            r#"{"status":"pulling manifest"}"#,
+            "\n",
+            r#"{"status":"pulling abc","digest":"abc","total":100,"completed":50}"#,
+            "\n",
+            r#"{"status":"pull"#
+        )
+        .to_string();
+
+        let progress: Vec<PullProgress> = drain_lines(&mut buffer).unwrap();
+        assert_eq!(progress.len(), 2);
+        assert_eq!(progress[0].status, "pulling manifest");
+        assert_eq!(progress[1].total, Some(100));
+        assert_eq!(buffer, r#"{"status":"pull"#);
+    }
+
+    #[test]
+    fn test_drain_lines_reports_error_field() {
+        let mut buffer = "{\"error\":\"pull model manifest: file does not exist\"}\n".to_string();
+        let progress: Vec<PullProgress> = drain_lines(&mut buffer).unwrap();
+        assert!(progress[0].error.is_some());
+    }
+}
@@ -0,0 +1,109 @@
+//! Speculative racing of the same request against two hosts.
+//!
+//! Shared GPU boxes have unpredictable queueing delays. Sending an
+//! interactive request to two hosts and keeping whichever answers first hides
+//! that variance; the slower request is dropped, which closes its connection
+//! and lets Ollama abandon the generation.
+
+use crate::config::OllamaHost;
+use anyhow::Result;
+use std::future::Future;
+use tracing::{info, warn};
+
+/// Run `request` against both hosts concurrently and return the first success.
+///
+/// If one host fails, the other is still awaited. `record` is called with each
+/// host's outcome as it completes; the losing request is cancelled unrecorded.
+pub async fn race_hosts<'a, T, F, Fut>(
+    hosts: [&'a OllamaHost; 2],
+    request: F,
+    mut record: impl FnMut(&str, bool),
+) -> Result<(&'a OllamaHost, T)>
+where
+    F: Fn(&'a OllamaHost) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let [a, b] = hosts;
+    info!(first = %a.name, second = %b.name, "Racing hosts");
+    let first = request(a);
+    let second = request(b);
+    tokio::pin!(first, second);
+
+    let (done, result, other, pending) = tokio::select! {
+        result = &mut first => (a, result, b, second.as_mut()),
+        result = &mut second => (b, result, a, first.as_mut()),
+    };
+    record(&done.name, result.is_ok());
+    match result {
+        Ok(value) => {
+            info!(winner = %done.name, cancelled = %other.name, "Race won");
+            Ok((done, value))
+        }
+        Err(e) => {
+            warn!(host = %done.name, error = %e, "Racing host failed, waiting for the other");
+            let result = pending.await;
+            record(&other.name, result.is_ok());
+            result.map(|value| (other, value))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_fastest_host_wins() {
+        let (fast, slow) = (OllamaHost::test("fast"), OllamaHost::test("slow"));
+        let mut recorded = Vec::new();
+        let (winner, value) = race_hosts(
+            [&slow, &fast],
+            |h| async move {
+                let delay = if h.name == "fast" { 1 } else { 500 };
+                tokio::time::sleep(Duration::from_millis(delay)).await;
+                Ok(h.name.clone())
+            },
+            |name, ok| recorded.push((name.to_string(), ok)),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(winner.name, "fast");
+        assert_eq!(value, "fast");
+        assert_eq!(recorded, vec![("fast".to_string(), true)]);
+    }
+
+    #[tokio::test]
+    async fn test_failure_falls_through_to_other_host() {
+        let (broken, slow) = (OllamaHost::test("broken"), OllamaHost::test("slow"));
+        let mut recorded = Vec::new();
+        let (winner, _) = race_hosts(
+            [&broken, &slow],
+            |h| async move {
+                if h.name == "broken" {
+                    anyhow::bail!("connection refused");
+                }
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                Ok(())
+            },
+            |name, ok| recorded.push((name.to_string(), ok)),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(winner.name, "slow");
+        assert_eq!(
+            recorded,
+            vec![("broken".to_string(), false), ("slow".to_string(), true)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_both_failing_is_error() {
+        let (a, b) = (OllamaHost::test("a"), OllamaHost::test("b"));
+        let result: Result<(&OllamaHost, ())> =
+            race_hosts([&a, &b], |_| async { anyhow::bail!("down") }, |_, _| {}).await;
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,155 @@
+//! Host scoring for select-host.
+//!
+//! With more than a couple of machines, "first reachable primary" ignores
+//! most of what matters. Each host is scored from its latency, recent
+//! availability, and whether the requested model is already loaded, then
+//! scaled by the host's configured `weight`.
+
+use super::auth::apply_auth;
+use super::{Capability, OllamaClient, OllamaModel};
+use crate::config::OllamaHost;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// Share of the score from recent availability.
+const AVAILABILITY_WEIGHT: f64 = 50.0;
+/// Share of the score from latency.
+const LATENCY_WEIGHT: f64 = 30.0;
+/// Share of the score for having the model already loaded in memory.
+const LOADED_WEIGHT: f64 = 20.0;
+/// Latency (ms) at which the latency component is halved.
+const LATENCY_HALF_MS: f64 = 100.0;
+
+/// Measurements and resulting score for one host.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HostScore {
+    /// Host name from the configuration.
+    pub host: String,
+    /// Whether the host can serve the request (reachable, has the model).
+    pub usable: bool,
+    /// Whether this is a fallback host.
+    pub fallback: bool,
+    /// Ping or rolling average latency.
+    pub latency_ms: Option<u64>,
+    /// Fraction of recent pings that succeeded (0.0 - 1.0).
+    pub availability: f64,
+    /// Whether the requested model is currently loaded in memory.
+    pub model_loaded: bool,
+    /// Combined score (0 for unusable hosts); higher is better.
+    pub score: f64,
+}
+
+impl HostScore {
+    /// Score a host from its measurements.
+    pub fn new(
+        host: &OllamaHost,
+        usable: bool,
+        latency_ms: Option<u64>,
+        availability: f64,
+        model_loaded: bool,
+    ) -> Self {
+        let score = if usable {
+            let latency =
+                latency_ms.map_or(0.0, |ms| LATENCY_HALF_MS / (LATENCY_HALF_MS + ms as f64));
+            let loaded = if model_loaded { 1.0 } else { 0.0 };
+            let base = AVAILABILITY_WEIGHT * availability
+                + LATENCY_WEIGHT * latency
+                + LOADED_WEIGHT * loaded;
+            base * host.weight.max(0.0)
+        } else {
+            0.0
+        };
+        Self {
+            host: host.name.clone(),
+            usable,
+            fallback: host.fallback,
+            latency_ms,
+            availability,
+            model_loaded,
+            score,
+        }
+    }
+}
+
+/// Sort scores best first: primaries before fallbacks, then by score.
+pub fn rank(scores: &mut [HostScore]) {
+    scores.sort_by(|a, b| {
+        (b.usable, !b.fallback)
+            .cmp(&(a.usable, !a.fallback))
+            .then(b.score.total_cmp(&a.score))
+    });
+}
+
+/// Response from the /api/ps endpoint.
+#[derive(Debug, Deserialize)]
+struct PsResponse {
+    #[serde(default)]
+    models: Vec<OllamaModel>,
+}
+
+impl OllamaClient {
+    /// Models currently loaded in memory on a host.
+    pub async fn running_models(&self, host: &OllamaHost) -> Result<Vec<OllamaModel>> {
+        self.require(host, Capability::RunningModels).await?;
+        let url = format!("{}/api/ps", host.base_url.trim_end_matches('/'));
+        let resp = apply_auth(self.client.get(&url), host)?
+            .send()
+            .await
+            .with_context(|| format!("Failed to connect to {}", host.name))?
+            .error_for_status()?;
+        let ps: PsResponse = resp
+            .json()
+            .await
+            .with_context(|| format!("Failed to parse /api/ps response from {}", host.name))?;
+        Ok(ps.models)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn host(name: &str, weight: f64, fallback: bool) -> OllamaHost {
+        OllamaHost {
+            weight,
+            fallback,
+            ..OllamaHost::test(name)
+        }
+    }
+
+    #[test]
+    fn test_unusable_host_scores_zero() {
+        let score = HostScore::new(&host("down", 1.0, false), false, None, 0.0, false);
+        assert_eq!(score.score, 0.0);
+    }
+
+    #[test]
+    fn test_score_components() {
+        let h = host("big72", 1.0, false);
+        let fast = HostScore::new(&h, true, Some(0), 1.0, true);
+        assert!((fast.score - 100.0).abs() < 1e-9);
+
+        let slow = HostScore::new(&h, true, Some(100), 1.0, false);
+        assert!((slow.score - 65.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_weight_scales_score() {
+        let light = HostScore::new(&host("a", 0.5, false), true, Some(0), 1.0, false);
+        let heavy = HostScore::new(&host("b", 2.0, false), true, Some(0), 1.0, false);
+        assert!((heavy.score - 4.0 * light.score).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rank_orders_tiers_then_score() {
+        let mut scores = vec![
+            HostScore::new(&host("down", 1.0, false), false, None, 0.0, false),
+            HostScore::new(&host("fast-fallback", 1.0, true), true, Some(1), 1.0, true),
+            HostScore::new(&host("slow", 1.0, false), true, Some(900), 0.5, false),
+            HostScore::new(&host("fast", 1.0, false), true, Some(10), 1.0, false),
+        ];
+        rank(&mut scores);
+        let order: Vec<_> = scores.iter().map(|s| s.host.as_str()).collect();
+        assert_eq!(order, ["fast", "slow", "fast-fallback", "down"]);
+    }
+}
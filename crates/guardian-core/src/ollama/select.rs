@@ -0,0 +1,101 @@
+//! Host selection: which reachable host serves a request.
+
+use super::{CircuitBreaker, HealthSnapshot, OllamaClient, has_model};
+use crate::config::{GuardianConfig, OllamaHost, cache_stats, state};
+use anyhow::Result;
+use std::fmt;
+
+/// No host was reachable, or none matched.
+#[derive(Debug)]
+pub struct NoHosts(pub &'static str);
+
+impl fmt::Display for NoHosts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl std::error::Error for NoHosts {}
+
+/// Load the circuit breaker state for the configured hosts.
+pub fn load_breaker(config: &GuardianConfig) -> CircuitBreaker {
+    CircuitBreaker::load(&config.ollama.circuit_breaker, state::path("circuit.json"))
+}
+
+/// Load recent host health published by a running `serve` daemon, if any.
+pub fn load_health() -> Option<HealthSnapshot> {
+    let snapshot = HealthSnapshot::load_fresh(&state::path("health.json")?);
+    cache_stats::record("health", snapshot.is_some());
+    let snapshot = snapshot?;
+    tracing::debug!(
+        updated_at = snapshot.updated_at,
+        "Using host health from serve daemon"
+    );
+    Some(snapshot)
+}
+
+/// Decide whether a host is reachable.
+///
+/// Uses the daemon's health snapshot when it tracks the host, avoiding a
+/// blocking ping; otherwise pings the host and records the outcome.
+pub async fn is_reachable(
+    client: &OllamaClient,
+    breaker: &mut CircuitBreaker,
+    health: Option<&HealthSnapshot>,
+    host: &OllamaHost,
+) -> bool {
+    if let Some(healthy) = health.and_then(|h| h.is_healthy(&host.name)) {
+        return healthy;
+    }
+    let reachable = client.ping_host(host).await.reachable;
+    breaker.record(&host.name, reachable);
+    reachable
+}
+
+/// Resolve the host to use: the named host, or the first reachable one.
+pub async fn resolve_host<'a>(
+    config: &'a GuardianConfig,
+    client: &OllamaClient,
+    breaker: &mut CircuitBreaker,
+    host_name: Option<&str>,
+) -> Result<&'a OllamaHost> {
+    match host_name {
+        Some(name) => config
+            .enabled_hosts()
+            .into_iter()
+            .find(|h| h.name == name)
+            .ok_or_else(|| anyhow::anyhow!("Host '{}' not found or disabled", name)),
+        None => {
+            let health = load_health();
+            for host in breaker.available(config.enabled_hosts()) {
+                if is_reachable(client, breaker, health.as_ref(), host).await {
+                    return Ok(host);
+                }
+            }
+            breaker.save();
+            Err(NoHosts("No reachable hosts found").into())
+        }
+    }
+}
+
+/// Find a second reachable host that already has `model`, for racing.
+pub async fn race_partner<'a>(
+    config: &'a GuardianConfig,
+    client: &OllamaClient,
+    breaker: &mut CircuitBreaker,
+    primary: &OllamaHost,
+    model: &str,
+) -> Option<&'a OllamaHost> {
+    let health = load_health();
+    for host in breaker.available(config.enabled_hosts()) {
+        if host.name == primary.name || !is_reachable(client, breaker, health.as_ref(), host).await
+        {
+            continue;
+        }
+        match client.list_models(host).await {
+            Ok(models) if has_model(&models, model) => return Some(host),
+            _ => {}
+        }
+    }
+    None
+}
@@ -0,0 +1,165 @@
+//! Reading streamed generate/chat responses.
+//!
+//! Generations are streamed so that the text received so far is available
+//! if the user interrupts a long evaluation; see [`OllamaClient::partial_output`].
+
+use super::chat::ChatMessage;
+use super::pull::drain_lines;
+use super::{GenerateResponse, OllamaClient};
+use crate::config::OllamaHost;
+use anyhow::Result;
+use reqwest::Response;
+use serde::Deserialize;
+
+/// One streamed line from /api/generate or /api/chat.
+#[derive(Debug, Deserialize)]
+struct StreamChunk {
+    /// Text fragment from /api/generate.
+    #[serde(default)]
+    response: String,
+    /// Message fragment from /api/chat.
+    #[serde(default)]
+    message: Option<ChatMessage>,
+    #[serde(default)]
+    done: bool,
+    #[serde(default)]
+    total_duration: Option<u64>,
+    #[serde(default)]
+    eval_count: Option<u64>,
+    #[serde(default)]
+    prompt_eval_count: Option<u64>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+impl StreamChunk {
+    /// Add this line's text and, on the final line, its stats to `result`.
+    fn apply_to(self, host: &OllamaHost, result: &mut GenerateResponse) -> Result<()> {
+        if let Some(error) = self.error {
+            anyhow::bail!("Host {} reported an error: {error}", host.name);
+        }
+        result.response.push_str(&self.response);
+        if let Some(message) = self.message {
+            result.response.push_str(&message.content);
+        }
+        if self.done {
+            result.done = true;
+            result.total_duration = self.total_duration;
+            result.eval_count = self.eval_count;
+            result.prompt_eval_count = self.prompt_eval_count;
+        }
+        Ok(())
+    }
+}
+
+impl OllamaClient {
+    /// Read a streamed response into a single [`GenerateResponse`].
+    ///
+    /// Text is mirrored into the client's partial output for `host` as it
+    /// arrives.
+    pub(super) async fn read_stream(
+        &self,
+        host: &OllamaHost,
+        mut resp: Response,
+    ) -> Result<GenerateResponse> {
+        let mut result = GenerateResponse {
+            response: String::new(),
+            done: false,
+            total_duration: None,
+            eval_count: None,
+            prompt_eval_count: None,
+        };
+        self.set_partial(host, "");
+
+        // Bytes are only decoded once a full line has arrived, so multi-byte
+        // characters split across chunks survive intact.
+        let mut pending: Vec<u8> = Vec::new();
+        let mut lines = String::new();
+        loop {
+            let chunk = resp.chunk().await?;
+            match &chunk {
+                Some(bytes) => pending.extend_from_slice(bytes),
+                // Tolerate a final line without a trailing newline
+                None if !pending.is_empty() => pending.push(b'\n'),
+                None => {}
+            }
+            if let Some(end) = pending.iter().rposition(|b| *b == b'\n') {
+                let complete: Vec<u8> = pending.drain(..=end).collect();
+                lines.push_str(&String::from_utf8_lossy(&complete));
+            }
+            for line in drain_lines::<StreamChunk>(&mut lines)? {
+                line.apply_to(host, &mut result)?;
+            }
+            self.set_partial(host, &result.response);
+            if chunk.is_none() {
+                return Ok(result);
+            }
+        }
+    }
+
+    fn set_partial(&self, host: &OllamaHost, text: &str) {
+        if let Ok(mut partial) = self.partial.lock() {
+            partial.insert(host.name.clone(), text.to_string());
+        }
+    }
+
+    /// The longest response text received so far by an in-flight generation.
+    pub fn partial_output(&self) -> Option<String> {
+        let partial = self.partial.lock().ok()?;
+        partial
+            .values()
+            .max_by_key(|text| text.len())
+            .filter(|text| !text.is_empty())
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply_all(lines: &[&str]) -> Result<GenerateResponse> {
+        let mut result = GenerateResponse {
+            response: String::new(),
+            done: false,
+            total_duration: None,
+            eval_count: None,
+            prompt_eval_count: None,
+        };
+        for line in lines {
+            let chunk: StreamChunk = serde_json::from_str(line)?;
+            chunk.apply_to(&OllamaHost::test("big72"), &mut result)?;
+        }
+        Ok(result)
+    }
+
+    #[test]
+    fn test_generate_stream_accumulates() {
+        let result = apply_all(&[
+            r#"{"response":"Hello","done":false}"#,
+            r#"{"response":", world","done":false}"#,
+            r#"{"response":"","done":true,"eval_count":3,"prompt_eval_count":7}"#,
+        ])
+        .unwrap();
+        assert_eq!(result.response, "Hello, world");
+        assert!(result.done);
+        assert_eq!(result.eval_count, Some(3));
+        assert_eq!(result.prompt_eval_count, Some(7));
+    }
+
+    #[test]
+    fn test_chat_stream_accumulates() {
+        let result = apply_all(&[
+            r#"{"message":{"role":"assistant","content":"LG"},"done":false}"#,
+            r#"{"message":{"role":"assistant","content":"TM"},"done":true}"#,
+        ])
+        .unwrap();
+        assert_eq!(result.response, "LGTM");
+    }
+
+    #[test]
+    fn test_stream_error_line() {
+        let err = apply_all(&[r#"{"error":"model runner crashed"}"#]).unwrap_err();
+        assert!(err.to_string().contains("model runner crashed"));
+    }
+}
@@ -0,0 +1,222 @@
+//! Structured (JSON) responses using Ollama's `format` parameter.
+//!
+//! Even with `format` set, small models occasionally return truncated or
+//! fenced JSON, so responses are parsed here and the model is re-prompted
+//! with the parse error when they don't hold up.
+
+use super::{GenerateResponse, OllamaClient};
+use crate::config::OllamaHost;
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::path::Path;
+use tracing::warn;
+
+/// Requested structure for a model response.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResponseFormat {
+    /// Any valid JSON value.
+    Json,
+    /// JSON constrained by a JSON schema.
+    Schema(Value),
+}
+
+impl ResponseFormat {
+    /// Load a JSON schema from a file.
+    pub fn from_schema_file(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read schema {}", path.display()))?;
+        let schema = serde_json::from_str(&text)
+            .with_context(|| format!("Schema {} is not valid JSON", path.display()))?;
+        Ok(Self::Schema(schema))
+    }
+
+    /// Value for the request's `format` field.
+    fn request_value(&self) -> Value {
+        match self {
+            Self::Json => Value::String("json".to_string()),
+            Self::Schema(schema) => schema.clone(),
+        }
+    }
+}
+
+impl OllamaClient {
+    /// Generate a JSON response, re-prompting up to `retries` times on invalid output.
+    pub async fn generate_json(
+        &self,
+        host: &OllamaHost,
+        model: &str,
+        prompt: &str,
+        format: &ResponseFormat,
+        retries: u32,
+    ) -> Result<(Value, GenerateResponse)> {
+        let mut request = self.generate_request(model, prompt);
+        request.format = Some(format.request_value());
+
+        let mut attempt = 0;
+        loop {
+            let response = self.send_generate(host, &request).await?;
+            match parse_json_response(&response.response, format) {
+                Ok(value) => return Ok((value, response)),
+                Err(e) if attempt < retries => {
+                    attempt += 1;
+                    warn!(host = %host.name, attempt, error = %e, "Invalid JSON response, re-prompting");
+                    request.prompt = repair_prompt(prompt, &response.response, &e.to_string());
+                }
+                Err(e) => anyhow::bail!(
+                    "Model {model} did not return valid JSON after {} attempt(s): {e}",
+                    attempt + 1
+                ),
+            }
+        }
+    }
+}
+
+/// Parse a model reply as JSON, checking it against the schema when one is
+/// given.
+pub fn parse_json_response(text: &str, format: &ResponseFormat) -> Result<Value> {
+    let value: Value = serde_json::from_str(strip_code_fence(text))?;
+    if let ResponseFormat::Schema(schema) = format {
+        let errors = schema_errors(schema, &value);
+        if !errors.is_empty() {
+            anyhow::bail!("{}", errors.join("; "));
+        }
+    }
+    Ok(value)
+}
+
+/// Remove a surrounding Markdown code fence (```json ... ```), if present.
+fn strip_code_fence(text: &str) -> &str {
+    let trimmed = text.trim();
+    let Some(rest) = trimmed.strip_prefix("```") else {
+        return trimmed;
+    };
+    let body = rest.split_once('\n').map_or("", |(_, body)| body);
+    body.trim_end().strip_suffix("```").unwrap_or(body).trim()
+}
+
+/// Where `value` breaks `schema`'s top level: missing `required` fields, and
+/// properties of the wrong `type` or outside their `enum`. These are the
+/// mistakes small models make despite `format`; nested schemas aren't
+/// checked.
+fn schema_errors(schema: &Value, value: &Value) -> Vec<String> {
+    let required = schema.get("required").and_then(Value::as_array);
+    let missing: Vec<&str> = required
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .filter(|key| value.get(key).is_none())
+        .collect();
+    let mut errors = Vec::new();
+    if !missing.is_empty() {
+        errors.push(format!("missing required field(s): {}", missing.join(", ")));
+    }
+    let properties = schema.get("properties").and_then(Value::as_object);
+    for (key, property) in properties.into_iter().flatten() {
+        let Some(actual) = value.get(key) else {
+            continue;
+        };
+        let kind = property.get("type").and_then(Value::as_str);
+        let typed = match kind {
+            Some("string") => actual.is_string(),
+            Some("array") => actual.is_array(),
+            Some("object") => actual.is_object(),
+            Some("boolean") => actual.is_boolean(),
+            Some("number") => actual.is_number(),
+            Some("integer") => actual.is_i64() || actual.is_u64(),
+            _ => true,
+        };
+        if !typed {
+            errors.push(format!("{key} is not of type {}", kind.unwrap_or_default()));
+        }
+        if let Some(allowed) = property.get("enum").and_then(Value::as_array) {
+            if !allowed.contains(actual) {
+                let allowed: Vec<String> = allowed.iter().map(Value::to_string).collect();
+                errors.push(format!(
+                    "{key} is {actual}, not one of {}",
+                    allowed.join(", ")
+                ));
+            }
+        }
+    }
+    errors
+}
+
+/// Build a follow-up prompt asking the model to fix its previous reply.
+fn repair_prompt(original: &str, bad_reply: &str, error: &str) -> String {
+    format!(
+        "{original}\n\n\
+        Your previous reply was not valid JSON ({error}):\n\
+        {bad_reply}\n\n\
+        Reply again with only the corrected JSON and no other text."
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_plain_json() {
+        let value = parse_json_response(r#"{"verdict": "pass"}"#, &ResponseFormat::Json).unwrap();
+        assert_eq!(value["verdict"], "pass");
+    }
+
+    #[test]
+    fn test_parse_fenced_json() {
+        let text = "```json\n{\"verdict\": \"fail\"}\n```";
+        let value = parse_json_response(text, &ResponseFormat::Json).unwrap();
+        assert_eq!(value["verdict"], "fail");
+    }
+
+    #[test]
+    fn test_invalid_json_is_error() {
+        assert!(parse_json_response("The code looks fine.", &ResponseFormat::Json).is_err());
+    }
+
+    #[test]
+    fn test_schema_required_fields() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "required": ["verdict", "reasons"]
+        });
+        let format = ResponseFormat::Schema(schema);
+        let err = parse_json_response(r#"{"verdict": "pass"}"#, &format).unwrap_err();
+        assert!(err.to_string().contains("reasons"));
+        assert!(parse_json_response(r#"{"verdict": "pass", "reasons": []}"#, &format).is_ok());
+    }
+
+    #[test]
+    fn test_schema_types_and_enums() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "verdict": {"type": "string", "enum": ["allow", "deny"]},
+                "reasons": {"type": "array", "items": {"type": "string"}},
+            },
+        });
+        let format = ResponseFormat::Schema(schema);
+        let reply = r#"{"verdict": "maybe", "reasons": "none"}"#;
+        let err = parse_json_response(reply, &format).unwrap_err().to_string();
+        assert!(err.contains("verdict is \"maybe\", not one of \"allow\", \"deny\""));
+        assert!(err.contains("reasons is not of type array"));
+        assert!(parse_json_response(r#"{"verdict": "deny", "reasons": []}"#, &format).is_ok());
+    }
+
+    #[test]
+    fn test_request_value() {
+        assert_eq!(ResponseFormat::Json.request_value(), "json");
+        let schema = serde_json::json!({"type": "object"});
+        assert_eq!(
+            ResponseFormat::Schema(schema.clone()).request_value(),
+            schema
+        );
+    }
+
+    #[test]
+    fn test_repair_prompt_includes_error() {
+        let prompt = repair_prompt("Summarize", "{oops", "EOF while parsing");
+        assert!(prompt.starts_with("Summarize"));
+        assert!(prompt.contains("EOF while parsing"));
+        assert!(prompt.contains("{oops"));
+    }
+}
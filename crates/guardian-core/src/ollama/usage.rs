@@ -0,0 +1,169 @@
+//! Per-host, per-model usage accounting.
+//!
+//! Every generate request made by a client with a usage log appends one
+//! record (tokens, wall time, success) to a JSON-lines file, which
+//! `guardian-cli usage` aggregates to show which boxes and models actually
+//! carry the load.
+
+use super::{GenerateResponse, OllamaClient};
+use crate::config::OllamaHost;
+use crate::config::state::{self, now_secs};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// One generate request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageRecord {
+    /// Unix timestamp (seconds) when the request finished.
+    pub timestamp: u64,
+    pub host: String,
+    pub model: String,
+    pub success: bool,
+    /// Wall time of the request in milliseconds.
+    pub duration_ms: u64,
+    #[serde(default)]
+    pub prompt_tokens: Option<u64>,
+    #[serde(default)]
+    pub eval_tokens: Option<u64>,
+}
+
+/// Aggregated usage for one host and model.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct UsageSummary {
+    pub host: String,
+    pub model: String,
+    pub requests: u64,
+    pub failures: u64,
+    pub prompt_tokens: u64,
+    pub eval_tokens: u64,
+    /// Total wall time in milliseconds.
+    pub total_ms: u64,
+}
+
+impl OllamaClient {
+    /// Append a usage record for every generate request to `path`.
+    pub fn with_usage_log(mut self, path: Option<PathBuf>) -> Self {
+        self.usage_log = path;
+        self
+    }
+
+    /// Record the outcome of a generate request, if usage logging is on.
+    pub(super) fn record_usage(
+        &self,
+        host: &OllamaHost,
+        model: &str,
+        result: &Result<GenerateResponse>,
+        elapsed: Duration,
+    ) {
+        let Some(path) = &self.usage_log else {
+            return;
+        };
+        let response = result.as_ref().ok();
+        let record = UsageRecord {
+            timestamp: now_secs(),
+            host: host.name.clone(),
+            model: model.to_string(),
+            success: result.is_ok(),
+            duration_ms: elapsed.as_millis() as u64,
+            prompt_tokens: response.and_then(|r| r.prompt_eval_count),
+            eval_tokens: response.and_then(|r| r.eval_count),
+        };
+        state::append_line(path, &record);
+    }
+}
+
+/// Load usage records at or after `since` (Unix seconds), skipping bad lines.
+pub fn load_usage(path: &Path, since: u64) -> Vec<UsageRecord> {
+    let Ok(contents) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    contents
+        .lines()
+        .filter_map(|line| serde_json::from_str::<UsageRecord>(line).ok())
+        .filter(|record| record.timestamp >= since)
+        .collect()
+}
+
+/// Aggregate records per host and model, busiest first.
+pub fn summarize(records: &[UsageRecord]) -> Vec<UsageSummary> {
+    let mut totals: BTreeMap<(&str, &str), UsageSummary> = BTreeMap::new();
+    for record in records {
+        let summary = totals
+            .entry((&record.host, &record.model))
+            .or_insert_with(|| UsageSummary {
+                host: record.host.clone(),
+                model: record.model.clone(),
+                ..UsageSummary::default()
+            });
+        summary.requests += 1;
+        summary.failures += u64::from(!record.success);
+        summary.prompt_tokens += record.prompt_tokens.unwrap_or(0);
+        summary.eval_tokens += record.eval_tokens.unwrap_or(0);
+        summary.total_ms += record.duration_ms;
+    }
+    let mut summaries: Vec<_> = totals.into_values().collect();
+    summaries.sort_by_key(|s| std::cmp::Reverse(s.requests));
+    summaries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn record(timestamp: u64, host: &str, model: &str, success: bool) -> UsageRecord {
+        UsageRecord {
+            timestamp,
+            host: host.to_string(),
+            model: model.to_string(),
+            success,
+            duration_ms: 1000,
+            prompt_tokens: success.then_some(100),
+            eval_tokens: success.then_some(20),
+        }
+    }
+
+    #[test]
+    fn test_load_filters_by_time() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("usage.jsonl");
+        state::append_line(&path, &record(100, "big72", "phi4", true));
+        state::append_line(&path, &record(200, "big72", "phi4", true));
+        std::fs::write(
+            &path,
+            std::fs::read_to_string(&path).unwrap() + "not json\n",
+        )
+        .unwrap();
+
+        assert_eq!(load_usage(&path, 0).len(), 2);
+        assert_eq!(
+            load_usage(&path, 150),
+            vec![record(200, "big72", "phi4", true)]
+        );
+        assert!(load_usage(&temp.path().join("missing"), 0).is_empty());
+    }
+
+    #[test]
+    fn test_summarize_groups_by_host_and_model() {
+        let records = vec![
+            record(1, "big72", "phi4", true),
+            record(2, "big72", "phi4", false),
+            record(3, "big72", "llama3.2", true),
+            record(4, "local", "phi4", true),
+            record(5, "big72", "phi4", true),
+        ];
+        let summaries = summarize(&records);
+        assert_eq!(summaries.len(), 3);
+
+        let top = &summaries[0];
+        assert_eq!((top.host.as_str(), top.model.as_str()), ("big72", "phi4"));
+        assert_eq!(top.requests, 3);
+        assert_eq!(top.failures, 1);
+        assert_eq!(top.prompt_tokens, 200);
+        assert_eq!(top.eval_tokens, 40);
+        assert_eq!(top.total_ms, 3000);
+    }
+}
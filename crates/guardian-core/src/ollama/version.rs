@@ -0,0 +1,160 @@
+//! Ollama server version detection and capability gating.
+//!
+//! Fleets usually run a mix of Ollama versions. Instead of letting an old
+//! server answer a newer request with an opaque 404 or by silently ignoring
+//! a field, features check the host's version first and explain what's
+//! missing.
+
+use super::OllamaClient;
+use super::auth::apply_auth;
+use crate::config::OllamaHost;
+use anyhow::Result;
+use serde::Deserialize;
+use std::fmt;
+use std::str::FromStr;
+use tracing::debug;
+
+/// A parsed Ollama server version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct OllamaVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl FromStr for OllamaVersion {
+    type Err = anyhow::Error;
+
+    /// Parse "0.5.7", ignoring a leading "v" and pre-release suffixes like "-rc1".
+    fn from_str(s: &str) -> Result<Self> {
+        let core = s.trim().trim_start_matches('v');
+        let core = core.split(['-', '+']).next().unwrap_or(core);
+        let mut parts = core.split('.').map(str::parse::<u32>);
+        let mut next = || parts.next().transpose().map(Option::unwrap_or_default);
+        let version = Self {
+            major: next()?,
+            minor: next()?,
+            patch: next()?,
+        };
+        Ok(version)
+    }
+}
+
+impl fmt::Display for OllamaVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Server features that depend on the Ollama version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Capability {
+    /// The /api/chat endpoint.
+    Chat,
+    /// `format: "json"` on generate/chat.
+    JsonFormat,
+    /// `format` given as a JSON schema (structured outputs).
+    JsonSchema,
+    /// The /api/ps endpoint listing loaded models.
+    RunningModels,
+    /// `keep_alive` on generate requests.
+    KeepAlive,
+}
+
+impl Capability {
+    /// First Ollama release with the capability, and a human-readable name.
+    fn requirement(self) -> (OllamaVersion, &'static str) {
+        let v = |minor, patch| OllamaVersion {
+            major: 0,
+            minor,
+            patch,
+        };
+        match self {
+            Self::Chat => (v(1, 14), "the chat endpoint"),
+            Self::JsonFormat => (v(1, 9), "JSON mode"),
+            Self::JsonSchema => (v(5, 0), "JSON schema structured outputs"),
+            Self::RunningModels => (v(1, 38), "/api/ps"),
+            Self::KeepAlive => (v(1, 23), "keep_alive"),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct VersionResponse {
+    version: String,
+}
+
+impl OllamaClient {
+    /// The host's Ollama version, queried once and cached for this client.
+    ///
+    /// Returns `None` if the version can't be determined.
+    pub async fn server_version(&self, host: &OllamaHost) -> Option<OllamaVersion> {
+        if let Some(cached) = self.versions.lock().ok()?.get(&host.name) {
+            return *cached;
+        }
+
+        let url = format!("{}/api/version", host.base_url.trim_end_matches('/'));
+        let request = apply_auth(self.client.get(&url), host).ok()?;
+        let version = match request.send().await {
+            Ok(resp) if resp.status().is_success() => resp
+                .json::<VersionResponse>()
+                .await
+                .ok()
+                .and_then(|v| v.version.parse().ok()),
+            _ => None,
+        };
+        debug!(host = %host.name, ?version, "Ollama version");
+
+        if let Ok(mut versions) = self.versions.lock() {
+            versions.insert(host.name.clone(), version);
+        }
+        version
+    }
+
+    /// Fail with a clear message if the host's Ollama is too old for `capability`.
+    ///
+    /// Hosts whose version can't be determined are assumed to be capable.
+    pub async fn require(&self, host: &OllamaHost, capability: Capability) -> Result<()> {
+        let Some(version) = self.server_version(host).await else {
+            return Ok(());
+        };
+        let (min, feature) = capability.requirement();
+        if version < min {
+            anyhow::bail!(
+                "Host {} runs Ollama {version}, which lacks {feature} (needs {min} or newer)",
+                host.name
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(s: &str) -> OllamaVersion {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_versions() {
+        assert_eq!(version("0.5.7").to_string(), "0.5.7");
+        assert_eq!(version("v0.1.32-rc1").to_string(), "0.1.32");
+        assert_eq!(version("0.6").to_string(), "0.6.0");
+        assert!("latest".parse::<OllamaVersion>().is_err());
+    }
+
+    #[test]
+    fn test_versions_order_numerically() {
+        assert!(version("0.1.9") < version("0.1.14"));
+        assert!(version("0.5.0") > version("0.4.10"));
+    }
+
+    #[test]
+    fn test_capability_requirements() {
+        let (min, _) = Capability::JsonSchema.requirement();
+        assert!(version("0.4.7") < min);
+        assert!(version("0.5.7") >= min);
+    }
+}
@@ -0,0 +1,78 @@
+//! Preloading models into memory ahead of the first real request.
+//!
+//! A generate request without a prompt makes Ollama load the model and
+//! return immediately; `keep_alive` then controls how long it stays resident.
+
+use super::auth::apply_auth;
+use super::{Capability, OllamaClient};
+use crate::config::OllamaHost;
+use anyhow::{Context, Result};
+use reqwest::StatusCode;
+use serde::Serialize;
+use std::time::{Duration, Instant};
+use tracing::info;
+
+/// Request body for a prompt-less /api/generate.
+#[derive(Debug, Serialize)]
+struct WarmupRequest<'a> {
+    model: &'a str,
+    keep_alive: &'a str,
+    stream: bool,
+}
+
+impl OllamaClient {
+    /// Load `model` on `host` and keep it resident for `keep_alive`
+    /// (e.g. "30m", "2h", or "-1m" for indefinitely).
+    ///
+    /// Returns how long the load took.
+    pub async fn warm_model(
+        &self,
+        host: &OllamaHost,
+        model: &str,
+        keep_alive: &str,
+    ) -> Result<Duration> {
+        self.require(host, Capability::KeepAlive).await?;
+        let url = format!("{}/api/generate", host.base_url.trim_end_matches('/'));
+        info!(host = %host.name, model = %model, keep_alive = %keep_alive, "Warming model");
+
+        let start = Instant::now();
+        let resp = apply_auth(self.client.post(&url), host)?
+            .json(&WarmupRequest {
+                model,
+                keep_alive,
+                stream: false,
+            })
+            .send()
+            .await
+            .with_context(|| format!("Failed to connect to {}", host.name))?;
+
+        let status = resp.status();
+        if status == StatusCode::NOT_FOUND {
+            anyhow::bail!("Model '{model}' is not available on host {}", host.name);
+        }
+        if !status.is_success() {
+            let body = resp.text().await.unwrap_or_default();
+            anyhow::bail!("Host {} returned HTTP {}: {}", host.name, status, body);
+        }
+        Ok(start.elapsed())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_warmup_request_has_no_prompt() {
+        let request = WarmupRequest {
+            model: "phi4",
+            keep_alive: "1h",
+            stream: false,
+        };
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"model": "phi4", "keep_alive": "1h", "stream": false})
+        );
+    }
+}
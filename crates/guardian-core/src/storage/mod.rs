@@ -0,0 +1,344 @@
+//! Result history: check runs, scores, and LLM verdicts in SQLite.
+//!
+//! Every command that runs the checks records its results here, so history,
+//! comparisons with the last run, and trends read one store instead of each
+//! keeping its own state files. Runs are keyed by the project's canonical
+//! path; the database is shared by every project on the machine.
+//!
+//! ```no_run
+//! use guardian_core::config::GuardianConfig;
+//! use guardian_core::storage::History;
+//! use std::path::Path;
+//!
+//! # fn main() -> anyhow::Result<()> {
+//! let config = GuardianConfig::load(None, None, None, None)?;
+//! let history = History::open(&config.storage)?;
+//! for run in history.runs(Path::new("."), 10)? {
+//!     println!("{} {} score {}", run.timestamp, run.outcome, run.score);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+mod recent;
+mod rows;
+
+use crate::checks::{CheckResult, Severity};
+use crate::config::StorageSettings;
+use crate::config::state::now_secs;
+use anyhow::{Context, Result, bail};
+use directories::ProjectDirs;
+use rusqlite::{Connection, params};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Version of [`SCHEMA`], kept in the database's `user_version`.
+const SCHEMA_VERSION: i64 = 1;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS runs (
+    id INTEGER PRIMARY KEY,
+    project TEXT NOT NULL,
+    command TEXT NOT NULL,
+    timestamp INTEGER NOT NULL,
+    outcome TEXT NOT NULL,
+    errors INTEGER NOT NULL,
+    warnings INTEGER NOT NULL,
+    score REAL NOT NULL,
+    host TEXT,
+    model TEXT,
+    response TEXT
+);
+CREATE INDEX IF NOT EXISTS runs_by_project ON runs (project, id);
+CREATE TABLE IF NOT EXISTS results (
+    run_id INTEGER NOT NULL REFERENCES runs (id) ON DELETE CASCADE,
+    check_name TEXT NOT NULL,
+    passed INTEGER NOT NULL,
+    severity TEXT NOT NULL,
+    message TEXT NOT NULL,
+    file TEXT,
+    line INTEGER,
+    column_number INTEGER,
+    fix TEXT
+);
+CREATE INDEX IF NOT EXISTS results_by_run ON results (run_id);
+";
+
+/// How long a write waits for another guardian process to finish its own.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A finished run to record.
+#[derive(Debug, Clone)]
+pub struct NewRun<'a> {
+    pub project: &'a Path,
+    /// The command that ran the checks, such as `check` or `evaluate`.
+    pub command: &'a str,
+    pub results: &'a [CheckResult],
+    /// 0 to 100, the score policies see.
+    pub score: f64,
+    /// How the run ended: `passed` or `failed`, or `govern`'s verdict.
+    pub outcome: &'a str,
+    /// The model's response, when one reviewed the results.
+    pub llm: Option<LlmResponse<'a>>,
+}
+
+/// What a model said about a run.
+#[derive(Debug, Clone, Copy)]
+pub struct LlmResponse<'a> {
+    pub host: &'a str,
+    pub model: &'a str,
+    pub text: &'a str,
+}
+
+/// A recorded run, without its results.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StoredRun {
+    pub id: i64,
+    /// Canonical path of the project.
+    pub project: String,
+    pub command: String,
+    /// Unix timestamp (seconds) when the run was recorded.
+    pub timestamp: u64,
+    pub outcome: String,
+    pub errors: u64,
+    pub warnings: u64,
+    pub score: f64,
+    pub host: Option<String>,
+    pub model: Option<String>,
+    pub response: Option<String>,
+}
+
+/// The result history database.
+#[derive(Debug)]
+pub struct History {
+    conn: Connection,
+    settings: StorageSettings,
+}
+
+/// Path of the history database.
+///
+/// Returns `~/.local/share/guardian-cli/history.db` on Linux.
+pub fn default_path() -> Option<PathBuf> {
+    let proj = ProjectDirs::from("com", "softwarewrighter", "guardian-cli")?;
+    Some(proj.data_dir().join("history.db"))
+}
+
+impl History {
+    /// Open the history database at its default path, creating it if needed.
+    pub fn open(settings: &StorageSettings) -> Result<Self> {
+        let path = default_path().context("Could not determine the data directory")?;
+        Self::open_at(&path, settings)
+    }
+
+    /// Open the history database at `path`, creating it if needed.
+    pub fn open_at(path: &Path, settings: &StorageSettings) -> Result<Self> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let conn =
+            Connection::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        conn.pragma_update(None, "foreign_keys", true)?;
+        let version: i64 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+        if version > SCHEMA_VERSION {
+            bail!(
+                "{} was written by a newer guardian (schema {version})",
+                path.display()
+            );
+        }
+        conn.execute_batch(SCHEMA)?;
+        conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+        Ok(Self {
+            conn,
+            settings: settings.clone(),
+        })
+    }
+
+    /// Record `run` and apply the retention settings to its project,
+    /// returning the new run's id.
+    pub fn record(&mut self, run: &NewRun<'_>) -> Result<i64> {
+        let project = rows::project_key(run.project);
+        let failed = run.results.iter().filter(|r| !r.passed);
+        let errors = failed
+            .clone()
+            .filter(|r| r.severity == Severity::Error)
+            .count();
+        let warnings = failed.filter(|r| r.severity == Severity::Warning).count();
+        let llm = run.llm.as_ref();
+
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "INSERT INTO runs (project, command, timestamp, outcome, errors, warnings, score,
+                               host, model, response)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                project,
+                run.command,
+                now_secs(),
+                run.outcome,
+                errors,
+                warnings,
+                run.score,
+                llm.map(|l| l.host),
+                llm.map(|l| l.model),
+                llm.map(|l| l.text),
+            ],
+        )?;
+        let id = tx.last_insert_rowid();
+        {
+            let mut insert = tx.prepare(
+                "INSERT INTO results (run_id, check_name, passed, severity, message, file, line,
+                                      column_number, fix)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            )?;
+            for result in run.results {
+                insert.execute(params![
+                    id,
+                    result.check_name,
+                    result.passed,
+                    rows::severity_name(result.severity),
+                    result.message,
+                    result.file,
+                    result.line,
+                    result.column,
+                    result.fix,
+                ])?;
+            }
+        }
+        tx.commit()?;
+        self.prune(&project)?;
+        Ok(id)
+    }
+
+    /// The newest `limit` runs of `project`, newest first.
+    pub fn runs(&self, project: &Path, limit: usize) -> Result<Vec<StoredRun>> {
+        let mut query = self.conn.prepare(
+            "SELECT id, project, command, timestamp, outcome, errors, warnings, score,
+                    host, model, response
+             FROM runs WHERE project = ?1 ORDER BY id DESC LIMIT ?2",
+        )?;
+        let runs = query
+            .query_map(params![rows::project_key(project), limit], rows::run)?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(runs)
+    }
+
+    /// The check results of run `id`, in the order they were recorded.
+    pub fn results(&self, id: i64) -> Result<Vec<CheckResult>> {
+        let mut query = self.conn.prepare(
+            "SELECT check_name, passed, severity, message, file, line, column_number, fix
+             FROM results WHERE run_id = ?1 ORDER BY rowid",
+        )?;
+        let results = query
+            .query_map([id], rows::result)?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(results)
+    }
+
+    /// Delete runs the retention settings no longer keep: every project's
+    /// expired runs, and `project`'s runs beyond the newest `max_runs`.
+    fn prune(&self, project: &str) -> Result<()> {
+        let days = self.settings.retention_days;
+        if days > 0 {
+            let cutoff = now_secs().saturating_sub(days.saturating_mul(86_400));
+            self.conn
+                .execute("DELETE FROM runs WHERE timestamp < ?1", [cutoff])?;
+        }
+        if self.settings.max_runs > 0 {
+            self.conn.execute(
+                "DELETE FROM runs WHERE project = ?1 AND id NOT IN
+                     (SELECT id FROM runs WHERE project = ?1 ORDER BY id DESC LIMIT ?2)",
+                params![project, self.settings.max_runs],
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(max_runs: u64) -> StorageSettings {
+        StorageSettings {
+            max_runs,
+            ..Default::default()
+        }
+    }
+
+    fn run<'a>(project: &'a Path, results: &'a [CheckResult]) -> NewRun<'a> {
+        NewRun {
+            project,
+            command: "check",
+            results,
+            score: 75.0,
+            outcome: "failed",
+            llm: None,
+        }
+    }
+
+    #[test]
+    fn test_record_and_read_back() {
+        let temp = tempfile::tempdir().unwrap();
+        let mut history = History::open_at(&temp.path().join("history.db"), &settings(0)).unwrap();
+        let results = [
+            CheckResult::pass("rust-edition", "Edition 2024"),
+            CheckResult::fail("loc-limits", Severity::Error, "main.rs: 612 lines")
+                .with_file("./src/main.rs")
+                .with_line(1)
+                .with_fix("Split main.rs"),
+        ];
+        history.record(&run(temp.path(), &results)).unwrap();
+        let mut reviewed = run(temp.path(), &results);
+        reviewed.command = "evaluate";
+        reviewed.llm = Some(LlmResponse {
+            host: "big72",
+            model: "qwen3",
+            text: "Split the file.",
+        });
+        let id = history.record(&reviewed).unwrap();
+
+        let runs = history.runs(temp.path(), 10).unwrap();
+        assert_eq!(runs.len(), 2);
+        let newest = &runs[0];
+        assert_eq!((newest.id, newest.command.as_str()), (id, "evaluate"));
+        assert_eq!((newest.errors, newest.warnings, newest.score), (1, 0, 75.0));
+        assert_eq!(newest.model.as_deref(), Some("qwen3"));
+        assert_eq!(runs[1].host, None);
+        assert!(
+            history
+                .runs(&temp.path().join("other"), 10)
+                .unwrap()
+                .is_empty()
+        );
+
+        let stored = history.results(id).unwrap();
+        assert_eq!(stored.len(), 2);
+        assert!(stored[0].passed);
+        let failure = &stored[1];
+        assert_eq!(failure.severity, Severity::Error);
+        assert_eq!(failure.file.as_deref(), Some("./src/main.rs"));
+        assert_eq!((failure.line, failure.column), (Some(1), None));
+        assert_eq!(failure.fix.as_deref(), Some("Split main.rs"));
+    }
+
+    #[test]
+    fn test_retention_keeps_newest_runs() {
+        let temp = tempfile::tempdir().unwrap();
+        let mut history = History::open_at(&temp.path().join("history.db"), &settings(2)).unwrap();
+        let results = [CheckResult::fail("module-count", Severity::Warning, "9")];
+        let ids: Vec<i64> = (0..3)
+            .map(|_| history.record(&run(temp.path(), &results)).unwrap())
+            .collect();
+        let kept: Vec<i64> = history
+            .runs(temp.path(), 10)
+            .unwrap()
+            .iter()
+            .map(|r| r.id)
+            .collect();
+        assert_eq!(kept, [ids[2], ids[1]]);
+        assert!(history.results(ids[0]).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,124 @@
+//! Reading runs other than one project's newest: across projects, as
+//! `serve`'s dashboard and metrics do, by id, and by time.
+
+use super::{History, StoredRun, rows};
+use anyhow::Result;
+use rusqlite::{OptionalExtension, params};
+use std::path::Path;
+
+impl History {
+    /// The newest `limit` runs of every project, newest first.
+    pub fn recent_runs(&self, limit: usize) -> Result<Vec<StoredRun>> {
+        let mut query = self.conn.prepare(
+            "SELECT id, project, command, timestamp, outcome, errors, warnings, score,
+                    host, model, response
+             FROM runs ORDER BY id DESC LIMIT ?1",
+        )?;
+        let runs = query
+            .query_map([limit], rows::run)?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(runs)
+    }
+
+    /// How many runs have been recorded: the newest run's id, which keeps
+    /// counting the runs retention has since deleted.
+    pub fn runs_recorded(&self) -> Result<u64> {
+        let id: i64 = self
+            .conn
+            .query_row("SELECT COALESCE(MAX(id), 0) FROM runs", [], |row| {
+                row.get(0)
+            })?;
+        Ok(id as u64)
+    }
+
+    /// Failed results in every project's newest run, counted by check and
+    /// severity, as (check, severity, count).
+    pub fn latest_violations(&self) -> Result<Vec<(String, String, u64)>> {
+        let mut query = self.conn.prepare(
+            "SELECT check_name, severity, COUNT(*) FROM results
+             WHERE passed = 0 AND run_id IN (SELECT MAX(id) FROM runs GROUP BY project)
+             GROUP BY check_name, severity ORDER BY check_name, severity",
+        )?;
+        let counts = query
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(counts)
+    }
+
+    /// Run `id`, if the history still has it.
+    pub fn run(&self, id: i64) -> Result<Option<StoredRun>> {
+        let run = self
+            .conn
+            .query_row(
+                "SELECT id, project, command, timestamp, outcome, errors, warnings, score,
+                        host, model, response
+                 FROM runs WHERE id = ?1",
+                [id],
+                rows::run,
+            )
+            .optional()?;
+        Ok(run)
+    }
+
+    /// The oldest `check` or `evaluate` run of `project` recorded at or
+    /// after `since`, a Unix timestamp.
+    pub fn first_run_since(&self, project: &Path, since: u64) -> Result<Option<StoredRun>> {
+        let run = self
+            .conn
+            .query_row(
+                "SELECT id, project, command, timestamp, outcome, errors, warnings, score,
+                        host, model, response
+                 FROM runs
+                 WHERE project = ?1 AND timestamp >= ?2 AND command IN ('check', 'evaluate')
+                 ORDER BY id LIMIT 1",
+                params![rows::project_key(project), since],
+                rows::run,
+            )
+            .optional()?;
+        Ok(run)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::checks::{CheckResult, Severity};
+    use crate::config::StorageSettings;
+    use crate::storage::{History, NewRun};
+
+    #[test]
+    fn test_recent_runs_across_projects() {
+        let temp = tempfile::tempdir().unwrap();
+        let settings = StorageSettings::default();
+        let mut history = History::open_at(&temp.path().join("history.db"), &settings).unwrap();
+        let results = [CheckResult::fail("loc-limits", Severity::Error, "Too long")];
+        let (app, lib) = (temp.path().join("app"), temp.path().join("lib"));
+        let mut ids = Vec::new();
+        for project in [&app, &lib, &app] {
+            let run = NewRun {
+                project,
+                command: "check",
+                results: &results,
+                score: 75.0,
+                outcome: "failed",
+                llm: None,
+            };
+            ids.push(history.record(&run).unwrap());
+        }
+
+        let recent = history.recent_runs(2).unwrap();
+        let recent: Vec<_> = recent.iter().map(|run| run.id).collect();
+        assert_eq!(recent, [ids[2], ids[1]]);
+        let run = history.run(ids[1]).unwrap().unwrap();
+        assert!(run.project.ends_with("lib"));
+        assert_eq!(run.errors, 1);
+        assert_eq!(history.run(ids[2] + 1).unwrap(), None);
+
+        assert_eq!(history.runs_recorded().unwrap(), ids[2] as u64);
+        let violations = history.latest_violations().unwrap();
+        assert_eq!(violations, [("loc-limits".into(), "error".into(), 2)]);
+
+        let first = history.first_run_since(&app, 0).unwrap().unwrap();
+        assert_eq!(first.id, ids[0]);
+        assert_eq!(history.first_run_since(&app, u64::MAX >> 1).unwrap(), None);
+    }
+}
@@ -0,0 +1,74 @@
+//! Conversions between history rows and guardian's types.
+
+use super::StoredRun;
+use crate::checks::{CheckResult, Severity};
+use rusqlite::Row;
+use rusqlite::types::Type;
+use std::path::Path;
+
+/// The key runs of `project` are stored under: its canonical path, so runs
+/// from different working directories land together.
+pub(super) fn project_key(project: &Path) -> String {
+    project
+        .canonicalize()
+        .unwrap_or_else(|_| project.to_path_buf())
+        .display()
+        .to_string()
+}
+
+/// How a severity is stored.
+pub(super) fn severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Info => "info",
+        Severity::Warning => "warning",
+        Severity::Error => "error",
+    }
+}
+
+/// A run from a `runs` row, columns in table order.
+pub(super) fn run(row: &Row<'_>) -> rusqlite::Result<StoredRun> {
+    Ok(StoredRun {
+        id: row.get(0)?,
+        project: row.get(1)?,
+        command: row.get(2)?,
+        timestamp: row.get(3)?,
+        outcome: row.get(4)?,
+        errors: row.get(5)?,
+        warnings: row.get(6)?,
+        score: row.get(7)?,
+        host: row.get(8)?,
+        model: row.get(9)?,
+        response: row.get(10)?,
+    })
+}
+
+/// A check result from a `results` row, without its `run_id`.
+pub(super) fn result(row: &Row<'_>) -> rusqlite::Result<CheckResult> {
+    let severity: String = row.get(2)?;
+    let severity = match severity.as_str() {
+        "info" => Severity::Info,
+        "warning" => Severity::Warning,
+        "error" => Severity::Error,
+        other => {
+            let err = format!("unknown severity '{other}'");
+            return Err(rusqlite::Error::FromSqlConversionFailure(
+                2,
+                Type::Text,
+                err.into(),
+            ));
+        }
+    };
+    Ok(CheckResult {
+        check_name: row.get(0)?,
+        passed: row.get(1)?,
+        severity,
+        message: row.get(3)?,
+        file: row.get(4)?,
+        line: row.get(5)?,
+        column: row.get(6)?,
+        fix: row.get(7)?,
+        diff: None,
+        blame: None,
+        project: None,
+    })
+}
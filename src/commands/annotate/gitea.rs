@@ -0,0 +1,237 @@
+//! The commit and pull request a Gitea or Forgejo CI job runs for, via the
+//! Gitea API (Forgejo serves the same one).
+//!
+//! The job is found from Gitea and Forgejo Actions' GitHub-style variables,
+//! or Woodpecker's.
+
+use crate::config::{GiteaSettings, SecretSource, get_secret};
+use anyhow::{Context, Result, anyhow};
+use reqwest::{RequestBuilder, Response, StatusCode};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::fmt;
+use std::time::Duration;
+
+/// Reviews fetched per page.
+const PER_PAGE: usize = 50;
+
+/// A repository on a Gitea or Forgejo instance, at the commit under test.
+pub(super) struct Repository {
+    client: reqwest::Client,
+    /// `.../api/v1/repos/:owner/:repo`
+    url: String,
+    token: String,
+    sha: String,
+    /// Number of the pull request, when the job runs for one.
+    pub pull: Option<u64>,
+    /// Name of the commit status.
+    context: String,
+}
+
+/// A comment on a line of a pull request's diff.
+#[derive(Debug, Deserialize)]
+pub(super) struct ReviewComment {
+    pub body: String,
+    /// Who resolved the conversation, if anyone did.
+    #[serde(default)]
+    pub resolver: Option<Value>,
+}
+
+/// An error status from the Gitea API.
+#[derive(Debug)]
+struct ApiError {
+    status: StatusCode,
+    body: String,
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Gitea API returned {}: {}",
+            self.status,
+            self.body.trim()
+        )
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+/// Find the repository, commit, and pull request from CI variables read
+/// through `var`, without the token.
+fn locate(
+    settings: &GiteaSettings,
+    var: impl Fn(&str) -> Option<String>,
+) -> Result<(String, String, Option<u64>)> {
+    let either = |actions: &str, woodpecker: &str| {
+        var(actions).or_else(|| var(woodpecker)).with_context(|| {
+            format!(
+                "{actions} is not set; annotate --gitea runs in Gitea, Forgejo, or Woodpecker CI"
+            )
+        })
+    };
+    let base_url = match &settings.base_url {
+        Some(url) => url.clone(),
+        None => either("GITHUB_SERVER_URL", "CI_FORGE_URL")?,
+    };
+    let repo = either("GITHUB_REPOSITORY", "CI_REPO")?;
+    let url = format!("{}/api/v1/repos/{repo}", base_url.trim_end_matches('/'));
+    let sha = either("GITHUB_SHA", "CI_COMMIT_SHA")?;
+    // refs/pull/<number>/head under Actions.
+    let pull = var("GITHUB_REF")
+        .and_then(|r| Some(r.strip_prefix("refs/pull/")?.split('/').next()?.to_string()))
+        .or_else(|| var("CI_COMMIT_PULL_REQUEST"))
+        .and_then(|number| number.parse().ok());
+    Ok((url, sha, pull))
+}
+
+impl Repository {
+    /// The job's repository, authenticating with the configured token.
+    pub fn from_env(settings: &GiteaSettings) -> Result<Self> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        let (url, sha, pull) = locate(settings, var)?;
+        let token = match settings.token_source()? {
+            SecretSource::Env(name) => var(name).ok_or_else(|| anyhow!("${name} is not set"))?,
+            SecretSource::Keyring(name) => get_secret(name)?,
+        };
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()?;
+        Ok(Self {
+            client,
+            url,
+            token,
+            sha,
+            pull,
+            context: settings.context.clone(),
+        })
+    }
+
+    /// Send an authenticated request, failing on an error status.
+    async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        let response = request
+            .header("Authorization", format!("token {}", self.token))
+            .send()
+            .await
+            .context("Gitea API request failed")?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(ApiError { status, body }.into());
+        }
+        Ok(response)
+    }
+
+    /// Every review comment on pull request `pull`, and the body of each
+    /// review as a comment of its own.
+    pub async fn comments(&self, pull: u64) -> Result<Vec<ReviewComment>> {
+        let reviews_url = format!("{}/pulls/{pull}/reviews", self.url);
+        let mut comments = Vec::new();
+        for page in 1.. {
+            let request = self
+                .client
+                .get(&reviews_url)
+                .query(&[("limit", PER_PAGE), ("page", page)]);
+            let reviews: Vec<Value> = self.send(request).await?.json().await?;
+            for review in &reviews {
+                let body = review["body"].as_str().unwrap_or_default().to_string();
+                comments.push(ReviewComment {
+                    body,
+                    resolver: None,
+                });
+                let Some(id) = review["id"].as_u64() else {
+                    continue;
+                };
+                let request = self.client.get(format!("{reviews_url}/{id}/comments"));
+                let batch: Vec<ReviewComment> = self.send(request).await?.json().await?;
+                comments.extend(batch);
+            }
+            if reviews.len() < PER_PAGE {
+                break;
+            }
+        }
+        Ok(comments)
+    }
+
+    /// Post a review of pull request `pull` with `body` and line `comments`.
+    ///
+    /// Gitea rejects comments on paths the diff doesn't touch, so on a
+    /// rejection everything goes into the review body instead.
+    pub async fn review(&self, pull: u64, body: &str, comments: &[(Value, String)]) -> Result<()> {
+        let url = format!("{}/pulls/{pull}/reviews", self.url);
+        let review = |body: &str, comments: Vec<&Value>| json!({"body": body, "event": "COMMENT", "commit_id": self.sha, "comments": comments});
+        if !comments.is_empty() {
+            let positioned = comments.iter().map(|(comment, _)| comment).collect();
+            match self
+                .send(self.client.post(&url).json(&review(body, positioned)))
+                .await
+            {
+                Ok(_) => return Ok(()),
+                Err(e)
+                    if e.downcast_ref::<ApiError>()
+                        .is_some_and(|e| e.status.is_client_error()) =>
+                {
+                    tracing::debug!(error = %e, "Comments rejected, posting them in the review body");
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        let mut all = body.to_string();
+        for (_, note) in comments {
+            all.push_str(&format!("\n\n---\n\n{note}"));
+        }
+        let all = all.trim_start_matches("\n\n---\n\n");
+        self.send(self.client.post(&url).json(&review(all, Vec::new())))
+            .await?;
+        Ok(())
+    }
+
+    /// Set guardian's status on the commit under test.
+    pub async fn status(&self, state: &str, description: &str) -> Result<()> {
+        let url = format!("{}/statuses/{}", self.url, self.sha);
+        let status = json!({"state": state, "context": self.context, "description": description});
+        self.send(self.client.post(url).json(&status)).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locate_in(vars: &[(&str, &str)]) -> Result<(String, String, Option<u64>)> {
+        let var = |name: &str| {
+            vars.iter()
+                .find(|(var, _)| *var == name)
+                .map(|(_, value)| value.to_string())
+        };
+        locate(&GiteaSettings::default(), var)
+    }
+
+    #[test]
+    fn test_locate_actions_job() {
+        let (url, sha, pull) = locate_in(&[
+            ("GITHUB_SERVER_URL", "https://git.example.com/"),
+            ("GITHUB_REPOSITORY", "team/app"),
+            ("GITHUB_SHA", "abc123"),
+            ("GITHUB_REF", "refs/pull/42/head"),
+        ])
+        .unwrap();
+        assert_eq!(url, "https://git.example.com/api/v1/repos/team/app");
+        assert_eq!((sha.as_str(), pull), ("abc123", Some(42)));
+    }
+
+    #[test]
+    fn test_locate_woodpecker_push() {
+        let (url, _, pull) = locate_in(&[
+            ("CI_FORGE_URL", "https://codeberg.org"),
+            ("CI_REPO", "team/app"),
+            ("CI_COMMIT_SHA", "abc123"),
+        ])
+        .unwrap();
+        assert_eq!(url, "https://codeberg.org/api/v1/repos/team/app");
+        assert_eq!(pull, None);
+        let err = locate_in(&[("CI_REPO", "team/app")]).unwrap_err();
+        assert!(err.to_string().contains("GITHUB_SERVER_URL is not set"));
+    }
+}
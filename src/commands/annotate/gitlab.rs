@@ -0,0 +1,165 @@
+//! The merge request a GitLab CI pipeline runs for, via the GitLab API.
+
+use anyhow::{Context, Result};
+use reqwest::{RequestBuilder, Response, StatusCode};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::fmt;
+use std::time::Duration;
+
+/// Discussions fetched per page.
+const PER_PAGE: usize = 100;
+
+/// A merge request, found from GitLab CI's predefined variables.
+pub(super) struct MergeRequest {
+    client: reqwest::Client,
+    /// `.../projects/:id/merge_requests/:iid`
+    url: String,
+    /// Auth header name and value.
+    token: (&'static str, String),
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct Discussion {
+    pub id: String,
+    pub notes: Vec<Note>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct Note {
+    pub body: String,
+    #[serde(default)]
+    pub resolved: bool,
+}
+
+/// An error status from the GitLab API.
+#[derive(Debug)]
+struct ApiError {
+    status: StatusCode,
+    body: String,
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "GitLab API returned {}: {}",
+            self.status,
+            self.body.trim()
+        )
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+/// The commits a positioned note refers to.
+#[derive(Debug, Clone, Deserialize)]
+pub(super) struct DiffRefs {
+    pub base_commit_sha: String,
+    pub start_commit_sha: String,
+    pub head_commit_sha: String,
+}
+
+impl MergeRequest {
+    /// The pipeline's merge request, authenticating with `GITLAB_TOKEN` when
+    /// set and `CI_JOB_TOKEN` otherwise.
+    pub fn from_env() -> Result<Self> {
+        let var = |name: &str| {
+            std::env::var(name).with_context(|| {
+                format!("{name} is not set; annotate runs in GitLab merge request pipelines")
+            })
+        };
+        let url = format!(
+            "{}/projects/{}/merge_requests/{}",
+            var("CI_API_V4_URL")?.trim_end_matches('/'),
+            var("CI_PROJECT_ID")?,
+            var("CI_MERGE_REQUEST_IID")?
+        );
+        let token = match std::env::var("GITLAB_TOKEN") {
+            Ok(token) => ("PRIVATE-TOKEN", token),
+            Err(_) => ("JOB-TOKEN", var("CI_JOB_TOKEN")?),
+        };
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()?;
+        Ok(Self { client, url, token })
+    }
+
+    /// Send an authenticated request, failing on an error status.
+    async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        let response = request
+            .header(self.token.0, &self.token.1)
+            .send()
+            .await
+            .context("GitLab API request failed")?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(ApiError { status, body }.into());
+        }
+        Ok(response)
+    }
+
+    /// Every discussion on the merge request.
+    pub async fn discussions(&self) -> Result<Vec<Discussion>> {
+        let mut discussions = Vec::new();
+        for page in 1.. {
+            let request = self
+                .client
+                .get(format!("{}/discussions", self.url))
+                .query(&[("per_page", PER_PAGE), ("page", page)]);
+            let batch: Vec<Discussion> = self.send(request).await?.json().await?;
+            let last = batch.len() < PER_PAGE;
+            discussions.extend(batch);
+            if last {
+                break;
+            }
+        }
+        Ok(discussions)
+    }
+
+    /// The commits of the merge request's latest diff.
+    pub async fn diff_refs(&self) -> Result<DiffRefs> {
+        let request = self.client.get(format!("{}/versions", self.url));
+        let versions: Vec<DiffRefs> = self.send(request).await?.json().await?;
+        versions
+            .into_iter()
+            .next()
+            .context("The merge request has no diff versions")
+    }
+
+    /// Start a discussion, on the diff at `position` when given.
+    ///
+    /// GitLab rejects positions on lines the diff doesn't show, so those
+    /// discussions are started on the merge request itself instead.
+    pub async fn post(&self, body: &str, position: Option<Value>) -> Result<()> {
+        let url = format!("{}/discussions", self.url);
+        if let Some(position) = position {
+            let request = self
+                .client
+                .post(&url)
+                .json(&json!({"body": body, "position": position}));
+            match self.send(request).await {
+                Ok(_) => return Ok(()),
+                Err(e)
+                    if e.downcast_ref::<ApiError>()
+                        .is_some_and(|e| e.status == StatusCode::BAD_REQUEST) =>
+                {
+                    tracing::debug!(error = %e, "Position rejected, posting on the merge request");
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        self.send(self.client.post(&url).json(&json!({"body": body})))
+            .await?;
+        Ok(())
+    }
+
+    /// Mark a discussion resolved.
+    pub async fn resolve(&self, discussion_id: &str) -> Result<()> {
+        let url = format!("{}/discussions/{discussion_id}", self.url);
+        self.send(self.client.put(url).query(&[("resolved", true)]))
+            .await?;
+        Ok(())
+    }
+}
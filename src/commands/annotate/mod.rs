@@ -0,0 +1,291 @@
+//! Annotate command: post check failures on the merge request under review.
+//!
+//! Each failure becomes a GitLab merge request discussion, or a Gitea or
+//! Forgejo pull request review comment, on its line of the diff, tagged with
+//! the finding's Code Quality fingerprint in a hidden comment. Later runs
+//! skip findings that already have an open discussion, and on GitLab resolve
+//! the discussions of findings that are gone. On Gitea and Forgejo the
+//! verdict is also set as a commit status.
+
+mod gitea;
+mod gitlab;
+
+use super::exit::FailPolicy;
+use super::output::{finding_path, fingerprints, report};
+use crate::checks::{CheckResult, Severity};
+use crate::checks::{configured_thresholds, run_selected_checks, select_checks};
+use crate::config::{GiteaSettings, GuardianConfig};
+use anyhow::Result;
+use gitlab::{DiffRefs, Discussion, MergeRequest};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Starts the hidden comment that tags a discussion with its finding.
+const MARKER: &str = "<!-- guardian:";
+
+/// Options for the annotate command.
+pub struct AnnotateOptions<'a> {
+    pub path: Option<&'a Path>,
+    /// Only run these checks, comma-separated.
+    pub only: Option<&'a str>,
+    /// Don't run these checks, comma-separated.
+    pub skip: Option<&'a str>,
+    pub json_output: bool,
+    pub policy: FailPolicy,
+    /// The forge to post to.
+    pub forge: Forge,
+}
+
+/// A forge `annotate` posts to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Forge {
+    Gitlab,
+    /// Gitea or Forgejo.
+    Gitea,
+}
+
+/// What a sync changed on the forge.
+#[derive(Debug, Default)]
+struct Synced {
+    posted: usize,
+    resolved: usize,
+    unchanged: usize,
+    /// The commit status set, if the forge has them.
+    status: Option<&'static str>,
+}
+
+/// Run the checks and sync their failures with the merge request's
+/// discussions.
+pub async fn annotate(config: &GuardianConfig, opts: AnnotateOptions<'_>) -> Result<()> {
+    let project_dir = opts.path.unwrap_or(Path::new("."));
+    let check_config = configured_thresholds(&config.check);
+    let selected = select_checks(
+        opts.only,
+        opts.skip,
+        config.check.only.as_deref(),
+        &config.checks,
+    )?;
+    let results = run_selected_checks(project_dir, &check_config, &selected);
+    let failures: Vec<&CheckResult> = results.iter().filter(|r| !r.passed).collect();
+    let ids = fingerprints(&failures);
+
+    let verdict = opts.policy.enforce(&results);
+    let synced = match opts.forge {
+        Forge::Gitlab => sync_gitlab(&failures, &ids).await?,
+        Forge::Gitea => {
+            let settings = &config.annotate.gitea;
+            sync_gitea(settings, &failures, &ids, verdict.is_ok()).await?
+        }
+    };
+
+    let Synced {
+        posted,
+        resolved,
+        unchanged,
+        status,
+    } = synced;
+    if opts.json_output {
+        let json = json!({
+            "posted": posted,
+            "resolved": resolved,
+            "unchanged": unchanged,
+            "status": status,
+        });
+        report!("{}", serde_json::to_string_pretty(&json)?);
+    } else if let Some(status) = status {
+        report!("Posted {posted} new comment(s), left {unchanged} open, set status {status}");
+    } else {
+        report!("Posted {posted} new discussion(s), resolved {resolved}, left {unchanged} open");
+    }
+    verdict
+}
+
+/// Sync the failures with the GitLab merge request's discussions.
+async fn sync_gitlab(failures: &[&CheckResult], ids: &[String]) -> Result<Synced> {
+    let merge_request = MergeRequest::from_env()?;
+    let discussions = merge_request.discussions().await?;
+    let open = open_findings(&discussions);
+    let new: Vec<_> = failures
+        .iter()
+        .zip(ids)
+        .filter(|(_, id)| !open.contains_key(id.as_str()))
+        .collect();
+    let refs = if new
+        .iter()
+        .any(|(r, _)| r.file.is_some() && r.line.is_some())
+    {
+        Some(merge_request.diff_refs().await?)
+    } else {
+        None
+    };
+    for (result, id) in &new {
+        let position = refs.as_ref().and_then(|refs| position(refs, result));
+        merge_request.post(&note_body(result, id), position).await?;
+    }
+    let fixed: Vec<&str> = open
+        .iter()
+        .filter(|(id, _)| !ids.iter().any(|current| current == *id))
+        .map(|(_, discussion)| *discussion)
+        .collect();
+    for discussion in &fixed {
+        merge_request.resolve(discussion).await?;
+    }
+    Ok(Synced {
+        posted: new.len(),
+        resolved: fixed.len(),
+        unchanged: failures.len() - new.len(),
+        status: None,
+    })
+}
+
+/// Set the Gitea or Forgejo commit status, and review the pull request with
+/// the failures that have no open comment yet. Gitea's API can't resolve
+/// conversations, so comments on fixed findings are left to reviewers.
+async fn sync_gitea(
+    settings: &GiteaSettings,
+    failures: &[&CheckResult],
+    ids: &[String],
+    passed: bool,
+) -> Result<Synced> {
+    let repo = gitea::Repository::from_env(settings)?;
+    let mut synced = Synced::default();
+    if let Some(pull) = repo.pull {
+        let comments = repo.comments(pull).await?;
+        let open: Vec<&str> = comments
+            .iter()
+            .filter(|comment| comment.resolver.is_none())
+            .flat_map(|comment| finding_ids(&comment.body))
+            .collect();
+        let mut body = Vec::new();
+        let mut positioned = Vec::new();
+        for (result, id) in failures.iter().zip(ids) {
+            if open.contains(&id.as_str()) {
+                synced.unchanged += 1;
+                continue;
+            }
+            synced.posted += 1;
+            let note = note_body(result, id);
+            match (&result.file, result.line) {
+                (Some(_), Some(line)) => {
+                    let path = finding_path(result);
+                    let comment = json!({"path": path, "body": note, "new_position": line});
+                    positioned.push((comment, note));
+                }
+                _ => body.push(note),
+            }
+        }
+        if synced.posted > 0 {
+            repo.review(pull, &body.join("\n\n---\n\n"), &positioned)
+                .await?;
+        }
+    }
+    let errors = failures
+        .iter()
+        .filter(|r| r.severity == Severity::Error)
+        .count();
+    let warnings = failures.len() - errors;
+    let state = if passed { "success" } else { "failure" };
+    let description = format!("{errors} error(s), {warnings} warning(s)");
+    repo.status(state, &description).await?;
+    synced.status = Some(state);
+    Ok(synced)
+}
+
+/// Discussion ids of the unresolved discussions guardian started, by the
+/// fingerprint of their finding.
+fn open_findings(discussions: &[Discussion]) -> HashMap<&str, &str> {
+    discussions
+        .iter()
+        .filter_map(|discussion| {
+            let note = discussion.notes.first().filter(|note| !note.resolved)?;
+            let id = finding_ids(&note.body).next()?;
+            Some((id, discussion.id.as_str()))
+        })
+        .collect()
+}
+
+/// Fingerprints of the findings a comment is about, from its hidden tags.
+fn finding_ids(body: &str) -> impl Iterator<Item = &str> {
+    body.split(MARKER)
+        .skip(1)
+        .filter_map(|rest| Some(rest.split_once(" -->")?.0))
+}
+
+fn note_body(result: &CheckResult, id: &str) -> String {
+    let severity = format!("{:?}", result.severity).to_lowercase();
+    let mut body = format!(
+        "**guardian** `{}` ({severity})\n\n{}\n",
+        result.check_name, result.message
+    );
+    if let Some(fix) = &result.fix {
+        body.push_str(&format!("\nFix: {fix}\n"));
+    }
+    body.push_str(&format!("\n{MARKER}{id} -->"));
+    body
+}
+
+/// Where on the diff a finding's discussion goes, if it names a line.
+fn position(refs: &DiffRefs, result: &CheckResult) -> Option<Value> {
+    let (Some(_), Some(line)) = (&result.file, result.line) else {
+        return None;
+    };
+    let path = finding_path(result);
+    Some(json!({
+        "position_type": "text",
+        "base_sha": refs.base_commit_sha,
+        "start_sha": refs.start_commit_sha,
+        "head_sha": refs.head_commit_sha,
+        "old_path": path,
+        "new_path": path,
+        "new_line": line,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failure() -> CheckResult {
+        let mut result = CheckResult::fail("loc-limits", Severity::Error, "main.rs: 612 lines")
+            .with_file("./src/main.rs")
+            .with_fix("Split main.rs");
+        result.line = Some(1);
+        result
+    }
+
+    #[test]
+    fn test_open_findings() {
+        let discussions: Vec<Discussion> = serde_json::from_value(json!([
+            {"id": "d1", "notes": [{"body": note_body(&failure(), "abc"), "resolved": false}]},
+            {"id": "d2", "notes": [{"body": note_body(&failure(), "def"), "resolved": true}]},
+            {"id": "d3", "notes": [{"body": "Looks good to me"}]},
+        ]))
+        .unwrap();
+        let open = open_findings(&discussions);
+        assert_eq!(open, HashMap::from([("abc", "d1")]));
+
+        let review = [note_body(&failure(), "abc"), note_body(&failure(), "def")].join("\n---\n");
+        assert_eq!(finding_ids(&review).collect::<Vec<_>>(), ["abc", "def"]);
+    }
+
+    #[test]
+    fn test_note_and_position() {
+        let body = note_body(&failure(), "abc");
+        assert!(body.starts_with("**guardian** `loc-limits` (error)\n\nmain.rs: 612 lines\n"));
+        assert!(body.contains("Fix: Split main.rs"));
+        assert!(body.ends_with("<!-- guardian:abc -->"));
+
+        let refs = DiffRefs {
+            base_commit_sha: "base".to_string(),
+            start_commit_sha: "start".to_string(),
+            head_commit_sha: "head".to_string(),
+        };
+        let at = position(&refs, &failure()).unwrap();
+        assert_eq!(at["new_path"], "src/main.rs");
+        assert_eq!(at["new_line"], 1);
+        assert_eq!(at["head_sha"], "head");
+        let project_wide = CheckResult::fail("module-count", Severity::Warning, "Too many");
+        assert!(position(&refs, &project_wide).is_none());
+    }
+}
@@ -0,0 +1,62 @@
+//! The `[serve]` token, and the middleware that turns away requests without
+//! it. The job API and the dashboard's run endpoints share both.
+
+use super::failure;
+use crate::config::{SecretSource, ServeSettings, get_secret};
+use anyhow::{Result, anyhow};
+use axum::extract::{Request, State};
+use axum::http::{StatusCode, header};
+use axum::middleware::Next;
+use axum::response::Response;
+use ring::digest::{SHA256, digest};
+use std::sync::Arc;
+
+/// The token `settings` names, or `None` when it names none.
+pub(in crate::commands) fn api_token(settings: &ServeSettings) -> Result<Option<Arc<str>>> {
+    let token =
+        match settings.token_source()? {
+            None => return Ok(None),
+            Some(SecretSource::Env(var)) => std::env::var(var)
+                .ok()
+                .filter(|token| !token.is_empty())
+                .ok_or_else(|| anyhow!("${var} is not set, and the API needs its token"))?,
+            Some(SecretSource::Keyring(name)) => get_secret(name)?,
+        };
+    Ok(Some(token.into()))
+}
+
+/// Turn away requests without the token.
+pub(in crate::commands) async fn authorize(
+    State(token): State<Arc<str>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let bearer = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    if !bearer.is_some_and(|bearer| same_token(bearer, &token)) {
+        let message = "Missing or wrong bearer token".to_string();
+        return failure(StatusCode::UNAUTHORIZED, message);
+    }
+    next.run(request).await
+}
+
+/// Whether `given` is `token`. The digests are compared rather than the
+/// tokens, so how long the comparison takes says nothing about the token.
+fn same_token(given: &str, token: &str) -> bool {
+    digest(&SHA256, given.as_bytes()).as_ref() == digest(&SHA256, token.as_bytes()).as_ref()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_token() {
+        assert!(same_token("s3cret", "s3cret"));
+        assert!(!same_token("s3cre", "s3cret"));
+        assert!(!same_token("", "s3cret"));
+    }
+}
@@ -0,0 +1,146 @@
+//! The job API `serve` offers once `[serve]` names a token: run `check` or
+//! `evaluate` on a project on the daemon's machine, or on one uploaded as
+//! a tarball, then poll the job and fetch its report in any
+//! `check --format`.
+//!
+//! ```text
+//! POST /api/jobs                  {"run": "check", "path": "/src/app"}
+//! POST /api/jobs/upload?run=check the project as a .tar.gz body
+//! GET  /api/jobs/{id}             the job's status and exit code
+//! GET  /api/jobs/{id}/report      its report; ?format=junit and so on
+//! ```
+//!
+//! Every request needs `Authorization: Bearer <token>`. Jobs go through
+//! the queue the schedule uses (see [`super::queue`]).
+
+mod auth;
+mod upload;
+
+pub(super) use auth::{api_token, authorize};
+
+use super::cli::ReportFormat;
+use super::output::check_report;
+use super::queue::{JobQueue, JobRequest, JobStatus};
+use super::saved::SavedResults;
+use crate::config::{ScheduledCommand, ServeSettings};
+use axum::extract::{DefaultBodyLimit, Path, Query, State};
+use axum::http::{StatusCode, header};
+use axum::middleware::from_fn_with_state;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::sync::Arc;
+
+/// State the job endpoints share.
+#[derive(Clone)]
+struct Api {
+    queue: Arc<JobQueue>,
+}
+
+/// `?format=` for a job's report.
+#[derive(Debug, Deserialize)]
+struct ReportQuery {
+    format: Option<String>,
+}
+
+/// The job endpoints behind `token`, or no routes without one.
+pub(super) fn job_routes(
+    settings: &ServeSettings,
+    token: Option<Arc<str>>,
+    queue: Arc<JobQueue>,
+) -> Router {
+    let Some(token) = token else {
+        return Router::new();
+    };
+    let limit = DefaultBodyLimit::max(settings.max_upload_mb.saturating_mul(1 << 20));
+    Router::new()
+        .route("/api/jobs", post(submit))
+        .route("/api/jobs/upload", post(upload::upload).layer(limit))
+        .route("/api/jobs/{id}", get(status))
+        .route("/api/jobs/{id}/report", get(report))
+        .layer(from_fn_with_state(token, authorize))
+        .with_state(Api { queue })
+}
+
+/// `POST /api/jobs` - queue a job on a project on this machine.
+async fn submit(State(api): State<Api>, Json(request): Json<JobRequest>) -> Response {
+    let path = &request.path;
+    if !path.is_absolute() || !path.is_dir() {
+        let message = format!("{} isn't an absolute path to a directory", path.display());
+        return failure(StatusCode::BAD_REQUEST, message);
+    }
+    queued(&api.queue, request)
+}
+
+/// `GET /api/jobs/{id}` - where the job is, and how it went.
+async fn status(State(api): State<Api>, Path(id): Path<u64>) -> Response {
+    match api.queue.get(id) {
+        Some(job) => Json(job).into_response(),
+        None => failure(StatusCode::NOT_FOUND, format!("No job {id}")),
+    }
+}
+
+/// `GET /api/jobs/{id}/report` - the finished job's report, as JSON or in
+/// another `check --format`.
+async fn report(
+    State(api): State<Api>,
+    Path(id): Path<u64>,
+    Query(query): Query<ReportQuery>,
+) -> Response {
+    let Some(job) = api.queue.get(id) else {
+        return failure(StatusCode::NOT_FOUND, format!("No job {id}"));
+    };
+    if job.status != JobStatus::Finished {
+        return failure(StatusCode::CONFLICT, format!("Job {id} hasn't finished"));
+    }
+    let Some(report) = job.report else {
+        let message = format!("Job {id} has no report: {}", job.error.unwrap_or_default());
+        return failure(StatusCode::NOT_FOUND, message);
+    };
+    let format = query
+        .format
+        .as_deref()
+        .map(|f| ReportFormat::from_str(f, true));
+    let format = match format {
+        None => ReportFormat::Json,
+        Some(Ok(format)) if format != ReportFormat::Template => format,
+        _ => {
+            let message = "format must be a check --format other than template".to_string();
+            return failure(StatusCode::BAD_REQUEST, message);
+        }
+    };
+    if format == ReportFormat::Json {
+        return Json(report).into_response();
+    }
+    if job.request.run == ScheduledCommand::Evaluate {
+        let message = "evaluate jobs only have JSON reports".to_string();
+        return failure(StatusCode::BAD_REQUEST, message);
+    }
+    let rendered = serde_json::from_value::<SavedResults>(report)
+        .map_err(anyhow::Error::from)
+        .and_then(|saved| check_report(&saved.results, format));
+    let content_type = match format {
+        ReportFormat::Gitlab => "application/json",
+        ReportFormat::Junit | ReportFormat::Checkstyle => "application/xml",
+        ReportFormat::Html => "text/html; charset=utf-8",
+        ReportFormat::Csv => "text/csv; charset=utf-8",
+        _ => "text/plain; charset=utf-8",
+    };
+    match rendered {
+        Ok(text) => ([(header::CONTENT_TYPE, content_type)], text).into_response(),
+        Err(e) => failure(StatusCode::INTERNAL_SERVER_ERROR, format!("{e:#}")),
+    }
+}
+
+/// Queue `request`, answering with the new job.
+fn queued(queue: &JobQueue, request: JobRequest) -> Response {
+    let id = queue.submit(request);
+    (StatusCode::ACCEPTED, Json(queue.get(id))).into_response()
+}
+
+/// An error response, `{"error": message}`.
+pub(super) fn failure(status: StatusCode, message: String) -> Response {
+    (status, Json(serde_json::json!({"error": message}))).into_response()
+}
@@ -0,0 +1,121 @@
+//! `POST /api/jobs/upload`: a project sent as a gzipped tarball.
+//!
+//! The tarball is unpacked with `tar` into a scratch directory that the
+//! queue deletes once the job is done. A tarball holding one directory,
+//! as `tar czf app.tar.gz app` makes, is checked from inside it.
+
+use super::{Api, failure, queued};
+use crate::commands::queue::JobRequest;
+use crate::config::ScheduledCommand;
+use anyhow::{Context, Result, bail};
+use axum::body::Bytes;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::Response;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::io::AsyncWriteExt;
+
+/// Uploads so far, naming their scratch directories.
+static UPLOADS: AtomicU64 = AtomicU64::new(0);
+
+/// What to run on the uploaded project.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct UploadQuery {
+    run: ScheduledCommand,
+    only: Option<String>,
+    skip: Option<String>,
+}
+
+/// Unpack the uploaded project and queue a job on it.
+pub(super) async fn upload(
+    State(api): State<Api>,
+    Query(query): Query<UploadQuery>,
+    tarball: Bytes,
+) -> Response {
+    let upload = UPLOADS.fetch_add(1, Ordering::Relaxed);
+    let name = format!("guardian-upload-{}-{upload}", std::process::id());
+    let scratch = std::env::temp_dir().join(name);
+    let path = match unpack(&tarball, &scratch).await {
+        Ok(path) => path,
+        Err(e) => {
+            let _ = std::fs::remove_dir_all(&scratch);
+            return failure(StatusCode::BAD_REQUEST, format!("{e:#}"));
+        }
+    };
+    let request = JobRequest {
+        run: query.run,
+        path,
+        only: query.only,
+        skip: query.skip,
+        scratch: Some(scratch),
+    };
+    queued(&api.queue, request)
+}
+
+/// Unpack `tarball` into `dir`, returning the project's directory.
+async fn unpack(tarball: &[u8], dir: &Path) -> Result<PathBuf> {
+    std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let mut tar = tokio::process::Command::new("tar")
+        .args(["-xzf", "-", "-C"])
+        .arg(dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to run tar")?;
+    let mut stdin = tar.stdin.take().context("tar has no stdin")?;
+    let written = stdin.write_all(tarball).await;
+    drop(stdin);
+    let output = tar.wait_with_output().await.context("Failed to run tar")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("The body isn't a gzipped tarball: {}", stderr.trim());
+    }
+    written.context("Failed to send the tarball to tar")?;
+
+    let entries: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<_, _>>()?;
+    match &entries[..] {
+        [] => bail!("The tarball is empty"),
+        [only] if only.is_dir() => Ok(only.clone()),
+        _ => Ok(dir.to_path_buf()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_unpack_finds_the_project() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("app");
+        std::fs::create_dir_all(project.join("src")).unwrap();
+        std::fs::write(project.join("src/main.rs"), "fn main() {}\n").unwrap();
+        let tarball = dir.path().join("app.tar.gz");
+        let status = std::process::Command::new("tar")
+            .arg("-czf")
+            .arg(&tarball)
+            .arg("-C")
+            .arg(dir.path())
+            .arg("app")
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let into = dir.path().join("unpacked");
+        let path = unpack(&std::fs::read(&tarball).unwrap(), &into)
+            .await
+            .unwrap();
+        assert_eq!(path, into.join("app"));
+        assert!(path.join("src/main.rs").is_file());
+
+        let error = unpack(b"not a tarball", &dir.path().join("bad")).await;
+        assert!(error.unwrap_err().to_string().contains("gzipped tarball"));
+    }
+}
@@ -0,0 +1,87 @@
+//! Splitting unified diffs into hunks that apply on their own.
+
+/// Each hunk of `diff` as a diff of its own, under its file's headers.
+///
+/// Hunks are delimited by the line counts in their `@@` headers, so removed
+/// lines that happen to start with `--` aren't mistaken for file headers.
+pub(super) fn hunks(diff: &str) -> Vec<String> {
+    let mut hunks = Vec::new();
+    let mut header = String::new();
+    let mut lines = diff.lines().peekable();
+    while let Some(line) = lines.next() {
+        let Some((mut old, mut new)) = counts(line) else {
+            if line.starts_with("diff ") || (line.starts_with("--- ") && header.contains("\n+++ "))
+            {
+                header.clear();
+            }
+            header.push_str(line);
+            header.push('\n');
+            continue;
+        };
+        let mut hunk = format!("{header}{line}\n");
+        while old + new > 0 {
+            let Some(line) = lines.next() else {
+                break;
+            };
+            match line.chars().next() {
+                Some('-') => old = old.saturating_sub(1),
+                Some('+') => new = new.saturating_sub(1),
+                Some('\\') => {}
+                _ => {
+                    old = old.saturating_sub(1);
+                    new = new.saturating_sub(1);
+                }
+            }
+            hunk.push_str(line);
+            hunk.push('\n');
+        }
+        // "\ No newline at end of file" after the last line.
+        if let Some(marker) = lines.next_if(|l| l.starts_with('\\')) {
+            hunk.push_str(marker);
+            hunk.push('\n');
+        }
+        hunks.push(hunk);
+    }
+    hunks
+}
+
+/// The old and new line counts of a `@@ -a,b +c,d @@` hunk header.
+fn counts(line: &str) -> Option<(usize, usize)> {
+    let ranges = line.strip_prefix("@@ -")?.split(" @@").next()?;
+    let (old, new) = ranges.split_once(" +")?;
+    let count = |range: &str| match range.split_once(',') {
+        Some((_, count)) => count.parse().ok(),
+        None => range.parse::<usize>().ok().map(|_| 1),
+    };
+    Some((count(old)?, count(new)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_hunks() {
+        let diff = "--- a/q.sql\n+++ b/q.sql\n@@ -1,2 +1,2 @@\n-- old\n+-- new\n select 1;\n\
+                    @@ -10 +10,2 @@\n-x\n+y\n+z\n";
+        let hunks = hunks(diff);
+        assert_eq!(
+            hunks,
+            [
+                "--- a/q.sql\n+++ b/q.sql\n@@ -1,2 +1,2 @@\n-- old\n+-- new\n select 1;\n",
+                "--- a/q.sql\n+++ b/q.sql\n@@ -10 +10,2 @@\n-x\n+y\n+z\n",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_files() {
+        let diff = "--- a/a.rs\n+++ b/a.rs\n@@ -1,1 +1,1 @@\n-a\n+b\n\\ No newline at end of file\n\
+                    --- a/b.rs\n+++ b/b.rs\n@@ -3,1 +3,0 @@\n-c\n";
+        let hunks = hunks(diff);
+        assert!(hunks[0].ends_with("+b\n\\ No newline at end of file\n"));
+        assert_eq!(hunks[1], "--- a/b.rs\n+++ b/b.rs\n@@ -3,1 +3,0 @@\n-c\n");
+        assert_eq!(counts("@@ -3 +3,0 @@ fn main() {"), Some((1, 0)));
+        assert_eq!(counts(" context"), None);
+    }
+}
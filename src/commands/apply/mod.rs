@@ -0,0 +1,234 @@
+//! Apply command: walk through fix diffs and apply the ones accepted.
+//!
+//! The fixes come from a saved JSON report (`check --format json`, or
+//! `evaluate --json` with `--suggest-patches`) or, without one, from
+//! running the checks. Each hunk is shown and accepted, skipped, or edited
+//! first; accepted hunks are applied with `git apply`. Then the checks that
+//! proposed them run again to confirm the violations are gone.
+
+mod hunks;
+
+use super::exit::{Exit, VIOLATIONS};
+use super::output::report;
+use super::patches::git_apply;
+use crate::checks::{CheckResult, configured_thresholds, run_selected_checks, select_checks};
+use crate::config::GuardianConfig;
+use anyhow::{Context, Result, bail};
+use dialoguer::theme::ColorfulTheme;
+use dialoguer::{Editor, Select};
+use serde::Deserialize;
+use std::io::IsTerminal;
+use std::path::Path;
+
+/// Options for the apply command.
+pub struct ApplyOptions<'a> {
+    /// JSON report holding the fixes; the checks are run when unset.
+    pub patches: Option<&'a Path>,
+    /// Project the checks run on.
+    pub path: Option<&'a Path>,
+}
+
+/// A diff fixing one check result.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+struct Fix {
+    check: String,
+    #[serde(default)]
+    file: Option<String>,
+    #[serde(default)]
+    message: String,
+    diff: String,
+}
+
+/// Offer each hunk of each fix, apply the accepted ones, and re-run the
+/// checks they came from.
+pub fn apply(config: &GuardianConfig, opts: ApplyOptions<'_>) -> Result<()> {
+    if !std::io::stdin().is_terminal() || !std::io::stderr().is_terminal() {
+        bail!("apply asks about each fix, so it needs a terminal");
+    }
+    let project_dir = opts.path.unwrap_or(Path::new("."));
+    let fixes = match opts.patches {
+        Some(path) => load_fixes(path)?,
+        None => {
+            eprintln!("Running checks on {}...", project_dir.display());
+            run_checks(config, project_dir, None)?
+                .into_iter()
+                .filter_map(|r| {
+                    Some(Fix {
+                        diff: r.diff?,
+                        check: r.check_name,
+                        file: r.file,
+                        message: r.message,
+                    })
+                })
+                .collect()
+        }
+    };
+    let hunks: Vec<(&Fix, String)> = fixes
+        .iter()
+        .flat_map(|fix| hunks::hunks(&fix.diff).into_iter().map(move |h| (fix, h)))
+        .collect();
+    if hunks.is_empty() {
+        report!("No fixes to apply.");
+        return Ok(());
+    }
+
+    let mut applied: Vec<&Fix> = Vec::new();
+    for (i, (fix, hunk)) in hunks.iter().enumerate() {
+        let file = fix.file.as_deref().unwrap_or("(project)");
+        eprintln!(
+            "\n[{}/{}] {} in {file}: {}\n\n{}",
+            i + 1,
+            hunks.len(),
+            fix.check,
+            fix.message,
+            hunk.trim_end()
+        );
+        let Some(hunk) = review(hunk)? else {
+            report!("Stopped; {} hunk(s) left unapplied.", hunks.len() - i);
+            break;
+        };
+        if hunk.is_empty() {
+            continue;
+        }
+        match git_apply(Path::new("."), &hunk, false) {
+            Ok(()) if applied.contains(fix) => {}
+            Ok(()) => applied.push(fix),
+            Err(e) => eprintln!("Couldn't apply it: {e:#}"),
+        }
+    }
+    if applied.is_empty() {
+        report!("Nothing applied.");
+        return Ok(());
+    }
+    confirm(config, project_dir, &applied)
+}
+
+/// The fixes with a diff anywhere in the JSON report at `path`.
+fn load_fixes(path: &Path) -> Result<Vec<Fix>> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let report: serde_json::Value = serde_json::from_str(&text)
+        .with_context(|| format!("{} is not a JSON report", path.display()))?;
+    let mut fixes = Vec::new();
+    collect(&report, &mut fixes);
+    Ok(fixes)
+}
+
+/// Gather fixes from `value` and everything in it, skipping duplicates.
+fn collect(value: &serde_json::Value, fixes: &mut Vec<Fix>) {
+    match value {
+        serde_json::Value::Object(map) if map.get("diff").is_some_and(|d| d.is_string()) => {
+            if let Ok(fix) = serde_json::from_value::<Fix>(value.clone()) {
+                if !fixes.contains(&fix) {
+                    fixes.push(fix);
+                }
+            }
+        }
+        serde_json::Value::Object(map) => map.values().for_each(|v| collect(v, fixes)),
+        serde_json::Value::Array(items) => items.iter().for_each(|v| collect(v, fixes)),
+        _ => {}
+    }
+}
+
+/// Ask what to do with `hunk`: the hunk to apply, which may have been
+/// edited, an empty one to skip it, or `None` to stop.
+fn review(hunk: &str) -> Result<Option<String>> {
+    let theme = ColorfulTheme::default();
+    let mut hunk = hunk.to_string();
+    loop {
+        let choice = Select::with_theme(&theme)
+            .with_prompt("Apply this hunk?")
+            .items(&["Accept", "Skip", "Edit", "Quit"])
+            .default(0)
+            .interact()?;
+        match choice {
+            0 => return Ok(Some(hunk)),
+            1 => return Ok(Some(String::new())),
+            2 => {
+                let Some(edited) = Editor::new().extension(".diff").edit(&hunk)? else {
+                    continue;
+                };
+                match git_apply(Path::new("."), &edited, true) {
+                    Ok(()) => {
+                        eprintln!("\n{}", edited.trim_end());
+                        hunk = edited;
+                    }
+                    Err(e) => eprintln!("The edited hunk doesn't apply: {e:#}"),
+                }
+            }
+            _ => return Ok(None),
+        }
+    }
+}
+
+/// Run the checks `only` names, or all configured ones, on `project_dir`.
+fn run_checks(
+    config: &GuardianConfig,
+    project_dir: &Path,
+    only: Option<&[&str]>,
+) -> Result<Vec<CheckResult>> {
+    let mut selected = select_checks(None, None, config.check.only.as_deref(), &config.checks)?;
+    if let Some(only) = only {
+        selected.retain(|name| only.contains(name));
+    }
+    let thresholds = configured_thresholds(&config.check);
+    Ok(run_selected_checks(project_dir, &thresholds, &selected))
+}
+
+/// Re-run the checks behind the `applied` fixes and report which are fixed.
+fn confirm(config: &GuardianConfig, project_dir: &Path, applied: &[&Fix]) -> Result<()> {
+    let mut checks: Vec<&str> = Vec::new();
+    for fix in applied {
+        if !checks.contains(&fix.check.as_str()) {
+            checks.push(&fix.check);
+        }
+    }
+    eprintln!("\nRe-running {}...", checks.join(", "));
+    let results = run_checks(config, project_dir, Some(&checks))?;
+    let normalize = |file: Option<&str>| file.map(|f| f.trim_start_matches("./").to_string());
+    let mut unfixed = 0;
+    for fix in applied {
+        let still_failing = results.iter().any(|r| {
+            !r.passed
+                && r.check_name == fix.check
+                && normalize(r.file.as_deref()) == normalize(fix.file.as_deref())
+        });
+        let status = if still_failing {
+            unfixed += 1;
+            "[STILL FAILING]"
+        } else {
+            "[FIXED]"
+        };
+        let file = fix.file.as_deref().unwrap_or("(project)");
+        report!("{status} {} in {file}: {}", fix.check, fix.message);
+    }
+    if unfixed > 0 {
+        return Err(Exit(VIOLATIONS).into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_fixes_from_reports() {
+        let diff = "--- a/Cargo.toml\n+++ b/Cargo.toml\n@@ -1 +1 @@\n-a\n+b\n";
+        let check = serde_json::json!({"results": [
+            {"check": "rust-edition", "message": "Edition 2021", "file": "Cargo.toml", "diff": diff},
+            {"check": "loc-limits", "message": "612 lines", "file": "src/main.rs", "diff": null},
+        ]});
+        let evaluate = serde_json::json!({
+            "violations": [{"check": "rust-edition", "message": "Edition 2021", "file": "Cargo.toml", "diff": diff}],
+            "suggested_patches": [{"check": "loc-limits", "message": "612 lines", "file": "src/main.rs", "line": 1, "diff": diff}],
+        });
+        let mut fixes = Vec::new();
+        collect(&check, &mut fixes);
+        assert_eq!(fixes.len(), 1);
+        assert_eq!(fixes[0].check, "rust-edition");
+        collect(&evaluate, &mut fixes);
+        let checks: Vec<_> = fixes.iter().map(|f| f.check.as_str()).collect();
+        assert_eq!(checks, ["rust-edition", "loc-limits"]);
+    }
+}
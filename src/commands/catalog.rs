@@ -0,0 +1,11 @@
+//! Rules command: the catalog of checks, for people and for tools.
+
+use super::output::catalog_report;
+use crate::checks::catalog::catalog;
+use anyhow::Result;
+
+/// List every check with what it looks for, its severities, and the
+/// settings it reads with their defaults.
+pub fn list_rules(json_output: bool) -> Result<()> {
+    catalog_report(&catalog(), json_output)
+}
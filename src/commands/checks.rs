@@ -1,25 +1,90 @@
 //! Check-related commands: run_checks.
+//!
+//! The opt-in `llm-readability` check runs after the others (see
+//! [`super::readability`]). With `--explain-llm`, the model also explains each kind of violation
+//! (see [`super::explain`]). A run stopped by Ctrl-C reports the results it
+//! has, marked partial (see [`super::interrupt`]). `check --recursive`
+//! runs the same steps per project (see [`super::projects`]).
 
+use super::cli::{GroupBy, ReportFormat};
+use super::exit::{Exit, FailPolicy, INTERRUPTED};
+use super::explain;
+use super::history;
+use super::interrupt;
+use super::notify;
 use super::output;
-use crate::checks::{self, CheckConfig, CheckResult};
-use anyhow::Result;
+use super::readability;
+use super::saved;
+use crate::checks::interrupt::interrupted;
+use crate::checks::walk::Scan;
+use crate::checks::{CheckConfig, CheckResult, blame, run_selected_checks, select_checks};
+use crate::config::{
+    ArchitectureRules, CheckToggle, ClippySettings, GuardianConfig, NotifyTarget, StorageSettings,
+};
+use anyhow::{Result, bail};
+use std::collections::BTreeMap;
 use std::path::Path;
 
 /// Options for the check command.
 pub struct CheckOptions<'a> {
     pub path: Option<&'a Path>,
+    /// Only run these checks, comma-separated (`--only`).
     pub only: Option<&'a str>,
+    /// Don't run these checks, comma-separated (`--skip`).
+    pub skip: Option<&'a str>,
+    /// `check.only` from the config, used when `only` is unset.
+    pub config_only: Option<&'a [String]>,
+    /// `[checks.NAME]` tables from the config.
+    pub toggles: &'a BTreeMap<String, CheckToggle>,
     pub max_loc: usize,
     pub warn_loc: usize,
     pub max_functions: usize,
     pub max_modules: usize,
     pub edition: &'a str,
-    pub json_output: bool,
+    /// `[check.architecture]` from the config.
+    pub architecture: &'a ArchitectureRules,
+    /// `[check.clippy]` from the config.
+    pub clippy: &'a ClippySettings,
+    /// How files are found and read, from `[check]`.
+    pub scan: Scan,
+    /// Hosts, routing, and `[check.readability]` for the `llm-readability`
+    /// check.
+    pub llm: &'a GuardianConfig,
+    pub format: ReportFormat,
+    pub group_by: GroupBy,
+    /// Template file for [`ReportFormat::Template`].
+    pub template: Option<&'a Path>,
+    pub policy: FailPolicy,
+    /// Webhooks told about new errors.
+    pub notify: &'a [NotifyTarget],
+    /// Where the run is recorded.
+    pub storage: &'a StorageSettings,
+    /// Attribute failures with git blame.
+    pub blame: bool,
+    /// Have the model explain each kind of violation, with this config's
+    /// hosts and routing (`--explain-llm`).
+    pub explain_llm: Option<&'a GuardianConfig>,
+    /// Also save the results here, for `evaluate --from`.
+    pub save: Option<&'a Path>,
 }
 
 /// Run checklist validation on a project.
-pub fn run_checks(opts: CheckOptions<'_>) -> Result<()> {
+pub async fn run_checks(opts: CheckOptions<'_>) -> Result<()> {
     let project_dir = opts.path.unwrap_or(Path::new("."));
+    let mut results = collect(&opts, project_dir).await?;
+    report(&opts, project_dir, &mut results).await?;
+    finish(&opts, project_dir, &results).await
+}
+
+/// Run the selected checks on `project_dir`.
+pub(super) async fn collect(
+    opts: &CheckOptions<'_>,
+    project_dir: &Path,
+) -> Result<Vec<CheckResult>> {
+    if opts.explain_llm.is_some() && !matches!(opts.format, ReportFormat::Text | ReportFormat::Json)
+    {
+        bail!("--explain-llm works with text and json output");
+    }
 
     let config = CheckConfig {
         max_file_loc: opts.max_loc,
@@ -27,62 +92,62 @@ pub fn run_checks(opts: CheckOptions<'_>) -> Result<()> {
         max_functions_per_module: opts.max_functions,
         max_modules_per_crate: opts.max_modules,
         required_edition: opts.edition.to_string(),
+        architecture: opts.architecture.clone(),
+        clippy: opts.clippy.clone(),
+        scan: opts.scan.clone(),
     };
 
-    let results = run_selected_checks(project_dir, &config, opts.only);
-    output::check_results(&results, opts.json_output)
+    let selected = select_checks(opts.only, opts.skip, opts.config_only, opts.toggles)?;
+    let mut results = run_selected_checks(project_dir, &config, &selected);
+    results.extend(readability::check(opts.llm, project_dir, &selected, None).await);
+    if opts.blame {
+        blame::attribute(&mut results);
+    }
+    Ok(results)
 }
 
-pub(crate) fn run_selected_checks(
+/// Write the report, saving the results and having the model explain them
+/// as asked. An interrupted run is reported as partial, and stops here.
+pub(super) async fn report(
+    opts: &CheckOptions<'_>,
     project_dir: &Path,
-    config: &CheckConfig,
-    only: Option<&str>,
-) -> Vec<CheckResult> {
-    let filter: Option<Vec<&str>> = only.map(|s| s.split(',').map(str::trim).collect());
-    let should_run = |name: &str| filter.as_ref().map_or(true, |f| f.contains(&name));
-
-    let mut results = Vec::new();
-
-    if should_run("rust-edition") {
-        results.extend(checks::rust_edition::check(
-            project_dir,
-            &config.required_edition,
-        ));
+    results: &mut Vec<CheckResult>,
+) -> Result<()> {
+    if interrupted() {
+        interrupt::mark_partial(results);
+        let (format, group_by) = (opts.format, opts.group_by);
+        output::check_results(results, &[], format, group_by, opts.template)?;
+        return Err(Exit(INTERRUPTED).into());
     }
-
-    if should_run("loc-limits") {
-        results.extend(checks::loc_limits::check(
-            project_dir,
-            config.max_file_loc,
-            config.warn_file_loc,
-        ));
-    }
-
-    if should_run("function-count") {
-        results.extend(checks::function_count::check(
-            project_dir,
-            config.max_functions_per_module,
-        ));
-    }
-
-    if should_run("module-count") {
-        results.extend(checks::module_count::check(
-            project_dir,
-            config.max_modules_per_crate,
-        ));
-    }
-
-    if should_run("test-quality") {
-        results.extend(checks::test_quality::check(project_dir));
-    }
-
-    if should_run("clippy-disables") {
-        results.extend(checks::clippy_disables::check(project_dir));
-    }
-
-    if should_run("cache-busting") {
-        results.extend(checks::cache_busting::check(project_dir));
+    if let Some(path) = opts.save {
+        saved::save(path, project_dir, results)?;
     }
+    let explanations = match opts.explain_llm {
+        Some(config) => explain::explain(config, results).await.unwrap_or_else(|e| {
+            tracing::warn!("Couldn't explain the violations: {e:#}");
+            Vec::new()
+        }),
+        None => Vec::new(),
+    };
+    output::check_results(
+        results,
+        &explanations,
+        opts.format,
+        opts.group_by,
+        opts.template,
+    )
+}
 
-    results
+/// Tell the webhooks about new errors, record the run, and judge it by the
+/// failure policy.
+pub(super) async fn finish(
+    opts: &CheckOptions<'_>,
+    project_dir: &Path,
+    results: &[CheckResult],
+) -> Result<()> {
+    notify::new_errors(opts.notify, project_dir, results).await;
+    let verdict = opts.policy.enforce(results);
+    let outcome = history::outcome(&verdict);
+    history::record_run(opts.storage, project_dir, "check", results, outcome, None);
+    verdict
 }
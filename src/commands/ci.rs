@@ -0,0 +1,132 @@
+//! CI environment detection, for defaults that suit pipelines.
+//!
+//! Under a recognized CI service, `check` defaults to the report format the
+//! service understands and fails on errors whatever the format, `--color
+//! auto` never colors, and model pulls report progress line by line instead
+//! of redrawing one. Explicit flags still win. `GUARDIAN_CI=off` turns
+//! detection off, and `GUARDIAN_CI=<provider>` forces a provider.
+
+use super::cli::ReportFormat;
+use std::sync::OnceLock;
+
+/// A CI service guardian recognizes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CiProvider {
+    GithubActions,
+    GitlabCi,
+    Jenkins,
+    Buildkite,
+}
+
+/// Detect the provider from environment variables read through `var`.
+fn from_env(var: impl Fn(&str) -> Option<String>) -> Option<CiProvider> {
+    if let Some(forced) = var("GUARDIAN_CI") {
+        return match forced.to_lowercase().as_str() {
+            "github" => Some(CiProvider::GithubActions),
+            "gitlab" => Some(CiProvider::GitlabCi),
+            "jenkins" => Some(CiProvider::Jenkins),
+            "buildkite" => Some(CiProvider::Buildkite),
+            other => {
+                if !matches!(other, "off" | "false" | "0") {
+                    tracing::warn!("Ignoring unknown GUARDIAN_CI={forced}");
+                }
+                None
+            }
+        };
+    }
+    let set = |name: &str| var(name).is_some_and(|v| v == "true");
+    if set("GITHUB_ACTIONS") {
+        Some(CiProvider::GithubActions)
+    } else if set("GITLAB_CI") {
+        Some(CiProvider::GitlabCi)
+    } else if set("BUILDKITE") {
+        Some(CiProvider::Buildkite)
+    } else if var("JENKINS_URL").is_some() {
+        Some(CiProvider::Jenkins)
+    } else {
+        None
+    }
+}
+
+impl CiProvider {
+    /// The CI service guardian is running under, if any.
+    pub fn detect() -> Option<Self> {
+        static DETECTED: OnceLock<Option<CiProvider>> = OnceLock::new();
+        *DETECTED.get_or_init(|| {
+            let provider = from_env(|var| std::env::var(var).ok().filter(|v| !v.is_empty()));
+            if let Some(provider) = provider {
+                tracing::debug!(ci = provider.name(), "Detected CI; using CI defaults");
+            }
+            provider
+        })
+    }
+
+    /// Name of the provider, as `GUARDIAN_CI` takes it.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::GithubActions => "github",
+            Self::GitlabCi => "gitlab",
+            Self::Jenkins => "jenkins",
+            Self::Buildkite => "buildkite",
+        }
+    }
+
+    /// The `check` report format the service shows best.
+    pub fn report_format(self) -> ReportFormat {
+        match self {
+            Self::GithubActions => ReportFormat::Github,
+            Self::GitlabCi => ReportFormat::Gitlab,
+            Self::Jenkins => ReportFormat::Junit,
+            Self::Buildkite => ReportFormat::Text,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect_in(vars: &[(&str, &str)]) -> Option<CiProvider> {
+        from_env(|name| {
+            vars.iter()
+                .find(|(var, _)| *var == name)
+                .map(|(_, value)| value.to_string())
+        })
+    }
+
+    #[test]
+    fn test_detect_provider() {
+        assert_eq!(detect_in(&[]), None);
+        assert_eq!(
+            detect_in(&[("GITHUB_ACTIONS", "true"), ("CI", "true")]),
+            Some(CiProvider::GithubActions)
+        );
+        assert_eq!(
+            detect_in(&[("GITLAB_CI", "true")]),
+            Some(CiProvider::GitlabCi)
+        );
+        assert_eq!(
+            detect_in(&[("JENKINS_URL", "https://ci.example.com/")]),
+            Some(CiProvider::Jenkins)
+        );
+        assert_eq!(
+            detect_in(&[("BUILDKITE", "true")]),
+            Some(CiProvider::Buildkite)
+        );
+        assert_eq!(detect_in(&[("GITHUB_ACTIONS", "false")]), None);
+    }
+
+    #[test]
+    fn test_guardian_ci_overrides_detection() {
+        assert_eq!(
+            detect_in(&[("GITHUB_ACTIONS", "true"), ("GUARDIAN_CI", "off")]),
+            None
+        );
+        assert_eq!(
+            detect_in(&[("GUARDIAN_CI", "Jenkins")]),
+            Some(CiProvider::Jenkins)
+        );
+        assert_eq!(detect_in(&[("GUARDIAN_CI", "travis")]), None);
+        assert_eq!(CiProvider::GitlabCi.report_format(), ReportFormat::Gitlab);
+    }
+}
@@ -0,0 +1,54 @@
+//! Arguments for the annotate command.
+
+use clap::{ArgGroup, Args};
+use std::path::PathBuf;
+
+/// Options for `annotate`.
+#[derive(Debug, Args)]
+#[command(group(ArgGroup::new("forge").required(true).args(["gitlab", "gitea"])))]
+pub struct AnnotateArgs {
+    /// Path to the project directory (default: current directory)
+    #[arg(value_name = "PATH")]
+    pub path: Option<PathBuf>,
+
+    /// Post to the GitLab merge request of the current CI pipeline
+    #[arg(long)]
+    pub gitlab: bool,
+
+    /// Post to the Gitea or Forgejo pull request and commit of the current CI job
+    #[arg(long)]
+    pub gitea: bool,
+
+    /// Only run specific check(s), comma-separated
+    #[arg(long, value_name = "CHECKS")]
+    pub only: Option<String>,
+
+    /// Don't run these check(s), comma-separated
+    #[arg(long, value_name = "CHECKS")]
+    pub skip: Option<String>,
+
+    /// Pass or fail by a policy expression (default: from config, or any error fails)
+    #[arg(long, value_name = "EXPR")]
+    pub policy: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::cli::{Cli, Commands};
+    use clap::Parser;
+    use std::path::Path;
+
+    #[test]
+    fn test_annotate_args() {
+        let cli = Cli::try_parse_from(["guardian-cli", "annotate", "--gitlab", "app"]).unwrap();
+        assert_eq!(cli.command.project_dir(), Some(Path::new("app")));
+        let Commands::Annotate(args) = cli.command else {
+            panic!("Expected Annotate command");
+        };
+        assert!(args.gitlab && !args.gitea);
+        assert!(Cli::try_parse_from(["guardian-cli", "annotate"]).is_err());
+        assert!(Cli::try_parse_from(["guardian-cli", "annotate", "--gitea"]).is_ok());
+        let both = ["guardian-cli", "annotate", "--gitlab", "--gitea"];
+        assert!(Cli::try_parse_from(both).is_err());
+    }
+}
@@ -0,0 +1,37 @@
+//! Arguments for the apply command.
+
+use clap::Args;
+use std::path::PathBuf;
+
+/// Options for `apply`.
+#[derive(Debug, Args)]
+pub struct ApplyArgs {
+    /// JSON report with fixes, from `check --format json` or `evaluate --json` (default: run the checks)
+    #[arg(value_name = "PATCHES")]
+    pub patches: Option<PathBuf>,
+
+    /// Project to run the checks on (default: current directory)
+    #[arg(long, value_name = "PATH")]
+    pub path: Option<PathBuf>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::cli::{Cli, Commands};
+    use clap::Parser;
+    use std::path::Path;
+
+    #[test]
+    fn test_apply_args() {
+        let cli =
+            Cli::try_parse_from(["guardian-cli", "apply", "fixes.json", "--path", "app"]).unwrap();
+        assert_eq!(cli.command.project_dir(), Some(Path::new("app")));
+        let Commands::Apply(args) = cli.command else {
+            panic!("Expected Apply command");
+        };
+        assert_eq!(args.patches.as_deref(), Some(Path::new("fixes.json")));
+
+        let cli = Cli::try_parse_from(["guardian-cli", "apply"]).unwrap();
+        assert_eq!(cli.command.project_dir(), None);
+    }
+}
@@ -0,0 +1,62 @@
+//! Arguments for the ask command.
+
+use clap::Args;
+use std::path::PathBuf;
+
+/// Options for `ask`.
+#[derive(Debug, Args)]
+pub struct AskArgs {
+    /// The prompt to send
+    pub prompt: String,
+
+    /// Model to use (default: from config or first available)
+    #[arg(long, short)]
+    pub model: Option<String>,
+
+    /// Specific host to use
+    #[arg(long)]
+    pub host: Option<String>,
+
+    /// Use the model and host routed to this task in [routing]
+    #[arg(long)]
+    pub task: Option<String>,
+
+    /// Ask the model to respond in a structured format
+    #[arg(long, value_parser = ["json"])]
+    pub format: Option<String>,
+
+    /// JSON schema file the response must follow (implies --format json)
+    #[arg(long, value_name = "FILE")]
+    pub schema: Option<PathBuf>,
+
+    /// Send the prompt to two hosts and use whichever answers first
+    #[arg(long)]
+    pub race: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::cli::{Cli, Commands};
+    use clap::Parser;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_ask_args_with_schema() {
+        let cli = Cli::try_parse_from([
+            "guardian-cli",
+            "ask",
+            "Review this",
+            "--format",
+            "json",
+            "--schema",
+            "verdict.json",
+        ])
+        .unwrap();
+        let Commands::Ask(args) = cli.command else {
+            panic!("Expected Ask command");
+        };
+        assert_eq!(args.format.as_deref(), Some("json"));
+        assert_eq!(args.schema, Some(PathBuf::from("verdict.json")));
+        assert!(Cli::try_parse_from(["guardian-cli", "ask", "hi", "--format", "xml"]).is_err());
+    }
+}
@@ -0,0 +1,223 @@
+//! Arguments for the check command.
+
+use super::{FailOn, GroupBy, ReportFormat};
+use crate::checks::walk::Scan;
+use crate::commands::{CheckOptions, FailPolicy, Policy};
+use crate::config::GuardianConfig;
+use anyhow::Result;
+use clap::Args;
+use std::path::PathBuf;
+
+/// Options for `check`.
+#[derive(Debug, Args)]
+pub struct CheckArgs {
+    /// Path to the project directory (default: current directory)
+    #[arg(value_name = "PATH")]
+    pub path: Option<PathBuf>,
+
+    /// Only run specific check(s), comma-separated
+    #[arg(long, value_name = "CHECKS")]
+    pub only: Option<String>,
+
+    /// Don't run these check(s), comma-separated
+    #[arg(long, value_name = "CHECKS")]
+    pub skip: Option<String>,
+
+    /// Maximum lines of code per file (default: from config, else 800 for
+    /// test-support crates and 500 for others)
+    #[arg(long, value_name = "N")]
+    pub max_loc: Option<usize>,
+
+    /// Warning threshold for file LOC (default: from config, else 600 for
+    /// test-support crates and 350 for others)
+    #[arg(long, value_name = "N")]
+    pub warn_loc: Option<usize>,
+
+    /// Maximum functions per module (default: from config, else 10 for bin and
+    /// proc-macro crates and 7 for others)
+    #[arg(long, value_name = "N")]
+    pub max_functions: Option<usize>,
+
+    /// Maximum modules per crate (default: from config or 4)
+    #[arg(long, value_name = "N")]
+    pub max_modules: Option<usize>,
+
+    /// Required Rust edition (default: from config or 2024)
+    #[arg(long)]
+    pub edition: Option<String>,
+
+    /// Report format (default: text, or json with --json)
+    #[arg(long, value_enum)]
+    pub format: Option<ReportFormat>,
+
+    /// Template file for --format template (Jinja syntax)
+    #[arg(long, value_name = "FILE", required_if_eq("format", "template"))]
+    pub template: Option<PathBuf>,
+
+    /// Group text and JSON output by check or by file
+    #[arg(long, value_enum, default_value_t = GroupBy::Check)]
+    pub group_by: GroupBy,
+
+    /// Which failures set a non-zero exit code (default: error, or never with json)
+    #[arg(long, value_enum)]
+    pub fail_on: Option<FailOn>,
+
+    /// Fail when more than N warnings are found (default: from config)
+    #[arg(long, value_name = "N")]
+    pub max_warnings: Option<usize>,
+
+    /// Pass or fail by a policy expression, e.g. "errors == 0 && score >= 85"
+    #[arg(long, value_name = "EXPR", conflicts_with_all = ["fail_on", "max_warnings"])]
+    pub policy: Option<String>,
+
+    /// Attribute each failure to the author and commit of its line (git blame)
+    #[arg(long)]
+    pub blame: bool,
+
+    /// Have the local model explain why each kind of violation matters and how to fix it
+    #[arg(long)]
+    pub explain_llm: bool,
+
+    /// Also save the results to FILE as JSON, for `evaluate --from`
+    #[arg(long, value_name = "FILE")]
+    pub save: Option<PathBuf>,
+
+    /// Check each project under PATH with its own config, in one report
+    #[arg(long)]
+    pub recursive: bool,
+}
+
+impl CheckArgs {
+    /// The options for checking with `config`, these arguments overriding
+    /// its settings.
+    pub fn options<'a>(
+        &'a self,
+        config: &'a GuardianConfig,
+        format: ReportFormat,
+        fail_on: FailOn,
+    ) -> Result<CheckOptions<'a>> {
+        // The configured policy gives way to explicit fail flags.
+        let explicit = self.fail_on.is_some() || self.max_warnings.is_some();
+        let policy = match self.policy.as_deref() {
+            Some(policy) => Some(policy),
+            None if explicit => None,
+            None => config.check.policy.as_deref(),
+        };
+        let check = &config.check;
+        Ok(CheckOptions {
+            path: self.path.as_deref(),
+            only: self.only.as_deref(),
+            skip: self.skip.as_deref(),
+            config_only: check.only.as_deref(),
+            toggles: &config.checks,
+            max_loc: self.max_loc.unwrap_or(check.max_loc),
+            warn_loc: self.warn_loc.unwrap_or(check.warn_loc),
+            max_functions: self.max_functions.unwrap_or(check.max_functions),
+            max_modules: self.max_modules.unwrap_or(check.max_modules),
+            edition: self.edition.as_deref().unwrap_or(&check.edition),
+            architecture: &check.architecture,
+            clippy: &check.clippy,
+            scan: Scan::from(check),
+            llm: config,
+            format,
+            group_by: self.group_by,
+            template: self.template.as_deref(),
+            policy: FailPolicy {
+                fail_on,
+                max_warnings: self.max_warnings.or(check.max_warnings),
+                expression: policy.map(Policy::parse).transpose()?,
+            },
+            notify: &config.notify,
+            storage: &config.storage,
+            blame: self.blame,
+            explain_llm: self.explain_llm.then_some(config),
+            save: self.save.as_deref(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::cli::{Cli, Commands, FailOn, GroupBy, ReportFormat};
+    use clap::Parser;
+
+    fn parse(args: &[&str]) -> super::CheckArgs {
+        let cli = Cli::try_parse_from([&["guardian-cli", "check"], args].concat()).unwrap();
+        match cli.command {
+            Commands::Check(args) => args,
+            _ => panic!("Expected Check command"),
+        }
+    }
+
+    #[test]
+    fn test_check_format() {
+        assert_eq!(
+            parse(&["--format", "junit"]).format,
+            Some(ReportFormat::Junit)
+        );
+        assert!(Cli::try_parse_from(["guardian-cli", "check", "--format", "yaml"]).is_err());
+        assert_eq!(parse(&[]).group_by, GroupBy::Check);
+        assert_eq!(parse(&["--group-by", "file"]).group_by, GroupBy::File);
+    }
+
+    #[test]
+    fn test_check_template_requires_file() {
+        let args = parse(&["--format", "template", "--template", "report.j2"]);
+        assert_eq!(
+            args.template.as_deref(),
+            Some(std::path::Path::new("report.j2"))
+        );
+        assert!(Cli::try_parse_from(["guardian-cli", "check", "--format", "template"]).is_err());
+    }
+
+    #[test]
+    fn test_check_fail_policy() {
+        let args = parse(&["--fail-on", "warning", "--max-warnings", "5"]);
+        assert_eq!(args.fail_on, Some(FailOn::Warning));
+        assert_eq!(args.max_warnings, Some(5));
+        assert_eq!(parse(&[]).fail_on, None);
+        assert_eq!(
+            parse(&["--policy", "score > 80"]).policy.as_deref(),
+            Some("score > 80")
+        );
+    }
+
+    #[test]
+    fn test_check_skip() {
+        let args = parse(&[
+            "--only",
+            "loc-limits,module-count",
+            "--skip",
+            "module-count",
+        ]);
+        assert_eq!(args.only.as_deref(), Some("loc-limits,module-count"));
+        assert_eq!(args.skip.as_deref(), Some("module-count"));
+    }
+
+    #[test]
+    fn test_check_blame() {
+        assert!(!parse(&[]).blame);
+        assert!(parse(&["--blame"]).blame);
+    }
+
+    #[test]
+    fn test_check_explain_llm() {
+        assert!(!parse(&[]).explain_llm);
+        assert!(parse(&["--explain-llm"]).explain_llm);
+    }
+
+    #[test]
+    fn test_check_save() {
+        assert_eq!(parse(&[]).save, None);
+        assert_eq!(
+            parse(&["--save", "results.json"]).save.as_deref(),
+            Some(std::path::Path::new("results.json"))
+        );
+    }
+
+    #[test]
+    fn test_check_recursive() {
+        assert!(!parse(&[]).recursive);
+        assert!(parse(&["--recursive"]).recursive);
+    }
+}
@@ -0,0 +1,67 @@
+//! Arguments for the show-config, migrate-config, and setup commands.
+
+use clap::Args;
+use std::path::PathBuf;
+
+/// Options for `show-config`.
+#[derive(Debug, Args)]
+pub struct ShowConfigArgs {
+    /// Show which config file set each value
+    #[arg(long)]
+    pub origin: bool,
+}
+
+/// Options for `migrate-config`.
+#[derive(Debug, Args)]
+pub struct MigrateConfigArgs {
+    /// Config file to upgrade (default: the --config file or the user config)
+    #[arg(value_name = "FILE")]
+    pub path: Option<PathBuf>,
+
+    /// Show what would change without writing anything
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// Options for `setup`.
+#[derive(Debug, Args)]
+pub struct SetupArgs {
+    /// Also probe every address on the local network
+    #[arg(long)]
+    pub scan: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::cli::{Cli, Commands};
+    use clap::Parser;
+
+    #[test]
+    fn test_cli_show_config_origin() {
+        let cli = Cli::try_parse_from(["guardian-cli", "show-config", "--origin"]).unwrap();
+        assert_eq!(cli.command.project_dir(), None);
+        let Commands::ShowConfig(args) = cli.command else {
+            panic!("Expected ShowConfig command");
+        };
+        assert!(args.origin);
+    }
+
+    #[test]
+    fn test_cli_migrate_config() {
+        let cli =
+            Cli::try_parse_from(["guardian-cli", "migrate-config", "a.toml", "--dry-run"]).unwrap();
+        let Commands::MigrateConfig(args) = cli.command else {
+            panic!("Expected MigrateConfig command");
+        };
+        assert!(args.dry_run);
+    }
+
+    #[test]
+    fn test_cli_setup_scan() {
+        let cli = Cli::try_parse_from(["guardian-cli", "setup", "--scan"]).unwrap();
+        let Commands::Setup(args) = cli.command else {
+            panic!("Expected Setup command");
+        };
+        assert!(args.scan);
+    }
+}
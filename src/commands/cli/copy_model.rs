@@ -0,0 +1,45 @@
+//! Arguments for the copy-model command.
+
+use clap::Args;
+
+/// Options for `copy-model`.
+#[derive(Debug, Args)]
+pub struct CopyModelArgs {
+    /// Model to copy, as the source host names it (e.g. qwen2.5-coder:14b)
+    pub model: String,
+
+    /// Host that has the model
+    #[arg(long, value_name = "HOST")]
+    pub from: String,
+
+    /// Host to copy it to
+    #[arg(long, value_name = "HOST")]
+    pub to: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::cli::{Cli, Commands};
+    use clap::Parser;
+
+    #[test]
+    fn test_copy_model_args() {
+        let cli = [
+            "guardian-cli",
+            "copy-model",
+            "qwen2.5-coder:14b",
+            "--from",
+            "big72",
+            "--to",
+            "local",
+        ];
+        let Commands::CopyModel(args) = Cli::try_parse_from(cli).unwrap().command else {
+            panic!("Expected CopyModel command");
+        };
+        assert_eq!(args.model, "qwen2.5-coder:14b");
+        assert_eq!((args.from.as_str(), args.to.as_str()), ("big72", "local"));
+        assert!(
+            Cli::try_parse_from(["guardian-cli", "copy-model", "phi4", "--from", "a"]).is_err()
+        );
+    }
+}
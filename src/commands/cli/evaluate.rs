@@ -0,0 +1,115 @@
+//! Arguments for the evaluate command.
+
+use clap::Args;
+use std::path::PathBuf;
+
+/// Options for `evaluate`.
+#[derive(Debug, Args)]
+pub struct EvaluateArgs {
+    /// Path to the project directory (default: current directory)
+    #[arg(value_name = "PATH")]
+    pub path: Option<PathBuf>,
+
+    /// Model to use for evaluation
+    #[arg(long, short)]
+    pub model: Option<String>,
+
+    /// Specific host to use
+    #[arg(long)]
+    pub host: Option<String>,
+
+    /// Only run specific check(s), comma-separated
+    #[arg(long)]
+    pub only: Option<String>,
+
+    /// Don't run these check(s), comma-separated
+    #[arg(long, value_name = "CHECKS")]
+    pub skip: Option<String>,
+
+    /// Also write an HTML report, including the evaluation, to FILE
+    #[arg(long, value_name = "FILE")]
+    pub html_report: Option<PathBuf>,
+
+    /// Pass or fail by a policy expression (default: from config, or any error fails)
+    #[arg(long, value_name = "EXPR")]
+    pub policy: Option<String>,
+
+    /// Attribute each failure to the author and commit of its line (git blame)
+    #[arg(long)]
+    pub blame: bool,
+
+    /// Summarize the failures with rules instead of an LLM (also used when no host is reachable)
+    #[arg(long)]
+    pub offline: bool,
+
+    /// Ask the model for a unified diff fixing each error, kept if it applies cleanly
+    #[arg(long, conflicts_with = "offline")]
+    pub suggest_patches: bool,
+
+    /// Have the small `triage` model set trivial violations aside, and evaluate only the rest
+    #[arg(long, conflicts_with = "offline")]
+    pub triage: bool,
+
+    /// After the evaluation, keep the conversation open for follow-up questions
+    #[arg(long, conflicts_with = "offline")]
+    pub interactive: bool,
+
+    /// Ask the model again even if it already evaluated identical results
+    #[arg(long, conflicts_with = "offline")]
+    pub force: bool,
+
+    /// Quote N lines of source on each side of each violation (default: 5, or none for over 20 violations; 0 turns them off)
+    #[arg(long, value_name = "N")]
+    pub snippet_lines: Option<usize>,
+
+    /// Evaluate results saved with `check --save` instead of running the checks
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["only", "skip"])]
+    pub from: Option<PathBuf>,
+
+    /// Evaluate only the staged change: checks on the staged files, and the diff itself
+    #[arg(long, conflicts_with = "from")]
+    pub staged: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::cli::{Cli, Commands};
+    use clap::Parser;
+
+    #[test]
+    fn test_model_features_need_a_model() {
+        let cli = Cli::try_parse_from(["guardian-cli", "evaluate", "--suggest-patches"]).unwrap();
+        match cli.command {
+            Commands::Evaluate(args) => assert!(args.suggest_patches && !args.offline),
+            _ => panic!("Expected Evaluate command"),
+        }
+        let offline = ["guardian-cli", "evaluate", "--offline", "--suggest-patches"];
+        assert!(Cli::try_parse_from(offline).is_err());
+        let offline = ["guardian-cli", "evaluate", "--offline", "--triage"];
+        assert!(Cli::try_parse_from(offline).is_err());
+        let offline = ["guardian-cli", "evaluate", "--offline", "--interactive"];
+        assert!(Cli::try_parse_from(offline).is_err());
+    }
+
+    #[test]
+    fn test_from_skips_check_selection() {
+        let cli = Cli::try_parse_from(["guardian-cli", "evaluate", "--from", "r.json"]).unwrap();
+        match cli.command {
+            Commands::Evaluate(args) => {
+                assert_eq!(args.from.as_deref(), Some(std::path::Path::new("r.json")));
+            }
+            _ => panic!("Expected Evaluate command"),
+        }
+        let only = [
+            "guardian-cli",
+            "evaluate",
+            "--from",
+            "r.json",
+            "--only",
+            "loc-limits",
+        ];
+        assert!(Cli::try_parse_from(only).is_err());
+        let staged = ["guardian-cli", "evaluate", "--from", "r.json", "--staged"];
+        assert!(Cli::try_parse_from(staged).is_err());
+    }
+}
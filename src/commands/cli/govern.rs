@@ -0,0 +1,66 @@
+//! Arguments for the govern command.
+
+use clap::Args;
+use std::path::PathBuf;
+
+/// Options for `govern`.
+#[derive(Debug, Args)]
+pub struct GovernArgs {
+    /// JSON file describing the proposed action, or - for stdin
+    #[arg(long, short, value_name = "FILE", default_value = "-")]
+    pub input: PathBuf,
+
+    /// Path to the project directory (default: current directory)
+    #[arg(value_name = "PATH")]
+    pub path: Option<PathBuf>,
+
+    /// Only run specific check(s), comma-separated
+    #[arg(long, value_name = "CHECKS")]
+    pub only: Option<String>,
+
+    /// Don't run these check(s), comma-separated
+    #[arg(long, value_name = "CHECKS")]
+    pub skip: Option<String>,
+
+    /// Decide from the checks alone, without asking an LLM
+    #[arg(long)]
+    pub checks_only: bool,
+
+    /// Model to judge the action with
+    #[arg(long, short, conflicts_with = "checks_only")]
+    pub model: Option<String>,
+
+    /// Specific host to judge the action on
+    #[arg(long, conflicts_with = "checks_only")]
+    pub host: Option<String>,
+
+    /// Check results this policy expression rejects need revising (default: from config, or any error)
+    #[arg(long, value_name = "EXPR")]
+    pub policy: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::cli::{Cli, Commands};
+    use clap::Parser;
+    use std::path::Path;
+
+    #[test]
+    fn test_govern_args() {
+        let cli = Cli::try_parse_from(["guardian-cli", "govern", "--checks-only"]).unwrap();
+        let Commands::Govern(args) = cli.command else {
+            panic!("Expected Govern command");
+        };
+        assert_eq!(args.input, Path::new("-"));
+        assert!(args.checks_only && args.path.is_none());
+        let cli = Cli::try_parse_from(["guardian-cli", "govern", "-i", "action.json", "../app"]);
+        assert_eq!(
+            cli.unwrap().command.project_dir(),
+            Some(Path::new("../app"))
+        );
+        assert!(
+            Cli::try_parse_from(["guardian-cli", "govern", "--checks-only", "--model", "m"])
+                .is_err()
+        );
+    }
+}
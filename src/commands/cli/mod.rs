@@ -0,0 +1,339 @@
+//! Command-line interface definition.
+
+use clap::{Parser, Subcommand};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+
+mod annotate;
+mod apply;
+mod ask;
+mod check;
+mod config;
+mod copy_model;
+mod evaluate;
+mod govern;
+mod pre_push;
+mod prune_models;
+mod repair;
+mod select_host;
+mod self_check;
+mod serve;
+mod stats;
+mod suggest_tests;
+mod values;
+mod warmup;
+mod watchdog;
+
+pub use annotate::AnnotateArgs;
+pub use apply::ApplyArgs;
+pub use ask::AskArgs;
+pub use check::CheckArgs;
+pub use config::{MigrateConfigArgs, SetupArgs, ShowConfigArgs};
+pub use copy_model::CopyModelArgs;
+pub use evaluate::EvaluateArgs;
+pub use govern::GovernArgs;
+pub use pre_push::PrePushArgs;
+pub use prune_models::PruneModelsArgs;
+pub use repair::RepairArgs;
+pub use select_host::SelectHostArgs;
+pub use self_check::SelfCheckArgs;
+pub use serve::{ServeArgs, UsageArgs};
+pub use stats::StatsArgs;
+pub use suggest_tests::SuggestTestsArgs;
+pub use values::{ColorChoice, FailOn, GroupBy, ReportFormat, TestsFormat};
+pub use warmup::WarmupArgs;
+pub use watchdog::WatchdogArgs;
+
+/// Guardian CLI - Local LLM governor for development process enforcement.
+#[derive(Debug, Parser)]
+#[command(name = "guardian-cli")]
+#[command(author, version, about, long_about = None)]
+pub struct Cli {
+    /// Path to configuration file (default: ~/.config/guardian-cli/guardian.toml)
+    #[arg(long, short, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// Output in JSON format
+    #[arg(long, global = true)]
+    pub json: bool,
+
+    /// Enable verbose output
+    #[arg(long, short, global = true)]
+    pub verbose: bool,
+
+    /// Write the report to a file instead of stdout (progress stays on stderr)
+    #[arg(long, short, global = true, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    /// Apply the [profile.NAME] section of the config (or set GUARDIAN_PROFILE)
+    #[arg(long, global = true, value_name = "NAME")]
+    pub profile: Option<String>,
+
+    /// Only use hosts with this tag (repeat to require several)
+    #[arg(long, global = true, value_name = "TAG")]
+    pub host_tag: Vec<String>,
+
+    /// Team policy config (URL, git+REPO//FILE, or path) under all others (or set GUARDIAN_POLICY_URL)
+    #[arg(long, global = true, value_name = "SOURCE")]
+    pub policy_url: Option<String>,
+
+    /// Follow symlinks when looking for files to check (cycles are skipped)
+    #[arg(long, global = true)]
+    pub follow_symlinks: bool,
+
+    /// Most files, hosts, and model requests handled at once (default: one per core)
+    #[arg(long, short, global = true, value_name = "N")]
+    pub jobs: Option<NonZeroUsize>,
+
+    /// Ping hosts and list their models again instead of reusing recent results
+    #[arg(long, global = true)]
+    pub fresh: bool,
+
+    /// When to color output
+    #[arg(long, global = true, value_enum, default_value_t = ColorChoice::Auto)]
+    pub color: ColorChoice,
+
+    #[command(subcommand)]
+    pub command: Commands,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Commands {
+    /// Ping all configured Ollama hosts to check availability
+    PingHosts,
+
+    /// List models available on reachable Ollama hosts
+    ListModels {
+        /// Only query a specific host by name
+        #[arg(long)]
+        host: Option<String>,
+    },
+
+    /// Show every host's Ollama version, models, disk use, and loaded models
+    Fleet,
+
+    /// Select the best available host (for scripting)
+    SelectHost(SelectHostArgs),
+
+    /// Show current configuration
+    ShowConfig(ShowConfigArgs),
+
+    /// Show default config file path
+    ConfigPath,
+
+    /// Upgrade a config file from an older layout, keeping a .bak copy
+    MigrateConfig(MigrateConfigArgs),
+
+    /// Find Ollama servers, ask a few questions, and write the config
+    Setup(SetupArgs),
+
+    /// Store a host secret in the OS keyring (prompts, or reads stdin)
+    ///
+    /// On Linux this needs a running Secret Service, such as GNOME Keyring.
+    SetSecret {
+        /// Name referenced by token_keyring or password_keyring
+        name: String,
+    },
+
+    /// Remove a host secret from the OS keyring
+    DeleteSecret {
+        /// Name of the secret
+        name: String,
+    },
+
+    /// Send a prompt to an Ollama model and get a response
+    Ask(AskArgs),
+
+    /// Run checks AND have LLM evaluate results to enforce process
+    Evaluate(EvaluateArgs),
+
+    /// Run as a daemon: monitor host health, run scheduled jobs, and serve the HTTP API
+    Serve(ServeArgs),
+
+    /// Show request, token, and wall-time usage per host and model
+    Usage(UsageArgs),
+
+    /// Load a model into memory ahead of use (e.g. from cron)
+    Warmup(WarmupArgs),
+
+    /// Delete models hosts no longer need, keeping the ones the config uses
+    PruneModels(PruneModelsArgs),
+
+    /// Copy a model onto another host from its registry, checking the digests match
+    CopyModel(CopyModelArgs),
+
+    /// Run checklist validation on a project
+    Check(CheckArgs),
+
+    /// Check the commits in a push, e.g. from a git pre-push hook
+    PrePush(PrePushArgs),
+
+    /// Walk through fix diffs, apply the accepted ones, and re-run their checks
+    Apply(ApplyArgs),
+
+    /// Judge an agent's proposed action and print an allow/revise/deny verdict
+    Govern(GovernArgs),
+
+    /// Have the model propose tests for public functions that have none
+    SuggestTests(SuggestTestsArgs),
+
+    /// Have the model fix violations in a scratch worktree, then apply the result once approved
+    Repair(RepairArgs),
+
+    /// Run every check on guardian's own source and environment, showing which fired and why
+    SelfCheck(SelfCheckArgs),
+
+    /// Re-run the checks on an interval and notify when they regress
+    Watchdog(WatchdogArgs),
+
+    /// Count open, recently introduced, and recently fixed violations per check or git author
+    Stats(StatsArgs),
+
+    /// List every check with its severities and settings (--json for tools)
+    Rules,
+
+    /// Post check failures on the GitLab merge request or Gitea pull request under review
+    Annotate(AnnotateArgs),
+
+    /// Run a language server that shows check failures in the editor
+    Lsp,
+
+    /// Serve JSON-RPC requests (check, evaluate, selectHost, ask) on stdio
+    Rpc,
+}
+
+impl Commands {
+    /// The project directory the command works on, if it names one.
+    ///
+    /// The project config is looked up from here, or from the current
+    /// directory when this is `None`.
+    pub fn project_dir(&self) -> Option<&Path> {
+        match self {
+            Commands::Check(args) => args.path.as_deref(),
+            Commands::Evaluate(args) => args.path.as_deref(),
+            Commands::PrePush(args) => args.path.as_deref(),
+            Commands::Govern(args) => args.path.as_deref(),
+            Commands::Annotate(args) => args.path.as_deref(),
+            Commands::Apply(args) => args.path.as_deref(),
+            Commands::SuggestTests(args) => args.path.as_deref(),
+            Commands::Repair(args) => args.path.as_deref(),
+            Commands::Watchdog(args) => args.path.as_deref(),
+            Commands::Stats(args) => args.path.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_cli_parsing() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_cli_ping_hosts() {
+        let cli = Cli::try_parse_from(["guardian-cli", "ping-hosts"]).unwrap();
+        assert!(matches!(cli.command, Commands::PingHosts));
+    }
+
+    #[test]
+    fn test_cli_list_models() {
+        let cli = Cli::try_parse_from(["guardian-cli", "list-models"]).unwrap();
+        assert!(matches!(cli.command, Commands::ListModels { host: None }));
+    }
+
+    #[test]
+    fn test_cli_list_models_with_host() {
+        let cli = Cli::try_parse_from(["guardian-cli", "list-models", "--host", "big72"]).unwrap();
+        match cli.command {
+            Commands::ListModels { host } => assert_eq!(host, Some("big72".to_string())),
+            _ => panic!("Expected ListModels command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_project_dir() {
+        let cli = Cli::try_parse_from(["guardian-cli", "check", "../other"]).unwrap();
+        assert_eq!(cli.command.project_dir(), Some(Path::new("../other")));
+    }
+
+    #[test]
+    fn test_cli_set_secret() {
+        let cli = Cli::try_parse_from(["guardian-cli", "set-secret", "big72"]).unwrap();
+        assert!(matches!(cli.command, Commands::SetSecret { ref name } if name == "big72"));
+    }
+
+    #[test]
+    fn test_cli_json_flag() {
+        let cli = Cli::try_parse_from(["guardian-cli", "--json", "ping-hosts"]).unwrap();
+        assert!(cli.json);
+    }
+
+    #[test]
+    fn test_cli_output_flag() {
+        let cli = Cli::try_parse_from(["guardian-cli", "-o", "hosts.json", "ping-hosts"]).unwrap();
+        assert_eq!(cli.output, Some(PathBuf::from("hosts.json")));
+        let cli = Cli::try_parse_from(["guardian-cli", "check", "--output", "report.xml"]).unwrap();
+        assert_eq!(cli.output, Some(PathBuf::from("report.xml")));
+    }
+
+    #[test]
+    fn test_cli_profile_flag() {
+        let cli = Cli::try_parse_from(["guardian-cli", "check", "--profile", "ci"]).unwrap();
+        assert_eq!(cli.profile.as_deref(), Some("ci"));
+    }
+
+    #[test]
+    fn test_cli_policy_url_flag() {
+        let cli =
+            Cli::try_parse_from(["guardian-cli", "--policy-url", "https://x/p.toml", "check"]);
+        assert_eq!(cli.unwrap().policy_url.as_deref(), Some("https://x/p.toml"));
+    }
+
+    #[test]
+    fn test_cli_host_tag_flag() {
+        let cli = Cli::try_parse_from(["guardian-cli", "--host-tag", "gpu", "ask", "hi"]).unwrap();
+        assert_eq!(cli.host_tag, ["gpu"]);
+    }
+
+    #[test]
+    fn test_cli_follow_symlinks_flag() {
+        let cli = Cli::try_parse_from(["guardian-cli", "check", "--follow-symlinks"]).unwrap();
+        assert!(cli.follow_symlinks);
+    }
+
+    #[test]
+    fn test_cli_jobs_flag() {
+        let cli = Cli::try_parse_from(["guardian-cli", "-j", "2", "ping-hosts"]).unwrap();
+        assert_eq!(cli.jobs, NonZeroUsize::new(2));
+        assert!(Cli::try_parse_from(["guardian-cli", "--jobs", "0", "check"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_fresh_flag() {
+        let cli = Cli::try_parse_from(["guardian-cli", "select-host", "--fresh"]).unwrap();
+        assert!(cli.fresh);
+    }
+
+    #[test]
+    fn test_cli_verbose_flag() {
+        let cli = Cli::try_parse_from(["guardian-cli", "-v", "ping-hosts"]).unwrap();
+        assert!(cli.verbose);
+    }
+
+    #[test]
+    fn test_cli_config_flag() {
+        let cli = Cli::try_parse_from([
+            "guardian-cli",
+            "--config",
+            "/path/to/config.toml",
+            "ping-hosts",
+        ])
+        .unwrap();
+        assert_eq!(cli.config, Some(PathBuf::from("/path/to/config.toml")));
+    }
+}
@@ -0,0 +1,66 @@
+//! Arguments for the pre-push command.
+
+use clap::Args;
+use std::path::PathBuf;
+
+/// Options for `pre-push`.
+#[derive(Debug, Args)]
+pub struct PrePushArgs {
+    /// Commits being pushed, as REMOTE..LOCAL (e.g. origin/main..HEAD)
+    #[arg(value_name = "RANGE")]
+    pub range: String,
+
+    /// Path to the project directory (default: current directory)
+    #[arg(value_name = "PATH")]
+    pub path: Option<PathBuf>,
+
+    /// Only run specific check(s), comma-separated
+    #[arg(long, value_name = "CHECKS")]
+    pub only: Option<String>,
+
+    /// Don't run these check(s), comma-separated
+    #[arg(long, value_name = "CHECKS")]
+    pub skip: Option<String>,
+
+    /// Also have an LLM review the pushed patches
+    #[arg(long)]
+    pub review: bool,
+
+    /// After the review, keep the conversation open for follow-up questions
+    #[arg(long, requires = "review")]
+    pub interactive: bool,
+
+    /// Model to use for the review
+    #[arg(long, short, requires = "review")]
+    pub model: Option<String>,
+
+    /// Specific host to use for the review
+    #[arg(long, requires = "review")]
+    pub host: Option<String>,
+
+    /// Pass or fail by a policy expression (default: from config, or any error fails)
+    #[arg(long, value_name = "EXPR")]
+    pub policy: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::cli::{Cli, Commands};
+    use clap::Parser;
+
+    #[test]
+    fn test_pre_push_args() {
+        let cli =
+            Cli::try_parse_from(["guardian-cli", "pre-push", "origin/main..HEAD", "--review"])
+                .unwrap();
+        let Commands::PrePush(args) = cli.command else {
+            panic!("Expected PrePush command");
+        };
+        assert_eq!(args.range, "origin/main..HEAD");
+        assert!(args.review && !args.interactive && args.path.is_none());
+        assert!(Cli::try_parse_from(["guardian-cli", "pre-push"]).is_err());
+        assert!(Cli::try_parse_from(["guardian-cli", "pre-push", "a..b", "--model", "m"]).is_err());
+        let interactive = ["guardian-cli", "pre-push", "a..b", "--interactive"];
+        assert!(Cli::try_parse_from(interactive).is_err());
+    }
+}
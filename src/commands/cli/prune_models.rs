@@ -0,0 +1,59 @@
+//! Arguments for the prune-models command.
+
+use clap::Args;
+
+/// Options for `prune-models`.
+#[derive(Debug, Args)]
+pub struct PruneModelsArgs {
+    /// Specific host to prune (default: first reachable)
+    #[arg(long, conflicts_with = "all")]
+    pub host: Option<String>,
+
+    /// Prune every enabled host
+    #[arg(long)]
+    pub all: bool,
+
+    /// Models to keep, comma-separated; a name without a tag keeps every tag
+    #[arg(long, value_name = "LIST")]
+    pub keep: Option<String>,
+
+    /// Only delete models not pulled or updated for this long (e.g. 30d, 2w)
+    #[arg(long, value_name = "AGE")]
+    pub older_than: Option<String>,
+
+    /// List what would be deleted and how much space it would free
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Delete without asking
+    #[arg(long, short)]
+    pub yes: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::cli::{Cli, Commands};
+    use clap::Parser;
+
+    #[test]
+    fn test_prune_models_args() {
+        let cli = [
+            "guardian-cli",
+            "prune-models",
+            "--all",
+            "--keep",
+            "llama3.2,phi4:14b",
+            "--older-than",
+            "30d",
+            "--dry-run",
+        ];
+        let Commands::PruneModels(args) = Cli::try_parse_from(cli).unwrap().command else {
+            panic!("Expected PruneModels command");
+        };
+        assert!(args.all && args.dry_run && !args.yes);
+        assert_eq!(args.keep.as_deref(), Some("llama3.2,phi4:14b"));
+        assert_eq!(args.older_than.as_deref(), Some("30d"));
+        let both = ["guardian-cli", "prune-models", "--all", "--host", "big72"];
+        assert!(Cli::try_parse_from(both).is_err());
+    }
+}
@@ -0,0 +1,72 @@
+//! Arguments for the repair command.
+
+use clap::Args;
+use std::path::PathBuf;
+
+/// Options for `repair`.
+#[derive(Debug, Args)]
+pub struct RepairArgs {
+    /// Path to the project directory (default: current directory)
+    #[arg(value_name = "PATH")]
+    pub path: Option<PathBuf>,
+
+    /// Only fix violations of these check(s), comma-separated
+    #[arg(long, value_name = "CHECKS")]
+    pub only: Option<String>,
+
+    /// Don't fix violations of these check(s), comma-separated
+    #[arg(long, value_name = "CHECKS")]
+    pub skip: Option<String>,
+
+    /// Most fixes to attempt
+    #[arg(long, value_name = "N", default_value_t = 5)]
+    pub max_iterations: usize,
+
+    /// Apply the repairs without asking
+    #[arg(long, short)]
+    pub yes: bool,
+
+    /// Model to write the fixes with
+    #[arg(long, short)]
+    pub model: Option<String>,
+
+    /// Specific host to use
+    #[arg(long)]
+    pub host: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::cli::{Cli, Commands};
+    use clap::Parser;
+    use std::path::Path;
+
+    #[test]
+    fn test_repair_args() {
+        let cli = Cli::try_parse_from(["guardian-cli", "repair"]).unwrap();
+        let Commands::Repair(args) = cli.command else {
+            panic!("Expected Repair command");
+        };
+        assert_eq!(args.max_iterations, 5);
+        assert!(!args.yes);
+
+        let cli = [
+            "guardian-cli",
+            "repair",
+            "../app",
+            "--only",
+            "clippy-disables",
+            "--max-iterations",
+            "3",
+            "-y",
+        ];
+        let cli = Cli::try_parse_from(cli).unwrap();
+        assert_eq!(cli.command.project_dir(), Some(Path::new("../app")));
+        let Commands::Repair(args) = cli.command else {
+            panic!("Expected Repair command");
+        };
+        assert_eq!(args.only.as_deref(), Some("clippy-disables"));
+        assert_eq!(args.max_iterations, 3);
+        assert!(args.yes);
+    }
+}
@@ -0,0 +1,31 @@
+//! Arguments for the select-host command.
+
+use clap::Args;
+
+/// Options for `select-host`.
+#[derive(Debug, Args)]
+pub struct SelectHostArgs {
+    /// Require a specific model to be available
+    #[arg(long)]
+    pub model: Option<String>,
+
+    /// Print every host ranked by score instead of just the best
+    #[arg(long)]
+    pub all: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::cli::{Cli, Commands};
+    use clap::Parser;
+
+    #[test]
+    fn test_cli_select_host_all() {
+        let cli = Cli::try_parse_from(["guardian-cli", "select-host", "--all"]).unwrap();
+        let Commands::SelectHost(args) = cli.command else {
+            panic!("Expected SelectHost command");
+        };
+        assert_eq!(args.model, None);
+        assert!(args.all);
+    }
+}
@@ -0,0 +1,34 @@
+//! Arguments for the self-check command.
+
+use clap::Args;
+use std::path::PathBuf;
+
+/// Options for `self-check`.
+#[derive(Debug, Args)]
+pub struct SelfCheckArgs {
+    /// Guardian's source tree (default: where this binary was built, if it is still there)
+    #[arg(long, value_name = "DIR")]
+    pub source: Option<PathBuf>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::cli::{Cli, Commands};
+    use clap::Parser;
+    use std::path::Path;
+
+    #[test]
+    fn test_self_check_args() {
+        let cli = Cli::try_parse_from(["guardian-cli", "self-check"]).unwrap();
+        let Commands::SelfCheck(args) = cli.command else {
+            panic!("Expected SelfCheck command");
+        };
+        assert!(args.source.is_none());
+
+        let cli = ["guardian-cli", "self-check", "--source", "../guardian"];
+        let Commands::SelfCheck(args) = Cli::try_parse_from(cli).unwrap().command else {
+            panic!("Expected SelfCheck command");
+        };
+        assert_eq!(args.source.as_deref(), Some(Path::new("../guardian")));
+    }
+}
@@ -0,0 +1,43 @@
+//! Arguments for the serve and usage commands.
+
+use clap::Args;
+
+/// Options for `serve`.
+#[derive(Debug, Args)]
+pub struct ServeArgs {
+    /// Address to listen on (default: from config or 127.0.0.1:7878)
+    #[arg(long, value_name = "ADDR")]
+    pub bind: Option<String>,
+}
+
+/// Options for `usage`.
+#[derive(Debug, Args)]
+pub struct UsageArgs {
+    /// Only include requests from this long ago (e.g. 24h, 7d, 2w)
+    #[arg(long, value_name = "AGE")]
+    pub since: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::cli::{Cli, Commands};
+    use clap::Parser;
+
+    #[test]
+    fn test_cli_serve_with_bind() {
+        let cli = Cli::try_parse_from(["guardian-cli", "serve", "--bind", "0.0.0.0:9000"]).unwrap();
+        let Commands::Serve(args) = cli.command else {
+            panic!("Expected Serve command");
+        };
+        assert_eq!(args.bind.as_deref(), Some("0.0.0.0:9000"));
+    }
+
+    #[test]
+    fn test_cli_usage_since() {
+        let cli = Cli::try_parse_from(["guardian-cli", "usage", "--since", "7d"]).unwrap();
+        let Commands::Usage(args) = cli.command else {
+            panic!("Expected Usage command");
+        };
+        assert_eq!(args.since.as_deref(), Some("7d"));
+    }
+}
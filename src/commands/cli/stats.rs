@@ -0,0 +1,52 @@
+//! Arguments for the stats command.
+
+use clap::Args;
+use std::path::PathBuf;
+
+/// Options for `stats`.
+#[derive(Debug, Args)]
+pub struct StatsArgs {
+    /// Path to the project directory (default: current directory)
+    #[arg(value_name = "PATH")]
+    pub path: Option<PathBuf>,
+
+    /// Group violations by the git author of their line instead of by check
+    #[arg(long)]
+    pub by_author: bool,
+
+    /// How far back "recent" goes for introduced and fixed violations, e.g. 7d, 2w
+    #[arg(long, value_name = "AGE", default_value = "30d")]
+    pub since: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::cli::{Cli, Commands};
+    use clap::Parser;
+    use std::path::Path;
+
+    #[test]
+    fn test_stats_args() {
+        let cli = Cli::try_parse_from(["guardian-cli", "stats"]).unwrap();
+        let Commands::Stats(args) = cli.command else {
+            panic!("Expected Stats command");
+        };
+        assert_eq!(args.since, "30d");
+        assert!(args.path.is_none() && !args.by_author);
+
+        let cli = [
+            "guardian-cli",
+            "stats",
+            "../agent",
+            "--by-author",
+            "--since",
+            "7d",
+        ];
+        let Commands::Stats(args) = Cli::try_parse_from(cli).unwrap().command else {
+            panic!("Expected Stats command");
+        };
+        assert_eq!(args.path.as_deref(), Some(Path::new("../agent")));
+        assert_eq!(args.since, "7d");
+        assert!(args.by_author);
+    }
+}
@@ -0,0 +1,62 @@
+//! Arguments for the suggest-tests command.
+
+use super::TestsFormat;
+use clap::Args;
+use std::path::PathBuf;
+
+/// Options for `suggest-tests`.
+#[derive(Debug, Args)]
+pub struct SuggestTestsArgs {
+    /// Path to the project directory (default: current directory)
+    #[arg(value_name = "PATH")]
+    pub path: Option<PathBuf>,
+
+    /// Only functions in the files staged for commit
+    #[arg(long)]
+    pub staged: bool,
+
+    /// Print the tests as Markdown or as Rust ready to paste
+    #[arg(long, value_enum, default_value = "markdown")]
+    pub format: TestsFormat,
+
+    /// Model to write the tests with
+    #[arg(long, short)]
+    pub model: Option<String>,
+
+    /// Specific host to use
+    #[arg(long)]
+    pub host: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::cli::{Cli, Commands, TestsFormat};
+    use clap::Parser;
+    use std::path::Path;
+
+    #[test]
+    fn test_suggest_tests_args() {
+        let cli = Cli::try_parse_from(["guardian-cli", "suggest-tests"]).unwrap();
+        let Commands::SuggestTests(args) = cli.command else {
+            panic!("Expected SuggestTests command");
+        };
+        assert_eq!(args.format, TestsFormat::Markdown);
+        assert!(!args.staged);
+
+        let cli = [
+            "guardian-cli",
+            "suggest-tests",
+            "../app",
+            "--staged",
+            "--format",
+            "rust",
+        ];
+        let cli = Cli::try_parse_from(cli).unwrap();
+        assert_eq!(cli.command.project_dir(), Some(Path::new("../app")));
+        let Commands::SuggestTests(args) = cli.command else {
+            panic!("Expected SuggestTests command");
+        };
+        assert_eq!(args.format, TestsFormat::Rust);
+        assert!(args.staged);
+    }
+}
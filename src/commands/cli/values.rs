@@ -0,0 +1,84 @@
+//! Value enums for command-line options.
+
+use clap::ValueEnum;
+
+/// When to color human-readable output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ColorChoice {
+    /// Color when stdout is a terminal and `NO_COLOR` is unset
+    Auto,
+    /// Always color
+    Always,
+    /// Never color
+    Never,
+}
+
+/// Output formats for check results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    /// Human-readable text
+    Text,
+    /// Guardian's JSON summary
+    Json,
+    /// JUnit XML for CI test reporting
+    Junit,
+    /// GitHub Actions annotations and job summary
+    Github,
+    /// GitLab Code Quality report
+    Gitlab,
+    /// Checkstyle XML
+    Checkstyle,
+    /// Markdown for pull request comments
+    Markdown,
+    /// Self-contained HTML page with charts
+    Html,
+    /// CSV, one row per result
+    Csv,
+    /// Compiler-style `path:line: severity: [check] message` lines
+    Short,
+    /// A user-supplied template (see --template)
+    Template,
+}
+
+/// Which failed checks fail the run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum FailOn {
+    /// Fail on errors
+    Error,
+    /// Fail on errors and warnings
+    Warning,
+    /// Never fail because of check results
+    Never,
+}
+
+/// How results are grouped in text and JSON output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum GroupBy {
+    /// One section per check
+    Check,
+    /// One section per file
+    File,
+}
+
+/// How `suggest-tests` prints the suggested tests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TestsFormat {
+    /// A Markdown section per function, with each test's purpose
+    Markdown,
+    /// Test functions ready to paste into each file's tests module
+    Rust,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::cli::{Cli, ColorChoice};
+    use clap::Parser;
+
+    #[test]
+    fn test_cli_color_flag() {
+        let cli = Cli::try_parse_from(["guardian-cli", "ping-hosts"]).unwrap();
+        assert_eq!(cli.color, ColorChoice::Auto);
+        let cli = Cli::try_parse_from(["guardian-cli", "check", "--color", "never"]).unwrap();
+        assert_eq!(cli.color, ColorChoice::Never);
+    }
+}
@@ -0,0 +1,43 @@
+//! Arguments for the warmup command.
+
+use clap::Args;
+
+/// Options for `warmup`.
+#[derive(Debug, Args)]
+pub struct WarmupArgs {
+    /// Model to load (default: the model routed to evaluate)
+    #[arg(long, short)]
+    pub model: Option<String>,
+
+    /// Specific host to warm (default: first reachable)
+    #[arg(long, conflicts_with = "all")]
+    pub host: Option<String>,
+
+    /// Warm every enabled host
+    #[arg(long)]
+    pub all: bool,
+
+    /// How long the model stays loaded (e.g. 30m, 8h, -1m for forever)
+    #[arg(long, default_value = "1h", allow_hyphen_values = true)]
+    pub keep_alive: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::cli::{Cli, Commands};
+    use clap::Parser;
+
+    #[test]
+    fn test_cli_warmup_all() {
+        let cli = Cli::try_parse_from(["guardian-cli", "warmup", "--all", "--keep-alive", "-1m"])
+            .unwrap();
+        let Commands::Warmup(args) = cli.command else {
+            panic!("Expected Warmup command");
+        };
+        assert!(args.all);
+        assert_eq!(args.keep_alive, "-1m");
+        assert!(
+            Cli::try_parse_from(["guardian-cli", "warmup", "--all", "--host", "big72"]).is_err()
+        );
+    }
+}
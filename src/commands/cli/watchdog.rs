@@ -0,0 +1,60 @@
+//! Arguments for the watchdog command.
+
+use clap::Args;
+use std::path::PathBuf;
+
+/// Options for `watchdog`.
+#[derive(Debug, Args)]
+pub struct WatchdogArgs {
+    /// Path to the project directory (default: current directory)
+    #[arg(value_name = "PATH")]
+    pub path: Option<PathBuf>,
+
+    /// Time between runs, e.g. 90s, 30m, 2h
+    #[arg(long, value_name = "AGE", default_value = "30m")]
+    pub interval: String,
+
+    /// Only run specific check(s), comma-separated
+    #[arg(long, value_name = "CHECKS")]
+    pub only: Option<String>,
+
+    /// Don't run these check(s), comma-separated
+    #[arg(long, value_name = "CHECKS")]
+    pub skip: Option<String>,
+
+    /// Also show regressions as desktop notifications
+    #[arg(long)]
+    pub desktop: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::cli::{Cli, Commands};
+    use clap::Parser;
+    use std::path::Path;
+
+    #[test]
+    fn test_watchdog_args() {
+        let cli = Cli::try_parse_from(["guardian-cli", "watchdog"]).unwrap();
+        let Commands::Watchdog(args) = cli.command else {
+            panic!("Expected Watchdog command");
+        };
+        assert_eq!(args.interval, "30m");
+        assert!(args.path.is_none() && !args.desktop);
+
+        let cli = [
+            "guardian-cli",
+            "watchdog",
+            "../agent",
+            "--interval",
+            "5m",
+            "--desktop",
+        ];
+        let Commands::Watchdog(args) = Cli::try_parse_from(cli).unwrap().command else {
+            panic!("Expected Watchdog command");
+        };
+        assert_eq!(args.path.as_deref(), Some(Path::new("../agent")));
+        assert_eq!(args.interval, "5m");
+        assert!(args.desktop);
+    }
+}
@@ -1,14 +1,20 @@
 //! Configuration-related commands.
 
-use crate::config::{GuardianConfig, OllamaHost};
-use anyhow::Result;
+use super::output::report;
+use crate::config::{GuardianConfig, HostAuth, OllamaHost, SecretSource};
+use anyhow::{Context, Result, anyhow};
+use std::fs;
+use std::path::{Path, PathBuf};
 
-/// Show current configuration.
-pub fn show_config(config: &GuardianConfig, json_output: bool) -> Result<()> {
+/// Show current configuration, optionally with the file each value came from.
+pub fn show_config(config: &GuardianConfig, origin: bool, json_output: bool) -> Result<()> {
     if json_output {
-        print_config_json(config)
+        print_config_json(config, origin)
     } else {
         print_config_text(config);
+        if origin {
+            print_origins(config);
+        }
         Ok(())
     }
 }
@@ -18,7 +24,7 @@ pub fn config_path(json_output: bool) -> Result<()> {
     let path = crate::config::default_config_path();
 
     if json_output {
-        println!(
+        report!(
             "{}",
             serde_json::to_string_pretty(&serde_json::json!({
                 "path": path.as_ref().map(|p| p.display().to_string()),
@@ -26,22 +32,87 @@ pub fn config_path(json_output: bool) -> Result<()> {
         );
     } else {
         match path {
-            Some(p) => println!("{}", p.display()),
-            None => {
-                eprintln!("Could not determine config path");
-                std::process::exit(1);
-            }
+            Some(p) => report!("{}", p.display()),
+            None => anyhow::bail!("Could not determine config path"),
         }
     }
 
     Ok(())
 }
 
-fn print_config_json(config: &GuardianConfig) -> Result<()> {
-    let json = serde_json::json!({
+/// Upgrade a config file written for an older layout to the current schema.
+///
+/// Changes are written only when something moved, after copying the
+/// original to `FILE.bak`. With `dry_run`, only the summary is printed.
+pub fn migrate_config(path: Option<&Path>, dry_run: bool, json_output: bool) -> Result<()> {
+    let path = match path {
+        Some(p) => p.to_path_buf(),
+        None => crate::config::default_config_path()
+            .context("Could not determine default config path")?,
+    };
+    let source = path.display().to_string();
+    let contents = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read config file at {source}"))?;
+    let migrated = crate::config::migrate(&source, &contents)?;
+
+    let backup = PathBuf::from(format!("{source}.bak"));
+    let written = !dry_run && migrated.changes.iter().any(|c| c.applied);
+    if written {
+        fs::write(&backup, &contents)
+            .with_context(|| format!("Failed to write backup at {}", backup.display()))?;
+        fs::write(&path, &migrated.contents)
+            .with_context(|| format!("Failed to write config file at {source}"))?;
+    }
+
+    if json_output {
+        report!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "path": source,
+                "backup": written.then(|| backup.display().to_string()),
+                "dry_run": dry_run,
+                "changes": migrated.changes.iter().map(|c| {
+                    serde_json::json!({ "from": c.from, "to": c.to, "applied": c.applied })
+                }).collect::<Vec<_>>(),
+                "problem": migrated.problem,
+            }))?
+        );
+    } else if migrated.changes.is_empty() {
+        report!("{source} already uses the current config layout");
+    } else {
+        let verb = if dry_run { "Would migrate" } else { "Migrated" };
+        report!("{verb} {source}:");
+        for change in &migrated.changes {
+            if change.applied {
+                report!("  {} -> {}", change.from, change.to);
+            } else {
+                report!(
+                    "  {} left in place: {} is already set, merge them by hand",
+                    change.from,
+                    change.to
+                );
+            }
+        }
+        if written {
+            report!("Original saved to {}", backup.display());
+        }
+    }
+
+    match migrated.problem {
+        Some(problem) => Err(anyhow!(problem).context("Config still needs manual changes")),
+        None => Ok(()),
+    }
+}
+
+fn print_config_json(config: &GuardianConfig, origin: bool) -> Result<()> {
+    let mut json = serde_json::json!({
+        "files": config.files,
+        "profile": config.active_profile,
+        "profiles": config.profile.keys().collect::<Vec<_>>(),
         "default_timeout_ms": config.default_timeout_ms(),
         "default_host": config.ollama.default_host,
         "default_model": config.ollama.default_model,
+        "system_prompt": config.ollama.system_prompt,
         "hosts": config.ollama.hosts.iter().map(|h| {
             serde_json::json!({
                 "name": h.name,
@@ -49,38 +120,177 @@ fn print_config_json(config: &GuardianConfig) -> Result<()> {
                 "enabled": h.enabled,
                 "fallback": h.fallback,
                 "description": h.description,
+                "weight": h.weight,
+                "max_concurrent": h.max_concurrent,
+                "auth": h.auth,
+                "group": h.group,
+                "tags": h.tags,
+                "default_model": h.default_model,
+                "models": h.models,
+                "default_timeout_ms": h.default_timeout_ms,
             })
         }).collect::<Vec<_>>(),
+        "routing": config.routing.iter().map(|(task, route)| {
+            (task.clone(), serde_json::json!({
+                "model": route.model,
+                "host": route.host,
+                "system_prompt": route.system_prompt,
+            }))
+        }).collect::<serde_json::Map<_, _>>(),
+        "check": {
+            "crate_kind": config.check.crate_kind,
+            "max_loc": config.check.max_loc,
+            "warn_loc": config.check.warn_loc,
+            "max_functions": config.check.max_functions,
+            "max_modules": config.check.max_modules,
+            "edition": config.check.edition,
+            "max_warnings": config.check.max_warnings,
+            "only": config.check.only,
+        },
+        "checks": config.checks.iter().map(|(name, toggle)| {
+            (name.clone(), serde_json::json!({ "enabled": toggle.enabled }))
+        }).collect::<serde_json::Map<_, _>>(),
     });
-    println!("{}", serde_json::to_string_pretty(&json)?);
+    if origin {
+        json["origins"] = config
+            .origins
+            .iter()
+            .map(|o| {
+                serde_json::json!({
+                    "key": o.key,
+                    "value": o.value,
+                    "layer": o.layer.name(),
+                    "source": o.source,
+                })
+            })
+            .collect();
+    }
+    report!("{}", serde_json::to_string_pretty(&json)?);
     Ok(())
 }
 
 fn print_config_text(config: &GuardianConfig) {
-    println!("Guardian CLI Configuration\n");
-    println!("Timeout: {}ms", config.default_timeout_ms());
+    report!("Guardian CLI Configuration\n");
+    if !config.files.is_empty() {
+        report!("Config files (lowest precedence first):");
+        for file in &config.files {
+            report!("  - {file}");
+        }
+        report!("");
+    }
+    if let Some(profile) = &config.active_profile {
+        report!("Profile: {profile}");
+    }
+    if !config.profile.is_empty() {
+        let names: Vec<&str> = config.profile.keys().map(String::as_str).collect();
+        report!("Available profiles: {}", names.join(", "));
+    }
+    report!("Timeout: {}ms", config.default_timeout_ms());
     if let Some(host) = &config.ollama.default_host {
-        println!("Default host: {host}");
+        report!("Default host: {host}");
     }
     if let Some(model) = &config.ollama.default_model {
-        println!("Default model: {model}");
+        report!("Default model: {model}");
     }
 
-    println!("\nConfigured hosts:");
+    report!("\nConfigured hosts:");
     if config.ollama.hosts.is_empty() {
-        println!("  (none)");
+        report!("  (none)");
     } else {
         for host in &config.ollama.hosts {
             print_host_info(host);
         }
     }
+
+    if let Some(kind) = config.check.crate_kind {
+        report!("\nCrate kind: {}", kind.name());
+    }
+    report!(
+        "\nCheck thresholds: max {} LOC (warn at {}), {} functions, {} modules, edition {}",
+        config.check.max_loc,
+        config.check.warn_loc,
+        config.check.max_functions,
+        config.check.max_modules,
+        config.check.edition
+    );
+
+    let disabled: Vec<&str> = config
+        .checks
+        .iter()
+        .filter(|(_, toggle)| !toggle.enabled)
+        .map(|(name, _)| name.as_str())
+        .collect();
+    if !disabled.is_empty() {
+        report!("Disabled checks: {}", disabled.join(", "));
+    }
+
+    if !config.routing.is_empty() {
+        report!("\nTask routing:");
+        for (task, route) in &config.routing {
+            let model = route.model.as_deref().unwrap_or("(default model)");
+            let host = route
+                .host
+                .as_deref()
+                .map(|h| format!(" @ {h}"))
+                .unwrap_or_default();
+            let system = if route.system_prompt.is_some() {
+                " (system prompt)"
+            } else {
+                ""
+            };
+            report!("  - {task}: {model}{host}{system}");
+        }
+    }
+}
+
+fn print_origins(config: &GuardianConfig) {
+    report!("\nValue origins (flags > env > profile > project > user > policy > defaults):");
+    if config.origins.is_empty() {
+        report!("  (no config files; all values are built-in defaults)");
+        return;
+    }
+    for o in &config.origins {
+        report!(
+            "  {} = {}  [{}: {}]",
+            o.key,
+            o.value,
+            o.layer.name(),
+            o.source
+        );
+    }
+    report!("  Everything else uses built-in defaults.");
 }
 
 fn print_host_info(host: &OllamaHost) {
     let status = if host.enabled { "enabled" } else { "disabled" };
     let fallback = if host.fallback { ", fallback" } else { "" };
-    println!("  - {} ({}) [{status}{fallback}]", host.name, host.base_url);
+    report!("  - {} ({}) [{status}{fallback}]", host.name, host.base_url);
     if let Some(desc) = &host.description {
-        println!("    {desc}");
+        report!("    {desc}");
+    }
+    if let Some(group) = &host.group {
+        report!("    group: {group}");
+    }
+    if !host.tags.is_empty() {
+        report!("    tags: {}", host.tags.join(", "));
+    }
+    if let Some(model) = &host.default_model {
+        report!("    default model: {model}");
+    }
+    for (task, model) in &host.models {
+        report!("    {task} model: {model}");
+    }
+    if let Some(auth) = &host.auth {
+        let from = match auth.secret_source() {
+            Ok(SecretSource::Env(var)) => format!("${var}"),
+            Ok(SecretSource::Keyring(name)) => format!("keyring secret '{name}'"),
+            Err(e) => format!("(invalid: {e})"),
+        };
+        match auth {
+            HostAuth::Bearer { .. } => report!("    auth: bearer token from {from}"),
+            HostAuth::Basic { username, .. } => {
+                report!("    auth: basic as {username}, password from {from}")
+            }
+        }
     }
 }
@@ -0,0 +1,119 @@
+//! Copy-model command: put a model one host has onto another.
+//!
+//! Ollama has no API for reading a model's layers off a host, so the
+//! target pulls the model from its registry, under the name the source
+//! has it by. The copy is then checked by comparing the two hosts'
+//! manifest digests: a different digest means the registry's tag has moved
+//! on since the source pulled it. Models created on a host and never
+//! pushed to a registry can't be copied this way.
+
+use super::llm::{self, pull_with_progress};
+use super::output::report;
+use crate::config::{GuardianConfig, OllamaHost};
+use crate::ollama::find_model;
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+/// Options for the copy-model command.
+pub struct CopyModelOptions<'a> {
+    pub model: &'a str,
+    /// Host that has the model.
+    pub from: &'a str,
+    /// Host to copy it to.
+    pub to: &'a str,
+    pub json_output: bool,
+}
+
+/// What a copy did, for `--json`.
+#[derive(Debug, Serialize)]
+struct Copied<'a> {
+    model: &'a str,
+    from: &'a str,
+    to: &'a str,
+    /// The manifest digest both hosts now have.
+    digest: Option<String>,
+    /// Whether the target had to pull it, rather than having it already.
+    pulled: bool,
+}
+
+/// Pull `opts.model` onto the `to` host unless it already has the source's
+/// version, then check that both hosts have the same digest.
+pub async fn copy_model(config: &GuardianConfig, opts: CopyModelOptions<'_>) -> Result<()> {
+    let (from, to) = (host(config, opts.from)?, host(config, opts.to)?);
+    if from.name == to.name {
+        anyhow::bail!("--from and --to are both {}", from.name);
+    }
+    let model = opts.model;
+    let client = llm::client(config, 60_000)?;
+    let source = client.list_models(from).await?;
+    let Some(digest) = find_model(&source, model).map(|m| m.digest.clone()) else {
+        anyhow::bail!("{} has no model {model}", from.name);
+    };
+
+    let target = client.list_models(to).await?;
+    let present =
+        find_model(&target, model).is_some_and(|m| digest.is_some() && m.digest == digest);
+    if !present {
+        eprintln!("Pulling {model} onto {}...", to.name);
+        pull_with_progress(config, to, model, None).await?;
+        let target = client.list_models(to).await?;
+        let Some(copied) = find_model(&target, model) else {
+            anyhow::bail!("{model} didn't show up on {} after the pull", to.name);
+        };
+        match (&digest, &copied.digest) {
+            (Some(want), Some(got)) if want != got => anyhow::bail!(
+                "{model} on {} has digest {}, not {} as on {}; the registry's {model} has changed since {} pulled it",
+                to.name,
+                short(got),
+                short(want),
+                from.name,
+                from.name
+            ),
+            (Some(_), Some(_)) => {}
+            _ => tracing::warn!("A host didn't report {model}'s digest; the copy isn't verified"),
+        }
+    }
+
+    if opts.json_output {
+        let copied = Copied {
+            model,
+            from: &from.name,
+            to: &to.name,
+            digest,
+            pulled: !present,
+        };
+        report!("{}", serde_json::to_string_pretty(&copied)?);
+    } else {
+        let digest = digest.as_deref().map(short).unwrap_or("unknown");
+        let verb = if present { "was already" } else { "is now" };
+        report!("{model} {verb} on {} (digest {digest})", to.name);
+    }
+    Ok(())
+}
+
+/// The enabled host called `name`.
+fn host<'a>(config: &'a GuardianConfig, name: &str) -> Result<&'a OllamaHost> {
+    config
+        .enabled_hosts()
+        .into_iter()
+        .find(|h| h.name == name)
+        .with_context(|| format!("Host '{name}' not found or disabled"))
+}
+
+/// The first 12 hex digits of `digest`, as `ollama list` shows it.
+fn short(digest: &str) -> &str {
+    let hex = digest.strip_prefix("sha256:").unwrap_or(digest);
+    hex.get(..12).unwrap_or(hex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_digest() {
+        let digest = "sha256:a8b0c51577010a279d933d14c2a8ab4b268079d44c5c8830c0a93900f1827c67";
+        assert_eq!(short(digest), "a8b0c5157701");
+        assert_eq!(short("abc"), "abc");
+    }
+}
@@ -0,0 +1,194 @@
+// The guardian serve dashboard: host health, each project's latest run,
+// score trends, and the violations of a run, all read from the daemon's
+// JSON endpoints and refreshed every half minute.
+"use strict";
+
+const REFRESH_MS = 30000;
+const SVG = "http://www.w3.org/2000/svg";
+const TOKEN_KEY = "guardian-serve-token";
+let selectedProject = null;
+let selectedRun = null;
+
+function cell(text, className) {
+  const td = document.createElement("td");
+  td.textContent = text ?? "";
+  if (className) td.className = className;
+  return td;
+}
+
+function row(cells, onclick) {
+  const tr = document.createElement("tr");
+  tr.append(...cells.map(c => (c instanceof Node ? c : cell(c))));
+  if (onclick) {
+    tr.classList.add("link");
+    tr.onclick = onclick;
+  }
+  return tr;
+}
+
+function fill(table, rows, empty) {
+  const body = document.querySelector(`#${table} tbody`);
+  if (rows.length === 0) {
+    const td = cell(empty, "muted");
+    td.colSpan = document.querySelectorAll(`#${table} th`).length;
+    rows = [row([td])];
+  }
+  body.replaceChildren(...rows);
+}
+
+function when(secs) {
+  return new Date(secs * 1000).toLocaleString();
+}
+
+function runCells(run) {
+  return [
+    run.command,
+    cell(run.outcome, run.outcome),
+    run.errors,
+    run.warnings,
+    run.score.toFixed(0),
+  ];
+}
+
+async function getJson(url) {
+  const token = sessionStorage.getItem(TOKEN_KEY);
+  const headers = token ? { Authorization: `Bearer ${token}` } : {};
+  const response = await fetch(url, { headers });
+  if (response.status === 401) {
+    const entered = prompt("The dashboard needs the [serve] token");
+    if (entered) {
+      sessionStorage.setItem(TOKEN_KEY, entered);
+      return getJson(url);
+    }
+  }
+  const body = await response.json();
+  if (!response.ok) throw new Error(body.error || `${url}: ${response.status}`);
+  return body;
+}
+
+async function loadHosts() {
+  const health = await getJson("/api/health");
+  document.getElementById("status").textContent = health.updated_at
+    ? `Hosts checked ${when(health.updated_at)}`
+    : "Waiting for the first health check";
+  fill("hosts", health.hosts.map(host => row([
+    host.name,
+    cell(host.healthy ? "healthy" : "down", host.healthy ? "healthy" : "down"),
+    `${(host.availability * 100).toFixed(0)}%`,
+    host.avg_latency_ms == null ? "-" : `${host.avg_latency_ms.toFixed(0)} ms`,
+    host.last_error,
+  ])), "No hosts configured");
+}
+
+async function loadProjects() {
+  const latest = new Map();
+  for (const run of await getJson("/api/runs?limit=1000")) {
+    if (!latest.has(run.project)) latest.set(run.project, run);
+  }
+  fill("projects", [...latest.values()].map(run => {
+    const tr = row([run.project, when(run.timestamp), ...runCells(run)],
+      () => selectProject(run.project));
+    tr.dataset.project = run.project;
+    tr.classList.toggle("selected", run.project === selectedProject);
+    return tr;
+  }), "No runs recorded yet");
+}
+
+async function selectProject(project) {
+  selectedProject = project;
+  selectedRun = null;
+  document.getElementById("run").hidden = true;
+  document.querySelectorAll("#projects tbody tr").forEach(tr =>
+    tr.classList.toggle("selected", tr.dataset.project === project));
+  await loadProject();
+}
+
+async function loadProject() {
+  const project = selectedProject;
+  const runs = await getJson(`/api/runs?project=${encodeURIComponent(project)}&limit=100`);
+  document.getElementById("project").hidden = false;
+  document.getElementById("project-name").textContent = project;
+  drawTrend(runs.slice().reverse());
+  fill("runs", runs.map(run => {
+    const tr = row([when(run.timestamp), ...runCells(run), run.model],
+      () => showRun(run.id));
+    tr.dataset.run = run.id;
+    tr.classList.toggle("selected", run.id === selectedRun);
+    return tr;
+  }), "No runs recorded yet");
+}
+
+function svg(tag, attributes, title) {
+  const element = document.createElementNS(SVG, tag);
+  for (const [name, value] of Object.entries(attributes)) element.setAttribute(name, value);
+  if (title) {
+    const tooltip = document.createElementNS(SVG, "title");
+    tooltip.textContent = title;
+    element.append(tooltip);
+  }
+  return element;
+}
+
+// Score (0-100) as a line, errors as bars scaled to the most in any run,
+// oldest run on the left.
+function drawTrend(runs) {
+  const [width, height, pad] = [800, 200, 24];
+  const span = width - 2 * pad;
+  const x = i => pad + (runs.length < 2 ? span / 2 : (i * span) / (runs.length - 1));
+  const y = score => height - pad - (score / 100) * (height - 2 * pad);
+  const maxErrors = Math.max(1, ...runs.map(run => run.errors));
+  const bar = Math.max(2, (span / Math.max(runs.length, 1)) * 0.5);
+
+  const parts = [];
+  for (const score of [0, 50, 100]) {
+    const label = svg("text", { x: 2, y: y(score) + 4 });
+    label.textContent = score;
+    parts.push(svg("line", { class: "grid", x1: pad, x2: width - pad, y1: y(score), y2: y(score) }), label);
+  }
+  runs.forEach((run, i) => {
+    const barHeight = (run.errors / maxErrors) * (height - 2 * pad);
+    parts.push(svg("rect", {
+      class: "errors", x: x(i) - bar / 2, y: height - pad - barHeight, width: bar, height: barHeight,
+    }, `${run.errors} errors, ${when(run.timestamp)}`));
+  });
+  const points = runs.map((run, i) => `${x(i)},${y(run.score)}`).join(" ");
+  parts.push(svg("polyline", { class: "score", points }));
+  runs.forEach((run, i) => {
+    parts.push(svg("circle", { class: "score", cx: x(i), cy: y(run.score), r: 3 },
+      `score ${run.score.toFixed(0)}, ${when(run.timestamp)}`));
+  });
+  document.getElementById("trend").replaceChildren(...parts);
+}
+
+async function showRun(id) {
+  selectedRun = id;
+  const { run, results } = await getJson(`/api/runs/${id}`);
+  document.querySelectorAll("#runs tbody tr").forEach(tr =>
+    tr.classList.toggle("selected", tr.dataset.run === String(id)));
+  document.getElementById("run").hidden = false;
+  document.getElementById("run-title").textContent =
+    `${run.command}, ${when(run.timestamp)}: ${run.outcome}`;
+  fill("violations", results.map(result => row([
+    cell(result.severity, result.severity),
+    result.check,
+    result.file ? (result.line ? `${result.file}:${result.line}` : result.file) : "",
+    result.message,
+    result.fix,
+  ])), "No violations");
+  const response = document.getElementById("response");
+  response.hidden = !run.response;
+  response.textContent = run.response || "";
+  document.getElementById("run").scrollIntoView({ behavior: "smooth" });
+}
+
+async function refresh() {
+  try {
+    await Promise.all([loadHosts(), loadProjects()]);
+    if (selectedProject) await loadProject();
+  } catch (error) {
+    document.getElementById("status").textContent = error.message;
+  }
+}
+
+refresh();
+setInterval(refresh, REFRESH_MS);
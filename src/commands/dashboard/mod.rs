@@ -0,0 +1,138 @@
+//! The web dashboard `serve` offers at `/`: host health, each project's
+//! latest run, score trends from the result history, and the violations of
+//! any run.
+//!
+//! The page, its script and its styles are compiled in, and read the JSON
+//! endpoints below and `/api/health`, so the dashboard needs nothing but
+//! the daemon:
+//!
+//! ```text
+//! GET /api/runs?project=PATH&limit=N  recent runs, newest first
+//! GET /api/runs/{id}                  a run and its failed results
+//! ```
+//!
+//! The page and its assets need no token. When `[serve]` names one, the
+//! run endpoints need it as the job API does, since runs hold reports and
+//! model responses; the page asks for it and keeps it for the session.
+
+use super::api::{authorize, failure};
+use super::output::result_json;
+use crate::config::StorageSettings;
+use crate::storage::History;
+use axum::extract::{Path, Query, State};
+use axum::http::{StatusCode, header};
+use axum::middleware::from_fn_with_state;
+use axum::response::{Html, IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::Deserialize;
+use std::sync::Arc;
+
+const INDEX: &str = include_str!("index.html");
+const SCRIPT: &str = include_str!("dashboard.js");
+const STYLE: &str = include_str!("dashboard.css");
+
+/// Runs `/api/runs` returns unless asked for fewer.
+const DEFAULT_RUNS: usize = 100;
+/// Most runs `/api/runs` returns.
+const MAX_RUNS: usize = 1000;
+
+/// `?project=` and `?limit=` for `/api/runs`.
+#[derive(Debug, Deserialize)]
+struct RunsQuery {
+    project: Option<String>,
+    limit: Option<usize>,
+}
+
+/// The dashboard's page, assets, and run endpoints, the endpoints behind
+/// `token` if there is one.
+pub(super) fn dashboard_routes(storage: &StorageSettings, token: Option<Arc<str>>) -> Router {
+    let script = (
+        [(header::CONTENT_TYPE, "text/javascript; charset=utf-8")],
+        SCRIPT,
+    );
+    let style = ([(header::CONTENT_TYPE, "text/css; charset=utf-8")], STYLE);
+    let mut api = Router::new()
+        .route("/api/runs", get(runs))
+        .route("/api/runs/{id}", get(run))
+        .with_state(Arc::new(storage.clone()));
+    if let Some(token) = token {
+        api = api.layer(from_fn_with_state(token, authorize));
+    }
+    Router::new()
+        .route("/", get(|| async { Html(INDEX) }))
+        .route("/dashboard.js", get(|| async { script }))
+        .route("/dashboard.css", get(|| async { style }))
+        .merge(api)
+}
+
+/// `GET /api/runs` - the newest runs of one project, or of every project.
+async fn runs(
+    State(storage): State<Arc<StorageSettings>>,
+    Query(query): Query<RunsQuery>,
+) -> Response {
+    let limit = query.limit.unwrap_or(DEFAULT_RUNS).min(MAX_RUNS);
+    let runs = History::open(&storage).and_then(|history| match &query.project {
+        Some(project) => history.runs(std::path::Path::new(project), limit),
+        None => history.recent_runs(limit),
+    });
+    match runs {
+        Ok(runs) => Json(runs).into_response(),
+        Err(e) => failure(StatusCode::INTERNAL_SERVER_ERROR, format!("{e:#}")),
+    }
+}
+
+/// `GET /api/runs/{id}` - a run and the results it failed.
+async fn run(State(storage): State<Arc<StorageSettings>>, Path(id): Path<i64>) -> Response {
+    let found = History::open(&storage).and_then(|history| {
+        let Some(run) = history.run(id)? else {
+            return Ok(None);
+        };
+        Ok(Some((run, history.results(id)?)))
+    });
+    match found {
+        Ok(Some((run, results))) => {
+            let failed: Vec<_> = results
+                .iter()
+                .filter(|r| !r.passed)
+                .map(result_json)
+                .collect();
+            Json(serde_json::json!({"run": run, "results": failed})).into_response()
+        }
+        Ok(None) => failure(StatusCode::NOT_FOUND, format!("No run {id}")),
+        Err(e) => failure(StatusCode::INTERNAL_SERVER_ERROR, format!("{e:#}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_run_endpoints_need_the_token() {
+        let storage = StorageSettings {
+            enabled: false,
+            ..StorageSettings::default()
+        };
+        let app = dashboard_routes(&storage, Some("secret".into()));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let client = reqwest::Client::new();
+        let get = |path: &str, token: &str| {
+            client
+                .get(format!("{base}{path}"))
+                .bearer_auth(token)
+                .send()
+        };
+        assert_eq!(get("/", "").await.unwrap().status(), 200);
+        assert_eq!(get("/dashboard.js", "").await.unwrap().status(), 200);
+        let status = get("/api/runs", "wrong").await.unwrap().status();
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let status = get("/api/runs/1", "").await.unwrap().status();
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let status = get("/api/runs", "secret").await.unwrap().status();
+        assert_ne!(status, StatusCode::UNAUTHORIZED);
+    }
+}
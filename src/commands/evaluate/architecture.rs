@@ -0,0 +1,83 @@
+//! The dependency graph section of the evaluation prompt.
+//!
+//! When the `architecture` check ran, the model sees the whole graph and
+//! the declared layers as well as the violations, so it can judge the
+//! structure: cycles, modules that depend on too much, and layers that
+//! should be declared but aren't.
+
+use crate::checks::CheckResult;
+use crate::checks::architecture::Graph;
+use crate::checks::walk::Scan;
+use crate::config::CheckSettings;
+use std::path::Path;
+
+/// The graph of the project at `project_dir` and the `[check.architecture]`
+/// rules it is held to, if the `architecture` check is among `results`.
+pub(super) fn section(
+    results: &[CheckResult],
+    project_dir: &Path,
+    settings: &CheckSettings,
+) -> Option<String> {
+    if !results.iter().any(|r| r.check_name == "architecture") {
+        return None;
+    }
+    let graph = Graph::extract(project_dir, &Scan::from(settings));
+    let rules = &settings.architecture;
+    let mut text = String::from("## Dependency Graph\n\n");
+    if rules.layers.is_empty() {
+        text.push_str("No layers are declared.\n");
+    } else {
+        let layers: Vec<String> = rules.layers.iter().map(|l| l.join(", ")).collect();
+        text.push_str(&format!(
+            "Declared layers, top down: {}\n",
+            layers.join("; ")
+        ));
+    }
+    for (name, allowed) in &rules.allowed {
+        text.push_str(&format!(
+            "{name} may only depend on: {}\n",
+            allowed.join(", ")
+        ));
+    }
+    text.push_str(&format!("\n{}\n", graph.describe()));
+    text.push_str(
+        "Assess this architecture in your review: whether the dependencies \
+         keep the layers apart, any cycles, and crates or modules that depend \
+         on too much.\n\n",
+    );
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checks::Severity;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_section_describes_graph_and_rules() {
+        let dir = TempDir::new().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir(&src).unwrap();
+        fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        fs::write(src.join("checks.rs"), "use crate::config;\n").unwrap();
+        fs::write(src.join("config.rs"), "").unwrap();
+        let settings = CheckSettings {
+            architecture: toml::from_str(
+                "layers = [\"checks\", \"config\"]\nallowed = { config = [] }",
+            )
+            .unwrap(),
+            ..CheckSettings::default()
+        };
+
+        let loc = [CheckResult::fail("loc-limits", Severity::Warning, "x")];
+        assert_eq!(section(&loc, dir.path(), &settings), None);
+
+        let results = [CheckResult::pass("architecture", "ok")];
+        let text = section(&results, dir.path(), &settings).unwrap();
+        assert!(text.contains("Declared layers, top down: checks; config\n"));
+        assert!(text.contains("config may only depend on: \n"));
+        assert!(text.contains("checks -> config\nconfig -> (nothing)\n"));
+    }
+}
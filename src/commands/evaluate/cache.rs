@@ -0,0 +1,95 @@
+//! Evaluations cached by fingerprint, so unchanged results skip the model.
+//!
+//! Agents run `evaluate` over and over on the same tree. The fingerprint
+//! covers the model, the system prompt, and the evaluation prompt, which
+//! holds the check results, project rules, and the prompt template, so any
+//! change to those misses the cache. `--force` skips it.
+
+use crate::config::{cache_stats, sha256_hex, state};
+use crate::ollama::GenerateResponse;
+use serde::{Deserialize, Serialize};
+
+/// State file holding recent evaluations, oldest first.
+const CACHE_FILE: &str = "evaluations.json";
+
+/// Evaluations kept; the oldest are dropped beyond this.
+const MAX_ENTRIES: usize = 100;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Entry {
+    fingerprint: String,
+    /// The verdict as the model's JSON.
+    value: serde_json::Value,
+    /// The model's reply.
+    response: String,
+}
+
+/// Fingerprint of asking `model`, with `system`, for an evaluation of `prompt`.
+pub(super) fn fingerprint(model: &str, system: Option<&str>, prompt: &str) -> String {
+    let key = serde_json::json!([model, system, prompt]);
+    sha256_hex(key.to_string().as_bytes())
+}
+
+/// The cached verdict and response for `fingerprint`, if any.
+///
+/// The response has no timings or token counts, since no model ran.
+pub(super) fn lookup(fingerprint: &str) -> Option<(serde_json::Value, GenerateResponse)> {
+    let entries: Vec<Entry> = state::path(CACHE_FILE)
+        .and_then(|path| state::read(&path))
+        .unwrap_or_default();
+    let entry = entries.into_iter().find(|e| e.fingerprint == fingerprint);
+    cache_stats::record("evaluations", entry.is_some());
+    let entry = entry?;
+    let response = GenerateResponse {
+        response: entry.response,
+        done: true,
+        total_duration: None,
+        eval_count: None,
+        prompt_eval_count: None,
+    };
+    Some((entry.value, response))
+}
+
+/// Cache the verdict `value` and `response` under `fingerprint`.
+pub(super) fn store(fingerprint: &str, value: &serde_json::Value, response: &GenerateResponse) {
+    let Some(path) = state::path(CACHE_FILE) else {
+        return;
+    };
+    let mut entries: Vec<Entry> = state::read(&path).unwrap_or_default();
+    entries.retain(|e| e.fingerprint != fingerprint);
+    entries.push(Entry {
+        fingerprint: fingerprint.to_string(),
+        value: value.clone(),
+        response: response.response.clone(),
+    });
+    let excess = entries.len().saturating_sub(MAX_ENTRIES);
+    entries.drain(..excess);
+    state::write(&path, &entries);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint_covers_model_system_and_prompt() {
+        let base = fingerprint("qwen2.5-coder:14b", None, "## Errors\n- a\n");
+        assert_eq!(
+            base,
+            fingerprint("qwen2.5-coder:14b", None, "## Errors\n- a\n")
+        );
+        assert_eq!(base.len(), 64);
+        assert_ne!(
+            base,
+            fingerprint("qwen2.5-coder:32b", None, "## Errors\n- a\n")
+        );
+        assert_ne!(
+            base,
+            fingerprint("qwen2.5-coder:14b", Some(""), "## Errors\n- a\n")
+        );
+        assert_ne!(
+            base,
+            fingerprint("qwen2.5-coder:14b", None, "## Errors\n- b\n")
+        );
+    }
+}
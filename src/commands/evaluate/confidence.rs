@@ -0,0 +1,112 @@
+//! How sure the model is: low-confidence findings don't block.
+//!
+//! The verdict and each blocking finding come with the model's confidence.
+//! A 3B model's 0.3-confidence hunch shouldn't fail a pipeline, so blocking
+//! findings below the rubric's `min_confidence` become advice, and a
+//! failing verdict below it doesn't fail the run. With `low_confidence =
+//! "second-opinion"`, such a verdict is first put to the model routed to
+//! `second-opinion`, whose verdict stands if it is sure enough.
+
+use super::review::{Reviewed, VERDICT_RETRIES};
+use crate::commands::grounding;
+use crate::commands::llm::{Routed, cancellable, check_context_window, routed};
+use crate::commands::verdict::{Assessment, Verdict};
+use crate::config::{GuardianConfig, LowConfidence};
+use anyhow::{Context, Result};
+use std::path::Path;
+
+/// `reviewed` with its unsure blocking findings demoted, or a second
+/// opinion in its place when the rubric asks for one on an unsure verdict.
+pub(super) async fn settle<'c>(
+    config: &'c GuardianConfig,
+    project_dir: &Path,
+    mut reviewed: Reviewed<'c>,
+) -> Reviewed<'c> {
+    let min_confidence = config.rubric.min_confidence;
+    let (_, model, prompt, _, assessment) = &mut reviewed;
+    demote(assessment, model, min_confidence);
+    if assessment.verdict == Verdict::Approve || assessment.confidence >= min_confidence {
+        return reviewed;
+    }
+    eprintln!(
+        "{model} is {:.2} sure of {}, below the rubric's {min_confidence}",
+        assessment.confidence,
+        assessment.verdict.name()
+    );
+    if config.rubric.low_confidence == LowConfidence::SecondOpinion {
+        match second_opinion(config, project_dir, model, prompt).await {
+            Ok(Some(mut second)) => {
+                let (_, second_model, _, _, opinion) = &mut second;
+                demote(opinion, second_model, min_confidence);
+                if opinion.verdict == Verdict::Approve || opinion.confidence >= min_confidence {
+                    eprintln!("Going by {second_model}'s second opinion\n");
+                    return second;
+                }
+                eprintln!(
+                    "{second_model} is only {:.2} sure of its {} too",
+                    opinion.confidence,
+                    opinion.verdict.name()
+                );
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Couldn't get a second opinion: {e:#}"),
+        }
+    }
+    eprintln!("Not failing the run on it.\n");
+    reviewed
+}
+
+/// Move the blocking findings `model` is unsure of to the advice.
+fn demote(assessment: &mut Assessment, model: &str, min_confidence: f64) {
+    let moved = assessment.demote_unsure(min_confidence);
+    if moved > 0 {
+        eprintln!(
+            "{model} is less than {min_confidence} sure of {moved} blocking finding(s); \
+             they are advice instead\n"
+        );
+    }
+}
+
+/// The review of the model routed to `second-opinion` for the same
+/// `prompt`, or `None` when that is `first_model` again.
+async fn second_opinion<'c>(
+    config: &'c GuardianConfig,
+    project_dir: &Path,
+    first_model: &str,
+    prompt: &str,
+) -> Result<Option<Reviewed<'c>>> {
+    let Routed {
+        client,
+        mut breaker,
+        host,
+        model: model_name,
+        system,
+    } = routed(config, "second-opinion", 180_000, None, None).await?;
+    if model_name == first_model {
+        tracing::warn!("The second-opinion task uses {model_name} too; route it to another model");
+        return Ok(None);
+    }
+
+    let system = system.as_deref();
+    check_context_window(config, &client, host, &model_name, system, prompt).await?;
+    eprintln!("Asking {model_name} for a second opinion...\n");
+    let format = Assessment::format(&config.rubric.sections);
+    let response = cancellable(
+        &client,
+        client.generate_json(host, &model_name, prompt, &format, VERDICT_RETRIES),
+    )
+    .await;
+    breaker.record(&host.name, response.is_ok());
+    breaker.save();
+    let (value, response) = response?;
+    let mut assessment = serde_json::from_value(value)
+        .with_context(|| format!("{model_name} returned a malformed verdict"))?;
+    grounding::guard_assessment(&mut assessment, project_dir, config);
+    Ok(Some((
+        host,
+        model_name,
+        prompt.to_string(),
+        response,
+        assessment,
+    )))
+}
@@ -0,0 +1,305 @@
+//! Evaluate command: run checks, then have an LLM review the failures.
+//!
+//! The model answers with a structured verdict (see [`super::verdict`]);
+//! the run fails when the checks' policy does or the verdict isn't
+//! `APPROVE`, unless the model isn't sure of it (see [`confidence`]).
+//!
+//! With `--suggest-patches`, the model also writes a diff for each error
+//! (see [`super::patches`]).
+//!
+//! With `--staged`, only the staged change is evaluated (see [`staged`]).
+//!
+//! With `--triage`, a small model first sets aside the trivial violations,
+//! and only the rest are evaluated (see [`triage`]).
+//!
+//! An evaluation of the same results by the same model is reused unless
+//! `--force` is given (see [`cache`]).
+//!
+//! With `--interactive`, the user can then ask the model about its review
+//! (see [`super::followup`]).
+//!
+//! With `--offline`, or when no host is reachable, the failures get a
+//! rule-based summary instead, so gating works without an LLM.
+//!
+//! On Ctrl-C, no further model requests are made; the results gathered so
+//! far are reported, marked partial (see [`super::interrupt`]).
+
+mod architecture;
+mod cache;
+mod confidence;
+mod prompt;
+mod review;
+mod rubric;
+mod staged;
+mod triage;
+
+use super::exit::{Exit, FailPolicy, INTERRUPTED, VIOLATIONS};
+use super::output::{self, report};
+use super::{followup, history, interrupt, notify, offline, patches, readability, saved};
+use crate::checks::interrupt::interrupted;
+use crate::checks::{
+    CheckResult, blame, configured_thresholds, run_selected_checks, select_checks,
+};
+use crate::config::GuardianConfig;
+use crate::storage::LlmResponse;
+use anyhow::Result;
+use staged::Staged;
+use std::path::Path;
+
+/// Options for the evaluate command.
+pub struct EvaluateOptions<'a> {
+    pub path: Option<&'a Path>,
+    pub model: Option<&'a str>,
+    pub host: Option<&'a str>,
+    /// Only run these checks, comma-separated.
+    pub only: Option<&'a str>,
+    /// Don't run these checks, comma-separated.
+    pub skip: Option<&'a str>,
+    /// Also write an HTML report including the evaluation.
+    pub html_report: Option<&'a Path>,
+    pub json_output: bool,
+    /// Decides the exit status from the check results.
+    pub policy: FailPolicy,
+    /// Attribute failures with git blame.
+    pub blame: bool,
+    /// Summarize the failures with rules instead of asking an LLM.
+    pub offline: bool,
+    /// Have the model suggest a patch for each error.
+    pub suggest_patches: bool,
+    /// Have the `triage` model set trivial violations aside first.
+    pub triage: bool,
+    /// Take follow-up questions about the review afterwards.
+    pub interactive: bool,
+    /// Ask the model even if it already evaluated the same results.
+    pub force: bool,
+    /// Lines of source quoted on each side of a violation; by default a
+    /// few, unless there are many violations.
+    pub snippet_lines: Option<usize>,
+    /// Results saved by `check --save`, evaluated instead of running the
+    /// checks.
+    pub from: Option<&'a Path>,
+    /// Evaluate only the staged change, with its diff.
+    pub staged: bool,
+}
+
+/// Run checks and have LLM evaluate results to enforce process.
+pub async fn evaluate(config: &GuardianConfig, opts: EvaluateOptions<'_>) -> Result<()> {
+    if opts.interactive {
+        followup::require_terminal(opts.json_output)?;
+    }
+    let (saved_project, saved_results) = match opts.from {
+        Some(path) => {
+            let saved = saved::load(path)?;
+            eprintln!(
+                "Loaded {} saved results from {}\n",
+                saved.results.len(),
+                path.display()
+            );
+            (saved.project, Some(saved.results))
+        }
+        None => (None, None),
+    };
+    let project_dir = opts
+        .path
+        .or(saved_project.as_deref())
+        .unwrap_or(Path::new("."));
+
+    let staged = if opts.staged {
+        let staged = Staged::read(project_dir)?;
+        if staged.files.is_empty() {
+            report!("Nothing is staged.");
+            return Ok(());
+        }
+        eprintln!("Evaluating the {} staged file(s)", staged.files.len());
+        Some(staged)
+    } else {
+        None
+    };
+    let mut results = match saved_results {
+        Some(results) => results,
+        None => {
+            eprintln!("Running checks on {}...\n", project_dir.display());
+            let check_config = configured_thresholds(&config.check);
+            let selected = select_checks(
+                opts.only,
+                opts.skip,
+                config.check.only.as_deref(),
+                &config.checks,
+            )?;
+            let mut results = run_selected_checks(project_dir, &check_config, &selected);
+            let changed = staged.as_ref().map(|staged| &staged.files);
+            results.extend(readability::check(config, project_dir, &selected, changed).await);
+            results
+        }
+    };
+    if let Some(staged) = &staged {
+        results = staged.scope(results, project_dir);
+    }
+    if opts.blame {
+        blame::attribute(&mut results);
+    }
+    if interrupted() {
+        return report_partial(results, opts.json_output);
+    }
+
+    let failures: Vec<_> = results.iter().filter(|r| !r.passed).collect();
+    let passes: Vec<_> = results.iter().filter(|r| r.passed).collect();
+
+    eprintln!(
+        "Checks complete: {} passed, {} failed\n",
+        passes.len(),
+        failures.len()
+    );
+
+    // A staged change gets a review even when its checks pass.
+    if failures.is_empty() && (staged.is_none() || opts.offline) {
+        report!("All checks passed. No LLM evaluation needed.");
+        if let Some(path) = opts.html_report {
+            output::write_html_report(path, &results, None)?;
+        }
+        let verdict = opts.policy.enforce(&results);
+        let outcome = history::outcome(&verdict);
+        history::record_run(
+            &config.storage,
+            project_dir,
+            "evaluate",
+            &results,
+            outcome,
+            None,
+        );
+        let passed = verdict.is_ok();
+        notify::evaluate_verdict(&config.notify, project_dir, &results, None, passed).await;
+        return verdict;
+    }
+
+    let judged = if opts.triage && !opts.offline {
+        triage::triage(config, &results).await
+    } else {
+        None
+    };
+    if interrupted() {
+        return report_partial(results, opts.json_output);
+    }
+    let sent = judged.as_deref().unwrap_or(&results);
+    let violations = sent.iter().filter(|r| !r.passed).count();
+    let reviewed = if opts.offline {
+        eprintln!(
+            "Offline: summarizing {} violations without an LLM...\n",
+            failures.len()
+        );
+        None
+    } else if violations == 0 && staged.is_none() {
+        eprintln!("Every violation is trivial; summarizing them without the evaluation model...\n");
+        None
+    } else {
+        let diff = staged.as_ref().map(|s| s.diff.as_str());
+        if diff.is_some() {
+            eprintln!(
+                "Sending the staged change and {violations} violations to LLM for evaluation...\n"
+            );
+        } else {
+            eprintln!("Sending {violations} violations to LLM for evaluation...\n");
+        }
+        match review::review(config, &opts, sent, project_dir, diff).await? {
+            Some(reviewed) if !interrupted() => {
+                Some(confidence::settle(config, project_dir, reviewed).await)
+            }
+            reviewed => reviewed,
+        }
+    };
+    // Interrupted during the review: report it, but ask nothing more.
+    let stopped = interrupted();
+    if stopped {
+        interrupt::mark_partial(&mut results);
+    }
+
+    let text;
+    let evaluation = match &reviewed {
+        Some((host, model, _, response, assessment)) => {
+            let mut suggested = Vec::new();
+            if opts.suggest_patches && !stopped {
+                suggested = patches::suggest(config, host, model, &results)
+                    .await
+                    .unwrap_or_else(|e| {
+                        tracing::warn!("Couldn't suggest patches: {e:#}");
+                        Vec::new()
+                    });
+            }
+            output::evaluate_response(
+                host,
+                model,
+                &results,
+                response,
+                Some(assessment),
+                &suggested,
+                opts.json_output,
+            )?;
+            text = assessment.text();
+            output::Evaluation {
+                judge: Some((&host.name, model)),
+                text: &text,
+            }
+        }
+        None => {
+            text = offline::rule_based_summary(&results);
+            output::offline_summary(&results, &text, opts.json_output)?;
+            output::Evaluation {
+                judge: None,
+                text: &text,
+            }
+        }
+    };
+    if let Some(path) = opts.html_report {
+        output::write_html_report(path, &results, Some(&evaluation))?;
+    }
+    let follow_up = opts.interactive && !stopped;
+    if let Some((host, model, prompt, response, _)) = reviewed.as_ref().filter(|_| follow_up) {
+        let reply = &response.response;
+        followup::follow_up(config, "evaluate", host, model, prompt, reply).await?;
+    }
+
+    if stopped {
+        return Err(Exit(INTERRUPTED).into());
+    }
+    // The model can fail a run the checks pass, but not pass one they fail.
+    let mut verdict = opts.policy.enforce(&results);
+    let approved = reviewed
+        .as_ref()
+        .is_none_or(|(.., assessment)| !assessment.blocks(config.rubric.min_confidence));
+    if verdict.is_ok() && !approved {
+        verdict = Err(Exit(VIOLATIONS).into());
+    }
+    let llm = evaluation.judge.map(|(host, model)| LlmResponse {
+        host,
+        model,
+        text: evaluation.text,
+    });
+    let outcome = history::outcome(&verdict);
+    history::record_run(
+        &config.storage,
+        project_dir,
+        "evaluate",
+        &results,
+        outcome,
+        llm,
+    );
+    let passed = verdict.is_ok();
+    notify::evaluate_verdict(
+        &config.notify,
+        project_dir,
+        &results,
+        Some(&evaluation),
+        passed,
+    )
+    .await;
+    verdict
+}
+
+/// Report `results`, cut short by Ctrl-C, with the rule-based summary, and
+/// exit with [`INTERRUPTED`].
+fn report_partial(mut results: Vec<CheckResult>, json_output: bool) -> Result<()> {
+    interrupt::mark_partial(&mut results);
+    let text = offline::rule_based_summary(&results);
+    output::offline_summary(&results, &text, json_output)?;
+    Err(Exit(INTERRUPTED).into())
+}
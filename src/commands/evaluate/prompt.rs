@@ -0,0 +1,311 @@
+//! The evaluation prompt, assembled within a token budget.
+
+use super::{rubric, staged};
+use crate::checks::{CheckResult, Severity};
+use crate::config::RubricSettings;
+use crate::ollama::{Assembled, Priority, Prompt};
+use std::path::Path;
+
+/// Source lines quoted on each side of a violation, unless overridden.
+pub(super) const DEFAULT_SNIPPET_LINES: usize = 5;
+
+/// Most located violations that get snippets unless asked for explicitly.
+const MAX_SNIPPETS: usize = 20;
+
+/// What is evaluated besides the check results.
+pub(super) struct Scope<'a> {
+    pub project_dir: &'a Path,
+    /// The project rules file.
+    pub rules: Option<&'a str>,
+    /// The dependency graph section, when the `architecture` check ran.
+    pub graph: Option<&'a str>,
+    /// The staged diff, for `--staged`.
+    pub staged: Option<&'a str>,
+}
+
+impl<'a> Scope<'a> {
+    /// The whole project at `project_dir`, without rules or a graph.
+    pub(super) fn new(project_dir: &'a Path) -> Self {
+        Self {
+            project_dir,
+            rules: None,
+            graph: None,
+            staged: None,
+        }
+    }
+}
+
+/// The evaluation prompt, within `budget` tokens: errors first, then
+/// warnings, then the staged diff, then context such as the project rules,
+/// the dependency graph, and passing checks. The task at the end applies
+/// the `rubric`, and asks about the staged change if there is one.
+///
+/// Each violation with a file and line is followed by `snippet_lines` of
+/// source on each side. Snippets are context, so they are the first thing
+/// dropped after the passing checks.
+pub(super) fn build_evaluation_prompt(
+    results: &[CheckResult],
+    scope: &Scope<'_>,
+    rubric: &RubricSettings,
+    snippet_lines: usize,
+    budget: Option<u64>,
+) -> Assembled {
+    let mut prompt = Prompt::new();
+    prompt.push(
+        Priority::Required,
+        format!(
+            "You are a code quality guardian enforcing development process rules.\n\n\
+             ## Project\nDirectory: {}\n\n",
+            scope.project_dir.display()
+        ),
+    );
+    if let Some(rules) = scope.rules {
+        prompt.push_item(Priority::Context, "project rules file", rules);
+    }
+    if let Some(graph) = scope.graph {
+        prompt.push_item(Priority::Context, "dependency graph", graph);
+    }
+
+    let failed = results.iter().filter(|r| !r.passed);
+    let (errors, warnings): (Vec<_>, Vec<_>) = failed.partition(|r| r.severity == Severity::Error);
+    for (priority, heading, kind, group) in [
+        (Priority::Error, "## Errors", "error", errors),
+        (Priority::Warning, "## Warnings", "warning", warnings),
+    ] {
+        if !group.is_empty() {
+            prompt.push(priority, format!("{heading}\n\n"));
+        }
+        for result in group {
+            prompt.push_item(priority, kind, failure_text(result));
+            let snippet = (snippet_lines > 0).then(|| snippet(result, snippet_lines));
+            if let Some(snippet) = snippet.flatten() {
+                prompt.push_item(Priority::Context, "source snippet", snippet);
+            }
+        }
+    }
+    if let Some(diff) = scope.staged {
+        prompt.push(Priority::Warning, "## Staged Change\n\n");
+        for piece in staged::file_diffs(diff) {
+            prompt.push_item(Priority::Warning, "staged file diff", piece);
+        }
+    }
+    let passed: Vec<_> = results.iter().filter(|r| r.passed).collect();
+    if !passed.is_empty() {
+        prompt.push(Priority::Context, "## Passed\n\n");
+    }
+    for result in passed {
+        prompt.push_item(
+            Priority::Context,
+            "passing check",
+            format!("- [{}] {}\n", result.check_name, result.message),
+        );
+    }
+
+    let task = if scope.staged.is_some() {
+        "Judge whether the staged change above respects the process rules, \
+         given the errors and warnings in the files it touches, and reply with \
+         a JSON object:\n"
+    } else {
+        "Analyze the errors and warnings above and reply with a JSON object:\n"
+    };
+    prompt.push(
+        Priority::Required,
+        format!(
+            "\n## Your Task\n\n{task}\
+             - \"verdict\": \"APPROVE\" if the code can go ahead as it is, \"REVISE\" if \
+             it needs fixes first, or \"REJECT\" if the approach itself is wrong\n\
+             - \"confidence\": how sure you are of the verdict, from 0 to 1\n\
+             - \"blocking\": the violations that must be fixed first, most critical \
+             first, each an object with \"issue\" (why it violates good \
+             architecture/process and how to fix it) and \"confidence\" (from 0 to 1, \
+             how sure you are that it must block)\n\
+             - \"advice\": other specific, actionable improvements\n\n"
+        ),
+    );
+    prompt.push(Priority::Required, rubric::instructions(rubric));
+    prompt.assemble(budget)
+}
+
+/// Lines of source to quote around each violation: `requested`, or by
+/// default [`DEFAULT_SNIPPET_LINES`] unless there are too many violations
+/// with a location to quote them all.
+pub(super) fn snippet_lines(results: &[CheckResult], requested: Option<usize>) -> usize {
+    let located = results
+        .iter()
+        .filter(|r| !r.passed && r.file.is_some() && r.line.is_some())
+        .count();
+    match requested {
+        Some(lines) => lines,
+        None if located <= MAX_SNIPPETS => DEFAULT_SNIPPET_LINES,
+        None => {
+            eprintln!(
+                "Leaving source snippets out for {located} violations; \
+                 --snippet-lines N adds them\n"
+            );
+            0
+        }
+    }
+}
+
+/// The source within `radius` lines of `result`'s location, the line itself
+/// marked, or `None` without a location or a readable file.
+fn snippet(result: &CheckResult, radius: usize) -> Option<String> {
+    let (file, line) = (result.file.as_deref()?, result.line?);
+    let content = std::fs::read_to_string(file).ok()?;
+    let lines: Vec<&str> = content.lines().collect();
+    let center = line.checked_sub(1).filter(|&i| i < lines.len())?;
+    let start = center.saturating_sub(radius);
+    let end = (center + radius + 1).min(lines.len());
+    let width = end.to_string().len();
+    let mut text = String::from("  Source:\n");
+    for (number, source) in (start + 1..).zip(&lines[start..end]) {
+        let marker = if number == line { '>' } else { ' ' };
+        text.push_str(&format!("  {marker} {number:>width$} | {source}\n"));
+    }
+    Some(text)
+}
+
+/// One failed result, with where it is, its fix, and who last changed it.
+fn failure_text(result: &CheckResult) -> String {
+    let mut text = format!("- [{}] {}\n", result.check_name, result.message);
+    if let Some(file) = &result.file {
+        text.push_str(&format!("  File: {file}\n"));
+    }
+    if let Some(line) = result.line {
+        text.push_str(&format!("  Line: {line}\n"));
+    }
+    if let Some(fix) = &result.fix {
+        text.push_str(&format!("  Suggested fix: {fix}\n"));
+    }
+    if let Some(blame) = &result.blame {
+        text.push_str(&format!(
+            "  Last changed by: {} in {:.7} ({})\n",
+            blame.author, blame.commit, blame.summary
+        ));
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prompt_keeps_errors_over_passes() {
+        let mut results: Vec<_> = (0..40)
+            .map(|i| CheckResult::pass(&format!("check-{i}"), &"x".repeat(80)))
+            .collect();
+        results.push(CheckResult::fail(
+            "loc-limits",
+            Severity::Warning,
+            "lib.rs: 410 lines",
+        ));
+        results.push(
+            CheckResult::fail("rust-edition", Severity::Error, "Edition 2021")
+                .with_file("Cargo.toml")
+                .with_fix("Set edition = \"2024\""),
+        );
+
+        let full = build_evaluation_prompt(
+            &results,
+            &Scope::new(Path::new("app")),
+            &RubricSettings::default(),
+            0,
+            None,
+        );
+        assert_eq!(full.omitted_summary(), None);
+        let errors = full.text.find("## Errors").unwrap();
+        assert!(errors < full.text.find("## Warnings").unwrap());
+        assert!(
+            full.text
+                .contains("  Suggested fix: Set edition = \"2024\"\n")
+        );
+
+        // Half the budget: passing checks go, the newest first.
+        let budget = crate::ollama::estimate_tokens(&full.text) / 2;
+        let trimmed = build_evaluation_prompt(
+            &results,
+            &Scope::new(Path::new("app")),
+            &RubricSettings::default(),
+            0,
+            Some(budget),
+        );
+        let omitted = trimmed.omitted_summary().unwrap();
+        assert!(omitted.ends_with(" passing check(s)"), "{omitted}");
+        assert!(trimmed.text.contains("[check-0]") && !trimmed.text.contains("[check-39]"));
+        assert!(trimmed.text.contains("[rust-edition] Edition 2021"));
+        assert!(trimmed.text.contains("[loc-limits] lib.rs: 410 lines"));
+        assert!(trimmed.text.contains("## Your Task"));
+    }
+
+    #[test]
+    fn test_snippets_quote_source_around_violations() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("lib.rs");
+        let source: String = (1..=30).map(|i| format!("line {i}\n")).collect();
+        std::fs::write(&file, source).unwrap();
+        let result = CheckResult::fail("clippy-disables", Severity::Error, "Lint suppressed")
+            .with_file(file.to_str().unwrap())
+            .with_line(12);
+
+        let text = snippet(&result, 2).unwrap();
+        assert_eq!(
+            text,
+            "  Source:\n    10 | line 10\n    11 | line 11\n  > 12 | line 12\n    13 | line 13\n    14 | line 14\n"
+        );
+        assert_eq!(snippet(&result.clone().with_line(31), 2), None);
+
+        let results = [result];
+        let prompt = build_evaluation_prompt(
+            &results,
+            &Scope::new(dir.path()),
+            &RubricSettings::default(),
+            10,
+            None,
+        );
+        assert!(
+            prompt
+                .text
+                .contains("- [clippy-disables] Lint suppressed\n")
+        );
+        assert!(prompt.text.contains("  > 12 | line 12\n"));
+        // Just over budget: the snippet goes, the error stays.
+        let budget = crate::ollama::estimate_tokens(&prompt.text) - 5;
+        let trimmed = build_evaluation_prompt(
+            &results,
+            &Scope::new(dir.path()),
+            &RubricSettings::default(),
+            10,
+            Some(budget),
+        );
+        assert_eq!(
+            trimmed.omitted_summary().as_deref(),
+            Some("1 source snippet(s)")
+        );
+
+        let many: Vec<_> = (0..=MAX_SNIPPETS).map(|_| results[0].clone()).collect();
+        assert_eq!(snippet_lines(&many[1..], None), DEFAULT_SNIPPET_LINES);
+        assert_eq!(snippet_lines(&many, None), 0);
+        assert_eq!(snippet_lines(&many, Some(3)), 3);
+    }
+
+    #[test]
+    fn test_staged_change_in_prompt() {
+        let results = [CheckResult::pass("rust-edition", "Edition 2024")];
+        let scope = Scope {
+            staged: Some("diff --git a/a.rs b/a.rs\n+fn a() {}\n"),
+            ..Scope::new(Path::new("app"))
+        };
+        let prompt = build_evaluation_prompt(&results, &scope, &RubricSettings::default(), 0, None);
+        assert!(
+            prompt
+                .text
+                .contains("## Staged Change\n\ndiff --git a/a.rs b/a.rs\n+fn a() {}\n")
+        );
+        assert!(
+            prompt
+                .text
+                .contains("Judge whether the staged change above")
+        );
+    }
+}
@@ -0,0 +1,107 @@
+//! The evaluation model's review of the check results.
+//!
+//! References in the review to files or lines the project doesn't have are
+//! caught before it is shown (see [`grounding`]).
+
+use super::prompt::{self, Scope};
+use super::{EvaluateOptions, architecture, cache};
+use crate::checks::CheckResult;
+use crate::commands::llm::{Routed, cancellable, check_context_window, prompt_budget, routed};
+use crate::commands::verdict::Assessment;
+use crate::commands::{grounding, rules};
+use crate::config::{GuardianConfig, OllamaHost};
+use crate::ollama::GenerateResponse;
+use crate::ollama::select::NoHosts;
+use anyhow::{Context, Result};
+use std::path::Path;
+
+/// Re-prompts after a reply that isn't a well-formed verdict.
+pub(super) const VERDICT_RETRIES: u32 = 1;
+
+/// A model's review: its host, name, the prompt, the response, and the
+/// verdict in it.
+pub(super) type Reviewed<'c> = (&'c OllamaHost, String, String, GenerateResponse, Assessment);
+
+/// Have the routed model review `results`, and the `staged` diff if any:
+/// the host, model, response, and the assessment parsed from it.
+///
+/// Returns `None` when no host is reachable, so `evaluate` can fall back to
+/// the rule-based summary.
+pub(super) async fn review<'c>(
+    config: &'c GuardianConfig,
+    opts: &EvaluateOptions<'_>,
+    results: &[CheckResult],
+    project_dir: &Path,
+    staged: Option<&str>,
+) -> Result<Option<Reviewed<'c>>> {
+    let Routed {
+        client,
+        mut breaker,
+        host,
+        model: model_name,
+        system,
+    } = match routed(config, "evaluate", 180_000, opts.host, opts.model).await {
+        Ok(routed) => routed,
+        Err(e) if e.is::<NoHosts>() => {
+            tracing::warn!("{e}; falling back to a rule-based summary");
+            return Ok(None);
+        }
+        Err(e) => return Err(e),
+    };
+
+    let rules = rules::project_rules(project_dir)?;
+    let system = system.as_deref();
+    let budget = prompt_budget(config, &client, host, &model_name, system).await;
+    let graph = architecture::section(results, project_dir, &config.check);
+    let scope = Scope {
+        rules: rules.as_deref(),
+        graph: graph.as_deref(),
+        staged,
+        ..Scope::new(project_dir)
+    };
+    let prompt = prompt::build_evaluation_prompt(
+        results,
+        &scope,
+        &config.rubric,
+        prompt::snippet_lines(results, opts.snippet_lines),
+        budget,
+    );
+    if let (Some(omitted), Some(budget)) = (prompt.omitted_summary(), budget) {
+        eprintln!("Trimmed the prompt to ~{budget} tokens, leaving out {omitted}\n");
+    }
+    let prompt = prompt.text;
+    let fingerprint = cache::fingerprint(&model_name, system, &prompt);
+    let cached = if opts.force {
+        None
+    } else {
+        cache::lookup(&fingerprint)
+    };
+    let fresh = cached.is_none();
+    let (value, response) = match cached {
+        Some(cached) => {
+            eprintln!(
+                "Reusing {model_name}'s evaluation of these same results (--force re-runs it)\n"
+            );
+            cached
+        }
+        None => {
+            check_context_window(config, &client, host, &model_name, system, &prompt).await?;
+            let format = Assessment::format(&config.rubric.sections);
+            let response = cancellable(
+                &client,
+                client.generate_json(host, &model_name, &prompt, &format, VERDICT_RETRIES),
+            )
+            .await;
+            breaker.record(&host.name, response.is_ok());
+            breaker.save();
+            response?
+        }
+    };
+    let mut assessment = serde_json::from_value(value.clone())
+        .with_context(|| format!("{model_name} returned a malformed verdict"))?;
+    if fresh {
+        cache::store(&fingerprint, &value, &response);
+    }
+    grounding::guard_assessment(&mut assessment, project_dir, config);
+    Ok(Some((host, model_name, prompt, response, assessment)))
+}
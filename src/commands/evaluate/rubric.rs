@@ -0,0 +1,74 @@
+//! The rubric's part of the evaluation prompt.
+
+use crate::checks::Severity;
+use crate::config::RubricSettings;
+
+/// The instructions from `rubric`: tone, what must block, how to weigh
+/// aspects, and the sections to fill in.
+pub(super) fn instructions(rubric: &RubricSettings) -> String {
+    let mut text = match &rubric.tone {
+        Some(tone) => format!("Write in a {tone} tone."),
+        None => "Be concise and direct.".to_string(),
+    };
+    let blocking: Vec<&str> = [
+        (Severity::Error, "error"),
+        (Severity::Warning, "warning"),
+        (Severity::Info, "info finding"),
+    ]
+    .into_iter()
+    .filter(|(severity, _)| *severity == Severity::Error || !rubric.waivable.contains(severity))
+    .map(|(_, name)| name)
+    .collect();
+    let blocking = match blocking.as_slice() {
+        [error] => error.to_string(),
+        [error, other] => format!("{error} and {other}"),
+        names => format!("{}, and {}", names[..2].join(", "), names[2]),
+    };
+    text.push_str(&format!(" Every {blocking} belongs in \"blocking\".\n"));
+
+    let mut weights: Vec<_> = rubric.weights.iter().collect();
+    weights.sort_by_key(|&(aspect, weight)| (std::cmp::Reverse(weight), aspect));
+    if !weights.is_empty() {
+        let weights: Vec<String> = weights.iter().map(|(a, w)| format!("{a} ({w})")).collect();
+        text.push_str(&format!(
+            "Weigh findings by what they affect, heaviest first: {}.\n",
+            weights.join(", ")
+        ));
+    }
+    if !rubric.sections.is_empty() {
+        let titles: Vec<String> = rubric.sections.iter().map(|t| format!("\"{t}\"")).collect();
+        text.push_str(&format!(
+            "Also fill in \"sections\", with a paragraph under each of these titles: {}.\n",
+            titles.join(", ")
+        ));
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rubric_instructions() {
+        let default = instructions(&RubricSettings::default());
+        assert_eq!(
+            default,
+            "Be concise and direct. Every error belongs in \"blocking\".\n"
+        );
+
+        let rubric = RubricSettings {
+            weights: [("style".to_string(), 1), ("tests".to_string(), 2)].into(),
+            tone: Some("mentoring".to_string()),
+            waivable: Vec::new(),
+            sections: vec!["Risks".to_string()],
+            ..RubricSettings::default()
+        };
+        let text = instructions(&rubric);
+        assert!(
+            text.starts_with("Write in a mentoring tone. Every error, warning, and info finding")
+        );
+        assert!(text.contains("heaviest first: tests (2), style (1).\n"));
+        assert!(text.contains("each of these titles: \"Risks\".\n"));
+    }
+}
@@ -0,0 +1,104 @@
+//! `evaluate --staged`: evaluate the change about to be committed.
+//!
+//! Whole-project evaluation is too slow and noisy for a per-commit gate.
+//! With `--staged`, only results about the staged files count, and the
+//! staged diff goes into the prompt, so the model judges this change
+//! rather than the whole tree.
+
+use crate::checks::CheckResult;
+use crate::checks::vcs::Vcs;
+use anyhow::Result;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// Most bytes of staged diff sent to the model.
+const MAX_DIFF: usize = 48_000;
+
+/// The staged change in a project.
+pub(super) struct Staged {
+    /// Files it touches, relative to the project.
+    pub files: BTreeSet<PathBuf>,
+    pub diff: String,
+}
+
+impl Staged {
+    /// The change staged in `project_dir`, or in jj its working-copy change.
+    pub(super) fn read(project_dir: &Path) -> Result<Self> {
+        let vcs = Vcs::detect(project_dir);
+        Ok(Self {
+            files: vcs.staged_files(project_dir)?,
+            diff: vcs.staged_diff(project_dir)?,
+        })
+    }
+
+    /// `results` about the staged files, or about no file in particular.
+    pub(super) fn scope(&self, results: Vec<CheckResult>, project_dir: &Path) -> Vec<CheckResult> {
+        results
+            .into_iter()
+            .filter(|r| match &r.file {
+                Some(file) => Path::new(file)
+                    .strip_prefix(project_dir)
+                    .is_ok_and(|file| self.files.contains(file)),
+                None => true,
+            })
+            .collect()
+    }
+}
+
+/// `diff` split into one piece per file, cut off at [`MAX_DIFF`] bytes.
+pub(super) fn file_diffs(diff: &str) -> Vec<&str> {
+    let mut end = diff.len().min(MAX_DIFF);
+    while !diff.is_char_boundary(end) {
+        end -= 1;
+    }
+    let diff = &diff[..end];
+    let mut starts: Vec<usize> = diff
+        .match_indices("\ndiff --git ")
+        .map(|(at, _)| at + 1)
+        .collect();
+    starts.insert(0, 0);
+    starts.push(diff.len());
+    starts
+        .windows(2)
+        .map(|pair| &diff[pair[0]..pair[1]])
+        .filter(|piece| !piece.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checks::Severity;
+
+    #[test]
+    fn test_scope_to_staged_files() {
+        let staged = Staged {
+            files: BTreeSet::from([PathBuf::from("src/lib.rs")]),
+            diff: String::new(),
+        };
+        let results = vec![
+            CheckResult::fail("loc-limits", Severity::Error, "lib.rs").with_file("app/src/lib.rs"),
+            CheckResult::fail("loc-limits", Severity::Error, "main.rs")
+                .with_file("app/src/main.rs"),
+            CheckResult::pass("rust-edition", "Edition 2024"),
+        ];
+        let kept = staged.scope(results, Path::new("app"));
+        let messages: Vec<&str> = kept.iter().map(|r| r.message.as_str()).collect();
+        assert_eq!(messages, ["lib.rs", "Edition 2024"]);
+    }
+
+    #[test]
+    fn test_file_diffs() {
+        let diff = "diff --git a/a.rs b/a.rs\n+a\ndiff --git a/b.rs b/b.rs\n+b\n";
+        assert_eq!(
+            file_diffs(diff),
+            [
+                "diff --git a/a.rs b/a.rs\n+a\n",
+                "diff --git a/b.rs b/b.rs\n+b\n"
+            ]
+        );
+        assert!(file_diffs("").is_empty());
+        let long = "+".repeat(MAX_DIFF + 10);
+        assert_eq!(file_diffs(&long)[0].len(), MAX_DIFF);
+    }
+}
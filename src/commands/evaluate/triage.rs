@@ -0,0 +1,142 @@
+//! Triage for `evaluate --triage`: a small model sorts the violations first.
+//!
+//! The model routed to `triage` marks each violation trivial (its fix is
+//! mechanical and already known) or as needing judgment. Only the latter go
+//! to the evaluation model, so a busy GPU spends its time where a large
+//! model's judgment matters. Trivial violations still count toward the
+//! policy.
+
+use crate::checks::CheckResult;
+use crate::commands::llm::{Routed, cancellable, check_context_window, routed};
+use crate::config::GuardianConfig;
+use crate::ollama::ResponseFormat;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Triage {
+    /// Numbers of the trivial violations, as listed in the prompt.
+    trivial: Vec<usize>,
+}
+
+/// `results` without the failures the triage model finds trivial.
+///
+/// Returns `None` if triage fails, so that every violation is evaluated.
+pub(super) async fn triage(
+    config: &GuardianConfig,
+    results: &[CheckResult],
+) -> Option<Vec<CheckResult>> {
+    if !config.knows_task("triage") {
+        tracing::warn!("No [routing] model for the triage task; evaluating every violation");
+        return None;
+    }
+    let failures: Vec<&CheckResult> = results.iter().filter(|r| !r.passed).collect();
+    let trivial = match classify(config, &failures).await {
+        Ok(trivial) => trivial,
+        Err(e) => {
+            tracing::warn!("Triage failed, evaluating every violation: {e:#}");
+            return None;
+        }
+    };
+    let mut number = 0;
+    let kept: Vec<CheckResult> = results
+        .iter()
+        .filter(|r| {
+            if r.passed {
+                return true;
+            }
+            number += 1;
+            !trivial.contains(&number)
+        })
+        .cloned()
+        .collect();
+    let judged = kept.iter().filter(|r| !r.passed).count();
+    eprintln!(
+        "Triage: {judged} of {} violations need judgment\n",
+        failures.len()
+    );
+    Some(kept)
+}
+
+/// Ask the `triage` model which `failures` are trivial, by their numbers
+/// from 1.
+async fn classify(config: &GuardianConfig, failures: &[&CheckResult]) -> Result<Vec<usize>> {
+    let Routed {
+        client,
+        mut breaker,
+        host,
+        model: model_name,
+        system,
+    } = routed(config, "triage", 60_000, None, None).await?;
+
+    let prompt = build_prompt(failures);
+    let system = system.as_deref();
+    check_context_window(config, &client, host, &model_name, system, &prompt).await?;
+    eprintln!(
+        "Triaging {} violations with {model_name}...",
+        failures.len()
+    );
+    let format = ResponseFormat::Schema(serde_json::json!({
+        "type": "object",
+        "properties": {"trivial": {"type": "array", "items": {"type": "integer"}}},
+        "required": ["trivial"],
+    }));
+    let retries = config.ollama.json_retries;
+    let response = cancellable(
+        &client,
+        client.generate_json(host, &model_name, &prompt, &format, retries),
+    )
+    .await;
+    breaker.record(&host.name, response.is_ok());
+    breaker.save();
+    let (value, _) = response?;
+    let Triage { trivial } = serde_json::from_value(value)
+        .with_context(|| format!("{model_name} returned a malformed triage"))?;
+    Ok(trivial)
+}
+
+fn build_prompt(failures: &[&CheckResult]) -> String {
+    let mut prompt = String::from(
+        "You are sorting code quality violations before a senior reviewer sees \
+         them.\n\n## Violations\n\n",
+    );
+    for (i, result) in failures.iter().enumerate() {
+        prompt.push_str(&format!(
+            "{}. [{}] [{:?}] {}\n",
+            i + 1,
+            result.check_name,
+            result.severity,
+            result.message
+        ));
+        if let Some(fix) = &result.fix {
+            prompt.push_str(&format!("   Suggested fix: {fix}\n"));
+        }
+    }
+    prompt.push_str(
+        "\n## Your Task\n\n\
+         A violation is trivial when fixing it is mechanical and the fix is \
+         already clear, such as formatting or a setting to change. It needs \
+         judgment when the fix involves design choices or tradeoffs, or when \
+         you are unsure. Answer with JSON: \"trivial\" lists the numbers of the \
+         trivial violations.\n",
+    );
+    prompt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checks::Severity;
+
+    #[test]
+    fn test_prompt_numbers_violations() {
+        let edition = CheckResult::fail("rust-edition", Severity::Error, "Edition 2021")
+            .with_fix("Set edition = \"2024\"");
+        let loc = CheckResult::fail("loc-limits", Severity::Warning, "lib.rs: 410 lines");
+        let prompt = build_prompt(&[&edition, &loc]);
+        assert!(prompt.contains(
+            "1. [rust-edition] [Error] Edition 2021\n   Suggested fix: Set edition = \"2024\"\n"
+        ));
+        assert!(prompt.contains("2. [loc-limits] [Warning] lib.rs: 410 lines\n"));
+    }
+}
@@ -0,0 +1,164 @@
+//! Exit codes and the policy for when check results fail a run.
+//!
+//! Commands report failures as errors; `main` maps them to an exit code so
+//! callers can tell violations, tool errors, unreachable hosts, and
+//! interrupted runs apart.
+
+use super::cli::FailOn;
+use super::policy::Policy;
+use crate::checks::{CheckResult, Severity};
+use crate::ollama::select::NoHosts;
+use std::fmt;
+
+/// Checks found violations that the fail policy doesn't tolerate.
+pub const VIOLATIONS: u8 = 1;
+/// The tool itself failed: bad config, I/O, a model error, and so on.
+pub const TOOL_ERROR: u8 = 2;
+/// No configured host could be reached.
+pub const NO_HOSTS: u8 = 3;
+/// The run was interrupted by Ctrl-C, the shell's code for SIGINT.
+pub const INTERRUPTED: u8 = 130;
+
+/// Exit with `code`; the command has already reported why.
+#[derive(Debug)]
+pub struct Exit(pub u8);
+
+impl fmt::Display for Exit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "exit status {}", self.0)
+    }
+}
+
+impl std::error::Error for Exit {}
+
+/// The exit code for an error returned by a command.
+pub fn exit_code(err: &anyhow::Error) -> u8 {
+    if let Some(Exit(code)) = err.downcast_ref() {
+        *code
+    } else if err.is::<NoHosts>() {
+        NO_HOSTS
+    } else {
+        TOOL_ERROR
+    }
+}
+
+/// When check results should fail the run.
+#[derive(Debug, Clone)]
+pub struct FailPolicy {
+    pub fail_on: FailOn,
+    /// Fail when more than this many warnings are found.
+    pub max_warnings: Option<usize>,
+    /// Policy expression deciding instead of `fail_on` and `max_warnings`.
+    pub expression: Option<Policy>,
+}
+
+impl Default for FailPolicy {
+    fn default() -> Self {
+        Self {
+            fail_on: FailOn::Error,
+            max_warnings: None,
+            expression: None,
+        }
+    }
+}
+
+impl FailPolicy {
+    /// Err with [`VIOLATIONS`] when `results` break the policy.
+    pub fn enforce(&self, results: &[CheckResult]) -> anyhow::Result<()> {
+        if let Some(expression) = &self.expression {
+            if expression.allows(results)? {
+                return Ok(());
+            }
+            eprintln!("Policy not met: {expression}");
+            return Err(Exit(VIOLATIONS).into());
+        }
+        let failed: Vec<_> = results.iter().filter(|r| !r.passed).collect();
+        let errors = failed
+            .iter()
+            .filter(|r| r.severity == Severity::Error)
+            .count();
+        let warnings = failed.len() - errors;
+        let over_max = self.max_warnings.is_some_and(|max| warnings > max);
+        let violated = match self.fail_on {
+            FailOn::Never => false,
+            FailOn::Error => errors > 0 || over_max,
+            FailOn::Warning => !failed.is_empty(),
+        };
+        if violated {
+            return Err(Exit(VIOLATIONS).into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn results() -> Vec<CheckResult> {
+        vec![
+            CheckResult::pass("rust-edition", "ok"),
+            CheckResult::fail("loc-limits", Severity::Warning, "360 lines"),
+            CheckResult::fail("test-quality", Severity::Warning, "Trivial test"),
+        ]
+    }
+
+    fn policy(fail_on: FailOn, max_warnings: Option<usize>) -> FailPolicy {
+        FailPolicy {
+            fail_on,
+            max_warnings,
+            expression: None,
+        }
+    }
+
+    #[test]
+    fn test_fail_on() {
+        let warnings = results();
+        assert!(FailPolicy::default().enforce(&warnings).is_ok());
+        assert!(policy(FailOn::Warning, None).enforce(&warnings).is_err());
+
+        let mut errors = results();
+        errors.push(CheckResult::fail(
+            "module-count",
+            Severity::Error,
+            "9 modules",
+        ));
+        assert!(FailPolicy::default().enforce(&errors).is_err());
+        assert!(policy(FailOn::Never, None).enforce(&errors).is_ok());
+    }
+
+    #[test]
+    fn test_max_warnings() {
+        assert!(policy(FailOn::Error, Some(2)).enforce(&results()).is_ok());
+        let err = policy(FailOn::Error, Some(1))
+            .enforce(&results())
+            .unwrap_err();
+        assert_eq!(exit_code(&err), VIOLATIONS);
+    }
+
+    #[test]
+    fn test_policy_expression_decides() {
+        let strict = FailPolicy {
+            expression: Some(Policy::parse("warnings == 0").unwrap()),
+            ..FailPolicy::default()
+        };
+        let err = strict.enforce(&results()).unwrap_err();
+        assert_eq!(exit_code(&err), VIOLATIONS);
+
+        let lenient = FailPolicy {
+            fail_on: FailOn::Warning,
+            expression: Some(Policy::parse("checks['loc-limits'].failed <= 1").unwrap()),
+            ..FailPolicy::default()
+        };
+        assert!(lenient.enforce(&results()).is_ok());
+    }
+
+    #[test]
+    fn test_exit_codes() {
+        assert_eq!(
+            exit_code(&NoHosts("No reachable hosts found").into()),
+            NO_HOSTS
+        );
+        assert_eq!(exit_code(&anyhow::anyhow!("config error")), TOOL_ERROR);
+    }
+}
@@ -0,0 +1,172 @@
+//! Model-written explanations of check violations, for `check --explain-llm`.
+//!
+//! After the checks run, the model routed to `explain` writes a rationale
+//! and a concrete remediation for each kind of violation found, in one
+//! request. The checks say what is wrong; this says why it matters, so the
+//! report teaches as well as gates.
+
+use super::llm::{Routed, cancellable, check_context_window, routed};
+use crate::checks::CheckResult;
+use crate::config::GuardianConfig;
+use crate::ollama::ResponseFormat;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Violations of each check quoted in the prompt.
+const MAX_EXAMPLES: usize = 3;
+
+/// Why one kind of violation matters and how to fix it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(super) struct Explanation {
+    /// Name of the check.
+    pub check: String,
+    pub rationale: String,
+    pub remediation: String,
+}
+
+#[derive(Deserialize)]
+struct Explanations {
+    explanations: Vec<Explanation>,
+}
+
+/// Have the model explain each failing check in `results`, in check order.
+///
+/// Explanations the model gives for checks that didn't fail are dropped.
+pub(super) async fn explain(
+    config: &GuardianConfig,
+    results: &[CheckResult],
+) -> Result<Vec<Explanation>> {
+    let failed = failures(results);
+    if failed.is_empty() {
+        return Ok(Vec::new());
+    }
+    let Routed {
+        client,
+        mut breaker,
+        host,
+        model: model_name,
+        system,
+    } = routed(config, "explain", 180_000, None, None).await?;
+
+    let prompt = build_prompt(&failed);
+    let system = system.as_deref();
+    check_context_window(config, &client, host, &model_name, system, &prompt).await?;
+    eprintln!(
+        "Asking {model_name} to explain {} kind(s) of violation...",
+        failed.len()
+    );
+    let retries = config.ollama.json_retries;
+    let response = cancellable(
+        &client,
+        client.generate_json(host, &model_name, &prompt, &format(), retries),
+    )
+    .await;
+    breaker.record(&host.name, response.is_ok());
+    breaker.save();
+    let (value, _) = response?;
+    let Explanations { explanations } = serde_json::from_value(value)
+        .with_context(|| format!("{model_name} returned malformed explanations"))?;
+    let mut explained = Vec::new();
+    for check in failed.keys() {
+        let found = explanations.iter().find(|e| e.check == *check);
+        explained.extend(found.cloned());
+    }
+    Ok(explained)
+}
+
+/// Failed results by check name.
+fn failures(results: &[CheckResult]) -> BTreeMap<&str, Vec<&CheckResult>> {
+    let mut checks: BTreeMap<&str, Vec<&CheckResult>> = BTreeMap::new();
+    for result in results.iter().filter(|r| !r.passed) {
+        checks.entry(&result.check_name).or_default().push(result);
+    }
+    checks
+}
+
+fn build_prompt(failed: &BTreeMap<&str, Vec<&CheckResult>>) -> String {
+    let mut prompt = String::from(
+        "You are a senior engineer explaining code quality findings to a junior \
+         developer.\n\n## Violations\n",
+    );
+    for (check, results) in failed {
+        prompt.push_str(&format!(
+            "\n### {check} ({} violation(s))\n\n",
+            results.len()
+        ));
+        for result in results.iter().take(MAX_EXAMPLES) {
+            prompt.push_str(&format!("- [{:?}] {}\n", result.severity, result.message));
+        }
+        if let Some(fix) = results.iter().find_map(|r| r.fix.as_deref()) {
+            prompt.push_str(&format!("  Suggested fix: {fix}\n"));
+        }
+    }
+    prompt.push_str(
+        "\n## Your Task\n\n\
+         For each check above, explain in one short paragraph why the rule \
+         exists and what goes wrong when it is broken, then give concrete steps \
+         to fix these violations. Answer with JSON: \"explanations\" has one \
+         object per check, with \"check\" (its name as written above), \
+         \"rationale\", and \"remediation\".\n",
+    );
+    prompt
+}
+
+fn format() -> ResponseFormat {
+    let explanation = serde_json::json!({
+        "type": "object",
+        "properties": {
+            "check": {"type": "string"},
+            "rationale": {"type": "string"},
+            "remediation": {"type": "string"},
+        },
+        "required": ["check", "rationale", "remediation"],
+    });
+    ResponseFormat::Schema(serde_json::json!({
+        "type": "object",
+        "properties": {
+            "explanations": {"type": "array", "items": explanation},
+        },
+        "required": ["explanations"],
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checks::Severity;
+    use crate::ollama::parse_json_response;
+
+    #[test]
+    fn test_prompt_groups_violations_by_check() {
+        let mut results: Vec<_> = (0..5)
+            .map(|i| {
+                CheckResult::fail(
+                    "loc-limits",
+                    Severity::Error,
+                    &format!("m{i}.rs: 600 lines"),
+                )
+                .with_fix("Split the file")
+            })
+            .collect();
+        results.push(CheckResult::pass("rust-edition", "Edition 2024"));
+        results.push(CheckResult::fail(
+            "module-count",
+            Severity::Warning,
+            "9 modules",
+        ));
+        let prompt = build_prompt(&failures(&results));
+        assert!(prompt.contains("### loc-limits (5 violation(s))"));
+        assert!(prompt.contains("- [Error] m2.rs: 600 lines\n"));
+        assert!(!prompt.contains("m3.rs"));
+        assert_eq!(prompt.matches("Suggested fix: Split the file").count(), 1);
+        assert!(prompt.contains("### module-count (1 violation(s))"));
+        assert!(!prompt.contains("rust-edition"));
+
+        let reply = r#"{"explanations": [{"check": "loc-limits", "rationale": "Long files hide structure.", "remediation": "Split by concern."}]}"#;
+        let value = parse_json_response(reply, &format()).unwrap();
+        let Explanations { explanations } = serde_json::from_value(value).unwrap();
+        assert_eq!(explanations[0].remediation, "Split by concern.");
+        assert!(parse_json_response(r#"{"loc-limits": "r"}"#, &format()).is_err());
+    }
+}
@@ -0,0 +1,80 @@
+//! Fleet command: what every host runs, holds, and has loaded.
+//!
+//! `ping-hosts` only says whether a host answers. `fleet` also asks each
+//! enabled host for its Ollama version, the models installed on it with
+//! their sizes, and the ones loaded in memory, so the whole fleet can be
+//! read off one table.
+
+use super::exit::{Exit, NO_HOSTS};
+use super::llm;
+use super::output;
+use crate::config::{GuardianConfig, OllamaHost};
+use crate::ollama::{OllamaClient, OllamaModel};
+use futures::StreamExt;
+use serde::Serialize;
+
+/// One host's inventory.
+#[derive(Debug, Serialize)]
+pub struct HostInventory {
+    pub host: String,
+    pub reachable: bool,
+    /// The Ollama version, when the host reports it.
+    pub version: Option<String>,
+    /// Installed models, with their sizes on disk.
+    pub models: Vec<OllamaModel>,
+    /// Names of the models loaded in memory.
+    pub loaded: Vec<String>,
+    /// Total size of the installed models.
+    pub disk_bytes: u64,
+    /// Why the host's models couldn't be listed.
+    pub error: Option<String>,
+}
+
+/// Query every enabled host and report the fleet's inventory.
+pub async fn fleet(config: &GuardianConfig, json_output: bool) -> anyhow::Result<()> {
+    let hosts = config.enabled_hosts();
+    if hosts.is_empty() {
+        return output::no_hosts_error(json_output, "No hosts configured");
+    }
+
+    let client = llm::client(config, config.default_timeout_ms())?;
+    let queries = hosts.iter().map(|host| inventory(&client, host));
+    let inventories: Vec<_> = futures::stream::iter(queries)
+        .buffered(client.jobs())
+        .collect()
+        .await;
+
+    output::fleet_report(&inventories, json_output)?;
+    if !inventories.iter().any(|inventory| inventory.reachable) {
+        return Err(Exit(NO_HOSTS).into());
+    }
+    Ok(())
+}
+
+/// `host`'s version, installed models, and loaded models.
+async fn inventory(client: &OllamaClient, host: &OllamaHost) -> HostInventory {
+    let mut inventory = HostInventory {
+        host: host.name.clone(),
+        reachable: false,
+        version: None,
+        models: Vec::new(),
+        loaded: Vec::new(),
+        disk_bytes: 0,
+        error: None,
+    };
+    match client.list_models(host).await {
+        Ok(models) => inventory.models = models,
+        Err(e) => {
+            inventory.error = Some(format!("{e:#}"));
+            return inventory;
+        }
+    }
+    inventory.reachable = true;
+    inventory.disk_bytes = inventory.models.iter().filter_map(|m| m.size).sum();
+    inventory.version = client.server_version(host).await.map(|v| v.to_string());
+    // Hosts too old for /api/ps just show nothing loaded.
+    if let Ok(running) = client.running_models(host).await {
+        inventory.loaded = running.into_iter().map(|m| m.name).collect();
+    }
+    inventory
+}
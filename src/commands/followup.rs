@@ -0,0 +1,90 @@
+//! Follow-up questions after a model review, for `--interactive`.
+//!
+//! Once `evaluate` or `pre-push --review` has printed the model's review,
+//! the conversation stays open: the prompt with the check results and the
+//! model's reply are kept as chat history, so a question like "why is #3
+//! blocking?" goes to the same model on the same host without rebuilding
+//! that context.
+
+use super::llm::{cancellable, check_context_window, task_client};
+use super::output::report;
+use crate::config::{GuardianConfig, OllamaHost};
+use crate::ollama::ChatMessage;
+use crate::ollama::select::load_breaker;
+use anyhow::{Result, bail};
+use std::io::{BufRead, IsTerminal, Write};
+
+/// Fail early unless follow-up questions can be asked and answered here.
+pub(super) fn require_terminal(json_output: bool) -> Result<()> {
+    if json_output {
+        bail!("--interactive can't be combined with --json");
+    }
+    if !std::io::stdin().is_terminal() {
+        bail!("--interactive asks for questions, so it needs a terminal");
+    }
+    Ok(())
+}
+
+/// Answer questions about `reply`, the review `model` on `host` gave for
+/// `prompt` as the routed `task`, until the user is done.
+pub(super) async fn follow_up(
+    config: &GuardianConfig,
+    task: &str,
+    host: &OllamaHost,
+    model: &str,
+    prompt: &str,
+    reply: &str,
+) -> Result<()> {
+    let client = task_client(config, task, 180_000)?;
+    let system = config.route(task).system_prompt;
+    let system = system.as_deref();
+    let mut breaker = load_breaker(config);
+    let mut history = vec![
+        ChatMessage::new("user", prompt),
+        ChatMessage::new("assistant", reply),
+    ];
+    eprintln!("\nAsk {model} about this review; an empty line or Ctrl-D ends.");
+    let mut stdin = std::io::stdin().lock();
+    while let Some(question) = read_question(&mut stdin)? {
+        let mut context: Vec<&str> = history.iter().map(|m| m.content.as_str()).collect();
+        context.push(&question);
+        check_context_window(config, &client, host, model, system, &context.join("\n")).await?;
+        let response = cancellable(&client, client.chat(host, model, &history, &question)).await;
+        breaker.record(&host.name, response.is_ok());
+        match response {
+            Ok(response) => {
+                report!("\n{}\n", response.response.trim_end());
+                history.push(ChatMessage::new("user", question));
+                history.push(ChatMessage::new("assistant", response.response));
+            }
+            Err(e) => eprintln!("{model} couldn't answer: {e:#}"),
+        }
+    }
+    breaker.save();
+    Ok(())
+}
+
+/// The next question from `input`, or `None` at the end of the input or an
+/// empty line.
+fn read_question(input: &mut impl BufRead) -> Result<Option<String>> {
+    eprint!("> ");
+    std::io::stderr().flush()?;
+    let mut line = String::new();
+    input.read_line(&mut line)?;
+    let question = line.trim();
+    Ok((!question.is_empty()).then(|| question.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_questions_until_blank() {
+        let mut input = std::io::Cursor::new("why is #3 blocking?\n\nignored\n");
+        let question = read_question(&mut input).unwrap();
+        assert_eq!(question.as_deref(), Some("why is #3 blocking?"));
+        assert_eq!(read_question(&mut input).unwrap(), None);
+        assert_eq!(read_question(&mut std::io::Cursor::new("")).unwrap(), None);
+    }
+}
@@ -0,0 +1,314 @@
+//! Govern command: a machine verdict on an action an agent proposes.
+//!
+//! Reads a JSON description of the action, runs the file-scoped checks on
+//! the files as they would be written, and has the model routed to `govern`
+//! judge the action. The verdict is always JSON, for hook scripts:
+//!
+//! ```text
+//! -> {"intent": "...", "files": [{"path": "src/lib.rs", "content": "..."}], "diff": "..."}
+//! <- {"verdict": "revise", "reasons": ["..."], "violations": [...], "host": "...", "model": "..."}
+//! ```
+//!
+//! `allow` exits 0, and `revise` and `deny` exit 1. The stricter of the
+//! checks' and the model's verdicts wins.
+
+use super::exit::{Exit, FailPolicy, VIOLATIONS, exit_code};
+use super::llm::{Routed, cancellable, check_context_window, routed};
+use super::output;
+use super::{history, rules};
+use crate::checks::{CheckResult, configured_thresholds, run_source_checks, select_checks};
+use crate::config::{GuardianConfig, OllamaHost};
+use crate::ollama::ResponseFormat;
+use crate::storage::LlmResponse;
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Most bytes of proposed files and diff sent to the model.
+const MAX_ACTION_BYTES: usize = 48_000;
+
+/// Options for the govern command.
+pub struct GovernOptions<'a> {
+    /// JSON file describing the action, or `-` for stdin.
+    pub input: &'a Path,
+    pub path: Option<&'a Path>,
+    /// Only run these checks, comma-separated.
+    pub only: Option<&'a str>,
+    /// Don't run these checks, comma-separated.
+    pub skip: Option<&'a str>,
+    /// Decide from the checks alone.
+    pub checks_only: bool,
+    pub model: Option<&'a str>,
+    pub host: Option<&'a str>,
+    /// Decides whether the check results need revising.
+    pub policy: FailPolicy,
+}
+
+/// An action an agent proposes to take.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Action {
+    /// What the agent is trying to do, in its own words.
+    intent: Option<String>,
+    /// Files as they would be after the action.
+    files: Vec<ProposedFile>,
+    /// The change as a unified diff.
+    diff: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ProposedFile {
+    /// Relative to the project directory.
+    path: PathBuf,
+    content: String,
+}
+
+/// Whether the action may go ahead, from most to least permissive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Verdict {
+    /// Go ahead as proposed.
+    Allow,
+    /// The idea is sound but the change needs work first.
+    Revise,
+    /// Don't take this action.
+    Deny,
+}
+
+impl Verdict {
+    /// Name of the verdict in the JSON output.
+    fn name(self) -> &'static str {
+        match self {
+            Self::Allow => "allow",
+            Self::Revise => "revise",
+            Self::Deny => "deny",
+        }
+    }
+}
+
+/// The model's verdict on the action.
+#[derive(Debug, Deserialize)]
+struct Judgement {
+    verdict: Verdict,
+    reasons: Vec<String>,
+}
+
+/// Judge the action described in `opts.input` and print the verdict.
+pub async fn govern(config: &GuardianConfig, opts: GovernOptions<'_>) -> Result<()> {
+    let action = read_action(opts.input)?;
+    let project_dir = opts.path.unwrap_or(Path::new("."));
+
+    let thresholds = configured_thresholds(&config.check);
+    let selected = select_checks(
+        opts.only,
+        opts.skip,
+        config.check.only.as_deref(),
+        &config.checks,
+    )?;
+    let results: Vec<CheckResult> = action
+        .files
+        .iter()
+        .filter(|file| file.path.extension().is_some_and(|e| e == "rs"))
+        .flat_map(|file| {
+            let path = project_dir.join(&file.path);
+            run_source_checks(&path, &file.content, &thresholds, &selected)
+        })
+        .collect();
+    let failures: Vec<CheckResult> = results.iter().filter(|r| !r.passed).cloned().collect();
+
+    let mut verdict = match opts.policy.enforce(&results) {
+        Ok(()) => Verdict::Allow,
+        Err(err) if exit_code(&err) == VIOLATIONS => Verdict::Revise,
+        Err(err) => return Err(err),
+    };
+    let mut reasons: Vec<String> = failures
+        .iter()
+        .map(|r| format!("[{}] {}", r.check_name, r.message))
+        .collect();
+    let mut judge = None;
+    if !opts.checks_only {
+        let (judgement, host, model) = judge_action(config, &opts, &action, &failures).await?;
+        verdict = verdict.max(judgement.verdict);
+        reasons.extend(judgement.reasons);
+        judge = Some((host, model));
+    }
+
+    let judged_by = judge.as_ref().map(|(host, model)| (*host, model.as_str()));
+    let text = reasons.join("\n");
+    let llm = judged_by.map(|(host, model)| LlmResponse {
+        host: &host.name,
+        model,
+        text: &text,
+    });
+    let outcome = verdict.name();
+    history::record_run(
+        &config.storage,
+        project_dir,
+        "govern",
+        &results,
+        outcome,
+        llm,
+    );
+    output::govern_verdict(verdict, &reasons, &failures, judged_by)?;
+    match verdict {
+        Verdict::Allow => Ok(()),
+        Verdict::Revise | Verdict::Deny => Err(Exit(VIOLATIONS).into()),
+    }
+}
+
+/// Read and parse the action from `input`, or stdin for `-`.
+fn read_action(input: &Path) -> Result<Action> {
+    let text = if input == Path::new("-") {
+        let mut text = String::new();
+        std::io::stdin()
+            .read_to_string(&mut text)
+            .context("Failed to read the action from stdin")?;
+        text
+    } else {
+        std::fs::read_to_string(input)
+            .with_context(|| format!("Failed to read {}", input.display()))?
+    };
+    let action: Action = serde_json::from_str(&text).context("Invalid action JSON")?;
+    if action.files.is_empty() && action.diff.is_none() {
+        bail!("The action has no files or diff to judge");
+    }
+    Ok(action)
+}
+
+/// Have the model routed to `govern` judge the action.
+async fn judge_action<'a>(
+    config: &'a GuardianConfig,
+    opts: &GovernOptions<'_>,
+    action: &Action,
+    failures: &[CheckResult],
+) -> Result<(Judgement, &'a OllamaHost, String)> {
+    let Routed {
+        client,
+        mut breaker,
+        host,
+        model: model_name,
+        system,
+    } = routed(config, "govern", 180_000, opts.host, opts.model).await?;
+
+    let rules = rules::project_rules(opts.path.unwrap_or(Path::new(".")))?;
+    let prompt = build_prompt(action, failures, rules.as_deref());
+    let system = system.as_deref();
+    check_context_window(config, &client, host, &model_name, system, &prompt).await?;
+    let format = ResponseFormat::Schema(serde_json::json!({
+        "type": "object",
+        "properties": {
+            "verdict": {"type": "string", "enum": ["allow", "revise", "deny"]},
+            "reasons": {"type": "array", "items": {"type": "string"}},
+        },
+        "required": ["verdict", "reasons"],
+    }));
+    let retries = config.ollama.json_retries;
+    let response = cancellable(
+        &client,
+        client.generate_json(host, &model_name, &prompt, &format, retries),
+    )
+    .await;
+    breaker.record(&host.name, response.is_ok());
+    breaker.save();
+    let (value, _) = response?;
+    let judgement = serde_json::from_value(value).with_context(|| {
+        format!("{model_name} returned a verdict that isn't allow, revise, or deny")
+    })?;
+    Ok((judgement, host, model_name))
+}
+
+fn build_prompt(action: &Action, failures: &[CheckResult], rules: Option<&str>) -> String {
+    let mut prompt = String::from(
+        "You are a governor deciding whether a coding agent may take an action \
+         in this project.\n\n",
+    );
+    prompt.push_str(rules.unwrap_or_default());
+    prompt.push_str("## Intent\n\n");
+    prompt.push_str(action.intent.as_deref().unwrap_or("(not stated)"));
+    prompt.push_str("\n\n## Failed Checks\n\n");
+    if failures.is_empty() {
+        prompt.push_str("None.\n");
+    }
+    for result in failures {
+        prompt.push_str(&format!("- [{:?}] {}\n", result.severity, result.message));
+    }
+
+    let mut change = String::new();
+    for file in &action.files {
+        change.push_str(&format!(
+            "### {}\n\n```\n{}\n```\n\n",
+            file.path.display(),
+            file.content
+        ));
+    }
+    if let Some(diff) = &action.diff {
+        change.push_str(&format!("### Diff\n\n```diff\n{diff}\n```\n"));
+    }
+    let mut end = change.len().min(MAX_ACTION_BYTES);
+    while !change.is_char_boundary(end) {
+        end -= 1;
+    }
+    prompt.push_str("\n## Proposed Change\n\n");
+    prompt.push_str(&change[..end]);
+    if end < change.len() {
+        prompt.push_str("\n[... change truncated ...]\n");
+    }
+    prompt.push_str(
+        "\n## Your Task\n\n\
+         Decide whether the change does what the intent says without bugs, \
+         security problems, or breaking the project's conventions. Answer with \
+         JSON: \"verdict\" is \"allow\" if it can go ahead as is, \"revise\" if \
+         the idea is sound but the change needs work, or \"deny\" if the action \
+         should not be taken at all. \"reasons\" lists short, specific reasons; \
+         leave it empty when allowing without comment.\n",
+    );
+    prompt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checks::Severity;
+
+    #[test]
+    fn test_action_parsing() {
+        let action: Action = serde_json::from_str(
+            r#"{"intent": "Add a helper", "files": [{"path": "src/a.rs", "content": "fn a() {}"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(action.files[0].path, Path::new("src/a.rs"));
+        assert!(action.diff.is_none());
+        assert!(serde_json::from_str::<Action>(r#"{"file": "src/a.rs"}"#).is_err());
+
+        let judgement: Judgement =
+            serde_json::from_str(r#"{"verdict": "deny", "reasons": ["Deletes the tests"]}"#)
+                .unwrap();
+        assert_eq!(Verdict::Revise.max(judgement.verdict), Verdict::Deny);
+        assert_eq!(Verdict::Allow.max(Verdict::Revise), Verdict::Revise);
+    }
+
+    #[test]
+    fn test_prompt_truncates_change() {
+        let action = Action {
+            intent: None,
+            files: vec![ProposedFile {
+                path: PathBuf::from("src/big.rs"),
+                content: "//".repeat(MAX_ACTION_BYTES),
+            }],
+            diff: Some("+fn b() {}".to_string()),
+        };
+        let failures = [CheckResult::fail(
+            "loc-limits",
+            Severity::Error,
+            "big.rs: 900 lines (max 500)",
+        )];
+        let prompt = build_prompt(&action, &failures, None);
+        assert!(prompt.contains("(not stated)"));
+        assert!(prompt.contains("- [Error] big.rs: 900 lines"));
+        assert!(prompt.contains("### src/big.rs"));
+        assert!(prompt.contains("[... change truncated ...]"));
+        assert!(!prompt.contains("+fn b() {}"));
+    }
+}
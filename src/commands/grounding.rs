@@ -0,0 +1,324 @@
+//! Checking the file and line references in a model's review.
+//!
+//! Small local models cite files that don't exist, and lines past the end
+//! of ones that do, often enough to cost a review its credibility. Before a
+//! review reaches the user, each `path`, `path:line`, and `path (line N)`
+//! it mentions is looked up in the project; with `[ollama] verify_quotes`,
+//! code quoted in backticks beside a line reference must also be near that
+//! line. What happens to the points that fail is up to `[ollama]
+//! unverified_references`: they are flagged, stripped, or kept as written.
+
+use super::verdict::{Assessment, Finding};
+use crate::checks::walk::{self, Scan};
+use crate::config::{GuardianConfig, UnverifiedReferences};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Extensions of the files a reference can name.
+const EXTENSIONS: &[&str] = &[
+    "rs", "toml", "md", "json", "yaml", "yml", "lock", "txt", "sh",
+];
+
+/// How far from the cited line quoted code may be and still count.
+const NEAR_LINES: usize = 10;
+
+/// A file the model names, and the line, if it names one.
+#[derive(Debug, PartialEq)]
+struct Citation<'t> {
+    path: &'t str,
+    line: Option<usize>,
+}
+
+/// The files in a project that a review can cite.
+struct Project {
+    files: Vec<PathBuf>,
+    verify_quotes: bool,
+}
+
+/// `text` with the lines that make unverified references flagged or
+/// stripped, per `config`.
+pub(super) fn guard_text(text: &str, project_dir: &Path, config: &GuardianConfig) -> String {
+    let mode = config.ollama.unverified_references;
+    if mode == UnverifiedReferences::Keep {
+        return text.to_string();
+    }
+    let project = Project::scan(project_dir, config);
+    let (lines, count) = project.guard(text.lines(), mode);
+    note(count, mode);
+    let mut text = lines.join("\n");
+    text.push('\n');
+    text
+}
+
+/// Flag or strip the points of `assessment` that make unverified
+/// references, per `config`: whole items of `blocking` and `advice`, and
+/// lines of the rubric's sections.
+pub(super) fn guard_assessment(
+    assessment: &mut Assessment,
+    project_dir: &Path,
+    config: &GuardianConfig,
+) {
+    let mode = config.ollama.unverified_references;
+    if mode == UnverifiedReferences::Keep {
+        return;
+    }
+    let project = Project::scan(project_dir, config);
+    let mut count = 0;
+    let mut blocking = Vec::new();
+    for finding in assessment.blocking.drain(..) {
+        let (kept, unverified) = project.guard([finding.issue.as_str()].into_iter(), mode);
+        let confidence = finding.confidence;
+        blocking.extend(kept.into_iter().map(|issue| Finding { issue, confidence }));
+        count += unverified;
+    }
+    assessment.blocking = blocking;
+    let advice = assessment.advice.iter().map(String::as_str);
+    let (advice, unverified) = project.guard(advice, mode);
+    assessment.advice = advice;
+    count += unverified;
+    for content in assessment.sections.values_mut() {
+        let (lines, unverified) = project.guard(content.lines(), mode);
+        *content = lines.join("\n");
+        count += unverified;
+    }
+    note(count, mode);
+}
+
+/// Tell the user how many references didn't check out.
+fn note(count: usize, mode: UnverifiedReferences) {
+    if count == 0 {
+        return;
+    }
+    let done = match mode {
+        UnverifiedReferences::Strip => "left out",
+        _ => "marked [unverified]",
+    };
+    eprintln!(
+        "{count} point(s) in the review cite files or lines that don't match the project; {done}\n"
+    );
+}
+
+/// The file references in `text`: paths with a known extension, with a
+/// line from a `:N` suffix or a following `line N`.
+fn citations(text: &str) -> Vec<Citation<'_>> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let mut found = Vec::new();
+    for (i, word) in words.iter().enumerate() {
+        let word = word
+            .trim_matches(|c: char| "`'\"()[]{}<>,;*".contains(c))
+            .trim_end_matches(['.', ':']);
+        let (path, suffix) = match word.split_once(':') {
+            Some((path, rest)) => (path, rest.split([':', '-']).next()),
+            None => (word, None),
+        };
+        let Some((stem, extension)) = path.rsplit_once('.') else {
+            continue;
+        };
+        let path_chars = path
+            .chars()
+            .all(|c| c.is_alphanumeric() || "_-./".contains(c));
+        if !EXTENSIONS.contains(&extension) || stem.ends_with('/') || stem.is_empty() || !path_chars
+        {
+            continue;
+        }
+        let next = |k: usize| {
+            let word = words.get(i + k)?;
+            Some(word.trim_matches(|c: char| !c.is_alphanumeric()))
+        };
+        let line = suffix
+            .and_then(|n| n.parse().ok())
+            .or_else(|| match next(1) {
+                Some("line" | "lines") => next(2)?.split('-').next()?.parse().ok(),
+                _ => None,
+            });
+        found.push(Citation { path, line });
+    }
+    found
+}
+
+impl Project {
+    /// The citable files under `project_dir`, leaving out `target` and
+    /// hidden directories.
+    fn scan(project_dir: &Path, config: &GuardianConfig) -> Self {
+        let scan = Scan::from(&config.check);
+        Self {
+            files: walk::project_files(project_dir, EXTENSIONS, &scan),
+            verify_quotes: config.ollama.verify_quotes,
+        }
+    }
+
+    /// Why `citation` doesn't check out, if it doesn't. `quotes` is the code
+    /// quoted beside it.
+    fn problem(&self, citation: &Citation, quotes: &[&str]) -> Option<String> {
+        let cited = Path::new(citation.path);
+        let contents: Vec<String> = self
+            .files
+            .iter()
+            .filter(|file| file.ends_with(cited))
+            .filter_map(|file| fs::read_to_string(file).ok())
+            .collect();
+        if contents.is_empty() {
+            return Some(format!("{} doesn't exist", citation.path));
+        }
+        let line = citation.line?;
+        let longest = contents.iter().map(|c| c.lines().count()).max();
+        let longest = longest.unwrap_or_default();
+        if line == 0 || line > longest {
+            return Some(format!("{} has {longest} lines", citation.path));
+        }
+        if !self.verify_quotes || quotes.is_empty() {
+            return None;
+        }
+        let near = contents.iter().any(|content| {
+            let mut window = content
+                .lines()
+                .skip(line.saturating_sub(NEAR_LINES + 1))
+                .take(2 * NEAR_LINES + 1);
+            window.any(|text| quotes.iter().any(|quote| text.contains(quote)))
+        });
+        (!near).then(|| {
+            format!(
+                "the quoted code isn't near line {line} of {}",
+                citation.path
+            )
+        })
+    }
+
+    /// `pieces` with the ones that make unverified references flagged or
+    /// left out, and how many there were.
+    fn guard<'a>(
+        &self,
+        pieces: impl Iterator<Item = &'a str>,
+        mode: UnverifiedReferences,
+    ) -> (Vec<String>, usize) {
+        let mut kept = Vec::new();
+        let mut count = 0;
+        for piece in pieces {
+            let quotes: Vec<&str> = piece
+                .split('`')
+                .skip(1)
+                .step_by(2)
+                .map(str::trim)
+                .filter(|quote| quote.len() >= 4 && citations(quote).is_empty())
+                .collect();
+            let problem = citations(piece)
+                .iter()
+                .find_map(|citation| self.problem(citation, &quotes));
+            match problem {
+                None => kept.push(piece.to_string()),
+                Some(problem) => {
+                    count += 1;
+                    if mode == UnverifiedReferences::Flag {
+                        kept.push(format!("{piece} [unverified: {problem}]"));
+                    }
+                }
+            }
+        }
+        (kept, count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::verdict::Verdict;
+    use std::collections::BTreeMap;
+    use tempfile::TempDir;
+
+    fn project() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("src")).unwrap();
+        let lib = "fn parse() {\n    let value = input.unwrap();\n}\n";
+        fs::write(dir.path().join("src/lib.rs"), lib).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_citations() {
+        let text = "See src/lib.rs:12, `main.rs` (line 4), and Cargo.toml. \
+                    Not crate::checks, e.g. this, or https://example.com.";
+        let found = citations(text);
+        assert_eq!(
+            found,
+            [
+                Citation {
+                    path: "src/lib.rs",
+                    line: Some(12)
+                },
+                Citation {
+                    path: "main.rs",
+                    line: Some(4)
+                },
+                Citation {
+                    path: "Cargo.toml",
+                    line: None
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_guard_assessment() {
+        let dir = project();
+        let finding = |issue: &str| Finding {
+            issue: issue.to_string(),
+            confidence: 0.9,
+        };
+        let mut assessment = Assessment {
+            verdict: Verdict::Revise,
+            confidence: 0.8,
+            blocking: vec![
+                finding("Handle the error in src/lib.rs:2 instead of unwrapping."),
+                finding("Split src/parser.rs, it is too long."),
+            ],
+            advice: vec!["Document lib.rs line 40.".to_string()],
+            sections: BTreeMap::from([(
+                "Risks".to_string(),
+                "None.\nsrc/gone.rs panics.".to_string(),
+            )]),
+        };
+        let mut config = GuardianConfig::default();
+        guard_assessment(&mut assessment, dir.path(), &config);
+        assert_eq!(
+            assessment.blocking[0],
+            finding("Handle the error in src/lib.rs:2 instead of unwrapping.")
+        );
+        assert_eq!(
+            assessment.blocking[1],
+            finding(
+                "Split src/parser.rs, it is too long. [unverified: src/parser.rs doesn't exist]"
+            )
+        );
+        assert_eq!(
+            assessment.advice[0],
+            "Document lib.rs line 40. [unverified: lib.rs has 3 lines]"
+        );
+
+        config.ollama.unverified_references = UnverifiedReferences::Strip;
+        guard_assessment(&mut assessment, dir.path(), &config);
+        assert_eq!(assessment.blocking.len(), 1);
+        assert!(assessment.advice.is_empty());
+        assert_eq!(assessment.sections["Risks"], "None.");
+    }
+
+    #[test]
+    fn test_verify_quotes() {
+        let dir = project();
+        let mut config = GuardianConfig::default();
+        let text = "`input.unwrap()` at src/lib.rs:2 can panic.\n\
+                    `value.expect(\"x\")` at src/lib.rs:2 can panic.\n";
+        assert_eq!(guard_text(text, dir.path(), &config), text);
+
+        config.ollama.verify_quotes = true;
+        let guarded = guard_text(text, dir.path(), &config);
+        let lines: Vec<&str> = guarded.lines().collect();
+        assert_eq!(lines[0], "`input.unwrap()` at src/lib.rs:2 can panic.");
+        assert!(
+            lines[1].ends_with("[unverified: the quoted code isn't near line 2 of src/lib.rs]")
+        );
+
+        config.ollama.unverified_references = UnverifiedReferences::Keep;
+        let text = "src/gone.rs:1 is wrong.";
+        assert_eq!(guard_text(text, dir.path(), &config), text);
+    }
+}
@@ -0,0 +1,42 @@
+//! Recording finished check runs in the result history, which `serve`'s
+//! dashboard and metrics read.
+//!
+//! Like state files, the history only helps later runs, so failing to record
+//! a run is logged rather than failing the command.
+
+use super::policy;
+use crate::checks::CheckResult;
+use crate::config::StorageSettings;
+use crate::storage::{History, LlmResponse, NewRun};
+use anyhow::Result;
+use std::path::Path;
+
+/// Record the results of a run of `command` on `project_dir`.
+pub(super) fn record_run(
+    settings: &StorageSettings,
+    project_dir: &Path,
+    command: &str,
+    results: &[CheckResult],
+    outcome: &str,
+    llm: Option<LlmResponse<'_>>,
+) {
+    if !settings.enabled {
+        return;
+    }
+    let run = NewRun {
+        project: project_dir,
+        command,
+        results,
+        score: policy::score(results),
+        outcome,
+        llm,
+    };
+    if let Err(e) = History::open(settings).and_then(|mut history| history.record(&run)) {
+        tracing::warn!(error = %e, "Failed to record the run in the history");
+    }
+}
+
+/// The outcome recorded for a run the failure policy judged.
+pub(super) fn outcome(verdict: &Result<()>) -> &'static str {
+    if verdict.is_ok() { "passed" } else { "failed" }
+}
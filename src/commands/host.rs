@@ -1,9 +1,13 @@
 //! Host-related commands: ping, list-models, select-host.
 
-use super::output;
+use super::exit::{Exit, NO_HOSTS};
+use super::llm;
+use super::output::{self, report};
 use crate::config::{GuardianConfig, OllamaHost};
-use crate::ollama::OllamaClient;
+use crate::ollama::select::{load_breaker, load_health};
+use crate::ollama::{HealthSnapshot, HostScore, OllamaClient, has_model, rank};
 use anyhow::Result;
+use futures::StreamExt;
 
 fn host_result_json(
     host: &OllamaHost,
@@ -31,18 +35,27 @@ pub async fn ping_hosts(config: &GuardianConfig, json_output: bool) -> Result<()
         return output::no_hosts_error(json_output, "No hosts configured");
     }
 
-    let client = OllamaClient::new(config.default_timeout_ms())?;
+    let client = llm::client(config, config.default_timeout_ms())?;
     let results = client.ping_hosts(&hosts).await;
 
+    let mut breaker = load_breaker(config);
+    for result in &results {
+        breaker.record(&result.host.name, result.reachable);
+    }
+    breaker.save();
+
     if !json_output {
-        println!("Pinging {} host(s)...\n", hosts.len());
+        eprintln!("Pinging {} host(s)...\n", hosts.len());
     }
 
     output::ping_results(&results, json_output)?;
 
     if !json_output {
         let reachable = results.iter().filter(|r| r.reachable).count();
-        println!("\n{reachable}/{} hosts reachable", results.len());
+        report!("\n{reachable}/{} hosts reachable", results.len());
+    }
+    if !results.iter().any(|r| r.reachable) {
+        return Err(Exit(NO_HOSTS).into());
     }
     Ok(())
 }
@@ -66,14 +79,16 @@ pub async fn list_models(
         return output::no_hosts_error(json_output, "No matching hosts found");
     }
 
-    let client = OllamaClient::new(config.default_timeout_ms())?;
+    let client = llm::client(config, config.default_timeout_ms())?;
+    let mut breaker = load_breaker(config);
     let mut results = Vec::new();
 
     for host in &hosts {
         let ping = client.ping_host(host).await;
+        breaker.record(&host.name, ping.reachable);
         if !ping.reachable {
             if !json_output {
-                println!("\n{} ({}): UNREACHABLE", host.name, host.base_url);
+                report!("\n{} ({}): UNREACHABLE", host.name, host.base_url);
             }
             results.push(host_result_json(host, false, &[], None));
             continue;
@@ -89,56 +104,112 @@ pub async fn list_models(
             }
             Err(e) => {
                 if !json_output {
-                    println!("\n{} ({}): ERROR - {e}", host.name, host.base_url);
+                    report!("\n{} ({}): ERROR - {e}", host.name, host.base_url);
                 }
                 results.push(host_result_json(host, true, &[], Some(e.to_string())));
             }
         }
     }
 
+    breaker.save();
+
     if json_output {
-        println!("{}", serde_json::to_string_pretty(&results)?);
+        report!("{}", serde_json::to_string_pretty(&results)?);
     }
     Ok(())
 }
 
-/// Select the best available host.
+/// Select the best available host by score, or rank all hosts with `all`.
 pub async fn select_host(
     config: &GuardianConfig,
     required_model: Option<&str>,
+    all: bool,
     json_output: bool,
 ) -> Result<()> {
-    let client = OllamaClient::new(config.default_timeout_ms())?;
-
-    // Try primary hosts first, then fallbacks
-    for host in config.primary_hosts().iter().chain(config.fallback_hosts().iter()) {
-        if let Some(h) = try_host(&client, host, required_model).await {
-            return output::selected_host(h, json_output);
+    let client = llm::client(config, config.default_timeout_ms())?;
+    let mut breaker = load_breaker(config);
+    let health = load_health();
+
+    // Hosts with open circuits are skipped entirely
+    let hosts = breaker.available(config.enabled_hosts());
+    let measured: Vec<_> = futures::stream::iter(
+        hosts
+            .iter()
+            .map(|host| measure_host(&client, health.as_ref(), host, required_model)),
+    )
+    .buffered(client.jobs())
+    .collect()
+    .await;
+
+    let mut scores = Vec::new();
+    for (score, pinged) in measured {
+        if let Some(reachable) = pinged {
+            breaker.record(&score.host, reachable);
         }
+        scores.push(score);
+    }
+    breaker.save();
+    rank(&mut scores);
+
+    if all {
+        return output::ranked_hosts(&scores, json_output);
+    }
+
+    let best = scores.first().filter(|s| s.usable);
+    if let Some(host) = best.and_then(|s| hosts.iter().find(|h| h.name == s.host)) {
+        return output::selected_host(host, json_output);
     }
 
     if json_output {
-        println!(r#"{{"error": "No suitable hosts available"}}"#);
+        report!(r#"{{"error": "No suitable hosts available"}}"#);
     } else {
         eprintln!("No suitable hosts available");
     }
-    std::process::exit(1);
+    Err(Exit(NO_HOSTS).into())
 }
 
-async fn try_host<'a>(
+/// Measure and score one host.
+///
+/// Uses the serve daemon's rolling health when it tracks the host, otherwise
+/// pings it; the ping outcome is returned so the caller can record it.
+async fn measure_host(
     client: &OllamaClient,
-    host: &'a OllamaHost,
+    health: Option<&HealthSnapshot>,
+    host: &OllamaHost,
     required_model: Option<&str>,
-) -> Option<&'a OllamaHost> {
-    if !client.ping_host(host).await.reachable {
-        return None;
+) -> (HostScore, Option<bool>) {
+    let tracked = health.and_then(|h| h.hosts.iter().find(|t| t.name == host.name));
+    let (reachable, latency_ms, availability, pinged) = match tracked {
+        Some(t) => (t.healthy, t.avg_latency_ms, t.availability, None),
+        None => {
+            let ping = client.ping_host(host).await;
+            let availability = if ping.reachable { 1.0 } else { 0.0 };
+            (
+                ping.reachable,
+                ping.latency_ms,
+                availability,
+                Some(ping.reachable),
+            )
+        }
+    };
+    if !reachable {
+        return (
+            HostScore::new(host, false, latency_ms, availability, false),
+            pinged,
+        );
     }
 
-    match required_model {
-        Some(model) => match client.list_models(host).await {
-            Ok(models) if models.iter().any(|m| m.name == model) => Some(host),
-            _ => None,
-        },
-        None => Some(host),
-    }
+    let has_required = match required_model {
+        Some(model) => client
+            .list_models(host)
+            .await
+            .is_ok_and(|models| has_model(&models, model)),
+        None => true,
+    };
+    // Without a required model, any loaded model means a warm GPU
+    let loaded = client.running_models(host).await.is_ok_and(|running| {
+        required_model.map_or(!running.is_empty(), |model| has_model(&running, model))
+    });
+    let score = HostScore::new(host, has_required, latency_ms, availability, loaded);
+    (score, pinged)
 }
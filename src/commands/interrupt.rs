@@ -0,0 +1,64 @@
+//! Ctrl-C during `check` and `evaluate`: stop early with partial results.
+//! `watchdog` uses the same graceful stop to end its loop.
+//!
+//! The first Ctrl-C lets the step in progress finish, a model request
+//! included, but starts nothing new (see [`checks::interrupt`]). The
+//! command then reports what it gathered, marked partial by
+//! [`mark_partial`], and exits with [`INTERRUPTED`], so a wrapper can tell
+//! an interrupted run from a failed one. A second Ctrl-C exits at once.
+//!
+//! [`checks::interrupt`]: crate::checks::interrupt
+
+use super::exit::INTERRUPTED;
+use crate::checks::{CheckResult, Severity, interrupt};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Name of the result that marks a run's results partial.
+const CHECK_NAME: &str = "interrupted";
+
+/// Set by [`stop_gracefully`].
+static GRACEFUL: AtomicBool = AtomicBool::new(false);
+
+/// Stop the run gracefully on Ctrl-C, rather than exiting, from now on.
+pub fn stop_gracefully() {
+    GRACEFUL.store(true, Ordering::Relaxed);
+    tokio::spawn(async {
+        if tokio::signal::ctrl_c().await.is_err() {
+            return;
+        }
+        interrupt::interrupt();
+        eprintln!("\nInterrupted; finishing the current step (Ctrl-C again to quit now)");
+        let _ = tokio::signal::ctrl_c().await;
+        std::process::exit(INTERRUPTED.into());
+    });
+}
+
+/// Whether Ctrl-C stops the run gracefully, so a generation in progress
+/// should be left to finish.
+pub(super) fn graceful() -> bool {
+    GRACEFUL.load(Ordering::Relaxed)
+}
+
+/// Add a result saying `results` are partial, since the run was
+/// interrupted.
+pub(super) fn mark_partial(results: &mut Vec<CheckResult>) {
+    results.push(CheckResult::fail(
+        CHECK_NAME,
+        Severity::Warning,
+        "Interrupted by Ctrl-C; these results are partial",
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mark_partial() {
+        let mut results = vec![CheckResult::pass("rust-edition", "Edition 2024")];
+        mark_partial(&mut results);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[1].check_name, "interrupted");
+        assert!(!results[1].passed);
+    }
+}
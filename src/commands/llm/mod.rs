@@ -0,0 +1,274 @@
+//! LLM interaction commands: ask, plus helpers shared with evaluate and
+//! every other command that routes a task to a model.
+
+mod routed;
+
+pub(super) use routed::{Routed, client, routed, task_client};
+
+use super::ci::CiProvider;
+use super::exit::INTERRUPTED;
+use super::interrupt;
+use super::output::{self, report};
+use crate::config::{ContextOverflow, GuardianConfig, OllamaHost};
+use crate::ollama::{OllamaClient, ResponseFormat, estimate_tokens, has_model, race_hosts};
+use anyhow::Result;
+use std::path::Path;
+
+/// Timeout for model pulls, which can take many minutes on large models.
+const PULL_TIMEOUT_MS: u64 = 3_600_000;
+
+use crate::ollama::select::race_partner;
+
+/// Options for the ask command.
+pub struct AskOptions<'a> {
+    pub prompt: &'a str,
+    pub model: Option<&'a str>,
+    pub host: Option<&'a str>,
+    /// `[routing]` task whose model and host to use (default: "ask").
+    pub task: Option<&'a str>,
+    /// Request a JSON response.
+    pub json_format: bool,
+    /// JSON schema the response must follow (implies `json_format`).
+    pub schema: Option<&'a Path>,
+    /// Race the request against a second host.
+    pub race: bool,
+    pub json_output: bool,
+}
+
+/// Send a prompt to an Ollama model and get a response.
+pub async fn ask(config: &GuardianConfig, opts: AskOptions<'_>) -> Result<()> {
+    let format = match (opts.schema, opts.json_format) {
+        (Some(path), _) => Some(ResponseFormat::from_schema_file(path)?),
+        (None, true) => Some(ResponseFormat::Json),
+        (None, false) => None,
+    };
+
+    if let Some(task) = opts.task.filter(|t| !config.knows_task(t)) {
+        anyhow::bail!("No [routing] entry or host `models` entry for task '{task}'");
+    }
+    let task = opts.task.unwrap_or("ask");
+    let Routed {
+        client,
+        mut breaker,
+        host,
+        model: model_name,
+        system,
+    } = routed(config, task, 120_000, opts.host, opts.model).await?;
+    let system = system.as_deref();
+    check_context_window(config, &client, host, &model_name, system, opts.prompt).await?;
+
+    let retries = config.ollama.json_retries;
+    let request = |host| {
+        let (client, model_name, format) = (&client, &model_name, &format);
+        async move {
+            match format {
+                Some(format) => client
+                    .generate_json(host, model_name, opts.prompt, format, retries)
+                    .await
+                    .map(|(value, response)| (Some(value), response)),
+                None => client
+                    .generate(host, model_name, opts.prompt)
+                    .await
+                    .map(|response| (None, response)),
+            }
+        }
+    };
+
+    let partner = if opts.race || config.ollama.race {
+        race_partner(config, &client, &mut breaker, host, &model_name).await
+    } else {
+        None
+    };
+    let response = match partner {
+        Some(partner) => {
+            let race = race_hosts([host, partner], &request, |name, ok| {
+                breaker.record(name, ok)
+            });
+            cancellable(&client, race).await
+        }
+        None => {
+            if opts.race {
+                eprintln!("No second host has {model_name}; not racing");
+            }
+            let response = cancellable(&client, request(host)).await;
+            breaker.record(&host.name, response.is_ok());
+            response.map(|response| (host, response))
+        }
+    };
+    breaker.save();
+    let (host, (structured, response)) = response?;
+    output::ask_response(
+        host,
+        &model_name,
+        opts.prompt,
+        &response,
+        structured.as_ref(),
+        opts.json_output,
+    )
+}
+
+/// Await a generation, aborting it on Ctrl-C.
+///
+/// On interrupt the response text streamed so far is printed and the process
+/// exits with 130, the shell convention for SIGINT. While a command
+/// [stops gracefully](super::interrupt), the generation is left to finish.
+pub(super) async fn cancellable<T>(
+    client: &OllamaClient,
+    generation: impl Future<Output = T>,
+) -> T {
+    tokio::select! {
+        result = generation => result,
+        _ = tokio::signal::ctrl_c(), if !interrupt::graceful() => {
+            if let Some(partial) = client.partial_output() {
+                report!("{partial}");
+            }
+            eprintln!("\nInterrupted; generation cancelled");
+            std::process::exit(INTERRUPTED.into());
+        }
+    }
+}
+
+/// Confirm the requested model is on `host`, or pick the first available one.
+pub(super) async fn resolve_model(
+    config: &GuardianConfig,
+    client: &OllamaClient,
+    host: &OllamaHost,
+    requested: Option<&str>,
+) -> Result<String> {
+    let models = client.list_models(host).await;
+
+    let Some(name) = requested else {
+        return models?
+            .first()
+            .map(|m| m.name.clone())
+            .ok_or_else(|| anyhow::anyhow!("No models available on host {}", host.name));
+    };
+
+    match models {
+        Ok(models) if !has_model(&models, name) => {
+            pull_missing_model(config, host, name).await?;
+        }
+        Ok(_) => {}
+        // The model is known; let the generate request report any problem.
+        Err(e) => {
+            tracing::warn!(host = %host.name, error = %e, "Could not verify model availability")
+        }
+    }
+    Ok(name.to_string())
+}
+
+/// Pull a model the host doesn't have, if `auto_pull` allows it.
+async fn pull_missing_model(config: &GuardianConfig, host: &OllamaHost, model: &str) -> Result<()> {
+    if !config.ollama.auto_pull {
+        anyhow::bail!(
+            "Model '{model}' is not available on host {}. Pull it with `ollama pull {model}` or set auto_pull = true",
+            host.name
+        );
+    }
+
+    eprintln!("Model {model} not found on {}, pulling...", host.name);
+    let max_bytes = (config.ollama.auto_pull_max_gb * 1e9) as u64;
+    pull_with_progress(config, host, model, Some(max_bytes)).await
+}
+
+/// Pull `model` onto `host`, showing its progress on stderr. The pull is
+/// aborted once the model turns out larger than `max_bytes`.
+pub(super) async fn pull_with_progress(
+    config: &GuardianConfig,
+    host: &OllamaHost,
+    model: &str,
+    max_bytes: Option<u64>,
+) -> Result<()> {
+    let client = client(config, PULL_TIMEOUT_MS)?;
+    let mut last_status = String::new();
+    let mut mid_line = false;
+    // CI logs keep every redraw, so only report status changes there.
+    let redraw = CiProvider::detect().is_none();
+
+    client
+        .pull_model(host, model, max_bytes, |p| {
+            match (p.total, p.completed) {
+                (Some(total), Some(done)) if total > 0 && redraw => {
+                    eprint!(
+                        "\r  {}: {:>3}% of {:.1} GB",
+                        p.status,
+                        done * 100 / total,
+                        total as f64 / 1e9
+                    );
+                    mid_line = true;
+                }
+                _ if p.status != last_status => {
+                    if mid_line {
+                        eprintln!();
+                        mid_line = false;
+                    }
+                    eprintln!("  {}", p.status);
+                }
+                _ => {}
+            }
+            last_status.clone_from(&p.status);
+        })
+        .await?;
+
+    if mid_line {
+        eprintln!();
+    }
+    Ok(())
+}
+
+/// Tokens a prompt may use on `model`: `[ollama] prompt_budget`, or the
+/// context window less the system prompt and a quarter left for the reply.
+///
+/// `None` when neither is known, so the prompt isn't trimmed.
+pub(super) async fn prompt_budget(
+    config: &GuardianConfig,
+    client: &OllamaClient,
+    host: &OllamaHost,
+    model: &str,
+    system: Option<&str>,
+) -> Option<u64> {
+    if let Some(budget) = config.ollama.prompt_budget {
+        return Some(budget);
+    }
+    let info = client.show_model(host, model).await.ok()?;
+    let window = info.effective_context(config.ollama.num_ctx);
+    Some(window.saturating_sub(window / 4 + system.map_or(0, estimate_tokens)))
+}
+
+/// Warn or fail before sending a prompt that won't fit in the model's context.
+pub(super) async fn check_context_window(
+    config: &GuardianConfig,
+    client: &OllamaClient,
+    host: &OllamaHost,
+    model: &str,
+    system: Option<&str>,
+    prompt: &str,
+) -> Result<()> {
+    let info = match client.show_model(host, model).await {
+        Ok(info) => info,
+        Err(e) => {
+            tracing::debug!(error = %e, "Skipping context window check");
+            return Ok(());
+        }
+    };
+
+    let window = info.effective_context(config.ollama.num_ctx);
+    let tokens = estimate_tokens(prompt) + system.map_or(0, estimate_tokens);
+    if tokens <= window {
+        return Ok(());
+    }
+
+    let msg = format!(
+        "Prompt is ~{tokens} tokens but {model} on {} has a {window}-token context window; \
+        the start of the prompt would be silently truncated. \
+        Raise [ollama] num_ctx or shorten the prompt",
+        host.name
+    );
+    match config.ollama.context_overflow {
+        ContextOverflow::Error => anyhow::bail!(msg),
+        ContextOverflow::Warn => {
+            eprintln!("Warning: {msg}");
+            Ok(())
+        }
+    }
+}
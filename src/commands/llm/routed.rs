@@ -0,0 +1,75 @@
+//! The client, host, and model a `[routing]` task runs on, set up the same
+//! way for every command that asks a model something.
+
+use crate::commands::llm::resolve_model;
+use crate::commands::usage::USAGE_LOG;
+use crate::config::{GuardianConfig, OllamaHost, state};
+use crate::ollama::select::{load_breaker, resolve_host};
+use crate::ollama::{CircuitBreaker, OllamaClient, ProbeCache};
+use anyhow::Result;
+
+/// What a routed task needs to send its requests.
+pub(in crate::commands) struct Routed<'c> {
+    pub client: OllamaClient,
+    /// Where the requests' outcomes are recorded; save it when done.
+    pub breaker: CircuitBreaker,
+    pub host: &'c OllamaHost,
+    pub model: String,
+    /// The task's system prompt, which the client already sends.
+    pub system: Option<String>,
+}
+
+/// A client that gives up after `timeout_ms`, does `--jobs` things at once,
+/// and reuses recent probes for as long as the config says.
+pub(in crate::commands) fn client(
+    config: &GuardianConfig,
+    timeout_ms: u64,
+) -> Result<OllamaClient> {
+    Ok(OllamaClient::new(timeout_ms)?
+        .with_jobs(config.ollama.jobs)
+        .with_probe_cache(ProbeCache {
+            ttl_secs: config.ollama.probe_cache_secs,
+            fresh: config.ollama.fresh,
+        }))
+}
+
+/// A client for `task` that gives up after `timeout_ms`, with the task's
+/// system prompt and the config's context window, queue timeout, and usage
+/// log.
+pub(in crate::commands) fn task_client(
+    config: &GuardianConfig,
+    task: &str,
+    timeout_ms: u64,
+) -> Result<OllamaClient> {
+    Ok(client(config, timeout_ms)?
+        .with_num_ctx(config.ollama.num_ctx)
+        .with_system_prompt(config.route(task).system_prompt)
+        .with_queue_timeout(config.ollama.queue_timeout_secs)
+        .with_usage_log(state::path(USAGE_LOG)))
+}
+
+/// Resolve the host and model for `task`, unless `host` or `model` name
+/// others, with a client for them.
+pub(in crate::commands) async fn routed<'c>(
+    config: &'c GuardianConfig,
+    task: &str,
+    timeout_ms: u64,
+    host: Option<&str>,
+    model: Option<&str>,
+) -> Result<Routed<'c>> {
+    let route = config.route(task);
+    let client = task_client(config, task, timeout_ms)?;
+    let mut breaker = load_breaker(config);
+    let host_name = host.or(route.host.as_deref());
+    let host = resolve_host(config, &client, &mut breaker, host_name).await?;
+    let model = model.map(str::to_string);
+    let model = model.or_else(|| config.model_for(task, host));
+    let model = resolve_model(config, &client, host, model.as_deref()).await?;
+    Ok(Routed {
+        client,
+        breaker,
+        host,
+        model,
+        system: route.system_prompt,
+    })
+}
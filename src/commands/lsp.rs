@@ -0,0 +1,251 @@
+//! LSP command: a minimal language server over stdio.
+//!
+//! Handles `initialize`, `textDocument/didOpen`, `textDocument/didSave`, and
+//! `textDocument/didClose`, running the file-scoped checks on the buffer and
+//! publishing failed results as diagnostics with the check name as the code.
+//! Everything else is ignored, or answered with "method not found".
+
+use crate::checks::{CheckConfig, CheckResult, Severity};
+use crate::checks::{configured_thresholds, run_source_checks, select_checks};
+use crate::config::GuardianConfig;
+use anyhow::{Context, Result, bail};
+use serde_json::{Value, json};
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+
+/// JSON-RPC error code for an unsupported request.
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// Serve LSP on stdin and stdout until the client sends `exit`.
+pub fn lsp(config: &GuardianConfig) -> Result<()> {
+    let thresholds = configured_thresholds(&config.check);
+    let selected = select_checks(None, None, config.check.only.as_deref(), &config.checks)?;
+    let mut input = std::io::stdin().lock();
+    let mut output = std::io::stdout().lock();
+
+    while let Some(message) = read_message(&mut input)? {
+        let method = message["method"].as_str().unwrap_or_default();
+        let params = &message["params"];
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+        let reply = match method {
+            "initialize" => json!({
+                "capabilities": {
+                    "textDocumentSync": {"openClose": true, "save": {"includeText": true}},
+                },
+                "serverInfo": {"name": "guardian-cli", "version": env!("CARGO_PKG_VERSION")},
+            }),
+            "shutdown" => Value::Null,
+            "exit" => return Ok(()),
+            "textDocument/didOpen" | "textDocument/didSave" => {
+                let text = params["textDocument"]["text"]
+                    .as_str()
+                    .or(params["text"].as_str());
+                let diagnostics = diagnose(uri, text, &thresholds, &selected);
+                send(&mut output, &publish(uri, diagnostics))?;
+                continue;
+            }
+            "textDocument/didClose" => {
+                send(&mut output, &publish(uri, Vec::new()))?;
+                continue;
+            }
+            _ => {
+                // Requests need an answer; notifications don't.
+                if let Some(id) = message.get("id") {
+                    let message = format!("Unsupported method {method}");
+                    let error = json!({"code": METHOD_NOT_FOUND, "message": message});
+                    send(
+                        &mut output,
+                        &json!({"jsonrpc": "2.0", "id": id, "error": error}),
+                    )?;
+                }
+                continue;
+            }
+        };
+        send(
+            &mut output,
+            &json!({"jsonrpc": "2.0", "id": message["id"], "result": reply}),
+        )?;
+    }
+    Ok(())
+}
+
+/// Read one `Content-Length` framed message, or `None` at end of input.
+fn read_message(input: &mut impl BufRead) -> Result<Option<Value>> {
+    let mut length = None;
+    loop {
+        let mut header = String::new();
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                length = Some(
+                    value
+                        .trim()
+                        .parse::<usize>()
+                        .context("Bad Content-Length")?,
+                );
+            }
+        }
+    }
+    let Some(length) = length else {
+        bail!("LSP message without a Content-Length header");
+    };
+    let mut body = vec![0; length];
+    input.read_exact(&mut body)?;
+    Ok(Some(
+        serde_json::from_slice(&body).context("Invalid JSON-RPC message")?,
+    ))
+}
+
+fn send(output: &mut impl Write, message: &Value) -> Result<()> {
+    let body = message.to_string();
+    write!(output, "Content-Length: {}\r\n\r\n{body}", body.len())?;
+    output.flush()?;
+    Ok(())
+}
+
+fn publish(uri: &str, diagnostics: Vec<Value>) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": {"uri": uri, "diagnostics": diagnostics},
+    })
+}
+
+/// Diagnostics for the Rust file at `uri`, checking `text` when the client
+/// sent it and the file on disk otherwise.
+fn diagnose(
+    uri: &str,
+    text: Option<&str>,
+    thresholds: &CheckConfig,
+    selected: &[&str],
+) -> Vec<Value> {
+    let Some(path) = uri.strip_prefix("file://").map(percent_decode) else {
+        return Vec::new();
+    };
+    if path.extension().is_none_or(|e| e != "rs") {
+        return Vec::new();
+    }
+    let Some(content) = text
+        .map(str::to_string)
+        .or_else(|| std::fs::read_to_string(&path).ok())
+    else {
+        return Vec::new();
+    };
+    run_source_checks(&path, &content, thresholds, selected)
+        .iter()
+        .filter(|r| !r.passed)
+        .map(diagnostic)
+        .collect()
+}
+
+/// A failed result as an LSP diagnostic, covering its whole line.
+fn diagnostic(result: &CheckResult) -> Value {
+    let line = result.line.unwrap_or(1).saturating_sub(1);
+    let character = result.column.unwrap_or(1).saturating_sub(1);
+    let severity = match result.severity {
+        Severity::Error => 1,
+        Severity::Warning => 2,
+        Severity::Info => 3,
+    };
+    let message = match &result.fix {
+        Some(fix) => format!("{}\nFix: {fix}", result.message),
+        None => result.message.clone(),
+    };
+    json!({
+        "range": {
+            "start": {"line": line, "character": character},
+            "end": {"line": line + 1, "character": 0},
+        },
+        "severity": severity,
+        "code": result.check_name,
+        "source": "guardian",
+        "message": message,
+    })
+}
+
+/// Decode `%XX` escapes in the path part of a `file://` URI.
+fn percent_decode(path: &str) -> PathBuf {
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    PathBuf::from(String::from_utf8_lossy(&decoded).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_framing() {
+        let mut buffer = Vec::new();
+        send(&mut buffer, &json!({"id": 1})).unwrap();
+        send(&mut buffer, &json!({"method": "exit"})).unwrap();
+        let mut input = std::io::Cursor::new(buffer);
+        assert_eq!(read_message(&mut input).unwrap(), Some(json!({"id": 1})));
+        assert_eq!(
+            read_message(&mut input).unwrap(),
+            Some(json!({"method": "exit"}))
+        );
+        assert_eq!(read_message(&mut input).unwrap(), None);
+    }
+
+    #[test]
+    fn test_diagnose_buffer() {
+        let thresholds = CheckConfig {
+            max_functions_per_module: 1,
+            ..CheckConfig::default()
+        };
+        let source = r#"#[allow(clippy::all)]
+fn a() {}
+fn b() {}
+"#;
+        let diagnostics = diagnose(
+            "file:///tmp/my%20crate/src/lib.rs",
+            Some(source),
+            &thresholds,
+            &["function-count", "clippy-disables", "loc-limits"],
+        );
+        let codes: Vec<&str> = diagnostics
+            .iter()
+            .map(|d| d["code"].as_str().unwrap())
+            .collect();
+        assert_eq!(codes, ["function-count", "clippy-disables"]);
+        assert_eq!(diagnostics[0]["range"]["start"]["line"], 2);
+        assert_eq!(diagnostics[1]["severity"], 1);
+        assert!(
+            diagnose(
+                "file:///tmp/README.md",
+                Some(source),
+                &thresholds,
+                &["loc-limits"]
+            )
+            .is_empty()
+        );
+        assert_eq!(
+            percent_decode("/tmp/my%20crate/%zz"),
+            PathBuf::from("/tmp/my crate/%zz")
+        );
+    }
+}
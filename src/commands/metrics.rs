@@ -0,0 +1,234 @@
+//! Prometheus metrics for `serve`'s `/metrics` endpoint.
+//!
+//! Check runs and LLM requests happen in other guardian processes, so they
+//! are read from what those processes record: check runs from the result
+//! history and generate requests from the usage log. Host health comes from
+//! the daemon's own monitor, and cache lookups from the shared cache counts.
+//! Counters cover everything recorded, not just the daemon's lifetime.
+
+use super::usage::USAGE_LOG;
+use crate::config::{StorageSettings, cache_stats, state};
+use crate::ollama::{HealthSnapshot, load_usage};
+use crate::storage::History;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Content type of the text exposition format.
+pub(super) const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// Upper bounds, in seconds, of the LLM request latency buckets.
+const LATENCY_BUCKETS: [f64; 9] = [0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0];
+
+/// Render every metric in the Prometheus text format.
+pub(super) fn render(health: &HealthSnapshot, storage: &StorageSettings) -> String {
+    let mut out = String::new();
+    check_metrics(&mut out, storage);
+    llm_metrics(&mut out);
+    host_metrics(&mut out, health);
+
+    let caches = cache_stats::load();
+    for (name, help) in [
+        (
+            "guardian_cache_hits_total",
+            "Cache lookups that found a usable entry.",
+        ),
+        ("guardian_cache_misses_total", "Cache lookups that didn't."),
+    ] {
+        family(&mut out, name, "counter", help);
+        for (cache, stats) in &caches {
+            let count = if name.contains("hits") {
+                stats.hits
+            } else {
+                stats.misses
+            };
+            let labels = labels(&[("cache", cache)]);
+            let _ = writeln!(out, "{name}{labels} {count}");
+        }
+    }
+    out
+}
+
+/// Check runs, and the violations in each project's newest run, from the
+/// result history.
+fn check_metrics(out: &mut String, storage: &StorageSettings) {
+    let counts = if storage.enabled {
+        History::open(storage)
+            .and_then(|history| Ok((history.runs_recorded()?, history.latest_violations()?)))
+    } else {
+        Ok((0, Vec::new()))
+    };
+    let (runs, violations) = counts.unwrap_or_else(|e| {
+        tracing::warn!(error = %e, "Failed to read the history for metrics");
+        (0, Vec::new())
+    });
+    family(out, "guardian_check_runs_total", "counter", "Check runs.");
+    let _ = writeln!(out, "guardian_check_runs_total {runs}");
+    family(
+        out,
+        "guardian_violations",
+        "gauge",
+        "Failed check results in each project's newest run, by check and severity.",
+    );
+    for (check, severity, count) in &violations {
+        let labels = labels(&[("check", check), ("severity", severity)]);
+        let _ = writeln!(out, "guardian_violations{labels} {count}");
+    }
+}
+
+/// LLM request latency and failures, from the usage log.
+fn llm_metrics(out: &mut String) {
+    let records = state::path(USAGE_LOG)
+        .map(|path| load_usage(&path, 0))
+        .unwrap_or_default();
+    let mut requests: BTreeMap<(&str, &str), Vec<(f64, bool)>> = BTreeMap::new();
+    for record in &records {
+        let seconds = record.duration_ms as f64 / 1000.0;
+        let entry = requests.entry((&record.host, &record.model)).or_default();
+        entry.push((seconds, record.success));
+    }
+    family(
+        out,
+        "guardian_llm_request_duration_seconds",
+        "histogram",
+        "Wall time of LLM generate requests.",
+    );
+    for ((host, model), samples) in &requests {
+        let base = [("host", *host), ("model", *model)];
+        for bound in LATENCY_BUCKETS {
+            let count = samples.iter().filter(|(s, _)| *s <= bound).count();
+            let le = bound.to_string();
+            let labels = labels(&[base[0], base[1], ("le", &le)]);
+            let _ = writeln!(
+                out,
+                "guardian_llm_request_duration_seconds_bucket{labels} {count}"
+            );
+        }
+        let labels_inf = labels(&[base[0], base[1], ("le", "+Inf")]);
+        let sum: f64 = samples.iter().map(|(s, _)| s).sum();
+        let labels = labels(&base);
+        let _ = writeln!(
+            out,
+            "guardian_llm_request_duration_seconds_bucket{labels_inf} {}\n\
+             guardian_llm_request_duration_seconds_sum{labels} {sum}\n\
+             guardian_llm_request_duration_seconds_count{labels} {}",
+            samples.len(),
+            samples.len()
+        );
+    }
+    family(
+        out,
+        "guardian_llm_request_failures_total",
+        "counter",
+        "LLM generate requests that failed.",
+    );
+    for ((host, model), samples) in &requests {
+        let failures = samples.iter().filter(|(_, ok)| !ok).count();
+        let labels = labels(&[("host", host), ("model", model)]);
+        let _ = writeln!(
+            out,
+            "guardian_llm_request_failures_total{labels} {failures}"
+        );
+    }
+}
+
+/// Host availability, from the health monitor.
+fn host_metrics(out: &mut String, health: &HealthSnapshot) {
+    family(
+        out,
+        "guardian_host_up",
+        "gauge",
+        "Whether the health monitor considers the host up.",
+    );
+    for host in &health.hosts {
+        let labels = labels(&[("host", &host.name)]);
+        let _ = writeln!(out, "guardian_host_up{labels} {}", u8::from(host.healthy));
+    }
+    family(
+        out,
+        "guardian_host_availability_ratio",
+        "gauge",
+        "Fraction of recent health pings that succeeded.",
+    );
+    for host in &health.hosts {
+        let labels = labels(&[("host", &host.name)]);
+        let _ = writeln!(
+            out,
+            "guardian_host_availability_ratio{labels} {}",
+            host.availability
+        );
+    }
+    family(
+        out,
+        "guardian_host_ping_latency_seconds",
+        "gauge",
+        "Mean latency of recent successful health pings.",
+    );
+    for host in &health.hosts {
+        if let Some(ms) = host.avg_latency_ms {
+            let labels = labels(&[("host", &host.name)]);
+            let seconds = ms as f64 / 1000.0;
+            let _ = writeln!(out, "guardian_host_ping_latency_seconds{labels} {seconds}");
+        }
+    }
+}
+
+/// Write the `# HELP` and `# TYPE` lines of a metric family.
+fn family(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}\n# TYPE {name} {kind}");
+}
+
+/// A label set, escaping values as the text format requires.
+fn labels(pairs: &[(&str, &str)]) -> String {
+    let pairs: Vec<String> = pairs
+        .iter()
+        .map(|(name, value)| {
+            let value = value
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n");
+            format!("{name}=\"{value}\"")
+        })
+        .collect();
+    format!("{{{}}}", pairs.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_labels_are_escaped() {
+        assert_eq!(
+            labels(&[("host", "big72"), ("model", "a\"b\\c")]),
+            r#"{host="big72",model="a\"b\\c"}"#
+        );
+    }
+
+    #[test]
+    fn test_render_host_health() {
+        let health: HealthSnapshot = serde_json::from_value(serde_json::json!({
+            "updated_at": 0,
+            "interval_secs": 30,
+            "hosts": [{
+                "name": "big72",
+                "healthy": true,
+                "availability": 0.9,
+                "avg_latency_ms": 12,
+                "consecutive_failures": 0,
+                "consecutive_successes": 3,
+                "last_error": null,
+            }],
+        }))
+        .unwrap();
+        let storage = StorageSettings {
+            enabled: false,
+            ..StorageSettings::default()
+        };
+        let text = render(&health, &storage);
+        assert!(text.contains("# TYPE guardian_llm_request_duration_seconds histogram\n"));
+        assert!(text.contains("guardian_host_up{host=\"big72\"} 1\n"));
+        assert!(text.contains("guardian_host_availability_ratio{host=\"big72\"} 0.9\n"));
+        assert!(text.contains("guardian_host_ping_latency_seconds{host=\"big72\"} 0.012\n"));
+        assert!(text.contains("guardian_check_runs_total 0\n"));
+    }
+}
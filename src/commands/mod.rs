@@ -1,20 +1,124 @@
 //! Command implementations for Guardian CLI.
 //!
 //! Commands are organized into modules by function:
+//! - `cli`: Command-line argument definitions
 //! - `host`: Ollama host management (ping, list-models, select-host)
-//! - `config_cmd`: Configuration display
-//! - `llm`: LLM interaction (ask, evaluate)
+//! - `fleet`: Every host's version, installed and loaded models, and disk use
+//! - `config_cmd`: Configuration display and migration
+//! - `llm`: LLM interaction (ask) and shared generation helpers
+//! - `evaluate`: Checks reviewed by an LLM
 //! - `checks`: Code quality checks
+//! - `projects`: Checks on each project under a monorepo root (`check --recursive`)
+//! - `catalog`: The checks' descriptions, severities, and settings (`rules`)
+//! - `apply`: Interactive application of fix diffs
+//! - `pre_push`: Checks on the commits in a push
+//! - `annotate`: Check failures posted on GitLab merge requests or Gitea pull requests
+//! - `govern`: Allow/revise/deny verdicts on actions agents propose
+//! - `suggest_tests`: Model-written tests for public functions without any
+//! - `repair`: Model-written fixes tried in a worktree until the checks pass
+//! - `self_check`: Guardian's checks run on its own source and environment
+//! - `watchdog`: Checks re-run on an interval, notifying on regressions
+//! - `stats`: Open, introduced, and fixed violations per check or git author
+//! - `lsp`: Language server publishing check results as diagnostics
+//! - `rpc`: JSON-RPC over stdio for editor extensions and agents
+//! - `serve`: Daemon mode with host health monitoring and HTTP API
+//! - `schedule`: Check and evaluate jobs `serve` runs on cron schedules
+//! - `api`: `serve`'s authenticated API for submitting jobs and fetching reports
+//! - `queue`: The job queue behind the schedule and the job API
+//! - `dashboard`: The web dashboard `serve` offers at `/`
+//! - `metrics`: Prometheus metrics served by `serve`
+//! - `usage`: Per-host, per-model usage report
+//! - `warmup`: Preloading models into memory
+//! - `prune`: Deleting models hosts no longer need
+//! - `copy_model`: Putting a model one host has onto another
+//! - `secret`: Host credentials in the OS keyring
+//! - `setup`: Interactive setup wizard that finds hosts and writes the config
 //! - `output`: Shared output formatting
+//! - `exit`: Exit codes and the check failure policy
+//! - `interrupt`: Partial results when `check` or `evaluate` is interrupted
+//! - `ci`: CI environment detection and its defaults
+//! - `history`: Recording finished runs in the result history
+//! - `notify`: Webhook notifications of events
+//! - `policy`: Policy expressions that decide whether check results pass
 
+mod annotate;
+mod api;
+mod apply;
+mod catalog;
 mod checks;
+mod ci;
+pub mod cli;
 mod config_cmd;
+mod copy_model;
+mod dashboard;
+mod evaluate;
+mod exit;
+mod explain;
+mod fleet;
+mod followup;
+mod govern;
+mod grounding;
+mod history;
 mod host;
+mod interrupt;
 mod llm;
+mod lsp;
+mod metrics;
+mod notify;
+mod offline;
 mod output;
+mod patches;
+mod policy;
+mod pre_push;
+mod projects;
+mod prune;
+mod queue;
+mod readability;
+mod repair;
+mod rpc;
+mod rules;
+mod saved;
+mod schedule;
+mod secret;
+mod self_check;
+mod serve;
+mod setup;
+mod stats;
+mod suggest_tests;
+mod usage;
+mod verdict;
+mod warmup;
+mod watchdog;
 
 // Re-export public command functions
-pub use checks::{run_checks, CheckOptions};
-pub use config_cmd::{config_path, show_config};
+pub use annotate::{AnnotateOptions, Forge, annotate};
+pub use apply::{ApplyOptions, apply};
+pub use catalog::list_rules;
+pub use checks::{CheckOptions, run_checks};
+pub use ci::CiProvider;
+pub use config_cmd::{config_path, migrate_config, show_config};
+pub use copy_model::{CopyModelOptions, copy_model};
+pub use evaluate::{EvaluateOptions, evaluate};
+pub use exit::{Exit, FailPolicy, exit_code};
+pub use fleet::fleet;
+pub use govern::{GovernOptions, govern};
 pub use host::{list_models, ping_hosts, select_host};
-pub use llm::{ask, evaluate};
+pub use interrupt::stop_gracefully;
+pub use llm::{AskOptions, ask};
+pub use lsp::lsp;
+pub use output::{init_color, init_hyperlinks, init_output};
+pub use policy::Policy;
+pub use pre_push::{PrePushOptions, pre_push};
+pub use projects::check_projects;
+pub use prune::{PruneOptions, prune_models};
+pub use repair::{RepairOptions, repair};
+pub use rpc::rpc;
+pub use secret::{delete_secret, set_secret};
+pub use self_check::{SelfCheckOptions, self_check};
+pub use serve::serve;
+pub use setup::setup;
+pub use stats::{StatsOptions, stats};
+pub use suggest_tests::{SuggestTestsOptions, suggest_tests};
+pub use usage::show_usage;
+pub use warmup::{WarmupOptions, warmup};
+pub use watchdog::{WatchdogOptions, watchdog};
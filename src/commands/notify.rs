@@ -0,0 +1,263 @@
+//! Webhook notifications for the `[[notify]]` targets.
+//!
+//! Each event is a JSON object with the event's name as `event`, a short
+//! human-readable `text`, and the event's own fields. Slack and Discord get
+//! just the text; templates see the whole object. Delivery problems are
+//! logged and never fail the command that raised the event.
+//!
+//! `watchdog` can also raise its regressions as desktop notifications,
+//! through `notify-send` or, on macOS, `osascript`.
+
+use super::output::{Evaluation, fingerprints, result_json};
+use crate::checks::{CheckResult, Severity};
+use crate::config::{NotifyEvent, NotifyTarget, WebhookKind, state};
+use anyhow::Result;
+use minijinja::{Environment, UndefinedBehavior};
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+/// Longest a webhook may take to answer.
+const SEND_TIMEOUT: Duration = Duration::from_secs(10);
+/// Most findings listed in a notification's text.
+const MAX_LISTED: usize = 10;
+/// Discord rejects longer messages.
+const DISCORD_MAX_CHARS: usize = 2000;
+
+/// Tell the targets that want `event` about it.
+pub(super) async fn send(
+    targets: &[NotifyTarget],
+    event: NotifyEvent,
+    text: String,
+    fields: Value,
+) {
+    let targets: Vec<_> = targets.iter().filter(|t| t.wants(event)).collect();
+    if targets.is_empty() {
+        return;
+    }
+    let mut context = json!({"event": event.name(), "text": text});
+    if let (Some(context), Value::Object(fields)) = (context.as_object_mut(), fields) {
+        context.extend(fields);
+    }
+    let client = match reqwest::Client::builder().timeout(SEND_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(e) => {
+            tracing::warn!(error = %e, "Could not create the notification client");
+            return;
+        }
+    };
+
+    let deliveries = targets.into_iter().map(|target| {
+        let (client, context) = (&client, &context);
+        async move {
+            let result = async {
+                let body = payload(target, context)?;
+                client
+                    .post(&target.url)
+                    .header(reqwest::header::CONTENT_TYPE, "application/json")
+                    .body(body)
+                    .send()
+                    .await?
+                    .error_for_status()?;
+                anyhow::Ok(())
+            };
+            if let Err(e) = result.await {
+                tracing::warn!(url = %target.url, error = %e, "Notification failed");
+            }
+        }
+    });
+    futures::future::join_all(deliveries).await;
+}
+
+/// The request body for `target`.
+fn payload(target: &NotifyTarget, context: &Value) -> Result<String> {
+    if let Some(template) = &target.template {
+        let mut env = Environment::new();
+        env.set_undefined_behavior(UndefinedBehavior::Strict);
+        return Ok(env.render_str(template, context)?);
+    }
+    let text = context["text"].as_str().unwrap_or_default();
+    Ok(match target.kind {
+        WebhookKind::Json => context.to_string(),
+        WebhookKind::Slack => json!({"text": text}).to_string(),
+        WebhookKind::Discord => {
+            let text: String = text.chars().take(DISCORD_MAX_CHARS).collect();
+            json!({"content": text}).to_string()
+        }
+    })
+}
+
+/// Notify about errors in `results` that the last check of `project_dir`
+/// didn't find, and remember these errors for the next check.
+///
+/// Errors are matched by fingerprint, like GitLab Code Quality findings, so
+/// moved code and changed counts don't make an error new.
+pub(super) async fn new_errors(
+    targets: &[NotifyTarget],
+    project_dir: &Path,
+    results: &[CheckResult],
+) {
+    if !targets.iter().any(|t| t.wants(NotifyEvent::NewErrors)) {
+        return;
+    }
+    let Some(path) = state::path("errors.json") else {
+        return;
+    };
+    let project = project_dir
+        .canonicalize()
+        .unwrap_or_else(|_| project_dir.to_path_buf())
+        .display()
+        .to_string();
+    let errors: Vec<&CheckResult> = results
+        .iter()
+        .filter(|r| !r.passed && r.severity == Severity::Error)
+        .collect();
+    let ids = fingerprints(&errors);
+    let mut known: BTreeMap<String, Vec<String>> = state::read(&path).unwrap_or_default();
+    let previous = known
+        .insert(project.clone(), ids.clone())
+        .unwrap_or_default();
+    state::write(&path, &known);
+
+    let new: Vec<&CheckResult> = errors
+        .into_iter()
+        .zip(&ids)
+        .filter(|(_, id)| !previous.contains(id))
+        .map(|(result, _)| result)
+        .collect();
+    if new.is_empty() {
+        return;
+    }
+    let mut text = format!("guardian found {} new error(s) in {project}", new.len());
+    for result in new.iter().take(MAX_LISTED) {
+        text.push_str(&format!("\n- [{}] {}", result.check_name, result.message));
+    }
+    let errors: Vec<Value> = new.iter().map(|r| result_json(r)).collect();
+    let fields = json!({"project": project, "errors": errors});
+    send(targets, NotifyEvent::NewErrors, text, fields).await;
+}
+
+/// Notify about an `evaluate` run's verdict.
+pub(super) async fn evaluate_verdict(
+    targets: &[NotifyTarget],
+    project_dir: &Path,
+    results: &[CheckResult],
+    evaluation: Option<&Evaluation<'_>>,
+    passed: bool,
+) {
+    let failed: Vec<&CheckResult> = results.iter().filter(|r| !r.passed).collect();
+    let errors = failed
+        .iter()
+        .filter(|r| r.severity == Severity::Error)
+        .count();
+    let warnings = failed.len() - errors;
+    let verdict = if passed { "passed" } else { "failed" };
+    let project = project_dir.display();
+    let text = format!(
+        "guardian evaluate of {project} {verdict}: {errors} error(s), {warnings} warning(s)"
+    );
+    let fields = json!({
+        "project": project.to_string(),
+        "passed": passed,
+        "errors": errors,
+        "warnings": warnings,
+        "violations": failed.iter().map(|r| result_json(r)).collect::<Vec<_>>(),
+        "host": evaluation.and_then(|e| e.judge).map(|(host, _)| host),
+        "model": evaluation.and_then(|e| e.judge).map(|(_, model)| model),
+        "evaluation": evaluation.map(|e| e.text),
+        "offline": evaluation.is_some_and(|e| e.judge.is_none()),
+    });
+    send(targets, NotifyEvent::Evaluate, text, fields).await;
+}
+
+/// Notify about a `watchdog` run of `project` that has `new` errors, or a
+/// lower `score`, than its last known-good run, which scored `good_score`.
+pub(super) async fn regression(
+    targets: &[NotifyTarget],
+    project: &str,
+    new: &[&CheckResult],
+    score: f64,
+    good_score: f64,
+    desktop: bool,
+) {
+    let mut text = format!("guardian watchdog: {project} regressed");
+    if !new.is_empty() {
+        text.push_str(&format!(", {} new error(s)", new.len()));
+    }
+    if score < good_score {
+        text.push_str(&format!(", score {score} down from {good_score}"));
+    }
+    for result in new.iter().take(MAX_LISTED) {
+        text.push_str(&format!("\n- [{}] {}", result.check_name, result.message));
+    }
+    if desktop {
+        desktop_notification(&text);
+    }
+    let errors: Vec<Value> = new.iter().map(|r| result_json(r)).collect();
+    let fields = json!({
+        "project": project,
+        "errors": errors,
+        "score": score,
+        "known_good_score": good_score,
+    });
+    send(targets, NotifyEvent::Regression, text, fields).await;
+}
+
+/// Show `text` as a desktop notification, its first line as the title.
+fn desktop_notification(text: &str) {
+    let (title, body) = text.split_once('\n').unwrap_or((text, ""));
+    let shown = if cfg!(target_os = "macos") {
+        let quote = |s: &str| s.replace('\\', "\\\\").replace('"', "\\\"");
+        let script = format!(
+            "display notification \"{}\" with title \"{}\"",
+            quote(body),
+            quote(title)
+        );
+        Command::new("osascript").args(["-e", &script]).status()
+    } else {
+        Command::new("notify-send").args([title, body]).status()
+    };
+    match shown {
+        Ok(status) if status.success() => {}
+        Ok(status) => tracing::warn!("Desktop notification failed: {status}"),
+        Err(e) => tracing::warn!("Desktop notification failed: {e}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(kind: WebhookKind, template: Option<&str>) -> NotifyTarget {
+        NotifyTarget {
+            url: "http://127.0.0.1:9/hook".to_string(),
+            kind,
+            events: Vec::new(),
+            template: template.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_payloads() {
+        let context = json!({"event": "host_down", "text": "big72 is down", "host": "big72"});
+        let body = |target| serde_json::from_str::<Value>(&payload(&target, &context).unwrap());
+        assert_eq!(body(target(WebhookKind::Json, None)).unwrap(), context);
+        assert_eq!(
+            body(target(WebhookKind::Slack, None)).unwrap(),
+            json!({"text": "big72 is down"})
+        );
+        assert_eq!(
+            body(target(WebhookKind::Discord, None)).unwrap(),
+            json!({"content": "big72 is down"})
+        );
+        let custom = target(
+            WebhookKind::Json,
+            Some(r#"{"alert": "{{ event }}: {{ host }}"}"#),
+        );
+        assert_eq!(body(custom).unwrap(), json!({"alert": "host_down: big72"}));
+        let typo = target(WebhookKind::Json, Some("{{ hots }}"));
+        assert!(payload(&typo, &context).is_err());
+    }
+}
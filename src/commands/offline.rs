@@ -0,0 +1,121 @@
+//! Rule-based summary of check failures, for `evaluate` without an LLM.
+//!
+//! Used with `--offline` and when no host is reachable, so air-gapped
+//! runners still get a review and gating. The summary is deterministic: the
+//! same results always read the same, most severe checks first.
+
+use crate::checks::{CheckResult, Severity};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Files listed per check before the rest are counted.
+const MAX_FILES: usize = 5;
+
+/// Summarize the failures in `results`, grouped by check, in fix order.
+pub(super) fn rule_based_summary(results: &[CheckResult]) -> String {
+    let mut checks: BTreeMap<&str, Vec<&CheckResult>> = BTreeMap::new();
+    for result in results.iter().filter(|r| !r.passed) {
+        checks.entry(&result.check_name).or_default().push(result);
+    }
+    let worst = |failures: &[&CheckResult]| {
+        let rank = |r: &&CheckResult| match r.severity {
+            Severity::Info => 0,
+            Severity::Warning => 1,
+            Severity::Error => 2,
+        };
+        failures.iter().map(rank).max().unwrap_or(0)
+    };
+    let mut order: Vec<_> = checks.into_iter().collect();
+    // Most severe first, then the checks with the most failures; the map
+    // already sorted ties by name.
+    order.sort_by(|(_, a), (_, b)| worst(b).cmp(&worst(a)).then(b.len().cmp(&a.len())));
+
+    let failed = results.iter().filter(|r| !r.passed);
+    let errors = failed
+        .clone()
+        .filter(|r| r.severity == Severity::Error)
+        .count();
+    let warnings = failed.filter(|r| r.severity == Severity::Warning).count();
+    let mut summary = format!(
+        "{} check(s) failed: {errors} error(s), {warnings} warning(s).\n\nFix in this order:\n",
+        order.len()
+    );
+    for (rank, (check, failures)) in order.iter().enumerate() {
+        let severity = ["info", "warning", "error"][worst(failures)];
+        let _ = writeln!(
+            summary,
+            "\n{}. {check} [{severity}], {} violation(s): {}",
+            rank + 1,
+            failures.len(),
+            failures[0].message
+        );
+        let mut files = distinct(failures.iter().filter_map(|r| r.file.as_deref()));
+        if !files.is_empty() {
+            let more = files.len().saturating_sub(MAX_FILES);
+            files.truncate(MAX_FILES);
+            let more = if more > 0 {
+                format!(" and {more} more")
+            } else {
+                String::new()
+            };
+            let _ = writeln!(summary, "   Files: {}{more}", files.join(", "));
+        }
+        for fix in distinct(failures.iter().filter_map(|r| r.fix.as_deref())) {
+            let _ = writeln!(summary, "   Fix: {fix}");
+        }
+    }
+    summary
+}
+
+/// The distinct `items`, in the order they first appear.
+fn distinct<'a>(items: impl Iterator<Item = &'a str>) -> Vec<&'a str> {
+    let mut seen = Vec::new();
+    for item in items {
+        if !seen.contains(&item) {
+            seen.push(item);
+        }
+    }
+    seen
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_orders_checks_by_severity() {
+        let results = [
+            CheckResult::pass("rust-edition", "Edition 2024"),
+            CheckResult::fail("module-count", Severity::Warning, "9 modules")
+                .with_file("src/lib.rs"),
+            CheckResult::fail("loc-limits", Severity::Error, "main.rs: 612 lines")
+                .with_file("src/main.rs")
+                .with_fix("Split main.rs"),
+            CheckResult::fail("loc-limits", Severity::Warning, "cli.rs: 380 lines")
+                .with_file("src/cli.rs")
+                .with_fix("Split cli.rs"),
+        ];
+        let summary = rule_based_summary(&results);
+        assert!(summary.starts_with("2 check(s) failed: 1 error(s), 2 warning(s)."));
+        let loc = summary
+            .find("1. loc-limits [error], 2 violation(s)")
+            .unwrap();
+        let modules = summary.find("2. module-count [warning]").unwrap();
+        assert!(loc < modules);
+        assert!(summary.contains("Files: src/main.rs, src/cli.rs\n"));
+        assert!(summary.contains("Fix: Split main.rs\n   Fix: Split cli.rs\n"));
+        assert_eq!(summary, rule_based_summary(&results));
+    }
+
+    #[test]
+    fn test_summary_counts_extra_files() {
+        let results: Vec<_> = (0..7)
+            .map(|i| {
+                CheckResult::fail("clippy-disables", Severity::Warning, "allow")
+                    .with_file(&format!("src/m{i}.rs"))
+            })
+            .collect();
+        let summary = rule_based_summary(&results);
+        assert!(summary.contains("src/m4.rs and 2 more\n"));
+    }
+}
@@ -0,0 +1,62 @@
+//! Output for `rules`: the check catalog.
+
+use super::color::{self, Style, paint};
+use super::sink::write_report;
+use crate::checks::Severity;
+use crate::checks::catalog::CheckInfo;
+use anyhow::Result;
+use std::fmt::Write;
+
+/// Report each check with its severities and the settings it reads, or
+/// the catalog as JSON.
+pub fn catalog_report(catalog: &[CheckInfo], json_output: bool) -> Result<()> {
+    let report = if json_output {
+        format!("{}\n", serde_json::to_string_pretty(catalog)?)
+    } else {
+        render_text(catalog, color::enabled())
+    };
+    write_report(&report);
+    Ok(())
+}
+
+fn render_text(catalog: &[CheckInfo], color: bool) -> String {
+    let mut out = String::new();
+    for check in catalog {
+        let mut tags: Vec<_> = check
+            .severities
+            .iter()
+            .map(|severity| match severity {
+                Severity::Info => "info",
+                Severity::Warning => "warning",
+                Severity::Error => "error",
+            })
+            .collect();
+        if check.opt_in {
+            tags.push("opt-in");
+        }
+        let _ = writeln!(out, "{} [{}]", check.name, tags.join(", "));
+        let _ = writeln!(out, "    {}", check.description);
+        for option in &check.options {
+            let setting = format!("{} = {}", option.key, option.default);
+            let about = paint(&format!("  # {}", option.description), Style::Dim, color);
+            let _ = writeln!(out, "    {setting}{about}");
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checks::catalog::catalog;
+
+    #[test]
+    fn test_render_text_lists_every_check() {
+        let text = render_text(&catalog(), false);
+        assert!(text.starts_with("rust-edition [error]\n"));
+        assert!(text.contains("    check.max_loc = 500  # Lines a file may have"));
+        assert!(text.contains("llm-readability [warning, opt-in]\n"));
+        assert_eq!(text.matches("\n\n").count(), catalog().len());
+    }
+}
@@ -0,0 +1,166 @@
+//! Output for checklist results.
+
+use super::color;
+use super::sink::write_report;
+use super::text::{render_explanations, render_text};
+use super::{checkstyle, csv, github, gitlab, html, junit, markdown, short, template};
+use crate::checks::{CheckResult, Severity};
+use crate::commands::cli::{GroupBy, ReportFormat};
+use crate::commands::explain::Explanation;
+use anyhow::{Context, Result};
+use std::path::Path;
+
+/// Pass/fail counts over a set of check results.
+pub(super) struct CheckSummary {
+    pub total: usize,
+    pub passed: usize,
+    pub failed: usize,
+    pub errors: usize,
+    pub warnings: usize,
+}
+
+impl CheckSummary {
+    pub fn of(results: &[CheckResult]) -> Self {
+        let passed = results.iter().filter(|r| r.passed).count();
+        let failed = results.len() - passed;
+        let errors = results
+            .iter()
+            .filter(|r| !r.passed && r.severity == Severity::Error)
+            .count();
+        Self {
+            total: results.len(),
+            passed,
+            failed,
+            errors,
+            warnings: failed - errors,
+        }
+    }
+}
+
+/// Format check results and write the report.
+///
+/// `explanations` from `--explain-llm` follow the results in text output
+/// and go under `explanations` in JSON; other formats leave them out.
+pub fn check_results(
+    results: &[CheckResult],
+    explanations: &[Explanation],
+    format: ReportFormat,
+    group_by: GroupBy,
+    template: Option<&Path>,
+) -> Result<()> {
+    let color = color::enabled();
+    let report = render(results, explanations, format, group_by, template, color)?;
+    write_report(&report);
+    Ok(())
+}
+
+/// Format check results as a report, uncolored and grouped by check, for
+/// `serve`'s job API.
+pub fn check_report(results: &[CheckResult], format: ReportFormat) -> Result<String> {
+    render(results, &[], format, GroupBy::Check, None, false)
+}
+
+fn render(
+    results: &[CheckResult],
+    explanations: &[Explanation],
+    format: ReportFormat,
+    group_by: GroupBy,
+    template: Option<&Path>,
+    color: bool,
+) -> Result<String> {
+    let summary = CheckSummary::of(results);
+    Ok(match format {
+        ReportFormat::Text => {
+            render_text(results, &summary, group_by, color)
+                + &render_explanations(explanations, color)
+        }
+        ReportFormat::Json => render_json(results, explanations, &summary, group_by)?,
+        ReportFormat::Junit => junit::junit_report(results),
+        ReportFormat::Github => github::github_report(results)?,
+        ReportFormat::Gitlab => gitlab::gitlab_report(results)? + "\n",
+        ReportFormat::Checkstyle => checkstyle::checkstyle_report(results),
+        ReportFormat::Markdown => markdown::markdown_report(results),
+        ReportFormat::Html => html::html_report(results, None),
+        ReportFormat::Csv => csv::csv_report(results),
+        ReportFormat::Short => short::short_report(results),
+        ReportFormat::Template => {
+            let path = template.context("--format template needs --template FILE")?;
+            template::template_report(results, path)?
+        }
+    })
+}
+
+/// Results grouped by file, in order of first appearance.
+///
+/// A leading `./` is ignored, results are ordered by line within each file,
+/// and results without a file come last under `None`.
+pub(super) fn file_groups(results: &[CheckResult]) -> Vec<(Option<&str>, Vec<&CheckResult>)> {
+    let mut groups: Vec<(Option<&str>, Vec<&CheckResult>)> = Vec::new();
+    for result in results {
+        let file = result.file.as_deref().map(|f| f.trim_start_matches("./"));
+        match groups.iter_mut().find(|(f, _)| *f == file) {
+            Some((_, group)) => group.push(result),
+            None => groups.push((file, vec![result])),
+        }
+    }
+    groups.sort_by_key(|(file, _)| file.is_none());
+    for (_, group) in &mut groups {
+        group.sort_by_key(|r| r.line);
+    }
+    groups
+}
+
+/// One result as a JSON object, as used by the JSON and template formats.
+/// Only results of a `check --recursive` run name their project.
+pub fn result_json(r: &CheckResult) -> serde_json::Value {
+    let mut json = serde_json::json!({
+        "check": r.check_name,
+        "passed": r.passed,
+        "severity": format!("{:?}", r.severity).to_lowercase(),
+        "message": r.message,
+        "file": r.file,
+        "line": r.line,
+        "fix": r.fix,
+        "diff": r.diff,
+        "blame": r.blame,
+    });
+    if let Some(project) = &r.project {
+        json["project"] = project.as_str().into();
+    }
+    json
+}
+
+fn render_json(
+    results: &[CheckResult],
+    explanations: &[Explanation],
+    summary: &CheckSummary,
+    group_by: GroupBy,
+) -> Result<String> {
+    let mut report = serde_json::json!({
+        "total": summary.total,
+        "passed": summary.passed,
+        "failed": summary.failed,
+        "errors": summary.errors,
+    });
+    let (key, value): (&str, serde_json::Value) = match group_by {
+        GroupBy::Check => ("results", results.iter().map(result_json).collect()),
+        GroupBy::File => (
+            "files",
+            file_groups(results)
+                .into_iter()
+                .map(|(file, group)| {
+                    serde_json::json!({
+                        "file": file,
+                        "results": group.into_iter().map(result_json).collect::<Vec<_>>(),
+                    })
+                })
+                .collect(),
+        ),
+    };
+    report[key] = value;
+    if !explanations.is_empty() {
+        report["explanations"] = serde_json::to_value(explanations)?;
+    }
+
+    Ok(serde_json::to_string_pretty(&report)? + "\n")
+}
@@ -0,0 +1,71 @@
+//! Checkstyle XML, understood by many CI plugins and editor integrations.
+
+use super::gitlab::PROJECT_PATH;
+use super::junit::escape_xml;
+use crate::checks::{CheckResult, Severity};
+use std::fmt::Write;
+
+/// Render failed results as a Checkstyle report, grouped by file.
+pub fn checkstyle_report(results: &[CheckResult]) -> String {
+    let mut files: Vec<(&str, Vec<&CheckResult>)> = Vec::new();
+    for result in results.iter().filter(|r| !r.passed) {
+        let path = result
+            .file
+            .as_deref()
+            .map_or(PROJECT_PATH, |f| f.trim_start_matches("./"));
+        match files.iter_mut().find(|(p, _)| *p == path) {
+            Some((_, group)) => group.push(result),
+            None => files.push((path, vec![result])),
+        }
+    }
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<checkstyle version=\"4.3\">\n");
+    for (path, group) in files {
+        let _ = writeln!(xml, "  <file name=\"{}\">", escape_xml(path));
+        for result in group {
+            let severity = match result.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+                Severity::Info => "info",
+            };
+            let _ = writeln!(
+                xml,
+                "    <error line=\"{}\" severity=\"{severity}\" message=\"{}\" source=\"guardian.{}\"/>",
+                result.line.unwrap_or(1),
+                escape_xml(&result.message),
+                escape_xml(&result.check_name)
+            );
+        }
+        xml.push_str("  </file>\n");
+    }
+    xml.push_str("</checkstyle>\n");
+    xml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_groups_failures_by_file() {
+        let mut a = CheckResult::fail("loc-limits", Severity::Error, "612 > 500 lines");
+        a.file = Some("./src/main.rs".to_string());
+        let mut b = CheckResult::fail("test-quality", Severity::Warning, "trivial 'assert' & co");
+        b.file = Some("src/main.rs".to_string());
+        b.line = Some(40);
+        let c = CheckResult::fail("module-count", Severity::Info, "5 modules");
+        let results = [CheckResult::pass("rust-edition", "ok"), a, b, c];
+
+        let xml = checkstyle_report(&results);
+        assert_eq!(xml.matches("<file ").count(), 2);
+        assert!(xml.contains(
+            "  <file name=\"src/main.rs\">\n    \
+             <error line=\"1\" severity=\"error\" message=\"612 &gt; 500 lines\" source=\"guardian.loc-limits\"/>\n    \
+             <error line=\"40\" severity=\"warning\" message=\"trivial &apos;assert&apos; &amp; co\" source=\"guardian.test-quality\"/>\n  \
+             </file>"
+        ));
+        assert!(xml.contains("<file name=\"Cargo.toml\">"));
+        assert!(xml.contains("severity=\"info\""));
+    }
+}
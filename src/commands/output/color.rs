@@ -0,0 +1,80 @@
+//! ANSI color for human-readable output.
+//!
+//! Whether to color is decided once at startup from `--color`, `NO_COLOR`,
+//! whether reports go to a terminal, and whether this is a CI job.
+
+use crate::commands::ci::CiProvider;
+use crate::commands::cli::ColorChoice;
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Styles used by the text output.
+#[derive(Debug, Clone, Copy)]
+pub(super) enum Style {
+    Green,
+    Yellow,
+    Red,
+    Dim,
+}
+
+/// Decide whether reports are colored for the rest of the run.
+///
+/// Call after [`super::init_output`].
+pub fn init_color(choice: ColorChoice) {
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    let terminal = !super::to_file() && std::io::stdout().is_terminal();
+    let ci = CiProvider::detect().is_some();
+    let enabled = resolve(choice, no_color, terminal && !ci);
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// An explicit `--color` wins over `NO_COLOR`; `auto` needs a terminal
+/// outside CI.
+fn resolve(choice: ColorChoice, no_color: bool, terminal: bool) -> bool {
+    match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => terminal && !no_color,
+    }
+}
+
+/// Whether reports should be colored.
+pub(super) fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Wrap `text` in the escape codes for `style` when `color` is set.
+pub(super) fn paint(text: &str, style: Style, color: bool) -> String {
+    if !color {
+        return text.to_string();
+    }
+    let code = match style {
+        Style::Green => "32",
+        Style::Yellow => "33",
+        Style::Red => "31",
+        Style::Dim => "2",
+    };
+    format!("\x1b[{code}m{text}\x1b[0m")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_color_choice() {
+        assert!(resolve(ColorChoice::Auto, false, true));
+        assert!(!resolve(ColorChoice::Auto, false, false));
+        assert!(!resolve(ColorChoice::Auto, true, true));
+        assert!(resolve(ColorChoice::Always, true, false));
+        assert!(!resolve(ColorChoice::Never, false, true));
+    }
+
+    #[test]
+    fn test_paint() {
+        assert_eq!(paint("OK", Style::Green, true), "\x1b[32mOK\x1b[0m");
+        assert_eq!(paint("OK", Style::Green, false), "OK");
+    }
+}
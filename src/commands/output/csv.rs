@@ -0,0 +1,60 @@
+//! CSV export, one row per result, for spreadsheets and BI tools.
+
+use crate::checks::{CheckResult, Severity};
+use std::fmt::Write;
+
+/// Column order is part of the format; append new columns at the end.
+const HEADER: &str = "check,severity,passed,file,line,message,fix";
+
+/// Render every result (passed or not) as RFC 4180 CSV.
+pub fn csv_report(results: &[CheckResult]) -> String {
+    let mut csv = format!("{HEADER}\r\n");
+    for result in results {
+        let severity = match result.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "info",
+        };
+        let _ = write!(
+            csv,
+            "{},{severity},{},{},{},{},{}\r\n",
+            field(&result.check_name),
+            result.passed,
+            field(result.file.as_deref().unwrap_or("")),
+            result.line.map(|l| l.to_string()).unwrap_or_default(),
+            field(&result.message),
+            field(result.fix.as_deref().unwrap_or(""))
+        );
+    }
+    csv
+}
+
+/// Quote a field if it contains a delimiter, quote, or line break.
+fn field(value: &str) -> String {
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_report() {
+        let mut fail = CheckResult::fail("loc-limits", Severity::Error, "612 lines, \"main\"");
+        fail.file = Some("src/main.rs".to_string());
+        fail.line = Some(3);
+        fail.fix = Some("Split\ninto modules".to_string());
+        let results = [CheckResult::pass("rust-edition", "ok"), fail];
+
+        assert_eq!(
+            csv_report(&results),
+            "check,severity,passed,file,line,message,fix\r\n\
+             rust-edition,info,true,,,ok,\r\n\
+             loc-limits,error,false,src/main.rs,3,\"612 lines, \"\"main\"\"\",\"Split\ninto modules\"\r\n"
+        );
+    }
+}
@@ -0,0 +1,125 @@
+//! Output for `fleet`: each host's version, disk use, and models.
+
+use super::color::{self, Style, paint};
+use super::sink::write_report;
+use crate::commands::fleet::HostInventory;
+use anyhow::Result;
+use std::fmt::Write;
+
+/// Report the fleet as a table of hosts, then one of their models.
+pub fn fleet_report(inventories: &[HostInventory], json_output: bool) -> Result<()> {
+    let report = if json_output {
+        format!("{}\n", serde_json::to_string_pretty(inventories)?)
+    } else {
+        render_text(inventories, color::enabled())
+    };
+    write_report(&report);
+    Ok(())
+}
+
+/// `bytes` in gigabytes.
+fn gb(bytes: u64) -> String {
+    format!("{:.1} GB", bytes as f64 / 1e9)
+}
+
+fn render_text(inventories: &[HostInventory], color: bool) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{:<16} {:<10} {:>6} {:>10}  LOADED",
+        "HOST", "VERSION", "MODELS", "DISK"
+    );
+    for inventory in inventories {
+        if let Some(error) = &inventory.error {
+            let down = paint("DOWN", Style::Red, color);
+            let _ = writeln!(out, "{:<16} {down}: {error}", inventory.host);
+            continue;
+        }
+        let _ = writeln!(
+            out,
+            "{:<16} {:<10} {:>6} {:>10}  {}",
+            inventory.host,
+            inventory.version.as_deref().unwrap_or("unknown"),
+            inventory.models.len(),
+            gb(inventory.disk_bytes),
+            inventory.loaded.join(", ")
+        );
+    }
+
+    let up: Vec<_> = inventories.iter().filter(|i| i.reachable).collect();
+    if up.iter().any(|i| !i.models.is_empty()) {
+        let _ = writeln!(out, "\n{:<16} {:<32} {:>10}", "HOST", "MODEL", "SIZE");
+        for inventory in &up {
+            for model in &inventory.models {
+                let size = model.size.map(gb).unwrap_or_default();
+                let loaded = if inventory.loaded.contains(&model.name) {
+                    paint("  loaded", Style::Green, color)
+                } else {
+                    String::new()
+                };
+                let name = &model.name;
+                let _ = writeln!(out, "{:<16} {name:<32} {size:>10}{loaded}", inventory.host);
+            }
+        }
+    }
+
+    let models: usize = up.iter().map(|i| i.models.len()).sum();
+    let disk = gb(up.iter().map(|i| i.disk_bytes).sum());
+    let (reachable, total) = (up.len(), inventories.len());
+    let _ = writeln!(
+        out,
+        "\n{reachable}/{total} hosts reachable, {models} models, {disk} on disk"
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ollama::OllamaModel;
+
+    #[test]
+    fn test_render_text_lists_hosts_and_models() {
+        let model = |name: &str, size| OllamaModel {
+            name: name.to_string(),
+            modified_at: None,
+            size: Some(size),
+            digest: None,
+        };
+        let inventories = [
+            HostInventory {
+                host: "big72".to_string(),
+                reachable: true,
+                version: Some("0.5.7".to_string()),
+                models: vec![
+                    model("qwen2.5-coder:14b", 9_000_000_000),
+                    model("phi4:14b", 9_100_000_000),
+                ],
+                loaded: vec!["qwen2.5-coder:14b".to_string()],
+                disk_bytes: 18_100_000_000,
+                error: None,
+            },
+            HostInventory {
+                host: "local".to_string(),
+                reachable: false,
+                version: None,
+                models: Vec::new(),
+                loaded: Vec::new(),
+                disk_bytes: 0,
+                error: Some("connection refused".to_string()),
+            },
+        ];
+        let text = render_text(&inventories, false);
+        let lines: Vec<_> = text.lines().collect();
+        assert_eq!(
+            lines[1],
+            "big72            0.5.7           2    18.1 GB  qwen2.5-coder:14b"
+        );
+        assert_eq!(lines[2], "local            DOWN: connection refused");
+        assert!(
+            text.contains("big72            qwen2.5-coder:14b                    9.0 GB  loaded\n")
+        );
+        assert!(text.contains("big72            phi4:14b                             9.1 GB\n"));
+        assert!(text.ends_with("\n1/2 hosts reachable, 2 models, 18.1 GB on disk\n"));
+    }
+}
@@ -0,0 +1,90 @@
+//! GitHub Actions workflow commands for inline annotations.
+//!
+//! Failed results are printed as `::error` / `::warning` / `::notice`
+//! commands, which Actions turns into annotations on the changed lines. The
+//! Markdown report is appended to the job summary page as well.
+
+use super::markdown::markdown_report;
+use crate::checks::{CheckResult, Severity};
+use anyhow::{Context, Result};
+use std::fmt::Write;
+use std::io::Write as _;
+
+/// Render annotations, and write the job summary when running in Actions.
+pub fn github_report(results: &[CheckResult]) -> Result<String> {
+    if let Ok(path) = std::env::var("GITHUB_STEP_SUMMARY") {
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| file.write_all(markdown_report(results).as_bytes()))
+            .with_context(|| format!("Failed to write job summary to {path}"))?;
+    }
+    Ok(annotations(results))
+}
+
+/// One workflow command per failed result.
+fn annotations(results: &[CheckResult]) -> String {
+    let mut out = String::new();
+    for result in results.iter().filter(|r| !r.passed) {
+        let level = match result.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "notice",
+        };
+        let mut props = vec![format!("title={}", escape_property(&result.check_name))];
+        if let Some(file) = &result.file {
+            props.push(format!(
+                "file={}",
+                escape_property(file.trim_start_matches("./"))
+            ));
+            if let Some(line) = result.line {
+                props.push(format!("line={line}"));
+            }
+        }
+        let mut message = result.message.clone();
+        if let Some(fix) = &result.fix {
+            let _ = write!(message, "\nFix: {fix}");
+        }
+        let _ = writeln!(
+            out,
+            "::{level} {}::{}",
+            props.join(","),
+            escape_data(&message)
+        );
+    }
+    out
+}
+
+/// Escape a workflow command message.
+fn escape_data(s: &str) -> String {
+    s.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a workflow command property value.
+fn escape_property(s: &str) -> String {
+    escape_data(s).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_annotations() {
+        let mut fail = CheckResult::fail("loc-limits", Severity::Error, "612 lines, 100% over");
+        fail.file = Some("./src/a,b.rs".to_string());
+        fail.line = Some(12);
+        fail.fix = Some("Split it".to_string());
+        let warn = CheckResult::fail("test-quality", Severity::Warning, "Trivial test");
+        let results = [CheckResult::pass("rust-edition", "ok"), fail, warn];
+
+        assert_eq!(
+            annotations(&results),
+            "::error title=loc-limits,file=src/a%2Cb.rs,line=12::612 lines, 100%25 over%0AFix: Split it\n\
+             ::warning title=test-quality::Trivial test\n"
+        );
+    }
+}
@@ -0,0 +1,147 @@
+//! GitLab Code Quality report for the merge request widget.
+//!
+//! GitLab diffs the report of the source branch against the target branch
+//! by fingerprint, so fingerprints must identify the same finding across
+//! runs: they hash the check, the file, and the message with its digits
+//! removed (so "612 lines" and "640 lines" in the same file match), but not
+//! the line number.
+
+use crate::checks::{CheckResult, Severity};
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Findings without a file are reported against the crate manifest.
+pub(super) const PROJECT_PATH: &str = "Cargo.toml";
+
+#[derive(Debug, Serialize)]
+struct Issue<'a> {
+    description: &'a str,
+    check_name: &'a str,
+    fingerprint: String,
+    severity: &'static str,
+    location: Location<'a>,
+}
+
+#[derive(Debug, Serialize)]
+struct Location<'a> {
+    path: &'a str,
+    lines: Lines,
+}
+
+#[derive(Debug, Serialize)]
+struct Lines {
+    begin: usize,
+}
+
+/// Render failed results as a Code Quality JSON array.
+pub fn gitlab_report(results: &[CheckResult]) -> Result<String> {
+    let failures: Vec<&CheckResult> = results.iter().filter(|r| !r.passed).collect();
+    let issues: Vec<_> = failures
+        .iter()
+        .zip(fingerprints(&failures))
+        .map(|(r, fingerprint)| Issue {
+            description: &r.message,
+            check_name: &r.check_name,
+            fingerprint,
+            severity: match r.severity {
+                Severity::Error => "major",
+                Severity::Warning => "minor",
+                Severity::Info => "info",
+            },
+            location: Location {
+                path: finding_path(r),
+                lines: Lines {
+                    begin: r.line.unwrap_or(1),
+                },
+            },
+        })
+        .collect();
+    Ok(serde_json::to_string_pretty(&issues)?)
+}
+
+/// The repository path a finding is reported against.
+pub fn finding_path(result: &CheckResult) -> &str {
+    result
+        .file
+        .as_deref()
+        .map_or(PROJECT_PATH, |f| f.trim_start_matches("./"))
+}
+
+/// A fingerprint for each of `failures` that identifies it across runs.
+pub fn fingerprints(failures: &[&CheckResult]) -> Vec<String> {
+    let mut seen: HashMap<u64, u64> = HashMap::new();
+    failures
+        .iter()
+        .map(|r| {
+            // Identical findings in one file still need distinct fingerprints.
+            let base = fingerprint(&r.check_name, finding_path(r), &r.message);
+            let occurrence = seen.entry(base).or_default();
+            *occurrence += 1;
+            format!("{:016x}{:016x}", base, *occurrence)
+        })
+        .collect()
+}
+
+/// Stable 64-bit FNV-1a hash of a finding, ignoring digits in the message.
+fn fingerprint(check: &str, path: &str, message: &str) -> u64 {
+    let message: String = message.chars().filter(|c| !c.is_ascii_digit()).collect();
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for part in [check, path, &message] {
+        for byte in part.bytes().chain([0]) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fail(message: &str, file: Option<&str>, line: Option<usize>) -> CheckResult {
+        let mut result = CheckResult::fail("loc-limits", Severity::Error, message);
+        result.file = file.map(str::to_string);
+        result.line = line;
+        result
+    }
+
+    fn report(results: &[CheckResult]) -> Vec<serde_json::Value> {
+        serde_json::from_str(&gitlab_report(results).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_report_shape() {
+        let issues = report(&[
+            CheckResult::pass("rust-edition", "ok"),
+            fail("main.rs: 612 lines", Some("./src/main.rs"), Some(1)),
+            fail("Too many modules", None, None),
+        ]);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0]["check_name"], "loc-limits");
+        assert_eq!(issues[0]["severity"], "major");
+        assert_eq!(issues[0]["location"]["path"], "src/main.rs");
+        assert_eq!(issues[1]["location"]["path"], "Cargo.toml");
+        assert_eq!(issues[1]["location"]["lines"]["begin"], 1);
+    }
+
+    #[test]
+    fn test_fingerprints_are_stable() {
+        let before = report(&[fail("main.rs: 612 lines", Some("src/main.rs"), Some(3))]);
+        let after = report(&[fail("main.rs: 640 lines", Some("src/main.rs"), Some(9))]);
+        assert_eq!(before[0]["fingerprint"], after[0]["fingerprint"]);
+
+        let other = report(&[fail("main.rs: 612 lines", Some("src/lib.rs"), None)]);
+        assert_ne!(before[0]["fingerprint"], other[0]["fingerprint"]);
+    }
+
+    #[test]
+    fn test_duplicate_findings_get_distinct_fingerprints() {
+        let issues = report(&[
+            fail("Trivial test", Some("src/a.rs"), Some(10)),
+            fail("Trivial test", Some("src/a.rs"), Some(20)),
+        ]);
+        assert_ne!(issues[0]["fingerprint"], issues[1]["fingerprint"]);
+    }
+}
@@ -0,0 +1,283 @@
+//! Self-contained HTML report, for CI artifacts and non-terminal audiences.
+//!
+//! Everything (styles, charts, table sorting) is inlined so the file can be
+//! opened straight from a CI artifact store with no network access.
+
+use super::checks::CheckSummary;
+use super::junit::escape_xml;
+use crate::checks::{CheckResult, Severity};
+use anyhow::{Context, Result};
+use std::fmt::Write;
+use std::path::Path;
+
+/// LLM evaluation text to include in the report.
+pub struct Evaluation<'a> {
+    /// The host and model that wrote `text`, or `None` when it's the
+    /// rule-based summary written without an LLM.
+    pub judge: Option<(&'a str, &'a str)>,
+    pub text: &'a str,
+}
+
+const STYLE: &str = r#"
+body { font-family: system-ui, sans-serif; margin: 2rem auto; max-width: 72rem; color: #1f2328; }
+h1 { margin-bottom: 0.25rem; }
+.counts { display: flex; gap: 1rem; margin: 1rem 0; }
+.count { padding: 0.5rem 1rem; border-radius: 6px; background: #f6f8fa; }
+.count b { display: block; font-size: 1.5rem; }
+.bar { display: flex; height: 1rem; border-radius: 4px; overflow: hidden; background: #eaeef2; min-width: 12rem; }
+.bar span { display: block; height: 100%; }
+.error { background: #cf222e; } .warning { background: #d4a72c; }
+.info { background: #0969da; } .passed { background: #2da44e; }
+.legend span { display: inline-block; width: 0.8rem; height: 0.8rem; margin: 0 0.3rem 0 1rem; }
+table { border-collapse: collapse; width: 100%; margin: 1rem 0; }
+th, td { text-align: left; padding: 0.35rem 0.6rem; border-bottom: 1px solid #d0d7de; vertical-align: top; }
+th[data-sort] { cursor: pointer; user-select: none; }
+th[data-sort]::after { content: " \2195"; color: #8c959f; }
+.sev { font-weight: 600; } .sev-error { color: #cf222e; } .sev-warning { color: #9a6700; } .sev-info { color: #0969da; }
+details { margin: 0.4rem 0; } summary { cursor: pointer; }
+pre { white-space: pre-wrap; background: #f6f8fa; padding: 1rem; border-radius: 6px; }
+"#;
+
+const SCRIPT: &str = r#"
+document.querySelectorAll("th[data-sort]").forEach((th) => {
+  th.addEventListener("click", () => {
+    const body = th.closest("table").tBodies[0];
+    const col = th.cellIndex;
+    const asc = th.dataset.dir !== "asc";
+    th.dataset.dir = asc ? "asc" : "desc";
+    const key = (row) => row.cells[col].dataset.key ?? row.cells[col].textContent;
+    [...body.rows]
+      .sort((a, b) => key(a).localeCompare(key(b), undefined, { numeric: true }) * (asc ? 1 : -1))
+      .forEach((row) => body.appendChild(row));
+  });
+});
+"#;
+
+/// Render the report as a single HTML document.
+pub fn html_report(results: &[CheckResult], evaluation: Option<&Evaluation>) -> String {
+    let summary = CheckSummary::of(results);
+    let mut html = String::from("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n");
+    html.push_str("<meta charset=\"utf-8\">\n<title>Guardian report</title>\n");
+    let _ = writeln!(html, "<style>{STYLE}</style>\n</head>\n<body>");
+    html.push_str("<h1>Guardian report</h1>\n<div class=\"counts\">\n");
+    for (label, value) in [
+        ("Checks", summary.total),
+        ("Passed", summary.passed),
+        ("Errors", summary.errors),
+        ("Warnings", summary.warnings),
+    ] {
+        let _ = writeln!(html, "<div class=\"count\"><b>{value}</b>{label}</div>");
+    }
+    html.push_str("</div>\n");
+
+    html.push_str("<h2>Severity breakdown</h2>\n");
+    html.push_str(&bar(&severity_counts(results.iter())));
+    html.push_str(
+        "<p class=\"legend\"><span class=\"error\"></span>Error<span class=\"warning\"></span>Warning\
+         <span class=\"info\"></span>Info<span class=\"passed\"></span>Passed</p>\n",
+    );
+    by_check(&mut html, results);
+
+    if let Some(evaluation) = evaluation {
+        let by = match evaluation.judge {
+            Some((host, model)) => format!("{} on {}", escape_xml(model), escape_xml(host)),
+            None => "Rule-based summary: no LLM evaluation".to_string(),
+        };
+        let _ = writeln!(
+            html,
+            "<h2>LLM evaluation</h2>\n<p>{by}</p>\n<pre>{}</pre>",
+            escape_xml(evaluation.text)
+        );
+    }
+
+    violations_table(&mut html, results);
+    by_file(&mut html, results);
+    let _ = writeln!(html, "<script>{SCRIPT}</script>\n</body>\n</html>");
+    html
+}
+
+/// Write the report to `path`.
+pub fn write_html_report(
+    path: &Path,
+    results: &[CheckResult],
+    evaluation: Option<&Evaluation>,
+) -> Result<()> {
+    std::fs::write(path, html_report(results, evaluation))
+        .with_context(|| format!("Failed to write report to {}", path.display()))
+}
+
+/// Failed errors, warnings, and infos, then passes.
+fn severity_counts<'a>(results: impl Iterator<Item = &'a CheckResult>) -> [usize; 4] {
+    let mut counts = [0; 4];
+    for result in results {
+        let slot = match (result.passed, result.severity) {
+            (true, _) => 3,
+            (false, Severity::Error) => 0,
+            (false, Severity::Warning) => 1,
+            (false, Severity::Info) => 2,
+        };
+        counts[slot] += 1;
+    }
+    counts
+}
+
+/// A stacked bar of severity counts.
+fn bar(counts: &[usize; 4]) -> String {
+    let total: usize = counts.iter().sum();
+    let mut html = String::from("<div class=\"bar\">");
+    for (count, class) in counts.iter().zip(["error", "warning", "info", "passed"]) {
+        if *count > 0 {
+            let pct = *count as f64 * 100.0 / total as f64;
+            let _ = write!(
+                html,
+                "<span class=\"{class}\" style=\"width:{pct:.2}%\" title=\"{count} {class}\"></span>"
+            );
+        }
+    }
+    html.push_str("</div>\n");
+    html
+}
+
+fn by_check(html: &mut String, results: &[CheckResult]) {
+    let mut checks: Vec<&str> = Vec::new();
+    for result in results {
+        if !checks.contains(&result.check_name.as_str()) {
+            checks.push(&result.check_name);
+        }
+    }
+    html.push_str("<table>\n<thead><tr><th>Check</th><th>Failed</th><th>Passed</th><th>Breakdown</th></tr></thead>\n<tbody>\n");
+    for check in checks {
+        let counts = severity_counts(results.iter().filter(|r| r.check_name == check));
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            escape_xml(check),
+            counts[..3].iter().sum::<usize>(),
+            counts[3],
+            bar(&counts).trim_end()
+        );
+    }
+    html.push_str("</tbody>\n</table>\n");
+}
+
+fn violations_table(html: &mut String, results: &[CheckResult]) {
+    let failed: Vec<_> = results.iter().filter(|r| !r.passed).collect();
+    let _ = writeln!(html, "<h2>Violations ({})</h2>", failed.len());
+    if failed.is_empty() {
+        html.push_str("<p>No violations.</p>\n");
+        return;
+    }
+    html.push_str(
+        "<table>\n<thead><tr><th data-sort>Severity</th><th data-sort>Check</th>\
+         <th data-sort>File</th><th data-sort>Line</th><th>Message</th><th>Fix</th></tr></thead>\n<tbody>\n",
+    );
+    for result in failed {
+        let (rank, class) = match result.severity {
+            Severity::Error => (0, "error"),
+            Severity::Warning => (1, "warning"),
+            Severity::Info => (2, "info"),
+        };
+        let _ = writeln!(
+            html,
+            "<tr><td class=\"sev sev-{class}\" data-key=\"{rank}\">{class}</td><td>{}</td><td>{}</td>\
+             <td>{}</td><td>{}</td><td>{}</td></tr>",
+            escape_xml(&result.check_name),
+            escape_xml(result.file.as_deref().unwrap_or("")),
+            result.line.map(|l| l.to_string()).unwrap_or_default(),
+            escape_xml(&result.message),
+            escape_xml(result.fix.as_deref().unwrap_or(""))
+        );
+    }
+    html.push_str("</tbody>\n</table>\n");
+}
+
+fn by_file(html: &mut String, results: &[CheckResult]) {
+    let mut files: Vec<(&str, Vec<&CheckResult>)> = Vec::new();
+    for result in results.iter().filter(|r| !r.passed) {
+        let Some(file) = result.file.as_deref() else {
+            continue;
+        };
+        match files.iter_mut().find(|(f, _)| *f == file) {
+            Some((_, group)) => group.push(result),
+            None => files.push((file, vec![result])),
+        }
+    }
+    if files.is_empty() {
+        return;
+    }
+    files.sort_by_key(|(_, group)| std::cmp::Reverse(group.len()));
+
+    html.push_str("<h2>By file</h2>\n");
+    for (file, group) in files {
+        let _ = writeln!(
+            html,
+            "<details><summary><code>{}</code> ({})</summary>\n<ul>",
+            escape_xml(file),
+            group.len()
+        );
+        for result in group {
+            let line = result
+                .line
+                .map(|l| format!("line {l}: "))
+                .unwrap_or_default();
+            let _ = writeln!(
+                html,
+                "<li><b>{}</b> {line}{}</li>",
+                escape_xml(&result.check_name),
+                escape_xml(&result.message)
+            );
+        }
+        html.push_str("</ul></details>\n");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn results() -> Vec<CheckResult> {
+        let mut big = CheckResult::fail("loc-limits", Severity::Error, "612 > 500 lines");
+        big.file = Some("src/main.rs".to_string());
+        let mut fns = CheckResult::fail("function-count", Severity::Warning, "8 fns");
+        fns.file = Some("src/main.rs".to_string());
+        fns.line = Some(4);
+        vec![CheckResult::pass("rust-edition", "ok"), big, fns]
+    }
+
+    #[test]
+    fn test_html_report_sections() {
+        let html = html_report(&results(), None);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<h2>Violations (2)</h2>"));
+        assert!(html.contains("<td class=\"sev sev-error\" data-key=\"0\">error</td>"));
+        assert!(html.contains("612 &gt; 500 lines"));
+        assert!(html.contains("<code>src/main.rs</code> (2)"));
+        assert!(!html.contains("LLM evaluation"));
+        assert!(
+            !html.contains("http"),
+            "report must not load anything remotely"
+        );
+    }
+
+    #[test]
+    fn test_html_report_with_evaluation() {
+        let mut evaluation = Evaluation {
+            judge: Some(("big72", "qwen2.5-coder:14b")),
+            text: "Split <main.rs> first.",
+        };
+        let html = html_report(&results(), Some(&evaluation));
+        assert!(html.contains("<p>qwen2.5-coder:14b on big72</p>"));
+        assert!(html.contains("<pre>Split &lt;main.rs&gt; first.</pre>"));
+        evaluation.judge = None;
+        let html = html_report(&results(), Some(&evaluation));
+        assert!(html.contains("<p>Rule-based summary: no LLM evaluation</p>"));
+    }
+
+    #[test]
+    fn test_severity_bar() {
+        assert_eq!(severity_counts(results().iter()), [1, 1, 0, 1]);
+        let bar = bar(&[1, 0, 0, 3]);
+        assert!(bar.contains("class=\"error\" style=\"width:25.00%\""));
+        assert!(!bar.contains("warning"));
+    }
+}
@@ -0,0 +1,168 @@
+//! JUnit XML report for CI test-reporting UIs (Jenkins, GitLab, Buildkite).
+//!
+//! Each check/file pair becomes one test case; failed results for that pair
+//! become its `<failure>`, with every message and location in the body.
+
+use super::checks::CheckSummary;
+use crate::checks::{CheckResult, Severity};
+use std::fmt::Write;
+
+/// Render check results as a JUnit XML document.
+pub fn junit_report(results: &[CheckResult]) -> String {
+    let summary = CheckSummary::of(results);
+    let cases = group_cases(results);
+    let failures = cases
+        .iter()
+        .filter(|(_, group)| group.iter().any(|r| !r.passed))
+        .count();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        "<testsuites name=\"guardian\" tests=\"{}\" failures=\"{failures}\">",
+        cases.len()
+    );
+    let _ = writeln!(
+        xml,
+        "  <testsuite name=\"guardian-checks\" tests=\"{}\" failures=\"{failures}\" errors=\"0\" skipped=\"0\">",
+        cases.len()
+    );
+    xml.push_str("    <properties>\n");
+    for (name, value) in [
+        ("total", summary.total),
+        ("passed", summary.passed),
+        ("failed", summary.failed),
+        ("errors", summary.errors),
+        ("warnings", summary.warnings),
+    ] {
+        let _ = writeln!(xml, "      <property name=\"{name}\" value=\"{value}\"/>");
+    }
+    xml.push_str("    </properties>\n");
+
+    for ((check, file), group) in &cases {
+        let name = escape_xml(file.unwrap_or(check));
+        let _ = write!(
+            xml,
+            "    <testcase classname=\"{}\" name=\"{name}\"",
+            escape_xml(check)
+        );
+        let failed: Vec<_> = group.iter().filter(|r| !r.passed).collect();
+        let Some(first) = failed.first() else {
+            xml.push_str("/>\n");
+            continue;
+        };
+        let kind = if failed.iter().any(|r| r.severity == Severity::Error) {
+            "error"
+        } else {
+            "warning"
+        };
+        let _ = writeln!(
+            xml,
+            ">\n      <failure message=\"{}\" type=\"{kind}\">",
+            escape_xml(&first.message)
+        );
+        for result in &failed {
+            xml.push_str(&escape_xml(&failure_line(result)));
+        }
+        xml.push_str("      </failure>\n    </testcase>\n");
+    }
+
+    xml.push_str("  </testsuite>\n</testsuites>\n");
+    xml
+}
+
+/// Results for one check and (optional) file.
+type Case<'a> = ((&'a str, Option<&'a str>), Vec<&'a CheckResult>);
+
+/// Group results by check and file, in order of first appearance.
+fn group_cases(results: &[CheckResult]) -> Vec<Case<'_>> {
+    let mut cases: Vec<Case<'_>> = Vec::new();
+    for result in results {
+        let key = (result.check_name.as_str(), result.file.as_deref());
+        match cases.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, group)) => group.push(result),
+            None => cases.push((key, vec![result])),
+        }
+    }
+    cases
+}
+
+/// One line of failure detail: `file:line: [severity] message (fix: ...)`.
+fn failure_line(result: &CheckResult) -> String {
+    let mut line = String::new();
+    if let Some(file) = &result.file {
+        line.push_str(file);
+        if let Some(n) = result.line {
+            let _ = write!(line, ":{n}");
+        }
+        line.push_str(": ");
+    }
+    let _ = write!(
+        line,
+        "[{}] {}",
+        format!("{:?}", result.severity).to_lowercase(),
+        result.message
+    );
+    if let Some(fix) = &result.fix {
+        let _ = write!(line, " (fix: {fix})");
+    }
+    line.push('\n');
+    line
+}
+
+/// Escape text for use in XML attributes and content.
+pub(super) fn escape_xml(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn results() -> Vec<CheckResult> {
+        let mut big = CheckResult::fail("loc-limits", Severity::Error, "main.rs: 612 lines");
+        big.file = Some("src/main.rs".to_string());
+        let mut fns = CheckResult::fail("function-count", Severity::Warning, "8 > 7 fns");
+        fns.file = Some("src/lib.rs".to_string());
+        fns.line = Some(3);
+        let mut fns2 = CheckResult::fail("function-count", Severity::Warning, "Vec<T> & co");
+        fns2.file = Some("src/lib.rs".to_string());
+        vec![
+            CheckResult::pass("rust-edition", "Edition 2024"),
+            big,
+            fns,
+            fns2,
+        ]
+    }
+
+    #[test]
+    fn test_one_case_per_check_and_file() {
+        let xml = junit_report(&results());
+        assert_eq!(xml.matches("<testcase ").count(), 3);
+        assert!(xml.contains("<testsuites name=\"guardian\" tests=\"3\" failures=\"2\">"));
+        assert!(xml.contains("<testcase classname=\"rust-edition\" name=\"rust-edition\"/>"));
+        assert!(xml.contains("<failure message=\"main.rs: 612 lines\" type=\"error\">"));
+        assert!(xml.contains("src/lib.rs:3: [warning] 8 &gt; 7 fns\n"));
+        assert!(xml.contains("[warning] Vec&lt;T&gt; &amp; co\n"));
+    }
+
+    #[test]
+    fn test_summary_properties() {
+        let xml = junit_report(&results());
+        assert!(xml.contains("<property name=\"total\" value=\"4\"/>"));
+        assert!(xml.contains("<property name=\"failed\" value=\"3\"/>"));
+        assert!(xml.contains("<property name=\"errors\" value=\"1\"/>"));
+        assert!(xml.contains("<property name=\"warnings\" value=\"2\"/>"));
+    }
+}
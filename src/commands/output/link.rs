@@ -0,0 +1,111 @@
+//! OSC 8 terminal hyperlinks from file paths to the editor.
+//!
+//! Like color, this is decided once at startup: from the `[output]` config,
+//! whether reports go to a terminal, and whether that terminal is known to
+//! render OSC 8 links (others would show the escape codes as garbage).
+
+use crate::config::{Hyperlinks, OutputSettings};
+use std::io::IsTerminal;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// The link URL template, when hyperlinks are enabled.
+static EDITOR_URL: OnceLock<String> = OnceLock::new();
+
+const FILE_URL: &str = "file://{path}";
+
+/// Decide whether to emit hyperlinks for the rest of the run.
+pub fn init_hyperlinks(settings: &OutputSettings) {
+    let terminal = !super::to_file() && std::io::stdout().is_terminal();
+    let enabled = match settings.hyperlinks {
+        Hyperlinks::Never => false,
+        Hyperlinks::Always => terminal,
+        Hyperlinks::Auto => terminal && supported(|name| std::env::var(name).ok()),
+    };
+    if enabled {
+        let url = settings.editor_url.as_deref().unwrap_or(FILE_URL);
+        let _ = EDITOR_URL.set(url.to_string());
+    }
+}
+
+/// Whether the terminal, going by its environment, renders OSC 8 links.
+fn supported(var: impl Fn(&str) -> Option<String>) -> bool {
+    if var("TERM").as_deref() == Some("dumb") {
+        return false;
+    }
+    let program = var("TERM_PROGRAM").unwrap_or_default();
+    let vte = var("VTE_VERSION").and_then(|v| v.parse::<u32>().ok());
+    matches!(
+        program.as_str(),
+        "iTerm.app" | "WezTerm" | "vscode" | "ghostty" | "Hyper" | "tabby" | "rio"
+    ) || vte.is_some_and(|v| v >= 5000)
+        || [
+            "WT_SESSION",
+            "KITTY_WINDOW_ID",
+            "KONSOLE_VERSION",
+            "ALACRITTY_WINDOW_ID",
+        ]
+        .iter()
+        .any(|name| var(name).is_some())
+}
+
+/// Wrap `text` in a link to `file` at `line` and `column`, if enabled.
+pub(super) fn link(text: &str, file: &str, line: Option<usize>, column: Option<usize>) -> String {
+    match EDITOR_URL.get() {
+        Some(template) => hyperlink(text, &url(template, file, line, column)),
+        None => text.to_string(),
+    }
+}
+
+fn url(template: &str, file: &str, line: Option<usize>, column: Option<usize>) -> String {
+    let path = Path::new(file);
+    let absolute = path
+        .canonicalize()
+        .unwrap_or_else(|_| std::env::current_dir().unwrap_or_default().join(path));
+    template
+        .replace("{path}", &absolute.to_string_lossy().replace(' ', "%20"))
+        .replace("{line}", &line.unwrap_or(1).to_string())
+        .replace("{column}", &column.unwrap_or(1).to_string())
+}
+
+fn hyperlink(text: &str, url: &str) -> String {
+    format!("\x1b]8;;{url}\x1b\\{text}\x1b]8;;\x1b\\")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_supported_terminals() {
+        let env = |pairs: &'static [(&str, &str)]| {
+            move |name: &str| {
+                pairs
+                    .iter()
+                    .find(|(k, _)| *k == name)
+                    .map(|(_, v)| v.to_string())
+            }
+        };
+        assert!(supported(env(&[("TERM_PROGRAM", "WezTerm")])));
+        assert!(supported(env(&[("VTE_VERSION", "7200")])));
+        assert!(supported(env(&[("KITTY_WINDOW_ID", "1")])));
+        assert!(!supported(env(&[("VTE_VERSION", "4600")])));
+        assert!(!supported(env(&[("TERM", "xterm-256color")])));
+        assert!(!supported(env(&[("TERM", "dumb"), ("WT_SESSION", "x")])));
+    }
+
+    #[test]
+    fn test_url_and_hyperlink() {
+        let url = url(
+            "vscode://file{path}:{line}:{column}",
+            "/tmp/my app/main.rs",
+            Some(12),
+            None,
+        );
+        assert_eq!(url, "vscode://file/tmp/my%20app/main.rs:12:1");
+        assert_eq!(
+            hyperlink("main.rs", "file:///tmp/main.rs"),
+            "\x1b]8;;file:///tmp/main.rs\x1b\\main.rs\x1b]8;;\x1b\\"
+        );
+    }
+}
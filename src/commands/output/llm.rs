@@ -0,0 +1,199 @@
+//! Output for LLM responses.
+
+use super::super::patches::SuggestedPatch;
+use super::super::verdict::Assessment;
+use super::checks::result_json;
+use super::report;
+use crate::checks::CheckResult;
+use crate::config::OllamaHost;
+use crate::ollama::GenerateResponse;
+use anyhow::Result;
+
+/// Format LLM ask response.
+///
+/// `structured` is the parsed reply when a JSON format was requested.
+pub fn ask_response(
+    host: &OllamaHost,
+    model: &str,
+    prompt: &str,
+    response: &GenerateResponse,
+    structured: Option<&serde_json::Value>,
+    json_output: bool,
+) -> Result<()> {
+    if json_output {
+        let reply = structured
+            .cloned()
+            .unwrap_or_else(|| serde_json::Value::String(response.response.clone()));
+        let json = serde_json::json!({
+            "host": host.name,
+            "model": model,
+            "prompt": prompt,
+            "response": reply,
+            "done": response.done,
+            "total_duration_ns": response.total_duration,
+            "prompt_eval_count": response.prompt_eval_count,
+            "eval_count": response.eval_count,
+        });
+        report!("{}", serde_json::to_string_pretty(&json)?);
+    } else {
+        report!("[{}] Using model: {}\n", host.name, model);
+        match structured {
+            Some(value) => report!("{}", serde_json::to_string_pretty(value)?),
+            None => report!("{}", response.response),
+        }
+
+        if let Some(duration) = response.total_duration {
+            let duration_secs = duration as f64 / 1_000_000_000.0;
+            report!("\n---");
+            report!("Duration: {:.2}s", duration_secs);
+            if let Some(tokens) = response.eval_count {
+                let tps = tokens as f64 / duration_secs;
+                report!("Tokens: {} ({:.1} tokens/sec)", tokens, tps);
+            }
+            if let Some(prompt_tokens) = response.prompt_eval_count {
+                report!("Prompt tokens: {prompt_tokens}");
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Format LLM evaluation response.
+///
+/// `assessment` is the structured verdict parsed from the response, when
+/// one was asked for; it's shown instead of the raw reply. `patches` are
+/// the model's fixes from `--suggest-patches`.
+pub fn evaluate_response(
+    host: &OllamaHost,
+    model: &str,
+    results: &[CheckResult],
+    response: &GenerateResponse,
+    assessment: Option<&Assessment>,
+    patches: &[SuggestedPatch],
+    json_output: bool,
+) -> Result<()> {
+    let text = assessment.map_or_else(|| response.response.clone(), Assessment::text);
+    if json_output {
+        let failures: Vec<_> = results
+            .iter()
+            .filter(|r| !r.passed)
+            .map(|r| {
+                serde_json::json!({
+                    "check": r.check_name,
+                    "severity": format!("{:?}", r.severity).to_lowercase(),
+                    "message": r.message,
+                    "file": r.file,
+                    "line": r.line,
+                    "fix": r.fix,
+                    "blame": r.blame,
+                })
+            })
+            .collect();
+
+        let json = serde_json::json!({
+            "host": host.name,
+            "model": model,
+            "offline": false,
+            "total_checks": results.len(),
+            "passed": results.iter().filter(|r| r.passed).count(),
+            "failed": failures.len(),
+            "violations": failures,
+            "verdict": assessment.map(|a| a.verdict),
+            "blocking": assessment.map(|a| &a.blocking),
+            "advice": assessment.map(|a| &a.advice),
+            "sections": assessment.map(|a| &a.sections),
+            "llm_evaluation": text,
+            "suggested_patches": patches.iter().map(|p| serde_json::json!({
+                "check": p.result.check_name,
+                "message": p.result.message,
+                "file": p.result.file,
+                "line": p.result.line,
+                "diff": p.diff,
+            })).collect::<Vec<_>>(),
+            "eval_duration_ns": response.total_duration,
+            "prompt_eval_count": response.prompt_eval_count,
+            "eval_count": response.eval_count,
+        });
+
+        report!("{}", serde_json::to_string_pretty(&json)?);
+    } else {
+        report!("=== LLM Evaluation ({} on {}) ===\n", model, host.name);
+        report!("{}", text.trim_end());
+        if !patches.is_empty() {
+            report!("\n=== Suggested patches (unreviewed; apply with git apply) ===");
+        }
+        for patch in patches {
+            let location = match (&patch.result.file, patch.result.line) {
+                (Some(file), Some(line)) => format!("{file}:{line}"),
+                (file, _) => file.clone().unwrap_or_default(),
+            };
+            report!(
+                "\n[{}] {location}: {}",
+                patch.result.check_name,
+                patch.result.message
+            );
+            report!("{}", patch.diff.trim_end());
+        }
+
+        if let Some(duration) = response.total_duration {
+            let secs = duration as f64 / 1_000_000_000.0;
+            let tokens = match (response.prompt_eval_count, response.eval_count) {
+                (Some(prompt), Some(eval)) => format!(", {prompt} prompt + {eval} response tokens"),
+                _ => String::new(),
+            };
+            report!("\n[Evaluation took {:.1}s{tokens}]", secs);
+        }
+    }
+    Ok(())
+}
+
+/// Format the rule-based summary `evaluate` falls back to without an LLM.
+///
+/// JSON keeps the shape of [`evaluate_response`], with `llm_evaluation`
+/// null and the summary under `summary`.
+pub fn offline_summary(results: &[CheckResult], summary: &str, json_output: bool) -> Result<()> {
+    if json_output {
+        let failures: Vec<_> = results
+            .iter()
+            .filter(|r| !r.passed)
+            .map(result_json)
+            .collect();
+        let json = serde_json::json!({
+            "host": null,
+            "model": null,
+            "offline": true,
+            "total_checks": results.len(),
+            "passed": results.iter().filter(|r| r.passed).count(),
+            "failed": failures.len(),
+            "violations": failures,
+            "verdict": null,
+            "llm_evaluation": null,
+            "summary": summary,
+        });
+        report!("{}", serde_json::to_string_pretty(&json)?);
+    } else {
+        report!("=== Rule-based summary (no LLM evaluation) ===\n");
+        report!("{summary}");
+    }
+    Ok(())
+}
+
+/// Print a govern verdict, always as JSON so hook scripts can rely on it.
+///
+/// `judge` is the host and model that reviewed the action, if one did.
+pub fn govern_verdict(
+    verdict: impl serde::Serialize,
+    reasons: &[String],
+    failures: &[CheckResult],
+    judge: Option<(&OllamaHost, &str)>,
+) -> Result<()> {
+    let json = serde_json::json!({
+        "verdict": verdict,
+        "reasons": reasons,
+        "violations": failures.iter().map(result_json).collect::<Vec<_>>(),
+        "host": judge.map(|(host, _)| &host.name),
+        "model": judge.map(|(_, model)| model),
+    });
+    report!("{}", serde_json::to_string_pretty(&json)?);
+    Ok(())
+}
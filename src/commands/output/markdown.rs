@@ -0,0 +1,106 @@
+//! Markdown report, ready to paste into a pull request comment.
+
+use super::checks::CheckSummary;
+use super::junit::escape_xml;
+use crate::checks::{CheckResult, Severity};
+use std::fmt::Write;
+
+/// Render a summary table, then one collapsible section per check.
+///
+/// Sections with failures start expanded and list their fixes as a task list.
+pub fn markdown_report(results: &[CheckResult]) -> String {
+    let summary = CheckSummary::of(results);
+    let mut md = String::from("## Guardian check results\n\n");
+    md.push_str("| Total | Passed | Failed | Errors | Warnings |\n");
+    md.push_str("|------:|-------:|-------:|-------:|---------:|\n");
+    let _ = writeln!(
+        md,
+        "| {} | {} | {} | {} | {} |",
+        summary.total, summary.passed, summary.failed, summary.errors, summary.warnings
+    );
+
+    let mut checks: Vec<(&str, Vec<&CheckResult>)> = Vec::new();
+    for result in results {
+        match checks
+            .iter_mut()
+            .find(|(name, _)| *name == result.check_name)
+        {
+            Some((_, group)) => group.push(result),
+            None => checks.push((&result.check_name, vec![result])),
+        }
+    }
+    for (name, group) in checks {
+        check_section(&mut md, name, &group);
+    }
+    md
+}
+
+fn check_section(md: &mut String, name: &str, group: &[&CheckResult]) {
+    let failed: Vec<_> = group.iter().filter(|r| !r.passed).collect();
+    let passed = group.len() - failed.len();
+    let (open, status) = if failed.is_empty() {
+        ("", format!("all {passed} passed"))
+    } else {
+        (" open", format!("{} failed, {passed} passed", failed.len()))
+    };
+    let _ = writeln!(
+        md,
+        "\n<details{open}>\n<summary><b>{}</b>: {status}</summary>\n",
+        escape_xml(name)
+    );
+
+    if failed.is_empty() {
+        md.push_str("No issues.\n");
+    }
+    for result in &failed {
+        let label = match result.severity {
+            Severity::Error => "ERROR",
+            Severity::Warning => "WARN",
+            Severity::Info => "INFO",
+        };
+        let location = match (&result.file, result.line) {
+            (Some(file), Some(line)) => format!(" `{file}:{line}`"),
+            (Some(file), None) => format!(" `{file}`"),
+            _ => String::new(),
+        };
+        let _ = writeln!(
+            md,
+            "- **{label}**{location} {}",
+            escape_xml(&result.message)
+        );
+    }
+
+    let fixes: Vec<_> = failed.iter().filter_map(|r| r.fix.as_deref()).collect();
+    if !fixes.is_empty() {
+        md.push_str("\n**Fixes**\n\n");
+        for fix in fixes {
+            let _ = writeln!(md, "- [ ] {}", escape_xml(fix));
+        }
+    }
+    md.push_str("\n</details>\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_report() {
+        let mut big = CheckResult::fail("loc-limits", Severity::Error, "612 > 500 lines");
+        big.file = Some("src/main.rs".to_string());
+        big.line = Some(1);
+        big.fix = Some("Split main.rs into modules".to_string());
+        let results = [
+            CheckResult::pass("rust-edition", "Edition 2024"),
+            CheckResult::pass("loc-limits", "lib.rs: 120 lines"),
+            big,
+        ];
+
+        let md = markdown_report(&results);
+        assert!(md.contains("| 3 | 2 | 1 | 1 | 0 |"));
+        assert!(md.contains("<details>\n<summary><b>rust-edition</b>: all 1 passed</summary>"));
+        assert!(md.contains("<details open>\n<summary><b>loc-limits</b>: 1 failed, 1 passed"));
+        assert!(md.contains("- **ERROR** `src/main.rs:1` 612 &gt; 500 lines\n"));
+        assert!(md.contains("- [ ] Split main.rs into modules\n"));
+    }
+}
@@ -0,0 +1,251 @@
+//! Output formatting utilities for command results.
+
+mod catalog;
+mod checks;
+mod checkstyle;
+mod color;
+mod csv;
+mod fleet;
+mod github;
+mod gitlab;
+mod html;
+mod junit;
+mod link;
+mod llm;
+mod markdown;
+mod prune;
+mod self_check;
+mod short;
+mod sink;
+mod stats;
+mod template;
+mod text;
+
+pub use catalog::catalog_report;
+pub use checks::{check_report, check_results, result_json};
+pub use color::init_color;
+pub use fleet::fleet_report;
+pub use gitlab::{finding_path, fingerprints};
+pub use html::{Evaluation, write_html_report};
+pub use link::init_hyperlinks;
+pub use llm::{ask_response, evaluate_response, govern_verdict, offline_summary};
+pub use prune::{prune_preview, prune_report};
+pub use self_check::self_check_report;
+pub use sink::init_output;
+pub(crate) use sink::{capture, report, to_file, write_report};
+pub use stats::stats_report;
+
+use super::exit::{Exit, NO_HOSTS};
+use crate::config::OllamaHost;
+use crate::ollama::{HostScore, OllamaModel, PingResult, UsageSummary};
+use anyhow::Result;
+use color::{Style, enabled as color_enabled, paint};
+use std::time::Duration;
+
+/// Print an error when no hosts are available, and fail with [`NO_HOSTS`].
+pub fn no_hosts_error(json_output: bool, msg: &str) -> Result<()> {
+    if json_output {
+        report!(r#"{{"error": "{msg}"}}"#);
+    } else {
+        report!("{msg}. Add hosts to your guardian.toml file.");
+    }
+    Err(Exit(NO_HOSTS).into())
+}
+
+/// Format ping results for output.
+pub fn ping_results(results: &[PingResult], json_output: bool) -> Result<()> {
+    if json_output {
+        let json_results: Vec<_> = results
+            .iter()
+            .map(|r| {
+                serde_json::json!({
+                    "name": r.host.name,
+                    "base_url": r.host.base_url,
+                    "reachable": r.reachable,
+                    "fallback": r.host.fallback,
+                    "latency_ms": r.latency_ms,
+                    "error": r.error,
+                })
+            })
+            .collect();
+        report!("{}", serde_json::to_string_pretty(&json_results)?);
+    } else {
+        for result in results {
+            let status = if result.reachable {
+                paint("UP", Style::Green, color_enabled())
+            } else {
+                paint("DOWN", Style::Red, color_enabled())
+            };
+            let latency = result
+                .latency_ms
+                .map(|ms| format!(" ({ms}ms)"))
+                .unwrap_or_default();
+            let fallback = if result.host.fallback {
+                " [fallback]"
+            } else {
+                ""
+            };
+
+            if result.reachable {
+                report!("  [{status}] {}{latency}{fallback}", result.host.name);
+            } else {
+                let err = result.error.as_deref().unwrap_or("unknown error");
+                report!("  [{status}] {}{fallback} - {err}", result.host.name);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Format models list for a host.
+pub fn models_list(host: &OllamaHost, models: &[OllamaModel]) {
+    report!("\n{} ({}):", host.name, host.base_url);
+    if models.is_empty() {
+        report!("  (no models)");
+    } else {
+        for model in models {
+            let size = model
+                .size
+                .map(|s| format!(" ({:.1} GB)", s as f64 / 1e9))
+                .unwrap_or_default();
+            report!("  - {}{size}", model.name);
+        }
+    }
+}
+
+/// Format selected host for output.
+pub fn selected_host(host: &OllamaHost, json_output: bool) -> Result<()> {
+    if json_output {
+        report!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "host": host.name,
+                "base_url": host.base_url,
+                "fallback": host.fallback,
+            }))?
+        );
+    } else {
+        report!("{}", host.name);
+    }
+    Ok(())
+}
+
+/// Format ranked host scores for `select-host --all`.
+pub fn ranked_hosts(scores: &[HostScore], json_output: bool) -> Result<()> {
+    if json_output {
+        report!("{}", serde_json::to_string_pretty(scores)?);
+        return Ok(());
+    }
+    for (rank, score) in scores.iter().enumerate() {
+        let fallback = if score.fallback { " [fallback]" } else { "" };
+        if !score.usable {
+            report!("  -  {}{fallback}: unusable", score.host);
+            continue;
+        }
+        let latency = score
+            .latency_ms
+            .map(|ms| format!("{ms}ms, "))
+            .unwrap_or_default();
+        let loaded = if score.model_loaded {
+            ", model loaded"
+        } else {
+            ""
+        };
+        report!(
+            "  {}. {}{fallback}: {:.1} ({latency}{:.0}% available{loaded})",
+            rank + 1,
+            score.host,
+            score.score,
+            score.availability * 100.0
+        );
+    }
+    Ok(())
+}
+
+/// Format the usage report.
+pub fn usage_report(
+    summaries: &[UsageSummary],
+    since: Option<&str>,
+    json_output: bool,
+) -> Result<()> {
+    if json_output {
+        let json = serde_json::json!({ "since": since, "usage": summaries });
+        report!("{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+
+    match since {
+        Some(age) => report!("Usage over the last {age}\n"),
+        None => report!("Usage (all time)\n"),
+    }
+    if summaries.is_empty() {
+        report!("  (no requests recorded)");
+        return Ok(());
+    }
+    report!(
+        "{:<16} {:<24} {:>8} {:>6} {:>12} {:>12} {:>10}",
+        "HOST",
+        "MODEL",
+        "REQUESTS",
+        "FAILED",
+        "PROMPT TOK",
+        "RESP TOK",
+        "WALL"
+    );
+    for s in summaries {
+        report!(
+            "{:<16} {:<24} {:>8} {:>6} {:>12} {:>12} {:>9.1}s",
+            s.host,
+            s.model,
+            s.requests,
+            s.failures,
+            s.prompt_tokens,
+            s.eval_tokens,
+            s.total_ms as f64 / 1000.0
+        );
+    }
+    Ok(())
+}
+
+/// Format the outcome of loading a model on each host.
+pub fn warmup_results(
+    model: &str,
+    keep_alive: &str,
+    results: &[(&OllamaHost, Result<Duration>)],
+    json_output: bool,
+) -> Result<()> {
+    if json_output {
+        let json: Vec<_> = results
+            .iter()
+            .map(|(host, result)| {
+                serde_json::json!({
+                    "host": host.name,
+                    "model": model,
+                    "loaded": result.is_ok(),
+                    "load_ms": result.as_ref().ok().map(|d| d.as_millis() as u64),
+                    "error": result.as_ref().err().map(|e| format!("{e:#}")),
+                })
+            })
+            .collect();
+        report!("{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+
+    report!("Warming {model} (keep_alive {keep_alive})\n");
+    for (host, result) in results {
+        match result {
+            Ok(elapsed) => report!(
+                "  [{}] {} ({:.1}s)",
+                paint("OK", Style::Green, color_enabled()),
+                host.name,
+                elapsed.as_secs_f64()
+            ),
+            Err(e) => report!(
+                "  [{}] {} - {e:#}",
+                paint("FAILED", Style::Red, color_enabled()),
+                host.name
+            ),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,110 @@
+//! Output for `prune-models`: the models deleted from each host, or that
+//! would be, and the space it frees.
+
+use super::color::{self, Style, paint};
+use super::sink::write_report;
+use crate::commands::prune::HostPrune;
+use anyhow::Result;
+use std::fmt::Write;
+
+/// Report what was pruned from each host, or with `dry_run`, what would be.
+pub fn prune_report(plans: &[HostPrune], dry_run: bool, json_output: bool) -> Result<()> {
+    let report = if json_output {
+        let json = serde_json::json!({
+            "dry_run": dry_run,
+            "freed_bytes": freed(plans),
+            "hosts": plans,
+        });
+        format!("{}\n", serde_json::to_string_pretty(&json)?)
+    } else {
+        render_text(plans, dry_run, color::enabled())
+    };
+    write_report(&report);
+    Ok(())
+}
+
+/// Show what is about to be deleted, ahead of asking.
+pub fn prune_preview(plans: &[HostPrune]) {
+    eprint!("{}", render_text(plans, true, color::enabled()));
+}
+
+/// Bytes freed by the models deleted, or that would be.
+fn freed(plans: &[HostPrune]) -> u64 {
+    plans
+        .iter()
+        .flat_map(|plan| &plan.pruned)
+        .filter(|model| model.error.is_none())
+        .filter_map(|model| model.size)
+        .sum()
+}
+
+fn render_text(plans: &[HostPrune], dry_run: bool, color: bool) -> String {
+    let mut out = String::new();
+    for plan in plans {
+        if let Some(error) = &plan.error {
+            let failed = paint("FAILED", Style::Red, color);
+            let _ = writeln!(out, "{} [{failed}] {error}", plan.host);
+            continue;
+        }
+        let (pruned, kept) = (plan.pruned.len(), plan.kept);
+        let _ = writeln!(out, "{}: {pruned} to prune, {kept} kept", plan.host);
+        for model in &plan.pruned {
+            let size = model
+                .size
+                .map(|s| format!(" ({:.1} GB)", s as f64 / 1e9))
+                .unwrap_or_default();
+            let modified = model.modified_at.as_deref().unwrap_or("unknown");
+            let modified = modified.get(..10).unwrap_or(modified);
+            let line = format!("  {}{size}, modified {modified}", model.name);
+            let _ = match &model.error {
+                Some(error) => writeln!(out, "{} - {error}", paint(&line, Style::Red, color)),
+                None => writeln!(out, "{line}"),
+            };
+        }
+    }
+    let gb = freed(plans) as f64 / 1e9;
+    let count: usize = plans.iter().map(|plan| plan.pruned.len()).sum();
+    let _ = match (count, dry_run) {
+        (0, _) => writeln!(out, "\nNothing to prune"),
+        (_, true) => writeln!(out, "\nWould free {gb:.1} GB"),
+        (_, false) => writeln!(out, "\nFreed {gb:.1} GB"),
+    };
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::prune::PrunedModel;
+
+    #[test]
+    fn test_render_text_totals_what_is_freed() {
+        let model = |name: &str, error: Option<&str>| PrunedModel {
+            name: name.to_string(),
+            size: Some(4_700_000_000),
+            modified_at: Some("2024-05-01T10:00:00Z".to_string()),
+            error: error.map(str::to_string),
+        };
+        let plans = [
+            HostPrune {
+                host: "big72".to_string(),
+                pruned: vec![model("phi4:14b", None), model("mistral:7b", Some("busy"))],
+                kept: 3,
+                error: None,
+            },
+            HostPrune {
+                host: "local".to_string(),
+                pruned: Vec::new(),
+                kept: 0,
+                error: Some("connection refused".to_string()),
+            },
+        ];
+        let text = render_text(&plans, false, false);
+        assert!(text.starts_with("big72: 2 to prune, 3 kept\n"));
+        assert!(text.contains("  phi4:14b (4.7 GB), modified 2024-05-01\n"));
+        assert!(text.contains("  mistral:7b (4.7 GB), modified 2024-05-01 - busy\n"));
+        assert!(text.contains("local [FAILED] connection refused\n"));
+        assert!(text.ends_with("\nFreed 4.7 GB\n"));
+        assert!(render_text(&plans, true, false).ends_with("\nWould free 4.7 GB\n"));
+    }
+}
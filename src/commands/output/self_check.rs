@@ -0,0 +1,148 @@
+//! Output for `self-check`: which checks fired, and why.
+
+use super::checks::result_json;
+use super::color::{self, Style, paint};
+use super::sink::write_report;
+use crate::checks::{CheckResult, Severity};
+use anyhow::Result;
+use std::fmt::Write;
+
+/// Report each section's checks, with the failures of the ones that fired,
+/// then which fired in all.
+pub fn self_check_report(sections: &[(String, Vec<CheckResult>)], json_output: bool) -> Result<()> {
+    let report = if json_output {
+        render_json(sections)?
+    } else {
+        render_text(sections, color::enabled())
+    };
+    write_report(&report);
+    Ok(())
+}
+
+/// `results` by check, in order of each check's first result.
+fn by_check(results: &[CheckResult]) -> Vec<(&str, Vec<&CheckResult>)> {
+    let mut checks: Vec<(&str, Vec<&CheckResult>)> = Vec::new();
+    for result in results {
+        match checks
+            .iter_mut()
+            .find(|(name, _)| *name == result.check_name)
+        {
+            Some((_, group)) => group.push(result),
+            None => checks.push((&result.check_name, vec![result])),
+        }
+    }
+    checks
+}
+
+/// The checks that fired in any section, each once.
+fn fired(sections: &[(String, Vec<CheckResult>)]) -> Vec<&str> {
+    let mut fired = Vec::new();
+    for result in sections.iter().flat_map(|(_, results)| results) {
+        if !result.passed && !fired.contains(&result.check_name.as_str()) {
+            fired.push(result.check_name.as_str());
+        }
+    }
+    fired
+}
+
+fn render_text(sections: &[(String, Vec<CheckResult>)], color: bool) -> String {
+    let mut out = String::from("Guardian Self-Check\n");
+    for (title, results) in sections {
+        let _ = writeln!(out, "\n{title}");
+        for (check, group) in by_check(results) {
+            let failures: Vec<_> = group.iter().filter(|r| !r.passed).collect();
+            if failures.is_empty() {
+                let status = paint("[OK]", Style::Green, color);
+                let _ = writeln!(out, "  {status} {check} ({} passed)", group.len());
+                continue;
+            }
+            let error = failures.iter().any(|r| r.severity == Severity::Error);
+            let style = if error { Style::Red } else { Style::Yellow };
+            let status = paint("[FIRED]", style, color);
+            let (failed, total) = (failures.len(), group.len());
+            let _ = writeln!(out, "  {status} {check}: {failed} of {total} failed");
+            for failure in failures {
+                let location = match (&failure.file, failure.line) {
+                    (Some(file), Some(line)) => format!("{file}:{line}: "),
+                    (Some(file), None) => format!("{file}: "),
+                    (None, _) => String::new(),
+                };
+                let _ = writeln!(out, "      {location}{}", failure.message);
+                if let Some(fix) = &failure.fix {
+                    let fix = format!("        Fix: {fix}");
+                    let _ = writeln!(out, "{}", paint(&fix, Style::Dim, color));
+                }
+            }
+        }
+    }
+    out.push_str("\n---\n");
+    let fired = fired(sections);
+    if fired.is_empty() {
+        out.push_str("No check fired\n");
+    } else {
+        let _ = writeln!(out, "Fired: {}", fired.join(", "));
+    }
+    out
+}
+
+fn render_json(sections: &[(String, Vec<CheckResult>)]) -> Result<String> {
+    let titled: Vec<_> = sections
+        .iter()
+        .map(|(title, results)| {
+            let checks: Vec<_> = by_check(results)
+                .into_iter()
+                .map(|(check, group)| {
+                    let failures: Vec<_> = group.iter().filter(|r| !r.passed).collect();
+                    serde_json::json!({
+                        "check": check,
+                        "fired": !failures.is_empty(),
+                        "passed": group.len() - failures.len(),
+                        "failures": failures.into_iter().map(|r| result_json(r)).collect::<Vec<_>>(),
+                    })
+                })
+                .collect();
+            serde_json::json!({"title": title, "checks": checks})
+        })
+        .collect();
+    let report = serde_json::json!({"sections": titled, "fired": fired(sections)});
+    Ok(serde_json::to_string_pretty(&report)? + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_text_shows_what_fired() {
+        let sections = vec![
+            (
+                "Source: /src/guardian".to_string(),
+                vec![
+                    CheckResult::pass("rust-edition", "Edition 2024"),
+                    CheckResult::fail("loc-limits", Severity::Warning, "lib.rs: 360 lines")
+                        .with_file("src/lib.rs")
+                        .with_line(351),
+                    CheckResult::pass("loc-limits", "main.rs: 20 lines (OK)"),
+                ],
+            ),
+            (
+                "Environment".to_string(),
+                vec![CheckResult::fail(
+                    "hosts",
+                    Severity::Warning,
+                    "big72 is down",
+                )],
+            ),
+        ];
+        let text = render_text(&sections, false);
+        assert!(text.contains("  [OK] rust-edition (1 passed)\n"));
+        assert!(text.contains("  [FIRED] loc-limits: 1 of 2 failed\n"));
+        assert!(text.contains("      src/lib.rs:351: lib.rs: 360 lines\n"));
+        assert!(text.ends_with("Fired: loc-limits, hosts\n"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&render_json(&sections).unwrap()).unwrap();
+        assert_eq!(json["fired"], serde_json::json!(["loc-limits", "hosts"]));
+        assert_eq!(json["sections"][0]["checks"][1]["passed"], 1);
+    }
+}
@@ -0,0 +1,56 @@
+//! Compiler-style one-line findings for editor quickfix lists and CI
+//! problem matchers.
+
+use super::gitlab::PROJECT_PATH;
+use crate::checks::{CheckResult, Severity};
+use std::fmt::Write;
+
+/// One `path:line[:col]: severity: [check] message` line per failed result.
+pub fn short_report(results: &[CheckResult]) -> String {
+    let mut out = String::new();
+    for result in results.iter().filter(|r| !r.passed) {
+        let path = result
+            .file
+            .as_deref()
+            .map_or(PROJECT_PATH, |f| f.trim_start_matches("./"));
+        let column = result.column.map(|c| format!(":{c}")).unwrap_or_default();
+        let severity = match result.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "info",
+        };
+        let _ = writeln!(
+            out,
+            "{path}:{}{column}: {severity}: [{}] {}",
+            result.line.unwrap_or(1),
+            result.check_name,
+            result.message
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_report() {
+        let lint = CheckResult::fail("clippy-disables", Severity::Warning, "Lint suppression")
+            .with_file("./src/main.rs")
+            .with_line(12)
+            .with_column(5);
+        let big = CheckResult::fail("loc-limits", Severity::Error, "612 lines")
+            .with_file("src/lib.rs")
+            .with_line(501);
+        let modules = CheckResult::fail("module-count", Severity::Error, "9 modules");
+        let results = [CheckResult::pass("rust-edition", "ok"), lint, big, modules];
+
+        assert_eq!(
+            short_report(&results),
+            "src/main.rs:12:5: warning: [clippy-disables] Lint suppression\n\
+             src/lib.rs:501: error: [loc-limits] 612 lines\n\
+             Cargo.toml:1: error: [module-count] 9 modules\n"
+        );
+    }
+}
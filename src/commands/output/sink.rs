@@ -0,0 +1,62 @@
+//! Where reports go: stdout, or the file named by `--output`.
+//!
+//! Only the primary report goes through here; progress messages go to
+//! stderr so they never end up in a captured artifact.
+
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+static FILE: OnceLock<Mutex<File>> = OnceLock::new();
+
+/// Report text collected by [`capture`] instead of being written.
+static CAPTURED: Mutex<Option<String>> = Mutex::new(None);
+
+/// Like `println!`, but to the report destination.
+macro_rules! report {
+    ($($arg:tt)*) => {
+        $crate::commands::output::write_report(&format!("{}\n", format_args!($($arg)*)))
+    };
+}
+pub(crate) use report;
+
+/// Send reports to `path` instead of stdout for the rest of the run.
+pub fn init_output(path: Option<&Path>) -> Result<()> {
+    if let Some(path) = path {
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create output file {}", path.display()))?;
+        let _ = FILE.set(Mutex::new(file));
+    }
+    Ok(())
+}
+
+/// Whether reports are going to a file rather than stdout.
+pub(crate) fn to_file() -> bool {
+    FILE.get().is_some()
+}
+
+/// Run `work`, collecting the reports it writes instead of writing them.
+pub(crate) async fn capture<T>(work: impl Future<Output = T>) -> (T, String) {
+    *CAPTURED.lock().unwrap_or_else(|e| e.into_inner()) = Some(String::new());
+    let output = work.await;
+    let text = CAPTURED.lock().unwrap_or_else(|e| e.into_inner()).take();
+    (output, text.unwrap_or_default())
+}
+
+/// Write report text as-is.
+pub(crate) fn write_report(text: &str) {
+    if let Some(captured) = CAPTURED.lock().unwrap_or_else(|e| e.into_inner()).as_mut() {
+        captured.push_str(text);
+        return;
+    }
+    match FILE.get() {
+        Some(file) => {
+            let mut file = file.lock().unwrap_or_else(|e| e.into_inner());
+            file.write_all(text.as_bytes())
+                .expect("Failed to write report to output file");
+        }
+        None => print!("{text}"),
+    }
+}
@@ -0,0 +1,101 @@
+//! Output for `stats`: violations per check or author.
+
+use super::color::{self, Style, paint};
+use super::sink::write_report;
+use crate::commands::stats::Tally;
+use crate::storage::StoredRun;
+use anyhow::Result;
+use std::fmt::Write;
+
+/// Report the tallies grouped `by` "check" or "author", as a table or JSON.
+pub fn stats_report(
+    rows: &[Tally],
+    by: &str,
+    since: &str,
+    baseline: Option<&StoredRun>,
+    json_output: bool,
+) -> Result<()> {
+    let report = if json_output {
+        let json = serde_json::json!({
+            "by": by,
+            "since": since,
+            "baseline_run": baseline.map(|run| run.id),
+            "rows": rows,
+        });
+        format!("{}\n", serde_json::to_string_pretty(&json)?)
+    } else {
+        render_text(rows, by, since, baseline, color::enabled())
+    };
+    write_report(&report);
+    Ok(())
+}
+
+fn render_text(
+    rows: &[Tally],
+    by: &str,
+    since: &str,
+    baseline: Option<&StoredRun>,
+    color: bool,
+) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "Violations by {by}, introduced and fixed in the last {since}\n"
+    );
+    let width = rows.iter().map(|row| row.name.len()).max().unwrap_or(0);
+    let width = width.max(by.len());
+    let _ = writeln!(
+        out,
+        "{:<width$}  {:>5}  {:>10}  {:>5}",
+        by.to_uppercase(),
+        "OPEN",
+        "INTRODUCED",
+        "FIXED"
+    );
+    for row in rows {
+        let introduced = format!("{:>10}", row.introduced);
+        let introduced = match row.introduced {
+            0 => introduced,
+            _ => paint(&introduced, Style::Red, color),
+        };
+        let fixed = format!("{:>5}", row.fixed);
+        let fixed = match row.fixed {
+            0 => fixed,
+            _ => paint(&fixed, Style::Green, color),
+        };
+        let _ = writeln!(
+            out,
+            "{:<width$}  {:>5}  {introduced}  {fixed}",
+            row.name, row.open
+        );
+    }
+    if rows.is_empty() {
+        let _ = writeln!(out, "(no violations)");
+    }
+    let note = match baseline {
+        Some(run) => format!("Fixed counts against run {} in the result history", run.id),
+        None => format!("No check or evaluate run recorded in the last {since}, so none fixed"),
+    };
+    let _ = writeln!(out, "\n{}", paint(&note, Style::Dim, color));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_text() {
+        let rows = [Tally {
+            name: "Agent <agent@example.com>".to_string(),
+            open: 3,
+            introduced: 2,
+            fixed: 0,
+        }];
+        let text = render_text(&rows, "author", "7d", None, false);
+        assert!(text.starts_with("Violations by author, introduced and fixed in the last 7d\n"));
+        assert!(text.contains("AUTHOR                      OPEN  INTRODUCED  FIXED\n"));
+        assert!(text.contains("Agent <agent@example.com>      3           2      0\n"));
+        assert!(text.contains("No check or evaluate run recorded in the last 7d"));
+    }
+}
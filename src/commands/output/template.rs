@@ -0,0 +1,90 @@
+//! User-defined report formats, rendered from a Jinja template.
+//!
+//! Templates see `summary` (the JSON format's counts), `results` (every
+//! result, shaped like the JSON format's), and `files` (results grouped by
+//! file). Undefined variables are errors, so typos fail loudly.
+
+use super::checks::{CheckSummary, file_groups, result_json};
+use crate::checks::CheckResult;
+use anyhow::{Context, Result};
+use minijinja::{Environment, UndefinedBehavior};
+use std::path::Path;
+
+/// Render `results` through the template at `path`.
+pub fn template_report(results: &[CheckResult], path: &Path) -> Result<String> {
+    let source = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read template {}", path.display()))?;
+    render(results, &source)
+        .with_context(|| format!("Failed to render template {}", path.display()))
+}
+
+fn render(results: &[CheckResult], source: &str) -> Result<String> {
+    let summary = CheckSummary::of(results);
+    let files: Vec<_> = file_groups(results)
+        .into_iter()
+        .map(|(file, group)| {
+            serde_json::json!({
+                "file": file,
+                "results": group.into_iter().map(result_json).collect::<Vec<_>>(),
+            })
+        })
+        .collect();
+    let context = serde_json::json!({
+        "summary": {
+            "total": summary.total,
+            "passed": summary.passed,
+            "failed": summary.failed,
+            "errors": summary.errors,
+            "warnings": summary.warnings,
+        },
+        "results": results.iter().map(result_json).collect::<Vec<_>>(),
+        "files": files,
+    });
+
+    let mut env = Environment::new();
+    env.set_undefined_behavior(UndefinedBehavior::Strict);
+    env.set_keep_trailing_newline(true);
+    Ok(env.render_str(source, context)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checks::Severity;
+
+    fn results() -> Vec<CheckResult> {
+        let big = CheckResult::fail("loc-limits", Severity::Error, "612 > 500 lines \"main\"")
+            .with_file("./src/main.rs")
+            .with_line(501);
+        vec![CheckResult::pass("rust-edition", "ok"), big]
+    }
+
+    #[test]
+    fn test_render_template() {
+        let source = "{{ summary.errors }}/{{ summary.total }}\n\
+                      {% for r in results if not r.passed %}{{ r.file }}:{{ r.line }} {{ r.check }}\n{% endfor %}";
+        assert_eq!(
+            render(&results(), source).unwrap(),
+            "1/2\n./src/main.rs:501 loc-limits\n"
+        );
+        assert!(render(&results(), "{{ summary.typo }}").is_err());
+    }
+
+    #[test]
+    fn test_example_templates_render() {
+        let slack = render(
+            &results(),
+            include_str!("../../../examples/templates/slack.json.j2"),
+        )
+        .unwrap();
+        let payload: serde_json::Value = serde_json::from_str(&slack).unwrap();
+        assert!(payload["text"].as_str().unwrap().contains("1 error"));
+
+        let summary = render(
+            &results(),
+            include_str!("../../../examples/templates/summary.md.j2"),
+        )
+        .unwrap();
+        assert!(summary.contains("src/main.rs:501"));
+    }
+}
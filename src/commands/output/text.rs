@@ -0,0 +1,232 @@
+//! Human-readable check output, grouped by check or by file.
+//!
+//! Results from a `check --recursive` run name their project in the check
+//! headers, or under a file header, beside checks that name no file.
+
+use super::checks::{CheckSummary, file_groups};
+use super::color::{Style, paint};
+use super::link::link;
+use crate::checks::{CheckResult, Severity};
+use crate::commands::cli::GroupBy;
+use crate::commands::explain::Explanation;
+use std::fmt::Write;
+
+/// Header for results that aren't tied to a file.
+const PROJECT_HEADER: &str = "(project)";
+
+/// Render results under one header per check or per file, then the totals.
+pub(super) fn render_text(
+    results: &[CheckResult],
+    summary: &CheckSummary,
+    group_by: GroupBy,
+    color: bool,
+) -> String {
+    let groups: Vec<(&str, Vec<&CheckResult>)> = match group_by {
+        GroupBy::Check => {
+            let mut groups: Vec<(&str, Vec<&CheckResult>)> = Vec::new();
+            for result in results {
+                match groups.last_mut() {
+                    Some((name, group))
+                        if *name == result.check_name && group[0].project == result.project =>
+                    {
+                        group.push(result)
+                    }
+                    _ => groups.push((&result.check_name, vec![result])),
+                }
+            }
+            groups
+        }
+        GroupBy::File => file_groups(results)
+            .into_iter()
+            .map(|(file, group)| (file.unwrap_or(PROJECT_HEADER), group))
+            .collect(),
+    };
+
+    let mut out = String::from("Guardian Checklist Results\n");
+    for (header, group) in groups {
+        let header = match (group_by, &group[0].project) {
+            (GroupBy::File, _) if header != PROJECT_HEADER => link(header, header, None, None),
+            (GroupBy::Check, Some(project)) => format!("{project}: {header}"),
+            _ => header.to_string(),
+        };
+        let _ = writeln!(out, "\n[{header}]");
+        for result in group {
+            result_lines(&mut out, result, group_by, color);
+        }
+    }
+
+    out.push_str("\n---\n");
+    let _ = writeln!(
+        out,
+        "Total: {} | Passed: {} | Failed: {} ({} errors, {} warnings)",
+        summary.total, summary.passed, summary.failed, summary.errors, summary.warnings
+    );
+    out
+}
+
+/// The model's explanation of each failing check, or nothing without any.
+pub(super) fn render_explanations(explanations: &[Explanation], color: bool) -> String {
+    let mut out = String::new();
+    if !explanations.is_empty() {
+        out.push_str("\n=== Explanations ===\n");
+    }
+    for explanation in explanations {
+        let _ = writeln!(out, "\n[{}]", explanation.check);
+        let _ = writeln!(out, "  {}", explanation.rationale.trim());
+        let remediation = format!("  Remediation: {}", explanation.remediation.trim());
+        let _ = writeln!(out, "{}", paint(&remediation, Style::Dim, color));
+    }
+    out
+}
+
+/// One result, plus its fix. Under a file header, name the check and line.
+fn result_lines(out: &mut String, result: &CheckResult, group_by: GroupBy, color: bool) {
+    let status = match (result.passed, result.severity) {
+        (true, _) => paint("[OK]", Style::Green, color),
+        (false, Severity::Info) => "[FAIL]".to_string(),
+        (false, Severity::Warning) => paint("[FAIL] [WARN]", Style::Yellow, color),
+        (false, Severity::Error) => paint("[FAIL] [ERROR]", Style::Red, color),
+    };
+
+    // The part that names the location links to it
+    let linked = |text: &str| match &result.file {
+        Some(file) => link(text, file, result.line, result.column),
+        None => text.to_string(),
+    };
+    match (group_by, result.line) {
+        (GroupBy::Check, _) => {
+            let _ = writeln!(out, "  {status} {}", linked(&result.message));
+        }
+        (GroupBy::File, Some(line)) => {
+            let location = linked(&format!("{} (line {line})", result.check_name));
+            let _ = writeln!(out, "  {status} {location}: {}", result.message);
+        }
+        (GroupBy::File, None) => {
+            let check = match (&result.file, &result.project) {
+                (None, Some(project)) => format!("{project}: {}", result.check_name),
+                _ => result.check_name.clone(),
+            };
+            let _ = writeln!(out, "  {status} {check}: {}", result.message);
+        }
+    }
+
+    if let Some(fix) = &result.fix {
+        let _ = writeln!(
+            out,
+            "{}",
+            paint(&format!("       Fix: {fix}"), Style::Dim, color)
+        );
+    }
+    if let Some(blame) = &result.blame {
+        let line = format!(
+            "       Blame: {:.7} {} <{}>: {}",
+            blame.commit, blame.author, blame.email, blame.summary
+        );
+        let _ = writeln!(out, "{}", paint(&line, Style::Dim, color));
+    }
+    if let Some(diff) = &result.diff {
+        diff_lines(out, diff, color);
+    }
+}
+
+/// An indented diff, with removed lines in red and added lines in green.
+fn diff_lines(out: &mut String, diff: &str, color: bool) {
+    for line in diff.lines() {
+        let style = match line.chars().next() {
+            Some('+') if !line.starts_with("+++") => Style::Green,
+            Some('-') if !line.starts_with("---") => Style::Red,
+            _ => Style::Dim,
+        };
+        let _ = writeln!(out, "         {}", paint(line, style, color));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checks::blame::Blame;
+
+    fn results() -> Vec<CheckResult> {
+        let mut big = CheckResult::fail("loc-limits", Severity::Error, "612 > 500 lines");
+        big.file = Some("./src/main.rs".to_string());
+        let mut fns = CheckResult::fail("function-count", Severity::Warning, "8 fns");
+        fns.file = Some("src/main.rs".to_string());
+        fns.line = Some(4);
+        fns.fix = Some("Split it".to_string());
+        fns.blame = Some(Blame {
+            commit: "1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b".to_string(),
+            author: "Jane Doe".to_string(),
+            email: "jane@example.com".to_string(),
+            time: 1_760_000_000,
+            summary: "Add a".to_string(),
+        });
+        fns.diff = Some(
+            "--- a/src/main.rs\n+++ b/src/main.rs\n@@ -4,1 +4,1 @@\n-fn a() {}\n+fn b() {}\n"
+                .to_string(),
+        );
+        vec![
+            CheckResult::pass("rust-edition", "Edition 2024"),
+            big,
+            CheckResult::pass("function-count", "lib.rs: 3 fns"),
+            fns,
+        ]
+    }
+
+    #[test]
+    fn test_render_by_check() {
+        let results = results();
+        let text = render_text(&results, &CheckSummary::of(&results), GroupBy::Check, false);
+        assert!(text.starts_with(
+            "Guardian Checklist Results\n\n[rust-edition]\n  [OK] Edition 2024\n\n[loc-limits]\n"
+        ));
+        assert!(text.contains(
+            "[function-count]\n  [OK] lib.rs: 3 fns\n  [FAIL] [WARN] 8 fns\n       Fix: Split it\n       \
+             Blame: 1a2b3c4 Jane Doe <jane@example.com>: Add a\n"
+        ));
+        assert!(
+            text.contains("\n         @@ -4,1 +4,1 @@\n         -fn a() {}\n         +fn b() {}\n")
+        );
+        assert!(text.ends_with("Total: 4 | Passed: 2 | Failed: 2 (1 errors, 1 warnings)\n"));
+    }
+
+    #[test]
+    fn test_render_by_file() {
+        let results = results();
+        let text = render_text(&results, &CheckSummary::of(&results), GroupBy::File, false);
+        assert!(text.contains(
+            "\n[src/main.rs]\n  [FAIL] [ERROR] loc-limits: 612 > 500 lines\n  \
+             [FAIL] [WARN] function-count (line 4): 8 fns\n       Fix: Split it\n"
+        ));
+        assert!(text.contains("\n[(project)]\n  [OK] rust-edition: Edition 2024\n"));
+        assert_eq!(text.matches("[src/main.rs]").count(), 1);
+    }
+
+    #[test]
+    fn test_render_projects() {
+        let mut results = results();
+        for (result, project) in results.iter_mut().zip(["a", "a", "b", "b"]) {
+            result.project = Some(project.to_string());
+        }
+        let summary = CheckSummary::of(&results);
+        let text = render_text(&results, &summary, GroupBy::Check, false);
+        assert!(text.contains("\n[a: loc-limits]\n"));
+        assert!(text.contains("\n[b: function-count]\n  [OK] lib.rs: 3 fns\n  [FAIL]"));
+        let text = render_text(&results, &summary, GroupBy::File, false);
+        assert!(text.contains("\n[(project)]\n  [OK] a: rust-edition: Edition 2024\n"));
+    }
+
+    #[test]
+    fn test_render_explanations() {
+        assert_eq!(render_explanations(&[], false), "");
+        let explanations = [Explanation {
+            check: "loc-limits".to_string(),
+            rationale: "Long files hide structure.\n".to_string(),
+            remediation: "Split main.rs by concern.".to_string(),
+        }];
+        assert_eq!(
+            render_explanations(&explanations, false),
+            "\n=== Explanations ===\n\n[loc-limits]\n  Long files hide structure.\n  \
+             Remediation: Split main.rs by concern.\n"
+        );
+    }
+}
@@ -0,0 +1,180 @@
+//! Model-written fix patches, for `evaluate --suggest-patches`.
+//!
+//! Each error tied to a file is sent to the evaluating model with the source
+//! around it, and the model answers with a unified diff. Only diffs that
+//! touch that file alone and pass `git apply --check` from the current
+//! directory are kept. Nothing is applied; the report shows them for a
+//! person to review and apply.
+
+use super::llm::{cancellable, check_context_window, task_client};
+use crate::checks::{CheckResult, Severity};
+use crate::config::{GuardianConfig, OllamaHost};
+use crate::ollama::select::load_breaker;
+use anyhow::{Context, Result, bail};
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// Errors patches are asked for, so one run can't queue up unbounded work.
+const MAX_PATCHES: usize = 10;
+
+/// Lines of source sent on each side of the violation.
+const SNIPPET_RADIUS: usize = 30;
+
+/// A diff the model wrote for one error. It applied cleanly when suggested.
+pub(super) struct SuggestedPatch<'r> {
+    pub result: &'r CheckResult,
+    pub diff: String,
+}
+
+/// Ask `model` on `host` for a patch for each error in `results`, keeping
+/// the ones that apply.
+pub(super) async fn suggest<'r>(
+    config: &GuardianConfig,
+    host: &OllamaHost,
+    model: &str,
+    results: &'r [CheckResult],
+) -> Result<Vec<SuggestedPatch<'r>>> {
+    let errors: Vec<_> = results
+        .iter()
+        .filter(|r| !r.passed && r.severity == Severity::Error && r.file.is_some())
+        .collect();
+    if errors.len() > MAX_PATCHES {
+        tracing::warn!(
+            "Only suggesting patches for the first {MAX_PATCHES} of {} errors",
+            errors.len()
+        );
+    }
+    let client = task_client(config, "evaluate", 180_000)?;
+    let system = config.route("evaluate").system_prompt;
+    let system = system.as_deref();
+    let mut breaker = load_breaker(config);
+    let mut patches = Vec::new();
+    for result in errors.into_iter().take(MAX_PATCHES) {
+        let path = result.file.as_deref().unwrap_or_default();
+        let path = path.trim_start_matches("./");
+        let Ok(content) = std::fs::read_to_string(path) else {
+            tracing::debug!(path, "Not a readable file, no patch");
+            continue;
+        };
+        let prompt = build_prompt(result, path, &content);
+        check_context_window(config, &client, host, model, system, &prompt).await?;
+        eprintln!("Asking {model} for a patch to {path}...");
+        let response = cancellable(&client, client.generate(host, model, &prompt)).await;
+        breaker.record(&host.name, response.is_ok());
+        let response = response?;
+        let Some(diff) = extract_diff(&response.response, path) else {
+            tracing::warn!("{model} didn't answer with a diff for {path}; skipped");
+            continue;
+        };
+        match git_apply(Path::new("."), &diff, true) {
+            Ok(()) => patches.push(SuggestedPatch { result, diff }),
+            Err(e) => tracing::warn!("{model}'s patch for {path} was dropped: {e:#}"),
+        }
+    }
+    breaker.save();
+    Ok(patches)
+}
+
+/// The prompt for a patch to `path`, quoting the lines around the error.
+pub(super) fn build_prompt(result: &CheckResult, path: &str, content: &str) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let center = result.line.unwrap_or(1).saturating_sub(1);
+    let start = center.saturating_sub(SNIPPET_RADIUS).min(lines.len());
+    let end = (center + SNIPPET_RADIUS + 1).min(lines.len());
+    let mut prompt = format!(
+        "You are fixing a code quality violation.\n\n## Violation\n\n[{}] {}\n",
+        result.check_name, result.message
+    );
+    if let Some(fix) = &result.fix {
+        prompt.push_str(&format!("Suggested fix: {fix}\n"));
+    }
+    prompt.push_str(&format!(
+        "\n## Source\n\n{path}, lines {} to {} of {}:\n\n```\n",
+        start + 1,
+        end,
+        lines.len()
+    ));
+    for line in &lines[start..end] {
+        prompt.push_str(line);
+        prompt.push('\n');
+    }
+    prompt.push_str(&format!(
+        "```\n\n## Your Task\n\n\
+         Reply with only a unified diff that fixes the violation, changing \
+         nothing else. Use `--- a/{path}` and `+++ b/{path}` headers, correct \
+         `@@` line numbers, and context lines copied exactly from the source.\n"
+    ));
+    prompt
+}
+
+/// The diff in a model's `reply`, if it is one that only touches `path`.
+///
+/// Accepts a bare diff or one in a code fence.
+pub(super) fn extract_diff(reply: &str, path: &str) -> Option<String> {
+    let start = reply.find("--- ")?;
+    let body = &reply[start..];
+    let end = body.find("\n```").unwrap_or(body.len());
+    let diff = format!("{}\n", body[..end].trim_end());
+    let target = format!("+++ b/{path}");
+    let mut targets = diff.lines().filter(|l| l.starts_with("+++ ")).peekable();
+    targets.peek()?;
+    targets.all(|l| l.trim_end() == target).then_some(diff)
+}
+
+/// Apply `diff` from `dir` with `git apply`, or with `check_only` just
+/// check that it applies cleanly.
+pub(super) fn git_apply(dir: &Path, diff: &str, check_only: bool) -> Result<()> {
+    let args: &[&str] = if check_only {
+        &["apply", "--check", "-"]
+    } else {
+        &["apply", "-"]
+    };
+    let mut child = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to run git apply")?;
+    child
+        .stdin
+        .take()
+        .context("git apply has no stdin")?
+        .write_all(diff.as_bytes())?;
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prompt_quotes_lines_around_error() {
+        let content: String = (1..=100).map(|i| format!("line {i}\n")).collect();
+        let result = CheckResult::fail("clippy-disables", Severity::Error, "Lint suppressed")
+            .with_file("src/lib.rs")
+            .with_line(50);
+        let prompt = build_prompt(&result, "src/lib.rs", &content);
+        assert!(prompt.contains("[clippy-disables] Lint suppressed\n"));
+        assert!(prompt.contains("src/lib.rs, lines 20 to 80 of 100:\n\n```\nline 20\n"));
+        assert!(prompt.contains("line 80\n```"));
+        assert!(!prompt.contains("line 81\n"));
+        assert!(prompt.contains("`+++ b/src/lib.rs`"));
+    }
+
+    #[test]
+    fn test_extract_diff() {
+        let diff = "--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1,1 +1,1 @@\n-a\n+b\n";
+        let reply = format!("Here you go:\n\n```diff\n{diff}```\nDone.");
+        assert_eq!(extract_diff(&reply, "src/lib.rs").as_deref(), Some(diff));
+        assert_eq!(extract_diff(diff, "src/lib.rs").as_deref(), Some(diff));
+        assert_eq!(extract_diff(diff, "src/main.rs"), None);
+        assert_eq!(extract_diff("Looks fine to me.", "src/lib.rs"), None);
+    }
+}
@@ -0,0 +1,157 @@
+//! Evaluating a parsed policy expression.
+
+use super::parser::Expr;
+use anyhow::{Result, bail};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Value {
+    Num(f64),
+    Str(String),
+    Bool(bool),
+    Map(BTreeMap<String, Value>),
+}
+
+impl Value {
+    fn kind(&self) -> &'static str {
+        match self {
+            Value::Num(_) => "number",
+            Value::Str(_) => "string",
+            Value::Bool(_) => "boolean",
+            Value::Map(_) => "map",
+        }
+    }
+}
+
+/// Evaluate `expr` with `vars` as the top-level names.
+pub(super) fn eval(expr: &Expr, vars: &BTreeMap<String, Value>) -> Result<Value> {
+    Ok(match expr {
+        Expr::Num(n) => Value::Num(*n),
+        Expr::Str(s) => Value::Str(s.clone()),
+        Expr::Bool(b) => Value::Bool(*b),
+        Expr::Var(name) => lookup(vars, name, "name")?,
+        Expr::Not(inner) => match eval(inner, vars)? {
+            Value::Bool(b) => Value::Bool(!b),
+            other => bail!("`!` needs a boolean, got a {}", other.kind()),
+        },
+        Expr::Neg(inner) => match eval(inner, vars)? {
+            Value::Num(n) => Value::Num(-n),
+            other => bail!("`-` needs a number, got a {}", other.kind()),
+        },
+        Expr::Field(inner, name) => match eval(inner, vars)? {
+            Value::Map(map) => lookup(&map, name, "field")?,
+            other => bail!("a {} has no field `{name}`", other.kind()),
+        },
+        Expr::Index(inner, key) => match (eval(inner, vars)?, eval(key, vars)?) {
+            (Value::Map(map), Value::Str(key)) => lookup(&map, &key, "key")?,
+            (Value::Map(_), key) => bail!("map keys are strings, got a {}", key.kind()),
+            (other, _) => bail!("a {} can't be indexed", other.kind()),
+        },
+        Expr::Binary(op @ ("&&" | "||"), left, right) => {
+            let Value::Bool(left) = eval(left, vars)? else {
+                bail!("`{op}` needs booleans");
+            };
+            // Both sides are evaluated, so a mistake on the right fails even
+            // when the left side alone would decide.
+            match eval(right, vars)? {
+                Value::Bool(right) if *op == "&&" => Value::Bool(left && right),
+                Value::Bool(right) => Value::Bool(left || right),
+                _ => bail!("`{op}` needs booleans"),
+            }
+        }
+        Expr::Binary(op, left, right) => binary(op, eval(left, vars)?, eval(right, vars)?)?,
+    })
+}
+
+fn binary(op: &str, left: Value, right: Value) -> Result<Value> {
+    if op == "==" || op == "!=" {
+        if left.kind() != right.kind() {
+            bail!(
+                "can't compare a {} with a {} using `{op}`",
+                left.kind(),
+                right.kind()
+            );
+        }
+        return Ok(Value::Bool((left == right) == (op == "==")));
+    }
+    let (Value::Num(l), Value::Num(r)) = (&left, &right) else {
+        bail!(
+            "`{op}` needs numbers, got a {} and a {}",
+            left.kind(),
+            right.kind()
+        );
+    };
+    let (l, r) = (*l, *r);
+    Ok(match op {
+        "<" => Value::Bool(l < r),
+        "<=" => Value::Bool(l <= r),
+        ">" => Value::Bool(l > r),
+        ">=" => Value::Bool(l >= r),
+        "+" => Value::Num(l + r),
+        "-" => Value::Num(l - r),
+        "*" => Value::Num(l * r),
+        "/" => Value::Num(l / r),
+        _ => bail!("unknown operator `{op}`"),
+    })
+}
+
+fn lookup(map: &BTreeMap<String, Value>, name: &str, what: &str) -> Result<Value> {
+    match map.get(name) {
+        Some(value) => Ok(value.clone()),
+        None => {
+            let known: Vec<&str> = map.keys().map(String::as_str).collect();
+            bail!("unknown {what} `{name}` (known: {})", known.join(", "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{lexer::tokenize, parser::parse};
+    use super::*;
+
+    fn run(source: &str) -> Result<Value> {
+        let check = BTreeMap::from([("failed".to_string(), Value::Num(2.0))]);
+        let vars = BTreeMap::from([
+            ("errors".to_string(), Value::Num(0.0)),
+            ("score".to_string(), Value::Num(90.0)),
+            (
+                "checks".to_string(),
+                Value::Map(BTreeMap::from([(
+                    "loc-limits".to_string(),
+                    Value::Map(check),
+                )])),
+            ),
+        ]);
+        eval(&parse(tokenize(source)?)?, &vars)
+    }
+
+    #[test]
+    fn test_eval() {
+        assert_eq!(
+            run("errors == 0 && checks['loc-limits'].failed <= 2 && score >= 85").unwrap(),
+            Value::Bool(true)
+        );
+        assert_eq!(
+            run("errors + 1 > 1 || !(score < 50)").unwrap(),
+            Value::Bool(true)
+        );
+        assert_eq!(run("-score * 2").unwrap(), Value::Num(-180.0));
+    }
+
+    #[test]
+    fn test_eval_errors() {
+        let err = run("checks['loc-limit'].failed == 0").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown key `loc-limit` (known: loc-limits)"
+        );
+        let err = run("errors == 'none'").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "can't compare a number with a string using `==`"
+        );
+        assert!(run("errors && true").is_err());
+        assert!(run("false && errors").is_err());
+    }
+}
@@ -0,0 +1,114 @@
+//! Splitting a policy expression into tokens.
+
+use anyhow::{Result, bail};
+use std::fmt;
+
+/// Operators and punctuation, longest first so `<=` wins over `<`.
+const OPERATORS: &[&str] = &[
+    "||", "&&", "==", "!=", "<=", ">=", "<", ">", "!", "+", "-", "*", "/", "(", ")", "[", "]", ".",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Token {
+    Num(f64),
+    Str(String),
+    Ident(String),
+    Op(&'static str),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Num(n) => write!(f, "number {n}"),
+            Token::Str(s) => write!(f, "string '{s}'"),
+            Token::Ident(name) => write!(f, "`{name}`"),
+            Token::Op(op) => write!(f, "`{op}`"),
+        }
+    }
+}
+
+/// Tokenize `source`, pairing each token with its 1-based column.
+pub(super) fn tokenize(source: &str) -> Result<Vec<(usize, Token)>> {
+    let mut tokens = Vec::new();
+    let mut rest = source.char_indices().peekable();
+    while let Some(&(start, c)) = rest.peek() {
+        let column = source[..start].chars().count() + 1;
+        if c.is_whitespace() {
+            rest.next();
+        } else if c.is_ascii_digit() {
+            let mut end = start;
+            while let Some(&(i, c)) = rest.peek() {
+                if !(c.is_ascii_digit() || c == '.') {
+                    break;
+                }
+                end = i + c.len_utf8();
+                rest.next();
+            }
+            let text = &source[start..end];
+            let Ok(num) = text.parse() else {
+                bail!("invalid number `{text}` at column {column}");
+            };
+            tokens.push((column, Token::Num(num)));
+        } else if c.is_alphabetic() || c == '_' {
+            let mut ident = String::new();
+            while let Some(&(_, c)) = rest.peek() {
+                if !(c.is_alphanumeric() || c == '_') {
+                    break;
+                }
+                ident.push(c);
+                rest.next();
+            }
+            tokens.push((column, Token::Ident(ident)));
+        } else if c == '\'' || c == '"' {
+            rest.next();
+            let mut text = String::new();
+            loop {
+                match rest.next() {
+                    Some((_, end)) if end == c => break,
+                    Some((_, ch)) => text.push(ch),
+                    None => bail!("unterminated string at column {column}"),
+                }
+            }
+            tokens.push((column, Token::Str(text)));
+        } else if let Some(op) = OPERATORS
+            .iter()
+            .find(|op| source[start..].starts_with(**op))
+        {
+            for _ in 0..op.len() {
+                rest.next();
+            }
+            tokens.push((column, Token::Op(op)));
+        } else {
+            bail!("unexpected `{c}` at column {column}");
+        }
+    }
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize() {
+        let tokens = tokenize("checks['loc-limits'].failed <= 2.5").unwrap();
+        let tokens: Vec<Token> = tokens.into_iter().map(|(_, t)| t).collect();
+        assert_eq!(
+            tokens,
+            [
+                Token::Ident("checks".to_string()),
+                Token::Op("["),
+                Token::Str("loc-limits".to_string()),
+                Token::Op("]"),
+                Token::Op("."),
+                Token::Ident("failed".to_string()),
+                Token::Op("<="),
+                Token::Num(2.5),
+            ]
+        );
+
+        let err = tokenize("errors == 0 & warnings").unwrap_err();
+        assert_eq!(err.to_string(), "unexpected `&` at column 13");
+        assert!(tokenize("checks['loc").is_err());
+    }
+}
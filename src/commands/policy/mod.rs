@@ -0,0 +1,178 @@
+//! Policy expressions: a small CEL-like language that decides whether check
+//! results pass.
+//!
+//! ```text
+//! errors == 0 && checks['loc-limits'].failed == 0 && score >= 85
+//! ```
+//!
+//! An expression sees these names:
+//!
+//! - `errors`, `warnings`: failed results of each severity
+//! - `failed`, `passed`, `total`: result counts
+//! - `score`: 0 to 100, the share of checks that ran clean, where a check
+//!   with only warnings counts half
+//! - `checks['NAME']`: per-check `errors`, `warnings`, and `failed` counts,
+//!   and `ran`, which is false for checks that were skipped
+//!
+//! It supports `||`, `&&`, `!`, comparisons, `+ - * /`, parentheses, numbers,
+//! `'strings'`, and `true`/`false`, and must evaluate to a boolean.
+
+mod eval;
+mod lexer;
+mod parser;
+
+use crate::checks::{CHECK_NAMES, CheckResult, Severity};
+use anyhow::{Context, Result, bail};
+use eval::Value;
+use parser::Expr;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// A parsed policy expression.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Policy {
+    source: String,
+    expr: Expr,
+}
+
+impl Policy {
+    /// Parse `source`, and check it against empty results so unknown names
+    /// and type mistakes fail before any check runs.
+    pub fn parse(source: &str) -> Result<Self> {
+        let invalid = || format!("Invalid policy `{source}`");
+        let expr =
+            parser::parse(lexer::tokenize(source).with_context(invalid)?).with_context(invalid)?;
+        let policy = Self {
+            source: source.to_string(),
+            expr,
+        };
+        policy.allows(&[]).with_context(invalid)?;
+        Ok(policy)
+    }
+
+    /// Whether `results` satisfy the policy.
+    pub fn allows(&self, results: &[CheckResult]) -> Result<bool> {
+        match eval::eval(&self.expr, &summary(results))? {
+            Value::Bool(allowed) => Ok(allowed),
+            _ => bail!("the policy must be true or false"),
+        }
+    }
+}
+
+impl fmt::Display for Policy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+/// The names a policy can use, computed from `results`.
+fn summary(results: &[CheckResult]) -> BTreeMap<String, Value> {
+    let num = |n: usize| Value::Num(n as f64);
+    let mut checks = BTreeMap::new();
+    for &name in CHECK_NAMES {
+        let own: Vec<&CheckResult> = results.iter().filter(|r| r.check_name == name).collect();
+        let (errors, warnings, failed) = tally(own.iter().copied());
+        let check = BTreeMap::from([
+            ("errors".to_string(), num(errors)),
+            ("warnings".to_string(), num(warnings)),
+            ("failed".to_string(), num(failed)),
+            ("ran".to_string(), Value::Bool(!own.is_empty())),
+        ]);
+        checks.insert(name.to_string(), Value::Map(check));
+    }
+
+    let (errors, warnings, failed) = tally(results);
+    BTreeMap::from([
+        ("errors".to_string(), num(errors)),
+        ("warnings".to_string(), num(warnings)),
+        ("failed".to_string(), num(failed)),
+        ("passed".to_string(), num(results.len() - failed)),
+        ("total".to_string(), num(results.len())),
+        ("score".to_string(), Value::Num(score(results))),
+        ("checks".to_string(), Value::Map(checks)),
+    ])
+}
+
+/// 0 to 100, the share of checks that ran clean, where a check with only
+/// warnings counts half.
+pub fn score(results: &[CheckResult]) -> f64 {
+    let (mut ran, mut clean) = (0.0, 0.0_f64);
+    for &name in CHECK_NAMES {
+        let own: Vec<&CheckResult> = results.iter().filter(|r| r.check_name == name).collect();
+        if own.is_empty() {
+            continue;
+        }
+        let (errors, warnings, _) = tally(own);
+        ran += 1.0;
+        clean += match (errors, warnings) {
+            (0, 0) => 1.0,
+            (0, _) => 0.5,
+            _ => 0.0,
+        };
+    }
+    if ran == 0.0 {
+        100.0
+    } else {
+        (100.0 * clean / ran).round()
+    }
+}
+
+/// Count failed results: errors, warnings, and all failures.
+fn tally<'a>(results: impl IntoIterator<Item = &'a CheckResult>) -> (usize, usize, usize) {
+    let (mut errors, mut warnings, mut failed) = (0, 0, 0);
+    for result in results.into_iter().filter(|r| !r.passed) {
+        match result.severity {
+            Severity::Error => errors += 1,
+            Severity::Warning => warnings += 1,
+            Severity::Info => {}
+        }
+        failed += 1;
+    }
+    (errors, warnings, failed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn results() -> Vec<CheckResult> {
+        vec![
+            CheckResult::pass("rust-edition", "ok"),
+            CheckResult::pass("function-count", "ok"),
+            CheckResult::fail("loc-limits", Severity::Warning, "360 lines"),
+            CheckResult::fail("module-count", Severity::Error, "9 modules"),
+        ]
+    }
+
+    #[test]
+    fn test_policy_allows() {
+        let policy = Policy::parse("errors <= 1 && checks['loc-limits'].warnings == 1").unwrap();
+        assert!(policy.allows(&results()).unwrap());
+
+        // Two clean checks, one with warnings, one with errors: 2.5 of 4.
+        let policy = Policy::parse("score >= 85").unwrap();
+        assert!(!policy.allows(&results()).unwrap());
+        assert!(
+            Policy::parse("score == 63")
+                .unwrap()
+                .allows(&results())
+                .unwrap()
+        );
+        assert!(policy.allows(&[]).unwrap());
+
+        let policy = Policy::parse("!checks['test-quality'].ran && failed == 2").unwrap();
+        assert!(policy.allows(&results()).unwrap());
+    }
+
+    #[test]
+    fn test_policy_rejected_up_front() {
+        let err = format!(
+            "{:#}",
+            Policy::parse("checks['loc-limit'].failed == 0").unwrap_err()
+        );
+        assert!(err.starts_with("Invalid policy `checks['loc-limit'].failed == 0`: unknown key"));
+        let err = format!("{:#}", Policy::parse("errors + 1").unwrap_err());
+        assert!(err.ends_with("the policy must be true or false"));
+        assert!(Policy::parse("errors = 0").is_err());
+    }
+}
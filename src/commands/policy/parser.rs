@@ -0,0 +1,180 @@
+//! Parsing policy tokens into an expression tree.
+//!
+//! Precedence, loosest first: `||`, `&&`, `==` `!=`, `<` `<=` `>` `>=`,
+//! `+` `-`, `*` `/`, then prefix `!` and `-`, then `.field` and `[key]`.
+
+use super::lexer::Token;
+use anyhow::{Result, bail};
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Expr {
+    Num(f64),
+    Str(String),
+    Bool(bool),
+    Var(String),
+    Not(Box<Expr>),
+    Neg(Box<Expr>),
+    Binary(&'static str, Box<Expr>, Box<Expr>),
+    /// `value.name`
+    Field(Box<Expr>, String),
+    /// `value[key]`
+    Index(Box<Expr>, Box<Expr>),
+}
+
+/// Parse a whole token stream, which must form exactly one expression.
+pub(super) fn parse(tokens: Vec<(usize, Token)>) -> Result<Expr> {
+    let mut parser = Parser { tokens, pos: 0 };
+    let expr = parser.binary(0)?;
+    if let Some((column, token)) = parser.tokens.get(parser.pos) {
+        bail!("unexpected {token} at column {column}");
+    }
+    Ok(expr)
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    pos: usize,
+}
+
+impl Parser {
+    /// Parse operators binding at least as tightly as `min`.
+    fn binary(&mut self, min: u8) -> Result<Expr> {
+        let mut left = self.unary()?;
+        while let Some((_, Token::Op(op))) = self.tokens.get(self.pos) {
+            let op = *op;
+            let Some(prec) = precedence(op).filter(|&p| p >= min) else {
+                break;
+            };
+            self.pos += 1;
+            let right = self.binary(prec + 1)?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr> {
+        match self.tokens.get(self.pos) {
+            Some((_, Token::Op("!"))) => {
+                self.pos += 1;
+                Ok(Expr::Not(Box::new(self.unary()?)))
+            }
+            Some((_, Token::Op("-"))) => {
+                self.pos += 1;
+                Ok(Expr::Neg(Box::new(self.unary()?)))
+            }
+            _ => self.postfix(),
+        }
+    }
+
+    fn postfix(&mut self) -> Result<Expr> {
+        let mut expr = self.primary()?;
+        loop {
+            match self.tokens.get(self.pos) {
+                Some((_, Token::Op("."))) => {
+                    self.pos += 1;
+                    match self.tokens.get(self.pos) {
+                        Some((_, Token::Ident(name))) => {
+                            expr = Expr::Field(Box::new(expr), name.clone());
+                            self.pos += 1;
+                        }
+                        _ => bail!("expected a field name after `.`"),
+                    }
+                }
+                Some((_, Token::Op("["))) => {
+                    self.pos += 1;
+                    let key = self.binary(0)?;
+                    self.expect("]")?;
+                    expr = Expr::Index(Box::new(expr), Box::new(key));
+                }
+                _ => return Ok(expr),
+            }
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr> {
+        let Some((column, token)) = self.tokens.get(self.pos).cloned() else {
+            bail!("expression ends early");
+        };
+        self.pos += 1;
+        match token {
+            Token::Num(n) => Ok(Expr::Num(n)),
+            Token::Str(s) => Ok(Expr::Str(s)),
+            Token::Ident(name) => Ok(match name.as_str() {
+                "true" => Expr::Bool(true),
+                "false" => Expr::Bool(false),
+                _ => Expr::Var(name),
+            }),
+            Token::Op("(") => {
+                let expr = self.binary(0)?;
+                self.expect(")")?;
+                Ok(expr)
+            }
+            token => bail!("unexpected {token} at column {column}"),
+        }
+    }
+
+    fn expect(&mut self, op: &str) -> Result<()> {
+        match self.tokens.get(self.pos) {
+            Some((_, Token::Op(found))) if *found == op => {
+                self.pos += 1;
+                Ok(())
+            }
+            Some((column, token)) => {
+                bail!("expected `{op}`, found {token} at column {column}")
+            }
+            None => bail!("expected `{op}`, but the expression ends"),
+        }
+    }
+}
+
+fn precedence(op: &str) -> Option<u8> {
+    Some(match op {
+        "||" => 1,
+        "&&" => 2,
+        "==" | "!=" => 3,
+        "<" | "<=" | ">" | ">=" => 4,
+        "+" | "-" => 5,
+        "*" | "/" => 6,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::lexer::tokenize;
+    use super::*;
+
+    fn parse_str(source: &str) -> Result<Expr> {
+        parse(tokenize(source)?)
+    }
+
+    #[test]
+    fn test_precedence() {
+        let expr = parse_str("errors == 0 || warnings < 3 && !strict").unwrap();
+        let Expr::Binary("||", left, right) = expr else {
+            panic!("expected || at the root");
+        };
+        assert!(matches!(*left, Expr::Binary("==", _, _)));
+        assert!(matches!(*right, Expr::Binary("&&", _, _)));
+
+        let expr = parse_str("checks['loc-limits'].failed").unwrap();
+        assert!(matches!(expr, Expr::Field(ref inner, ref f)
+            if f == "failed" && matches!(**inner, Expr::Index(_, _))));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_str("errors == ").unwrap_err().to_string(),
+            "expression ends early"
+        );
+        assert_eq!(
+            parse_str("(errors == 0").unwrap_err().to_string(),
+            "expected `)`, but the expression ends"
+        );
+        assert_eq!(
+            parse_str("errors 0").unwrap_err().to_string(),
+            "unexpected number 0 at column 8"
+        );
+    }
+}
@@ -0,0 +1,241 @@
+//! Pre-push command: check the commits about to be pushed.
+//!
+//! Runs the checks on the files the pushed commits touch, the commit-message
+//! check on each commit, and optionally a quick LLM review of the patches,
+//! with follow-up questions about it when `--interactive`.
+
+use super::cli::{GroupBy, ReportFormat};
+use super::exit::FailPolicy;
+use super::llm::{Routed, cancellable, check_context_window, routed};
+use super::output::{self, report};
+use super::{followup, grounding, history, readability, rules};
+use crate::checks::vcs::{Range, Vcs};
+use crate::checks::{self, CheckResult};
+use crate::checks::{configured_thresholds, run_selected_checks, select_checks};
+use crate::config::GuardianConfig;
+use anyhow::{Result, bail};
+use std::path::Path;
+
+/// Most bytes of patch text sent for review.
+const MAX_REVIEW_PATCH: usize = 48_000;
+
+/// Options for the pre-push command.
+pub struct PrePushOptions<'a> {
+    /// Commits to check, as `REMOTE..LOCAL` (git revisions, or jj revsets in
+    /// a jj repository).
+    pub range: &'a str,
+    pub path: Option<&'a Path>,
+    /// Only run these checks, comma-separated.
+    pub only: Option<&'a str>,
+    /// Don't run these checks, comma-separated.
+    pub skip: Option<&'a str>,
+    /// Also have an LLM review the pushed patches.
+    pub review: bool,
+    /// Take follow-up questions about the review afterwards.
+    pub interactive: bool,
+    pub model: Option<&'a str>,
+    pub host: Option<&'a str>,
+    pub json_output: bool,
+    pub policy: FailPolicy,
+}
+
+/// Check the commits in `opts.range` before they are pushed.
+pub async fn pre_push(config: &GuardianConfig, opts: PrePushOptions<'_>) -> Result<()> {
+    let project_dir = opts.path.unwrap_or(Path::new("."));
+    if opts.interactive {
+        followup::require_terminal(opts.json_output)?;
+    }
+    let Some(range) = revisions(opts.range)? else {
+        eprintln!("Nothing to push.");
+        return Ok(());
+    };
+
+    let vcs = Vcs::detect(project_dir);
+    let commits = vcs.commits(project_dir, range)?;
+    let changed = vcs.changed_files(project_dir, range)?;
+    eprintln!(
+        "Checking {} commit(s) touching {} file(s)...\n",
+        commits.len(),
+        changed.len()
+    );
+
+    let check_config = configured_thresholds(&config.check);
+    let selected = select_checks(
+        opts.only,
+        opts.skip,
+        config.check.only.as_deref(),
+        &config.checks,
+    )?;
+    // Results about files the push doesn't touch aren't this push's doing.
+    let mut results: Vec<CheckResult> = run_selected_checks(project_dir, &check_config, &selected)
+        .into_iter()
+        .filter(|r| match &r.file {
+            Some(file) => Path::new(file)
+                .strip_prefix(project_dir)
+                .is_ok_and(|file| changed.contains(file)),
+            None => true,
+        })
+        .collect();
+    results.extend(readability::check(config, project_dir, &selected, Some(&changed)).await);
+    for commit in &commits {
+        results.extend(checks::commit_message::check(&commit.id, &commit.message));
+    }
+
+    if opts.review {
+        let patches = vcs.patches(project_dir, range)?;
+        review(config, &opts, &results, &patches, project_dir).await?;
+    } else {
+        let format = output_format(opts.json_output);
+        output::check_results(&results, &[], format, GroupBy::Check, None)?;
+    }
+    let verdict = opts.policy.enforce(&results);
+    let outcome = history::outcome(&verdict);
+    history::record_run(
+        &config.storage,
+        project_dir,
+        "pre-push",
+        &results,
+        outcome,
+        None,
+    );
+    verdict
+}
+
+/// The commits in `range`, or `None` when the push deletes a branch.
+///
+/// A remote side of all zeros, as git's pre-push hook passes for a new
+/// branch, selects the commits no remote has yet.
+fn revisions(range: &str) -> Result<Option<Range<'_>>> {
+    let Some((remote, local)) = range.split_once("..") else {
+        bail!("Expected a commit range like origin/main..HEAD, got '{range}'");
+    };
+    let zeros = |rev: &str| !rev.is_empty() && rev.bytes().all(|b| b == b'0');
+    Ok((!zeros(local)).then(|| Range {
+        base: (!zeros(remote)).then_some(remote),
+        head: local,
+    }))
+}
+
+/// Have the model routed to `review` look over the pushed patches.
+async fn review(
+    config: &GuardianConfig,
+    opts: &PrePushOptions<'_>,
+    results: &[CheckResult],
+    patches: &str,
+    project_dir: &Path,
+) -> Result<()> {
+    let Routed {
+        client,
+        mut breaker,
+        host,
+        model: model_name,
+        system,
+    } = routed(config, "review", 180_000, opts.host, opts.model).await?;
+
+    let rules = rules::project_rules(project_dir)?;
+    let prompt = build_review_prompt(results, patches, rules.as_deref());
+    let system = system.as_deref();
+    check_context_window(config, &client, host, &model_name, system, &prompt).await?;
+    eprintln!("Sending the pushed patches to {model_name} for review...\n");
+    let response = cancellable(&client, client.generate(host, &model_name, &prompt)).await;
+    breaker.record(&host.name, response.is_ok());
+    breaker.save();
+    let mut response = response?;
+    response.response = grounding::guard_text(&response.response, project_dir, config);
+
+    if !opts.json_output {
+        output::check_results(results, &[], ReportFormat::Text, GroupBy::Check, None)?;
+        report!("");
+    }
+    output::evaluate_response(
+        host,
+        &model_name,
+        results,
+        &response,
+        None,
+        &[],
+        opts.json_output,
+    )?;
+    if opts.interactive {
+        let reply = &response.response;
+        followup::follow_up(config, "review", host, &model_name, &prompt, reply).await?;
+    }
+    Ok(())
+}
+
+fn build_review_prompt(results: &[CheckResult], patches: &str, rules: Option<&str>) -> String {
+    let mut prompt = String::from(
+        "You are a code reviewer giving a last look at commits before they are pushed.\n\n",
+    );
+    prompt.push_str(rules.unwrap_or_default());
+    prompt.push_str("## Failed Checks\n\n");
+    for result in results.iter().filter(|r| !r.passed) {
+        prompt.push_str(&format!("- [{:?}] {}\n", result.severity, result.message));
+    }
+    let mut end = patches.len().min(MAX_REVIEW_PATCH);
+    while !patches.is_char_boundary(end) {
+        end -= 1;
+    }
+    prompt.push_str("\n## Patches\n\n");
+    prompt.push_str(&patches[..end]);
+    if end < patches.len() {
+        prompt.push_str("\n[... patches truncated ...]\n");
+    }
+    prompt.push_str(
+        "\n## Your Task\n\n\
+         Point out bugs, risky changes, and missing tests in these patches, most \
+         serious first. Say so plainly if nothing needs fixing before the push. \
+         Be brief.\n",
+    );
+    prompt
+}
+
+fn output_format(json: bool) -> ReportFormat {
+    if json {
+        ReportFormat::Json
+    } else {
+        ReportFormat::Text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_revisions() {
+        assert_eq!(
+            revisions("origin/main..HEAD").unwrap(),
+            Some(Range {
+                base: Some("origin/main"),
+                head: "HEAD"
+            })
+        );
+        assert_eq!(
+            revisions("0000000000..abc123").unwrap(),
+            Some(Range {
+                base: None,
+                head: "abc123"
+            })
+        );
+        assert_eq!(revisions("abc123..0000000000").unwrap(), None);
+        assert!(revisions("HEAD").is_err());
+    }
+
+    #[test]
+    fn test_review_prompt_truncates_patches() {
+        let results = [CheckResult::fail(
+            "commit-message",
+            checks::Severity::Warning,
+            "abc1234: subject is 60 characters (max 50)",
+        )];
+        let rules = "## Project Rules\n\n- Every fix needs a test\n\n";
+        let patches = "+".repeat(MAX_REVIEW_PATCH + 10);
+        let prompt = build_review_prompt(&results, &patches, Some(rules));
+        assert!(
+            prompt.contains("pushed.\n\n## Project Rules\n\n- Every fix needs a test\n\n## Failed")
+        );
+        assert!(prompt.contains("- [Warning] abc1234: subject is 60 characters"));
+        assert!(prompt.contains("[... patches truncated ...]"));
+    }
+}
@@ -0,0 +1,69 @@
+//! Monorepo checks: `check --recursive` checking each project under a root.
+//!
+//! The projects are the directories the root's `[projects]` globs match,
+//! or else the ones holding a `Cargo.toml` (see [`discover`]). Each is
+//! checked with its own config, found from its directory as a plain `check`
+//! there would, with the command line's settings on top. The results come
+//! out as one report, each tagged with its project's path under the root,
+//! while webhooks, the failure policy, and the result history treat every
+//! project as a run of its own.
+
+use super::checks::{collect, finish, report};
+use super::cli::{CheckArgs, FailOn, ReportFormat};
+use crate::checks::interrupt::interrupted;
+use crate::checks::projects::discover;
+use crate::config::GuardianConfig;
+use anyhow::{Result, bail};
+use std::path::Path;
+
+/// Check every project under the `args.path` root, loading each one's
+/// config with `load`, and report them together.
+pub async fn check_projects(
+    args: &CheckArgs,
+    config: &GuardianConfig,
+    format: ReportFormat,
+    fail_on: FailOn,
+    load: impl Fn(Option<&Path>) -> Result<GuardianConfig>,
+) -> Result<()> {
+    let root = args.path.as_deref().unwrap_or(Path::new("."));
+    let projects = discover(root, &config.projects);
+    if projects.is_empty() {
+        bail!(
+            "No projects found under {}; list them under [projects]",
+            root.display()
+        );
+    }
+    let configs = projects
+        .iter()
+        .map(|dir| load(Some(dir)))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut checked = Vec::new();
+    for (dir, config) in projects.iter().zip(&configs) {
+        if interrupted() {
+            break;
+        }
+        let opts = args.options(config, format, fail_on)?;
+        let tag = dir.strip_prefix(root).unwrap_or(dir).display().to_string();
+        let mut results = collect(&opts, dir).await?;
+        for result in &mut results {
+            result.project = Some(tag.clone());
+        }
+        checked.push((dir, config, tag, results));
+    }
+
+    let mut all: Vec<_> = checked
+        .iter()
+        .flat_map(|(.., results)| results.iter().cloned())
+        .collect();
+    report(&args.options(config, format, fail_on)?, root, &mut all).await?;
+    let mut verdict = Ok(());
+    for (dir, config, tag, results) in &checked {
+        let opts = args.options(config, format, fail_on)?;
+        if let Err(e) = finish(&opts, dir, results).await {
+            eprintln!("{tag}: checks failed");
+            verdict = verdict.and(Err(e));
+        }
+    }
+    verdict
+}
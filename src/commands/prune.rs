@@ -0,0 +1,284 @@
+//! Prune-models command: delete the models hosts no longer need.
+//!
+//! A model is kept when the config could route a task to it on that host,
+//! when `--keep` names it, or, with `--older-than`, when it was pulled or
+//! updated more recently than that. Everything else on the chosen hosts is
+//! deleted through Ollama's /api/delete once approved, or only listed with
+//! `--dry-run`.
+
+use super::llm;
+use super::output;
+use super::usage::parse_age;
+use crate::config::state::now_secs;
+use crate::config::{GuardianConfig, OllamaHost};
+use crate::ollama::OllamaModel;
+use crate::ollama::select::{load_breaker, resolve_host};
+use anyhow::Result;
+use dialoguer::Confirm;
+use dialoguer::theme::ColorfulTheme;
+use futures::StreamExt;
+use serde::Serialize;
+use std::io::IsTerminal;
+
+/// Options for the prune-models command.
+pub struct PruneOptions<'a> {
+    pub host: Option<&'a str>,
+    /// Prune every enabled host instead of a single one.
+    pub all: bool,
+    /// Models to keep, comma-separated (`--keep`).
+    pub keep: Option<&'a str>,
+    /// Only delete models older than this, e.g. "30d" (`--older-than`).
+    pub older_than: Option<&'a str>,
+    pub dry_run: bool,
+    /// Delete without asking.
+    pub yes: bool,
+    pub json_output: bool,
+}
+
+/// What pruning did, or would do, on one host.
+#[derive(Debug, Serialize)]
+pub struct HostPrune {
+    pub host: String,
+    /// The models deleted, or that would be.
+    pub pruned: Vec<PrunedModel>,
+    /// How many models were kept.
+    pub kept: usize,
+    /// Why the host's models couldn't be listed.
+    pub error: Option<String>,
+}
+
+/// A model pruned from a host.
+#[derive(Debug, Serialize)]
+pub struct PrunedModel {
+    pub name: String,
+    pub size: Option<u64>,
+    pub modified_at: Option<String>,
+    /// Why it couldn't be deleted.
+    pub error: Option<String>,
+}
+
+/// Delete the models no keep-rule covers from one host, or from every
+/// host with `all`.
+pub async fn prune_models(config: &GuardianConfig, opts: PruneOptions<'_>) -> Result<()> {
+    let cutoff = match opts.older_than {
+        Some(age) => Some(now_secs().saturating_sub(parse_age(age)?)),
+        None => None,
+    };
+    let keep: Vec<&str> = opts
+        .keep
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .collect();
+
+    let client = llm::client(config, 60_000)?;
+    let hosts: Vec<&OllamaHost> = if opts.all {
+        config.enabled_hosts()
+    } else {
+        let mut breaker = load_breaker(config);
+        let host = resolve_host(config, &client, &mut breaker, opts.host).await?;
+        breaker.save();
+        vec![host]
+    };
+    if hosts.is_empty() {
+        return output::no_hosts_error(opts.json_output, "No hosts configured");
+    }
+
+    let listings = hosts.iter().map(|host| client.list_models(host));
+    let listed: Vec<_> = futures::stream::iter(listings)
+        .buffered(client.jobs())
+        .collect()
+        .await;
+    let mut plans: Vec<_> = hosts
+        .iter()
+        .zip(listed)
+        .map(|(host, models)| plan(config, host, models, &keep, cutoff))
+        .collect();
+
+    let count: usize = plans.iter().map(|plan| plan.pruned.len()).sum();
+    let dry_run = opts.dry_run || count == 0 || !approved(&opts, count, &plans)?;
+    if !dry_run {
+        for (host, plan) in hosts.iter().zip(&mut plans) {
+            for model in &mut plan.pruned {
+                let deleted = client.delete_model(host, &model.name).await;
+                model.error = deleted.err().map(|e| format!("{e:#}"));
+            }
+        }
+    }
+
+    output::prune_report(&plans, dry_run, opts.json_output)?;
+    let failed = plans.iter().flat_map(|plan| &plan.pruned);
+    if failed.filter(|model| model.error.is_some()).count() > 0 {
+        anyhow::bail!("Some models couldn't be deleted");
+    }
+    if plans.iter().all(|plan| plan.error.is_some()) {
+        anyhow::bail!("No host could list its models");
+    }
+    Ok(())
+}
+
+/// Split `host`'s `models` into the ones to prune and the ones a
+/// keep-rule covers.
+fn plan(
+    config: &GuardianConfig,
+    host: &OllamaHost,
+    models: Result<Vec<OllamaModel>>,
+    keep: &[&str],
+    cutoff: Option<u64>,
+) -> HostPrune {
+    let models = match models {
+        Ok(models) => models,
+        Err(e) => {
+            return HostPrune {
+                host: host.name.clone(),
+                pruned: Vec::new(),
+                kept: 0,
+                error: Some(format!("{e:#}")),
+            };
+        }
+    };
+    let routed = config.models_on(host);
+    let total = models.len();
+    let pruned: Vec<_> = models
+        .into_iter()
+        .filter(|model| {
+            let kept = |names: &[&str]| names.iter().any(|name| keeps(name, &model.name));
+            let recent = cutoff.is_some_and(|cutoff| {
+                let modified = model.modified_at.as_deref().and_then(unix_secs);
+                modified.is_none_or(|modified| modified > cutoff)
+            });
+            !kept(keep) && !kept(&routed) && !recent
+        })
+        .map(|model| PrunedModel {
+            name: model.name,
+            size: model.size,
+            modified_at: model.modified_at,
+            error: None,
+        })
+        .collect();
+    HostPrune {
+        host: host.name.clone(),
+        kept: total - pruned.len(),
+        pruned,
+        error: None,
+    }
+}
+
+/// Whether the keep-rule `name` covers `model`: the same model, with a
+/// missing tag read as `:latest`, or any tag of it when `name` has none.
+fn keeps(name: &str, model: &str) -> bool {
+    let (base, tag) = model.split_once(':').unwrap_or((model, "latest"));
+    match name.split_once(':') {
+        Some((name, wanted)) => name == base && wanted == tag,
+        None => name == base,
+    }
+}
+
+/// Seconds since the Unix epoch at an RFC 3339 `timestamp`, like the
+/// `modified_at` Ollama reports.
+fn unix_secs(timestamp: &str) -> Option<u64> {
+    let (date, time) = timestamp.split_once('T')?;
+    let mut parts = date.splitn(3, '-').map(|part| part.parse::<i64>().ok());
+    let (year, month, day) = (parts.next()??, parts.next()??, parts.next()??);
+    let mut clock = time
+        .get(..8)?
+        .split(':')
+        .map(|part| part.parse::<i64>().ok());
+    let secs = clock.try_fold(0, |total, part| Some(total * 60 + part?))?;
+    let zone = &time[8..][time[8..].find(['Z', '+', '-'])?..];
+    let offset = match zone.split_once(':') {
+        Some((hours, minutes)) => {
+            let sign = if hours.starts_with('-') { -1 } else { 1 };
+            let hours: i64 = hours[1..].parse().ok()?;
+            sign * (hours * 3600 + minutes.parse::<i64>().ok()? * 60)
+        }
+        None => 0,
+    };
+    // Days from 1970-01-01 to the date, in the proleptic Gregorian calendar.
+    let year = if month <= 2 { year - 1 } else { year };
+    let (era, year_of_era) = (year.div_euclid(400), year.rem_euclid(400));
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+    u64::try_from(days * 86_400 + secs - offset).ok()
+}
+
+/// Whether to delete the `count` models `plans` prune: with `--yes`, or
+/// when a person at the terminal agrees.
+fn approved(opts: &PruneOptions<'_>, count: usize, plans: &[HostPrune]) -> Result<bool> {
+    if opts.yes {
+        return Ok(true);
+    }
+    if !std::io::stdin().is_terminal() || !std::io::stderr().is_terminal() {
+        anyhow::bail!("Pass --yes to delete models without asking, or --dry-run to list them");
+    }
+    output::prune_preview(plans);
+    let hosts = plans.iter().filter(|plan| !plan.pruned.is_empty()).count();
+    let delete = Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt(format!("Delete {count} model(s) from {hosts} host(s)?"))
+        .default(false)
+        .interact()?;
+    Ok(delete)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keeps() {
+        assert!(keeps("llama3.2", "llama3.2:latest"));
+        assert!(keeps("llama3.2", "llama3.2:3b"));
+        assert!(keeps("llama3.2:latest", "llama3.2"));
+        assert!(keeps("phi4:14b", "phi4:14b"));
+        assert!(!keeps("phi4:14b", "phi4:latest"));
+        assert!(!keeps("llama3", "llama3.2:latest"));
+    }
+
+    #[test]
+    fn test_unix_secs() {
+        assert_eq!(unix_secs("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(unix_secs("2024-01-01T00:00:00Z"), Some(1_704_067_200));
+        assert_eq!(
+            unix_secs("2024-05-01T10:00:00.123456789-07:00"),
+            Some(1_714_582_800)
+        );
+        assert_eq!(unix_secs("2024-02-29T12:30:00+01:00"), Some(1_709_206_200));
+        assert_eq!(unix_secs("yesterday"), None);
+    }
+
+    #[test]
+    fn test_plan_keeps_routed_listed_and_recent_models() {
+        let toml = r#"
+[ollama]
+default_model = "qwen2.5-coder:7b"
+
+[[ollama.hosts]]
+name = "big72"
+base_url = "http://big72:11434"
+"#;
+        let config: GuardianConfig = toml::from_str(toml).unwrap();
+        let model = |name: &str, modified: &str| OllamaModel {
+            name: name.to_string(),
+            modified_at: Some(modified.to_string()),
+            size: Some(4_000_000_000),
+            digest: None,
+        };
+        let models = vec![
+            model("qwen2.5-coder:7b", "2023-01-01T00:00:00Z"),
+            model("phi4:14b", "2023-01-01T00:00:00Z"),
+            model("llama3.2:3b", "2023-01-01T00:00:00Z"),
+            model("mistral:latest", "2024-06-01T00:00:00Z"),
+        ];
+        let cutoff = unix_secs("2024-01-01T00:00:00Z");
+        let host = &config.ollama.hosts[0];
+        let pruning = plan(&config, host, Ok(models), &["phi4"], cutoff);
+        let pruned: Vec<_> = pruning.pruned.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(pruned, ["llama3.2:3b"]);
+        assert_eq!(pruning.kept, 3);
+
+        let failed = plan(&config, host, Err(anyhow::anyhow!("down")), &[], None);
+        assert_eq!(failed.error.as_deref(), Some("down"));
+    }
+}
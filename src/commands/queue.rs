@@ -0,0 +1,227 @@
+//! The queue `serve` runs `check` and `evaluate` jobs through, for its
+//! schedule (see [`super::schedule`]) and its job API (see [`super::api`]).
+//!
+//! A job runs as the JSON-RPC method of the same name would (see
+//! [`super::rpc`]), so the run lands in the result history and its webhooks
+//! fire as for a `check --json` or `evaluate --json` run, and its JSON
+//! report is kept with the job. Jobs run one at a time, on a thread and
+//! runtime of their own: an `evaluate` future isn't `Send`, reports are
+//! captured through one shared buffer, and a long job should never hold up
+//! the HTTP API. The queue lives in memory, so jobs don't outlast the
+//! daemon.
+//!
+//! Jobs on uploaded projects skip the [`BUILDING_CHECKS`], which would run
+//! the upload's build scripts and proc macros, unless `[serve]
+//! allow_build_scripts` is set.
+
+use super::exit::{TOOL_ERROR, VIOLATIONS, exit_code};
+use super::output::capture;
+use super::rpc;
+use crate::config::{GuardianConfig, ScheduledCommand, state::now_secs};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel};
+
+/// Finished jobs kept for their status and report; older ones are dropped.
+const KEPT_JOBS: usize = 500;
+
+/// Checks that build the project, running code it ships.
+const BUILDING_CHECKS: &[&str] = &["clippy-budget"];
+
+/// What to run, and on which project.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct JobRequest {
+    pub run: ScheduledCommand,
+    pub path: PathBuf,
+    #[serde(default)]
+    pub only: Option<String>,
+    #[serde(default)]
+    pub skip: Option<String>,
+    /// Directory to delete once the job is done: an uploaded project.
+    #[serde(skip)]
+    pub scratch: Option<PathBuf>,
+}
+
+/// Where a job is in the queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(super) enum JobStatus {
+    Queued,
+    Running,
+    Finished,
+}
+
+/// A job and, once it has finished, how it went.
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct Job {
+    pub id: u64,
+    #[serde(flatten)]
+    pub request: JobRequest,
+    pub status: JobStatus,
+    /// The command's exit code: 0 passed, 1 failed the policy, 2 and up
+    /// couldn't run.
+    pub exit_code: Option<u8>,
+    /// Why the command couldn't run.
+    pub error: Option<String>,
+    pub submitted_at: u64,
+    pub finished_at: Option<u64>,
+    /// The command's JSON report.
+    #[serde(skip)]
+    pub report: Option<Value>,
+}
+
+/// The jobs submitted, and the runner they are sent to.
+pub(super) struct JobQueue {
+    jobs: Mutex<BTreeMap<u64, Job>>,
+    runner: UnboundedSender<u64>,
+}
+
+impl JobQueue {
+    /// Start the runner for `config`.
+    pub fn start(config: &GuardianConfig) -> Result<Arc<Self>> {
+        let (runner, ids) = unbounded_channel();
+        let queue = Arc::new(Self {
+            jobs: Mutex::new(BTreeMap::new()),
+            runner,
+        });
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .context("Failed to start the job runner")?;
+        let (config, jobs) = (config.clone(), queue.clone());
+        std::thread::spawn(move || runtime.block_on(run_jobs(config, jobs, ids)));
+        Ok(queue)
+    }
+
+    /// Queue `request`, returning the job's id.
+    pub fn submit(&self, request: JobRequest) -> u64 {
+        let mut jobs = self.jobs.lock().unwrap_or_else(|e| e.into_inner());
+        let id = jobs.last_key_value().map_or(1, |(id, _)| id + 1);
+        jobs.insert(
+            id,
+            Job {
+                id,
+                request,
+                status: JobStatus::Queued,
+                exit_code: None,
+                error: None,
+                submitted_at: now_secs(),
+                finished_at: None,
+                report: None,
+            },
+        );
+        let finished = jobs
+            .values()
+            .filter(|job| job.status == JobStatus::Finished);
+        let dropped: Vec<u64> = finished
+            .map(|job| job.id)
+            .take(jobs.len().saturating_sub(KEPT_JOBS))
+            .collect();
+        for id in dropped {
+            jobs.remove(&id);
+        }
+        let _ = self.runner.send(id);
+        id
+    }
+
+    /// The job `id`, if it is still kept.
+    pub fn get(&self, id: u64) -> Option<Job> {
+        let jobs = self.jobs.lock().unwrap_or_else(|e| e.into_inner());
+        jobs.get(&id).cloned()
+    }
+
+    /// Set the job `id`'s status, returning the job.
+    fn update(&self, id: u64, status: JobStatus, done: impl FnOnce(&mut Job)) -> Option<Job> {
+        let mut jobs = self.jobs.lock().unwrap_or_else(|e| e.into_inner());
+        let job = jobs.get_mut(&id)?;
+        job.status = status;
+        done(job);
+        Some(job.clone())
+    }
+}
+
+/// Run each job sent to the runner in turn, for as long as the daemon runs.
+async fn run_jobs(config: GuardianConfig, queue: Arc<JobQueue>, mut ids: UnboundedReceiver<u64>) {
+    while let Some(id) = ids.recv().await {
+        let Some(job) = queue.update(id, JobStatus::Running, |_| {}) else {
+            continue;
+        };
+        let request = job.request;
+        let method = request.run.name();
+        let skip = job_skip(&config, &request);
+        let params = json!({"path": request.path, "only": request.only, "skip": skip});
+        let (outcome, report) = capture(rpc::call(&config, method, params)).await;
+        let (code, error) = match outcome {
+            Ok(Ok(())) => (0, None),
+            Ok(Err(err)) => match exit_code(&err) {
+                VIOLATIONS => (VIOLATIONS, None),
+                code => (code, Some(format!("{err:#}"))),
+            },
+            Err((_, message)) => (TOOL_ERROR, Some(message)),
+        };
+        let path = request.path.display();
+        match (code, &error) {
+            (0, _) => eprintln!("Job {id}, {method} of {path}: passed"),
+            (_, None) => eprintln!("Job {id}, {method} of {path}: failed"),
+            (_, Some(error)) => eprintln!("Job {id}, {method} of {path}: exit {code}: {error}"),
+        }
+        queue.update(id, JobStatus::Finished, |job| {
+            job.exit_code = Some(code);
+            job.error = error;
+            job.finished_at = Some(now_secs());
+            job.report = serde_json::from_str(report.trim()).ok();
+        });
+        if let Some(scratch) = &request.scratch {
+            let _ = std::fs::remove_dir_all(scratch);
+        }
+    }
+}
+
+/// The checks `request` skips: its own, and the [`BUILDING_CHECKS`] when it
+/// is for an uploaded project and the config doesn't allow build scripts.
+fn job_skip(config: &GuardianConfig, request: &JobRequest) -> Option<String> {
+    if request.scratch.is_none() || config.serve.allow_build_scripts {
+        return request.skip.clone();
+    }
+    let own = request.skip.iter().map(String::as_str);
+    Some(
+        own.chain(BUILDING_CHECKS.iter().copied())
+            .collect::<Vec<_>>()
+            .join(","),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uploads_skip_building_checks() {
+        let mut config = GuardianConfig::default();
+        let mut request = JobRequest {
+            run: ScheduledCommand::Check,
+            path: PathBuf::from("/srv/app"),
+            only: Some("clippy-budget".to_string()),
+            skip: Some("loc-limits".to_string()),
+            scratch: None,
+        };
+        assert_eq!(job_skip(&config, &request).as_deref(), Some("loc-limits"));
+
+        request.scratch = Some(PathBuf::from("/tmp/upload"));
+        let skip = job_skip(&config, &request);
+        assert_eq!(skip.as_deref(), Some("loc-limits,clippy-budget"));
+        request.skip = None;
+        assert_eq!(
+            job_skip(&config, &request).as_deref(),
+            Some("clippy-budget")
+        );
+
+        config.serve.allow_build_scripts = true;
+        assert_eq!(job_skip(&config, &request), None);
+    }
+}
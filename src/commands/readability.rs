@@ -0,0 +1,345 @@
+//! The `llm-readability` check: the model reviews naming and readability.
+//!
+//! Unlike the other checks, this one is a judgement call, so it is opt-in
+//! and held to a tight rubric. The model routed to `readability` reads each
+//! selected file (by default the files changed since the last commit) and
+//! answers with findings tied to line numbers. Answers that don't match the
+//! schema are rejected; findings on lines the file doesn't have, or below
+//! `[check.readability] min_confidence`, are dropped. The rest become
+//! warnings like any other check's.
+
+use super::llm::{Routed, cancellable, check_context_window, routed};
+use crate::checks::interrupt::interrupted;
+use crate::checks::text;
+use crate::checks::vcs::Vcs;
+use crate::checks::{CheckResult, Severity};
+use crate::config::{GuardianConfig, ReadabilitySettings};
+use crate::ollama::ResponseFormat;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+const CHECK_NAME: &str = "llm-readability";
+
+/// What kind of problem a finding is.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Kind {
+    Naming,
+    Readability,
+}
+
+/// One problem the model reports.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Finding {
+    /// 1-based line, as numbered in the prompt.
+    line: usize,
+    kind: Kind,
+    message: String,
+    fix: String,
+    /// How sure the model is, from 0 to 1.
+    confidence: f64,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Reply {
+    findings: Vec<Finding>,
+}
+
+/// Run the `llm-readability` check on `project_dir` if it is `selected`.
+///
+/// Reviews the `[check.readability]` files, or else those in `changed`
+/// (relative to `project_dir`), or else the files changed since the last
+/// commit. When the model can't be reached, a warning is printed and no
+/// results are added, as the check is advisory.
+pub(super) async fn check(
+    config: &GuardianConfig,
+    project_dir: &Path,
+    selected: &[&str],
+    changed: Option<&BTreeSet<PathBuf>>,
+) -> Vec<CheckResult> {
+    if !selected.contains(&CHECK_NAME) || interrupted() {
+        return Vec::new();
+    }
+    let settings = &config.check.readability;
+    let files = match target_files(settings, project_dir, changed) {
+        Ok(files) => files,
+        Err(e) => {
+            tracing::warn!("Couldn't find the files for {CHECK_NAME}: {e:#}");
+            return Vec::new();
+        }
+    };
+    if files.is_empty() {
+        return vec![CheckResult::pass(
+            CHECK_NAME,
+            "No changed Rust files to review",
+        )];
+    }
+    review(config, project_dir, &files)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!("Couldn't run {CHECK_NAME}: {e:#}");
+            Vec::new()
+        })
+}
+
+/// The Rust files to review, relative to `project_dir`, at most
+/// `max_files` of them.
+fn target_files(
+    settings: &ReadabilitySettings,
+    project_dir: &Path,
+    changed: Option<&BTreeSet<PathBuf>>,
+) -> Result<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = if !settings.files.is_empty() {
+        settings.files.clone()
+    } else if let Some(changed) = changed {
+        changed.iter().cloned().collect()
+    } else {
+        let vcs = Vcs::detect(project_dir);
+        vcs.uncommitted_files(project_dir)?.into_iter().collect()
+    };
+    files.retain(|f| f.extension().is_some_and(|e| e == "rs") && project_dir.join(f).is_file());
+    if files.len() > settings.max_files {
+        tracing::warn!(
+            "Only reviewing the first {} of {} files for {CHECK_NAME}",
+            settings.max_files,
+            files.len()
+        );
+        files.truncate(settings.max_files);
+    }
+    Ok(files)
+}
+
+/// Have the model routed to `readability` review each of `files`, skipping
+/// the ones it fails on.
+async fn review(
+    config: &GuardianConfig,
+    project_dir: &Path,
+    files: &[PathBuf],
+) -> Result<Vec<CheckResult>> {
+    let Routed {
+        client,
+        mut breaker,
+        host,
+        model: model_name,
+        system,
+    } = routed(config, "readability", 180_000, None, None).await?;
+
+    let system = system.as_deref();
+    let format = ResponseFormat::Schema(serde_json::json!({
+        "type": "object",
+        "properties": {"findings": {"type": "array", "items": {
+            "type": "object",
+            "properties": {
+                "line": {"type": "integer", "minimum": 1},
+                "kind": {"type": "string", "enum": ["naming", "readability"]},
+                "message": {"type": "string"},
+                "fix": {"type": "string"},
+                "confidence": {"type": "number", "minimum": 0, "maximum": 1},
+            },
+            "required": ["line", "kind", "message", "fix", "confidence"],
+            "additionalProperties": false,
+        }}},
+        "required": ["findings"],
+        "additionalProperties": false,
+    }));
+    let mut results = Vec::new();
+    for (i, file) in files.iter().enumerate() {
+        if interrupted() {
+            break;
+        }
+        let path = project_dir.join(file).display().to_string();
+        let text = text::read(&project_dir.join(file), config.check.max_file_bytes)
+            .with_context(|| format!("Failed to read {path}"))?;
+        let Some(content) = text.into_source() else {
+            continue;
+        };
+        eprintln!(
+            "Reviewing the readability of {} ({}/{}) with {model_name}...",
+            file.display(),
+            i + 1,
+            files.len()
+        );
+        let prompt = build_prompt(&file.display().to_string(), &content);
+        check_context_window(config, &client, host, &model_name, system, &prompt).await?;
+        let retries = config.ollama.json_retries;
+        let response = cancellable(
+            &client,
+            client.generate_json(host, &model_name, &prompt, &format, retries),
+        )
+        .await;
+        breaker.record(&host.name, response.is_ok());
+        let findings = response.and_then(|(value, _)| {
+            let min_confidence = config.check.readability.min_confidence;
+            findings_to_results(value, &path, content.lines().count(), min_confidence)
+                .with_context(|| format!("{model_name} returned malformed findings"))
+        });
+        match findings {
+            Ok(findings) => results.extend(findings),
+            Err(e) => tracing::warn!("Couldn't review {}: {e:#}", file.display()),
+        }
+    }
+    breaker.save();
+    eprintln!();
+    Ok(results)
+}
+
+fn build_prompt(file: &str, content: &str) -> String {
+    let mut prompt = String::from(
+        "You are reviewing one Rust file for naming and readability only. \
+         Correctness, performance, formatting, and anything rustfmt or \
+         clippy would catch are out of scope.\n\n\
+         Report only clear problems of these kinds:\n\
+         - naming: a name that misleads, says nothing about what it holds \
+         or does (such as `data`, `tmp`, or `handle_it`), or breaks Rust \
+         naming conventions\n\
+         - readability: code a reader has to work to follow, such as deep \
+         nesting, a long expression that needs a named intermediate, an \
+         unexplained magic number, or a comment the code contradicts\n\n",
+    );
+    prompt.push_str(&format!("## File: {file}\n\n```rust\n"));
+    for (i, line) in content.lines().enumerate() {
+        prompt.push_str(&format!("{:>4} | {line}\n", i + 1));
+    }
+    prompt.push_str(
+        "```\n\n## Your Task\n\n\
+         Answer with JSON: \"findings\" lists at most 10 objects with \
+         \"line\" (the line number shown), \"kind\" (\"naming\" or \
+         \"readability\"), \"message\" (the problem, in one sentence), \
+         \"fix\" (a concrete change, such as the better name), and \
+         \"confidence\" (from 0 to 1, how sure you are a careful reviewer \
+         would flag it). If nothing is clearly wrong, answer with an empty \
+         list.\n",
+    );
+    prompt
+}
+
+/// The results for the model's `reply` about the file at `path`, which has
+/// `lines` lines: a warning per finding at `min_confidence` or above, or a
+/// pass when there are none.
+///
+/// A reply that doesn't match the schema is an error; a finding on a line
+/// the file doesn't have, or with a confidence outside 0 to 1, is dropped.
+fn findings_to_results(
+    reply: serde_json::Value,
+    path: &str,
+    lines: usize,
+    min_confidence: f64,
+) -> Result<Vec<CheckResult>> {
+    let reply: Reply = serde_json::from_value(reply)?;
+    let mut results = Vec::new();
+    for finding in reply.findings {
+        let message = finding.message.trim();
+        if finding.line == 0 || finding.line > lines || message.is_empty() {
+            tracing::warn!("Dropping a finding for line {} of {path}", finding.line);
+            continue;
+        }
+        if !(0.0..=1.0).contains(&finding.confidence) {
+            tracing::warn!("Dropping a finding with confidence {}", finding.confidence);
+            continue;
+        }
+        if finding.confidence < min_confidence {
+            continue;
+        }
+        let kind = match finding.kind {
+            Kind::Naming => "Naming",
+            Kind::Readability => "Readability",
+        };
+        let mut result = CheckResult::fail(
+            CHECK_NAME,
+            Severity::Warning,
+            &format!("{kind}: {message} (confidence {:.2})", finding.confidence),
+        )
+        .with_file(path)
+        .with_line(finding.line);
+        if !finding.fix.trim().is_empty() {
+            result = result.with_fix(finding.fix.trim());
+        }
+        results.push(result);
+    }
+    if results.is_empty() {
+        let message = format!("No naming or readability findings in {path}");
+        results.push(CheckResult::pass(CHECK_NAME, &message).with_file(path));
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_findings_to_results() {
+        let reply = json!({"findings": [
+            {"line": 3, "kind": "naming", "message": "`d` says nothing.",
+             "fix": "Rename it to `deadline`.", "confidence": 0.9},
+            {"line": 2, "kind": "readability", "message": "Deep nesting.",
+             "fix": "", "confidence": 0.4},
+            {"line": 40, "kind": "naming", "message": "Made up.",
+             "fix": "", "confidence": 0.95},
+        ]});
+        let results = findings_to_results(reply, "app/src/lib.rs", 5, 0.7).unwrap();
+        assert_eq!(results.len(), 1);
+        let result = &results[0];
+        assert!(!result.passed);
+        assert_eq!(result.severity, Severity::Warning);
+        assert_eq!(
+            result.message,
+            "Naming: `d` says nothing. (confidence 0.90)"
+        );
+        assert_eq!(result.file.as_deref(), Some("app/src/lib.rs"));
+        assert_eq!(result.line, Some(3));
+        assert_eq!(result.fix.as_deref(), Some("Rename it to `deadline`."));
+
+        let results = findings_to_results(json!({"findings": []}), "lib.rs", 5, 0.7).unwrap();
+        assert!(results[0].passed);
+    }
+
+    #[test]
+    fn test_findings_parse_strictly() {
+        let extra = json!({"findings": [], "summary": "Looks fine."});
+        assert!(findings_to_results(extra, "lib.rs", 5, 0.7).is_err());
+        let kind = json!({"findings": [
+            {"line": 1, "kind": "style", "message": "m", "fix": "", "confidence": 1.0},
+        ]});
+        assert!(findings_to_results(kind, "lib.rs", 5, 0.7).is_err());
+    }
+
+    #[test]
+    fn test_target_files() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("src")).unwrap();
+        for name in ["src/a.rs", "src/b.rs", "README.md"] {
+            fs::write(dir.path().join(name), "").unwrap();
+        }
+        let changed = ["src/a.rs", "src/b.rs", "src/gone.rs", "README.md"]
+            .map(PathBuf::from)
+            .into();
+        let mut settings = ReadabilitySettings::default();
+        let files = target_files(&settings, dir.path(), Some(&changed)).unwrap();
+        assert_eq!(files, ["src/a.rs", "src/b.rs"].map(PathBuf::from));
+
+        settings.max_files = 1;
+        let files = target_files(&settings, dir.path(), Some(&changed)).unwrap();
+        assert_eq!(files, [PathBuf::from("src/a.rs")]);
+        settings.files = vec![PathBuf::from("src/b.rs")];
+        let files = target_files(&settings, dir.path(), Some(&changed)).unwrap();
+        assert_eq!(files, [PathBuf::from("src/b.rs")]);
+    }
+
+    #[test]
+    fn test_prompt_numbers_lines() {
+        let prompt = build_prompt("src/lib.rs", "fn a() {}\nfn b() {}\n");
+        assert!(
+            prompt.contains(
+                "## File: src/lib.rs\n\n```rust\n   1 | fn a() {}\n   2 | fn b() {}\n```\n"
+            )
+        );
+    }
+}
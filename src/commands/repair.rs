@@ -0,0 +1,240 @@
+//! Repair command: the model fixes violations until the checks pass.
+//!
+//! The fixing happens in a scratch worktree (see [`Worktree`]), one
+//! violation at a time, errors first: the model routed to `repair` writes a
+//! patch for it, the patch is applied, and the checks run again. A patch
+//! that doesn't apply, leaves the violation in place, or adds errors is
+//! undone and that violation isn't tried again. When the checks pass, or
+//! nothing is left to try, or the iteration limit is reached, the combined
+//! diff is shown and applied to the project only once approved.
+
+use super::llm::{Routed, cancellable, check_context_window, routed};
+use super::output::report;
+use super::patches::{build_prompt, extract_diff, git_apply};
+use crate::checks::vcs::Worktree;
+use crate::checks::{
+    CheckResult, Severity, configured_thresholds, run_selected_checks, select_checks,
+};
+use crate::config::GuardianConfig;
+use anyhow::Result;
+use dialoguer::Confirm;
+use dialoguer::theme::ColorfulTheme;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs;
+use std::io::IsTerminal;
+use std::path::Path;
+
+/// Options for the repair command.
+pub struct RepairOptions<'a> {
+    pub path: Option<&'a Path>,
+    /// Only fix violations of these checks, comma-separated (`--only`).
+    pub only: Option<&'a str>,
+    /// Don't fix violations of these checks, comma-separated (`--skip`).
+    pub skip: Option<&'a str>,
+    /// Most patches asked for.
+    pub max_iterations: usize,
+    /// Apply the result without asking.
+    pub yes: bool,
+    pub model: Option<&'a str>,
+    pub host: Option<&'a str>,
+    pub json_output: bool,
+}
+
+/// A violation, by what identifies it across runs of the checks.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+struct Violation {
+    check: String,
+    /// Relative to the project directory.
+    file: String,
+    message: String,
+}
+
+/// What a repair did, for `--json`.
+#[derive(Debug, Serialize)]
+struct Outcome {
+    fixed: Vec<Violation>,
+    /// Violations still failing in the repaired copy.
+    remaining: usize,
+    diff: String,
+    applied: bool,
+}
+
+/// Fix violations in a worktree, then show the diff and apply it once
+/// approved.
+pub async fn repair(config: &GuardianConfig, opts: RepairOptions<'_>) -> Result<()> {
+    let project_dir = opts.path.unwrap_or(Path::new("."));
+    let worktree = Worktree::create(project_dir)?;
+    let (fixed, remaining) = fix_violations(config, &opts, &worktree).await?;
+    let diff = worktree.diff()?;
+    drop(worktree);
+    if !opts.json_output {
+        if diff.is_empty() {
+            report!("No fixes; {remaining} violation(s) remain.");
+            return Ok(());
+        }
+        report!("{}", diff.trim_end());
+        report!("\nFixed {} violation(s); {remaining} remain.", fixed.len());
+    }
+    let applied = !diff.is_empty() && approved(&opts, project_dir)?;
+    if applied {
+        git_apply(project_dir, &diff, false)?;
+    }
+    if opts.json_output {
+        let outcome = Outcome {
+            fixed,
+            remaining,
+            diff,
+            applied,
+        };
+        report!("{}", serde_json::to_string_pretty(&outcome)?);
+    } else if applied {
+        report!("Applied to {}.", project_dir.display());
+    } else {
+        report!("Not applied; run again with --yes to apply it.");
+    }
+    Ok(())
+}
+
+/// Have the model fix the selected checks' violations in `worktree`, one
+/// at a time, returning the ones fixed and how many remain.
+async fn fix_violations(
+    config: &GuardianConfig,
+    opts: &RepairOptions<'_>,
+    worktree: &Worktree,
+) -> Result<(Vec<Violation>, usize)> {
+    let dir = worktree.project_dir();
+    let check = &config.check;
+    let selected = select_checks(opts.only, opts.skip, check.only.as_deref(), &config.checks)?;
+    let thresholds = configured_thresholds(check);
+    let Routed {
+        client,
+        mut breaker,
+        host,
+        model: model_name,
+        system,
+    } = routed(config, "repair", 180_000, opts.host, opts.model).await?;
+    let system = system.as_deref();
+
+    let mut results = run_selected_checks(&dir, &thresholds, &selected);
+    let mut tried = HashSet::new();
+    let mut fixed = Vec::new();
+    for iteration in 1..=opts.max_iterations {
+        let Some((result, violation)) = next_violation(&results, &dir, &tried) else {
+            break;
+        };
+        tried.insert(violation.clone());
+        let target = dir.join(&violation.file);
+        let Ok(before) = fs::read_to_string(&target) else {
+            continue;
+        };
+        let path = worktree.prefix().join(&violation.file);
+        let path = path.to_string_lossy();
+        let prompt = build_prompt(result, &path, &before);
+        check_context_window(config, &client, host, &model_name, system, &prompt).await?;
+        eprintln!(
+            "[{iteration}/{}] Asking {model_name} to fix {} in {}...",
+            opts.max_iterations, violation.check, violation.file
+        );
+        let response = cancellable(&client, client.generate(host, &model_name, &prompt)).await;
+        breaker.record(&host.name, response.is_ok());
+        let response = response?;
+        let Some(diff) = extract_diff(&response.response, &path) else {
+            tracing::warn!("{model_name} didn't answer with a diff for {path}; skipped");
+            continue;
+        };
+        if let Err(e) = git_apply(&dir, &diff, false) {
+            tracing::warn!("{model_name}'s patch for {path} didn't apply: {e:#}");
+            continue;
+        }
+
+        let after = run_selected_checks(&dir, &thresholds, &selected);
+        let still_failing = after.iter().any(|r| {
+            next_violation(std::slice::from_ref(r), &dir, &HashSet::new())
+                .is_some_and(|(_, v)| v == violation)
+        });
+        if still_failing || errors(&after) > errors(&results) {
+            eprintln!("The patch didn't fix it without breaking something else; undone");
+            fs::write(&target, before)?;
+            continue;
+        }
+        fixed.push(violation);
+        results = after;
+    }
+    breaker.save();
+    let remaining = results.iter().filter(|r| !r.passed).count();
+    Ok((fixed, remaining))
+}
+
+/// The first failing result in `results` tied to a file and not yet
+/// `tried`, errors before warnings, with its violation.
+fn next_violation<'r>(
+    results: &'r [CheckResult],
+    project_dir: &Path,
+    tried: &HashSet<Violation>,
+) -> Option<(&'r CheckResult, Violation)> {
+    let candidates = results.iter().filter_map(|result| {
+        let file = Path::new(result.file.as_deref()?);
+        let file = file.strip_prefix(project_dir).unwrap_or(file);
+        let violation = Violation {
+            check: result.check_name.clone(),
+            file: file.display().to_string(),
+            message: result.message.clone(),
+        };
+        (!result.passed && !tried.contains(&violation)).then_some((result, violation))
+    });
+    let (errors, others): (Vec<_>, Vec<_>) =
+        candidates.partition(|(result, _)| result.severity == Severity::Error);
+    errors.into_iter().chain(others).next()
+}
+
+/// How many errors `results` has.
+fn errors(results: &[CheckResult]) -> usize {
+    results
+        .iter()
+        .filter(|r| !r.passed && r.severity == Severity::Error)
+        .count()
+}
+
+/// Whether to apply the repair to `project_dir`: with `--yes`, or when a
+/// person at the terminal agrees.
+fn approved(opts: &RepairOptions<'_>, project_dir: &Path) -> Result<bool> {
+    if opts.yes {
+        return Ok(true);
+    }
+    if !std::io::stdin().is_terminal() || !std::io::stderr().is_terminal() {
+        return Ok(false);
+    }
+    let apply = Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt(format!("Apply this to {}?", project_dir.display()))
+        .default(false)
+        .interact()?;
+    Ok(apply)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_violation_prefers_errors() {
+        let dir = Path::new("/tmp/worktree");
+        let warning = CheckResult::fail("file-loc", Severity::Warning, "Long file")
+            .with_file("/tmp/worktree/src/lib.rs");
+        let error = CheckResult::fail("clippy-disables", Severity::Error, "Lint suppressed")
+            .with_file("/tmp/worktree/src/main.rs");
+        let unplaced = CheckResult::fail("rust-edition", Severity::Error, "Old edition");
+        let results = [warning, unplaced, error];
+
+        let mut tried = HashSet::new();
+        let (result, violation) = next_violation(&results, dir, &tried).unwrap();
+        assert_eq!(result.check_name, "clippy-disables");
+        assert_eq!(violation.file, "src/main.rs");
+
+        tried.insert(violation);
+        let (_, violation) = next_violation(&results, dir, &tried).unwrap();
+        assert_eq!(violation.file, "src/lib.rs");
+        tried.insert(violation);
+        assert!(next_violation(&results, dir, &tried).is_none());
+    }
+}
@@ -0,0 +1,151 @@
+//! RPC command: newline-delimited JSON-RPC 2.0 over stdin and stdout.
+//!
+//! Each line of input is one request, and each gets one line of response.
+//! The methods `check`, `evaluate`, `selectHost`, and `ask` run the command
+//! of the same name with `--json`, and the result is that JSON report with
+//! the command's exit code:
+//!
+//! ```text
+//! -> {"jsonrpc": "2.0", "id": 1, "method": "check", "params": {"path": "."}}
+//! <- {"jsonrpc": "2.0", "id": 1, "result": {"exitCode": 0, "report": {...}}}
+//! ```
+//!
+//! Tool errors (exit code 2) are JSON-RPC errors instead. The process stays
+//! up between requests and monitors host health in the background, like
+//! `serve`, so host selection doesn't wait on pings.
+
+mod params;
+
+use super::exit::{TOOL_ERROR, exit_code};
+use super::output::capture;
+use super::serve::start_monitor;
+use crate::config::GuardianConfig;
+use anyhow::Result;
+use params::{AskParams, CheckParams, EvaluateParams, SelectHostParams};
+use serde_json::{Value, json};
+use std::io::Write;
+use tokio::io::{AsyncBufReadExt, BufReader};
+
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+/// A command failed with a tool error.
+const COMMAND_FAILED: i64 = -32000;
+
+/// Serve JSON-RPC requests from stdin until it closes.
+pub async fn rpc(config: &GuardianConfig) -> Result<()> {
+    start_monitor(config)?;
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = handle(config, &line).await;
+        let mut stdout = std::io::stdout().lock();
+        writeln!(stdout, "{response}")?;
+        stdout.flush()?;
+    }
+    Ok(())
+}
+
+/// Answer one request line.
+async fn handle(config: &GuardianConfig, line: &str) -> Value {
+    let request: Value = match serde_json::from_str(line) {
+        Ok(request) => request,
+        Err(e) => return error(Value::Null, PARSE_ERROR, format!("Parse error: {e}"), None),
+    };
+    let id = request.get("id").cloned().unwrap_or(Value::Null);
+    let method = request["method"].as_str().unwrap_or_default();
+    let params = request.get("params").cloned().unwrap_or_else(|| json!({}));
+
+    let (outcome, report) = capture(call(config, method, params)).await;
+    let result = match outcome {
+        Ok(result) => result,
+        Err((code, message)) => return error(id, code, message, None),
+    };
+    let report = report.trim();
+    let report = serde_json::from_str(report).unwrap_or_else(|_| Value::from(report));
+    let exit_code = result.as_ref().map_or_else(exit_code, |()| 0);
+    match result {
+        Err(err) if exit_code == TOOL_ERROR => {
+            let data = json!({"exitCode": exit_code, "report": report});
+            error(id, COMMAND_FAILED, format!("{err:#}"), Some(data))
+        }
+        _ => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "result": {"exitCode": exit_code, "report": report},
+        }),
+    }
+}
+
+/// Run `method`, or say why it can't run as a JSON-RPC error code and
+/// message.
+pub(super) async fn call(
+    config: &GuardianConfig,
+    method: &str,
+    params: Value,
+) -> Result<Result<()>, (i64, String)> {
+    fn parse<T: serde::de::DeserializeOwned>(params: Value) -> Result<T, (i64, String)> {
+        serde_json::from_value(params).map_err(|e| (INVALID_PARAMS, format!("Invalid params: {e}")))
+    }
+    Ok(match method {
+        "check" => parse::<CheckParams>(params)?.run(config).await,
+        "evaluate" => parse::<EvaluateParams>(params)?.run(config).await,
+        "selectHost" => parse::<SelectHostParams>(params)?.run(config).await,
+        "ask" => parse::<AskParams>(params)?.run(config).await,
+        _ => return Err((METHOD_NOT_FOUND, format!("Unknown method '{method}'"))),
+    })
+}
+
+fn error(id: Value, code: i64, message: String, data: Option<Value>) -> Value {
+    let mut error = json!({"code": code, "message": message});
+    if let Some(data) = data {
+        error["data"] = data;
+    }
+    json!({"jsonrpc": "2.0", "id": id, "error": error})
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    // One test, since captured reports are process-wide.
+    #[tokio::test]
+    async fn test_rpc_requests() {
+        let temp = TempDir::new().unwrap();
+        fs::write(
+            temp.path().join("Cargo.toml"),
+            "[package]\nname = \"demo\"\nedition = \"2021\"\n",
+        )
+        .unwrap();
+        let config = GuardianConfig::default();
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 7,
+            "method": "check",
+            "params": {"path": temp.path(), "only": "rust-edition"},
+        });
+
+        let response = handle(&config, &request.to_string()).await;
+        assert_eq!(response["id"], 7);
+        assert_eq!(response["result"]["exitCode"], 1);
+        assert_eq!(response["result"]["report"]["errors"], 1);
+
+        let response = handle(&config, "{not json").await;
+        assert_eq!(response["error"]["code"], PARSE_ERROR);
+
+        let response = handle(&config, r#"{"id": 1, "method": "fly"}"#).await;
+        assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+
+        let response = handle(&config, r#"{"id": 2, "method": "ask", "params": {}}"#).await;
+        assert_eq!(response["error"]["code"], INVALID_PARAMS);
+
+        let line = r#"{"id": 3, "method": "check", "params": {"only": "nope"}}"#;
+        let response = handle(&config, line).await;
+        assert_eq!(response["error"]["code"], COMMAND_FAILED);
+        assert_eq!(response["error"]["data"]["exitCode"], TOOL_ERROR);
+    }
+}
@@ -0,0 +1,157 @@
+//! Parameters of each RPC method, and running the matching command.
+//!
+//! Names are camelCase, like the method names, and unknown names are an
+//! error so typos don't go unnoticed.
+
+use super::super::cli::{GroupBy, ReportFormat};
+use super::super::{checks, evaluate, exit, host, llm, policy};
+use crate::checks::walk::Scan;
+use crate::config::GuardianConfig;
+use anyhow::Result;
+use serde::Deserialize;
+use std::path::PathBuf;
+
+/// `check`: run the checks on a project.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub(super) struct CheckParams {
+    path: Option<PathBuf>,
+    only: Option<String>,
+    skip: Option<String>,
+    policy: Option<String>,
+    blame: bool,
+    explain_llm: bool,
+}
+
+/// `evaluate`: run the checks and have an LLM review the failures.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub(super) struct EvaluateParams {
+    path: Option<PathBuf>,
+    model: Option<String>,
+    host: Option<String>,
+    only: Option<String>,
+    skip: Option<String>,
+    policy: Option<String>,
+    blame: bool,
+    offline: bool,
+    suggest_patches: bool,
+    triage: bool,
+    force: bool,
+    snippet_lines: Option<usize>,
+    staged: bool,
+}
+
+/// `selectHost`: pick the best host, or rank them all.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub(super) struct SelectHostParams {
+    model: Option<String>,
+    all: bool,
+}
+
+/// `ask`: send a prompt to a model.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub(super) struct AskParams {
+    prompt: String,
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default)]
+    host: Option<String>,
+    #[serde(default)]
+    task: Option<String>,
+    /// Request a JSON response from the model.
+    #[serde(default)]
+    json: bool,
+}
+
+impl CheckParams {
+    pub(super) async fn run(self, config: &GuardianConfig) -> Result<()> {
+        let settings = &config.check;
+        checks::run_checks(checks::CheckOptions {
+            path: self.path.as_deref(),
+            only: self.only.as_deref(),
+            skip: self.skip.as_deref(),
+            config_only: settings.only.as_deref(),
+            toggles: &config.checks,
+            max_loc: settings.max_loc,
+            warn_loc: settings.warn_loc,
+            max_functions: settings.max_functions,
+            max_modules: settings.max_modules,
+            edition: &settings.edition,
+            architecture: &settings.architecture,
+            clippy: &settings.clippy,
+            scan: Scan::from(settings),
+            llm: config,
+            format: ReportFormat::Json,
+            group_by: GroupBy::Check,
+            template: None,
+            policy: fail_policy(config, self.policy.as_deref())?,
+            notify: &config.notify,
+            storage: &config.storage,
+            blame: self.blame,
+            explain_llm: self.explain_llm.then_some(config),
+            save: None,
+        })
+        .await
+    }
+}
+
+impl EvaluateParams {
+    pub(super) async fn run(self, config: &GuardianConfig) -> Result<()> {
+        let opts = evaluate::EvaluateOptions {
+            path: self.path.as_deref(),
+            model: self.model.as_deref(),
+            host: self.host.as_deref(),
+            only: self.only.as_deref(),
+            skip: self.skip.as_deref(),
+            html_report: None,
+            json_output: true,
+            policy: fail_policy(config, self.policy.as_deref())?,
+            blame: self.blame,
+            offline: self.offline,
+            suggest_patches: self.suggest_patches,
+            triage: self.triage,
+            interactive: false,
+            force: self.force,
+            snippet_lines: self.snippet_lines,
+            staged: self.staged,
+            from: None,
+        };
+        evaluate::evaluate(config, opts).await
+    }
+}
+
+impl SelectHostParams {
+    pub(super) async fn run(self, config: &GuardianConfig) -> Result<()> {
+        host::select_host(config, self.model.as_deref(), self.all, true).await
+    }
+}
+
+impl AskParams {
+    pub(super) async fn run(self, config: &GuardianConfig) -> Result<()> {
+        let opts = llm::AskOptions {
+            prompt: &self.prompt,
+            model: self.model.as_deref(),
+            host: self.host.as_deref(),
+            task: self.task.as_deref(),
+            json_format: self.json,
+            schema: None,
+            race: false,
+            json_output: true,
+        };
+        llm::ask(config, opts).await
+    }
+}
+
+/// Fail on errors, on too many warnings, or by `policy` or the configured
+/// policy expression, as `check` does by default.
+fn fail_policy(config: &GuardianConfig, policy: Option<&str>) -> Result<exit::FailPolicy> {
+    let policy = policy.or(config.check.policy.as_deref());
+    Ok(exit::FailPolicy {
+        max_warnings: config.check.max_warnings,
+        expression: policy.map(policy::Policy::parse).transpose()?,
+        ..Default::default()
+    })
+}
@@ -0,0 +1,91 @@
+//! Project rules: a repository's own instructions for the model.
+//!
+//! A `GUARDIAN.md`, or `.guardian/rules.md`, at the project root goes into
+//! the evaluate, review, and govern prompts as rules the code must follow,
+//! so each repository can tune guardian's judgment without touching its
+//! prompts. Long files are cut to a token budget.
+
+use crate::ollama::estimate_tokens;
+use anyhow::{Context, Result};
+use std::io::ErrorKind;
+use std::path::Path;
+
+/// Where the rules are looked for, first match wins.
+const RULES_FILES: [&str; 2] = ["GUARDIAN.md", ".guardian/rules.md"];
+
+/// Most tokens of rules sent to the model.
+const MAX_RULES_TOKENS: u64 = 2_000;
+
+/// The project rules in `project_dir` as a prompt section, if it has any.
+pub(super) fn project_rules(project_dir: &Path) -> Result<Option<String>> {
+    for name in RULES_FILES {
+        let path = project_dir.join(name);
+        let rules = match std::fs::read_to_string(&path) {
+            Ok(rules) => rules,
+            Err(e) if e.kind() == ErrorKind::NotFound => continue,
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to read {}", path.display()));
+            }
+        };
+        let rules = rules.trim();
+        if rules.is_empty() {
+            continue;
+        }
+        tracing::debug!(path = %path.display(), tokens = estimate_tokens(rules), "Project rules");
+        return Ok(Some(section(rules)));
+    }
+    Ok(None)
+}
+
+/// `rules` under a heading, cut to [`MAX_RULES_TOKENS`].
+fn section(rules: &str) -> String {
+    let mut section = String::from(
+        "## Project Rules\n\n\
+         Project-specific rules the code must follow, from the project itself:\n\n",
+    );
+    if estimate_tokens(rules) <= MAX_RULES_TOKENS {
+        section.push_str(rules);
+    } else {
+        let chars = (MAX_RULES_TOKENS * 4) as usize;
+        let end = rules
+            .char_indices()
+            .nth(chars)
+            .map_or(rules.len(), |(i, _)| i);
+        section.push_str(&rules[..end]);
+        section.push_str("\n[... rules truncated ...]");
+    }
+    section.push_str("\n\n");
+    section
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_project_rules() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(project_rules(dir.path()).unwrap(), None);
+
+        std::fs::create_dir(dir.path().join(".guardian")).unwrap();
+        std::fs::write(dir.path().join(".guardian/rules.md"), "- No unwrap\n").unwrap();
+        let rules = project_rules(dir.path()).unwrap().unwrap();
+        assert!(rules.starts_with("## Project Rules\n"));
+        assert!(rules.ends_with("\n\n- No unwrap\n\n"));
+
+        std::fs::write(dir.path().join("GUARDIAN.md"), "  \n").unwrap();
+        assert_eq!(project_rules(dir.path()).unwrap(), Some(rules));
+        std::fs::write(dir.path().join("GUARDIAN.md"), "- Keep main.rs thin").unwrap();
+        let rules = project_rules(dir.path()).unwrap().unwrap();
+        assert!(rules.contains("- Keep main.rs thin"));
+        assert!(!rules.contains("No unwrap"));
+    }
+
+    #[test]
+    fn test_rules_truncated_to_budget() {
+        let section = section(&"é".repeat(10_000));
+        assert!(section.ends_with("é\n[... rules truncated ...]\n\n"));
+        let kept = section.matches('é').count() as u64;
+        assert_eq!(kept, MAX_RULES_TOKENS * 4);
+    }
+}
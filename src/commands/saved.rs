@@ -0,0 +1,77 @@
+//! Check results saved by `check --save`, for `evaluate --from`.
+//!
+//! Running the checks is quick and evaluating them is GPU-bound, so CI can
+//! run the two as separate jobs and retry the evaluation alone. The file is
+//! a `check --format json` report with the project added, and such a report
+//! loads too.
+
+use crate::checks::CheckResult;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// A run's results and where they came from.
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) struct SavedResults {
+    /// Project the checks ran on.
+    #[serde(default)]
+    pub project: Option<PathBuf>,
+    pub results: Vec<CheckResult>,
+}
+
+/// Save `results` of checking `project_dir` to `path`.
+pub(super) fn save(path: &Path, project_dir: &Path, results: &[CheckResult]) -> Result<()> {
+    let saved = serde_json::json!({"project": project_dir, "results": results});
+    std::fs::write(path, serde_json::to_string_pretty(&saved)? + "\n")
+        .with_context(|| format!("Failed to save results to {}", path.display()))
+}
+
+/// Load results saved with [`save`], or from a JSON report grouped by check.
+pub(super) fn load(path: &Path) -> Result<SavedResults> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&text).with_context(|| {
+        format!(
+            "{} doesn't hold check results; write it with check --save",
+            path.display()
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checks::Severity;
+    use crate::commands::output::result_json;
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("results.json");
+        let results = [
+            CheckResult::pass("rust-edition", "Edition 2024"),
+            CheckResult::fail("loc-limits", Severity::Error, "main.rs: 612 lines")
+                .with_file("src/main.rs")
+                .with_line(1)
+                .with_fix("Split main.rs"),
+        ];
+        save(&path, Path::new("app"), &results).unwrap();
+        let saved = load(&path).unwrap();
+        assert_eq!(saved.project.as_deref(), Some(Path::new("app")));
+        assert_eq!(saved.results.len(), 2);
+        let loaded = &saved.results[1];
+        assert_eq!(loaded.check_name, "loc-limits");
+        assert_eq!(loaded.severity, Severity::Error);
+        assert_eq!(loaded.fix.as_deref(), Some("Split main.rs"));
+
+        // A JSON report reads back the same, without the project.
+        let report = serde_json::json!({"total": 2, "results": results.iter().map(result_json).collect::<Vec<_>>()});
+        std::fs::write(&path, report.to_string()).unwrap();
+        let saved = load(&path).unwrap();
+        assert_eq!(saved.project, None);
+        assert_eq!(saved.results[1].line, Some(1));
+
+        std::fs::write(&path, r#"{"files": []}"#).unwrap();
+        assert!(load(&path).is_err());
+    }
+}
@@ -0,0 +1,53 @@
+//! Scheduled jobs: `serve` running `check` and `evaluate` on projects on
+//! the cron schedules in `[[schedule]]`.
+//!
+//! Each run goes through the job queue (see [`super::queue`]), like a job
+//! submitted to the API. A run that comes due while another job runs starts
+//! right after it, and runs missed while the daemon was down are skipped.
+
+use super::queue::{JobQueue, JobRequest};
+use crate::config::{GuardianConfig, ScheduledJob, state::now_secs};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Queue the configured jobs as they come due, in the background.
+pub(super) fn start_schedule(config: &GuardianConfig, queue: Arc<JobQueue>) {
+    if config.schedule.is_empty() {
+        return;
+    }
+    if !config.storage.enabled {
+        tracing::warn!("[storage] is disabled, so scheduled runs aren't recorded");
+    }
+    tokio::spawn(queue_jobs(config.schedule.clone(), queue));
+}
+
+/// Wait for each job's next run time and queue it, forever.
+async fn queue_jobs(schedule: Vec<ScheduledJob>, queue: Arc<JobQueue>) {
+    let now = now_secs();
+    let mut next: Vec<(u64, &ScheduledJob)> = Vec::new();
+    for job in &schedule {
+        match job.cron.next_after(now) {
+            Some(at) => next.push((at, job)),
+            None => tracing::warn!(
+                cron = job.cron.expression(),
+                "Scheduled job never runs; skipping it"
+            ),
+        }
+    }
+
+    while let Some(&(at, job)) = next.iter().min_by_key(|(at, _)| *at) {
+        let wait = at.saturating_sub(now_secs());
+        tokio::time::sleep(Duration::from_secs(wait)).await;
+        queue.submit(JobRequest {
+            run: job.run,
+            path: job.path.clone(),
+            only: job.only.clone(),
+            skip: job.skip.clone(),
+            scratch: None,
+        });
+        next.retain(|(_, other)| !std::ptr::eq(*other, job));
+        if let Some(at) = job.cron.next_after(now_secs().max(at)) {
+            next.push((at, job));
+        }
+    }
+}
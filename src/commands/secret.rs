@@ -0,0 +1,48 @@
+//! Secret commands: store and remove host credentials in the OS keyring.
+
+use super::output::report;
+use crate::config;
+use anyhow::{Context, Result};
+use std::io::{IsTerminal, Read};
+
+/// Store a secret read from a hidden prompt, or from stdin when piped.
+pub fn set_secret(name: &str, json_output: bool) -> Result<()> {
+    let value = if std::io::stdin().is_terminal() {
+        rpassword::prompt_password(format!("Secret for {name}: "))
+            .context("Failed to read secret")?
+    } else {
+        let mut value = String::new();
+        std::io::stdin()
+            .read_to_string(&mut value)
+            .context("Failed to read secret from stdin")?;
+        value.trim_end_matches(['\r', '\n']).to_string()
+    };
+    if value.is_empty() {
+        anyhow::bail!("Secret for {name} is empty; nothing stored");
+    }
+
+    config::set_secret(name, &value)?;
+    if json_output {
+        report!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({ "secret": name, "stored": true }))?
+        );
+    } else {
+        report!("Stored secret '{name}' in the OS keyring");
+    }
+    Ok(())
+}
+
+/// Remove a stored secret.
+pub fn delete_secret(name: &str, json_output: bool) -> Result<()> {
+    config::delete_secret(name)?;
+    if json_output {
+        report!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({ "secret": name, "deleted": true }))?
+        );
+    } else {
+        report!("Deleted secret '{name}' from the OS keyring");
+    }
+    Ok(())
+}
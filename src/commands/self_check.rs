@@ -0,0 +1,184 @@
+//! Self-check command: guardian's checks run on guardian itself.
+//!
+//! A smoke test of an installation, and a demo of every check. Guardian's
+//! own source, when it is still where the binary was built (or is given
+//! with `--source`), gets every check but the opt-in `llm-readability`, at
+//! the built-in thresholds, with each crate of the workspace checked on its
+//! own. The environment guardian runs in gets checks of its own: the config
+//! files loaded, each enabled host, a writable cache directory, and `git`.
+//! Warnings count, so the run fails when any check fires.
+
+use super::cli::FailOn;
+use super::exit::FailPolicy;
+use super::llm;
+use super::output;
+use crate::checks::{
+    CHECK_NAMES, CheckResult, OPT_IN_CHECKS, Severity, configured_thresholds, run_selected_checks,
+};
+use crate::config::{CheckSettings, GuardianConfig, state};
+use anyhow::{Result, bail};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Options for the self-check command.
+pub struct SelfCheckOptions<'a> {
+    /// Guardian's source, instead of where the binary was built.
+    pub source: Option<&'a Path>,
+    pub json_output: bool,
+}
+
+/// Check guardian's source and environment, and report which checks fired
+/// and why.
+pub async fn self_check(config: &GuardianConfig, opts: SelfCheckOptions<'_>) -> Result<()> {
+    let source = opts.source.unwrap_or(Path::new(env!("CARGO_MANIFEST_DIR")));
+    let mut sections = Vec::new();
+    if source.join("Cargo.toml").is_file() {
+        let thresholds = configured_thresholds(&CheckSettings::default());
+        let selected: Vec<&str> = CHECK_NAMES
+            .iter()
+            .copied()
+            .filter(|name| !OPT_IN_CHECKS.contains(name))
+            .collect();
+        for dir in crate_dirs(source) {
+            eprintln!("Checking {}...", dir.display());
+            let results = run_selected_checks(&dir, &thresholds, &selected);
+            sections.push((format!("Source: {}", dir.display()), results));
+        }
+    } else if opts.source.is_some() {
+        bail!("No Cargo.toml in {}", source.display());
+    } else {
+        eprintln!(
+            "Guardian's source isn't at {}; checking the environment only",
+            source.display()
+        );
+    }
+    eprintln!("Checking the environment...\n");
+    sections.push(("Environment".to_string(), environment(config).await?));
+
+    output::self_check_report(&sections, opts.json_output)?;
+    let results: Vec<_> = sections.into_iter().flat_map(|(_, r)| r).collect();
+    let policy = FailPolicy {
+        fail_on: FailOn::Warning,
+        ..FailPolicy::default()
+    };
+    policy.enforce(&results)
+}
+
+/// `source` and each crate in its `crates` directory.
+fn crate_dirs(source: &Path) -> Vec<PathBuf> {
+    let mut members: Vec<PathBuf> = fs::read_dir(source.join("crates"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|dir| dir.join("Cargo.toml").is_file())
+        .collect();
+    members.sort();
+    members.insert(0, source.to_path_buf());
+    members
+}
+
+/// Checks of what guardian runs with: its config, hosts, cache directory,
+/// and `git`.
+async fn environment(config: &GuardianConfig) -> Result<Vec<CheckResult>> {
+    let mut results = vec![if config.files.is_empty() {
+        CheckResult::fail(
+            "config",
+            Severity::Warning,
+            "No config file; using the built-in defaults",
+        )
+        .with_fix("Run `guardian-cli setup` to write one")
+    } else {
+        CheckResult::pass("config", &format!("Loaded {}", config.files.join(", ")))
+    }];
+
+    let hosts = config.enabled_hosts();
+    if hosts.is_empty() {
+        results.push(
+            CheckResult::fail("hosts", Severity::Warning, "No enabled hosts")
+                .with_fix("Add [[ollama.hosts]] to the config for the commands that use a model"),
+        );
+    }
+    let client = llm::client(config, config.default_timeout_ms())?;
+    for ping in client.ping_hosts(&hosts).await {
+        let name = &ping.host.name;
+        results.push(match (ping.reachable, ping.latency_ms) {
+            (true, ms) => {
+                CheckResult::pass("hosts", &format!("{name} is up ({}ms)", ms.unwrap_or(0)))
+            }
+            (false, _) => {
+                let error = ping.error.as_deref().unwrap_or("unknown error");
+                CheckResult::fail(
+                    "hosts",
+                    Severity::Warning,
+                    &format!("{name} is down: {error}"),
+                )
+                .with_fix(&format!(
+                    "Start Ollama at {}, or disable the host",
+                    ping.host.base_url
+                ))
+            }
+        });
+    }
+
+    results.push(cache_dir());
+    results.push(match Command::new("git").arg("--version").output() {
+        Ok(out) if out.status.success() => {
+            CheckResult::pass("git", String::from_utf8_lossy(&out.stdout).trim())
+        }
+        _ => CheckResult::fail("git", Severity::Warning, "git isn't on the PATH")
+            .with_fix("Install git; --staged, pre-push, and repair need it"),
+    });
+    Ok(results)
+}
+
+/// Whether guardian can keep state (history, usage, caches) between runs.
+fn cache_dir() -> CheckResult {
+    let Some(probe) = state::path("self-check") else {
+        return CheckResult::fail(
+            "cache-dir",
+            Severity::Warning,
+            "No cache directory on this platform",
+        );
+    };
+    let dir = probe.parent().unwrap_or(Path::new("."));
+    let written = fs::create_dir_all(dir)
+        .and_then(|()| fs::write(&probe, ""))
+        .and_then(|()| fs::remove_file(&probe));
+    match written {
+        Ok(()) => CheckResult::pass("cache-dir", &format!("{} is writable", dir.display())),
+        Err(e) => CheckResult::fail(
+            "cache-dir",
+            Severity::Warning,
+            &format!("Can't write to {}: {e}", dir.display()),
+        )
+        .with_fix("History, usage, and caches won't be kept until it is writable"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crate_dirs() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        for member in ["crates/b", "crates/a"] {
+            fs::create_dir_all(dir.join(member)).unwrap();
+            fs::write(dir.join(member).join("Cargo.toml"), "").unwrap();
+        }
+        fs::create_dir_all(dir.join("crates/notes")).unwrap();
+
+        let dirs = crate_dirs(dir);
+        assert_eq!(
+            dirs,
+            [
+                dir.to_path_buf(),
+                dir.join("crates/a"),
+                dir.join("crates/b")
+            ]
+        );
+    }
+}
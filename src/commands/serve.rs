@@ -0,0 +1,135 @@
+//! Daemon mode: continuous host health monitoring behind an HTTP API, the
+//! scheduled jobs (see [`super::schedule`]), the job API (see
+//! [`super::api`]), and the web dashboard (see [`super::dashboard`]).
+
+use super::queue::JobQueue;
+use super::{api, dashboard, metrics, notify, schedule};
+use crate::config::{GuardianConfig, NotifyEvent, OllamaHost, state};
+use crate::ollama::select::load_breaker;
+use crate::ollama::{HealthSnapshot, HealthTracker, OllamaClient};
+use anyhow::{Context, Result};
+use axum::extract::State;
+use axum::http::header;
+use axum::routing::get;
+use axum::{Json, Router};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+
+/// State shared between the monitor task and HTTP handlers.
+pub(super) struct AppState {
+    config: GuardianConfig,
+    health: RwLock<HealthTracker>,
+}
+
+pub(super) type SharedState = Arc<AppState>;
+
+/// Run the guardian daemon until interrupted.
+pub async fn serve(config: &GuardianConfig, bind: Option<&str>) -> Result<()> {
+    let bind = bind.unwrap_or(&config.serve.bind).to_string();
+    let state = start_monitor(config)?;
+    let queue = JobQueue::start(config)?;
+    schedule::start_schedule(config, queue.clone());
+    let token = api::api_token(&config.serve)?;
+    let app = Router::new()
+        .route("/api/health", get(health))
+        .route("/api/select-host", get(select_host))
+        .route("/metrics", get(prometheus_metrics))
+        .with_state(state)
+        .merge(api::job_routes(&config.serve, token.clone(), queue))
+        .merge(dashboard::dashboard_routes(&config.storage, token));
+
+    let listener = tokio::net::TcpListener::bind(&bind)
+        .await
+        .with_context(|| format!("Failed to bind {bind}"))?;
+    println!("guardian serving on http://{bind}");
+    axum::serve(listener, app)
+        .await
+        .context("HTTP server failed")
+}
+
+/// Start monitoring host health in the background, publishing it for the
+/// host selection of this and other guardian processes.
+pub(super) fn start_monitor(config: &GuardianConfig) -> Result<SharedState> {
+    let client = OllamaClient::new(config.default_timeout_ms())?.with_jobs(config.ollama.jobs);
+    let state = Arc::new(AppState {
+        config: config.clone(),
+        health: RwLock::new(HealthTracker::new(&config.serve)),
+    });
+    tokio::spawn(monitor_hosts(client, state.clone()));
+    Ok(state)
+}
+
+/// Ping every enabled host on the configured interval and publish health.
+async fn monitor_hosts(client: OllamaClient, state: SharedState) {
+    let config = &state.config;
+    let mut interval = tokio::time::interval(Duration::from_secs(
+        config.serve.health_interval_secs.max(1),
+    ));
+    let snapshot_path = state::path("health.json");
+
+    loop {
+        interval.tick().await;
+        let results = client.ping_hosts(&config.enabled_hosts()).await;
+
+        let mut breaker = load_breaker(config);
+        for result in &results {
+            breaker.record(&result.host.name, result.reachable);
+        }
+        breaker.save();
+
+        let (before, snapshot) = {
+            let mut tracker = state.health.write().await;
+            let before = tracker.snapshot();
+            tracker.update(&results);
+            (before, tracker.snapshot())
+        };
+        for result in &results {
+            let host = &result.host.name;
+            if before.is_healthy(host) == Some(true) && snapshot.is_healthy(host) == Some(false) {
+                let error = result.error.as_deref().unwrap_or("not responding");
+                let text = format!("Ollama host {host} is down: {error}");
+                let fields = serde_json::json!({"host": host, "base_url": result.host.base_url, "error": error});
+                notify::send(&config.notify, NotifyEvent::HostDown, text, fields).await;
+            }
+        }
+        if let Some(path) = &snapshot_path {
+            state::write(path, &snapshot);
+        }
+    }
+}
+
+/// `GET /api/health` - rolling health of all hosts.
+async fn health(State(state): State<SharedState>) -> Json<HealthSnapshot> {
+    Json(state.health.read().await.snapshot())
+}
+
+/// `GET /api/select-host` - first healthy host, primaries before fallbacks.
+async fn select_host(State(state): State<SharedState>) -> Json<serde_json::Value> {
+    let snapshot = state.health.read().await.snapshot();
+    let selected: Option<&OllamaHost> = state
+        .config
+        .enabled_hosts()
+        .into_iter()
+        .find(|h| snapshot.is_healthy(&h.name) == Some(true));
+
+    Json(match selected {
+        Some(host) => serde_json::json!({
+            "host": host.name,
+            "base_url": host.base_url,
+            "fallback": host.fallback,
+        }),
+        None => serde_json::json!({"error": "No suitable hosts available"}),
+    })
+}
+
+/// `GET /metrics` - Prometheus metrics.
+async fn prometheus_metrics(
+    State(state): State<SharedState>,
+) -> ([(header::HeaderName, &'static str); 1], String) {
+    let snapshot = state.health.read().await.snapshot();
+    (
+        [(header::CONTENT_TYPE, metrics::CONTENT_TYPE)],
+        metrics::render(&snapshot, &state.config.storage),
+    )
+}
@@ -0,0 +1,342 @@
+//! Setup wizard: find Ollama servers, ask a few questions, write the config.
+
+use super::output::report;
+use crate::config::{CheckSettings, OllamaHost};
+use crate::ollama::OllamaClient;
+use anyhow::{Context, Result, bail};
+use dialoguer::theme::ColorfulTheme;
+use dialoguer::{Confirm, Input, MultiSelect, Select};
+use std::fs;
+use std::io::IsTerminal;
+use std::net::{IpAddr, UdpSocket};
+use std::path::{Path, PathBuf};
+use toml_edit::{ArrayOfTables, DocumentMut, Item, Table, value};
+
+/// Ports tried on this machine: Ollama's default and the next two, where
+/// a second or third instance usually goes.
+const LOCAL_PORTS: &[u16] = &[11434, 11435, 11436];
+
+/// How long a candidate gets to answer before it's skipped.
+const PROBE_TIMEOUT_MS: u64 = 700;
+
+/// Threshold presets offered before "Custom": name, max_loc, warn_loc,
+/// max_functions.
+const PRESETS: &[(&str, usize, usize, usize)] = &[
+    ("Standard", 500, 350, 7),
+    ("Strict", 300, 200, 5),
+    ("Relaxed", 800, 600, 12),
+];
+
+/// An Ollama server that answered, with the models it has.
+#[derive(Debug, Clone, PartialEq)]
+struct Found {
+    name: String,
+    base_url: String,
+    models: Vec<String>,
+}
+
+/// Probe for Ollama servers, let the user pick hosts, a default model, and
+/// check thresholds, then write the config to `path` (default: the user
+/// config), keeping any existing file as `FILE.bak`.
+///
+/// With `scan`, every address on the local /24 network is tried on the
+/// default port too.
+pub async fn setup(path: Option<&Path>, scan: bool, json_output: bool) -> Result<()> {
+    if !std::io::stdin().is_terminal() || !std::io::stderr().is_terminal() {
+        bail!("setup asks questions, so it needs a terminal");
+    }
+    let path = match path {
+        Some(p) => p.to_path_buf(),
+        None => crate::config::default_config_path()
+            .context("Could not determine default config path")?,
+    };
+    if path.extension().is_some_and(|ext| ext != "toml") {
+        bail!(
+            "setup writes TOML; pass --config with a .toml path instead of {}",
+            path.display()
+        );
+    }
+    let theme = ColorfulTheme::default();
+
+    eprintln!("Looking for Ollama servers...");
+    let found = discover(scan).await?;
+    let hosts: Vec<Found> = if found.is_empty() {
+        eprintln!("No Ollama servers answered; the config will use http://localhost:11434.");
+        vec![Found {
+            name: "local".to_string(),
+            base_url: "http://localhost:11434".to_string(),
+            models: Vec::new(),
+        }]
+    } else {
+        let labels: Vec<String> = found
+            .iter()
+            .map(|f| format!("{} ({} models)", f.base_url, f.models.len()))
+            .collect();
+        let picked = MultiSelect::with_theme(&theme)
+            .with_prompt("Hosts to use (space toggles, enter confirms)")
+            .items(&labels)
+            .defaults(&vec![true; found.len()])
+            .interact()?;
+        picked.into_iter().map(|i| found[i].clone()).collect()
+    };
+
+    let mut models: Vec<&str> = hosts
+        .iter()
+        .flat_map(|h| h.models.iter().map(String::as_str))
+        .collect();
+    models.sort_unstable();
+    models.dedup();
+    let model = if models.is_empty() {
+        None
+    } else {
+        let choice = Select::with_theme(&theme)
+            .with_prompt("Default model")
+            .items(&models)
+            .default(0)
+            .interact()?;
+        Some(models[choice])
+    };
+    let check = choose_thresholds(&theme)?;
+
+    let contents = render_config(&hosts, model, &check);
+    if path.exists() {
+        let overwrite = Confirm::with_theme(&theme)
+            .with_prompt(format!(
+                "Replace {}? The current file is kept as .bak",
+                path.display()
+            ))
+            .default(false)
+            .interact()?;
+        if !overwrite {
+            bail!("Left {} unchanged", path.display());
+        }
+    }
+    let backup = write_config(&path, &contents)?;
+
+    if json_output {
+        report!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "path": path.display().to_string(),
+                "backup": backup.map(|b| b.display().to_string()),
+                "hosts": hosts.iter().map(|h| &h.name).collect::<Vec<_>>(),
+                "default_model": model,
+            }))?
+        );
+    } else {
+        report!("Wrote {}", path.display());
+        report!("Run `guardian-cli ping-hosts` to check the hosts.");
+    }
+    Ok(())
+}
+
+/// Probe this machine's common ports (and the local network with `scan`)
+/// and return every server that lists its models.
+async fn discover(scan: bool) -> Result<Vec<Found>> {
+    let mut candidates: Vec<(String, String)> = LOCAL_PORTS
+        .iter()
+        .map(|&port| match port {
+            11434 => ("local".to_string(), format!("http://localhost:{port}")),
+            _ => (format!("local-{port}"), format!("http://localhost:{port}")),
+        })
+        .collect();
+    if scan {
+        candidates.extend(lan_candidates());
+    }
+
+    let client = OllamaClient::new(PROBE_TIMEOUT_MS)?;
+    let probes = candidates.into_iter().map(|(name, base_url)| {
+        let client = client.clone();
+        async move {
+            let host = OllamaHost {
+                name: name.clone(),
+                base_url: base_url.clone(),
+                enabled: true,
+                fallback: false,
+                description: None,
+                weight: 1.0,
+                max_concurrent: None,
+                auth: None,
+                group: None,
+                tags: Vec::new(),
+                default_model: None,
+                default_timeout_ms: None,
+                models: Default::default(),
+            };
+            let models = client.list_models(&host).await.ok()?;
+            Some(Found {
+                name,
+                base_url,
+                models: models.into_iter().map(|m| m.name).collect(),
+            })
+        }
+    });
+    Ok(futures::future::join_all(probes)
+        .await
+        .into_iter()
+        .flatten()
+        .collect())
+}
+
+/// Every other address on this machine's /24 network, on the default port.
+///
+/// Only private IPv4 networks are scanned. Connecting a UDP socket sends
+/// nothing; it just reveals which local address the default route uses.
+fn lan_candidates() -> Vec<(String, String)> {
+    let local = UdpSocket::bind("0.0.0.0:0")
+        .and_then(|socket| {
+            socket.connect("192.0.2.1:80")?;
+            socket.local_addr()
+        })
+        .map(|addr| addr.ip());
+    let Ok(IpAddr::V4(local)) = local else {
+        return Vec::new();
+    };
+    if !local.is_private() {
+        return Vec::new();
+    }
+    let [a, b, c, own] = local.octets();
+    (1..=254u8)
+        .filter(|&d| d != own)
+        .map(|d| {
+            (
+                format!("{a}-{b}-{c}-{d}"),
+                format!("http://{a}.{b}.{c}.{d}:11434"),
+            )
+        })
+        .collect()
+}
+
+/// Pick a threshold preset, or enter each threshold.
+fn choose_thresholds(theme: &ColorfulTheme) -> Result<CheckSettings> {
+    let mut labels: Vec<String> = PRESETS
+        .iter()
+        .map(|(name, max_loc, warn_loc, max_functions)| {
+            format!(
+                "{name}: files up to {max_loc} lines (warn at {warn_loc}), \
+                 {max_functions} functions per file"
+            )
+        })
+        .collect();
+    labels.push("Custom".to_string());
+    let choice = Select::with_theme(theme)
+        .with_prompt("Check thresholds")
+        .items(&labels)
+        .default(0)
+        .interact()?;
+
+    let (max_loc, warn_loc, max_functions) = match PRESETS.get(choice) {
+        Some(&(_, max_loc, warn_loc, max_functions)) => (max_loc, warn_loc, max_functions),
+        None => {
+            let max_loc: usize = Input::with_theme(theme)
+                .with_prompt("Maximum lines per file")
+                .default(PRESETS[0].1)
+                .interact_text()?;
+            let warn_loc: usize = Input::with_theme(theme)
+                .with_prompt("Warn above lines per file")
+                .default(max_loc.min(PRESETS[0].2))
+                .validate_with(|v: &usize| {
+                    if *v <= max_loc {
+                        Ok(())
+                    } else {
+                        Err(format!("must be at most {max_loc}"))
+                    }
+                })
+                .interact_text()?;
+            let max_functions: usize = Input::with_theme(theme)
+                .with_prompt("Maximum functions per file")
+                .default(PRESETS[0].3)
+                .interact_text()?;
+            (max_loc, warn_loc, max_functions)
+        }
+    };
+    Ok(CheckSettings {
+        max_loc,
+        warn_loc,
+        max_functions,
+        ..CheckSettings::default()
+    })
+}
+
+/// The config file for the chosen hosts, model, and thresholds.
+fn render_config(hosts: &[Found], model: Option<&str>, check: &CheckSettings) -> String {
+    let mut doc = DocumentMut::new();
+    doc.decor_mut()
+        .set_prefix("# Written by `guardian-cli setup`. See docs/usage.md for every setting.\n\n");
+
+    let mut ollama = Table::new();
+    // Without a default model, only [[ollama.hosts]] needs a header.
+    ollama.set_implicit(true);
+    if let Some(model) = model {
+        ollama["default_model"] = value(model);
+    }
+    let mut list = ArrayOfTables::new();
+    for host in hosts {
+        let mut table = Table::new();
+        table["name"] = value(&host.name);
+        table["base_url"] = value(&host.base_url);
+        list.push(table);
+    }
+    ollama["hosts"] = Item::ArrayOfTables(list);
+    doc["ollama"] = Item::Table(ollama);
+
+    let mut table = Table::new();
+    table["max_loc"] = value(check.max_loc as i64);
+    table["warn_loc"] = value(check.warn_loc as i64);
+    table["max_functions"] = value(check.max_functions as i64);
+    doc["check"] = Item::Table(table);
+    doc.to_string()
+}
+
+/// Write the config, first copying any existing file to `FILE.bak`, and
+/// return the backup's path if one was made.
+fn write_config(path: &Path, contents: &str) -> Result<Option<PathBuf>> {
+    let backup = path
+        .exists()
+        .then(|| PathBuf::from(format!("{}.bak", path.display())));
+    if let Some(backup) = &backup {
+        fs::copy(path, backup)
+            .with_context(|| format!("Failed to write backup at {}", backup.display()))?;
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    fs::write(path, contents)
+        .with_context(|| format!("Failed to write config file at {}", path.display()))?;
+    Ok(backup)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GuardianConfig;
+
+    #[test]
+    fn test_render_config_loads() {
+        let hosts = [
+            Found {
+                name: "local".to_string(),
+                base_url: "http://localhost:11434".to_string(),
+                models: vec!["llama3.2".to_string()],
+            },
+            Found {
+                name: "192-168-1-20".to_string(),
+                base_url: "http://192.168.1.20:11434".to_string(),
+                models: Vec::new(),
+            },
+        ];
+        let check = CheckSettings {
+            max_loc: 300,
+            warn_loc: 200,
+            max_functions: 5,
+            ..CheckSettings::default()
+        };
+        let contents = render_config(&hosts, Some("llama3.2"), &check);
+        let cfg: GuardianConfig = toml::from_str(&contents).unwrap();
+        assert_eq!(cfg.ollama.default_model.as_deref(), Some("llama3.2"));
+        assert_eq!(cfg.ollama.hosts.len(), 2);
+        assert_eq!(cfg.ollama.hosts[1].base_url, "http://192.168.1.20:11434");
+        assert_eq!(cfg.check.max_loc, 300);
+        assert_eq!(cfg.check.max_functions, 5);
+    }
+}
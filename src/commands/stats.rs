@@ -0,0 +1,191 @@
+//! Stats command: open violations, and the ones recently introduced and
+//! fixed, per check or, with `--by-author`, per git author.
+//!
+//! Every failure is attributed with blame (see [`blame::attribute`]), and
+//! counts as introduced when the commit that last changed its line is newer
+//! than `--since`. The violations fixed are the ones the oldest `check` or
+//! `evaluate` run in the result history since then had, matched by
+//! fingerprint, that the checks no longer find. A fix goes to whoever last
+//! changed the line it pointed at, if they did so since then; fixes made by
+//! deleting lines, and lines nobody has committed, are unattributed.
+
+use super::output::{self, fingerprints};
+use super::usage::parse_age;
+use crate::checks::{
+    CheckResult, blame, configured_thresholds, run_selected_checks, select_checks,
+};
+use crate::config::{GuardianConfig, state::now_secs};
+use crate::storage::{History, StoredRun};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+/// The author row for violations blame can't attribute.
+const UNATTRIBUTED: &str = "(unattributed)";
+
+/// Options for the stats command.
+pub struct StatsOptions<'a> {
+    pub path: Option<&'a Path>,
+    /// Group by git author instead of by check.
+    pub by_author: bool,
+    /// How far back introduced and fixed violations go, e.g. "30d".
+    pub since: &'a str,
+    pub json_output: bool,
+}
+
+/// One check's or author's violations.
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct Tally {
+    /// The check, or the author as `Name <email>`.
+    pub name: String,
+    pub open: usize,
+    pub introduced: usize,
+    pub fixed: usize,
+}
+
+/// Run the checks with blame and report the violations per check or author.
+pub fn stats(config: &GuardianConfig, opts: StatsOptions<'_>) -> Result<()> {
+    let since = now_secs().saturating_sub(parse_age(opts.since)?);
+    let project_dir = opts.path.unwrap_or(Path::new("."));
+    let check = &config.check;
+    let selected = select_checks(None, None, check.only.as_deref(), &config.checks)?;
+    let mut results = run_selected_checks(project_dir, &configured_thresholds(check), &selected);
+    blame::attribute(&mut results);
+    let open: Vec<&CheckResult> = results.iter().filter(|r| !r.passed).collect();
+
+    let (baseline, mut fixed) = if config.storage.enabled {
+        let history = History::open(&config.storage).context("Failed to open the history")?;
+        fixed_since(&history, project_dir, since, &results)?
+    } else {
+        (None, Vec::new())
+    };
+    blame::attribute(&mut fixed);
+    let rows = tally(&open, &fixed, since, opts.by_author);
+    let by = if opts.by_author { "author" } else { "check" };
+    output::stats_report(&rows, by, opts.since, baseline.as_ref(), opts.json_output)
+}
+
+/// The baseline run, the oldest recorded since `since`, and the violations
+/// it had that `results` don't, among the checks `results` ran.
+fn fixed_since(
+    history: &History,
+    project_dir: &Path,
+    since: u64,
+    results: &[CheckResult],
+) -> Result<(Option<StoredRun>, Vec<CheckResult>)> {
+    let Some(baseline) = history.first_run_since(project_dir, since)? else {
+        return Ok((None, Vec::new()));
+    };
+    let ran: HashSet<&str> = results.iter().map(|r| r.check_name.as_str()).collect();
+    let open: Vec<&CheckResult> = results.iter().filter(|r| !r.passed).collect();
+    let open: HashSet<String> = fingerprints(&open).into_iter().collect();
+
+    let before: Vec<CheckResult> = history
+        .results(baseline.id)?
+        .into_iter()
+        .filter(|r| !r.passed && ran.contains(r.check_name.as_str()))
+        .collect();
+    let prints = fingerprints(&before.iter().collect::<Vec<_>>());
+    let fixed = before
+        .into_iter()
+        .zip(prints)
+        .filter(|(_, print)| !open.contains(print))
+        .map(|(result, _)| result)
+        .collect();
+    Ok((Some(baseline), fixed))
+}
+
+/// Count `open` and `fixed` violations per check or author, most open first.
+fn tally(open: &[&CheckResult], fixed: &[CheckResult], since: u64, by_author: bool) -> Vec<Tally> {
+    let mut rows: BTreeMap<String, Tally> = BTreeMap::new();
+    for result in open {
+        let row = rows.entry(group(result, by_author, 0)).or_default();
+        row.open += 1;
+        if result.blame.as_ref().is_some_and(|b| b.time >= since) {
+            row.introduced += 1;
+        }
+    }
+    for result in fixed {
+        rows.entry(group(result, by_author, since))
+            .or_default()
+            .fixed += 1;
+    }
+    let mut rows: Vec<Tally> = rows
+        .into_iter()
+        .map(|(name, row)| Tally { name, ..row })
+        .collect();
+    rows.sort_by_key(|row| std::cmp::Reverse((row.open, row.introduced, row.fixed)));
+    rows
+}
+
+/// The row `result` counts toward: its check, or the author who last
+/// changed its line, if they did so at or after `since`.
+fn group(result: &CheckResult, by_author: bool, since: u64) -> String {
+    if !by_author {
+        return result.check_name.clone();
+    }
+    match &result.blame {
+        Some(blame) if blame.time >= since => format!("{} <{}>", blame.author, blame.email),
+        _ => UNATTRIBUTED.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checks::Severity;
+    use crate::checks::blame::Blame;
+
+    fn blamed(check: &str, author: &str, time: u64) -> CheckResult {
+        let mut result = CheckResult::fail(check, Severity::Error, "Too long");
+        result.blame = Some(Blame {
+            commit: "abc123".to_string(),
+            author: author.to_string(),
+            email: format!("{}@example.com", author.to_lowercase()),
+            time,
+            summary: "Add things".to_string(),
+        });
+        result
+    }
+
+    #[test]
+    fn test_tally_by_author() {
+        let open = [
+            blamed("loc-limits", "Agent", 500),
+            blamed("loc-limits", "Agent", 50),
+            blamed("clippy-disables", "Jane", 200),
+            CheckResult::fail("todo-markers", Severity::Warning, "TODO"),
+        ];
+        let open: Vec<&CheckResult> = open.iter().collect();
+        let fixed = [
+            blamed("loc-limits", "Jane", 300),
+            blamed("loc-limits", "Old", 10),
+        ];
+
+        let rows = tally(&open, &fixed, 100, true);
+        let agent = Tally {
+            name: "Agent <agent@example.com>".to_string(),
+            open: 2,
+            introduced: 1,
+            fixed: 0,
+        };
+        let jane = Tally {
+            name: "Jane <jane@example.com>".to_string(),
+            open: 1,
+            introduced: 1,
+            fixed: 1,
+        };
+        let unattributed = Tally {
+            name: UNATTRIBUTED.to_string(),
+            open: 1,
+            introduced: 0,
+            fixed: 1,
+        };
+        assert_eq!(rows, [agent, jane, unattributed]);
+
+        let rows = tally(&open, &fixed, 100, false);
+        assert_eq!(rows[0].name, "loc-limits");
+        assert_eq!((rows[0].open, rows[0].introduced, rows[0].fixed), (2, 1, 2));
+    }
+}
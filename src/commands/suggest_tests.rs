@@ -0,0 +1,291 @@
+//! Suggest-tests command: the model proposes tests for untested functions.
+//!
+//! Finding the public functions no test mentions is deterministic (see
+//! [`untested_functions`]); the model routed to `suggest-tests` then writes
+//! concrete tests for each, with names and assertions. The tests print as
+//! Markdown to read, or as Rust to paste into each file's tests module.
+//! Nothing is written to the project.
+
+use super::cli::TestsFormat;
+use super::llm::{Routed, cancellable, check_context_window, routed};
+use super::output::report;
+use super::rules;
+use crate::checks::untested::{UntestedFunction, untested_functions};
+use crate::checks::vcs::Vcs;
+use crate::checks::walk::Scan;
+use crate::config::GuardianConfig;
+use crate::ollama::ResponseFormat;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Functions tests are asked for per run, so one run can't queue up
+/// unbounded work.
+const MAX_FUNCTIONS: usize = 10;
+
+/// Options for the suggest-tests command.
+pub struct SuggestTestsOptions<'a> {
+    pub path: Option<&'a Path>,
+    /// Only functions in the staged files.
+    pub staged: bool,
+    pub format: TestsFormat,
+    pub model: Option<&'a str>,
+    pub host: Option<&'a str>,
+    pub json_output: bool,
+}
+
+/// A test the model proposes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct SuggestedTest {
+    /// Name of the test function.
+    name: String,
+    /// What the test checks, in a sentence.
+    purpose: String,
+    /// The test function's source, attribute included.
+    code: String,
+}
+
+#[derive(Deserialize)]
+struct Reply {
+    tests: Vec<SuggestedTest>,
+}
+
+/// The tests proposed for one function.
+#[derive(Debug, Serialize)]
+struct Suggestion {
+    /// Relative to the project directory.
+    file: String,
+    line: usize,
+    function: String,
+    tests: Vec<SuggestedTest>,
+}
+
+/// Find the public functions without tests and print tests for them.
+pub async fn suggest_tests(config: &GuardianConfig, opts: SuggestTestsOptions<'_>) -> Result<()> {
+    let project_dir = opts.path.unwrap_or(Path::new("."));
+    let mut functions = untested_functions(project_dir, &Scan::from(&config.check));
+    if opts.staged {
+        let staged = Vcs::detect(project_dir).staged_files(project_dir)?;
+        if staged.is_empty() {
+            report!("Nothing is staged.");
+            return Ok(());
+        }
+        functions.retain(|f| {
+            f.file
+                .strip_prefix(project_dir)
+                .is_ok_and(|file| staged.contains(file))
+        });
+    }
+    if functions.is_empty() {
+        report!("Every public function has a test.");
+        return Ok(());
+    }
+    eprintln!("{} public functions have no test\n", functions.len());
+    if functions.len() > MAX_FUNCTIONS {
+        tracing::warn!(
+            "Only suggesting tests for the first {MAX_FUNCTIONS} of {} functions",
+            functions.len()
+        );
+        functions.truncate(MAX_FUNCTIONS);
+    }
+
+    let suggestions = ask_for_tests(config, &opts, &functions, project_dir).await?;
+    if opts.json_output {
+        report!("{}", serde_json::to_string_pretty(&suggestions)?);
+    } else {
+        let text = match opts.format {
+            TestsFormat::Markdown => render_markdown(&suggestions),
+            TestsFormat::Rust => render_rust(&suggestions),
+        };
+        report!("{}", text.trim_end());
+    }
+    Ok(())
+}
+
+/// Have the model routed to `suggest-tests` write tests for each of
+/// `functions`, skipping the ones it fails on.
+async fn ask_for_tests(
+    config: &GuardianConfig,
+    opts: &SuggestTestsOptions<'_>,
+    functions: &[UntestedFunction],
+    project_dir: &Path,
+) -> Result<Vec<Suggestion>> {
+    let Routed {
+        client,
+        mut breaker,
+        host,
+        model: model_name,
+        system,
+    } = routed(config, "suggest-tests", 180_000, opts.host, opts.model).await?;
+
+    let rules = rules::project_rules(project_dir)?;
+    let system = system.as_deref();
+    let format = ResponseFormat::Schema(serde_json::json!({
+        "type": "object",
+        "properties": {"tests": {"type": "array", "items": {
+            "type": "object",
+            "properties": {
+                "name": {"type": "string"},
+                "purpose": {"type": "string"},
+                "code": {"type": "string"},
+            },
+            "required": ["name", "purpose", "code"],
+        }}},
+        "required": ["tests"],
+    }));
+    let mut suggestions = Vec::new();
+    for (i, function) in functions.iter().enumerate() {
+        let file = function
+            .file
+            .strip_prefix(project_dir)
+            .unwrap_or(&function.file);
+        let file = file.display().to_string();
+        eprintln!(
+            "Writing tests for {} ({}/{}) with {model_name}...",
+            function.name,
+            i + 1,
+            functions.len()
+        );
+        let prompt = build_prompt(function, &file, rules.as_deref());
+        check_context_window(config, &client, host, &model_name, system, &prompt).await?;
+        let retries = config.ollama.json_retries;
+        let response = cancellable(
+            &client,
+            client.generate_json(host, &model_name, &prompt, &format, retries),
+        )
+        .await;
+        breaker.record(&host.name, response.is_ok());
+        let reply = response.and_then(|(value, _)| {
+            serde_json::from_value::<Reply>(value)
+                .with_context(|| format!("{model_name} returned malformed tests"))
+        });
+        match reply {
+            Ok(reply) => suggestions.push(Suggestion {
+                file,
+                line: function.line,
+                function: function.name.clone(),
+                tests: reply.tests,
+            }),
+            Err(e) => tracing::warn!("No tests for {}: {e:#}", function.name),
+        }
+    }
+    breaker.save();
+    eprintln!();
+    Ok(suggestions)
+}
+
+fn build_prompt(function: &UntestedFunction, file: &str, rules: Option<&str>) -> String {
+    let mut prompt =
+        String::from("You are writing unit tests for a public Rust function that has none.\n\n");
+    prompt.push_str(rules.unwrap_or_default());
+    prompt.push_str(&format!(
+        "## Function\n\nFile: {file} (line {})\n\n```rust\n{}```\n",
+        function.line, function.source
+    ));
+    prompt.push_str(
+        "\n## Your Task\n\n\
+         Propose up to 3 concrete unit tests for this function: the typical \
+         case, then the edge and error cases it handles. Each test is a \
+         complete `#[test]` function (`#[tokio::test]` if the function is \
+         async) for the `tests` module of its file, where `use super::*;` is \
+         in scope. Assert specific values, not just that nothing panics. \
+         Answer with JSON: \"tests\" lists objects with \"name\" (the test \
+         function's name, starting with test_), \"purpose\" (one sentence on \
+         what it checks), and \"code\" (the test function's source, attribute \
+         included).\n",
+    );
+    prompt
+}
+
+/// A section per function, each test's purpose over its code.
+fn render_markdown(suggestions: &[Suggestion]) -> String {
+    let mut text = String::new();
+    for suggestion in suggestions {
+        text.push_str(&format!(
+            "## `{}` ({}:{})\n\n",
+            suggestion.function, suggestion.file, suggestion.line
+        ));
+        for test in &suggestion.tests {
+            text.push_str(&format!(
+                "- **{}**: {}\n\n```rust\n{}\n```\n\n",
+                test.name,
+                test.purpose,
+                test.code.trim_end()
+            ));
+        }
+    }
+    text
+}
+
+/// The test functions, under a comment naming the file whose tests module
+/// they go in.
+fn render_rust(suggestions: &[Suggestion]) -> String {
+    let mut text = String::new();
+    let mut current = None;
+    for suggestion in suggestions {
+        if current != Some(&suggestion.file) {
+            text.push_str(&format!(
+                "// {}: paste into its tests module\n\n",
+                suggestion.file
+            ));
+            current = Some(&suggestion.file);
+        }
+        for test in &suggestion.tests {
+            text.push_str(&format!(
+                "// {} (line {}): {}\n{}\n\n",
+                suggestion.function,
+                suggestion.line,
+                test.purpose,
+                test.code.trim_end()
+            ));
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn suggestions() -> Vec<Suggestion> {
+        let test = SuggestedTest {
+            name: "test_parse_empty".to_string(),
+            purpose: "Empty input parses to 0.".to_string(),
+            code: "#[test]\nfn test_parse_empty() {\n    assert_eq!(parse(\"\"), 0);\n}\n"
+                .to_string(),
+        };
+        vec![Suggestion {
+            file: "src/lib.rs".to_string(),
+            line: 12,
+            function: "parse".to_string(),
+            tests: vec![test],
+        }]
+    }
+
+    #[test]
+    fn test_render_suggestions() {
+        let markdown = render_markdown(&suggestions());
+        assert!(markdown.starts_with(
+            "## `parse` (src/lib.rs:12)\n\n- **test_parse_empty**: Empty input parses to 0.\n\n```rust\n#[test]\n"
+        ));
+        let rust = render_rust(&suggestions());
+        assert!(rust.starts_with(
+            "// src/lib.rs: paste into its tests module\n\n// parse (line 12): Empty input parses to 0.\n#[test]\nfn test_parse_empty() {\n"
+        ));
+    }
+
+    #[test]
+    fn test_prompt_quotes_the_function() {
+        let function = UntestedFunction {
+            file: "app/src/lib.rs".into(),
+            line: 12,
+            name: "parse".to_string(),
+            source: "pub fn parse(s: &str) -> u8 {\n    s.len() as u8\n}\n".to_string(),
+        };
+        let prompt = build_prompt(&function, "src/lib.rs", Some("- No unwrap.\n\n"));
+        assert!(prompt.contains("- No unwrap.\n\n## Function\n\nFile: src/lib.rs (line 12)\n\n"));
+        assert!(
+            prompt.contains("```rust\npub fn parse(s: &str) -> u8 {\n    s.len() as u8\n}\n```\n")
+        );
+    }
+}
@@ -0,0 +1,60 @@
+//! Usage report command: per-host, per-model request accounting.
+
+use super::output;
+use crate::config::state::{self, now_secs};
+use crate::ollama::{load_usage, summarize};
+use anyhow::{Context, Result};
+
+/// State file that LLM commands append usage records to.
+pub(super) const USAGE_LOG: &str = "usage.jsonl";
+
+/// Show accumulated usage, optionally only for the last `since` (e.g. "7d").
+pub fn show_usage(since: Option<&str>, json_output: bool) -> Result<()> {
+    let cutoff = match since {
+        Some(s) => now_secs().saturating_sub(parse_age(s)?),
+        None => 0,
+    };
+    let records = state::path(USAGE_LOG)
+        .map(|path| load_usage(&path, cutoff))
+        .unwrap_or_default();
+    output::usage_report(&summarize(&records), since, json_output)
+}
+
+/// Parse an age like "30m", "24h", "7d", or "2w" into seconds.
+pub(super) fn parse_age(s: &str) -> Result<u64> {
+    let s = s.trim();
+    let split = s.len() - s.chars().last().map_or(0, char::len_utf8);
+    let (number, unit) = s.split_at(split);
+    let number: u64 = number
+        .parse()
+        .with_context(|| format!("Invalid duration '{s}', expected e.g. 24h or 7d"))?;
+    let unit_secs = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        "d" => 86_400,
+        "w" => 604_800,
+        _ => anyhow::bail!("Invalid duration unit in '{s}', use s, m, h, d, or w"),
+    };
+    number
+        .checked_mul(unit_secs)
+        .with_context(|| format!("Duration '{s}' is too large"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_age() {
+        assert_eq!(parse_age("30m").unwrap(), 1800);
+        assert_eq!(parse_age("24h").unwrap(), 86_400);
+        assert_eq!(parse_age("7d").unwrap(), 604_800);
+        assert_eq!(parse_age("2w").unwrap(), 1_209_600);
+        assert!(parse_age("7").is_err());
+        assert!(parse_age("d").is_err());
+        assert!(parse_age("7y").is_err());
+        let err = parse_age("40000000000000w").unwrap_err();
+        assert!(err.to_string().contains("too large"));
+    }
+}
@@ -0,0 +1,211 @@
+//! The structured verdict `evaluate` asks the model for.
+//!
+//! Prose can't gate a pipeline, so the model answers with JSON in
+//! [`Assessment::format`]'s shape. Replies that don't fit are re-prompted
+//! once, and anything but `APPROVE` fails the run, unless the model is
+//! less sure of it than the rubric's `min_confidence`.
+
+use crate::ollama::ResponseFormat;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// What the model says should happen to the change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Verdict {
+    /// Good to go; any advice can wait.
+    Approve,
+    /// Fix the blocking problems first.
+    Revise,
+    /// The approach itself is wrong.
+    Reject,
+}
+
+/// The model's review of a run's check results.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Assessment {
+    pub verdict: Verdict,
+    /// How sure the model is of the verdict, from 0 to 1.
+    pub confidence: f64,
+    /// Problems that must be fixed first, most critical first.
+    pub blocking: Vec<Finding>,
+    /// Suggestions that needn't hold the change up.
+    pub advice: Vec<String>,
+    /// The sections the rubric asks for, by title.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub sections: BTreeMap<String, String>,
+}
+
+/// A problem the model says must be fixed first.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Finding {
+    pub issue: String,
+    /// How sure the model is that it must block, from 0 to 1.
+    pub confidence: f64,
+}
+
+impl Verdict {
+    /// Name of the verdict, as the model writes it.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Approve => "APPROVE",
+            Self::Revise => "REVISE",
+            Self::Reject => "REJECT",
+        }
+    }
+}
+
+impl Assessment {
+    /// The response format that asks for an assessment, with the rubric's
+    /// `sections` when there are any.
+    pub fn format(sections: &[String]) -> ResponseFormat {
+        let confidence = serde_json::json!({"type": "number", "minimum": 0, "maximum": 1});
+        let mut schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "verdict": {"type": "string", "enum": ["APPROVE", "REVISE", "REJECT"]},
+                "confidence": confidence,
+                "blocking": {"type": "array", "items": {
+                    "type": "object",
+                    "properties": {"issue": {"type": "string"}, "confidence": confidence},
+                    "required": ["issue", "confidence"],
+                }},
+                "advice": {"type": "array", "items": {"type": "string"}},
+            },
+            "required": ["verdict", "confidence", "blocking", "advice"],
+        });
+        if !sections.is_empty() {
+            let properties: serde_json::Map<_, _> = sections
+                .iter()
+                .map(|title| (title.clone(), serde_json::json!({"type": "string"})))
+                .collect();
+            schema["properties"]["sections"] = serde_json::json!({
+                "type": "object",
+                "properties": properties,
+                "required": sections,
+            });
+            schema["required"] =
+                serde_json::json!(["verdict", "confidence", "blocking", "advice", "sections"]);
+        }
+        ResponseFormat::Schema(schema)
+    }
+
+    /// The assessment as text, for reports, notifications, and the history.
+    pub fn text(&self) -> String {
+        let mut text = format!(
+            "Verdict: {} (confidence {:.2})\n",
+            self.verdict.name(),
+            self.confidence
+        );
+        if !self.blocking.is_empty() {
+            text.push_str("\nBlocking:\n");
+            for finding in &self.blocking {
+                let confidence = finding.confidence;
+                text.push_str(&format!(
+                    "- {} (confidence {confidence:.2})\n",
+                    finding.issue
+                ));
+            }
+        }
+        if !self.advice.is_empty() {
+            text.push_str("\nAdvice:\n");
+            for item in &self.advice {
+                text.push_str(&format!("- {item}\n"));
+            }
+        }
+        for (title, content) in &self.sections {
+            text.push_str(&format!("\n{title}:\n{}\n", content.trim_end()));
+        }
+        text
+    }
+
+    /// Move the blocking findings the model is less sure of than
+    /// `min_confidence` to the advice, and say how many moved.
+    pub fn demote_unsure(&mut self, min_confidence: f64) -> usize {
+        let (sure, unsure): (Vec<Finding>, Vec<Finding>) = self
+            .blocking
+            .drain(..)
+            .partition(|finding| finding.confidence >= min_confidence);
+        self.blocking = sure;
+        let moved = unsure.len();
+        let demoted = unsure.into_iter().map(|finding| {
+            format!(
+                "{} (low confidence {:.2})",
+                finding.issue, finding.confidence
+            )
+        });
+        self.advice.splice(0..0, demoted);
+        moved
+    }
+
+    /// Whether the verdict should fail the run: anything but `APPROVE`,
+    /// given with at least `min_confidence`.
+    pub fn blocks(&self, min_confidence: f64) -> bool {
+        self.verdict != Verdict::Approve && self.confidence >= min_confidence
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ollama::parse_json_response;
+
+    #[test]
+    fn test_parse_assessment() {
+        let reply = r#"{"verdict": "REVISE", "confidence": 0.8,
+                        "blocking": [{"issue": "Split main.rs", "confidence": 0.9}],
+                        "advice": []}"#;
+        let value = parse_json_response(reply, &Assessment::format(&[])).unwrap();
+        let assessment: Assessment = serde_json::from_value(value).unwrap();
+        assert_eq!(assessment.verdict, Verdict::Revise);
+        assert_eq!(
+            assessment.text(),
+            "Verdict: REVISE (confidence 0.80)\n\nBlocking:\n- Split main.rs (confidence 0.90)\n"
+        );
+
+        let reply = r#"{"verdict": "LGTM", "confidence": 1, "blocking": [], "advice": []}"#;
+        assert!(parse_json_response(reply, &Assessment::format(&[])).is_err());
+        let reply = r#"{"verdict": "APPROVE", "blocking": [], "advice": []}"#;
+        assert!(parse_json_response(reply, &Assessment::format(&[])).is_err());
+    }
+
+    #[test]
+    fn test_rubric_sections() {
+        let format = Assessment::format(&["Risks".to_string()]);
+        let reply = r#"{"verdict": "APPROVE", "confidence": 1, "blocking": [], "advice": []}"#;
+        assert!(parse_json_response(reply, &format).is_err());
+        let reply = r#"{"verdict": "APPROVE", "confidence": 1, "blocking": [], "advice": [],
+                        "sections": {"Risks": "None worth noting."}}"#;
+        let value = parse_json_response(reply, &format).unwrap();
+        let assessment: Assessment = serde_json::from_value(value).unwrap();
+        assert_eq!(
+            assessment.text(),
+            "Verdict: APPROVE (confidence 1.00)\n\nRisks:\nNone worth noting.\n"
+        );
+    }
+
+    #[test]
+    fn test_unsure_findings_dont_block() {
+        let finding = |issue: &str, confidence| Finding {
+            issue: issue.to_string(),
+            confidence,
+        };
+        let mut assessment = Assessment {
+            verdict: Verdict::Revise,
+            confidence: 0.3,
+            blocking: vec![finding("Split main.rs", 0.9), finding("Rename x", 0.2)],
+            advice: vec!["Add docs".to_string()],
+            sections: BTreeMap::new(),
+        };
+        assert_eq!(assessment.demote_unsure(0.5), 1);
+        assert_eq!(assessment.blocking, [finding("Split main.rs", 0.9)]);
+        assert_eq!(
+            assessment.advice,
+            ["Rename x (low confidence 0.20)", "Add docs"]
+        );
+        assert!(!assessment.blocks(0.5));
+        assert!(assessment.blocks(0.3));
+        assessment.verdict = Verdict::Approve;
+        assert!(!assessment.blocks(0.0));
+    }
+}
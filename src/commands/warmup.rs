@@ -0,0 +1,60 @@
+//! Warmup command: preload models so the first evaluation isn't a cold start.
+
+use super::llm;
+use super::output;
+use crate::config::{GuardianConfig, OllamaHost};
+use crate::ollama::select::{load_breaker, resolve_host};
+use anyhow::Result;
+use futures::StreamExt;
+
+/// Loading a large model from disk can take minutes on a cold box.
+const WARMUP_TIMEOUT_MS: u64 = 600_000;
+
+/// Options for the warmup command.
+pub struct WarmupOptions<'a> {
+    /// Model to load (default: the model routed to `evaluate`).
+    pub model: Option<&'a str>,
+    pub host: Option<&'a str>,
+    /// Warm every enabled host instead of a single one.
+    pub all: bool,
+    /// How long the model should stay loaded, e.g. "1h".
+    pub keep_alive: &'a str,
+    pub json_output: bool,
+}
+
+/// Load a model into memory on one host, or on every host with `all`.
+pub async fn warmup(config: &GuardianConfig, opts: WarmupOptions<'_>) -> Result<()> {
+    let route = config.route("evaluate");
+    let Some(model) = opts.model.or(route.model.as_deref()) else {
+        anyhow::bail!("No model to warm up; pass --model or set default_model in [ollama]");
+    };
+
+    let client = llm::client(config, WARMUP_TIMEOUT_MS)?;
+    let hosts: Vec<&OllamaHost> = if opts.all {
+        config.enabled_hosts()
+    } else {
+        let mut breaker = load_breaker(config);
+        let host_name = opts.host.or(route.host.as_deref());
+        let host = resolve_host(config, &client, &mut breaker, host_name).await?;
+        breaker.save();
+        vec![host]
+    };
+    if hosts.is_empty() {
+        return output::no_hosts_error(opts.json_output, "No hosts configured");
+    }
+
+    let loads = hosts
+        .iter()
+        .map(|host| client.warm_model(host, model, opts.keep_alive));
+    let loaded: Vec<_> = futures::stream::iter(loads)
+        .buffered(client.jobs())
+        .collect()
+        .await;
+    let results: Vec<_> = hosts.iter().copied().zip(loaded).collect();
+
+    output::warmup_results(model, opts.keep_alive, &results, opts.json_output)?;
+    if results.iter().all(|(_, result)| result.is_err()) {
+        anyhow::bail!("No host could load {model}");
+    }
+    Ok(())
+}
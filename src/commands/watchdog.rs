@@ -0,0 +1,152 @@
+//! Watchdog command: re-run the checks on a schedule and speak up only
+//! when things get worse.
+//!
+//! Meant to supervise long unattended agent sessions. The first run is
+//! taken as known-good. Each run after it is compared with the last
+//! known-good one: errors it didn't have, matched by fingerprint, or a lower
+//! score (see [`policy::score`]) are a regression, notified once through the
+//! `regression` webhooks and, with `--desktop`, the desktop. A run with
+//! neither becomes the new known-good run. The known-good run is kept in a
+//! state file, so a restarted watchdog picks up where it left off.
+
+use super::notify;
+use super::output::fingerprints;
+use super::policy;
+use super::usage::parse_age;
+use crate::checks::interrupt::interrupted;
+use crate::checks::{
+    CheckResult, Severity, configured_thresholds, run_selected_checks, select_checks,
+};
+use crate::config::{GuardianConfig, state};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+
+/// State file holding each project's known-good run.
+const KNOWN_GOOD: &str = "watchdog.json";
+
+/// Options for the watchdog command.
+pub struct WatchdogOptions<'a> {
+    pub path: Option<&'a Path>,
+    /// How long to wait between runs, e.g. "30m".
+    pub interval: &'a str,
+    /// Only run these checks, comma-separated (`--only`).
+    pub only: Option<&'a str>,
+    /// Don't run these checks, comma-separated (`--skip`).
+    pub skip: Option<&'a str>,
+    /// Also raise regressions as desktop notifications.
+    pub desktop: bool,
+}
+
+/// What a run found, as far as regressions go.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Snapshot {
+    score: f64,
+    /// Fingerprints of the errors.
+    errors: Vec<String>,
+}
+
+/// Run the checks on the project every `interval` until Ctrl-C, notifying
+/// when a run regresses from the last known-good one.
+pub async fn watchdog(config: &GuardianConfig, opts: WatchdogOptions<'_>) -> Result<()> {
+    let interval = Duration::from_secs(parse_age(opts.interval)?.max(1));
+    let project_dir = opts.path.unwrap_or(Path::new("."));
+    let project = project_dir
+        .canonicalize()
+        .unwrap_or_else(|_| project_dir.to_path_buf())
+        .display()
+        .to_string();
+    let check = &config.check;
+    let selected = select_checks(opts.only, opts.skip, check.only.as_deref(), &config.checks)?;
+    let thresholds = configured_thresholds(check);
+    let path = state::path(KNOWN_GOOD);
+    let mut known: BTreeMap<String, Snapshot> =
+        path.as_deref().and_then(state::read).unwrap_or_default();
+    let mut alerted = None;
+
+    eprintln!("Watching {project} every {}; Ctrl-C stops", opts.interval);
+    while !interrupted() {
+        let results = run_selected_checks(project_dir, &thresholds, &selected);
+        if interrupted() {
+            break;
+        }
+        let errors: Vec<&CheckResult> = results
+            .iter()
+            .filter(|r| !r.passed && r.severity == Severity::Error)
+            .collect();
+        let now = Snapshot {
+            score: policy::score(&results),
+            errors: fingerprints(&errors),
+        };
+        let good = known.get(&project).cloned().unwrap_or_else(|| now.clone());
+        let new = regression(&good, &now, &errors);
+        let status = format!("score {}, {} error(s)", now.score, errors.len());
+        if new.is_empty() && now.score >= good.score {
+            eprintln!("{status}: no regression");
+            known.insert(project.clone(), now);
+            if let Some(path) = &path {
+                state::write(path, &known);
+            }
+            alerted = None;
+        } else if alerted.as_ref() != Some(&now) {
+            eprintln!("{status}: regressed from score {}, notifying", good.score);
+            let targets = &config.notify;
+            notify::regression(targets, &project, &new, now.score, good.score, opts.desktop).await;
+            alerted = Some(now);
+        } else {
+            eprintln!("{status}: still regressed, already notified");
+        }
+        wait(interval).await;
+    }
+    Ok(())
+}
+
+/// The `errors` of the run `now` that the known-good run `good` didn't
+/// have.
+fn regression<'r>(
+    good: &Snapshot,
+    now: &Snapshot,
+    errors: &[&'r CheckResult],
+) -> Vec<&'r CheckResult> {
+    errors
+        .iter()
+        .zip(&now.errors)
+        .filter(|(_, id)| !good.errors.contains(id))
+        .map(|(result, _)| *result)
+        .collect()
+}
+
+/// Sleep for `interval`, waking early once the run is interrupted.
+async fn wait(interval: Duration) {
+    let tick = Duration::from_millis(200);
+    let mut waited = Duration::ZERO;
+    while waited < interval && !interrupted() {
+        tokio::time::sleep(tick.min(interval - waited)).await;
+        waited += tick;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_regression_finds_new_errors() {
+        let old = CheckResult::fail("loc-limits", Severity::Error, "Too long");
+        let new = CheckResult::fail("clippy-disables", Severity::Error, "Lint suppressed");
+        let good = Snapshot {
+            score: 90.0,
+            errors: vec!["a".to_string()],
+        };
+        let now = Snapshot {
+            score: 80.0,
+            errors: vec!["a".to_string(), "b".to_string()],
+        };
+        let found = regression(&good, &now, &[&old, &new]);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].check_name, "clippy-disables");
+        assert!(regression(&now, &now, &[&old, &new]).is_empty());
+    }
+}
@@ -4,185 +4,302 @@
 //! and architecture rules while reducing token usage for cloud-based AI
 //! coding agents.
 
-mod checks;
 mod commands;
-mod config;
-mod ollama;
 
+use guardian_core::{checks, config, ollama, storage};
+
+use crate::commands::cli::{Cli, Commands, FailOn, ReportFormat};
 use crate::config::GuardianConfig;
 use anyhow::Result;
-use clap::{Parser, Subcommand};
-use std::path::PathBuf;
-
-/// Guardian CLI - Local LLM governor for development process enforcement.
-#[derive(Debug, Parser)]
-#[command(name = "guardian-cli")]
-#[command(author, version, about, long_about = None)]
-struct Cli {
-    /// Path to configuration file (default: ~/.config/guardian-cli/guardian.toml)
-    #[arg(long, short, value_name = "FILE")]
-    config: Option<PathBuf>,
-
-    /// Output in JSON format
-    #[arg(long, global = true)]
-    json: bool,
-
-    /// Enable verbose output
-    #[arg(long, short, global = true)]
-    verbose: bool,
-
-    #[command(subcommand)]
-    command: Commands,
-}
-
-#[derive(Debug, Subcommand)]
-enum Commands {
-    /// Ping all configured Ollama hosts to check availability
-    PingHosts,
-
-    /// List models available on reachable Ollama hosts
-    ListModels {
-        /// Only query a specific host by name
-        #[arg(long)]
-        host: Option<String>,
-    },
-
-    /// Select the best available host (for scripting)
-    SelectHost {
-        /// Require a specific model to be available
-        #[arg(long)]
-        model: Option<String>,
-    },
-
-    /// Show current configuration
-    ShowConfig,
-
-    /// Show default config file path
-    ConfigPath,
-
-    /// Send a prompt to an Ollama model and get a response
-    Ask {
-        /// The prompt to send
-        prompt: String,
-
-        /// Model to use (default: from config or first available)
-        #[arg(long, short)]
-        model: Option<String>,
-
-        /// Specific host to use
-        #[arg(long)]
-        host: Option<String>,
-    },
-
-    /// Run checks AND have LLM evaluate results to enforce process
-    Evaluate {
-        /// Path to the project directory (default: current directory)
-        #[arg(value_name = "PATH")]
-        path: Option<PathBuf>,
-
-        /// Model to use for evaluation
-        #[arg(long, short)]
-        model: Option<String>,
-
-        /// Specific host to use
-        #[arg(long)]
-        host: Option<String>,
-
-        /// Only run specific check(s), comma-separated
-        #[arg(long)]
-        only: Option<String>,
-    },
-
-    /// Run checklist validation on a project
-    Check {
-        /// Path to the project directory (default: current directory)
-        #[arg(value_name = "PATH")]
-        path: Option<PathBuf>,
-
-        /// Only run specific check(s), comma-separated
-        #[arg(long, value_name = "CHECKS")]
-        only: Option<String>,
-
-        /// Maximum lines of code per file
-        #[arg(long, default_value = "500")]
-        max_loc: usize,
-
-        /// Warning threshold for file LOC
-        #[arg(long, default_value = "350")]
-        warn_loc: usize,
-
-        /// Maximum functions per module
-        #[arg(long, default_value = "7")]
-        max_functions: usize,
-
-        /// Maximum modules per crate
-        #[arg(long, default_value = "4")]
-        max_modules: usize,
-
-        /// Required Rust edition
-        #[arg(long, default_value = "2024")]
-        edition: String,
-    },
-}
+use clap::Parser;
+use std::path::Path;
+use std::process::ExitCode;
 
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() -> ExitCode {
     let cli = Cli::parse();
     init_tracing(cli.verbose);
 
-    let config = GuardianConfig::load(cli.config.as_deref())?;
+    match run(cli).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            // Exit errors have already been reported by the command.
+            if !err.is::<commands::Exit>() {
+                eprintln!("Error: {err:?}");
+            }
+            ExitCode::from(commands::exit_code(&err))
+        }
+    }
+}
+
+async fn run(cli: Cli) -> Result<()> {
+    commands::init_output(cli.output.as_deref())?;
+    commands::init_color(cli.color);
+    // These don't need the config, and must work while it is invalid: the
+    // current schema rejects old layouts, so migrate before loading.
+    match &cli.command {
+        Commands::MigrateConfig(args) => {
+            let path = args.path.as_deref().or(cli.config.as_deref());
+            return commands::migrate_config(path, args.dry_run, cli.json);
+        }
+        Commands::Setup(args) => {
+            return commands::setup(cli.config.as_deref(), args.scan, cli.json).await;
+        }
+        Commands::SetSecret { name } => return commands::set_secret(name, cli.json),
+        Commands::DeleteSecret { name } => return commands::delete_secret(name, cli.json),
+        _ => {}
+    }
+    let serving = matches!(cli.command, Commands::Serve(_));
+    let load_config = |project_dir: Option<&Path>| {
+        let (file, profile) = (cli.config.as_deref(), cli.profile.as_deref());
+        let mut config =
+            GuardianConfig::load(file, project_dir, profile, cli.policy_url.as_deref())?;
+        config.retain_tagged(&cli.host_tag)?;
+        config.apply_crate_kind(project_dir.unwrap_or(Path::new(".")));
+        config.check.follow_symlinks |= cli.follow_symlinks;
+        config.ollama.fresh = cli.fresh;
+        (config.check.jobs, config.ollama.jobs) = (cli.jobs, cli.jobs);
+        // The daemon pings to track health, so it never reuses a ping.
+        if serving {
+            config.ollama.probe_cache_secs = 0;
+        }
+        anyhow::Ok(config)
+    };
+    let cwd = std::env::current_dir().ok();
+    let config = load_config(cli.command.project_dir().or(cwd.as_deref()))?;
+    commands::init_hyperlinks(&config.output);
 
     match cli.command {
         Commands::PingHosts => commands::ping_hosts(&config, cli.json).await,
         Commands::ListModels { host } => {
             commands::list_models(&config, host.as_deref(), cli.json).await
         }
-        Commands::SelectHost { model } => {
-            commands::select_host(&config, model.as_deref(), cli.json).await
+        Commands::Fleet => commands::fleet(&config, cli.json).await,
+        Commands::SelectHost(args) => {
+            commands::select_host(&config, args.model.as_deref(), args.all, cli.json).await
         }
-        Commands::ShowConfig => commands::show_config(&config, cli.json),
+        Commands::ShowConfig(args) => commands::show_config(&config, args.origin, cli.json),
         Commands::ConfigPath => commands::config_path(cli.json),
-        Commands::Ask {
-            prompt,
-            model,
-            host,
-        } => {
-            commands::ask(&config, &prompt, model.as_deref(), host.as_deref(), cli.json).await
+        Commands::Rules => commands::list_rules(cli.json),
+        Commands::MigrateConfig(_)
+        | Commands::Setup(_)
+        | Commands::SetSecret { .. }
+        | Commands::DeleteSecret { .. } => unreachable!("handled before loading config"),
+        Commands::Ask(args) => {
+            let opts = commands::AskOptions {
+                prompt: &args.prompt,
+                model: args.model.as_deref(),
+                host: args.host.as_deref(),
+                task: args.task.as_deref(),
+                json_format: args.format.is_some(),
+                schema: args.schema.as_deref(),
+                race: args.race,
+                json_output: cli.json,
+            };
+            commands::ask(&config, opts).await
+        }
+        Commands::Evaluate(args) => {
+            commands::stop_gracefully();
+            let policy = args.policy.as_deref().or(config.check.policy.as_deref());
+            let opts = commands::EvaluateOptions {
+                path: args.path.as_deref(),
+                model: args.model.as_deref(),
+                host: args.host.as_deref(),
+                only: args.only.as_deref(),
+                skip: args.skip.as_deref(),
+                html_report: args.html_report.as_deref(),
+                json_output: cli.json,
+                policy: commands::FailPolicy {
+                    max_warnings: config.check.max_warnings,
+                    expression: policy.map(commands::Policy::parse).transpose()?,
+                    ..Default::default()
+                },
+                blame: args.blame,
+                offline: args.offline,
+                suggest_patches: args.suggest_patches,
+                triage: args.triage,
+                interactive: args.interactive,
+                force: args.force,
+                snippet_lines: args.snippet_lines,
+                staged: args.staged,
+                from: args.from.as_deref(),
+            };
+            commands::evaluate(&config, opts).await
+        }
+        Commands::Lsp => commands::lsp(&config),
+        Commands::Rpc => commands::rpc(&config).await,
+        Commands::Serve(args) => commands::serve(&config, args.bind.as_deref()).await,
+        Commands::Usage(args) => commands::show_usage(args.since.as_deref(), cli.json),
+        Commands::CopyModel(args) => {
+            let opts = commands::CopyModelOptions {
+                model: &args.model,
+                from: &args.from,
+                to: &args.to,
+                json_output: cli.json,
+            };
+            commands::copy_model(&config, opts).await
+        }
+        Commands::PruneModels(args) => {
+            let opts = commands::PruneOptions {
+                host: args.host.as_deref(),
+                all: args.all,
+                keep: args.keep.as_deref(),
+                older_than: args.older_than.as_deref(),
+                dry_run: args.dry_run,
+                yes: args.yes,
+                json_output: cli.json,
+            };
+            commands::prune_models(&config, opts).await
+        }
+        Commands::Warmup(args) => {
+            let opts = commands::WarmupOptions {
+                model: args.model.as_deref(),
+                host: args.host.as_deref(),
+                all: args.all,
+                keep_alive: &args.keep_alive,
+                json_output: cli.json,
+            };
+            commands::warmup(&config, opts).await
+        }
+        Commands::PrePush(args) => {
+            let policy = args.policy.as_deref().or(config.check.policy.as_deref());
+            let opts = commands::PrePushOptions {
+                range: &args.range,
+                path: args.path.as_deref(),
+                only: args.only.as_deref(),
+                skip: args.skip.as_deref(),
+                review: args.review,
+                interactive: args.interactive,
+                model: args.model.as_deref(),
+                host: args.host.as_deref(),
+                json_output: cli.json,
+                policy: commands::FailPolicy {
+                    max_warnings: config.check.max_warnings,
+                    expression: policy.map(commands::Policy::parse).transpose()?,
+                    ..Default::default()
+                },
+            };
+            commands::pre_push(&config, opts).await
+        }
+        Commands::Annotate(args) => {
+            let policy = args.policy.as_deref().or(config.check.policy.as_deref());
+            let opts = commands::AnnotateOptions {
+                path: args.path.as_deref(),
+                only: args.only.as_deref(),
+                skip: args.skip.as_deref(),
+                json_output: cli.json,
+                policy: commands::FailPolicy {
+                    max_warnings: config.check.max_warnings,
+                    expression: policy.map(commands::Policy::parse).transpose()?,
+                    ..Default::default()
+                },
+                forge: if args.gitea {
+                    commands::Forge::Gitea
+                } else {
+                    commands::Forge::Gitlab
+                },
+            };
+            commands::annotate(&config, opts).await
         }
-        Commands::Evaluate {
-            path,
-            model,
-            host,
-            only,
-        } => {
-            commands::evaluate(
-                &config,
-                path.as_deref(),
-                model.as_deref(),
-                host.as_deref(),
-                only.as_deref(),
-                cli.json,
-            )
-            .await
+        Commands::Apply(args) => commands::apply(
+            &config,
+            commands::ApplyOptions {
+                patches: args.patches.as_deref(),
+                path: args.path.as_deref(),
+            },
+        ),
+        Commands::Govern(args) => {
+            let policy = args.policy.as_deref().or(config.check.policy.as_deref());
+            let opts = commands::GovernOptions {
+                input: &args.input,
+                path: args.path.as_deref(),
+                only: args.only.as_deref(),
+                skip: args.skip.as_deref(),
+                checks_only: args.checks_only,
+                model: args.model.as_deref(),
+                host: args.host.as_deref(),
+                policy: commands::FailPolicy {
+                    max_warnings: config.check.max_warnings,
+                    expression: policy.map(commands::Policy::parse).transpose()?,
+                    ..Default::default()
+                },
+            };
+            commands::govern(&config, opts).await
+        }
+        Commands::SuggestTests(args) => {
+            let opts = commands::SuggestTestsOptions {
+                path: args.path.as_deref(),
+                staged: args.staged,
+                format: args.format,
+                model: args.model.as_deref(),
+                host: args.host.as_deref(),
+                json_output: cli.json,
+            };
+            commands::suggest_tests(&config, opts).await
+        }
+        Commands::Repair(args) => {
+            let opts = commands::RepairOptions {
+                path: args.path.as_deref(),
+                only: args.only.as_deref(),
+                skip: args.skip.as_deref(),
+                max_iterations: args.max_iterations,
+                yes: args.yes,
+                model: args.model.as_deref(),
+                host: args.host.as_deref(),
+                json_output: cli.json,
+            };
+            commands::repair(&config, opts).await
+        }
+        Commands::SelfCheck(args) => {
+            let opts = commands::SelfCheckOptions {
+                source: args.source.as_deref(),
+                json_output: cli.json,
+            };
+            commands::self_check(&config, opts).await
+        }
+        Commands::Watchdog(args) => {
+            commands::stop_gracefully();
+            let opts = commands::WatchdogOptions {
+                path: args.path.as_deref(),
+                interval: &args.interval,
+                only: args.only.as_deref(),
+                skip: args.skip.as_deref(),
+                desktop: args.desktop,
+            };
+            commands::watchdog(&config, opts).await
+        }
+        Commands::Stats(args) => {
+            let opts = commands::StatsOptions {
+                path: args.path.as_deref(),
+                by_author: args.by_author,
+                since: &args.since,
+                json_output: cli.json,
+            };
+            commands::stats(&config, opts)
+        }
+        Commands::Check(args) => {
+            commands::stop_gracefully();
+            let ci = commands::CiProvider::detect();
+            let format = args.format.unwrap_or(if cli.json {
+                ReportFormat::Json
+            } else {
+                ci.map_or(ReportFormat::Text, commands::CiProvider::report_format)
+            });
+            // JSON output is for scripts, which read the results instead,
+            // but a CI job is gated by the exit code whatever the format.
+            let fail_on = args
+                .fail_on
+                .unwrap_or(if format == ReportFormat::Json && ci.is_none() {
+                    FailOn::Never
+                } else {
+                    FailOn::Error
+                });
+            if args.recursive {
+                return commands::check_projects(&args, &config, format, fail_on, load_config)
+                    .await;
+            }
+            commands::run_checks(args.options(&config, format, fail_on)?).await
         }
-        Commands::Check {
-            path,
-            only,
-            max_loc,
-            warn_loc,
-            max_functions,
-            max_modules,
-            edition,
-        } => commands::run_checks(commands::CheckOptions {
-            path: path.as_deref(),
-            only: only.as_deref(),
-            max_loc,
-            warn_loc,
-            max_functions,
-            max_modules,
-            edition: &edition,
-            json_output: cli.json,
-        }),
     }
 }
 
@@ -193,7 +310,9 @@ fn init_tracing(verbose: bool) {
     let filter = tracing_subscriber::EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new(level));
 
+    // Logs are progress, not report, so they go to stderr
     let fmt_layer = tracing_subscriber::fmt::layer()
+        .with_writer(std::io::stderr)
         .with_target(false)
         .with_level(true);
 
@@ -202,59 +321,3 @@ fn init_tracing(verbose: bool) {
         .with(fmt_layer)
         .init();
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use clap::CommandFactory;
-
-    #[test]
-    fn test_cli_parsing() {
-        Cli::command().debug_assert();
-    }
-
-    #[test]
-    fn test_cli_ping_hosts() {
-        let cli = Cli::try_parse_from(["guardian-cli", "ping-hosts"]).unwrap();
-        assert!(matches!(cli.command, Commands::PingHosts));
-    }
-
-    #[test]
-    fn test_cli_list_models() {
-        let cli = Cli::try_parse_from(["guardian-cli", "list-models"]).unwrap();
-        assert!(matches!(cli.command, Commands::ListModels { host: None }));
-    }
-
-    #[test]
-    fn test_cli_list_models_with_host() {
-        let cli = Cli::try_parse_from(["guardian-cli", "list-models", "--host", "big72"]).unwrap();
-        match cli.command {
-            Commands::ListModels { host } => assert_eq!(host, Some("big72".to_string())),
-            _ => panic!("Expected ListModels command"),
-        }
-    }
-
-    #[test]
-    fn test_cli_json_flag() {
-        let cli = Cli::try_parse_from(["guardian-cli", "--json", "ping-hosts"]).unwrap();
-        assert!(cli.json);
-    }
-
-    #[test]
-    fn test_cli_verbose_flag() {
-        let cli = Cli::try_parse_from(["guardian-cli", "-v", "ping-hosts"]).unwrap();
-        assert!(cli.verbose);
-    }
-
-    #[test]
-    fn test_cli_config_flag() {
-        let cli = Cli::try_parse_from([
-            "guardian-cli",
-            "--config",
-            "/path/to/config.toml",
-            "ping-hosts",
-        ])
-        .unwrap();
-        assert_eq!(cli.config, Some(PathBuf::from("/path/to/config.toml")));
-    }
-}